@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Defaults for `stream` and `record` loaded from a TOML file.
+//!
+//! Each setting is resolved with the precedence flag > config > built-in
+//! default: a value given on the command line always wins, a value from the
+//! `--config` file replaces the built-in default, and settings missing from
+//! both keep the default shown in `--help`.
+//!
+//! ```toml
+//! device = "/dev/video0"
+//! resolution = "1280x720"
+//! format = "NV12"
+//! fps = 30
+//! codec = "h265"
+//! bitrate = "10000"
+//! ```
+//!
+//! Settings apply to every command that has the matching flag; `codec` is
+//! only used by `record`.
+
+use crate::error::CliError;
+use clap::{parser::ValueSource, ArgMatches};
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+/// Defaults read from a `--config` file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Camera device
+    pub device: Option<String>,
+    /// Resolution in WxH format
+    pub resolution: Option<String>,
+    /// Pixel format FOURCC
+    pub format: Option<String>,
+    /// Target frame rate
+    pub fps: Option<i32>,
+    /// Video codec: h264|h265
+    pub codec: Option<String>,
+    /// Encoding bitrate in kbps
+    pub bitrate: Option<String>,
+}
+
+impl Config {
+    /// Reads and parses the config file at `path`.
+    pub fn load(path: &Path) -> Result<Self, CliError> {
+        let text = fs::read_to_string(path).map_err(|e| {
+            CliError::InvalidArgs(format!("Cannot read config {}: {}", path.display(), e))
+        })?;
+        Self::parse(&text)
+            .map_err(|e| CliError::InvalidArgs(format!("Invalid config {}: {}", path.display(), e)))
+    }
+
+    /// Parses config file contents.
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+}
+
+/// Replaces `field` with `value` unless the argument `id` was given on the
+/// command line.
+pub fn merge<T: Clone>(field: &mut T, value: &Option<T>, matches: &ArgMatches, id: &str) {
+    if let Some(value) = value {
+        if matches.value_source(id) != Some(ValueSource::CommandLine) {
+            *field = value.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse(
+            r#"
+            device = "/dev/video0"
+            resolution = "1280x720"
+            fps = 15
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.device.as_deref(), Some("/dev/video0"));
+        assert_eq!(config.resolution.as_deref(), Some("1280x720"));
+        assert_eq!(config.fps, Some(15));
+        assert!(config.codec.is_none());
+    }
+
+    #[test]
+    fn test_parse_config_rejects_unknown_keys() {
+        assert!(Config::parse("devcie = \"/dev/video0\"").is_err());
+        assert!(Config::parse("fps = \"fast\"").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_config() {
+        let err = Config::load(Path::new("/nonexistent/videostream.toml")).unwrap_err();
+        assert!(matches!(err, CliError::InvalidArgs(_)));
+    }
+}
@@ -1,6 +1,22 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright 2025 Au-Zone Technologies
 
+//! CLI errors and their process exit codes.
+//!
+//! Every subcommand exits with one of these codes so scripts can tell
+//! failures apart without parsing messages:
+//!
+//! | Code | Error | Meaning |
+//! |------|-------|---------|
+//! | 0 | | Success |
+//! | 1 | [`CliError::General`] | Any other failure |
+//! | 2 | [`CliError::InvalidArgs`] | Invalid arguments or unsupported format (also used by argument parsing) |
+//! | 3 | [`CliError::CameraNotFound`] | Camera or device missing or inaccessible |
+//! | 4 | [`CliError::EncoderUnavailable`] | Encoder/decoder hardware or library support unavailable |
+//! | 5 | [`CliError::SocketError`] | VSL socket connection or binding failed |
+//! | 6 | [`CliError::Timeout`] | Operation timed out |
+//! | 7 | [`CliError::Io`] | File or other I/O failure |
+
 use std::fmt;
 use std::process::ExitCode;
 
@@ -17,6 +33,8 @@ pub enum CliError {
     SocketError(String),
     /// Operation timed out
     Timeout(String),
+    /// File or other I/O failure
+    Io(String),
     /// General error from VideoStream library
     General(String),
 }
@@ -31,6 +49,7 @@ impl fmt::Display for CliError {
             }
             CliError::SocketError(msg) => write!(f, "Socket error: {}", msg),
             CliError::Timeout(msg) => write!(f, "Timeout: {}", msg),
+            CliError::Io(msg) => write!(f, "I/O error: {}", msg),
             CliError::General(msg) => write!(f, "Error: {}", msg),
         }
     }
@@ -47,6 +66,7 @@ impl CliError {
             CliError::EncoderUnavailable(_) => ExitCode::from(4),
             CliError::SocketError(_) => ExitCode::from(5),
             CliError::Timeout(_) => ExitCode::from(6),
+            CliError::Io(_) => ExitCode::from(7),
             CliError::General(_) => ExitCode::from(1),
         }
     }
@@ -83,7 +103,7 @@ impl From<videostream::Error> for CliError {
                 std::io::ErrorKind::PermissionDenied => {
                     CliError::CameraNotFound(format!("Permission denied: {}", io_err))
                 }
-                _ => CliError::General(format!("I/O error: {}", io_err)),
+                _ => CliError::Io(io_err.to_string()),
             },
 
             // Library loading errors
@@ -100,6 +120,20 @@ impl From<videostream::Error> for CliError {
                 CliError::General(format!("Integer conversion error: {}", int_err))
             }
 
+            // Format errors are caused by the requested format
+            Error::InvalidFourCC(fourcc) => {
+                CliError::InvalidArgs(format!("Invalid format {:?}", fourcc))
+            }
+            Error::UnsupportedFormat(fourcc) => {
+                CliError::InvalidArgs(format!("Unsupported format {}", fourcc))
+            }
+            Error::ConversionUnsupported { .. } => CliError::InvalidArgs(err.to_string()),
+
+            // Socket path errors name the path and the cause
+            Error::SocketPathTooLong(_)
+            | Error::SocketDirectoryNotFound(_)
+            | Error::AddressInUse(_) => CliError::SocketError(err.to_string()),
+
             // Null pointer errors
             Error::NullPointer => CliError::General("Unexpected null pointer".to_string()),
 
@@ -146,12 +180,28 @@ mod tests {
             CliError::Timeout("test".into()).exit_code(),
             ExitCode::from(6)
         );
+        assert_eq!(CliError::Io("test".into()).exit_code(), ExitCode::from(7));
         assert_eq!(
             CliError::General("test".into()).exit_code(),
             ExitCode::from(1)
         );
     }
 
+    #[test]
+    fn test_io_errors() {
+        let err = CliError::from(videostream::Error::Io(std::io::Error::from(
+            std::io::ErrorKind::UnexpectedEof,
+        )));
+        assert_eq!(err.exit_code(), ExitCode::from(7));
+    }
+
+    #[test]
+    fn test_socket_path_errors() {
+        let err = CliError::from(videostream::Error::AddressInUse("/tmp/camera.sock".into()));
+        assert_eq!(err.exit_code(), ExitCode::from(5));
+        assert!(err.to_string().contains("/tmp/camera.sock"), "{}", err);
+    }
+
     #[test]
     fn test_error_display() {
         let err = CliError::CameraNotFound("/dev/video0".to_string());
@@ -6,7 +6,18 @@ use clap::Args as ClapArgs;
 use serde::Serialize;
 use std::fs;
 use std::path::Path;
-use videostream::{camera, decoder, encoder, v4l2::DeviceEnumerator};
+use videostream::{
+    camera,
+    codec::{self, CodecCaps},
+    decoder, encoder,
+    v4l2::{DeviceEnumerator, DeviceType},
+};
+
+/// Camera formats listed per device in the capabilities section.
+const MAX_CAMERA_FORMATS: usize = 5;
+
+/// Largest resolutions listed per camera format in the capabilities section.
+const MAX_CAMERA_RESOLUTIONS: usize = 3;
 
 #[derive(ClapArgs, Debug)]
 pub struct Args {
@@ -33,11 +44,20 @@ pub struct Args {
     /// Show V4L2 codec device information
     #[arg(long)]
     v4l2: bool,
+
+    /// Show DMA heaps available for frame allocation
+    #[arg(long)]
+    dma: bool,
+
+    /// Show per-codec, camera and DMA heap capabilities
+    #[arg(long)]
+    capabilities: bool,
 }
 
 #[derive(Debug, Serialize)]
 struct SystemInfo {
     version: String,
+    library: String,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     camera: Option<CameraInfo>,
@@ -50,6 +70,12 @@ struct SystemInfo {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     v4l2_codecs: Option<V4L2CodecInfo>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dma_heaps: Option<Vec<DmaHeap>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    capabilities: Option<Capabilities>,
 }
 
 #[derive(Debug, Serialize)]
@@ -69,6 +95,41 @@ struct HardwareInfo {
     codecs: Option<Vec<String>>,
 }
 
+#[derive(Debug, Serialize)]
+struct DmaHeap {
+    name: String,
+    path: String,
+    contiguous: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Capabilities {
+    codecs: Vec<CodecCapability>,
+    cameras: Vec<CameraCapability>,
+    dma_heaps: Vec<DmaHeap>,
+}
+
+#[derive(Debug, Serialize)]
+struct CodecCapability {
+    codec: String,
+    fourcc: String,
+    encode: bool,
+    decode: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CameraCapability {
+    path: String,
+    name: String,
+    formats: Vec<CameraFormat>,
+}
+
+#[derive(Debug, Serialize)]
+struct CameraFormat {
+    fourcc: String,
+    resolutions: Vec<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct V4L2CodecInfo {
     devices: Vec<V4L2Device>,
@@ -89,20 +150,34 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
     log::debug!("Executing info command: {:?}", args);
 
     // Determine what to show: if --all or no specific flags, show everything
-    let show_all = args.all || !(args.camera || args.encoder || args.decoder || args.v4l2);
+    let show_all = args.all
+        || !(args.camera
+            || args.encoder
+            || args.decoder
+            || args.v4l2
+            || args.dma
+            || args.capabilities);
     let show_camera = show_all || args.camera;
     let show_encoder = show_all || args.encoder;
     let show_decoder = show_all || args.decoder;
     let show_v4l2 = show_all || args.v4l2;
+    let show_dma = show_all || args.dma;
+    let show_capabilities = show_all || args.capabilities;
 
     let version = videostream::version().unwrap_or_else(|_| "unknown".to_string());
+    let library = videostream::library_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|_| "not loaded".to_string());
 
     let mut info = SystemInfo {
         version,
+        library,
         camera: None,
         encoder: None,
         decoder: None,
         v4l2_codecs: None,
+        dma_heaps: None,
+        capabilities: None,
     };
 
     // Query camera information
@@ -125,6 +200,16 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
         info.v4l2_codecs = Some(query_v4l2_codecs());
     }
 
+    // Query DMA heaps
+    if show_dma {
+        info.dma_heaps = Some(query_dma_heaps());
+    }
+
+    // Query per-codec, camera and DMA heap capabilities
+    if show_capabilities {
+        info.capabilities = Some(query_capabilities());
+    }
+
     // Output results
     if json {
         let json_str = serde_json::to_string_pretty(&info)
@@ -204,6 +289,84 @@ fn query_decoder_info() -> HardwareInfo {
     }
 }
 
+fn query_dma_heaps() -> Vec<DmaHeap> {
+    log::debug!("Querying DMA heaps");
+
+    match videostream::dma_heaps() {
+        Ok(heaps) => heaps
+            .into_iter()
+            .map(|heap| DmaHeap {
+                name: heap.name,
+                path: heap.path.display().to_string(),
+                contiguous: heap.contiguous,
+            })
+            .collect(),
+        Err(e) => {
+            log::warn!("Failed to enumerate DMA heaps: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+fn query_capabilities() -> Capabilities {
+    log::debug!("Querying capabilities");
+
+    // Every codec is listed even when probing fails so the section keeps a
+    // stable shape for scripts.
+    let caps = codec::capabilities().unwrap_or_else(|e| {
+        log::warn!("Failed to probe codec capabilities: {}", e);
+        CodecCaps::default()
+    });
+    let codecs = caps
+        .iter()
+        .map(|(codec, support)| CodecCapability {
+            codec: codec.to_string(),
+            fourcc: codec.fourcc().to_string(),
+            encode: support.encode,
+            decode: support.decode,
+        })
+        .collect();
+
+    let cameras = match DeviceEnumerator::enumerate_type(DeviceType::Camera) {
+        Ok(devices) => devices
+            .iter()
+            .map(|device| CameraCapability {
+                path: device.path_str().to_string(),
+                name: device.card().to_string(),
+                formats: device
+                    .capture_formats()
+                    .iter()
+                    .take(MAX_CAMERA_FORMATS)
+                    .map(|format| {
+                        let mut resolutions = format.resolutions.clone();
+                        resolutions.sort_by_key(|res| {
+                            std::cmp::Reverse(u64::from(res.width) * u64::from(res.height))
+                        });
+                        CameraFormat {
+                            fourcc: format.fourcc.to_string(),
+                            resolutions: resolutions
+                                .iter()
+                                .take(MAX_CAMERA_RESOLUTIONS)
+                                .map(|res| res.to_string())
+                                .collect(),
+                        }
+                    })
+                    .collect(),
+            })
+            .collect(),
+        Err(e) => {
+            log::warn!("Failed to enumerate cameras: {}", e);
+            Vec::new()
+        }
+    };
+
+    Capabilities {
+        codecs,
+        cameras,
+        dma_heaps: query_dma_heaps(),
+    }
+}
+
 fn query_v4l2_codecs() -> V4L2CodecInfo {
     log::debug!("Querying V4L2 codec devices");
 
@@ -402,7 +565,8 @@ fn probe_v4l2_device(path: &Path) -> Option<V4L2Device> {
 fn print_text_info(info: &SystemInfo) {
     println!("VideoStream System Information");
     println!("===============================");
-    println!("Library Version: {}\n", info.version);
+    println!("Library Version: {}", info.version);
+    println!("Library Path: {}\n", info.library);
 
     // Print camera info
     if let Some(ref cam) = info.camera {
@@ -504,4 +668,62 @@ fn print_text_info(info: &SystemInfo) {
         }
         println!();
     }
+
+    // Print DMA heaps
+    if let Some(ref heaps) = info.dma_heaps {
+        println!("DMA Heaps:");
+        if heaps.is_empty() {
+            println!("  No DMA heaps found (shared memory fallback)");
+        } else {
+            for heap in heaps {
+                println!(
+                    "  {} ({})",
+                    heap.path,
+                    if heap.contiguous {
+                        "contiguous"
+                    } else {
+                        "system"
+                    }
+                );
+            }
+        }
+        println!();
+    }
+    // Print capabilities
+    if let Some(ref caps) = info.capabilities {
+        println!("Capabilities:");
+        println!("  Codecs:");
+        for codec in &caps.codecs {
+            println!(
+                "    {:<6} encode: {}  decode: {}",
+                codec.codec,
+                if codec.encode { "✓" } else { "✗" },
+                if codec.decode { "✓" } else { "✗" }
+            );
+        }
+
+        println!("  Cameras:");
+        if caps.cameras.is_empty() {
+            println!("    No cameras found");
+        }
+        for camera in &caps.cameras {
+            println!("    {} ({})", camera.path, camera.name);
+            for format in &camera.formats {
+                if format.resolutions.is_empty() {
+                    println!("      {}", format.fourcc);
+                } else {
+                    println!("      {}: {}", format.fourcc, format.resolutions.join(", "));
+                }
+            }
+        }
+
+        println!("  DMA Heaps:");
+        if caps.dma_heaps.is_empty() {
+            println!("    No DMA heaps found");
+        }
+        for heap in &caps.dma_heaps {
+            println!("    {}", heap.path);
+        }
+        println!();
+    }
 }
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Discovery of VSL streams published on this system.
+
+use crate::error::CliError;
+use clap::Args as ClapArgs;
+use serde::Serialize;
+use std::path::PathBuf;
+use videostream::StreamEndpoint;
+
+#[derive(ClapArgs, Debug)]
+pub struct Args {
+    /// Directory to scan for VSL sockets
+    #[arg(default_value = "/tmp")]
+    dir: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamOutput {
+    path: String,
+    active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fourcc: Option<String>,
+}
+
+impl From<&StreamEndpoint> for StreamOutput {
+    fn from(stream: &StreamEndpoint) -> Self {
+        StreamOutput {
+            path: stream.path.display().to_string(),
+            active: stream.info.is_some(),
+            width: stream.info.map(|info| info.width),
+            height: stream.info.map(|info| info.height),
+            fourcc: stream.info.map(|info| info.fourcc.to_string()),
+        }
+    }
+}
+
+pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
+    log::debug!("Executing list command: {:?}", args);
+
+    let streams = videostream::discover_streams(&args.dir)?;
+    let output: Vec<StreamOutput> = streams.iter().map(StreamOutput::from).collect();
+
+    if json {
+        let json_str = serde_json::to_string_pretty(&output)
+            .map_err(|e| CliError::General(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json_str);
+        return Ok(());
+    }
+
+    if output.is_empty() {
+        println!("No VSL streams found in {}", args.dir.display());
+        return Ok(());
+    }
+    for stream in &output {
+        match (stream.width, stream.height, &stream.fourcc) {
+            (Some(width), Some(height), Some(fourcc)) => {
+                println!("{}  {}x{} {}", stream.path, width, height, fourcc)
+            }
+            _ => println!("{}  (idle)", stream.path),
+        }
+    }
+
+    Ok(())
+}
@@ -1,19 +1,23 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright 2025 Au-Zone Technologies
 
+mod config;
 mod convert;
 mod devices;
 mod error;
 mod info;
+mod list;
 mod metrics;
+mod probe;
 mod receive;
 mod record;
 mod stream;
+mod unmux;
 mod utils;
 
-use clap::{Parser, Subcommand};
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 use error::result_to_exit_code;
-use std::process::ExitCode;
+use std::{path::PathBuf, process::ExitCode};
 
 /// VideoStream CLI - Camera streaming, encoding, and metrics tool
 #[derive(Parser)]
@@ -35,6 +39,14 @@ struct Cli {
     #[arg(long, global = true)]
     json: bool,
 
+    /// Path to libvideostream.so (overrides VIDEOSTREAM_LIBRARY)
+    #[arg(long, global = true, value_name = "PATH")]
+    library: Option<PathBuf>,
+
+    /// TOML file with stream/record defaults; command-line flags override it
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -50,6 +62,9 @@ enum Commands {
     /// Convert raw H.264/H.265 bitstream to MP4 container
     Convert(convert::Args),
 
+    /// Extract the raw H.264/H.265 bitstream from an MP4 container
+    Unmux(unmux::Args),
+
     /// Receive frames from a VSL socket and measure performance
     Receive(receive::Args),
 
@@ -58,27 +73,70 @@ enum Commands {
 
     /// List V4L2 devices with filtering and grouping
     Devices(devices::Args),
+
+    /// List VSL streams published in a directory
+    List(list::Args),
+
+    /// Self-test the camera, encoder and decoder with a short round-trip
+    Probe(probe::Args),
 }
 
 fn main() -> ExitCode {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     // Initialize logging based on verbosity
     init_logging(cli.verbose, cli.quiet);
 
+    // Fill in defaults from --config for flags not given on the command line
+    if let Err(e) = apply_config(&mut cli, &matches) {
+        return result_to_exit_code::<()>(Err(e));
+    }
+
+    // Select the library before anything loads it
+    if let Some(ref library) = cli.library {
+        if let Err(e) = videostream::set_library_path(library) {
+            log::warn!("Ignoring --library {}: {}", library.display(), e);
+        }
+    }
+
     // Execute the subcommand and convert result to exit code
     let result = match cli.command {
         Commands::Stream(args) => stream::execute(args, cli.json),
         Commands::Record(args) => record::execute(args, cli.json),
         Commands::Convert(args) => convert::execute(args, cli.json),
+        Commands::Unmux(args) => unmux::execute(args, cli.json),
         Commands::Receive(args) => receive::execute(args, cli.json),
         Commands::Info(args) => info::execute(args, cli.json),
         Commands::Devices(args) => devices::execute(args, cli.json),
+        Commands::List(args) => list::execute(args, cli.json),
+        Commands::Probe(args) => probe::execute(args, cli.json),
     };
 
     result_to_exit_code(result)
 }
 
+/// Apply the `--config` file, if any, to the subcommand's arguments.
+///
+/// Precedence is flag > config > built-in default.
+fn apply_config(cli: &mut Cli, matches: &ArgMatches) -> Result<(), error::CliError> {
+    let Some(path) = cli.config.as_deref() else {
+        return Ok(());
+    };
+    let config = config::Config::load(path)?;
+    log::debug!("Loaded config {}: {:?}", path.display(), config);
+
+    let Some((_, sub_matches)) = matches.subcommand() else {
+        return Ok(());
+    };
+    match &mut cli.command {
+        Commands::Stream(args) => args.apply_config(&config, sub_matches),
+        Commands::Record(args) => args.apply_config(&config, sub_matches),
+        _ => {}
+    }
+    Ok(())
+}
+
 /// Initialize env_logger based on verbosity flags
 fn init_logging(verbose: bool, quiet: bool) {
     // Determine log level from flags or RUST_LOG environment variable
@@ -3,6 +3,7 @@
 
 use serde::Serialize;
 use std::time::Instant;
+use videostream::rate::RateMeter;
 
 /// Performance metrics collected during operation
 #[derive(Debug, Clone, Serialize)]
@@ -15,6 +16,10 @@ pub struct FrameMetrics {
     pub duration_ms: u64,
     /// Average throughput in frames per second
     pub throughput_fps: f64,
+    /// Frame rate over the last second
+    pub current_fps: f64,
+    /// 5th percentile of the per-frame rates over the last second
+    pub current_fps_p5: f64,
     /// Average bandwidth in megabits per second
     pub bandwidth_mbps: f64,
     /// Minimum latency in microseconds
@@ -31,15 +36,19 @@ pub struct FrameMetrics {
     pub latency_p99_us: u64,
     /// Number of dropped frames detected
     pub dropped_frames: u64,
+    /// Number of encoded frames containing an IDR picture
+    pub keyframes: u64,
 }
 
 /// Metrics collector for tracking frame processing performance
 pub struct MetricsCollector {
     start_time: Instant,
+    rate: RateMeter,
     latencies_us: Vec<u64>,
     bytes: u64,
     prev_serial: Option<i64>,
     dropped_frames: u64,
+    keyframes: u64,
 }
 
 impl MetricsCollector {
@@ -47,10 +56,12 @@ impl MetricsCollector {
     pub fn new() -> Self {
         Self {
             start_time: Instant::now(),
+            rate: RateMeter::default(),
             latencies_us: Vec::new(),
             bytes: 0,
             prev_serial: None,
             dropped_frames: 0,
+            keyframes: 0,
         }
     }
 
@@ -58,12 +69,18 @@ impl MetricsCollector {
     pub fn record_latency_ns(&mut self, latency_ns: i64) {
         // Convert nanoseconds to microseconds
         let latency_us = (latency_ns.max(0) / 1000) as u64;
-        self.latencies_us.push(latency_us);
+        self.record_latency_us(latency_us);
     }
 
     /// Record a frame's latency in microseconds
     pub fn record_latency_us(&mut self, latency_us: u64) {
         self.latencies_us.push(latency_us);
+        self.rate.record();
+    }
+
+    /// Frame rate over the last second
+    pub fn current_fps(&self) -> f64 {
+        self.rate.current_fps()
     }
 
     /// Record bytes processed
@@ -71,6 +88,11 @@ impl MetricsCollector {
         self.bytes += bytes;
     }
 
+    /// Record an encoded keyframe
+    pub fn record_keyframe(&mut self) {
+        self.keyframes += 1;
+    }
+
     /// Track frame serial number to detect drops
     /// Returns the number of dropped frames detected (0 or N)
     pub fn track_serial(&mut self, serial: i64) -> u64 {
@@ -104,14 +126,7 @@ impl MetricsCollector {
         let duration_ms = duration.as_millis() as u64;
         let duration_secs = duration.as_secs_f64();
 
-        let frames_processed = self.latencies_us.len() as u64;
-
-        // Calculate throughput
-        let throughput_fps = if duration_secs > 0.0 {
-            frames_processed as f64 / duration_secs
-        } else {
-            0.0
-        };
+        let frames_processed = self.rate.count();
 
         // Calculate bandwidth in Mbps
         let bandwidth_mbps = if duration_secs > 0.0 {
@@ -143,7 +158,9 @@ impl MetricsCollector {
             frames_processed,
             bytes_processed: self.bytes,
             duration_ms,
-            throughput_fps,
+            throughput_fps: self.rate.average_fps(),
+            current_fps: self.rate.current_fps(),
+            current_fps_p5: self.rate.percentile_fps(5.0),
             bandwidth_mbps,
             latency_min_us: min_us,
             latency_max_us: max_us,
@@ -152,6 +169,7 @@ impl MetricsCollector {
             latency_p95_us: p95_us,
             latency_p99_us: p99_us,
             dropped_frames: self.dropped_frames,
+            keyframes: self.keyframes,
         }
     }
 
@@ -182,6 +200,10 @@ impl MetricsCollector {
             metrics.duration_ms as f64 / 1000.0
         );
         println!("Throughput:        {:.2} fps", metrics.throughput_fps);
+        println!(
+            "Current rate:      {:.2} fps (p5 {:.2} fps)",
+            metrics.current_fps, metrics.current_fps_p5
+        );
         println!("Bandwidth:         {:.2} Mbps", metrics.bandwidth_mbps);
 
         if metrics.frames_processed > 0 {
@@ -194,6 +216,10 @@ impl MetricsCollector {
             println!("  P99:    {}", metrics.latency_p99_us);
         }
 
+        if metrics.keyframes > 0 {
+            println!("\nKeyframes:         {}", metrics.keyframes);
+        }
+
         if metrics.dropped_frames > 0 {
             println!(
                 "\nDropped frames:    {} ({:.2}%)",
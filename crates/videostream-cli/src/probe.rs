@@ -0,0 +1,293 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Hardware self-test: capture, encode, decode and convert a short clip.
+
+use crate::error::CliError;
+use crate::utils;
+use clap::Args as ClapArgs;
+use serde::Serialize;
+use std::time::Instant;
+use videostream::{camera, encoder, fourcc::FourCC, frame::Frame, v4l2::DeviceEnumerator};
+
+#[derive(ClapArgs, Debug)]
+pub struct Args {
+    /// Camera device
+    #[arg(short, long, default_value = "/dev/video3")]
+    device: String,
+
+    /// Resolution in WxH format
+    #[arg(short, long, default_value = "1280x720")]
+    resolution: String,
+
+    /// Camera pixel format
+    #[arg(long, default_value = "YUYV")]
+    format: String,
+
+    /// Number of frames to encode and decode
+    #[arg(short, long, default_value = "30")]
+    frames: u32,
+
+    /// Video codec: h264|h265|mjpg|vp8|vp9
+    #[arg(long, default_value = "h264")]
+    codec: String,
+
+    /// Encoding bitrate in kbps
+    #[arg(short, long, default_value = "5000")]
+    bitrate: String,
+}
+
+/// Outcome of one probe stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Pass,
+    Fail,
+    Skip,
+}
+
+#[derive(Debug, Serialize)]
+struct StageResult {
+    name: &'static str,
+    status: Status,
+    duration_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProbeReport {
+    passed: bool,
+    stages: Vec<StageResult>,
+}
+
+/// Names of the probe stages, in the order they run
+const STAGES: [&str; 5] = ["devices", "camera", "encode", "decode", "convert"];
+
+/// Runs the stages in order, skipping the rest once one fails
+struct Probe {
+    stages: Vec<StageResult>,
+}
+
+impl Probe {
+    fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    fn failed(&self) -> bool {
+        self.stages.iter().any(|stage| stage.status == Status::Fail)
+    }
+
+    /// Record the stages that never ran because an earlier one failed.
+    fn skip_remaining(&mut self) {
+        for &name in STAGES.iter().skip(self.stages.len()) {
+            self.stages.push(StageResult {
+                name,
+                status: Status::Skip,
+                duration_ms: 0.0,
+                detail: None,
+                error: None,
+            });
+        }
+    }
+
+    /// Run `f` as stage `name` and record its outcome; returns `None` if the
+    /// stage failed or an earlier stage already had.
+    fn run<T>(
+        &mut self,
+        name: &'static str,
+        f: impl FnOnce() -> Result<(T, String), CliError>,
+    ) -> Option<T> {
+        if self.failed() {
+            return None;
+        }
+
+        log::info!("Probing {}", name);
+        let start = Instant::now();
+        let result = f();
+        let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+        match result {
+            Ok((value, detail)) => {
+                self.stages.push(StageResult {
+                    name,
+                    status: Status::Pass,
+                    duration_ms,
+                    detail: Some(detail),
+                    error: None,
+                });
+                Some(value)
+            }
+            Err(e) => {
+                log::debug!("Stage {} failed: {}", name, e);
+                self.stages.push(StageResult {
+                    name,
+                    status: Status::Fail,
+                    duration_ms,
+                    detail: None,
+                    error: Some(e.to_string()),
+                });
+                None
+            }
+        }
+    }
+}
+
+pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
+    log::debug!("Executing probe command: {:?}", args);
+
+    let (width, height) = utils::parse_resolution(&args.resolution)?;
+    let fourcc = utils::fourcc_from_str(&args.format)?;
+    if args.frames == 0 {
+        return Err(CliError::InvalidArgs(
+            "--frames must be at least 1".to_string(),
+        ));
+    }
+
+    let mut probe = Probe::new();
+
+    probe.run("devices", || {
+        let devices = DeviceEnumerator::enumerate()?;
+        if devices.is_empty() {
+            return Err(CliError::General("No V4L2 devices found".to_string()));
+        }
+        Ok(((), format!("{} V4L2 devices", devices.len())))
+    });
+
+    let cam = probe.run("camera", || {
+        let cam = camera::create_camera()
+            .with_device(&args.device)
+            .with_resolution(width, height)
+            .with_format(FourCC(fourcc.to_le_bytes()))
+            .open()?;
+        cam.start()?;
+        cam.read()?;
+        let detail = format!(
+            "{} {}x{} {}",
+            args.device,
+            cam.width(),
+            cam.height(),
+            cam.format()
+        );
+        Ok((cam, detail))
+    });
+
+    let packets = cam.and_then(|cam| {
+        probe.run("encode", || {
+            let (encoder, _) = utils::create_encoder_if_requested(
+                true,
+                &args.codec,
+                &args.bitrate,
+                30,
+                fourcc,
+                None,
+            )?;
+            let encoder = encoder.expect("encoder requested");
+            let crop = encoder::VSLRect::new(0, 0, width, height);
+
+            let mut packets = Vec::with_capacity(args.frames as usize);
+            let mut keyframes = 0;
+            for i in 0..args.frames as i64 {
+                let buffer = cam.read()?;
+                let output = encoder.new_output_frame(width, height, -1, i, i)?;
+                let mut keyframe = 0;
+                let size = encoder.encode_camera_buffer(&buffer, &output, &crop, &mut keyframe)?;
+                if keyframe != 0 {
+                    keyframes += 1;
+                }
+                packets.push(output.map()?[..size as usize].to_vec());
+            }
+
+            let bytes: usize = packets.iter().map(Vec::len).sum();
+            let detail = format!(
+                "{} frames, {} bytes, {} keyframes",
+                packets.len(),
+                bytes,
+                keyframes
+            );
+            Ok((packets, detail))
+        })
+    });
+
+    let decoded = packets.and_then(|packets| {
+        probe.run("decode", || {
+            let decoder = utils::create_decoder_if_requested(true, &args.codec, 30)?
+                .expect("decoder requested");
+
+            let mut frames = 0;
+            let mut last: Option<Frame> = None;
+            for packet in &packets {
+                let mut data = packet.as_slice();
+                while !data.is_empty() {
+                    let (_, consumed, frame) = decoder.decode_frame(data)?;
+                    if let Some(frame) = frame {
+                        frames += 1;
+                        last = Some(frame);
+                    }
+                    if consumed == 0 {
+                        break;
+                    }
+                    data = &data[consumed.min(data.len())..];
+                }
+            }
+
+            let frame = last.ok_or_else(|| {
+                CliError::General(format!(
+                    "Decoder produced no frames from {} packets",
+                    packets.len()
+                ))
+            })?;
+            Ok((frame, format!("{} frames", frames)))
+        })
+    });
+
+    if let Some(frame) = decoded {
+        probe.run("convert", || {
+            let rgb = frame.to_format(FourCC(*b"RGB3"))?;
+            let detail = format!(
+                "{} {}x{} -> RGB3",
+                FourCC::from(frame.fourcc()?),
+                rgb.width()?,
+                rgb.height()?
+            );
+            Ok(((), detail))
+        });
+    }
+
+    probe.skip_remaining();
+
+    let report = ProbeReport {
+        passed: !probe.failed(),
+        stages: probe.stages,
+    };
+
+    if json {
+        let json_str = serde_json::to_string_pretty(&report)
+            .map_err(|e| CliError::General(format!("JSON serialization failed: {}", e)))?;
+        println!("{}", json_str);
+    } else {
+        for stage in &report.stages {
+            let status = match stage.status {
+                Status::Pass => "PASS",
+                Status::Fail => "FAIL",
+                Status::Skip => "SKIP",
+            };
+            let message = stage
+                .error
+                .as_deref()
+                .or(stage.detail.as_deref())
+                .unwrap_or("");
+            println!(
+                "{:<8} {}  {:>9.1} ms  {}",
+                stage.name, status, stage.duration_ms, message
+            );
+        }
+    }
+
+    if report.passed {
+        Ok(())
+    } else {
+        Err(CliError::General("Hardware probe failed".to_string()))
+    }
+}
@@ -6,7 +6,7 @@ use crate::metrics::MetricsCollector;
 use crate::utils;
 use clap::Args as ClapArgs;
 use std::sync::atomic::Ordering;
-use videostream::{client::Client, client::Reconnect};
+use videostream::{client::Client, client::Reconnect, fourcc::FourCC, nal::Codec};
 
 #[derive(ClapArgs, Debug)]
 pub struct Args {
@@ -88,9 +88,9 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
         let latency_ns = now - frame_ts;
         metrics_collector.record_latency_ns(latency_ns);
 
-        // Record bytes
-        let frame_size = frame.size()? as u64;
-        metrics_collector.record_bytes(frame_size);
+        // Record bytes, counting only the encoded data of encoded frames
+        let frame_size = frame.used_size()?;
+        metrics_collector.record_bytes(frame_size as u64);
 
         // Track serial for dropped frames
         let serial = frame.serial()?;
@@ -99,14 +99,23 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
             log::warn!("Detected {} dropped frame(s)", drops);
         }
 
+        // Count keyframes from the NAL headers of encoded frames
+        let fourcc = FourCC::from(frame.fourcc()?);
+        if Codec::from_fourcc(fourcc).is_some_and(Codec::is_annex_b) && frame.is_keyframe(fourcc)? {
+            metrics_collector.record_keyframe();
+        }
+
         // Decode if requested
         if let Some(ref decoder) = decoder_opt {
             // Lock frame for reading
             frame.trylock()?;
-            let data = frame.mmap()?;
-
-            // Decode the frame
-            let (_ret_code, _bytes_used, _output_frame) = decoder.decode_frame(data)?;
+            // Decode the whole frame, which the decoder may consume in
+            // parts; the mapping is released before unlocking
+            {
+                let data = frame.map()?;
+                let data = &data[..frame_size.min(data.len())];
+                decoder.decode_all(data)?;
+            }
 
             frame.unlock()?;
 
@@ -117,7 +126,11 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
 
         // Log progress periodically
         if frame_count.is_multiple_of(30) {
-            log::debug!("Received {} frames", frame_count);
+            log::debug!(
+                "Received {} frames ({:.1} fps)",
+                frame_count,
+                metrics_collector.current_fps()
+            );
         }
     }
 
@@ -1,14 +1,15 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright 2025 Au-Zone Technologies
 
+use crate::config::{self, Config};
 use crate::error::CliError;
 use crate::utils;
-use clap::{Args as ClapArgs, ValueEnum};
+use clap::{ArgMatches, Args as ClapArgs, ValueEnum};
 use std::fs::File;
 use std::io::Write;
 use std::sync::atomic::Ordering;
 use std::time::Instant;
-use videostream::{camera, client, encoder, fourcc::FourCC, frame::Frame};
+use videostream::{camera, client, encoder, fourcc::FourCC};
 
 #[derive(ClapArgs, Debug)]
 pub struct Args {
@@ -58,6 +59,28 @@ pub struct Args {
     /// Encoder backend: auto|v4l2|hantro
     #[arg(long, default_value = "auto")]
     backend: Backend,
+
+    /// V4L2 encoder device to encode on, e.g. /dev/video1 (default: first
+    /// encoder for the codec; see `devices --encoders`)
+    #[arg(long, value_name = "PATH")]
+    encoder_device: Option<String>,
+}
+
+impl Args {
+    /// Take defaults from `config` for flags not given on the command line.
+    pub fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        config::merge(&mut self.device, &config.device, matches, "device");
+        config::merge(
+            &mut self.resolution,
+            &config.resolution,
+            matches,
+            "resolution",
+        );
+        config::merge(&mut self.format, &config.format, matches, "format");
+        config::merge(&mut self.fps, &config.fps, matches, "fps");
+        config::merge(&mut self.codec, &config.codec, matches, "codec");
+        config::merge(&mut self.bitrate, &config.bitrate, matches, "bitrate");
+    }
 }
 
 /// Encoder backend selection
@@ -97,7 +120,12 @@ impl RecordConfig {
         let expected_ext = match args.codec.as_str() {
             "h264" => ".h264",
             "h265" | "hevc" => ".h265",
-            _ => "",
+            _ => {
+                return Err(CliError::InvalidArgs(format!(
+                    "Unsupported codec for recording: {} (supported: h264, h265, hevc)",
+                    args.codec
+                )))
+            }
         };
         if !args.output.ends_with(expected_ext) {
             log::warn!(
@@ -122,11 +150,8 @@ impl RecordConfig {
         );
 
         // Check encoder availability
-        if !encoder::is_available().unwrap_or(false) {
-            return Err(CliError::EncoderUnavailable(
-                "VPU encoder not available on this system. Recording requires hardware encoder."
-                    .to_string(),
-            ));
+        if !utils::is_encoder_available(output_fourcc) {
+            return Err(utils::codec_unavailable(output_fourcc, true));
         }
 
         Ok(Self {
@@ -198,6 +223,17 @@ fn init_encoder(args: &Args, config: &RecordConfig) -> Result<encoder::Encoder,
         args.backend
     );
     let profile = utils::bitrate_to_encoder_profile(config.bitrate_kbps);
+    if let Some(path) = &args.encoder_device {
+        if args.backend == Backend::Hantro {
+            return Err(CliError::InvalidArgs(
+                "--encoder-device selects a V4L2 encoder and cannot be used with --backend hantro"
+                    .to_string(),
+            ));
+        }
+        let encoder_config =
+            encoder::EncoderConfig::new(profile as u32, config.output_fourcc, args.fps);
+        return utils::create_encoder_on_device(path, encoder_config);
+    }
     let backend: encoder::CodecBackend = args.backend.into();
     let encoder =
         encoder::Encoder::create_ex(profile as u32, config.output_fourcc, args.fps, backend)?;
@@ -211,8 +247,7 @@ fn init_encoder(args: &Args, config: &RecordConfig) -> Result<encoder::Encoder,
 
 /// Create output file for bitstream
 fn create_output_file(path: &str) -> Result<File, CliError> {
-    File::create(path)
-        .map_err(|e| CliError::General(format!("Failed to create output file: {}", e)))
+    File::create(path).map_err(|e| CliError::Io(format!("Failed to create output file: {}", e)))
 }
 
 /// Frame source abstraction for camera or IPC input
@@ -230,6 +265,97 @@ fn init_ipc_client(socket_path: &str, timeout: f64) -> Result<client::Client, Cl
     Ok(client)
 }
 
+/// Read the next frame from `source` and encode it into `output_frame`,
+/// returning the encoded size and keyframe flag
+fn encode_next(
+    source: &FrameSource,
+    encoder: &encoder::Encoder,
+    output_frame: &videostream::frame::Frame,
+    crop: &encoder::VSLRect,
+    frame_count: u64,
+) -> Result<(i32, i32), CliError> {
+    let mut keyframe: i32 = 0;
+    let size = match source {
+        FrameSource::Camera(cam) => {
+            // Read frame from camera
+            log::trace!("Reading frame {} from camera", frame_count);
+            let buffer = cam.read()?;
+            log::debug!("Camera read succeeded: frame {}", frame_count);
+
+            // Encode the camera buffer directly, reusing its input frame
+            log::trace!("Encoding frame {}", frame_count);
+            encoder.encode_camera_buffer(&buffer, output_frame, crop, &mut keyframe)?
+        }
+        FrameSource::Ipc(client) => {
+            // Get frame from IPC socket
+            log::trace!("Waiting for frame {} from IPC", frame_count);
+            let input_frame = client.get_frame(0)?;
+            log::debug!(
+                "IPC frame received: {}x{} serial={}",
+                input_frame.width()?,
+                input_frame.height()?,
+                input_frame.serial()?
+            );
+
+            // Lock frame for reading (required for IPC frames)
+            input_frame.trylock()?;
+
+            // Encode the frame
+            log::trace!("Encoding frame {}", frame_count);
+            let size = unsafe { encoder.frame(&input_frame, output_frame, crop, &mut keyframe)? };
+
+            // Unlock frame
+            input_frame.unlock()?;
+            size
+        }
+    };
+    if size < 0 {
+        return Err(CliError::General(format!(
+            "Failed to encode frame {}",
+            frame_count
+        )));
+    }
+
+    log::debug!(
+        "Frame {} encoded successfully (size={}, keyframe={})",
+        frame_count,
+        size,
+        keyframe
+    );
+    Ok((size, keyframe))
+}
+
+/// Write the first `size` bytes of an encoded frame to the output file
+fn write_frame(
+    output_file: &mut File,
+    frame: &videostream::frame::Frame,
+    size: usize,
+) -> Result<(), CliError> {
+    // Note: Encoder output frames don't need locking (they're not from a client)
+    log::trace!("Memory mapping output frame");
+    let frame_data = frame.map()?;
+    let size = size.min(frame_data.len());
+    log::debug!("Output frame mapped, writing {} bytes", size);
+
+    output_file
+        .write_all(&frame_data[..size])
+        .map_err(|e| CliError::Io(format!("Failed to write frame data: {}", e)))
+}
+
+/// Write the frames still buffered in the encoder, returning how many
+fn drain_encoder(encoder: &encoder::Encoder, output_file: &mut File) -> Result<u64, CliError> {
+    let frames = match encoder.flush() {
+        Ok(frames) => frames,
+        // Libraries before 2.6 cannot drain the encoder
+        Err(videostream::Error::SymbolNotFound(_)) => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+    for frame in &frames {
+        write_frame(output_file, frame, frame.used_size()?)?;
+    }
+    Ok(frames.len() as u64)
+}
+
 pub fn execute(args: Args, _json: bool) -> Result<(), CliError> {
     log::info!("Recording to file: {}", args.output);
     log::debug!("Record parameters: {:?}", args);
@@ -269,6 +395,7 @@ pub fn execute(args: Args, _json: bool) -> Result<(), CliError> {
     let start_time = Instant::now();
     let limits = RecordLimits::from_args(&args);
     let mut frame_count = 0u64;
+    let mut frames_written = 0u64;
     let crop = encoder::VSLRect::new(0, 0, config.width, config.height);
 
     while limits.should_continue(frame_count, start_time.elapsed()) && !term.load(Ordering::Relaxed)
@@ -285,75 +412,24 @@ pub fn execute(args: Args, _json: bool) -> Result<(), CliError> {
         log::debug!("Output frame created successfully");
 
         // Get frame from source and encode
-        let keyframe = match &source {
-            FrameSource::Camera(cam) => {
-                // Read frame from camera
-                log::trace!("Reading frame {} from camera", frame_count);
-                let buffer = cam.read()?;
-                log::debug!("Camera read succeeded: frame {}", frame_count);
-
-                // Create input frame from camera buffer (borrows buffer)
-                log::trace!("Converting CameraBuffer to Frame");
-                let input_frame: Frame = (&buffer).try_into()?;
-                log::debug!("Input frame created successfully");
-
-                // Encode the frame
-                log::trace!("Encoding frame {}", frame_count);
-                let mut keyframe: i32 = 0;
-                unsafe {
-                    encoder.frame(&input_frame, &output_frame, &crop, &mut keyframe)?;
+        let (size, keyframe) =
+            match encode_next(&source, &encoder, &output_frame, &crop, frame_count) {
+                Ok(encoded) => encoded,
+                // A signal can interrupt a blocking read; stop and drain the
+                // encoder rather than fail
+                Err(e) if term.load(Ordering::Relaxed) => {
+                    log::debug!("Frame {} interrupted: {}", frame_count, e);
+                    break;
                 }
-                log::debug!(
-                    "Frame {} encoded successfully (keyframe={})",
-                    frame_count,
-                    keyframe
-                );
-                keyframe
-            }
-            FrameSource::Ipc(client) => {
-                // Get frame from IPC socket
-                log::trace!("Waiting for frame {} from IPC", frame_count);
-                let input_frame = client.get_frame(0)?;
-                log::debug!(
-                    "IPC frame received: {}x{} serial={}",
-                    input_frame.width()?,
-                    input_frame.height()?,
-                    input_frame.serial()?
-                );
-
-                // Lock frame for reading (required for IPC frames)
-                input_frame.trylock()?;
-
-                // Encode the frame
-                log::trace!("Encoding frame {}", frame_count);
-                let mut keyframe: i32 = 0;
-                unsafe {
-                    encoder.frame(&input_frame, &output_frame, &crop, &mut keyframe)?;
-                }
-
-                // Unlock frame
-                input_frame.unlock()?;
-
-                log::debug!(
-                    "Frame {} encoded successfully (keyframe={})",
-                    frame_count,
-                    keyframe
-                );
-                keyframe
-            }
-        };
-
-        // Write encoded frame to file (raw Annex-B bitstream)
-        // Note: Encoder output frames don't need locking (they're not from a client)
-        log::trace!("Memory mapping output frame");
-        let frame_data = output_frame.mmap()?;
-        log::debug!("Output frame mapped, size={} bytes", frame_data.len());
-
-        log::trace!("Writing frame data to file");
-        output_file
-            .write_all(frame_data)
-            .map_err(|e| CliError::General(format!("Failed to write frame data: {}", e)))?;
-        log::debug!("Frame data written successfully");
+                Err(e) => return Err(e),
+            };
+
+        // Write encoded frame to file (raw Annex-B bitstream). The encoder
+        // may return a frame late, in which case this one is empty.
+        if size > 0 {
+            write_frame(&mut output_file, &output_frame, size as usize)?;
+            frames_written += 1;
+        }
 
         if keyframe != 0 {
             log::trace!("Recorded keyframe {}", frame_count);
@@ -373,17 +449,25 @@ pub fn execute(args: Args, _json: bool) -> Result<(), CliError> {
         log::info!("Received Ctrl+C, stopping...");
     }
 
+    // Write the frames still held by the encoder so the file ends on a
+    // complete frame
+    let drained = drain_encoder(&encoder, &mut output_file)?;
+    if drained > 0 {
+        log::debug!("Drained {} buffered frames from the encoder", drained);
+    }
+    frames_written += drained;
+
     // Flush and close file
     output_file
         .flush()
         .map_err(|e| CliError::General(format!("Failed to flush output file: {}", e)))?;
 
     let elapsed = start_time.elapsed();
-    let fps = frame_count as f64 / elapsed.as_secs_f64();
+    let fps = frames_written as f64 / elapsed.as_secs_f64();
 
     log::info!(
         "Recording complete: {} frames in {:.1}s ({:.1} fps)",
-        frame_count,
+        frames_written,
         elapsed.as_secs_f64(),
         fps
     );
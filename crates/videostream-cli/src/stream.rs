@@ -1,12 +1,24 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright 2025 Au-Zone Technologies
 
+use crate::config::{self, Config};
 use crate::error::CliError;
-use crate::metrics::MetricsCollector;
+use crate::metrics::{FrameMetrics, MetricsCollector};
 use crate::utils;
-use clap::Args as ClapArgs;
-use std::sync::atomic::Ordering;
-use videostream::{camera, encoder, fourcc::FourCC, frame::Frame, host::Host};
+use clap::{ArgMatches, Args as ClapArgs};
+use serde::Serialize;
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+use videostream::{
+    camera::{self, CameraReader},
+    client::{Client, Reconnect},
+    encoder,
+    fourcc::FourCC,
+    host::Host,
+    rate::RateMeter,
+};
 
 #[derive(ClapArgs, Debug)]
 pub struct Args {
@@ -33,10 +45,20 @@ pub struct Args {
     #[arg(short, long)]
     encode: bool,
 
+    /// Re-publish a raw VSL stream (vsl://path) instead of the camera,
+    /// encoding each frame received from it (implies --encode)
+    #[arg(long, value_name = "URI")]
+    source: Option<String>,
+
     /// Encoding bitrate in kbps
     #[arg(short, long, default_value = "25000")]
     bitrate: String,
 
+    /// V4L2 encoder device to encode on, e.g. /dev/video1 (default: first
+    /// H.264 encoder; see `devices --encoders`)
+    #[arg(long, value_name = "PATH")]
+    encoder_device: Option<String>,
+
     /// Number of frames to process (0=unlimited)
     #[arg(short, long, default_value = "0")]
     frames: u64,
@@ -50,8 +72,70 @@ pub struct Args {
     metrics_interval: Option<u64>,
 }
 
+/// Stream progress reported with `--json`, once per `--metrics-interval`
+/// and on completion. The final report keeps the performance metrics keys
+/// printed by earlier releases at the top level and adds these counters.
+#[derive(Debug, Default, Serialize)]
+struct StreamSummary {
+    /// Frames read from the camera or `--source` stream
+    frames_captured: u64,
+    /// Frames passed through the encoder (0 without `--encode`)
+    frames_encoded: u64,
+    /// Frames posted to the host
+    frames_posted: u64,
+    /// Frames the camera dropped, from gaps in the V4L2 sequence, or frames
+    /// missed from the `--source` stream, from gaps in its serials
+    frames_dropped: u64,
+    /// Time since streaming started in milliseconds
+    elapsed_ms: u64,
+    /// Achieved posting rate in frames per second
+    fps: f64,
+    /// Posting rate over the last second, left out of the final report
+    /// where the metrics' `current_fps` takes its place
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_fps: Option<f64>,
+    /// Performance metrics, in the final report
+    #[serde(flatten)]
+    metrics: Option<FrameMetrics>,
+}
+
+impl StreamSummary {
+    fn update(&mut self, elapsed: Duration, rate: &RateMeter) {
+        self.elapsed_ms = elapsed.as_millis() as u64;
+        self.fps = rate.average_fps();
+        self.current_fps = Some(rate.current_fps());
+    }
+}
+
+impl Args {
+    /// Take defaults from `config` for flags not given on the command line.
+    pub fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        config::merge(&mut self.device, &config.device, matches, "device");
+        config::merge(
+            &mut self.resolution,
+            &config.resolution,
+            matches,
+            "resolution",
+        );
+        config::merge(&mut self.format, &config.format, matches, "format");
+        config::merge(&mut self.fps, &config.fps, matches, "fps");
+        config::merge(&mut self.bitrate, &config.bitrate, matches, "bitrate");
+    }
+}
+
+/// Where the streamed frames come from
+enum Input {
+    Camera(CameraReader),
+    /// Raw frames from another VSL host, re-published encoded
+    Stream(Client),
+}
+
 pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
-    log::info!("Starting camera stream to {}", args.socket);
+    let source = args.source.as_deref().map(parse_source).transpose()?;
+    match source {
+        Some(path) => log::info!("Starting stream bridge from {} to {}", path, args.socket),
+        None => log::info!("Starting camera stream to {}", args.socket),
+    }
     log::debug!("Stream parameters: {:?}", args);
 
     // Parse resolution
@@ -65,29 +149,39 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
     // Install signal handler for graceful shutdown
     let term = utils::install_signal_handler()?;
 
-    // Warn if metrics-interval is requested (not yet implemented)
-    if let Some(interval) = args.metrics_interval {
-        log::warn!(
-            "--metrics-interval is not yet implemented (requested: {}s). \
-             Metrics will only be displayed at the end of streaming.",
-            interval
-        );
-    }
+    // Frames from a source stream are always encoded, as re-publishing them
+    // raw would only copy the stream.
+    let encode = args.encode || source.is_some();
 
     // Create encoder if requested (using helper to reduce complexity)
-    let (encoder_opt, _output_fourcc) =
-        utils::create_encoder_if_requested(args.encode, "h264", &args.bitrate, args.fps, fourcc)?;
-
-    // Open camera
-    log::info!("Opening camera: {}", args.device);
-    let cam = camera::create_camera()
-        .with_device(&args.device)
-        .with_resolution(width, height)
-        .with_format(FourCC(fourcc.to_le_bytes()))
-        .open()?;
+    let (encoder_opt, _output_fourcc) = utils::create_encoder_if_requested(
+        encode,
+        "h264",
+        &args.bitrate,
+        args.fps,
+        fourcc,
+        args.encoder_device.as_deref(),
+    )?;
 
-    log::info!("Starting camera capture");
-    cam.start()?;
+    let input = if let Some(path) = source {
+        log::info!("Connecting to source stream: {}", path);
+        let client = Client::new(path, Reconnect::Yes)?;
+        // Wake up regularly so Ctrl+C is handled while the source is idle
+        client.set_timeout(1.0)?;
+        Input::Stream(client)
+    } else {
+        // Open camera
+        log::info!("Opening camera: {}", args.device);
+        let cam = camera::create_camera()
+            .with_device(&args.device)
+            .with_resolution(width, height)
+            .with_format(FourCC(fourcc.to_le_bytes()))
+            .open()?;
+
+        log::info!("Starting camera capture");
+        cam.start()?;
+        Input::Camera(cam)
+    };
 
     // Create VSL host
     log::info!("Creating VSL host at: {}", args.socket);
@@ -100,6 +194,13 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
     } else {
         None
     };
+    let mut summary = StreamSummary::default();
+    let mut prev_sequence: Option<u32> = None;
+    let mut prev_serial: Option<i64> = None;
+    let interval = args
+        .metrics_interval
+        .filter(|&secs| secs > 0)
+        .map(Duration::from_secs);
 
     let mut frame_count = 0u64;
     let max_frames = utils::normalize_frame_count(args.frames);
@@ -108,7 +209,7 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
     let estimated_frame_size = utils::estimate_frame_size(
         width as u32,
         height as u32,
-        args.encode,
+        encode,
         &args.bitrate,
         args.fps,
         fourcc,
@@ -124,40 +225,99 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
         }
     );
 
+    let start = Instant::now();
+    let mut last_report = start;
+    let mut rate = RateMeter::default();
+
     while frame_count < max_frames && !term.load(Ordering::Relaxed) {
-        // Read frame from camera
-        let buffer = cam.read()?;
-
-        // Convert camera buffer to frame or encode it
-        let output_frame = if let Some(ref encoder) = encoder_opt {
-            // Create input frame from camera buffer
-            let input_frame: Frame = (&buffer).try_into()?;
-
-            // Create output frame for encoded data
-            let output_frame = encoder.new_output_frame(
-                width, height, -1, // duration (not used)
-                -1, // PTS (not used)
-                -1, // DTS (not used)
-            )?;
-
-            // Encode the frame
-            let crop = encoder::VSLRect::new(0, 0, width, height);
-            let mut keyframe: i32 = 0;
-
-            unsafe {
-                encoder.frame(&input_frame, &output_frame, &crop, &mut keyframe)?;
+        let (output_frame, serial) = match &input {
+            Input::Camera(cam) => {
+                // Read frame from camera
+                let buffer = cam.read()?;
+                summary.frames_captured += 1;
+
+                // Gaps in the driver sequence are frames the camera dropped;
+                // libraries before 2.5 cannot report it and drops go uncounted.
+                let sequence = buffer.sequence().ok();
+                if let (Some(prev), Some(sequence)) = (prev_sequence, sequence) {
+                    summary.frames_dropped +=
+                        u64::from(sequence.wrapping_sub(prev).saturating_sub(1));
+                }
+                prev_sequence = sequence;
+
+                // Convert camera buffer to frame or encode it
+                let output_frame = if let Some(ref encoder) = encoder_opt {
+                    // Create output frame for encoded data
+                    let output_frame = encoder.new_output_frame(
+                        width, height, -1, // duration (not used)
+                        -1, // PTS (not used)
+                        -1, // DTS (not used)
+                    )?;
+
+                    // Encode the frame
+                    let crop = encoder::VSLRect::new(0, 0, width, height);
+                    let mut keyframe: i32 = 0;
+                    encoder.encode_camera_buffer(&buffer, &output_frame, &crop, &mut keyframe)?;
+                    summary.frames_encoded += 1;
+
+                    log::trace!(
+                        "Encoded frame {} (keyframe: {})",
+                        frame_count,
+                        keyframe != 0
+                    );
+
+                    output_frame
+                } else {
+                    // Raw frame - convert camera buffer to frame
+                    (&buffer).try_into()?
+                };
+                (output_frame, sequence.map(i64::from))
+            }
+            Input::Stream(client) => {
+                let frame = match client.get_frame(0) {
+                    Ok(frame) => frame,
+                    Err(videostream::Error::Io(ref e))
+                        if e.kind() == std::io::ErrorKind::TimedOut =>
+                    {
+                        log::trace!("No frame from source stream");
+                        continue;
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+                summary.frames_captured += 1;
+
+                // Gaps in the host serials are frames this bridge missed
+                let serial = frame.serial()?;
+                if let Some(prev) = prev_serial {
+                    summary.frames_dropped += (serial - prev - 1).max(0) as u64;
+                }
+                prev_serial = Some(serial);
+
+                let Some(ref encoder) = encoder_opt else {
+                    unreachable!("source streams are always encoded")
+                };
+                let (frame_width, frame_height) = (frame.width()?, frame.height()?);
+                let output_frame =
+                    encoder.new_output_frame(frame_width, frame_height, -1, -1, -1)?;
+
+                // Hold the frame so the host cannot recycle it mid-encode
+                frame.trylock()?;
+                let crop = encoder::VSLRect::new(0, 0, frame_width, frame_height);
+                let mut keyframe: i32 = 0;
+                // Safety: `keyframe` is a valid local for the duration of the call
+                let encoded = unsafe { encoder.frame(&frame, &output_frame, &crop, &mut keyframe) };
+                frame.unlock()?;
+                encoded?;
+                summary.frames_encoded += 1;
+
+                log::trace!(
+                    "Encoded source frame {} (keyframe: {})",
+                    serial,
+                    keyframe != 0
+                );
+
+                (output_frame, Some(serial))
             }
-
-            log::trace!(
-                "Encoded frame {} (keyframe: {})",
-                frame_count,
-                keyframe != 0
-            );
-
-            output_frame
-        } else {
-            // Raw frame - convert camera buffer to frame
-            (&buffer).try_into()?
         };
 
         // Get current timestamp for frame expiration
@@ -166,11 +326,11 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
 
         // Post frame to host (ownership transfers)
         host.post(output_frame, expires, -1, -1, -1)?;
+        summary.frames_posted += 1;
+        rate.record();
 
-        // Poll for client activity
-        if host.poll(1)? > 0 {
-            host.process()?;
-        }
+        // Service clients and expire frames even when no client is active
+        host.service_timeout(Duration::from_millis(1))?;
 
         // Track metrics if enabled
         if let Some(ref mut metrics) = metrics_collector {
@@ -178,7 +338,7 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
             metrics.record_bytes(estimated_frame_size);
             // Latency not applicable for streaming (we're the source)
             metrics.record_latency_us(0);
-            metrics.track_serial(frame_count as i64);
+            metrics.track_serial(serial.unwrap_or(frame_count as i64));
         }
 
         frame_count += 1;
@@ -188,11 +348,13 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
             log::debug!("Streamed {} frames", frame_count);
         }
 
-        // Print interval metrics if requested
-        if let Some(interval) = args.metrics_interval {
-            // TODO: Implement interval metrics printing
-            // This would require tracking time and printing every N seconds
-            let _ = interval; // Suppress unused warning for now
+        // Print interval progress if requested
+        if let Some(interval) = interval {
+            if last_report.elapsed() >= interval {
+                last_report = Instant::now();
+                summary.update(start.elapsed(), &rate);
+                print_summary(&summary, json, false)?;
+            }
         }
     }
 
@@ -202,16 +364,146 @@ pub fn execute(args: Args, json: bool) -> Result<(), CliError> {
 
     log::info!("Streamed {} frames total", frame_count);
 
-    // Print final metrics if requested
-    if let Some(ref mut metrics) = metrics_collector {
-        if json {
-            metrics
-                .print_json()
-                .map_err(|e| CliError::General(format!("Failed to output JSON metrics: {}", e)))?;
-        } else if args.metrics {
-            metrics.print_text();
-        }
+    summary.update(start.elapsed(), &rate);
+    if json {
+        summary.metrics = metrics_collector.as_mut().map(MetricsCollector::finalize);
+        summary.current_fps = None;
+        print_summary(&summary, json, true)?;
+    } else if let Some(ref mut metrics) = metrics_collector {
+        metrics.print_text();
     }
 
     Ok(())
 }
+
+/// Returns the socket path of a `vsl://path` source URI.
+fn parse_source(uri: &str) -> Result<&str, CliError> {
+    match uri.strip_prefix("vsl://") {
+        Some(path) if !path.is_empty() => Ok(path),
+        _ => Err(CliError::InvalidArgs(format!(
+            "Invalid source '{}': expected vsl://<socket path>",
+            uri
+        ))),
+    }
+}
+
+/// Prints stream progress. JSON interval reports are single lines so they
+/// can be consumed as they arrive; the final JSON summary is pretty printed
+/// like the other subcommands.
+fn print_summary(summary: &StreamSummary, json: bool, last: bool) -> Result<(), CliError> {
+    if json {
+        let out = if last {
+            serde_json::to_string_pretty(summary)
+        } else {
+            serde_json::to_string(summary)
+        }
+        .map_err(|e| CliError::General(format!("Failed to output JSON summary: {}", e)))?;
+        println!("{}", out);
+    } else {
+        println!(
+            "Streamed {} frames in {:.1} s ({:.2} fps, {:.2} fps current, {} dropped)",
+            summary.frames_posted,
+            summary.elapsed_ms as f64 / 1000.0,
+            summary.fps,
+            summary.current_fps.unwrap_or_default(),
+            summary.frames_dropped
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::metrics::MetricsCollector;
+    use crate::{apply_config, Cli, Commands};
+    use clap::{CommandFactory, FromArgMatches};
+    use std::fs;
+
+    fn parse(args: &[&str]) -> super::Args {
+        let matches = Cli::command().try_get_matches_from(args).unwrap();
+        let mut cli = Cli::from_arg_matches(&matches).unwrap();
+        apply_config(&mut cli, &matches).unwrap();
+        match cli.command {
+            Commands::Stream(args) => args,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_config_precedence() {
+        let path = std::env::temp_dir().join(format!("vsl_config_{}.toml", std::process::id()));
+        fs::write(&path, "device = \"/dev/video0\"\nfps = 15\n").unwrap();
+        let config = path.to_str().unwrap();
+
+        // Config replaces built-in defaults
+        let args = parse(&["videostream", "--config", config, "stream", "/tmp/vsl.sock"]);
+        assert_eq!(args.device, "/dev/video0");
+        assert_eq!(args.fps, 15);
+        assert_eq!(args.resolution, "1920x1080");
+
+        // Flags override the config
+        let args = parse(&[
+            "videostream",
+            "--config",
+            config,
+            "stream",
+            "/tmp/vsl.sock",
+            "--device",
+            "/dev/video9",
+        ]);
+        assert_eq!(args.device, "/dev/video9");
+        assert_eq!(args.fps, 15);
+
+        // Without a config the built-in defaults apply
+        let args = parse(&["videostream", "stream", "/tmp/vsl.sock"]);
+        assert_eq!(args.device, "/dev/video3");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_source() {
+        assert_eq!(
+            super::parse_source("vsl:///tmp/camera.vsl").unwrap(),
+            "/tmp/camera.vsl"
+        );
+        assert_eq!(super::parse_source("vsl://camera").unwrap(), "camera");
+        assert!(super::parse_source("/tmp/camera.vsl").is_err());
+        assert!(super::parse_source("vsl://").is_err());
+
+        let args = parse(&[
+            "videostream",
+            "stream",
+            "/tmp/encoded.vsl",
+            "--source",
+            "vsl:///tmp/camera.vsl",
+        ]);
+        assert_eq!(args.source.as_deref(), Some("vsl:///tmp/camera.vsl"));
+        assert!(!args.encode);
+    }
+
+    #[test]
+    fn test_summary_metrics_json() {
+        let mut summary = super::StreamSummary {
+            current_fps: Some(30.0),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&summary).unwrap();
+        assert!(json.get("frames_processed").is_none());
+        assert_eq!(json["current_fps"], 30.0);
+
+        // The final report keeps the metrics keys at the top level
+        let mut metrics = MetricsCollector::new();
+        metrics.record_bytes(1000);
+        metrics.record_latency_us(0);
+        summary.metrics = Some(metrics.finalize());
+        summary.current_fps = None;
+        summary.frames_posted = 1;
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["frames_processed"], 1);
+        assert_eq!(json["bytes_processed"], 1000);
+        assert_eq!(json["frames_posted"], 1);
+        assert!(json["current_fps"].is_number());
+        assert!(json.get("metrics").is_none());
+    }
+}
@@ -0,0 +1,252 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+use crate::error::CliError;
+use clap::Args as ClapArgs;
+use mp4::{Mp4Reader, Mp4Track};
+use std::fs::{self, File};
+use std::io::{BufWriter, Cursor, Write};
+use videostream::nal::{self, Codec};
+
+#[derive(ClapArgs, Debug)]
+pub struct Args {
+    /// Input MP4 file
+    input: String,
+
+    /// Output raw bitstream file (.h264 or .h265)
+    output: String,
+}
+
+/// Codec parameters of the video track
+struct TrackParams {
+    codec: Codec,
+    /// Size of the NAL unit length prefix in each sample
+    nal_length_size: u8,
+    /// Parameter sets (VPS, SPS, PPS) in decoding order, without start codes
+    param_sets: Vec<Vec<u8>>,
+}
+
+pub fn execute(args: Args, _json: bool) -> Result<(), CliError> {
+    log::info!("Extracting {} to {}", args.input, args.output);
+
+    let data = fs::read(&args.input)
+        .map_err(|e| CliError::Io(format!("Failed to read input file: {}", e)))?;
+    let size = data.len() as u64;
+    let mut reader = Mp4Reader::read_header(Cursor::new(data.as_slice()), size)
+        .map_err(|e| CliError::General(format!("Failed to parse MP4: {}", e)))?;
+
+    let track = reader
+        .tracks()
+        .values()
+        .filter(|track| track.media_type().is_ok_and(is_video))
+        .min_by_key(|track| track.track_id())
+        .ok_or_else(|| CliError::InvalidArgs("No H.264/H.265 video track found".to_string()))?;
+    let track_id = track.track_id();
+    let params = track_params(track, &data)?;
+    let samples = reader
+        .sample_count(track_id)
+        .map_err(|e| CliError::General(format!("Failed to read sample table: {}", e)))?;
+
+    log::info!("Codec: {:?}", params.codec);
+    log::info!(
+        "Found {} samples, {} parameter sets",
+        samples,
+        params.param_sets.len()
+    );
+
+    let output_file = File::create(&args.output)
+        .map_err(|e| CliError::Io(format!("Failed to create output file: {}", e)))?;
+    let mut writer = BufWriter::new(output_file);
+
+    // Sample ids start at 1
+    for sample_id in 1..=samples {
+        let sample = reader
+            .read_sample(track_id, sample_id)
+            .map_err(|e| CliError::General(format!("Failed to read sample {}: {}", sample_id, e)))?
+            .ok_or_else(|| CliError::General(format!("Sample {} is missing", sample_id)))?;
+
+        let units = nal::parse_avcc(&sample.bytes, params.nal_length_size).ok_or_else(|| {
+            CliError::General(format!("Sample {} has a truncated NAL unit", sample_id))
+        })?;
+
+        // MP4 keeps the parameter sets in the sample entry, so repeat them
+        // before each sync sample that does not carry its own, as a raw
+        // bitstream decoder can only start at such a point.
+        if sample.is_sync && !units.iter().any(|nal| params.codec.is_sps(nal)) {
+            for param_set in &params.param_sets {
+                write_nal(&mut writer, param_set)?;
+            }
+        }
+        let annexb = nal::avcc_to_annexb(&sample.bytes, params.nal_length_size);
+        writer
+            .write_all(&annexb)
+            .map_err(|e| CliError::Io(format!("Failed to write output file: {}", e)))?;
+
+        if sample_id % 100 == 0 {
+            log::debug!("Wrote {} / {} frames", sample_id, samples);
+        }
+    }
+
+    writer
+        .flush()
+        .map_err(|e| CliError::Io(format!("Failed to write output file: {}", e)))?;
+
+    log::info!("Extraction complete!");
+    log::info!("Input:  {} ({} bytes)", args.input, size);
+    log::info!("Output: {}", args.output);
+    log::info!("Frames: {}", samples);
+
+    Ok(())
+}
+
+fn is_video(media_type: mp4::MediaType) -> bool {
+    matches!(media_type, mp4::MediaType::H264 | mp4::MediaType::H265)
+}
+
+fn write_nal(writer: &mut impl Write, nal: &[u8]) -> Result<(), CliError> {
+    writer
+        .write_all(&nal::START_CODE)
+        .and_then(|_| writer.write_all(nal))
+        .map_err(|e| CliError::Io(format!("Failed to write output file: {}", e)))
+}
+
+/// Reads the codec, NAL length size and parameter sets of `track`.
+fn track_params(track: &Mp4Track, file: &[u8]) -> Result<TrackParams, CliError> {
+    if let Some(avc1) = &track.trak.mdia.minf.stbl.stsd.avc1 {
+        let avcc = &avc1.avcc;
+        let param_sets = avcc
+            .sequence_parameter_sets
+            .iter()
+            .chain(&avcc.picture_parameter_sets)
+            .map(|nal| nal.bytes.clone())
+            .collect();
+        return Ok(TrackParams {
+            codec: Codec::H264,
+            nal_length_size: (avcc.length_size_minus_one & 0x3) + 1,
+            param_sets,
+        });
+    }
+
+    // The mp4 crate does not keep the hvcC parameter set arrays, so read the
+    // record from the file.
+    let (nal_length_size, param_sets) = find_hvcc(file)
+        .and_then(parse_hvcc)
+        .ok_or_else(|| CliError::General("Failed to find hvcC in H.265 track".to_string()))?;
+    Ok(TrackParams {
+        codec: Codec::Hevc,
+        nal_length_size,
+        param_sets,
+    })
+}
+
+/// Iterates over the boxes in `data` as (type, payload) pairs.
+fn boxes(data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut rest = data;
+    std::iter::from_fn(move || {
+        let header = rest.get(..8)?;
+        let size = u32::from_be_bytes(header[..4].try_into().ok()?) as u64;
+        let (start, size) = match size {
+            0 => (8, rest.len() as u64),
+            1 => (16, u64::from_be_bytes(rest.get(8..16)?.try_into().ok()?)),
+            _ => (8, size),
+        };
+        let size = usize::try_from(size).ok()?;
+        let item = (&header[4..8], rest.get(start..size)?);
+        rest = &rest[size..];
+        Some(item)
+    })
+}
+
+/// Returns the payload of the first child box of type `name`.
+fn child<'a>(data: &'a [u8], name: &[u8; 4]) -> Option<&'a [u8]> {
+    boxes(data)
+        .find(|(kind, _)| kind == name)
+        .map(|(_, payload)| payload)
+}
+
+/// Finds the hvcC record of the first H.265 track in an MP4 file.
+fn find_hvcc(file: &[u8]) -> Option<&[u8]> {
+    let moov = child(file, b"moov")?;
+    boxes(moov)
+        .filter(|(kind, _)| kind == b"trak")
+        .find_map(|(_, trak)| {
+            let stbl = [b"mdia", b"minf", b"stbl"]
+                .iter()
+                .try_fold(trak, |data, name| child(data, name))?;
+            // Version, flags and entry count precede the sample entries
+            let stsd = child(stbl, b"stsd")?.get(8..)?;
+            let entry = child(stsd, b"hev1").or_else(|| child(stsd, b"hvc1"))?;
+            // The visual sample entry fields precede its child boxes
+            child(entry.get(78..)?, b"hvcC")
+        })
+}
+
+/// Parses an HEVCDecoderConfigurationRecord (ISO/IEC 14496-15 8.3.3) into
+/// its NAL length size and parameter sets.
+fn parse_hvcc(record: &[u8]) -> Option<(u8, Vec<Vec<u8>>)> {
+    let nal_length_size = (record.get(21)? & 0x3) + 1;
+    let arrays = *record.get(22)?;
+    let mut rest = record.get(23..)?;
+    let mut param_sets = Vec::new();
+
+    for _ in 0..arrays {
+        let count = u16::from_be_bytes(rest.get(1..3)?.try_into().ok()?);
+        rest = &rest[3..];
+        for _ in 0..count {
+            let len = usize::from(u16::from_be_bytes(rest.get(..2)?.try_into().ok()?));
+            param_sets.push(rest.get(2..2 + len)?.to_vec());
+            rest = &rest[2 + len..];
+        }
+    }
+
+    Some((nal_length_size, param_sets))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wraps `payload` in a box of type `name`.
+    fn mp4_box(name: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut data = ((payload.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(name);
+        data.extend_from_slice(payload);
+        data
+    }
+
+    #[test]
+    fn test_find_hvcc() {
+        let vps = [0x40, 0x01, 0x0c];
+        let sps = [0x42, 0x01, 0x01, 0x60];
+        let pps = [0x44, 0x01, 0xc1];
+
+        let mut record = vec![1; 21];
+        record.push(0xf0 | 3); // lengthSizeMinusOne
+        record.push(3); // numOfArrays
+        for nal in [&vps[..], &sps, &pps] {
+            record.push(0x80 | (nal[0] >> 1));
+            record.extend_from_slice(&1u16.to_be_bytes());
+            record.extend_from_slice(&(nal.len() as u16).to_be_bytes());
+            record.extend_from_slice(nal);
+        }
+
+        let mut entry = vec![0; 78];
+        entry.extend(mp4_box(b"hvcC", &record));
+        let mut stsd = vec![0, 0, 0, 0, 0, 0, 0, 1];
+        stsd.extend(mp4_box(b"hev1", &entry));
+        let stbl = mp4_box(b"stbl", &mp4_box(b"stsd", &stsd));
+        let trak = mp4_box(b"mdia", &mp4_box(b"minf", &stbl));
+        let mut moov = mp4_box(b"mvhd", &[0; 100]);
+        moov.extend(mp4_box(b"trak", &trak));
+        let mut file = mp4_box(b"ftyp", b"isom");
+        file.extend(mp4_box(b"moov", &moov));
+
+        assert_eq!(find_hvcc(&file), Some(record.as_slice()));
+        assert_eq!(
+            parse_hvcc(&record),
+            Some((4, vec![vps.to_vec(), sps.to_vec(), pps.to_vec()]))
+        );
+        assert_eq!(parse_hvcc(&record[..30]), None);
+        assert_eq!(find_hvcc(&file[..20]), None);
+    }
+}
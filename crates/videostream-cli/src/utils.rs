@@ -6,7 +6,12 @@ use signal_hook::consts::{SIGINT, SIGTERM};
 use signal_hook::flag;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use videostream::encoder;
+use videostream::{
+    codec::{self, Codec, CodecCaps},
+    encoder,
+    fourcc::FourCC,
+    nal,
+};
 
 /// Helper to parse and validate resolution parts
 fn parse_resolution_parts(
@@ -174,7 +179,8 @@ pub fn bitrate_to_encoder_profile(bitrate_kbps: u32) -> encoder::VSLEncoderProfi
 
 /// Convert codec string to FOURCC value
 ///
-/// Converts codec name (h264, h265, hevc) to 32-bit FOURCC identifier.
+/// Converts codec name (h264, h265, hevc, mjpg, mjpeg, vp8, vp9) to 32-bit
+/// FOURCC identifier.
 ///
 /// # Examples
 /// ```
@@ -182,13 +188,17 @@ pub fn bitrate_to_encoder_profile(bitrate_kbps: u32) -> encoder::VSLEncoderProfi
 /// assert_eq!(codec_to_fourcc("h264").unwrap(), 0x34363248);
 /// assert_eq!(codec_to_fourcc("h265").unwrap(), 0x43564548);
 /// assert_eq!(codec_to_fourcc("hevc").unwrap(), 0x43564548);
+/// assert_eq!(codec_to_fourcc("mjpg").unwrap(), 0x47504a4d);
 /// ```
 pub fn codec_to_fourcc(codec: &str) -> Result<u32, CliError> {
     match codec.to_lowercase().as_str() {
         "h264" => Ok(u32::from_le_bytes(*b"H264")),
         "h265" | "hevc" => Ok(u32::from_le_bytes(*b"HEVC")),
+        "mjpg" | "mjpeg" => Ok(u32::from_le_bytes(*b"MJPG")),
+        "vp8" => Ok(u32::from_le_bytes(*b"VP80")),
+        "vp9" => Ok(u32::from_le_bytes(*b"VP90")),
         _ => Err(CliError::InvalidArgs(format!(
-            "Unsupported codec: {} (supported: h264, h265, hevc)",
+            "Unsupported codec: {} (supported: h264, h265, hevc, mjpg, vp8, vp9)",
             codec
         ))),
     }
@@ -217,7 +227,7 @@ pub fn extract_parameter_sets_h264(data: &[u8]) -> Result<ParameterSets, CliErro
     let mut sps = Vec::new();
     let mut pps = Vec::new();
 
-    let nal_units = parse_nal_units(data)?;
+    let nal_units = nal::parse_annex_b(data);
 
     for nal in nal_units {
         if nal.is_empty() {
@@ -259,7 +269,7 @@ pub fn extract_parameter_sets_h265(data: &[u8]) -> Result<ParameterSets, CliErro
     let mut sps = Vec::new();
     let mut pps = Vec::new();
 
-    let nal_units = parse_nal_units(data)?;
+    let nal_units = nal::parse_annex_b(data);
 
     for nal in nal_units {
         if nal.is_empty() {
@@ -295,74 +305,17 @@ pub fn extract_parameter_sets_h265(data: &[u8]) -> Result<ParameterSets, CliErro
     Ok(ParameterSets { sps, pps })
 }
 
-/// Detect Annex B start code at given position
-///
-/// Reference: ITU-T H.264 (ISO/IEC 14496-10) Annex B.1.1
-/// "byte_stream_nal_unit() syntax uses start code prefix 0x000001 or 0x00000001"
-///
-/// Returns the length of the start code (3 or 4 bytes) or None if no start code found
-fn detect_start_code(data: &[u8], pos: usize) -> Option<usize> {
-    if pos + 3 < data.len()
-        && data[pos] == 0
-        && data[pos + 1] == 0
-        && data[pos + 2] == 0
-        && data[pos + 3] == 1
-    {
-        Some(4)
-    } else if pos + 2 < data.len() && data[pos] == 0 && data[pos + 1] == 0 && data[pos + 2] == 1 {
-        Some(3)
-    } else {
-        None
-    }
-}
-
-/// Parse NAL units from Annex-B format bitstream
-///
-/// Annex-B format uses start codes:
-/// - 0x00 0x00 0x00 0x01 (4-byte)
-/// - 0x00 0x00 0x01 (3-byte)
-///
-/// Returns NAL units without start codes
-pub fn parse_nal_units(data: &[u8]) -> Result<Vec<&[u8]>, CliError> {
-    let mut nal_units = Vec::new();
-    let mut i = 0;
-
-    while i < data.len() {
-        // Find start code
-        let start_code_len = match detect_start_code(data, i) {
-            Some(len) => len,
-            None => {
-                i += 1;
-                continue;
-            }
-        };
-
-        // Found start code, skip it
-        i += start_code_len;
-        let nal_start = i;
-
-        // Find next start code
-        while i < data.len() {
-            if detect_start_code(data, i).is_some() {
-                break;
-            }
-            i += 1;
-        }
-
-        // Extract NAL unit (without start code)
-        if nal_start < i {
-            nal_units.push(&data[nal_start..i]);
-        }
-    }
-
-    Ok(nal_units)
-}
-
 /// Normalize codec alias to canonical form
 ///
 /// Converts various codec name aliases to their canonical lowercase form:
 /// - H.264, h.264, H264, h264 → "h264"
 /// - H.265, h.265, H265, h265, HEVC, hevc → "h265"
+/// - MJPG, MJPEG, M-JPEG, JPEG → "mjpg"
+/// - VP8, vp8 → "vp8"
+/// - VP9, vp9 → "vp9"
+///
+/// Whether the hardware can encode or decode the codec is only known once
+/// the encoder or decoder is created.
 ///
 /// # Examples
 /// ```
@@ -372,18 +325,73 @@ pub fn parse_nal_units(data: &[u8]) -> Result<Vec<&[u8]>, CliError> {
 /// assert_eq!(normalize_codec_alias("H.265").unwrap(), "h265");
 /// assert_eq!(normalize_codec_alias("HEVC").unwrap(), "h265");
 /// assert_eq!(normalize_codec_alias("hevc").unwrap(), "h265");
+/// assert_eq!(normalize_codec_alias("M-JPEG").unwrap(), "mjpg");
+/// assert_eq!(normalize_codec_alias("VP9").unwrap(), "vp9");
 /// ```
 pub fn normalize_codec_alias(codec: &str) -> Result<&'static str, CliError> {
     match codec.to_lowercase().replace(['.', '-'], "").as_str() {
         "h264" | "avc" => Ok("h264"),
         "h265" | "hevc" => Ok("h265"),
+        "mjpg" | "mjpeg" | "jpeg" => Ok("mjpg"),
+        "vp8" => Ok("vp8"),
+        "vp9" => Ok("vp9"),
         _ => Err(CliError::InvalidArgs(format!(
-            "Unsupported codec: {} (supported: H.264/h264/AVC, H.265/h265/HEVC)",
+            "Unsupported codec: {} (supported: H.264/h264/AVC, H.265/h265/HEVC, MJPEG, VP8, VP9)",
             codec
         ))),
     }
 }
 
+/// Returns `true` if an encoder, hardware or software, can produce the
+/// codec with bitstream fourcc `codec_fourcc`.
+pub fn is_encoder_available(codec_fourcc: u32) -> bool {
+    Codec::from_fourcc(FourCC::from(codec_fourcc))
+        .is_some_and(|codec| encoder::is_available_for(codec).unwrap_or(false))
+}
+
+/// Build the error for a missing hardware encoder or decoder
+///
+/// Probes the V4L2 codec devices so the message tells a codec that is
+/// decode-only (or encode-only) apart from one without hardware support,
+/// and lists the codecs that are supported instead.
+pub fn codec_unavailable(codec_fourcc: u32, encode: bool) -> CliError {
+    let role = if encode { "encoder" } else { "decoder" };
+    let message = match (
+        Codec::from_fourcc(FourCC::from(codec_fourcc)),
+        codec::capabilities(),
+    ) {
+        (Some(codec), Ok(caps)) => describe_codec_unavailable(&caps, codec, encode),
+        _ => format!("VPU {} not available on this system", role),
+    };
+    CliError::EncoderUnavailable(message)
+}
+
+fn describe_codec_unavailable(caps: &CodecCaps, codec: Codec, encode: bool) -> String {
+    let support = caps.get(codec);
+    let (role, others, other_only) = if encode {
+        ("encoder", caps.encoders(), support.decode)
+    } else {
+        ("decoder", caps.decoders(), support.encode)
+    };
+
+    let mut message = format!("No hardware {} {} available", codec, role);
+    if other_only {
+        let only = if encode { "decode-only" } else { "encode-only" };
+        message.push_str(&format!(" ({} is {} on this system)", codec, only));
+    }
+    if others.is_empty() {
+        message.push_str(&format!("; no hardware {}s were found", role));
+    } else {
+        let names: Vec<String> = others.iter().map(Codec::to_string).collect();
+        message.push_str(&format!(
+            "; hardware {} supports: {}",
+            role,
+            names.join(", ")
+        ));
+    }
+    message
+}
+
 /// Create encoder if requested, with automatic availability check
 ///
 /// Returns `(Option<Encoder>, output_fourcc)` where:
@@ -396,6 +404,8 @@ pub fn normalize_codec_alias(codec: &str) -> Result<&'static str, CliError> {
 /// * `bitrate` - Target bitrate (e.g., "25000", "25Mbps")
 /// * `fps` - Target frame rate
 /// * `fallback_fourcc` - FourCC to use when encoding is disabled
+/// * `encoder_device` - V4L2 encoder node to encode on, or `None` to pick
+///   one automatically
 ///
 /// # Errors
 /// Returns `CliError::EncoderUnavailable` if encoding requested but VPU not available
@@ -404,7 +414,7 @@ pub fn normalize_codec_alias(codec: &str) -> Result<&'static str, CliError> {
 /// ```no_run
 /// use videostream_cli::utils::create_encoder_if_requested;
 /// let (encoder, fourcc) = create_encoder_if_requested(
-///     true, "h264", "25000", 30, 0x56595559
+///     true, "h264", "25000", 30, 0x56595559, None
 /// ).unwrap();
 /// assert!(encoder.is_some());
 /// ```
@@ -414,22 +424,21 @@ pub fn create_encoder_if_requested(
     bitrate: &str,
     fps: i32,
     fallback_fourcc: u32,
+    encoder_device: Option<&str>,
 ) -> Result<(Option<encoder::Encoder>, u32), CliError> {
     if !encode {
         return Ok((None, fallback_fourcc));
     }
 
-    // Check encoder availability first
-    if !encoder::is_available().unwrap_or(false) {
-        return Err(CliError::EncoderUnavailable(
-            "VPU encoder not available on this system".to_string(),
-        ));
-    }
-
     // Normalize codec alias and convert to FourCC
     let normalized_codec = normalize_codec_alias(codec)?;
     let codec_fourcc = codec_to_fourcc(normalized_codec)?;
 
+    // Check encoder availability first
+    if !is_encoder_available(codec_fourcc) {
+        return Err(codec_unavailable(codec_fourcc, true));
+    }
+
     // Parse bitrate and map to encoder profile
     let bitrate_kbps = parse_bitrate(bitrate)?;
     let profile = bitrate_to_encoder_profile(bitrate_kbps);
@@ -442,24 +451,59 @@ pub fn create_encoder_if_requested(
     );
 
     // Create encoder
-    let enc = encoder::Encoder::create(profile as u32, codec_fourcc, fps)?;
+    let config = encoder::EncoderConfig::new(profile as u32, codec_fourcc, fps);
+    let enc = match encoder_device {
+        Some(path) => create_encoder_on_device(path, config)?,
+        None => {
+            encoder::Encoder::create(profile as u32, codec_fourcc, fps).map_err(|e| match e {
+                videostream::Error::UnsupportedCodec(_) => codec_unavailable(codec_fourcc, true),
+                e => e.into(),
+            })?
+        }
+    };
 
     Ok((Some(enc), codec_fourcc))
 }
 
+/// Create an encoder on the V4L2 encoder node selected with
+/// `--encoder-device`
+///
+/// # Errors
+/// Returns `CliError::InvalidArgs` if `path` is not an encoder or cannot
+/// produce the configured codec
+pub fn create_encoder_on_device(
+    path: &str,
+    config: encoder::EncoderConfig,
+) -> Result<encoder::Encoder, CliError> {
+    log::info!("Using encoder device {}", path);
+    encoder::Encoder::create_on_device(path, config).map_err(|e| match e {
+        videostream::Error::UnsupportedCodec(codec) => {
+            CliError::InvalidArgs(format!("Encoder device {} cannot encode {}", path, codec))
+        }
+        videostream::Error::Io(e) if e.kind() == std::io::ErrorKind::InvalidInput => {
+            CliError::InvalidArgs(format!(
+                "{}; list encoders with `videostream devices --encoders`",
+                e
+            ))
+        }
+        e => e.into(),
+    })
+}
+
 /// Create decoder if requested, with automatic availability check
 ///
 /// Returns `Option<Decoder>`:
 /// - If `decode` is false, returns None
-/// - If `decode` is true, creates H.264 decoder and returns Some(decoder)
+/// - If `decode` is true, creates a decoder for `codec` and returns Some(decoder)
 ///
 /// # Arguments
 /// * `decode` - Whether to enable decoding
-/// * `codec` - Codec name (supports aliases: H.264, h264, H.265, hevc, etc.)
+/// * `codec` - Codec name (supports aliases: H.264, h264, H.265, hevc, MJPEG, VP8, VP9, etc.)
 /// * `fps` - Frame rate hint for decoder
 ///
 /// # Errors
-/// Returns `CliError::EncoderUnavailable` if decoding requested but VPU not available
+/// Returns `CliError::EncoderUnavailable` if decoding requested but VPU not
+/// available, or the hardware cannot decode `codec`
 ///
 /// # Examples
 /// ```no_run
@@ -478,20 +522,23 @@ pub fn create_decoder_if_requested(
         return Ok(None);
     }
 
+    // Normalize codec alias
+    let normalized_codec = normalize_codec_alias(codec)?;
+
+    let codec_fourcc = codec_to_fourcc(normalized_codec)?;
+
     // Check decoder availability first
     if !decoder::is_available().unwrap_or(false) {
-        return Err(CliError::EncoderUnavailable(
-            "VPU decoder not available on this system".to_string(),
-        ));
+        return Err(codec_unavailable(codec_fourcc, false));
     }
 
-    // Normalize codec alias
-    let normalized_codec = normalize_codec_alias(codec)?;
-
     // Map to decoder codec enum
     let decoder_codec = match normalized_codec {
         "h264" => decoder::DecoderCodec::H264,
         "h265" => decoder::DecoderCodec::HEVC,
+        "mjpg" => decoder::DecoderCodec::MJPG,
+        "vp8" => decoder::DecoderCodec::VP8,
+        "vp9" => decoder::DecoderCodec::VP9,
         _ => {
             return Err(CliError::InvalidArgs(format!(
                 "Unsupported decoder codec: {}",
@@ -503,7 +550,10 @@ pub fn create_decoder_if_requested(
     log::info!("Creating {} decoder", normalized_codec.to_uppercase());
 
     // Create decoder
-    let dec = decoder::Decoder::create(decoder_codec, fps)?;
+    let dec = decoder::Decoder::create(decoder_codec, fps).map_err(|e| match e {
+        videostream::Error::UnsupportedCodec(_) => codec_unavailable(codec_fourcc, false),
+        e => e.into(),
+    })?;
 
     Ok(Some(dec))
 }
@@ -549,26 +599,17 @@ pub fn estimate_frame_size(
         let bitrate_kbps = parse_bitrate(bitrate)?;
         Ok((bitrate_kbps as u64 * 1000) / (fps as u64 * 8))
     } else {
-        // Raw frame size: width * height * bytes_per_pixel
-        // Determine bytes per pixel based on format
-        let bytes_per_pixel = match &fourcc_to_str(format).to_uppercase()[..] {
-            "YUYV" | "UYVY" => 2, // YUV 4:2:2 packed formats
-            "NV12" | "NV21" => {
-                // YUV 4:2:0 planar formats (1.5 bytes per pixel average)
-                return Ok((width as u64 * height as u64 * 3) / 2);
-            }
-            "RGB3" | "BGR3" => 3, // RGB/BGR 24-bit
-            "RGBA" | "BGRA" => 4, // RGB/BGR 32-bit with alpha
-            _ => {
-                log::warn!(
-                    "Unknown format FourCC 0x{:08x}, assuming 2 bytes/pixel",
-                    format
-                );
-                2
-            }
-        };
-
-        Ok(width as u64 * height as u64 * bytes_per_pixel)
+        // Raw frame size: width * height * bytes_per_pixel, where planar
+        // formats use the average across planes (e.g. 1.5 for NV12)
+        let bytes_per_pixel = FourCC::from(format).bytes_per_pixel().unwrap_or_else(|| {
+            log::warn!(
+                "Unknown format FourCC 0x{:08x}, assuming 2 bytes/pixel",
+                format
+            );
+            2.0
+        });
+
+        Ok((width as f64 * height as f64 * bytes_per_pixel as f64) as u64)
     }
 }
 
@@ -657,190 +698,6 @@ mod tests {
     // NAL Unit Parsing Tests - ITU-T H.264 Annex B / ITU-T H.265 Annex B
     // =========================================================================
 
-    /// Test detect_start_code() with 4-byte start code (0x00000001)
-    ///
-    /// Reference: ITU-T H.264 (ISO/IEC 14496-10) Annex B.1.1
-    /// "byte_stream_nal_unit() syntax uses start code prefix 0x000001 or 0x00000001"
-    #[test]
-    fn test_detect_start_code_four_byte() {
-        let data = vec![0x00, 0x00, 0x00, 0x01, 0x67];
-        assert_eq!(detect_start_code(&data, 0), Some(4));
-    }
-
-    /// Test detect_start_code() with 3-byte start code (0x000001)
-    ///
-    /// Reference: ITU-T H.264 (ISO/IEC 14496-10) Annex B.1.1
-    #[test]
-    fn test_detect_start_code_three_byte() {
-        let data = vec![0x00, 0x00, 0x01, 0x68];
-        assert_eq!(detect_start_code(&data, 0), Some(3));
-    }
-
-    /// Test detect_start_code() with no start code
-    #[test]
-    fn test_detect_start_code_none() {
-        let data = vec![0x00, 0x00, 0x02, 0x67];
-        assert_eq!(detect_start_code(&data, 0), None);
-
-        let data2 = vec![0x01, 0x00, 0x00, 0x01];
-        assert_eq!(detect_start_code(&data2, 0), None);
-    }
-
-    /// Test detect_start_code() at different positions
-    #[test]
-    fn test_detect_start_code_positions() {
-        let data = vec![
-            0xFF, 0xFF, // Non-start-code prefix
-            0x00, 0x00, 0x01, // 3-byte start code at pos 2
-            0x67, // NAL data
-            0x00, 0x00, 0x00, 0x01, // 4-byte start code at pos 6
-            0x68, // NAL data
-        ];
-
-        assert_eq!(detect_start_code(&data, 0), None);
-        assert_eq!(detect_start_code(&data, 1), None);
-        assert_eq!(detect_start_code(&data, 2), Some(3));
-        assert_eq!(detect_start_code(&data, 6), Some(4));
-    }
-
-    /// Test detect_start_code() with truncated data
-    #[test]
-    fn test_detect_start_code_truncated() {
-        // Only 2 bytes - cannot form even 3-byte start code
-        let data = vec![0x00, 0x00];
-        assert_eq!(detect_start_code(&data, 0), None);
-
-        // Only 3 bytes - can check for 3-byte but not 4-byte at end
-        let data2 = vec![0x00, 0x00, 0x01];
-        assert_eq!(detect_start_code(&data2, 0), Some(3));
-
-        // Check boundary: position too close to end
-        let data3 = vec![0x00, 0x00, 0x00, 0x01];
-        assert_eq!(detect_start_code(&data3, 2), None); // Only 2 bytes left
-    }
-
-    /// Test parse_nal_units() with 4-byte start code (0x00000001)
-    ///
-    /// Reference: ITU-T H.264 (ISO/IEC 14496-10) Annex B.1.1
-    /// "byte_stream_nal_unit() syntax uses start code prefix 0x000001 or 0x00000001"
-    #[test]
-    fn test_parse_nal_units_four_byte_start_code() {
-        // Single NAL unit with 4-byte start code
-        let data = vec![
-            0x00, 0x00, 0x00, 0x01, // 4-byte start code
-            0x67, 0x42, 0x00, 0x0A, // NAL unit data (SPS header)
-        ];
-        let nal_units = parse_nal_units(&data).unwrap();
-        assert_eq!(nal_units.len(), 1);
-        assert_eq!(nal_units[0], &[0x67, 0x42, 0x00, 0x0A]);
-    }
-
-    /// Test parse_nal_units() with 3-byte start code (0x000001)
-    ///
-    /// Reference: ITU-T H.264 Annex B.1.1
-    /// "The 3-byte start code 0x000001 may be used for byte stream NAL units"
-    #[test]
-    fn test_parse_nal_units_three_byte_start_code() {
-        // Single NAL unit with 3-byte start code
-        let data = vec![
-            0x00, 0x00, 0x01, // 3-byte start code
-            0x68, 0xCE, 0x3C, 0x80, // NAL unit data (PPS)
-        ];
-        let nal_units = parse_nal_units(&data).unwrap();
-        assert_eq!(nal_units.len(), 1);
-        assert_eq!(nal_units[0], &[0x68, 0xCE, 0x3C, 0x80]);
-    }
-
-    /// Test parse_nal_units() with multiple NAL units using mixed start codes
-    ///
-    /// Reference: ITU-T H.264 Annex B.1
-    /// "A byte stream NAL unit syntax structure contains a sequence of byte stream NAL units"
-    #[test]
-    fn test_parse_nal_units_multiple_mixed_start_codes() {
-        let data = vec![
-            0x00, 0x00, 0x00, 0x01, // 4-byte start code
-            0x67, 0x42, // SPS start
-            0x00, 0x00, 0x01, // 3-byte start code
-            0x68, 0xCE, // PPS start
-            0x00, 0x00, 0x00, 0x01, // 4-byte start code
-            0x65, 0x88, // IDR slice
-        ];
-        let nal_units = parse_nal_units(&data).unwrap();
-        assert_eq!(nal_units.len(), 3);
-        assert_eq!(nal_units[0], &[0x67, 0x42]);
-        assert_eq!(nal_units[1], &[0x68, 0xCE]);
-        assert_eq!(nal_units[2], &[0x65, 0x88]);
-    }
-
-    /// Test parse_nal_units() with emulation prevention bytes
-    ///
-    /// Reference: ITU-T H.264 Section 7.4.1
-    /// "Within NAL unit, 0x000003 is emulation prevention - 0x03 prevents start code emulation"
-    #[test]
-    fn test_parse_nal_units_with_emulation_prevention() {
-        // NAL unit containing 0x000003 (emulation prevention sequence)
-        let data = vec![
-            0x00, 0x00, 0x00, 0x01, // Start code
-            0x67, 0x00, 0x00, 0x03, 0x01, 0x42, // NAL with emulation prevention
-        ];
-        let nal_units = parse_nal_units(&data).unwrap();
-        assert_eq!(nal_units.len(), 1);
-        // The 0x000003 should be kept in the NAL unit (parser doesn't decode, just splits)
-        assert_eq!(nal_units[0], &[0x67, 0x00, 0x00, 0x03, 0x01, 0x42]);
-    }
-
-    /// Test parse_nal_units() with zero-length NAL unit (consecutive start codes)
-    ///
-    /// Reference: ITU-T H.264 Annex B
-    /// "Zero-length NAL units should be handled gracefully"
-    #[test]
-    fn test_parse_nal_units_zero_length() {
-        let data = vec![
-            0x00, 0x00, 0x00, 0x01, // First start code
-            0x00, 0x00, 0x01, // Immediate second start code (zero-length NAL)
-            0x67, 0x42, // Actual NAL data
-        ];
-        let nal_units = parse_nal_units(&data).unwrap();
-        // Should handle zero-length gracefully and still parse the valid NAL
-        assert!(nal_units.iter().any(|nal| nal.starts_with(&[0x67, 0x42])));
-    }
-
-    /// Test parse_nal_units() with no start codes (invalid bitstream)
-    ///
-    /// Edge case: Data without start codes should return empty
-    #[test]
-    fn test_parse_nal_units_no_start_codes() {
-        let data = vec![0x67, 0x42, 0x00, 0x0A, 0x68, 0xCE];
-        let nal_units = parse_nal_units(&data).unwrap();
-        assert_eq!(nal_units.len(), 0);
-    }
-
-    /// Test parse_nal_units() with partial start code at end (truncated stream)
-    ///
-    /// Edge case: Bitstream ending with incomplete start code
-    #[test]
-    fn test_parse_nal_units_truncated_start_code() {
-        let data = vec![
-            0x00, 0x00, 0x00, 0x01, // Valid start code
-            0x67, 0x42, 0x00, 0x0A, // NAL data
-            0x00, 0x00, // Partial start code at end (truncated)
-        ];
-        let nal_units = parse_nal_units(&data).unwrap();
-        assert_eq!(nal_units.len(), 1);
-        // The partial start code should be included in the NAL unit
-        assert_eq!(nal_units[0], &[0x67, 0x42, 0x00, 0x0A, 0x00, 0x00]);
-    }
-
-    /// Test parse_nal_units() with empty input
-    ///
-    /// Edge case: Empty bitstream
-    #[test]
-    fn test_parse_nal_units_empty() {
-        let data = vec![];
-        let nal_units = parse_nal_units(&data).unwrap();
-        assert_eq!(nal_units.len(), 0);
-    }
-
     /// Test extract_parameter_sets_h264() with valid SPS and PPS
     ///
     /// Reference: ITU-T H.264 Section 7.3.2.1 (SPS) and 7.3.2.2 (PPS)
@@ -965,6 +822,31 @@ mod tests {
         assert!(codec_to_fourcc("").is_err());
     }
 
+    /// Test the Motion JPEG, VP8 and VP9 aliases normalize to names whose
+    /// fourcc matches the library's codec
+    #[test]
+    fn test_codec_alias_mjpg_vp8_vp9() {
+        let cases = [
+            ("MJPG", Codec::Mjpg),
+            ("mjpeg", Codec::Mjpg),
+            ("M-JPEG", Codec::Mjpg),
+            ("JPEG", Codec::Mjpg),
+            ("VP8", Codec::Vp8),
+            ("vp8", Codec::Vp8),
+            ("VP9", Codec::Vp9),
+            ("vp9", Codec::Vp9),
+        ];
+        for (alias, codec) in cases {
+            let normalized = normalize_codec_alias(alias).unwrap();
+            let fourcc = FourCC::from(codec_to_fourcc(normalized).unwrap());
+            assert_eq!(fourcc, codec.fourcc(), "{}", alias);
+            assert_eq!(Codec::from_fourcc(fourcc), Some(codec), "{}", alias);
+        }
+
+        assert!(normalize_codec_alias("vp7").is_err());
+        assert!(codec_to_fourcc("av1").is_err());
+    }
+
     /// Test bitrate_to_encoder_profile() boundary conditions
     ///
     /// Verifies correct profile selection at tier boundaries
@@ -1058,7 +940,7 @@ mod tests {
     /// Test normalize_codec_alias() with unsupported codec
     #[test]
     fn test_normalize_codec_alias_invalid() {
-        assert!(normalize_codec_alias("vp9").is_err());
+        assert!(normalize_codec_alias("vp7").is_err());
         assert!(normalize_codec_alias("av1").is_err());
         assert!(normalize_codec_alias("invalid").is_err());
     }
@@ -1145,6 +1027,39 @@ mod tests {
         assert_eq!(size, 2764800);
     }
 
+    /// Test estimate_frame_size() for 32-bit RGBA/BGRA frames (4 bytes/pixel)
+    #[test]
+    fn test_estimate_frame_size_raw_rgba() {
+        // 1920x1080 RGBA = 1920 * 1080 * 4 = 8294400 bytes
+        for format in ["RGBA", "BGRA"] {
+            let fourcc = fourcc_from_str(format).unwrap();
+            let size = estimate_frame_size(1920, 1080, false, "0", 30, fourcc).unwrap();
+            assert_eq!(size, 8294400, "{}", format);
+        }
+    }
+
+    /// Test estimate_frame_size() for 10/16-bit 4:2:0 frames (3 bytes/pixel)
+    #[test]
+    fn test_estimate_frame_size_raw_p010() {
+        // 1920x1080 P010 = 1920 * 1080 * 2 * 1.5 = 6220800 bytes
+        for format in ["P010", "P016"] {
+            let fourcc = fourcc_from_str(format).unwrap();
+            let size = estimate_frame_size(1920, 1080, false, "0", 30, fourcc).unwrap();
+            assert_eq!(size, 6220800, "{}", format);
+        }
+    }
+
+    /// Test estimate_frame_size() for planar I420/YV12 frames (1.5 bytes/pixel)
+    #[test]
+    fn test_estimate_frame_size_raw_i420() {
+        // 1920x1080 I420 = 1920 * 1080 * 1.5 = 3110400 bytes
+        for format in ["I420", "YV12"] {
+            let fourcc = fourcc_from_str(format).unwrap();
+            let size = estimate_frame_size(1920, 1080, false, "0", 30, fourcc).unwrap();
+            assert_eq!(size, 3110400, "{}", format);
+        }
+    }
+
     /// Test estimate_frame_size() for unknown format (defaults to 2 bytes/pixel)
     #[test]
     fn test_estimate_frame_size_unknown_format() {
@@ -1170,12 +1085,34 @@ mod tests {
     }
 
     /// Test create_encoder_if_requested() with encoding disabled
+    #[test]
+    fn test_describe_codec_unavailable() {
+        let mut caps = CodecCaps::default();
+        caps.h264.encode = true;
+        caps.hevc.decode = true;
+
+        assert_eq!(
+            describe_codec_unavailable(&caps, Codec::Hevc, true),
+            "No hardware HEVC encoder available (HEVC is decode-only on this system); \
+             hardware encoder supports: H.264"
+        );
+        assert_eq!(
+            describe_codec_unavailable(&caps, Codec::H264, false),
+            "No hardware H.264 decoder available (H.264 is encode-only on this system); \
+             hardware decoder supports: HEVC"
+        );
+        assert_eq!(
+            describe_codec_unavailable(&CodecCaps::default(), Codec::H264, true),
+            "No hardware H.264 encoder available; no hardware encoders were found"
+        );
+    }
+
     #[test]
     fn test_create_encoder_disabled() {
         let fallback_fourcc = fourcc_from_str("YUYV").unwrap();
 
         let (encoder, fourcc) =
-            create_encoder_if_requested(false, "h264", "25000", 30, fallback_fourcc).unwrap();
+            create_encoder_if_requested(false, "h264", "25000", 30, fallback_fourcc, None).unwrap();
 
         assert!(encoder.is_none());
         assert_eq!(fourcc, fallback_fourcc);
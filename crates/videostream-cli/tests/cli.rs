@@ -16,6 +16,7 @@ use std::{
     thread,
     time::Duration,
 };
+use videostream::{h264::SpsInfo, nal};
 
 /// Small delay to allow hardware resources (camera, V4L2 encoder) to be released
 /// between tests. This prevents "device busy" issues when tests run back-to-back.
@@ -147,6 +148,68 @@ fn test_info_help() {
         .stdout(predicate::str::contains("capabilities"));
 }
 
+#[test]
+fn test_list_help() {
+    videostream_cmd()
+        .arg("list")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("List VSL streams"))
+        .stdout(predicate::str::contains("[DIR]"));
+}
+
+#[test]
+fn test_list_empty_dir() {
+    let dir = std::env::temp_dir().join(format!("videostream_test_list_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    videostream_cmd()
+        .arg("list")
+        .arg(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No VSL streams found"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_probe_help() {
+    videostream_cmd()
+        .arg("probe")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Self-test"))
+        .stdout(predicate::str::contains("--frames"));
+}
+
+#[test]
+#[ignore = "requires camera and VPU hardware (run with --include-ignored on hardware)"]
+#[serial]
+fn test_probe_round_trip() {
+    hardware_cleanup_delay(); // Allow previous test's hardware to be released
+
+    let output = videostream_cmd()
+        .arg("--json")
+        .arg("probe")
+        .arg("--frames")
+        .arg("30")
+        .timeout(Duration::from_secs(60))
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output).expect("probe JSON");
+    assert_eq!(report["passed"], true);
+    let stages = report["stages"].as_array().unwrap();
+    assert_eq!(stages.len(), 5);
+    for stage in stages {
+        assert_eq!(stage["status"], "pass", "stage failed: {}", stage);
+    }
+}
+
 #[test]
 fn test_convert_help() {
     videostream_cmd()
@@ -172,6 +235,19 @@ fn test_info_basic() {
         .stdout(predicate::str::contains("System Information"));
 }
 
+#[test]
+fn test_info_library_path() {
+    let library =
+        env::var("VIDEOSTREAM_LIBRARY").unwrap_or_else(|_| "libvideostream.so".to_string());
+
+    videostream_cmd()
+        .args(["--library", &library, "info"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"Library Version: \d+\.\d+\.\d+").unwrap())
+        .stdout(predicate::str::is_match(r"Library Path: \S*libvideostream").unwrap());
+}
+
 #[test]
 fn test_info_json_output() {
     videostream_cmd()
@@ -182,6 +258,31 @@ fn test_info_json_output() {
         .stdout(predicate::str::is_match(r#"\{"#).unwrap());
 }
 
+#[test]
+fn test_info_json_capabilities() {
+    let output = videostream_cmd()
+        .args(["info", "--capabilities", "--json"])
+        .output()
+        .expect("Failed to run info");
+    assert!(output.status.success());
+
+    let info: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("info output is not valid JSON");
+    let caps = info["capabilities"]
+        .as_object()
+        .expect("capabilities object missing");
+    let codecs = caps["codecs"].as_array().expect("codecs array missing");
+    assert!(!codecs.is_empty());
+    for codec in codecs {
+        assert!(codec["codec"].is_string());
+        assert!(codec["encode"].is_boolean());
+        assert!(codec["decode"].is_boolean());
+    }
+    assert!(codecs.iter().any(|codec| codec["fourcc"] == "H264"));
+    assert!(caps["cameras"].is_array());
+    assert!(caps["dma_heaps"].is_array());
+}
+
 // =============================================================================
 // Convert Command Tests (No Hardware Required)
 // =============================================================================
@@ -194,7 +295,33 @@ fn test_convert_missing_input() {
         .arg("output.mp4")
         .assert()
         .failure()
-        .code(1); // GeneralError
+        .code(7); // Io
+}
+
+#[test]
+fn test_stream_invalid_resolution_exit_code() {
+    videostream_cmd()
+        .arg("stream")
+        .arg("/tmp/videostream_test_bad_resolution")
+        .arg("--resolution")
+        .arg("12")
+        .assert()
+        .failure()
+        .code(2); // InvalidArgs
+}
+
+#[test]
+fn test_stream_missing_device_exit_code() {
+    videostream_cmd()
+        .arg("stream")
+        .arg("/tmp/videostream_test_missing_device")
+        .arg("--device")
+        .arg("/dev/videoNONEXISTENT")
+        .arg("--frames")
+        .arg("1")
+        .assert()
+        .failure()
+        .code(3); // CameraNotFound
 }
 
 #[test]
@@ -215,6 +342,86 @@ fn test_convert_invalid_extension() {
     fs::remove_file(&input).ok();
 }
 
+#[test]
+fn test_unmux_missing_input() {
+    videostream_cmd()
+        .arg("unmux")
+        .arg("/nonexistent/input.mp4")
+        .arg("output.h264")
+        .assert()
+        .failure()
+        .code(7); // Io
+}
+
+/// Converts a synthetic H.264 stream to MP4 and back and expects the same
+/// frames, with the parameter sets restored before each IDR.
+#[test]
+fn test_convert_unmux_round_trip() {
+    const FRAMES: u8 = 10;
+    let test_dir = get_test_data_dir();
+    let h264_file = test_dir.join("test_unmux_input.h264");
+    let mp4_file = test_dir.join("test_unmux.mp4");
+    let output_file = test_dir.join("test_unmux_output.h264");
+
+    // SPS of a 1920x1080 stream from openh264 and a matching PPS
+    let sps = [
+        0x67, 0x42, 0xc0, 0x28, 0x8c, 0x68, 0x07, 0x80, 0x22, 0x7e, 0x58, 0x07, 0x84, 0x42, 0x35,
+    ];
+    let pps = [0x68, 0xce, 0x3c, 0x80];
+    let mut slices = Vec::new();
+    let mut input = Vec::new();
+    for i in 0..FRAMES {
+        let slice = if i % 5 == 0 {
+            for nal in [&sps[..], &pps] {
+                input.extend_from_slice(&[0, 0, 0, 1]);
+                input.extend_from_slice(nal);
+            }
+            vec![0x65, 0x88, 0x84, i + 1, 0x80]
+        } else {
+            vec![0x41, 0x9a, 0x02, i + 1, 0x80]
+        };
+        // Three byte start codes must round-trip too
+        input.extend_from_slice(&[0, 0, 1]);
+        input.extend_from_slice(&slice);
+        slices.push(slice);
+    }
+    fs::write(&h264_file, &input).unwrap();
+
+    videostream_cmd()
+        .arg("convert")
+        .arg(&h264_file)
+        .arg(&mp4_file)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!("Frames: {} (", FRAMES)));
+
+    videostream_cmd()
+        .arg("unmux")
+        .arg(&mp4_file)
+        .arg(&output_file)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!("Frames: {}", FRAMES)));
+
+    let output = fs::read(&output_file).unwrap();
+    assert!(output.starts_with(&[0, 0, 0, 1]));
+    let nals = nal::parse_annex_b(&output);
+    let types: Vec<u8> = nals.iter().map(|nal| nal[0] & 0x1f).collect();
+    assert_eq!(types, [7, 8, 5, 1, 1, 1, 1, 7, 8, 5, 1, 1, 1, 1]);
+    assert_eq!(nals[0], sps);
+    assert_eq!(nals[1], pps);
+    let restored: Vec<&[u8]> = nals
+        .iter()
+        .copied()
+        .filter(|nal| matches!(nal[0] & 0x1f, 1 | 5))
+        .collect();
+    assert_eq!(restored, slices);
+
+    fs::remove_file(&h264_file).ok();
+    fs::remove_file(&mp4_file).ok();
+    fs::remove_file(&output_file).ok();
+}
+
 // =============================================================================
 // Hardware Tests (Camera Required)
 // =============================================================================
@@ -335,6 +542,90 @@ fn test_record_and_convert_to_mp4() {
     fs::remove_file(&mp4_file).ok();
 }
 
+#[test]
+#[ignore = "requires camera and VPU hardware (run with --include-ignored on hardware)"]
+#[serial]
+fn test_record_sigint_ends_on_complete_frame() {
+    hardware_cleanup_delay(); // Allow previous test's hardware to be released
+
+    let test_dir = get_test_data_dir();
+    let output_file = test_dir.join("test_record_sigint.h264");
+    fs::remove_file(&output_file).ok();
+
+    // Record without a frame or duration limit until interrupted
+    let mut record_cmd = StdCommand::new(videostream_bin());
+    record_cmd
+        .arg("record")
+        .arg(&output_file)
+        .arg("--device")
+        .arg("/dev/video3")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    if let Ok(profile_file) = env::var("LLVM_PROFILE_FILE") {
+        record_cmd.env("LLVM_PROFILE_FILE", profile_file);
+    }
+    let record_process = record_cmd.spawn().expect("Failed to start record command");
+
+    thread::sleep(Duration::from_secs(3));
+    let killed = StdCommand::new("kill")
+        .arg("-INT")
+        .arg(record_process.id().to_string())
+        .status()
+        .expect("Failed to run kill");
+    assert!(killed.success());
+
+    let output = record_process
+        .wait_with_output()
+        .expect("Failed to wait for record command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "record failed: {}", stderr);
+    assert!(stderr.contains("Received Ctrl+C"), "{}", stderr);
+    assert!(stderr.contains("Recording complete"), "{}", stderr);
+
+    // Every NAL unit is whole, and the last one is a slice whose header
+    // parses, so the file ends on a complete frame
+    let data = fs::read(&output_file).expect("Output file should exist");
+    assert!(data.starts_with(&[0, 0, 0, 1]) || data.starts_with(&[0, 0, 1]));
+    let nals = nal::parse_annex_b(&data);
+    let slices = nals
+        .iter()
+        .filter(|nal| matches!(nal.first().map(|b| b & 0x1f), Some(1..=5)))
+        .count();
+    assert!(slices > 0, "no slices in {} NAL units", nals.len());
+
+    let last = nals.last().unwrap();
+    assert!(
+        matches!(last[0] & 0x1f, 1 | 5),
+        "last NAL unit has type {}",
+        last[0] & 0x1f
+    );
+    // first_mb_in_slice is 0, coded as a single 1 bit
+    assert!(
+        last.get(1).is_some_and(|byte| byte & 0x80 != 0),
+        "last slice does not start a frame"
+    );
+    let sps = nals
+        .iter()
+        .find(|nal| nal[0] & 0x1f == 7)
+        .expect("no SPS in the stream");
+    let sps = SpsInfo::parse(sps).expect("invalid SPS");
+    assert!(sps.width > 0 && sps.height > 0);
+
+    // The converter parses the whole stream
+    let mp4_file = test_dir.join("test_record_sigint.mp4");
+    videostream_cmd()
+        .arg("convert")
+        .arg(&output_file)
+        .arg(&mp4_file)
+        .timeout(Duration::from_secs(60))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!("Frames: {} (", slices)));
+
+    fs::remove_file(&output_file).ok();
+    fs::remove_file(&mp4_file).ok();
+}
+
 // =============================================================================
 // Stream/Receive Tests (Camera Required)
 // =============================================================================
@@ -405,6 +696,160 @@ fn test_stream_and_receive() {
     fs::remove_file(socket_path).ok();
 }
 
+#[test]
+#[ignore = "requires camera hardware (run with --include-ignored on hardware)"]
+#[serial]
+fn test_stream_json_summary() {
+    hardware_cleanup_delay(); // Allow previous test's hardware to be released
+
+    let socket_path = "/tmp/videostream_test_stream_json";
+    fs::remove_file(socket_path).ok();
+
+    let output = videostream_cmd()
+        .arg("stream")
+        .arg(socket_path)
+        .arg("--device")
+        .arg("/dev/video3")
+        .arg("--frames")
+        .arg("30")
+        .arg("--json")
+        .timeout(Duration::from_secs(30))
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let summary: serde_json::Value =
+        serde_json::from_slice(&output).expect("stream --json should print a JSON summary");
+    for key in [
+        "frames_captured",
+        "frames_encoded",
+        "frames_posted",
+        "frames_dropped",
+        "elapsed_ms",
+        "fps",
+        "current_fps",
+        // Keys printed by earlier releases
+        "frames_processed",
+        "throughput_fps",
+        "dropped_frames",
+    ] {
+        assert!(summary.get(key).is_some(), "missing {} in {}", key, summary);
+    }
+    assert_eq!(summary["frames_posted"], 30);
+    assert_eq!(summary["frames_encoded"], 0);
+
+    fs::remove_file(socket_path).ok();
+}
+
+#[test]
+#[ignore = "requires camera hardware (run with --include-ignored on hardware)"]
+#[serial]
+fn test_stream_json_metrics() {
+    hardware_cleanup_delay(); // Allow previous test's hardware to be released
+
+    let socket_path = "/tmp/videostream_test_stream_json_metrics";
+    fs::remove_file(socket_path).ok();
+
+    let output = videostream_cmd()
+        .arg("stream")
+        .arg(socket_path)
+        .arg("--device")
+        .arg("/dev/video3")
+        .arg("--frames")
+        .arg("30")
+        .arg("--metrics")
+        .arg("--json")
+        .timeout(Duration::from_secs(30))
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    // Metrics share the summary object so stdout stays one JSON document
+    let summary: serde_json::Value = serde_json::from_slice(&output)
+        .expect("stream --metrics --json should print a JSON summary");
+    for key in ["frames_processed", "throughput_fps", "dropped_frames"] {
+        assert!(summary.get(key).is_some(), "missing {} in {}", key, summary);
+    }
+    assert_eq!(summary["frames_processed"], 30);
+
+    fs::remove_file(socket_path).ok();
+}
+
+#[test]
+#[ignore = "requires camera and VPU hardware (run with --include-ignored on hardware)"]
+#[serial]
+fn test_stream_source_bridge() {
+    hardware_cleanup_delay(); // Allow previous test's hardware to be released
+
+    let raw_socket = "/tmp/videostream_test_bridge_raw";
+    let encoded_socket = "/tmp/videostream_test_bridge_encoded";
+    fs::remove_file(raw_socket).ok();
+    fs::remove_file(encoded_socket).ok();
+
+    let spawn = |args: &[&str]| {
+        let mut cmd = StdCommand::new(videostream_bin());
+        cmd.args(args).stdout(Stdio::null()).stderr(Stdio::null());
+        if let Ok(profile_file) = env::var("LLVM_PROFILE_FILE") {
+            cmd.env("LLVM_PROFILE_FILE", profile_file);
+        }
+        cmd.spawn().expect("Failed to start stream command")
+    };
+
+    // Raw camera host, bridged into an encoded host without a camera
+    let mut raw_process = spawn(&[
+        "stream",
+        raw_socket,
+        "--device",
+        "/dev/video3",
+        "--frames",
+        "300",
+    ]);
+    thread::sleep(Duration::from_secs(2));
+    let source = format!("vsl://{}", raw_socket);
+    let mut bridge_process = spawn(&[
+        "stream",
+        encoded_socket,
+        "--source",
+        &source,
+        "--frames",
+        "200",
+    ]);
+    thread::sleep(Duration::from_secs(2));
+
+    let output = videostream_cmd()
+        .arg("receive")
+        .arg(encoded_socket)
+        .arg("--frames")
+        .arg("30")
+        .arg("--json")
+        .timeout(Duration::from_secs(30))
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    bridge_process.wait().ok();
+    raw_process.wait().ok();
+
+    // The encoded stream starts with an IDR frame
+    let metrics: serde_json::Value =
+        serde_json::from_slice(&output).expect("receive --json should print metrics");
+    assert_eq!(metrics["frames_processed"], 30);
+    assert!(
+        metrics["keyframes"].as_u64().unwrap_or(0) > 0,
+        "no keyframes in {}",
+        metrics
+    );
+
+    fs::remove_file(raw_socket).ok();
+    fs::remove_file(encoded_socket).ok();
+}
+
 #[test]
 #[ignore = "requires camera and VPU hardware (run with --include-ignored on hardware)"]
 #[serial]
@@ -7,6 +7,9 @@ pub const VSL_VERSION: &[u8; 6] = b"2.5.1\0";
 pub const VSL_V4L2_MAX_FORMATS: u32 = 64;
 pub const VSL_V4L2_MAX_RESOLUTIONS: u32 = 32;
 pub const VSL_V4L2_MAX_FRAMERATES: u32 = 16;
+pub const VSL_COMMAND_MAX: u32 = 4096;
+pub const VSL_ENCODER_MAX_ROI: u32 = 8;
+pub const VSL_FRAME_MAX_PLANES: u32 = 3;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct vsl_host {
@@ -66,6 +69,51 @@ const _: () = {
 };
 #[doc = " The VSLRect structure represents a rectangle region of a frame and is used to\n define cropping regions for sub-frames."]
 pub type VSLRect = vsl_rect;
+#[doc = " Metadata of a pending frame returned by vsl_client_peek().\n\n @since 2.6"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct vsl_frame_meta {
+    #[doc = " Frame serial number assigned by the host."]
+    pub serial: i64,
+    #[doc = " Host timestamp in nanoseconds when the frame was posted."]
+    pub timestamp: i64,
+    #[doc = " Presentation timestamp in nanoseconds, or -1 if unknown."]
+    pub pts: i64,
+    #[doc = " Size of the frame buffer in bytes."]
+    pub size: usize,
+    #[doc = " Frame pixel format as a FourCC code."]
+    pub fourcc: u32,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of vsl_frame_meta"][::std::mem::size_of::<vsl_frame_meta>() - 40usize];
+    ["Alignment of vsl_frame_meta"][::std::mem::align_of::<vsl_frame_meta>() - 8usize];
+    ["Offset of field: vsl_frame_meta::serial"][::std::mem::offset_of!(vsl_frame_meta, serial) - 0usize];
+    ["Offset of field: vsl_frame_meta::timestamp"][::std::mem::offset_of!(vsl_frame_meta, timestamp) - 8usize];
+    ["Offset of field: vsl_frame_meta::pts"][::std::mem::offset_of!(vsl_frame_meta, pts) - 16usize];
+    ["Offset of field: vsl_frame_meta::size"][::std::mem::offset_of!(vsl_frame_meta, size) - 24usize];
+    ["Offset of field: vsl_frame_meta::fourcc"][::std::mem::offset_of!(vsl_frame_meta, fourcc) - 32usize];
+};
+#[doc = " Metadata of a pending frame returned by vsl_client_peek().\n\n @since 2.6"]
+pub type VSLFrameMeta = vsl_frame_meta;
+#[doc = " Frames discarded by a host, by cause, returned by vsl_host_drop_stats().\n\n @since 2.6"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct vsl_host_drop_stats {
+    #[doc = " Frames that expired before any client locked them."]
+    pub expired: u64,
+    #[doc = " Frame events not delivered because a client's socket buffer was full,\n counted once per client."]
+    pub client_slow: u64,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of vsl_host_drop_stats"][::std::mem::size_of::<vsl_host_drop_stats>() - 16usize];
+    ["Alignment of vsl_host_drop_stats"][::std::mem::align_of::<vsl_host_drop_stats>() - 8usize];
+    ["Offset of field: vsl_host_drop_stats::expired"][::std::mem::offset_of!(vsl_host_drop_stats, expired) - 0usize];
+    ["Offset of field: vsl_host_drop_stats::client_slow"][::std::mem::offset_of!(vsl_host_drop_stats, client_slow) - 8usize];
+};
+#[doc = " Frames discarded by a host, by cause, returned by vsl_host_drop_stats().\n\n @since 2.6"]
+pub type VSLHostDropStats = vsl_host_drop_stats;
 #[doc = " Automatic bitrate selection (encoder default).\n\n Platform and version dependent. Testing shows approximately 10000 kbps\n on i.MX8M Plus. Use this for general-purpose encoding when specific\n bitrate control is not required."]
 pub const vsl_encode_profile_VSL_ENCODE_PROFILE_AUTO: vsl_encode_profile = 0;
 #[doc = " 5 Mbps target bitrate.\n\n Suitable for moderate quality 1080p video or high quality 720p."]
@@ -84,6 +132,12 @@ pub use self::vsl_encode_profile as VSLEncoderProfile;
 pub const VSLDecoderCodec_VSL_DEC_H264: VSLDecoderCodec = 0;
 #[doc = " H.265/HEVC (High Efficiency Video Coding) codec.\n\n Next-generation standard (ISO/IEC 23008-2, ITU-T H.265) providing\n approximately 50% better compression than H.264 at equivalent quality.\n Recommended when bandwidth/storage are constrained and decoder support\n is confirmed."]
 pub const VSLDecoderCodec_VSL_DEC_HEVC: VSLDecoderCodec = 1;
+#[doc = " Motion JPEG, a sequence of independently coded JPEG images.\n\n Only decoded by V4L2 devices that report the MJPG format.\n @since 2.6"]
+pub const VSLDecoderCodec_VSL_DEC_MJPG: VSLDecoderCodec = 2;
+#[doc = " VP8 codec (RFC 6386).\n\n Only decoded by V4L2 devices that report the VP80 format.\n @since 2.6"]
+pub const VSLDecoderCodec_VSL_DEC_VP8: VSLDecoderCodec = 3;
+#[doc = " VP9 codec.\n\n Only decoded by V4L2 devices that report the VP90 format.\n @since 2.6"]
+pub const VSLDecoderCodec_VSL_DEC_VP9: VSLDecoderCodec = 4;
 #[doc = " Video codec type for hardware decoder.\n\n Specifies which video compression standard to use for decoding.\n Both codecs are supported via Hantro VPU hardware acceleration on i.MX8."]
 pub type VSLDecoderCodec = ::std::os::raw::c_uint;
 #[doc = " Auto-detect best available backend.\n\n Selection priority:\n 1. Check VSL_CODEC_BACKEND environment variable\n 2. Prefer V4L2 if device available and has M2M capability\n 3. Fall back to Hantro if V4L2 unavailable\n 4. Fail if no backend available"]
@@ -94,8 +148,83 @@ pub const VSLCodecBackend_VSL_CODEC_BACKEND_HANTRO: VSLCodecBackend = 1;
 pub const VSLCodecBackend_VSL_CODEC_BACKEND_V4L2: VSLCodecBackend = 2;
 #[doc = " Codec backend selection for encoder/decoder.\n\n Allows selection between V4L2 kernel driver and Hantro user-space\n library (libcodec.so) backends. Use with vsl_decoder_create_ex() and\n vsl_encoder_create_ex() for explicit backend control.\n\n The VSL_CODEC_BACKEND environment variable can override the AUTO selection:\n - \"hantro\" - Force Hantro backend even if V4L2 available\n - \"v4l2\"   - Force V4L2 backend (fail if unavailable)\n - \"auto\"   - Auto-detect (default)\n\n @since 2.0"]
 pub type VSLCodecBackend = ::std::os::raw::c_uint;
+#[doc = " No buffer has been allocated or attached."]
+pub const VSLFrameMemory_VSL_FRAME_MEMORY_NONE: VSLFrameMemory = 0;
+#[doc = " DmaBuf allocated from a DMA heap; CPU access requires vsl_frame_sync()."]
+pub const VSLFrameMemory_VSL_FRAME_MEMORY_DMABUF: VSLFrameMemory = 1;
+#[doc = " POSIX shared memory; coherent, vsl_frame_sync() is a no-op."]
+pub const VSLFrameMemory_VSL_FRAME_MEMORY_SHM: VSLFrameMemory = 2;
+#[doc = " Descriptor attached through vsl_frame_attach() or received from a\nhost; vsl_frame_sync() is a no-op."]
+pub const VSLFrameMemory_VSL_FRAME_MEMORY_EXTERNAL: VSLFrameMemory = 3;
+#[doc = " Memory backing a frame, as reported by vsl_frame_memory().\n\n @since 2.6"]
+pub type VSLFrameMemory = ::std::os::raw::c_uint;
+#[doc = " Constant bitrate: the encoder holds the target bitrate, varying\n quality with scene complexity."]
+pub const VSLEncoderRateControl_VSL_ENCODER_RC_CBR: VSLEncoderRateControl = 0;
+#[doc = " Variable bitrate: the encoder averages the target bitrate, spending\n more bits on complex scenes."]
+pub const VSLEncoderRateControl_VSL_ENCODER_RC_VBR: VSLEncoderRateControl = 1;
+#[doc = " Constant QP: frame level rate control is disabled and every frame is\n quantized with a fixed QP, ignoring the target bitrate."]
+pub const VSLEncoderRateControl_VSL_ENCODER_RC_CONSTANT_QP: VSLEncoderRateControl = 2;
+#[doc = " Encoder rate control mode, selected with vsl_encoder_set_rate_control().\n\n @since 2.6"]
+pub type VSLEncoderRateControl = ::std::os::raw::c_uint;
+#[doc = " Context-adaptive variable-length coding, the only mode of the\n Baseline profile. Cheaper to decode and supported by every decoder."]
+pub const VSLEncoderEntropyMode_VSL_ENCODER_ENTROPY_CAVLC: VSLEncoderEntropyMode = 0;
+#[doc = " Context-adaptive binary arithmetic coding, available from the Main\n profile. Typically 10-15% smaller at the same quality, at a higher\n decoding cost."]
+pub const VSLEncoderEntropyMode_VSL_ENCODER_ENTROPY_CABAC: VSLEncoderEntropyMode = 1;
+#[doc = " H.264 entropy coding mode, selected with vsl_encoder_set_entropy_mode().\n\n @since 2.6"]
+pub type VSLEncoderEntropyMode = ::std::os::raw::c_uint;
 #[doc = " Function pointer definition which will be called as part of\n @ref vsl_frame_unregister.  This is typically used to free resources\n associated with the frame on either client or host side."]
 pub type vsl_frame_cleanup = ::std::option::Option<unsafe extern "C" fn(frame: *mut VSLFrame)>;
+#[doc = " Function pointer definition for servicing client commands, registered with\n @ref vsl_host_set_command_handler.\n\n @param host The host instance\n @param request Request bytes sent by the client\n @param request_size Size of the request in bytes\n @param reply Buffer receiving the reply\n @param reply_capacity Capacity of the reply buffer (VSL_COMMAND_MAX)\n @param userptr Pointer given to vsl_host_set_command_handler()\n @return Size of the reply in bytes, or -1 to report failure to the client\n @since 2.6"]
+pub type vsl_host_command_handler = ::std::option::Option<
+    unsafe extern "C" fn(
+        host: *mut VSLHost,
+        request: *const ::std::os::raw::c_void,
+        request_size: usize,
+        reply: *mut ::std::os::raw::c_void,
+        reply_capacity: usize,
+        userptr: *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int,
+>;
+#[doc = " Layout of one plane of a planar or semi-planar frame, such as the luma\n and chroma planes of NV12.\n\n @since 2.6"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct vsl_frame_plane {
+    #[doc = " Byte offset of the plane within the frame's buffer, counted from the\n start of the buffer like vsl_frame_offset()."]
+    pub offset: usize,
+    #[doc = " Row stride of the plane in bytes."]
+    pub stride: ::std::os::raw::c_int,
+    #[doc = " Size of the plane in bytes."]
+    pub size: usize,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of vsl_frame_plane"][::std::mem::size_of::<vsl_frame_plane>() - 24usize];
+    ["Alignment of vsl_frame_plane"][::std::mem::align_of::<vsl_frame_plane>() - 8usize];
+    ["Offset of field: vsl_frame_plane::offset"][::std::mem::offset_of!(vsl_frame_plane, offset) - 0usize];
+    ["Offset of field: vsl_frame_plane::stride"][::std::mem::offset_of!(vsl_frame_plane, stride) - 8usize];
+    ["Offset of field: vsl_frame_plane::size"][::std::mem::offset_of!(vsl_frame_plane, size) - 16usize];
+};
+#[doc = " Layout of one plane of a planar or semi-planar frame, such as the luma\n and chroma planes of NV12.\n\n @since 2.6"]
+pub type VSLFramePlane = vsl_frame_plane;
+#[doc = " Region of interest with its own encoding quality.\n\n @since 2.6"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct vsl_encoder_roi {
+    #[doc = " Region in source frame coordinates."]
+    pub rect: VSLRect,
+    #[doc = " QP offset applied to macroblocks in the region, from -51 to 51.\n Negative values raise quality, positive values lower it."]
+    pub qp_delta: ::std::os::raw::c_int,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of vsl_encoder_roi"][::std::mem::size_of::<vsl_encoder_roi>() - 20usize];
+    ["Alignment of vsl_encoder_roi"][::std::mem::align_of::<vsl_encoder_roi>() - 4usize];
+    ["Offset of field: vsl_encoder_roi::rect"][::std::mem::offset_of!(vsl_encoder_roi, rect) - 0usize];
+    ["Offset of field: vsl_encoder_roi::qp_delta"]
+        [::std::mem::offset_of!(vsl_encoder_roi, qp_delta) - 16usize];
+};
+#[doc = " Region of interest with its own encoding quality.\n\n @since 2.6"]
+pub type VSLEncoderROI = vsl_encoder_roi;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct vsl_camera_buffer {
@@ -287,6 +416,123 @@ const _: () = {
     ["Offset of field: VSLDeviceList::count"]
         [::std::mem::offset_of!(VSLDeviceList, count) - 8usize];
 };
+#[doc = " @struct VSLMediaEntity\n @brief Entity in a media controller graph\n\n An entity is a hardware or software block in the pipeline such as a\n sensor, CSI receiver, ISI channel or video capture node. Entities that\n are exposed to userspace through a `/dev/video*` or `/dev/v4l-subdev*`\n node carry the resolved node path in @ref devnode.\n\n @since 2.6"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VSLMediaEntity {
+    #[doc = " Entity ID, unique within the media device"]
+    pub id: u32,
+    #[doc = " Entity name (e.g., \"mxc_isi.0\", \"csidev-4ad30000.csi\")"]
+    pub name: [::std::os::raw::c_char; 64usize],
+    #[doc = " Entity function (`MEDIA_ENT_F_*`)"]
+    pub function: u32,
+    #[doc = " Entity flags (`MEDIA_ENT_FL_*`)"]
+    pub flags: u32,
+    #[doc = " Device node path (e.g., \"/dev/video0\"), empty if the entity has none"]
+    pub devnode: [::std::os::raw::c_char; 64usize],
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of VSLMediaEntity"][::std::mem::size_of::<VSLMediaEntity>() - 140usize];
+    ["Alignment of VSLMediaEntity"][::std::mem::align_of::<VSLMediaEntity>() - 4usize];
+    ["Offset of field: VSLMediaEntity::id"][::std::mem::offset_of!(VSLMediaEntity, id) - 0usize];
+    ["Offset of field: VSLMediaEntity::name"]
+        [::std::mem::offset_of!(VSLMediaEntity, name) - 4usize];
+    ["Offset of field: VSLMediaEntity::function"]
+        [::std::mem::offset_of!(VSLMediaEntity, function) - 68usize];
+    ["Offset of field: VSLMediaEntity::flags"]
+        [::std::mem::offset_of!(VSLMediaEntity, flags) - 72usize];
+    ["Offset of field: VSLMediaEntity::devnode"]
+        [::std::mem::offset_of!(VSLMediaEntity, devnode) - 76usize];
+};
+#[doc = " @struct VSLMediaLink\n @brief Data link between two entity pads\n\n @since 2.6"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VSLMediaLink {
+    #[doc = " Source entity ID"]
+    pub source_entity: u32,
+    #[doc = " Source pad index on the source entity"]
+    pub source_pad: u32,
+    #[doc = " Sink entity ID"]
+    pub sink_entity: u32,
+    #[doc = " Sink pad index on the sink entity"]
+    pub sink_pad: u32,
+    #[doc = " Link flags (`MEDIA_LNK_FL_ENABLED`, `MEDIA_LNK_FL_IMMUTABLE`)"]
+    pub flags: u32,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of VSLMediaLink"][::std::mem::size_of::<VSLMediaLink>() - 20usize];
+    ["Alignment of VSLMediaLink"][::std::mem::align_of::<VSLMediaLink>() - 4usize];
+    ["Offset of field: VSLMediaLink::source_entity"]
+        [::std::mem::offset_of!(VSLMediaLink, source_entity) - 0usize];
+    ["Offset of field: VSLMediaLink::source_pad"]
+        [::std::mem::offset_of!(VSLMediaLink, source_pad) - 4usize];
+    ["Offset of field: VSLMediaLink::sink_entity"]
+        [::std::mem::offset_of!(VSLMediaLink, sink_entity) - 8usize];
+    ["Offset of field: VSLMediaLink::sink_pad"]
+        [::std::mem::offset_of!(VSLMediaLink, sink_pad) - 12usize];
+    ["Offset of field: VSLMediaLink::flags"][::std::mem::offset_of!(VSLMediaLink, flags) - 16usize];
+};
+#[doc = " @struct VSLMediaDevice\n @brief Media controller device and its topology\n\n @note This structure is allocated by vsl_media_enumerate() and must be\n       freed with vsl_media_device_list_free().\n\n @since 2.6"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VSLMediaDevice {
+    #[doc = " Device path (e.g., \"/dev/media0\")"]
+    pub path: [::std::os::raw::c_char; 64usize],
+    #[doc = " Driver name (e.g., \"mxc-isi\")"]
+    pub driver: [::std::os::raw::c_char; 16usize],
+    #[doc = " Device model (e.g., \"FSL Capture Media Device\")"]
+    pub model: [::std::os::raw::c_char; 32usize],
+    #[doc = " Bus info (e.g., \"platform:4ad50000.isi\")"]
+    pub bus_info: [::std::os::raw::c_char; 32usize],
+    #[doc = " Array of entities in the graph"]
+    pub entities: *mut VSLMediaEntity,
+    #[doc = " Number of valid entries in entities"]
+    pub num_entities: usize,
+    #[doc = " Array of data links between entity pads"]
+    pub links: *mut VSLMediaLink,
+    #[doc = " Number of valid entries in links"]
+    pub num_links: usize,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of VSLMediaDevice"][::std::mem::size_of::<VSLMediaDevice>() - 176usize];
+    ["Alignment of VSLMediaDevice"][::std::mem::align_of::<VSLMediaDevice>() - 8usize];
+    ["Offset of field: VSLMediaDevice::path"][::std::mem::offset_of!(VSLMediaDevice, path) - 0usize];
+    ["Offset of field: VSLMediaDevice::driver"]
+        [::std::mem::offset_of!(VSLMediaDevice, driver) - 64usize];
+    ["Offset of field: VSLMediaDevice::model"]
+        [::std::mem::offset_of!(VSLMediaDevice, model) - 80usize];
+    ["Offset of field: VSLMediaDevice::bus_info"]
+        [::std::mem::offset_of!(VSLMediaDevice, bus_info) - 112usize];
+    ["Offset of field: VSLMediaDevice::entities"]
+        [::std::mem::offset_of!(VSLMediaDevice, entities) - 144usize];
+    ["Offset of field: VSLMediaDevice::num_entities"]
+        [::std::mem::offset_of!(VSLMediaDevice, num_entities) - 152usize];
+    ["Offset of field: VSLMediaDevice::links"]
+        [::std::mem::offset_of!(VSLMediaDevice, links) - 160usize];
+    ["Offset of field: VSLMediaDevice::num_links"]
+        [::std::mem::offset_of!(VSLMediaDevice, num_links) - 168usize];
+};
+#[doc = " @struct VSLMediaDeviceList\n @brief List of discovered media controller devices\n\n Returned by vsl_media_enumerate(). Must be freed with\n vsl_media_device_list_free().\n\n @since 2.6"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VSLMediaDeviceList {
+    #[doc = " Array of media device descriptors"]
+    pub devices: *mut VSLMediaDevice,
+    #[doc = " Number of devices in the array"]
+    pub count: usize,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of VSLMediaDeviceList"][::std::mem::size_of::<VSLMediaDeviceList>() - 16usize];
+    ["Alignment of VSLMediaDeviceList"][::std::mem::align_of::<VSLMediaDeviceList>() - 8usize];
+    ["Offset of field: VSLMediaDeviceList::devices"]
+        [::std::mem::offset_of!(VSLMediaDeviceList, devices) - 0usize];
+    ["Offset of field: VSLMediaDeviceList::count"]
+        [::std::mem::offset_of!(VSLMediaDeviceList, count) - 8usize];
+};
 pub struct VideoStreamLibrary {
     __library: ::libloading::Library,
     pub vsl_version:
@@ -748,6 +994,51 @@ pub struct VideoStreamLibrary {
         ) -> *mut ::std::os::raw::c_char,
         ::libloading::Error,
     >,
+    pub vsl_media_enumerate:
+        Result<unsafe extern "C" fn() -> *mut VSLMediaDeviceList, ::libloading::Error>,
+    pub vsl_media_device_list_free:
+        Result<unsafe extern "C" fn(list: *mut VSLMediaDeviceList), ::libloading::Error>,
+    pub vsl_frame_capture_time: Result<unsafe extern "C" fn(frame: *const VSLFrame) -> i64, ::libloading::Error>,
+    pub vsl_frame_set_capture_time: Result<unsafe extern "C" fn(frame: *mut VSLFrame, capture_time: i64), ::libloading::Error>,
+    pub vsl_client_peek: Result<unsafe extern "C" fn(client: *mut VSLClient, meta: *mut VSLFrameMeta) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_host_set_command_handler: Result<unsafe extern "C" fn(host: *mut VSLHost, handler: vsl_host_command_handler, userptr: *mut ::std::os::raw::c_void) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_client_send_command: Result<unsafe extern "C" fn(client: *mut VSLClient, request: *const ::std::os::raw::c_void, request_size: usize, reply: *mut ::std::os::raw::c_void, reply_capacity: usize) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_frame_rotate: Result<unsafe extern "C" fn(target: *mut VSLFrame, source: *mut VSLFrame, degrees: ::std::os::raw::c_int) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_client_fd: Result<unsafe extern "C" fn(client: *mut VSLClient) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_camera_set_selection: Result<unsafe extern "C" fn(ctx: *const vsl_camera, rect: *mut VSLRect) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_camera_get_selection: Result<unsafe extern "C" fn(ctx: *const vsl_camera, rect: *mut VSLRect) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_encoder_set_roi: Result<unsafe extern "C" fn(encoder: *mut VSLEncoder, regions: *const VSLEncoderROI, count: ::std::os::raw::c_int) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_frame_offset: Result<unsafe extern "C" fn(frame: *const VSLFrame) -> usize, ::libloading::Error>,
+    pub vsl_frame_memory: Result<unsafe extern "C" fn(frame: *const VSLFrame) -> VSLFrameMemory, ::libloading::Error>,
+    pub vsl_camera_set_field: Result<unsafe extern "C" fn(ctx: *mut vsl_camera, field: u32) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_camera_field: Result<unsafe extern "C" fn(ctx: *const vsl_camera) -> u32, ::libloading::Error>,
+    pub vsl_encoder_set_bitrate: Result<unsafe extern "C" fn(encoder: *mut VSLEncoder, kbps: u32) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_decoder_set_output_fourcc: Result<unsafe extern "C" fn(decoder: *mut VSLDecoder, fourcc: u32) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_host_drop_stats: Result<unsafe extern "C" fn(host: *mut VSLHost, stats: *mut VSLHostDropStats) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_frame_keyframe: Result<unsafe extern "C" fn(frame: *const VSLFrame) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_frame_set_keyframe: Result<unsafe extern "C" fn(frame: *mut VSLFrame, keyframe: ::std::os::raw::c_int), ::libloading::Error>,
+    pub vsl_encoder_mark_ltr: Result<unsafe extern "C" fn(encoder: *mut VSLEncoder) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_encoder_use_ltr: Result<unsafe extern "C" fn(encoder: *mut VSLEncoder) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_decoder_set_output_buffers: Result<unsafe extern "C" fn(decoder: *mut VSLDecoder, count: ::std::os::raw::c_int) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_decoder_set_low_latency: Result<unsafe extern "C" fn(decoder: *mut VSLDecoder, enable: ::std::os::raw::c_int) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_encoder_flush: Result<unsafe extern "C" fn(encoder: *mut VSLEncoder, destination: *mut VSLFrame, keyframe: *mut ::std::os::raw::c_int) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_frame_display_rect: Result<unsafe extern "C" fn(frame: *const VSLFrame, rect: *mut VSLRect) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_frame_set_display_rect: Result<unsafe extern "C" fn(frame: *mut VSLFrame, rect: *const VSLRect) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_client_reconnects: Result<unsafe extern "C" fn(client: *mut VSLClient) -> i64, ::libloading::Error>,
+    pub vsl_encoder_set_gop_size: Result<unsafe extern "C" fn(encoder: *mut VSLEncoder, frames: u32) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_encoder_gop_size: Result<unsafe extern "C" fn(encoder: *const VSLEncoder) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_frame_used_size: Result<unsafe extern "C" fn(frame: *const VSLFrame) -> usize, ::libloading::Error>,
+    pub vsl_frame_set_used_size: Result<unsafe extern "C" fn(frame: *mut VSLFrame, used: usize) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_camera_set_memory: Result<unsafe extern "C" fn(ctx: *mut vsl_camera, memory: u32) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_encoder_set_rate_control: Result<unsafe extern "C" fn(encoder: *mut VSLEncoder, mode: VSLEncoderRateControl, qp: ::std::os::raw::c_int) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_encoder_set_entropy_mode: Result<unsafe extern "C" fn(encoder: *mut VSLEncoder, mode: VSLEncoderEntropyMode) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_frame_set_planes: Result<unsafe extern "C" fn(frame: *mut VSLFrame, planes: *const VSLFramePlane, count: ::std::os::raw::c_int) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_frame_planes: Result<unsafe extern "C" fn(frame: *const VSLFrame, planes: *mut VSLFramePlane, max: ::std::os::raw::c_int) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_encoder_create_device: Result<unsafe extern "C" fn(device: *const ::std::os::raw::c_char, profile: VSLEncoderProfile, outputFourcc: u32, fps: ::std::os::raw::c_int) -> *mut VSLEncoder, ::libloading::Error>,
+    pub vsl_frame_source_id: Result<unsafe extern "C" fn(frame: *const VSLFrame, id: *mut u32) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_frame_set_source_id: Result<unsafe extern "C" fn(frame: *mut VSLFrame, id: u32), ::libloading::Error>,
+    pub vsl_camera_get_format: Result<unsafe extern "C" fn(ctx: *const vsl_camera, width: *mut ::std::os::raw::c_int, height: *mut ::std::os::raw::c_int, fourcc: *mut u32) -> ::std::os::raw::c_int, ::libloading::Error>,
+    pub vsl_client_discarded: Result<unsafe extern "C" fn(client: *mut VSLClient) -> i64, ::libloading::Error>,
 }
 impl VideoStreamLibrary {
     pub unsafe fn new<P>(path: P) -> Result<Self, ::libloading::Error>
@@ -909,6 +1200,51 @@ impl VideoStreamLibrary {
         let vsl_v4l2_fourcc_to_string = __library
             .get(b"vsl_v4l2_fourcc_to_string\0")
             .map(|sym| *sym);
+        let vsl_media_enumerate = __library.get(b"vsl_media_enumerate\0").map(|sym| *sym);
+        let vsl_media_device_list_free = __library
+            .get(b"vsl_media_device_list_free\0")
+            .map(|sym| *sym);
+        let vsl_frame_capture_time = __library.get(b"vsl_frame_capture_time\0").map(|sym| *sym);
+        let vsl_frame_set_capture_time = __library.get(b"vsl_frame_set_capture_time\0").map(|sym| *sym);
+        let vsl_client_peek = __library.get(b"vsl_client_peek\0").map(|sym| *sym);
+        let vsl_host_set_command_handler = __library.get(b"vsl_host_set_command_handler\0").map(|sym| *sym);
+        let vsl_client_send_command = __library.get(b"vsl_client_send_command\0").map(|sym| *sym);
+        let vsl_frame_rotate = __library.get(b"vsl_frame_rotate\0").map(|sym| *sym);
+        let vsl_client_fd = __library.get(b"vsl_client_fd\0").map(|sym| *sym);
+        let vsl_camera_set_selection = __library.get(b"vsl_camera_set_selection\0").map(|sym| *sym);
+        let vsl_camera_get_selection = __library.get(b"vsl_camera_get_selection\0").map(|sym| *sym);
+        let vsl_encoder_set_roi = __library.get(b"vsl_encoder_set_roi\0").map(|sym| *sym);
+        let vsl_frame_offset = __library.get(b"vsl_frame_offset\0").map(|sym| *sym);
+        let vsl_frame_memory = __library.get(b"vsl_frame_memory\0").map(|sym| *sym);
+        let vsl_camera_set_field = __library.get(b"vsl_camera_set_field\0").map(|sym| *sym);
+        let vsl_camera_field = __library.get(b"vsl_camera_field\0").map(|sym| *sym);
+        let vsl_encoder_set_bitrate = __library.get(b"vsl_encoder_set_bitrate\0").map(|sym| *sym);
+        let vsl_decoder_set_output_fourcc = __library.get(b"vsl_decoder_set_output_fourcc\0").map(|sym| *sym);
+        let vsl_host_drop_stats = __library.get(b"vsl_host_drop_stats\0").map(|sym| *sym);
+        let vsl_frame_keyframe = __library.get(b"vsl_frame_keyframe\0").map(|sym| *sym);
+        let vsl_frame_set_keyframe = __library.get(b"vsl_frame_set_keyframe\0").map(|sym| *sym);
+        let vsl_encoder_mark_ltr = __library.get(b"vsl_encoder_mark_ltr\0").map(|sym| *sym);
+        let vsl_encoder_use_ltr = __library.get(b"vsl_encoder_use_ltr\0").map(|sym| *sym);
+        let vsl_decoder_set_output_buffers = __library.get(b"vsl_decoder_set_output_buffers\0").map(|sym| *sym);
+        let vsl_decoder_set_low_latency = __library.get(b"vsl_decoder_set_low_latency\0").map(|sym| *sym);
+        let vsl_encoder_flush = __library.get(b"vsl_encoder_flush\0").map(|sym| *sym);
+        let vsl_frame_display_rect = __library.get(b"vsl_frame_display_rect\0").map(|sym| *sym);
+        let vsl_frame_set_display_rect = __library.get(b"vsl_frame_set_display_rect\0").map(|sym| *sym);
+        let vsl_client_reconnects = __library.get(b"vsl_client_reconnects\0").map(|sym| *sym);
+        let vsl_encoder_set_gop_size = __library.get(b"vsl_encoder_set_gop_size\0").map(|sym| *sym);
+        let vsl_encoder_gop_size = __library.get(b"vsl_encoder_gop_size\0").map(|sym| *sym);
+        let vsl_frame_used_size = __library.get(b"vsl_frame_used_size\0").map(|sym| *sym);
+        let vsl_frame_set_used_size = __library.get(b"vsl_frame_set_used_size\0").map(|sym| *sym);
+        let vsl_camera_set_memory = __library.get(b"vsl_camera_set_memory\0").map(|sym| *sym);
+        let vsl_encoder_set_rate_control = __library.get(b"vsl_encoder_set_rate_control\0").map(|sym| *sym);
+        let vsl_encoder_set_entropy_mode = __library.get(b"vsl_encoder_set_entropy_mode\0").map(|sym| *sym);
+        let vsl_frame_set_planes = __library.get(b"vsl_frame_set_planes\0").map(|sym| *sym);
+        let vsl_frame_planes = __library.get(b"vsl_frame_planes\0").map(|sym| *sym);
+        let vsl_encoder_create_device = __library.get(b"vsl_encoder_create_device\0").map(|sym| *sym);
+        let vsl_frame_source_id = __library.get(b"vsl_frame_source_id\0").map(|sym| *sym);
+        let vsl_frame_set_source_id = __library.get(b"vsl_frame_set_source_id\0").map(|sym| *sym);
+        let vsl_camera_get_format = __library.get(b"vsl_camera_get_format\0").map(|sym| *sym);
+        let vsl_client_discarded = __library.get(b"vsl_client_discarded\0").map(|sym| *sym);
         Ok(VideoStreamLibrary {
             __library,
             vsl_version,
@@ -1012,6 +1348,49 @@ impl VideoStreamLibrary {
             vsl_v4l2_device_type_name,
             vsl_v4l2_is_compressed_format,
             vsl_v4l2_fourcc_to_string,
+            vsl_media_enumerate,
+            vsl_media_device_list_free,
+            vsl_frame_capture_time,
+            vsl_frame_set_capture_time,
+            vsl_client_peek,
+            vsl_host_set_command_handler,
+            vsl_client_send_command,
+            vsl_frame_rotate,
+            vsl_client_fd,
+            vsl_camera_set_selection,
+            vsl_camera_get_selection,
+            vsl_encoder_set_roi,
+            vsl_frame_offset,
+            vsl_frame_memory,
+            vsl_camera_set_field,
+            vsl_camera_field,
+            vsl_encoder_set_bitrate,
+            vsl_decoder_set_output_fourcc,
+            vsl_host_drop_stats,
+            vsl_frame_keyframe,
+            vsl_frame_set_keyframe,
+            vsl_encoder_mark_ltr,
+            vsl_encoder_use_ltr,
+            vsl_decoder_set_output_buffers,
+            vsl_decoder_set_low_latency,
+            vsl_encoder_flush,
+            vsl_frame_display_rect,
+            vsl_frame_set_display_rect,
+            vsl_client_reconnects,
+            vsl_encoder_set_gop_size,
+            vsl_encoder_gop_size,
+            vsl_frame_used_size,
+            vsl_frame_set_used_size,
+            vsl_camera_set_memory,
+            vsl_encoder_set_rate_control,
+            vsl_encoder_set_entropy_mode,
+            vsl_frame_set_planes,
+            vsl_frame_planes,
+            vsl_encoder_create_device,
+            vsl_frame_source_id,
+            vsl_frame_set_source_id,
+            vsl_camera_get_format,
+            vsl_client_discarded,
         })
     }
     #[doc = " Returns the VideoStream Library version string.\n\n @return Version string in \"MAJOR.MINOR.PATCH\" format (e.g., \"1.5.4\")\n @since 1.0"]
@@ -1028,7 +1407,7 @@ impl VideoStreamLibrary {
             .as_ref()
             .expect("Expected function, got error."))()
     }
-    #[doc = " Creates a host on the requested path for inter-process frame sharing.\n\n The host manages a UNIX domain socket at the specified path and accepts\n connections from clients. Frames posted to the host are broadcast to all\n connected clients.\n\n @param path UNIX socket path (filesystem or abstract). If path starts with\n             '/' it creates a filesystem socket, otherwise an abstract socket.\n @return Pointer to VSLHost object on success, NULL on failure (sets errno)\n @since 1.0\n @memberof VSLHost"]
+    #[doc = " Creates a host on the requested path for inter-process frame sharing.\n\n The host manages a UNIX domain socket at the specified path and accepts\n connections from clients. Frames posted to the host are broadcast to all\n connected clients.\n\n A path starting with '@' names a socket in the Linux abstract namespace:\n the '@' stands for the leading NUL byte of the address, and the socket has\n no filesystem entry, so it leaves no stale file and needs no shared\n directory, but is only reachable from the same network namespace. Other\n paths create a socket file, relative to the working directory unless they\n start with '/'.\n\n @param path UNIX socket path, or '@' followed by an abstract socket name\n @return Pointer to VSLHost object on success, NULL on failure (sets errno)\n @since 1.0\n @memberof VSLHost"]
     pub unsafe fn vsl_host_init(&self, path: *const ::std::os::raw::c_char) -> *mut VSLHost {
         (self
             .vsl_host_init
@@ -1115,7 +1494,7 @@ impl VideoStreamLibrary {
             .as_ref()
             .expect("Expected function, got error."))(host, frame)
     }
-    #[doc = " Creates a client and connects to the host at the provided path.\n\n Establishes a connection to a VSLHost via UNIX domain socket. The client\n can receive frames broadcast by the host.\n\n @param path UNIX socket path matching the host's path\n @param userptr Optional user data pointer (retrievable via\n vsl_client_userptr)\n @param reconnect If true, automatically reconnect if connection is lost\n @return Pointer to VSLClient object on success, NULL on failure (sets errno)\n @since 1.0\n @memberof VSLClient"]
+    #[doc = " Creates a client and connects to the host at the provided path.\n\n Establishes a connection to a VSLHost via UNIX domain socket. The client\n can receive frames broadcast by the host.\n\n @param path UNIX socket path matching the host's path, including the\n             leading '@' of an abstract socket (see vsl_host_init())\n @param userptr Optional user data pointer (retrievable via\n vsl_client_userptr)\n @param reconnect If true, automatically reconnect if connection is lost\n @return Pointer to VSLClient object on success, NULL on failure (sets errno)\n @since 1.0\n @memberof VSLClient"]
     pub unsafe fn vsl_client_init(
         &self,
         path: *const ::std::os::raw::c_char,
@@ -1374,7 +1753,7 @@ impl VideoStreamLibrary {
             .as_ref()
             .expect("Expected function, got error."))(frame)
     }
-    #[doc = " Returns the stride in bytes of the video frame.\n\n Stride is the number of bytes from the start of one row to the next.\n May be larger than width*bytes_per_pixel due to alignment requirements.\n\n @param frame The frame instance\n @return Row stride in bytes\n @since 1.3\n @memberof VSLFrame"]
+    #[doc = " Returns the stride in bytes of the video frame.\n\n Stride is the number of bytes from the start of one row to the next.\n May be larger than width*bytes_per_pixel due to alignment requirements.\n\n For planar and semi-planar YUV formats such as NV12 the stride is the\n luma row stride plus half of it, width*3/2 for tightly packed planes.\n Use vsl_frame_planes() for the stride of each plane.\n\n @param frame The frame instance\n @return Row stride in bytes\n @since 1.3\n @memberof VSLFrame"]
     pub unsafe fn vsl_frame_stride(&self, frame: *const VSLFrame) -> ::std::os::raw::c_int {
         (self
             .vsl_frame_stride
@@ -1952,4 +2331,182 @@ impl VideoStreamLibrary {
             .as_ref()
             .expect("Expected function, got error."))(fourcc, buf)
     }
+    #[doc = " @brief Enumerates media controller devices and their topology\n\n Scans `/dev/media*` and reads each device's graph using\n `MEDIA_IOC_DEVICE_INFO` and `MEDIA_IOC_G_TOPOLOGY`. Entities exposed\n through a device node are associated with their `/dev` path, so the\n `/dev/video*` nodes returned by vsl_v4l2_enumerate() can be located in\n the camera pipeline.\n\n @return Media device list on success (caller must free with\n vsl_media_device_list_free()), or NULL on error\n @retval NULL Failed to enumerate (check errno)\n @retval errno=ENOMEM Out of memory\n @retval errno=ENOTSUP Media controller API not available\n\n @note Devices that are busy or inaccessible are silently skipped.\n @note The returned list may be empty if no media devices are found.\n\n @see vsl_media_device_list_free\n @since 2.6"]
+    pub unsafe fn vsl_media_enumerate(&self) -> *mut VSLMediaDeviceList {
+        (self
+            .vsl_media_enumerate
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    #[doc = " @brief Frees a media device list returned by vsl_media_enumerate()\n\n @param[in] list Media device list to free (NULL is safe and does nothing)\n\n @since 2.6"]
+    pub unsafe fn vsl_media_device_list_free(&self, list: *mut VSLMediaDeviceList) {
+        (self
+            .vsl_media_device_list_free
+            .as_ref()
+            .expect("Expected function, got error."))(list)
+    }
+    #[doc = " Returns the capture timestamp for this frame in nanoseconds.\n\n The capture time is carried with the frame from host to client so\n consumers can measure end-to-end latency as\n vsl_frame_timestamp() - vsl_frame_capture_time(). It uses the same\n CLOCK_MONOTONIC time base as vsl_timestamp().\n\n @param frame The frame instance\n @return Capture timestamp in nanoseconds, or 0 if not set\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_capture_time(&self, frame: *const VSLFrame) -> i64 {
+        (self.vsl_frame_capture_time.as_ref().expect("Expected function, got error."))(frame)
+    }
+    #[doc = " Sets the capture timestamp for this frame in nanoseconds.\n\n Must be set before the frame is posted with vsl_host_post() for clients\n to receive it.\n\n @param frame The frame instance\n @param capture_time Capture timestamp in nanoseconds (CLOCK_MONOTONIC),\n                     or 0 to clear\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_set_capture_time(&self, frame: *mut VSLFrame, capture_time: i64) {
+        (self.vsl_frame_set_capture_time.as_ref().expect("Expected function, got error."))(frame, capture_time)
+    }
+    #[doc = " Reads the metadata of the next queued frame without dequeuing it.\n\n Does not block. Control messages and expired frames at the head of the\n queue are discarded, and a connection closed by the host is closed here\n too. A disconnected client created with reconnect enabled first makes one\n attempt to reconnect. The next call to vsl_frame_wait() with until=0\n returns the peeked frame. The frame is not locked, so the host may still\n expire it before it is received.\n\n @param client The client instance\n @param meta Output for the pending frame's metadata\n @return 1 if a frame is pending, 0 if none is queued or the client is\n         disconnected, -1 on error (sets errno)\n @since 2.6\n @memberof VSLClient"]
+    pub unsafe fn vsl_client_peek(&self, client: *mut VSLClient, meta: *mut VSLFrameMeta) -> ::std::os::raw::c_int {
+        (self.vsl_client_peek.as_ref().expect("Expected function, got error."))(client, meta)
+    }
+    #[doc = " Registers the handler for commands sent by clients.\n\n Commands arrive on the same socket as frame lock requests and are serviced\n by vsl_host_service() and vsl_host_process(), which call the handler with\n the host lock held, so the handler must not call other vsl_host functions.\n Without a handler clients receive ENOTSUP.\n\n @param host The host instance\n @param handler Command handler, or NULL to remove the current handler\n @param userptr Pointer passed to each handler call\n @return 0 on success, -1 on error (sets errno)\n @since 2.6\n @memberof VSLHost"]
+    pub unsafe fn vsl_host_set_command_handler(&self, host: *mut VSLHost, handler: vsl_host_command_handler, userptr: *mut ::std::os::raw::c_void) -> ::std::os::raw::c_int {
+        (self.vsl_host_set_command_handler.as_ref().expect("Expected function, got error."))(host, handler, userptr)
+    }
+    #[doc = " Sends a command to the host and waits for its reply.\n\n The request is serviced by the handler registered with\n vsl_host_set_command_handler(). Frames arriving while waiting for the reply\n are discarded, as with vsl_frame_trylock().\n\n @param client The client instance\n @param request Request bytes, at most VSL_COMMAND_MAX\n @param request_size Size of the request in bytes\n @param reply Buffer receiving the reply, truncated to reply_capacity\n @param reply_capacity Capacity of the reply buffer\n @return Size of the host's reply in bytes, or -1 on error (sets errno:\n         EMSGSIZE if the request is too large, ENOTSUP if the host has no\n         handler, EIO if the handler failed, ETIMEDOUT if the host did not\n         reply within the timeout set by vsl_client_set_timeout(), ENOTCONN\n         if disconnected)\n @since 2.6\n @memberof VSLClient"]
+    pub unsafe fn vsl_client_send_command(&self, client: *mut VSLClient, request: *const ::std::os::raw::c_void, request_size: usize, reply: *mut ::std::os::raw::c_void, reply_capacity: usize) -> ::std::os::raw::c_int {
+        (self.vsl_client_send_command.as_ref().expect("Expected function, got error."))(client, request, request_size, reply, reply_capacity)
+    }
+    #[doc = " Rotates the source frame clockwise into the target frame using G2D.\n\n Both frames must share the pixel format. The target must be the source\n size for 180 degrees and have width and height swapped for 90 and 270\n degrees. Only DmaBuf frames with physical addresses can be rotated; other\n frames, or systems without G2D, fail with ENOTSUP so callers can fall back\n to a software rotation.\n\n @param target Destination frame (receives rotated data)\n @param source Source frame to rotate\n @param degrees Clockwise rotation: 90, 180 or 270\n @return 0 on success, -1 on failure (sets errno: EINVAL for invalid\n         rotation or mismatched frames, ENOTSUP if G2D cannot rotate them)\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_rotate(&self, target: *mut VSLFrame, source: *mut VSLFrame, degrees: ::std::os::raw::c_int) -> ::std::os::raw::c_int {
+        (self.vsl_frame_rotate.as_ref().expect("Expected function, got error."))(target, source, degrees)
+    }
+    #[doc = " Returns the socket connecting the client to the host.\n\n The socket becomes readable when the host sends a frame, so it can be\n polled alongside other descriptors before calling vsl_client_peek() or\n vsl_frame_wait(). The descriptor changes when the client reconnects and\n remains owned by the client.\n\n @param client The client instance\n @return Socket file descriptor, or -1 if disconnected (sets errno ENOTCONN)\n @since 2.6\n @memberof VSLClient"]
+    pub unsafe fn vsl_client_fd(&self, client: *mut VSLClient) -> ::std::os::raw::c_int {
+        (self.vsl_client_fd.as_ref().expect("Expected function, got error."))(client)
+    }
+    #[doc = " Sets the sensor crop rectangle (region of interest) for capture.\n\n Uses VIDIOC_S_SELECTION with the V4L2_SEL_TGT_CROP target. The rectangle\n is validated against the V4L2_SEL_TGT_CROP_BOUNDS reported by the driver,\n which may further adjust it to hardware constraints; on success @p rect\n holds the rectangle actually applied. Depending on the driver the capture\n format may change to the cropped size or the crop may be scaled back to\n the configured format; the stride of the camera buffers is refreshed and\n vsl_camera_get_format() reports the resulting size.\n\n @param ctx Camera context\n @param rect Crop rectangle in sensor coordinates, updated with the\n             rectangle applied by the driver\n @return 0 on success, -1 on failure (sets errno: EINVAL if the rectangle\n         is empty or outside the crop bounds, ENOTTY if the driver does not\n         support cropping)\n @since 2.6\n @memberof VSLCamera"]
+    pub unsafe fn vsl_camera_set_selection(&self, ctx: *const vsl_camera, rect: *mut VSLRect) -> ::std::os::raw::c_int {
+        (self.vsl_camera_set_selection.as_ref().expect("Expected function, got error."))(ctx, rect)
+    }
+    #[doc = " Returns the current sensor crop rectangle.\n\n Uses VIDIOC_G_SELECTION with the V4L2_SEL_TGT_CROP target.\n\n @param ctx Camera context\n @param rect Receives the crop rectangle in sensor coordinates\n @return 0 on success, -1 on failure (sets errno: ENOTTY if the driver does\n         not support cropping)\n @since 2.6\n @memberof VSLCamera"]
+    pub unsafe fn vsl_camera_get_selection(&self, ctx: *const vsl_camera, rect: *mut VSLRect) -> ::std::os::raw::c_int {
+        (self.vsl_camera_get_selection.as_ref().expect("Expected function, got error."))(ctx, rect)
+    }
+    #[doc = " @brief Sets encoder regions of interest\n\n Encodes the given regions with their QP offset relative to the rest of the\n frame, so a region can be kept sharp (negative delta) while the bitrate is\n spent elsewhere, or degraded (positive delta). Regions replace any\n previously set and apply to subsequent frames; pass a count of 0 to clear\n them. The encoder may align regions to its macroblock grid.\n\n ROI encoding is available on the V4L2 backend when the driver exposes the\n i.MX VPU ROI control (vsi_v4l2 on i.MX 8M Plus). Other backends and drivers\n fail with ENOTSUP.\n\n @param encoder VSLEncoder instance\n @param regions Array of @p count regions (may be NULL when @p count is 0)\n @param count Number of regions, at most VSL_ENCODER_MAX_ROI\n @retval 0 on success\n @retval -1 on failure (sets errno: EINVAL for an invalid region or count,\n         ENOTSUP if the backend does not support ROI encoding)\n @since 2.6"]
+    pub unsafe fn vsl_encoder_set_roi(&self, encoder: *mut VSLEncoder, regions: *const VSLEncoderROI, count: ::std::os::raw::c_int) -> ::std::os::raw::c_int {
+        (self.vsl_encoder_set_roi.as_ref().expect("Expected function, got error."))(encoder, regions, count)
+    }
+    #[doc = " Returns the byte offset of the frame data within its buffer.\n\n Non-zero only for frames attached with an offset through\n vsl_frame_attach().\n\n @param frame The frame instance\n @return Offset in bytes, or 0 if @p frame is NULL\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_offset(&self, frame: *const VSLFrame) -> usize {
+        (self.vsl_frame_offset.as_ref().expect("Expected function, got error."))(frame)
+    }
+    #[doc = " Returns the kind of memory backing the frame.\n\n @param frame The frame instance\n @return The frame memory type, VSL_FRAME_MEMORY_NONE if @p frame is NULL\n         or has no buffer\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_memory(&self, frame: *const VSLFrame) -> VSLFrameMemory {
+        (self.vsl_frame_memory.as_ref().expect("Expected function, got error."))(frame)
+    }
+    #[doc = " Requests the field order used when the capture format is negotiated.\n\n Sets the `field` member passed to VIDIOC_S_FMT by the next\n vsl_camera_init_device() call, so must be called before it. The value is\n a raw V4L2 `V4L2_FIELD_*` constant. The default `V4L2_FIELD_ANY` (0) lets\n the driver choose.\n\n @param ctx Camera context from vsl_camera_open_device()\n @param field Requested `V4L2_FIELD_*` value\n @return 0 on success, -1 on failure (sets errno: EINVAL)\n @since 2.6"]
+    pub unsafe fn vsl_camera_set_field(&self, ctx: *mut vsl_camera, field: u32) -> ::std::os::raw::c_int {
+        (self.vsl_camera_set_field.as_ref().expect("Expected function, got error."))(ctx, field)
+    }
+    #[doc = " Returns the field order negotiated for the camera.\n\n @param ctx Camera context\n @return `V4L2_FIELD_*` enum value, or 0 (`V4L2_FIELD_ANY`) if `ctx` is\n         NULL or the device has not been initialized.\n @since 2.6"]
+    pub unsafe fn vsl_camera_field(&self, ctx: *const vsl_camera) -> u32 {
+        (self.vsl_camera_field.as_ref().expect("Expected function, got error."))(ctx)
+    }
+    #[doc = " @brief Changes the encoder target bitrate\n\n Overrides the bitrate selected by the encoder profile. Once the encoder is\n streaming the new target applies to subsequent frames without a keyframe\n or stream restart; before the first frame it replaces the profile bitrate.\n The encoder's rate control converges on the new target over a few frames.\n\n Runtime bitrate changes are available on the V4L2 backend. The Hantro\n backend fails with ENOTSUP.\n\n @param encoder VSLEncoder instance\n @param kbps Target bitrate in kilobits per second, from 1 to 2147483\n @retval 0 on success\n @retval -1 on failure (sets errno: EINVAL for a NULL encoder or an out of\n         range bitrate, ENOTSUP if the backend cannot change its bitrate)\n @since 2.6"]
+    pub unsafe fn vsl_encoder_set_bitrate(&self, encoder: *mut VSLEncoder, kbps: u32) -> ::std::os::raw::c_int {
+        (self.vsl_encoder_set_bitrate.as_ref().expect("Expected function, got error."))(encoder, kbps)
+    }
+    #[doc = " @brief Requests the pixel format of decoded frames\n\n Asks the decoder to write decoded frames in @p fourcc instead of NV12, such\n as VSL_FOURCC('R','G','B','3') for display or inference consumers, saving a\n separate conversion pass. The format is negotiated when the stream headers\n are parsed by the first vsl_decode_frame() calls. If the hardware cannot\n produce it the decoder prints a warning and keeps NV12, so callers should\n check vsl_frame_fourcc() of the decoded frames.\n\n Output format selection is available on the V4L2 backend. The Hantro\n backend fails with ENOTSUP.\n\n @param decoder VSLDecoder instance\n @param fourcc Requested pixel format of decoded frames\n @retval 0 on success\n @retval -1 on failure (sets errno: EINVAL for a NULL decoder or zero\n         fourcc, EBUSY once the decoder is initialized, ENOTSUP if the\n         backend cannot select its output format)\n @since 2.6"]
+    pub unsafe fn vsl_decoder_set_output_fourcc(&self, decoder: *mut VSLDecoder, fourcc: u32) -> ::std::os::raw::c_int {
+        (self.vsl_decoder_set_output_fourcc.as_ref().expect("Expected function, got error."))(decoder, fourcc)
+    }
+    #[doc = " Reads the host's dropped frame counters.\n\n Counters accumulate from host creation. Frames expired by\n vsl_host_post() and vsl_host_process() are counted as expired only if no\n client locked them; frames removed with vsl_host_drop() are not counted.\n\n @param host The host instance\n @param stats Output for the counters\n @return 0 on success, -1 on error (sets errno)\n @since 2.6\n @memberof VSLHost"]
+    pub unsafe fn vsl_host_drop_stats(&self, host: *mut VSLHost, stats: *mut VSLHostDropStats) -> ::std::os::raw::c_int {
+        (self.vsl_host_drop_stats.as_ref().expect("Expected function, got error."))(host, stats)
+    }
+    #[doc = " Returns whether the frame is flagged as a keyframe.\n\n The flag is set by the producer with vsl_frame_set_keyframe() before the\n frame is posted and is carried from host to client, so clients joining a\n stream mid-way can wait for a decode entry point.\n\n @param frame The frame instance\n @return 1 if the frame is flagged as a keyframe, 0 otherwise\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_keyframe(&self, frame: *const VSLFrame) -> ::std::os::raw::c_int {
+        (self.vsl_frame_keyframe.as_ref().expect("Expected function, got error."))(frame)
+    }
+    #[doc = " Flags the frame as a keyframe, or clears the flag.\n\n Must be set before the frame is posted with vsl_host_post() for clients\n to receive it.\n\n @param frame The frame instance\n @param keyframe Non-zero to flag the frame as a keyframe, 0 to clear\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_set_keyframe(&self, frame: *mut VSLFrame, keyframe: ::std::os::raw::c_int) {
+        (self.vsl_frame_set_keyframe.as_ref().expect("Expected function, got error."))(frame, keyframe)
+    }
+    #[doc = " @brief Marks the next encoded frame as the long-term reference\n\n The next frame passed to vsl_encode_frame() is stored as the encoder's\n long-term reference (LTR), replacing any previous one.\n\n LTR control is available on the V4L2 backend when the driver implements\n the V4L2_CID_MPEG_VIDEO_FRAME_LTR_INDEX and\n V4L2_CID_MPEG_VIDEO_USE_LTR_FRAMES controls. Other backends and drivers\n fail with ENOTSUP.\n\n @param encoder VSLEncoder instance\n @retval 0 on success\n @retval -1 on failure (sets errno)\n @since 2.6"]
+    pub unsafe fn vsl_encoder_mark_ltr(&self, encoder: *mut VSLEncoder) -> ::std::os::raw::c_int {
+        (self.vsl_encoder_mark_ltr.as_ref().expect("Expected function, got error."))(encoder)
+    }
+    #[doc = " @brief Encodes the next frame against the long-term reference\n\n The next frame passed to vsl_encode_frame() is predicted only from the\n frame marked with vsl_encoder_mark_ltr().\n\n @param encoder VSLEncoder instance\n @retval 0 on success\n @retval -1 on failure (sets errno: EINVAL when no frame has been marked,\n         ENOTSUP if the backend or driver has no LTR control)\n @since 2.6"]
+    pub unsafe fn vsl_encoder_use_ltr(&self, encoder: *mut VSLEncoder) -> ::std::os::raw::c_int {
+        (self.vsl_encoder_use_ltr.as_ref().expect("Expected function, got error."))(encoder)
+    }
+    #[doc = " @brief Sets the number of decoded frame buffers\n\n Sizes the pool of buffers the decoder writes decoded frames into. The\n driver may raise the count to the minimum the stream needs.\n\n @param decoder VSLDecoder instance\n @param count Number of buffers from 1 to 8, or 0 for the default of 8\n @retval 0 on success\n @retval -1 on failure (sets errno: EINVAL, EBUSY once initialized,\n         ENOTSUP if the backend cannot size its pool)\n @since 2.6"]
+    pub unsafe fn vsl_decoder_set_output_buffers(&self, decoder: *mut VSLDecoder, count: ::std::os::raw::c_int) -> ::std::os::raw::c_int {
+        (self.vsl_decoder_set_output_buffers.as_ref().expect("Expected function, got error."))(decoder, count)
+    }
+    #[doc = " @brief Enables low-latency decoding\n\n Returns each frame as soon as it is decoded instead of holding frames\n back to reorder B-frames. Must be called before the first\n vsl_decode_frame().\n\n @param decoder VSLDecoder instance\n @param enable Non-zero to enable, 0 to restore the default\n @retval 0 on success\n @retval -1 on failure (sets errno: EINVAL, EBUSY once decoding has\n         started, ENOTSUP if the backend or driver cannot change its\n         display delay)\n @since 2.6"]
+    pub unsafe fn vsl_decoder_set_low_latency(&self, decoder: *mut VSLDecoder, enable: ::std::os::raw::c_int) -> ::std::os::raw::c_int {
+        (self.vsl_decoder_set_low_latency.as_ref().expect("Expected function, got error."))(decoder, enable)
+    }
+    #[doc = " @brief Drains a frame still held by the encoder at end of stream\n\n A V4L2 encoder may return from vsl_encode_frame() before the frame is\n encoded, leaving it to come out of a later call. When the stream ends,\n call vsl_encoder_flush() until it returns 0 to collect those frames so the\n output ends on a complete frame. Each call writes at most one encoded\n frame to @p destination, as vsl_encode_frame() does.\n\n The first call stops the encoder; vsl_encode_frame() fails with EPIPE\n afterwards. Backends that encode synchronously return 0 straight away.\n\n @param encoder VSLEncoder instance\n @param destination Frame from vsl_encoder_new_output_frame() receiving the\n                    encoded data\n @param keyframe Output: 1 if the drained frame is a keyframe (may be NULL)\n @return Encoded size in bytes, 0 once the encoder is drained, or -1 on\n         error (sets errno: EINVAL for a NULL encoder or destination)\n @since 2.6"]
+    pub unsafe fn vsl_encoder_flush(&self, encoder: *mut VSLEncoder, destination: *mut VSLFrame, keyframe: *mut ::std::os::raw::c_int) -> ::std::os::raw::c_int {
+        (self.vsl_encoder_flush.as_ref().expect("Expected function, got error."))(encoder, destination, keyframe)
+    }
+    #[doc = " Returns the region of the frame intended for display.\n\n Encoders pad frames to macroblock boundaries, so a decoded frame can be\n larger than the picture it carries. The display rectangle is set by the\n producer with vsl_frame_set_display_rect() and is carried from host to\n client so consumers know which sub-region to show.\n\n @param frame The frame instance\n @param rect Output: the display rectangle, or all zeros if none is set\n @return 1 if a display rectangle is set, 0 if not, -1 on error (sets\n         errno: EINVAL for a NULL frame or rect)\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_display_rect(&self, frame: *const VSLFrame, rect: *mut VSLRect) -> ::std::os::raw::c_int {
+        (self.vsl_frame_display_rect.as_ref().expect("Expected function, got error."))(frame, rect)
+    }
+    #[doc = " Sets the region of the frame intended for display, or clears it.\n\n Must be set before the frame is posted with vsl_host_post() for clients\n to receive it.\n\n @param frame The frame instance\n @param rect The display rectangle, or NULL to clear it\n @return 0 on success, -1 on error (sets errno: EINVAL for a NULL frame or\n         a rectangle outside the frame)\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_set_display_rect(&self, frame: *mut VSLFrame, rect: *const VSLRect) -> ::std::os::raw::c_int {
+        (self.vsl_frame_set_display_rect.as_ref().expect("Expected function, got error."))(frame, rect)
+    }
+    #[doc = " Returns how many times the client has reconnected to the host.\n\n The count increases each time the client opens a new connection after\n the previous one was closed, whether by vsl_client_disconnect() or by the\n host going away. Each connection is a new session: a restarted host\n numbers its frames from 1 again, so callers tracking frame serials should\n reset their baseline when the count changes.\n\n @param client The client instance\n @return Number of reconnections, or -1 on error (sets errno: EINVAL for a\n         NULL client)\n @since 2.6\n @memberof VSLClient"]
+    pub unsafe fn vsl_client_reconnects(&self, client: *mut VSLClient) -> i64 {
+        (self.vsl_client_reconnects.as_ref().expect("Expected function, got error."))(client)
+    }
+    #[doc = " @brief Sets the keyframe interval (GOP size) in frames\n\n Every @p frames-th encoded frame is an IDR. Once the encoder is streaming\n the new interval applies without a stream restart and does not force a\n keyframe itself: the next IDR comes when the new interval has elapsed\n since the last one, as counted by the driver. Before the first frame it\n replaces the default interval of one second of frames.\n\n Runtime changes are available on the V4L2 backend when the driver accepts\n V4L2_CID_MPEG_VIDEO_GOP_SIZE while streaming. The Hantro backend only\n accepts a new interval before the first frame and fails with ENOTSUP\n afterwards.\n\n @param encoder VSLEncoder instance\n @param frames Frames from one IDR to the next, from 1 to INT32_MAX\n @retval 0 on success\n @retval -1 on failure (sets errno: EINVAL for a NULL encoder or an out of\n         range interval, ENOTSUP if the backend cannot change its interval,\n         or the driver's error from VIDIOC_S_CTRL)\n @since 2.6"]
+    pub unsafe fn vsl_encoder_set_gop_size(&self, encoder: *mut VSLEncoder, frames: u32) -> ::std::os::raw::c_int {
+        (self.vsl_encoder_set_gop_size.as_ref().expect("Expected function, got error."))(encoder, frames)
+    }
+    #[doc = " @brief Returns the keyframe interval (GOP size) in frames\n\n Reports the interval set with vsl_encoder_set_gop_size(), or the default\n of one second of frames at the encoder's frame rate.\n\n @param encoder VSLEncoder instance\n @return Frames from one IDR to the next, or -1 on error (sets errno:\n         EINVAL for a NULL encoder)\n @since 2.6"]
+    pub unsafe fn vsl_encoder_gop_size(&self, encoder: *const VSLEncoder) -> ::std::os::raw::c_int {
+        (self.vsl_encoder_gop_size.as_ref().expect("Expected function, got error."))(encoder)
+    }
+    #[doc = " Returns the number of valid bytes in the video frame buffer.\n\n Encoders set this to the size of the encoded data and, as in earlier\n releases, also report it from vsl_frame_size(). Producers posting a\n partially filled buffer set it with vsl_frame_set_used_size() while\n vsl_frame_size() stays the buffer size. Otherwise it equals\n vsl_frame_size(). The value is carried from host to client with the frame.\n\n @param frame The frame instance\n @return Valid payload size in bytes\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_used_size(&self, frame: *const VSLFrame) -> usize {
+        (self.vsl_frame_used_size.as_ref().expect("Expected function, got error."))(frame)
+    }
+    #[doc = " Sets the number of valid bytes in the video frame buffer.\n\n Producers of variable-size payloads call this before posting a frame so\n clients read only the valid data. Pass 0 to mark the whole buffer valid.\n\n @param frame The frame instance\n @param used Valid payload size in bytes, at most vsl_frame_size()\n @return 0 on success, -1 with errno EINVAL if @p used exceeds the buffer\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_set_used_size(&self, frame: *mut VSLFrame, used: usize) -> ::std::os::raw::c_int {
+        (self.vsl_frame_set_used_size.as_ref().expect("Expected function, got error."))(frame, used)
+    }
+    #[doc = " Requests how camera buffers are shared with other devices.\n\n Must be called before vsl_camera_init_device(). Capture buffers are always\n allocated by the driver and memory mapped; @p memory selects what else is\n provided:\n\n - `VSL_V4L2_MEM_DMABUF` exports every buffer as a DMABUF for zero-copy\n   hand-off to an encoder or GPU, and vsl_camera_init_device() fails if the\n   driver cannot export one.\n - `VSL_V4L2_MEM_MMAP` skips the export for CPU-only access, so\n   vsl_camera_buffer_dma_fd() returns -1.\n\n Without this call buffers are exported when the driver allows it. The\n request is only recorded here; vsl_camera_init_device() fails with ENOTSUP\n if the driver cannot provide it.\n\n @param ctx Camera context from vsl_camera_open_device()\n @param memory A single `VSLMemoryType` value\n @return 0 on success, -1 on failure (sets errno: EINVAL if @p ctx is NULL\n         or @p memory is not a single memory type, ENOTSUP for\n         `VSL_V4L2_MEM_USERPTR`)\n @since 2.6\n @memberof VSLCamera"]
+    pub unsafe fn vsl_camera_set_memory(&self, ctx: *mut vsl_camera, memory: u32) -> ::std::os::raw::c_int {
+        (self.vsl_camera_set_memory.as_ref().expect("Expected function, got error."))(ctx, memory)
+    }
+    #[doc = " @brief Selects the encoder rate control mode\n\n Replaces the driver's default rate control, which targets the profile\n bitrate, with @p mode. VSL_ENCODER_RC_CBR and VSL_ENCODER_RC_VBR target the\n bitrate set by the profile or vsl_encoder_set_bitrate().\n VSL_ENCODER_RC_CONSTANT_QP encodes every frame at @p qp instead, trading a\n predictable bitrate for a predictable quality; low QP values approach\n visually lossless output for analytics and archival pipelines. The H.264\n and HEVC QP range is 0 (finest) to 51 (coarsest); @p qp is ignored by the\n bitrate modes.\n\n Call before the first frame: most drivers reject rate control changes\n while streaming with EBUSY. Rate control modes are available on the V4L2\n backend, constant QP only when the driver implements\n V4L2_CID_MPEG_VIDEO_FRAME_RC_ENABLE. The Hantro backend fails with\n ENOTSUP.\n\n @param encoder VSLEncoder instance\n @param mode Rate control mode\n @param qp Quantization parameter for VSL_ENCODER_RC_CONSTANT_QP, from 0\n        to 51\n @retval 0 on success\n @retval -1 on failure (sets errno: EINVAL for a NULL encoder, an unknown\n         mode or an out of range QP, ENOTSUP if the backend or driver lacks\n         the mode, or the driver's error from VIDIOC_S_CTRL)\n @since 2.6"]
+    pub unsafe fn vsl_encoder_set_rate_control(&self, encoder: *mut VSLEncoder, mode: VSLEncoderRateControl, qp: ::std::os::raw::c_int) -> ::std::os::raw::c_int {
+        (self.vsl_encoder_set_rate_control.as_ref().expect("Expected function, got error."))(encoder, mode, qp)
+    }
+    #[doc = " @brief Selects the H.264 entropy coding mode\n\n Replaces the driver's default entropy coding, which is CABAC for the High\n profile streams the V4L2 backend produces, with @p mode. Choose CAVLC for\n decoders limited to the Baseline profile or to save decoding power.\n\n Call before the first frame: most drivers reject the change while\n streaming with EBUSY. Available on the V4L2 backend for H.264 when the\n driver implements V4L2_CID_MPEG_VIDEO_H264_ENTROPY_MODE. HEVC always\n codes with CABAC, so only VSL_ENCODER_ENTROPY_CABAC is accepted for it.\n The Hantro backend fails with ENOTSUP.\n\n @param encoder VSLEncoder instance\n @param mode Entropy coding mode\n @retval 0 on success\n @retval -1 on failure (sets errno: EINVAL for a NULL encoder, an unknown\n         mode or CAVLC with HEVC, ENOTSUP if the backend or driver lacks\n         the control, or the driver's error from VIDIOC_S_CTRL)\n @since 2.6"]
+    pub unsafe fn vsl_encoder_set_entropy_mode(&self, encoder: *mut VSLEncoder, mode: VSLEncoderEntropyMode) -> ::std::os::raw::c_int {
+        (self.vsl_encoder_set_entropy_mode.as_ref().expect("Expected function, got error."))(encoder, mode)
+    }
+    #[doc = " Describes the planes of the frame buffer.\n\n The descriptors are carried from host to client with the frame, so a\n client can locate each plane even when the producer aligned its planes\n differently from the defaults derived from the stride. Every plane must\n lie within the frame buffer, so set them after vsl_frame_alloc() or\n vsl_frame_attach(). Pass a @p count of 0 to clear them.\n\n @param frame The frame instance\n @param planes Array of @p count plane descriptors (may be NULL when\n        @p count is 0)\n @param count Number of planes, at most VSL_FRAME_MAX_PLANES\n @return 0 on success, -1 with errno EINVAL for a NULL frame, a count out\n         of range or a plane outside the frame buffer\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_set_planes(&self, frame: *mut VSLFrame, planes: *const VSLFramePlane, count: ::std::os::raw::c_int) -> ::std::os::raw::c_int {
+        (self.vsl_frame_set_planes.as_ref().expect("Expected function, got error."))(frame, planes, count)
+    }
+    #[doc = " Returns the plane descriptors of the frame.\n\n Copies up to @p max descriptors set with vsl_frame_set_planes(), on the\n host or before the frame was posted, into @p planes.\n\n @param frame The frame instance\n @param planes Array receiving up to @p max descriptors (may be NULL when\n        @p max is 0)\n @param max Capacity of @p planes\n @return Number of planes described, which may exceed @p max, 0 if the\n         frame has no descriptors, or -1 with errno EINVAL for a NULL frame\n         or a negative @p max\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_planes(&self, frame: *const VSLFrame, planes: *mut VSLFramePlane, max: ::std::os::raw::c_int) -> ::std::os::raw::c_int {
+        (self.vsl_frame_planes.as_ref().expect("Expected function, got error."))(frame, planes, max)
+    }
+    #[doc = " @brief Creates VSLEncoder instance on a specific V4L2 encoder device\n\n Like vsl_encoder_create() with the V4L2 backend, but opens @p device\n instead of auto-detecting the encoder. Systems with several VPU instances\n can use this to pin each stream to one of them. The VSL_V4L2_ENCODER_DEV\n environment override is not consulted.\n\n @param device V4L2 encoder device path (e.g. \"/dev/video1\")\n @param profile VSLEncoderProfile determining encode quality\n @param outputFourcc fourcc code defining the codec (H264 or HEVC)\n @param fps output stream fps\n @return VSLEncoder* new encoder instance, or NULL with errno set to\n         EINVAL for a NULL @p device or unsupported codec, ENOTSUP if the\n         library was built without the V4L2 backend, or the error from\n         opening the device\n\n @since 2.6"]
+    pub unsafe fn vsl_encoder_create_device(&self, device: *const ::std::os::raw::c_char, profile: VSLEncoderProfile, outputFourcc: u32, fps: ::std::os::raw::c_int) -> *mut VSLEncoder {
+        (self.vsl_encoder_create_device.as_ref().expect("Expected function, got error."))(device, profile, outputFourcc, fps)
+    }
+    #[doc = " Returns the identifier of the source that produced the frame.\n\n Pipelines that publish frames from several cameras on one stream stamp\n each frame with its camera index using vsl_frame_set_source_id(). The\n identifier is carried from host to client and through the encoder, so\n receivers can demultiplex the combined stream.\n\n @param frame The frame instance\n @param id Output: the source identifier, or 0 if none is set\n @return 1 if a source identifier is set, 0 if not, -1 on error (sets\n         errno: EINVAL for a NULL frame or id)\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_source_id(&self, frame: *const VSLFrame, id: *mut u32) -> ::std::os::raw::c_int {
+        (self.vsl_frame_source_id.as_ref().expect("Expected function, got error."))(frame, id)
+    }
+    #[doc = " Sets the identifier of the source that produced the frame, such as the\n index of its camera in a multi-camera rig.\n\n Must be set before the frame is posted with vsl_host_post() for clients\n to receive it.\n\n @param frame The frame instance\n @param id Source identifier\n @since 2.6\n @memberof VSLFrame"]
+    pub unsafe fn vsl_frame_set_source_id(&self, frame: *mut VSLFrame, id: u32) {
+        (self.vsl_frame_set_source_id.as_ref().expect("Expected function, got error."))(frame, id)
+    }
+    #[doc = " Returns the current capture format.\n\n Uses VIDIOC_G_FMT. The format is negotiated by vsl_camera_init_device() but\n may change afterwards, for example when vsl_camera_set_selection() crops\n on a driver which delivers the crop at its own size.\n\n @param ctx Camera context\n @param width Receives the frame width in pixels, may be NULL\n @param height Receives the frame height in pixels, may be NULL\n @param fourcc Receives the pixel format, may be NULL\n @return 0 on success, -1 on failure (sets errno)\n @since 2.6\n @memberof VSLCamera"]
+    pub unsafe fn vsl_camera_get_format(&self, ctx: *const vsl_camera, width: *mut ::std::os::raw::c_int, height: *mut ::std::os::raw::c_int, fourcc: *mut u32) -> ::std::os::raw::c_int {
+        (self.vsl_camera_get_format.as_ref().expect("Expected function, got error."))(ctx, width, height, fourcc)
+    }
+    #[doc = " Returns how many frame events the client discarded while waiting for a\n reply from the host.\n\n vsl_frame_trylock(), vsl_frame_unlock() and vsl_client_send_command() read\n their reply from the socket the frames arrive on and discard the frame\n events queued ahead of it. Those frames never reach vsl_frame_wait(), so\n callers counting dropped frames should include them. The count is not\n reset when the client reconnects.\n\n @param client The client instance\n @return Number of discarded frame events, or -1 on error (sets errno:\n         EINVAL for a NULL client)\n @since 2.6\n @memberof VSLClient"]
+    pub unsafe fn vsl_client_discarded(&self, client: *mut VSLClient) -> i64 {
+        (self.vsl_client_discarded.as_ref().expect("Expected function, got error."))(client)
+    }
 }
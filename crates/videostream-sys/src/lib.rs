@@ -10,9 +10,13 @@
 
 include!("ffi.rs");
 
+#[cfg(feature = "mock")]
+mod mock;
+
 // Re-export libloading for error handling
 pub use libloading;
 
+use std::ffi::CString;
 use std::sync::{Mutex, OnceLock};
 
 // Store a leaked (never-freed) reference to the library to prevent dlclose() at program exit.
@@ -21,14 +25,46 @@ use std::sync::{Mutex, OnceLock};
 // Using a leaked Box ensures the library pointer remains valid for the entire program lifetime.
 static LIBRARY: OnceLock<&'static VideoStreamLibrary> = OnceLock::new();
 static INIT_LOCK: Mutex<()> = Mutex::new(());
+static LIBRARY_PATH: OnceLock<String> = OnceLock::new();
+
+/// Library name searched by the dynamic linker when no path is set.
+const DEFAULT_LIBRARY: &str = "libvideostream.so";
+
+/// Set the path of the library loaded by [`init()`].
+///
+/// Takes precedence over the `VIDEOSTREAM_LIBRARY` environment variable.
+/// Returns `false` if the library is already loaded or a path was already
+/// set, in which case the call has no effect.
+pub fn set_library_path<S: Into<String>>(path: S) -> bool {
+    let _guard = INIT_LOCK.lock().unwrap();
+    LIBRARY.get().is_none() && LIBRARY_PATH.set(path.into()).is_ok()
+}
+
+/// Returns the library path or name [`init()`] loads, before resolution by
+/// the dynamic linker.
+///
+/// This is the path given to [`set_library_path()`], otherwise
+/// `VIDEOSTREAM_LIBRARY`, otherwise `libvideostream.so`.
+pub fn library_path() -> String {
+    LIBRARY_PATH
+        .get()
+        .cloned()
+        .or_else(|| std::env::var("VIDEOSTREAM_LIBRARY").ok())
+        .unwrap_or_else(|| DEFAULT_LIBRARY.to_string())
+}
 
 /// Initialize the VideoStream library by loading libvideostream.so
 ///
 /// This must be called before using any other VideoStream functions.
 /// Returns an error if the library cannot be loaded.
 ///
-/// The environment variable `VIDEOSTREAM_LIBRARY` can be used to specify
-/// a custom path to the library. If not set, searches standard system paths.
+/// The path can be set with [`set_library_path()`] or the environment
+/// variable `VIDEOSTREAM_LIBRARY`. If neither is set, searches standard
+/// system paths. If an explicit path fails to load, a warning is logged and
+/// the standard search is tried instead; when that fails too the error
+/// names both attempts. The in-memory mock of the `mock` feature is only
+/// used once selected with [`use_mock()`], never in place of a library that
+/// fails to load.
 pub fn init() -> Result<&'static VideoStreamLibrary, libloading::Error> {
     if let Some(lib) = LIBRARY.get() {
         return Ok(lib);
@@ -41,13 +77,60 @@ pub fn init() -> Result<&'static VideoStreamLibrary, libloading::Error> {
         return Ok(lib);
     }
 
-    // Check for VIDEOSTREAM_LIBRARY environment variable
-    let lib_path = std::env::var("VIDEOSTREAM_LIBRARY")
-        .ok()
-        .unwrap_or_else(|| "libvideostream.so".to_string());
+    let lib_path = library_path();
+
+    let loaded = match unsafe { VideoStreamLibrary::new(lib_path.as_str()) } {
+        Ok(lib) => Ok(lib),
+        Err(err) if lib_path != DEFAULT_LIBRARY => {
+            log::warn!(
+                "failed to load {}: {}, falling back to {}",
+                lib_path,
+                err,
+                DEFAULT_LIBRARY
+            );
+            unsafe { VideoStreamLibrary::new(DEFAULT_LIBRARY) }.map_err(|fallback| {
+                let msg = format!(
+                    "{}: {}; fallback {}: {}",
+                    lib_path, err, DEFAULT_LIBRARY, fallback
+                );
+                libloading::Error::DlOpen {
+                    source: CString::new(msg).unwrap_or_default().as_c_str().into(),
+                }
+            })
+        }
+        Err(err) => Err(err),
+    };
 
-    let lib = unsafe { VideoStreamLibrary::new(lib_path.as_str())? };
+    Ok(install(loaded?))
+}
+
+/// Install the in-memory mock in place of `libvideostream.so`.
+///
+/// Frames live in memfd buffers, the camera generates a test pattern, the
+/// codecs pass raw frames through fake NAL units and hosts only reach
+/// clients in the same process. Returns `false` if a library is already
+/// loaded, in which case the call has no effect.
+#[cfg(feature = "mock")]
+pub fn use_mock() -> bool {
+    let _guard = INIT_LOCK.lock().unwrap();
+    if LIBRARY.get().is_some() {
+        return false;
+    }
+    match mock::library() {
+        Ok(lib) => {
+            install(lib);
+            true
+        }
+        Err(err) => {
+            log::error!("failed to create the mock library: {}", err);
+            false
+        }
+    }
+}
 
+/// Stores the library for the rest of the program, called with `INIT_LOCK`
+/// held.
+fn install(lib: VideoStreamLibrary) -> &'static VideoStreamLibrary {
     // Leak the library to prevent dlclose() at program exit.
     // This intentionally leaks memory but prevents segfaults from cleanup code
     // trying to access unloaded library code.
@@ -58,7 +141,7 @@ pub fn init() -> Result<&'static VideoStreamLibrary, libloading::Error> {
         .ok()
         .expect("Failed to initialize library");
 
-    Ok(*LIBRARY.get().unwrap())
+    leaked_lib
 }
 
 /// Get a reference to the loaded library
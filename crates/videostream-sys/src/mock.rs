@@ -0,0 +1,2023 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! In-memory implementation of `libvideostream.so` for development hosts and
+//! CI machines without the library or its hardware.
+//!
+//! Frames are backed by memfd buffers, the camera generates a moving test
+//! pattern, the encoder wraps raw pixels in Annex B NAL units that the
+//! decoder unwraps again, and hosts and clients exchange frames through an
+//! in-process registry instead of UNIX sockets, so a host and its clients
+//! must live in the same process. Symbols without a mock implementation are
+//! reported as missing, as an older library would.
+
+use crate::*;
+use std::collections::{HashMap, VecDeque};
+use std::ffi::{CStr, CString};
+use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd};
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+
+/// Builds a library whose implemented symbols point at this module.
+pub(crate) fn library() -> Result<VideoStreamLibrary, libloading::Error> {
+    // The running program does not export the vsl_* symbols, so every
+    // symbol starts out missing and only the ones below are provided.
+    let mut lib =
+        unsafe { VideoStreamLibrary::from_library(libloading::os::unix::Library::this())? };
+
+    lib.vsl_version = Ok(vsl_version);
+    lib.vsl_timestamp = Ok(vsl_timestamp);
+
+    lib.vsl_frame_init = Ok(vsl_frame_init);
+    lib.vsl_frame_alloc = Ok(vsl_frame_alloc);
+    lib.vsl_frame_unalloc = Ok(vsl_frame_unalloc);
+    lib.vsl_frame_attach = Ok(vsl_frame_attach);
+    lib.vsl_frame_path = Ok(vsl_frame_path);
+    lib.vsl_frame_copy = Ok(vsl_frame_copy);
+    lib.vsl_frame_userptr = Ok(vsl_frame_userptr);
+    lib.vsl_frame_set_userptr = Ok(vsl_frame_set_userptr);
+    lib.vsl_frame_wait = Ok(vsl_frame_wait);
+    lib.vsl_frame_release = Ok(vsl_frame_release);
+    lib.vsl_frame_trylock = Ok(vsl_frame_trylock);
+    lib.vsl_frame_unlock = Ok(vsl_frame_unlock);
+    lib.vsl_frame_serial = Ok(vsl_frame_serial);
+    lib.vsl_frame_timestamp = Ok(vsl_frame_timestamp);
+    lib.vsl_frame_duration = Ok(vsl_frame_duration);
+    lib.vsl_frame_pts = Ok(vsl_frame_pts);
+    lib.vsl_frame_dts = Ok(vsl_frame_dts);
+    lib.vsl_frame_expires = Ok(vsl_frame_expires);
+    lib.vsl_frame_fourcc = Ok(vsl_frame_fourcc);
+    lib.vsl_frame_width = Ok(vsl_frame_width);
+    lib.vsl_frame_height = Ok(vsl_frame_height);
+    lib.vsl_frame_stride = Ok(vsl_frame_stride);
+    lib.vsl_frame_size = Ok(vsl_frame_size);
+    lib.vsl_frame_handle = Ok(vsl_frame_handle);
+    lib.vsl_frame_paddr = Ok(vsl_frame_paddr);
+    lib.vsl_frame_mmap = Ok(vsl_frame_mmap);
+    lib.vsl_frame_munmap = Ok(vsl_frame_munmap);
+    lib.vsl_frame_sync = Ok(vsl_frame_sync);
+    lib.vsl_frame_capture_time = Ok(vsl_frame_capture_time);
+    lib.vsl_frame_set_capture_time = Ok(vsl_frame_set_capture_time);
+    lib.vsl_frame_offset = Ok(vsl_frame_offset);
+    lib.vsl_frame_memory = Ok(vsl_frame_memory);
+    lib.vsl_frame_keyframe = Ok(vsl_frame_keyframe);
+    lib.vsl_frame_set_keyframe = Ok(vsl_frame_set_keyframe);
+    lib.vsl_frame_display_rect = Ok(vsl_frame_display_rect);
+    lib.vsl_frame_set_display_rect = Ok(vsl_frame_set_display_rect);
+    lib.vsl_frame_source_id = Ok(vsl_frame_source_id);
+    lib.vsl_frame_set_source_id = Ok(vsl_frame_set_source_id);
+
+    lib.vsl_camera_open_device = Ok(vsl_camera_open_device);
+    lib.vsl_camera_init_device = Ok(vsl_camera_init_device);
+    lib.vsl_camera_mirror = Ok(vsl_camera_mirror);
+    lib.vsl_camera_mirror_v = Ok(vsl_camera_mirror);
+    lib.vsl_camera_start_capturing = Ok(vsl_camera_start_capturing);
+    lib.vsl_camera_get_data = Ok(vsl_camera_get_data);
+    lib.vsl_camera_release_buffer = Ok(vsl_camera_release_buffer);
+    lib.vsl_camera_stop_capturing = Ok(vsl_camera_stop_capturing);
+    lib.vsl_camera_uninit_device = Ok(vsl_camera_uninit_device);
+    lib.vsl_camera_close_device = Ok(vsl_camera_close_device);
+    lib.vsl_camera_enum_fmts = Ok(vsl_camera_enum_fmts);
+    lib.vsl_camera_buffer_mmap = Ok(vsl_camera_buffer_mmap);
+    lib.vsl_camera_buffer_dma_fd = Ok(vsl_camera_buffer_dma_fd);
+    lib.vsl_camera_buffer_length = Ok(vsl_camera_buffer_length);
+    lib.vsl_camera_buffer_fourcc = Ok(vsl_camera_buffer_fourcc);
+    lib.vsl_camera_buffer_bytes_per_line = Ok(vsl_camera_buffer_bytes_per_line);
+    lib.vsl_camera_buffer_sequence = Ok(vsl_camera_buffer_sequence);
+    lib.vsl_camera_buffer_timestamp = Ok(vsl_camera_buffer_timestamp);
+    lib.vsl_camera_color_space = Ok(vsl_camera_color_default);
+    lib.vsl_camera_color_transfer = Ok(vsl_camera_color_default);
+    lib.vsl_camera_color_encoding = Ok(vsl_camera_color_default);
+    lib.vsl_camera_color_range = Ok(vsl_camera_color_default);
+
+    lib.vsl_encoder_create = Ok(vsl_encoder_create);
+    lib.vsl_encoder_create_ex = Ok(vsl_encoder_create_ex);
+    lib.vsl_encoder_release = Ok(vsl_encoder_release);
+    lib.vsl_encoder_new_output_frame = Ok(vsl_encoder_new_output_frame);
+    lib.vsl_encode_frame = Ok(vsl_encode_frame);
+    lib.vsl_encoder_flush = Ok(vsl_encoder_flush);
+    lib.vsl_encoder_set_gop_size = Ok(vsl_encoder_set_gop_size);
+    lib.vsl_encoder_gop_size = Ok(vsl_encoder_gop_size);
+
+    lib.vsl_decoder_create = Ok(vsl_decoder_create);
+    lib.vsl_decoder_create_ex = Ok(vsl_decoder_create_ex);
+    lib.vsl_decode_frame = Ok(vsl_decode_frame);
+    lib.vsl_decoder_width = Ok(vsl_decoder_width);
+    lib.vsl_decoder_height = Ok(vsl_decoder_height);
+    lib.vsl_decoder_crop = Ok(vsl_decoder_crop);
+    lib.vsl_decoder_release = Ok(vsl_decoder_release);
+
+    lib.vsl_host_init = Ok(vsl_host_init);
+    lib.vsl_host_release = Ok(vsl_host_release);
+    lib.vsl_host_path = Ok(vsl_host_path);
+    lib.vsl_host_poll = Ok(vsl_host_poll);
+    lib.vsl_host_service = Ok(vsl_host_service);
+    lib.vsl_host_process = Ok(vsl_host_process);
+    lib.vsl_host_post = Ok(vsl_host_post);
+    lib.vsl_host_drop = Ok(vsl_host_drop);
+    lib.vsl_host_drop_stats = Ok(vsl_host_drop_stats);
+    lib.vsl_host_sockets = Ok(vsl_host_sockets);
+    lib.vsl_host_set_command_handler = Ok(vsl_host_set_command_handler);
+
+    lib.vsl_client_init = Ok(vsl_client_init);
+    lib.vsl_client_release = Ok(vsl_client_release);
+    lib.vsl_client_disconnect = Ok(vsl_client_disconnect);
+    lib.vsl_client_userptr = Ok(vsl_client_userptr);
+    lib.vsl_client_path = Ok(vsl_client_path);
+    lib.vsl_client_set_timeout = Ok(vsl_client_set_timeout);
+    lib.vsl_client_peek = Ok(vsl_client_peek);
+    lib.vsl_client_fd = Ok(vsl_client_fd);
+    lib.vsl_client_reconnects = Ok(vsl_client_reconnects);
+    lib.vsl_client_discarded = Ok(vsl_client_discarded);
+    lib.vsl_client_send_command = Ok(vsl_client_send_command);
+
+    Ok(lib)
+}
+
+const fn fourcc(code: &[u8; 4]) -> u32 {
+    u32::from_le_bytes(*code)
+}
+
+const H264: u32 = fourcc(b"H264");
+const HEVC: u32 = fourcc(b"HEVC");
+const YUYV: u32 = fourcc(b"YUYV");
+const NV12: u32 = fourcc(b"NV12");
+
+const NO_RECT: VSLRect = VSLRect {
+    x: 0,
+    y: 0,
+    width: 0,
+    height: 0,
+};
+
+fn set_errno(code: c_int) {
+    // Safety: __errno_location always returns the calling thread's errno.
+    unsafe { *libc::__errno_location() = code };
+}
+
+/// Monotonic time in nanoseconds, the clock of `vsl_timestamp()`.
+fn now() -> i64 {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // Safety: ts is a valid timespec for the call to fill in.
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
+    // time_t and c_long are 32 bits on some targets
+    #[allow(clippy::unnecessary_cast)]
+    let ns = ts.tv_sec as i64 * 1_000_000_000 + ts.tv_nsec as i64;
+    ns
+}
+
+/// Row size in bytes used by the library for `fourcc`, or 0 if unknown.
+fn frame_stride(fourcc: u32, width: u32) -> u32 {
+    match &fourcc.to_le_bytes() {
+        b"RGBA" | b"RGBX" | b"BGRA" | b"BGRX" => width * 4,
+        b"RGB3" | b"BGR3" => width * 3,
+        b"YUYV" | b"YUY2" | b"YVYU" | b"UYVY" | b"VYUY" => width * 2,
+        b"NV12" | b"I420" | b"YV12" | b"NV21" | b"NV16" | b"NV61" => width + (width >> 1),
+        b"GREY" => width,
+        b"Y16 " => width * 2,
+        _ => 0,
+    }
+}
+
+/// Creates an anonymous shared memory buffer of `size` bytes.
+fn memfd(size: usize) -> c_int {
+    // Safety: the name is a valid C string and the descriptor is checked.
+    unsafe {
+        let fd = libc::memfd_create(b"vsl-mock\0".as_ptr().cast(), libc::MFD_CLOEXEC);
+        if fd >= 0 && libc::ftruncate(fd, size as libc::off_t) != 0 {
+            libc::close(fd);
+            return -1;
+        }
+        fd
+    }
+}
+
+/// Maps `size` bytes of `fd` starting at `offset`, or null on failure.
+fn map(fd: c_int, size: usize, offset: usize) -> *mut u8 {
+    let page = 4096;
+    let delta = offset % page;
+    // Safety: mmap validates the descriptor and range itself.
+    let map = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            size + delta,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED,
+            fd,
+            (offset - delta) as libc::off_t,
+        )
+    };
+    if map == libc::MAP_FAILED {
+        return ptr::null_mut();
+    }
+    // Safety: delta is within the mapping.
+    unsafe { map.cast::<u8>().add(delta) }
+}
+
+/// Releases a mapping returned by [`map()`].
+fn unmap(data: *mut u8, size: usize, offset: usize) {
+    let delta = offset % 4096;
+    // Safety: data and size describe a mapping created by map().
+    unsafe { libc::munmap(data.sub(delta).cast(), size + delta) };
+}
+
+/// Frame properties sent from a host to its clients.
+#[derive(Clone)]
+struct Info {
+    width: u32,
+    height: u32,
+    stride: u32,
+    fourcc: u32,
+    size: usize,
+    offset: usize,
+    serial: i64,
+    timestamp: i64,
+    duration: i64,
+    pts: i64,
+    dts: i64,
+    expires: i64,
+    capture_time: i64,
+    keyframe: c_int,
+    display_rect: VSLRect,
+    source_id: Option<u32>,
+}
+
+struct Frame {
+    info: Info,
+    handle: c_int,
+    memory: VSLFrameMemory,
+    map: *mut u8,
+    map_size: usize,
+    path: Option<CString>,
+    userptr: *mut c_void,
+    cleanup: vsl_frame_cleanup,
+    /// Set once a client locks the frame, shared by all copies of a post
+    locked: Option<Arc<AtomicBool>>,
+}
+
+impl Frame {
+    fn new(info: Info) -> Self {
+        Frame {
+            info,
+            handle: -1,
+            memory: VSLFrameMemory_VSL_FRAME_MEMORY_NONE,
+            map: ptr::null_mut(),
+            map_size: 0,
+            path: None,
+            userptr: ptr::null_mut(),
+            cleanup: None,
+            locked: None,
+        }
+    }
+
+    fn into_raw(self) -> *mut VSLFrame {
+        Box::into_raw(Box::new(self)).cast()
+    }
+
+    fn map(&mut self) -> *mut u8 {
+        if self.map.is_null() && self.handle >= 0 {
+            self.map = map(self.handle, self.info.size, self.info.offset);
+            self.map_size = self.info.size;
+        }
+        self.map
+    }
+
+    fn unmap(&mut self) {
+        if !self.map.is_null() {
+            unmap(self.map, self.map_size, self.info.offset);
+            self.map = ptr::null_mut();
+        }
+    }
+
+    fn unalloc(&mut self) {
+        self.unmap();
+        if self.handle >= 0 {
+            // Safety: the frame owns its descriptor.
+            unsafe { libc::close(self.handle) };
+        }
+        self.handle = -1;
+        self.info.size = 0;
+        self.info.offset = 0;
+        self.memory = VSLFrameMemory_VSL_FRAME_MEMORY_NONE;
+        self.path = None;
+    }
+
+    /// Copies the frame contents, mapping the frame only for the copy if
+    /// it is not already mapped.
+    fn read_all(&mut self) -> Option<Vec<u8>> {
+        let mapped = !self.map.is_null();
+        let data = self.map();
+        if data.is_null() {
+            return None;
+        }
+        // Safety: the mapping spans info.size bytes.
+        let copy = unsafe { std::slice::from_raw_parts(data, self.info.size) }.to_vec();
+        if !mapped {
+            self.unmap();
+        }
+        Some(copy)
+    }
+}
+
+impl Drop for Frame {
+    fn drop(&mut self) {
+        self.unalloc();
+    }
+}
+
+/// # Safety
+/// `frame` must be null or a frame created by this module.
+unsafe fn frame<'a>(frame: *const VSLFrame) -> Option<&'a mut Frame> {
+    // Safety: forwarded from the caller.
+    let frame = unsafe { (frame as *mut Frame).as_mut() };
+    if frame.is_none() {
+        set_errno(libc::EINVAL);
+    }
+    frame
+}
+
+unsafe extern "C" fn vsl_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "-mock\0")
+        .as_ptr()
+        .cast()
+}
+
+unsafe extern "C" fn vsl_timestamp() -> i64 {
+    now()
+}
+
+unsafe extern "C" fn vsl_frame_init(
+    width: u32,
+    height: u32,
+    stride: u32,
+    fourcc: u32,
+    userptr: *mut c_void,
+    cleanup: vsl_frame_cleanup,
+) -> *mut VSLFrame {
+    if width == 0 || height == 0 || fourcc == 0 {
+        set_errno(libc::EINVAL);
+        return ptr::null_mut();
+    }
+    let stride = if stride != 0 {
+        stride
+    } else {
+        frame_stride(fourcc, width)
+    };
+    if stride == 0 {
+        set_errno(libc::ENOTSUP);
+        return ptr::null_mut();
+    }
+
+    let mut frame = Frame::new(Info {
+        width,
+        height,
+        stride,
+        fourcc,
+        size: 0,
+        offset: 0,
+        serial: 0,
+        timestamp: 0,
+        duration: 0,
+        pts: 0,
+        dts: 0,
+        expires: 0,
+        capture_time: 0,
+        keyframe: 0,
+        display_rect: NO_RECT,
+        source_id: None,
+    });
+    frame.userptr = userptr;
+    frame.cleanup = cleanup;
+    frame.into_raw()
+}
+
+/// Gives `frame` a new memfd buffer of `size` bytes.
+fn alloc(frame: &mut Frame, size: usize) -> c_int {
+    frame.unalloc();
+    let fd = memfd(size);
+    if fd < 0 {
+        return -1;
+    }
+    frame.handle = fd;
+    frame.info.size = size;
+    frame.memory = VSLFrameMemory_VSL_FRAME_MEMORY_SHM;
+    0
+}
+
+unsafe extern "C" fn vsl_frame_alloc(frame: *mut VSLFrame, path: *const c_char) -> c_int {
+    let Some(frame) = (unsafe { self::frame(frame) }) else {
+        return -1;
+    };
+    let size = frame_stride(frame.info.fourcc, frame.info.width) * frame.info.height;
+    if size == 0 {
+        set_errno(libc::ENOTSUP);
+        return -1;
+    }
+    if alloc(frame, size as usize) != 0 {
+        return -1;
+    }
+    if !path.is_null() {
+        // Safety: the caller passes a valid C string.
+        frame.path = Some(unsafe { CStr::from_ptr(path) }.to_owned());
+    }
+    0
+}
+
+unsafe extern "C" fn vsl_frame_unalloc(frame: *mut VSLFrame) {
+    if let Some(frame) = unsafe { self::frame(frame) } {
+        frame.unalloc();
+    }
+}
+
+unsafe extern "C" fn vsl_frame_attach(
+    frame: *mut VSLFrame,
+    fd: c_int,
+    size: usize,
+    offset: usize,
+) -> c_int {
+    let Some(frame) = (unsafe { self::frame(frame) }) else {
+        return -1;
+    };
+    let size = match size {
+        0 => (frame_stride(frame.info.fourcc, frame.info.width) * frame.info.height) as usize,
+        size => size,
+    };
+    if size == 0 {
+        set_errno(libc::ENOTSUP);
+        return -1;
+    }
+
+    // Safety: fstat and dup validate the descriptor.
+    let mut st: libc::stat = unsafe { std::mem::zeroed() };
+    if unsafe { libc::fstat(fd, &mut st) } != 0 {
+        return -1;
+    }
+    let len = st.st_size as usize;
+    if len > 0 && (offset > len || size > len - offset) {
+        set_errno(libc::EINVAL);
+        return -1;
+    }
+    let handle = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 3) };
+    if handle < 0 {
+        return -1;
+    }
+
+    frame.unalloc();
+    frame.handle = handle;
+    frame.info.size = size;
+    frame.info.offset = offset;
+    frame.memory = VSLFrameMemory_VSL_FRAME_MEMORY_EXTERNAL;
+    0
+}
+
+unsafe extern "C" fn vsl_frame_path(frame: *const VSLFrame) -> *const c_char {
+    unsafe { self::frame(frame) }
+        .and_then(|frame| frame.path.as_ref())
+        .map_or(ptr::null(), |path| path.as_ptr())
+}
+
+unsafe extern "C" fn vsl_frame_copy(
+    _target: *mut VSLFrame,
+    _source: *mut VSLFrame,
+    _crop: *const VSLRect,
+) -> c_int {
+    // Without G2D every copy takes the software path of the bindings
+    set_errno(libc::ENOTSUP);
+    -1
+}
+
+unsafe extern "C" fn vsl_frame_userptr(frame: *mut VSLFrame) -> *mut c_void {
+    unsafe { self::frame(frame) }.map_or(ptr::null_mut(), |frame| frame.userptr)
+}
+
+unsafe extern "C" fn vsl_frame_set_userptr(frame: *mut VSLFrame, userptr: *mut c_void) {
+    if let Some(frame) = unsafe { self::frame(frame) } {
+        frame.userptr = userptr;
+    }
+}
+
+unsafe extern "C" fn vsl_frame_release(frame: *mut VSLFrame) {
+    if frame.is_null() {
+        set_errno(libc::EINVAL);
+        return;
+    }
+    // Safety: frames are boxed by this module and released once.
+    let mut owned = unsafe { Box::from_raw(frame as *mut Frame) };
+    owned.unalloc();
+    if let Some(cleanup) = owned.cleanup {
+        // Safety: the cleanup callback expects the frame being released.
+        unsafe { cleanup(frame) };
+    }
+}
+
+unsafe extern "C" fn vsl_frame_trylock(frame: *mut VSLFrame) -> c_int {
+    let Some(frame) = (unsafe { self::frame(frame) }) else {
+        return -1;
+    };
+    if let Some(locked) = &frame.locked {
+        locked.store(true, Ordering::Release);
+    }
+    0
+}
+
+unsafe extern "C" fn vsl_frame_unlock(frame: *mut VSLFrame) -> c_int {
+    match unsafe { self::frame(frame) } {
+        Some(_) => 0,
+        None => -1,
+    }
+}
+
+unsafe extern "C" fn vsl_frame_sync(
+    _frame: *const VSLFrame,
+    _enable: c_int,
+    _mode: c_int,
+) -> c_int {
+    0
+}
+
+unsafe extern "C" fn vsl_frame_paddr(_frame: *mut VSLFrame) -> isize {
+    -1
+}
+
+unsafe extern "C" fn vsl_frame_mmap(frame: *mut VSLFrame, size: *mut usize) -> *mut c_void {
+    let Some(frame) = (unsafe { self::frame(frame) }) else {
+        return ptr::null_mut();
+    };
+    let data = frame.map();
+    if !data.is_null() && !size.is_null() {
+        // Safety: the caller passes a valid size pointer or null.
+        unsafe { *size = frame.map_size };
+    }
+    data.cast()
+}
+
+unsafe extern "C" fn vsl_frame_munmap(frame: *mut VSLFrame) {
+    if let Some(frame) = unsafe { self::frame(frame) } {
+        frame.unmap();
+    }
+}
+
+macro_rules! getter {
+    ($name:ident, $ty:ty, |$frame:ident| $value:expr) => {
+        unsafe extern "C" fn $name(frame: *const VSLFrame) -> $ty {
+            match unsafe { self::frame(frame) } {
+                Some($frame) => $value as $ty,
+                None => Default::default(),
+            }
+        }
+    };
+}
+
+getter!(vsl_frame_serial, i64, |f| f.info.serial);
+getter!(vsl_frame_timestamp, i64, |f| f.info.timestamp);
+getter!(vsl_frame_duration, i64, |f| f.info.duration);
+getter!(vsl_frame_pts, i64, |f| f.info.pts);
+getter!(vsl_frame_dts, i64, |f| f.info.dts);
+getter!(vsl_frame_expires, i64, |f| f.info.expires);
+getter!(vsl_frame_capture_time, i64, |f| f.info.capture_time);
+getter!(vsl_frame_keyframe, c_int, |f| f.info.keyframe);
+getter!(vsl_frame_fourcc, u32, |f| f.info.fourcc);
+getter!(vsl_frame_width, c_int, |f| f.info.width);
+getter!(vsl_frame_height, c_int, |f| f.info.height);
+getter!(vsl_frame_stride, c_int, |f| f.info.stride);
+getter!(vsl_frame_size, c_int, |f| f.info.size);
+getter!(vsl_frame_offset, usize, |f| f.info.offset);
+getter!(vsl_frame_memory, VSLFrameMemory, |f| f.memory);
+
+unsafe extern "C" fn vsl_frame_handle(frame: *const VSLFrame) -> c_int {
+    unsafe { self::frame(frame) }.map_or(-1, |frame| frame.handle)
+}
+
+unsafe extern "C" fn vsl_frame_set_capture_time(frame: *mut VSLFrame, capture_time: i64) {
+    if let Some(frame) = unsafe { self::frame(frame) } {
+        frame.info.capture_time = capture_time;
+    }
+}
+
+unsafe extern "C" fn vsl_frame_display_rect(frame: *const VSLFrame, rect: *mut VSLRect) -> c_int {
+    let Some(frame) = (unsafe { self::frame(frame) }) else {
+        return -1;
+    };
+    let display = frame.info.display_rect;
+    // Safety: the caller passes a valid rect pointer.
+    unsafe { *rect = display };
+    (display.width > 0 && display.height > 0) as c_int
+}
+
+unsafe extern "C" fn vsl_frame_set_display_rect(
+    frame: *mut VSLFrame,
+    rect: *const VSLRect,
+) -> c_int {
+    let Some(frame) = (unsafe { self::frame(frame) }) else {
+        return -1;
+    };
+    // Safety: the caller passes a valid rect pointer or null.
+    let Some(&rect) = (unsafe { rect.as_ref() }) else {
+        frame.info.display_rect = NO_RECT;
+        return 0;
+    };
+    if rect.x < 0
+        || rect.y < 0
+        || rect.width <= 0
+        || rect.height <= 0
+        || (rect.x + rect.width) as u32 > frame.info.width
+        || (rect.y + rect.height) as u32 > frame.info.height
+    {
+        set_errno(libc::EINVAL);
+        return -1;
+    }
+    frame.info.display_rect = rect;
+    0
+}
+
+unsafe extern "C" fn vsl_frame_set_keyframe(frame: *mut VSLFrame, keyframe: c_int) {
+    if let Some(frame) = unsafe { self::frame(frame) } {
+        frame.info.keyframe = keyframe;
+    }
+}
+
+unsafe extern "C" fn vsl_frame_source_id(frame: *const VSLFrame, id: *mut u32) -> c_int {
+    let Some(frame) = (unsafe { self::frame(frame) }) else {
+        return -1;
+    };
+    // Safety: the caller passes a valid id pointer.
+    unsafe { *id = frame.info.source_id.unwrap_or(0) };
+    frame.info.source_id.is_some() as c_int
+}
+
+unsafe extern "C" fn vsl_frame_set_source_id(frame: *mut VSLFrame, id: u32) {
+    if let Some(frame) = unsafe { self::frame(frame) } {
+        frame.info.source_id = Some(id);
+    }
+}
+
+/// Capture buffer filled by [`vsl_camera_get_data`].
+struct CameraBuffer {
+    fd: c_int,
+    map: *mut u8,
+    length: u32,
+    bytes_per_line: u32,
+    fourcc: u32,
+    sequence: u32,
+    timestamp: i64,
+    /// Dequeued by the application and not yet released
+    dequeued: bool,
+}
+
+impl Drop for CameraBuffer {
+    fn drop(&mut self) {
+        if !self.map.is_null() {
+            unmap(self.map, self.length as usize, 0);
+        }
+        // Safety: the buffer owns its descriptor.
+        unsafe { libc::close(self.fd) };
+    }
+}
+
+struct Camera {
+    width: u32,
+    height: u32,
+    fourcc: u32,
+    /// Never resized while capturing, so buffer pointers stay valid
+    buffers: Vec<CameraBuffer>,
+    sequence: u32,
+    last: i64,
+}
+
+/// Frame interval of the generated stream, 30 fps.
+const CAMERA_INTERVAL: i64 = 1_000_000_000 / 30;
+
+/// # Safety
+/// `ctx` must be null or a camera created by this module.
+unsafe fn camera<'a>(ctx: *const vsl_camera) -> Option<&'a mut Camera> {
+    // Safety: forwarded from the caller.
+    let camera = unsafe { (ctx as *mut Camera).as_mut() };
+    if camera.is_none() {
+        set_errno(libc::EINVAL);
+    }
+    camera
+}
+
+unsafe extern "C" fn vsl_camera_open_device(filename: *const c_char) -> *mut vsl_camera {
+    if filename.is_null() {
+        set_errno(libc::EINVAL);
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new(Camera {
+        width: 0,
+        height: 0,
+        fourcc: 0,
+        buffers: Vec::new(),
+        sequence: 0,
+        last: 0,
+    }))
+    .cast()
+}
+
+unsafe extern "C" fn vsl_camera_enum_fmts(
+    ctx: *const vsl_camera,
+    codes: *mut u32,
+    size: c_int,
+) -> c_int {
+    if unsafe { camera(ctx) }.is_none() {
+        return -1;
+    }
+    let formats = [YUYV, NV12];
+    let count = formats.len().min(size.max(0) as usize);
+    // Safety: the caller provides room for `size` codes.
+    unsafe { ptr::copy_nonoverlapping(formats.as_ptr(), codes, count) };
+    count as c_int
+}
+
+unsafe extern "C" fn vsl_camera_init_device(
+    ctx: *mut vsl_camera,
+    width: *mut c_int,
+    height: *mut c_int,
+    buf_count: *mut c_int,
+    fourcc: *mut u32,
+) -> c_int {
+    let Some(camera) = (unsafe { self::camera(ctx) }) else {
+        return -1;
+    };
+    // Like a V4L2 driver, unsupported requests are adjusted to the nearest
+    // supported setting and reported back.
+    // Safety: the caller passes valid pointers.
+    let (width, height, buf_count, fourcc) =
+        unsafe { (&mut *width, &mut *height, &mut *buf_count, &mut *fourcc) };
+    if *width <= 0 || *height <= 0 {
+        (*width, *height) = (640, 480);
+    }
+    *width &= !1;
+    if *buf_count <= 0 {
+        *buf_count = 4;
+    }
+    if *fourcc != NV12 {
+        *fourcc = YUYV;
+    }
+
+    let (w, h) = (*width as u32, *height as u32);
+    let (bytes_per_line, length) = match *fourcc {
+        NV12 => (w, w * h * 3 / 2),
+        _ => (w * 2, w * h * 2),
+    };
+    camera.buffers.clear();
+    for _ in 0..*buf_count {
+        let fd = memfd(length as usize);
+        if fd < 0 {
+            camera.buffers.clear();
+            return -1;
+        }
+        camera.buffers.push(CameraBuffer {
+            fd,
+            map: map(fd, length as usize, 0),
+            length,
+            bytes_per_line,
+            fourcc: *fourcc,
+            sequence: 0,
+            timestamp: 0,
+            dequeued: false,
+        });
+    }
+    (camera.width, camera.height, camera.fourcc) = (w, h, *fourcc);
+    0
+}
+
+unsafe extern "C" fn vsl_camera_mirror(ctx: *const vsl_camera, _mirror: bool) -> c_int {
+    match unsafe { camera(ctx) } {
+        Some(_) => 0,
+        None => -1,
+    }
+}
+
+unsafe extern "C" fn vsl_camera_start_capturing(ctx: *mut vsl_camera) -> c_int {
+    let Some(camera) = (unsafe { self::camera(ctx) }) else {
+        return -1;
+    };
+    camera.last = 0;
+    0
+}
+
+unsafe extern "C" fn vsl_camera_stop_capturing(ctx: *const vsl_camera) -> c_int {
+    vsl_camera_mirror(ctx, false)
+}
+
+unsafe extern "C" fn vsl_camera_get_data(ctx: *mut vsl_camera) -> *mut vsl_camera_buffer {
+    let Some(camera) = (unsafe { self::camera(ctx) }) else {
+        return ptr::null_mut();
+    };
+    let (width, height, sequence) = (
+        camera.width as usize,
+        camera.height as usize,
+        camera.sequence,
+    );
+    let Some(buffer) = camera.buffers.iter_mut().find(|buf| !buf.dequeued) else {
+        set_errno(libc::EAGAIN);
+        return ptr::null_mut();
+    };
+    if buffer.map.is_null() {
+        set_errno(libc::ENOMEM);
+        return ptr::null_mut();
+    }
+
+    // Pace the stream at the nominal frame rate
+    let due = camera.last + CAMERA_INTERVAL;
+    let wait = due - now();
+    if camera.last != 0 && wait > 0 {
+        std::thread::sleep(Duration::from_nanos(wait as u64));
+    }
+
+    // A diagonal luma ramp that moves with each frame over neutral chroma
+    // Safety: the mapping spans the whole buffer.
+    let data = unsafe { std::slice::from_raw_parts_mut(buffer.map, buffer.length as usize) };
+    let luma = |x: usize, y: usize| (x + 2 * y + 3 * sequence as usize) as u8;
+    match buffer.fourcc {
+        NV12 => {
+            let (y_plane, uv_plane) = data.split_at_mut(width * height);
+            for (i, y) in y_plane.iter_mut().enumerate() {
+                *y = luma(i % width, i / width);
+            }
+            uv_plane.fill(128);
+        }
+        _ => {
+            for (i, pair) in data.chunks_exact_mut(2).enumerate() {
+                pair[0] = luma(i % width, i / width);
+                pair[1] = 128;
+            }
+        }
+    }
+
+    buffer.sequence = sequence;
+    buffer.timestamp = now();
+    buffer.dequeued = true;
+    camera.last = buffer.timestamp;
+    camera.sequence = sequence.wrapping_add(1);
+    buffer as *mut CameraBuffer as *mut vsl_camera_buffer
+}
+
+unsafe extern "C" fn vsl_camera_release_buffer(
+    ctx: *mut vsl_camera,
+    buffer: *const vsl_camera_buffer,
+) -> c_int {
+    let Some(camera) = (unsafe { self::camera(ctx) }) else {
+        return -1;
+    };
+    let buffer = buffer as *const CameraBuffer;
+    match camera
+        .buffers
+        .iter_mut()
+        .find(|buf| ptr::eq(&**buf, buffer))
+    {
+        Some(buf) => {
+            buf.dequeued = false;
+            0
+        }
+        None => {
+            set_errno(libc::EINVAL);
+            -1
+        }
+    }
+}
+
+unsafe extern "C" fn vsl_camera_uninit_device(ctx: *mut vsl_camera) {
+    if let Some(camera) = unsafe { self::camera(ctx) } {
+        camera.buffers.clear();
+    }
+}
+
+unsafe extern "C" fn vsl_camera_close_device(ctx: *mut vsl_camera) {
+    if !ctx.is_null() {
+        // Safety: cameras are boxed by this module and closed once.
+        drop(unsafe { Box::from_raw(ctx as *mut Camera) });
+    }
+}
+
+unsafe extern "C" fn vsl_camera_color_default(_ctx: *const vsl_camera) -> u32 {
+    0
+}
+
+/// # Safety
+/// `buffer` must be a buffer returned by [`vsl_camera_get_data`].
+unsafe fn buffer<'a>(buffer: *const vsl_camera_buffer) -> &'a mut CameraBuffer {
+    // Safety: forwarded from the caller.
+    unsafe { &mut *(buffer as *mut CameraBuffer) }
+}
+
+unsafe extern "C" fn vsl_camera_buffer_mmap(buf: *mut vsl_camera_buffer) -> *mut c_void {
+    unsafe { buffer(buf) }.map.cast()
+}
+
+unsafe extern "C" fn vsl_camera_buffer_dma_fd(buf: *const vsl_camera_buffer) -> c_int {
+    unsafe { buffer(buf) }.fd
+}
+
+unsafe extern "C" fn vsl_camera_buffer_length(buf: *const vsl_camera_buffer) -> u32 {
+    unsafe { buffer(buf) }.length
+}
+
+unsafe extern "C" fn vsl_camera_buffer_fourcc(buf: *const vsl_camera_buffer) -> u32 {
+    unsafe { buffer(buf) }.fourcc
+}
+
+unsafe extern "C" fn vsl_camera_buffer_bytes_per_line(buf: *const vsl_camera_buffer) -> u32 {
+    unsafe { buffer(buf) }.bytes_per_line
+}
+
+unsafe extern "C" fn vsl_camera_buffer_sequence(buf: *const vsl_camera_buffer) -> u32 {
+    unsafe { buffer(buf) }.sequence
+}
+
+unsafe extern "C" fn vsl_camera_buffer_timestamp(
+    buf: *const vsl_camera_buffer,
+    seconds: *mut i64,
+    nanoseconds: *mut i64,
+) {
+    let timestamp = unsafe { buffer(buf) }.timestamp;
+    // Safety: the caller passes valid pointers.
+    unsafe {
+        *seconds = timestamp / 1_000_000_000;
+        *nanoseconds = timestamp % 1_000_000_000;
+    }
+}
+
+/// First payload byte of each encoded NAL, standing in for a slice header
+/// that starts a picture: `first_mb_in_slice` 0 in H.264 and
+/// `first_slice_segment_in_pic_flag` 1 in H.265.
+const SLICE_HEADER: u8 = 0x80;
+
+/// Length of the stream header following the slice header in each encoded
+/// NAL: width, height, fourcc and stride as little-endian `u32`.
+const HEADER_LEN: usize = 16;
+
+struct Encoder {
+    codec: u32,
+    gop_size: u64,
+    /// Frames encoded since the last IDR, `None` before the first frame.
+    since_idr: Option<u64>,
+}
+
+/// Appends `payload` to `out` with emulation prevention bytes, so it never
+/// contains a start code.
+fn escape(payload: &[u8], out: &mut Vec<u8>) {
+    let mut zeros = 0;
+    for &byte in payload {
+        if zeros == 2 && byte <= 3 {
+            out.push(3);
+            zeros = 0;
+        }
+        out.push(byte);
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+    }
+}
+
+/// Removes the emulation prevention bytes inserted by [`escape()`].
+///
+/// This mirrors `unescape()` in the `videostream` crate's `nal` module,
+/// which cannot be used here since `videostream` depends on this crate.
+fn unescape(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut zeros = 0;
+    for &byte in data {
+        if zeros == 2 && byte == 3 {
+            zeros = 0;
+            continue;
+        }
+        out.push(byte);
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+    }
+    out
+}
+
+fn create_encoder(codec: u32, fps: c_int) -> *mut VSLEncoder {
+    if codec != H264 && codec != HEVC {
+        set_errno(libc::ENOTSUP);
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new(Encoder {
+        codec,
+        gop_size: fps.max(1) as u64,
+        since_idr: None,
+    }))
+    .cast()
+}
+
+unsafe extern "C" fn vsl_encoder_create(
+    _profile: VSLEncoderProfile,
+    output_fourcc: u32,
+    fps: c_int,
+) -> *mut VSLEncoder {
+    create_encoder(output_fourcc, fps)
+}
+
+unsafe extern "C" fn vsl_encoder_create_ex(
+    _profile: VSLEncoderProfile,
+    output_fourcc: u32,
+    fps: c_int,
+    _backend: VSLCodecBackend,
+) -> *mut VSLEncoder {
+    create_encoder(output_fourcc, fps)
+}
+
+unsafe extern "C" fn vsl_encoder_release(encoder: *mut VSLEncoder) {
+    if !encoder.is_null() {
+        // Safety: encoders are boxed by this module and released once.
+        drop(unsafe { Box::from_raw(encoder as *mut Encoder) });
+    }
+}
+
+unsafe extern "C" fn vsl_encoder_new_output_frame(
+    encoder: *const VSLEncoder,
+    width: c_int,
+    height: c_int,
+    duration: i64,
+    pts: i64,
+    dts: i64,
+) -> *mut VSLFrame {
+    // Safety: the caller passes an encoder created by this module.
+    let Some(encoder) = (unsafe { (encoder as *const Encoder).as_ref() }) else {
+        set_errno(libc::EINVAL);
+        return ptr::null_mut();
+    };
+    let (width, height) = (width.max(0) as u32, height.max(0) as u32);
+    let ptr = unsafe { vsl_frame_init(width, height, width, encoder.codec, ptr::null_mut(), None) };
+    let Some(frame) = (unsafe { frame(ptr) }) else {
+        return ptr::null_mut();
+    };
+
+    // Room for escaped 32-bit pixels; memfd pages are only committed once
+    // written.
+    let capacity = width as usize * height as usize * 6 + 1024;
+    if alloc(frame, capacity) != 0 {
+        unsafe { vsl_frame_release(ptr) };
+        return ptr::null_mut();
+    }
+    frame.info.duration = duration;
+    frame.info.pts = pts;
+    frame.info.dts = dts;
+    ptr
+}
+
+unsafe extern "C" fn vsl_encode_frame(
+    encoder: *mut VSLEncoder,
+    source: *mut VSLFrame,
+    destination: *mut VSLFrame,
+    _crop_region: *const VSLRect,
+    keyframe: *mut c_int,
+) -> c_int {
+    // Safety: the caller passes objects created by this module.
+    let (Some(encoder), Some(source), Some(destination)) = (unsafe {
+        (
+            (encoder as *mut Encoder).as_mut(),
+            frame(source),
+            frame(destination),
+        )
+    }) else {
+        set_errno(libc::EINVAL);
+        return -1;
+    };
+    let Some(pixels) = source.read_all() else {
+        return -1;
+    };
+
+    let idr = encoder
+        .since_idr
+        .map_or(true, |frames| frames >= encoder.gop_size);
+    encoder.since_idr = Some(if idr {
+        1
+    } else {
+        encoder.since_idr.unwrap_or(0) + 1
+    });
+    let mut nal = vec![0, 0, 0, 1];
+    match (encoder.codec, idr) {
+        (H264, true) => nal.push(0x65),
+        (H264, false) => nal.push(0x41),
+        (_, true) => nal.extend_from_slice(&[0x26, 0x01]),
+        (_, false) => nal.extend_from_slice(&[0x02, 0x01]),
+    }
+    let mut payload = Vec::with_capacity(1 + HEADER_LEN + pixels.len());
+    payload.push(SLICE_HEADER);
+    for value in [
+        source.info.width,
+        source.info.height,
+        source.info.fourcc,
+        source.info.stride,
+    ] {
+        payload.extend_from_slice(&value.to_le_bytes());
+    }
+    payload.extend_from_slice(&pixels);
+    escape(&payload, &mut nal);
+    // RBSP stop bit
+    nal.push(0x80);
+
+    let capacity = destination.info.size.max(destination.map_size);
+    let data = destination.map();
+    if data.is_null() || nal.len() > capacity {
+        set_errno(libc::ENOBUFS);
+        return -1;
+    }
+    // Safety: the destination mapping holds `capacity` bytes.
+    unsafe { ptr::copy_nonoverlapping(nal.as_ptr(), data, nal.len()) };
+    destination.info.size = nal.len();
+    destination.info.keyframe = idr as c_int;
+    if !keyframe.is_null() {
+        // Safety: the caller passes a valid pointer or null.
+        unsafe { *keyframe = idr as c_int };
+    }
+    nal.len() as c_int
+}
+
+unsafe extern "C" fn vsl_encoder_set_gop_size(encoder: *mut VSLEncoder, frames: u32) -> c_int {
+    // Safety: the caller passes an encoder created by this module.
+    let Some(encoder) = (unsafe { (encoder as *mut Encoder).as_mut() }) else {
+        set_errno(libc::EINVAL);
+        return -1;
+    };
+    if frames == 0 || frames > i32::MAX as u32 {
+        set_errno(libc::EINVAL);
+        return -1;
+    }
+    encoder.gop_size = u64::from(frames);
+    0
+}
+
+unsafe extern "C" fn vsl_encoder_gop_size(encoder: *const VSLEncoder) -> c_int {
+    // Safety: the caller passes an encoder created by this module.
+    let Some(encoder) = (unsafe { (encoder as *const Encoder).as_ref() }) else {
+        set_errno(libc::EINVAL);
+        return -1;
+    };
+    encoder.gop_size as c_int
+}
+
+unsafe extern "C" fn vsl_encoder_flush(
+    _encoder: *mut VSLEncoder,
+    _destination: *mut VSLFrame,
+    _keyframe: *mut c_int,
+) -> c_int {
+    // Frames are encoded synchronously, so nothing is held back
+    0
+}
+
+struct Decoder {
+    codec: u32,
+    width: c_int,
+    height: c_int,
+}
+
+fn create_decoder(codec: u32) -> *mut VSLDecoder {
+    if codec != H264 && codec != HEVC {
+        set_errno(libc::ENOTSUP);
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new(Decoder {
+        codec,
+        width: 0,
+        height: 0,
+    }))
+    .cast()
+}
+
+/// # Safety
+/// `decoder` must be null or a decoder created by this module.
+unsafe fn decoder<'a>(decoder: *const VSLDecoder) -> Option<&'a mut Decoder> {
+    // Safety: forwarded from the caller.
+    let decoder = unsafe { (decoder as *mut Decoder).as_mut() };
+    if decoder.is_none() {
+        set_errno(libc::EINVAL);
+    }
+    decoder
+}
+
+unsafe extern "C" fn vsl_decoder_create(codec: VSLDecoderCodec, _fps: c_int) -> *mut VSLDecoder {
+    match codec {
+        VSLDecoderCodec_VSL_DEC_H264 => create_decoder(H264),
+        VSLDecoderCodec_VSL_DEC_HEVC => create_decoder(HEVC),
+        _ => create_decoder(0),
+    }
+}
+
+unsafe extern "C" fn vsl_decoder_create_ex(
+    codec: u32,
+    _fps: c_int,
+    _backend: VSLCodecBackend,
+) -> *mut VSLDecoder {
+    create_decoder(codec)
+}
+
+/// Returns the position of the next `00 00 01` start code at or after
+/// `from`, including a leading zero of a four byte start code.
+fn find_start_code(data: &[u8], from: usize) -> Option<usize> {
+    let at = data.get(from..)?.windows(3).position(|w| w == [0, 0, 1])? + from;
+    Some(if at > from && data[at - 1] == 0 {
+        at - 1
+    } else {
+        at
+    })
+}
+
+/// Decodes the pixels carried by one VCL NAL unit into a new frame.
+fn decode_picture(rbsp: &[u8]) -> Option<(Info, Vec<u8>)> {
+    // Drop the stop bit and any trailing zero bytes
+    let end = rbsp.iter().rposition(|&b| b != 0)?;
+    let rbsp = rbsp.get(1..end)?;
+    let (header, pixels) = (rbsp.get(..HEADER_LEN)?, rbsp.get(HEADER_LEN..)?);
+    let field = |i: usize| u32::from_le_bytes(header[i * 4..i * 4 + 4].try_into().unwrap());
+    let info = Info {
+        width: field(0),
+        height: field(1),
+        fourcc: field(2),
+        stride: field(3),
+        size: pixels.len(),
+        offset: 0,
+        serial: 0,
+        timestamp: 0,
+        duration: 0,
+        pts: 0,
+        dts: 0,
+        expires: 0,
+        capture_time: 0,
+        keyframe: 0,
+        display_rect: NO_RECT,
+        source_id: None,
+    };
+    if info.width == 0 || info.height == 0 || pixels.is_empty() {
+        return None;
+    }
+    Some((info, pixels.to_vec()))
+}
+
+unsafe extern "C" fn vsl_decode_frame(
+    decoder: *mut VSLDecoder,
+    data: *const c_void,
+    data_length: c_uint,
+    bytes_used: *mut usize,
+    output_frame: *mut *mut VSLFrame,
+) -> VSLDecoderRetCode {
+    let Some(decoder) = (unsafe { self::decoder(decoder) }) else {
+        return VSLDecoderRetCode_VSL_DEC_ERR;
+    };
+    let data = match data_length {
+        0 => &[][..],
+        // Safety: the caller passes data_length readable bytes.
+        len => unsafe { std::slice::from_raw_parts(data as *const u8, len as usize) },
+    };
+    // Safety: the caller passes valid out pointers.
+    unsafe {
+        *bytes_used = 0;
+        *output_frame = ptr::null_mut();
+    }
+
+    // Consume one NAL unit, from its start code to the next or the end
+    let Some(start) = find_start_code(data, 0) else {
+        unsafe { *bytes_used = data.len() };
+        return VSLDecoderRetCode_VSL_DEC_SUCCESS;
+    };
+    let nal_start = start
+        + if data[start] == 0 && data.get(start + 2) == Some(&0) {
+            4
+        } else {
+            3
+        };
+    let end = find_start_code(data, nal_start + 1).unwrap_or(data.len());
+    unsafe { *bytes_used = end };
+    let nal = &data[nal_start.min(end)..end];
+
+    let (vcl, header_len) = match (decoder.codec, nal.first()) {
+        (_, None) => return VSLDecoderRetCode_VSL_DEC_SUCCESS,
+        (H264, Some(&b)) => ((1..=5).contains(&(b & 0x1f)), 1),
+        (_, Some(&b)) => ((b >> 1) & 0x3f < 32, 2),
+    };
+    if !vcl {
+        return VSLDecoderRetCode_VSL_DEC_SUCCESS;
+    }
+
+    let Some((info, pixels)) = nal
+        .get(header_len..)
+        .and_then(|rbsp| decode_picture(&unescape(rbsp)))
+    else {
+        return VSLDecoderRetCode_VSL_DEC_ERR;
+    };
+    let (width, height) = (info.width as c_int, info.height as c_int);
+    let mut frame = Frame::new(info);
+    if alloc(&mut frame, pixels.len()) != 0 || frame.map().is_null() {
+        return VSLDecoderRetCode_VSL_DEC_ERR;
+    }
+    // Safety: the new mapping holds pixels.len() bytes.
+    unsafe { ptr::copy_nonoverlapping(pixels.as_ptr(), frame.map, pixels.len()) };
+    frame.unmap();
+
+    decoder.width = width;
+    decoder.height = height;
+    unsafe { *output_frame = frame.into_raw() };
+    VSLDecoderRetCode_VSL_DEC_FRAME_DEC
+}
+
+unsafe extern "C" fn vsl_decoder_width(decoder: *const VSLDecoder) -> c_int {
+    unsafe { self::decoder(decoder) }.map_or(0, |decoder| decoder.width)
+}
+
+unsafe extern "C" fn vsl_decoder_height(decoder: *const VSLDecoder) -> c_int {
+    unsafe { self::decoder(decoder) }.map_or(0, |decoder| decoder.height)
+}
+
+unsafe extern "C" fn vsl_decoder_crop(decoder: *const VSLDecoder) -> VSLRect {
+    let (width, height) =
+        unsafe { self::decoder(decoder) }.map_or((0, 0), |decoder| (decoder.width, decoder.height));
+    VSLRect {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    }
+}
+
+unsafe extern "C" fn vsl_decoder_release(decoder: *mut VSLDecoder) -> c_int {
+    if decoder.is_null() {
+        set_errno(libc::EINVAL);
+        return -1;
+    }
+    // Safety: decoders are boxed by this module and released once.
+    drop(unsafe { Box::from_raw(decoder as *mut Decoder) });
+    0
+}
+
+/// A frame posted to one client, holding its own descriptor.
+struct Event {
+    fd: OwnedFd,
+    info: Info,
+    locked: Arc<AtomicBool>,
+}
+
+/// Frame events waiting for one client.
+struct Queue {
+    events: VecDeque<Event>,
+    /// Readable while events are queued, returned by `vsl_client_fd()` so
+    /// clients can be polled like the library's sockets
+    notify: OwnedFd,
+}
+
+impl Queue {
+    fn new() -> Option<Self> {
+        // Safety: eventfd has no memory arguments and the result is checked.
+        let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+        (fd >= 0).then(|| Queue {
+            events: VecDeque::new(),
+            // Safety: the descriptor was just created and is owned here.
+            notify: unsafe { OwnedFd::from_raw_fd(fd) },
+        })
+    }
+
+    /// Makes `notify` readable exactly while events are queued.
+    fn signal(&self) {
+        self.set_readable(!self.events.is_empty());
+    }
+
+    fn set_readable(&self, readable: bool) {
+        let mut count: u64 = 1;
+        let fd = self.notify.as_raw_fd();
+        let count_ptr = &mut count as *mut u64 as *mut c_void;
+        // Safety: count is 8 bytes, as an eventfd requires. Reading an
+        // empty non-blocking eventfd fails with EAGAIN, which is harmless.
+        unsafe {
+            if readable {
+                libc::write(fd, count_ptr, 8);
+            } else {
+                libc::read(fd, count_ptr, 8);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ChannelState {
+    closed: bool,
+    /// Connections and commands since the host last processed them
+    pending: usize,
+    next_client: u64,
+    queues: HashMap<u64, Queue>,
+    /// Command requests waiting for the host, by client
+    commands: VecDeque<(u64, Vec<u8>)>,
+    /// Replies to commands by client, the reply bytes or an errno
+    replies: HashMap<u64, Result<Vec<u8>, c_int>>,
+}
+
+/// Connection point shared by a host and its clients in place of a socket.
+#[derive(Default)]
+struct Channel {
+    state: Mutex<ChannelState>,
+    changed: Condvar,
+}
+
+impl Channel {
+    fn lock(&self) -> MutexGuard<'_, ChannelState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Open channels by socket path.
+fn channels() -> MutexGuard<'static, HashMap<CString, Arc<Channel>>> {
+    static CHANNELS: OnceLock<Mutex<HashMap<CString, Arc<Channel>>>> = OnceLock::new();
+    CHANNELS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+/// Waits on `channel` until notified or `deadline` (monotonic nanoseconds)
+/// passes, returning false on timeout.
+fn wait_until<'a>(
+    channel: &'a Channel,
+    state: MutexGuard<'a, ChannelState>,
+    deadline: Option<i64>,
+) -> (MutexGuard<'a, ChannelState>, bool) {
+    match deadline {
+        None => (
+            channel
+                .changed
+                .wait(state)
+                .unwrap_or_else(|err| err.into_inner()),
+            true,
+        ),
+        Some(deadline) => {
+            let left = deadline - now();
+            if left <= 0 {
+                return (state, false);
+            }
+            let (state, _) = channel
+                .changed
+                .wait_timeout(state, Duration::from_nanos(left as u64))
+                .unwrap_or_else(|err| err.into_inner());
+            (state, true)
+        }
+    }
+}
+
+struct Posted {
+    frame: *mut VSLFrame,
+    expires: i64,
+    locked: Arc<AtomicBool>,
+}
+
+struct Host {
+    path: CString,
+    channel: Arc<Channel>,
+    frames: Vec<Posted>,
+    serial: i64,
+    expired: u64,
+    handler: vsl_host_command_handler,
+    userptr: *mut c_void,
+}
+
+/// # Safety
+/// `host` must be null or a host created by this module.
+unsafe fn host<'a>(host: *const VSLHost) -> Option<&'a mut Host> {
+    // Safety: forwarded from the caller.
+    let host = unsafe { (host as *mut Host).as_mut() };
+    if host.is_none() {
+        set_errno(libc::EINVAL);
+    }
+    host
+}
+
+unsafe extern "C" fn vsl_host_init(path: *const c_char) -> *mut VSLHost {
+    if path.is_null() {
+        set_errno(libc::EINVAL);
+        return ptr::null_mut();
+    }
+    // Safety: the caller passes a valid C string.
+    let path = unsafe { CStr::from_ptr(path) }.to_owned();
+    let mut channels = channels();
+    if channels.contains_key(&path) {
+        set_errno(libc::EADDRINUSE);
+        return ptr::null_mut();
+    }
+    let channel = Arc::new(Channel::default());
+    channels.insert(path.clone(), channel.clone());
+    Box::into_raw(Box::new(Host {
+        path,
+        channel,
+        frames: Vec::new(),
+        serial: 0,
+        expired: 0,
+        handler: None,
+        userptr: ptr::null_mut(),
+    }))
+    .cast()
+}
+
+unsafe extern "C" fn vsl_host_release(host: *mut VSLHost) {
+    if host.is_null() {
+        return;
+    }
+    // Safety: hosts are boxed by this module and released once.
+    let host = unsafe { Box::from_raw(host as *mut Host) };
+    channels().remove(&host.path);
+    let mut state = host.channel.lock();
+    state.closed = true;
+    // Wake clients polling their descriptor, as a hangup would
+    for queue in state.queues.values() {
+        queue.set_readable(true);
+    }
+    drop(state);
+    host.channel.changed.notify_all();
+    for posted in host.frames {
+        unsafe { vsl_frame_release(posted.frame) };
+    }
+}
+
+unsafe extern "C" fn vsl_host_path(host: *const VSLHost) -> *const c_char {
+    unsafe { self::host(host) }.map_or(ptr::null(), |host| host.path.as_ptr())
+}
+
+unsafe extern "C" fn vsl_host_poll(host: *mut VSLHost, wait: i64) -> c_int {
+    let Some(host) = (unsafe { self::host(host) }) else {
+        return -1;
+    };
+    let deadline = (wait >= 0).then(|| now().saturating_add(wait.saturating_mul(1_000_000)));
+    let mut state = host.channel.lock();
+    loop {
+        if state.pending > 0 {
+            return state.pending as c_int;
+        }
+        let woken;
+        (state, woken) = wait_until(&host.channel, state, deadline);
+        if !woken {
+            return 0;
+        }
+    }
+}
+
+unsafe extern "C" fn vsl_host_service(_host: *mut VSLHost, _sock: c_int) -> c_int {
+    0
+}
+
+unsafe extern "C" fn vsl_host_process(host: *mut VSLHost) -> c_int {
+    let Some(mock) = (unsafe { self::host(host) }) else {
+        return -1;
+    };
+    let commands = {
+        let mut state = mock.channel.lock();
+        state.pending = 0;
+        std::mem::take(&mut state.commands)
+    };
+    if !commands.is_empty() {
+        // The handler runs without the channel lock, as it may take a while
+        let replies: Vec<_> = commands
+            .into_iter()
+            .map(|(id, request)| (id, unsafe { run_command(mock, host, &request) }))
+            .collect();
+        mock.channel.lock().replies.extend(replies);
+        mock.channel.changed.notify_all();
+    }
+    let host = mock;
+
+    let now = now();
+    let (expired, live) = std::mem::take(&mut host.frames)
+        .into_iter()
+        .partition::<Vec<_>, _>(|posted| posted.expires <= now);
+    host.frames = live;
+    for posted in expired {
+        if !posted.locked.load(Ordering::Acquire) {
+            host.expired += 1;
+        }
+        unsafe { vsl_frame_release(posted.frame) };
+    }
+    0
+}
+
+unsafe extern "C" fn vsl_host_post(
+    host: *mut VSLHost,
+    frame: *mut VSLFrame,
+    expires: i64,
+    duration: i64,
+    pts: i64,
+    dts: i64,
+) -> c_int {
+    let (Some(host), Some(posted)) = (unsafe { self::host(host) }, unsafe { self::frame(frame) })
+    else {
+        return -1;
+    };
+    if posted.handle < 0 {
+        set_errno(libc::EINVAL);
+        return -1;
+    }
+
+    host.serial += 1;
+    posted.info.serial = host.serial;
+    posted.info.timestamp = now();
+    posted.info.expires = expires;
+    posted.info.duration = duration;
+    posted.info.pts = pts;
+    posted.info.dts = dts;
+    let locked = Arc::new(AtomicBool::new(false));
+
+    let mut state = host.channel.lock();
+    for queue in state.queues.values_mut() {
+        // Safety: the duplicate is a new descriptor owned by the event.
+        let fd = unsafe { libc::fcntl(posted.handle, libc::F_DUPFD_CLOEXEC, 3) };
+        if fd < 0 {
+            return -1;
+        }
+        queue.events.push_back(Event {
+            fd: unsafe { OwnedFd::from_raw_fd(fd) },
+            info: posted.info.clone(),
+            locked: locked.clone(),
+        });
+        queue.signal();
+    }
+    drop(state);
+    host.channel.changed.notify_all();
+
+    host.frames.push(Posted {
+        frame,
+        expires,
+        locked,
+    });
+    0
+}
+
+unsafe extern "C" fn vsl_host_drop(host: *mut VSLHost, frame: *mut VSLFrame) -> c_int {
+    let Some(host) = (unsafe { self::host(host) }) else {
+        return -1;
+    };
+    match host.frames.iter().position(|posted| posted.frame == frame) {
+        Some(index) => {
+            host.frames.remove(index);
+            0
+        }
+        None => {
+            set_errno(libc::ENOENT);
+            -1
+        }
+    }
+}
+
+unsafe extern "C" fn vsl_host_drop_stats(
+    host: *mut VSLHost,
+    stats: *mut VSLHostDropStats,
+) -> c_int {
+    let Some(host) = (unsafe { self::host(host) }) else {
+        return -1;
+    };
+    // Safety: the caller passes a valid stats pointer.
+    unsafe {
+        *stats = VSLHostDropStats {
+            expired: host.expired,
+            client_slow: 0,
+        }
+    };
+    0
+}
+
+unsafe extern "C" fn vsl_host_sockets(
+    host: *mut VSLHost,
+    _n_sockets: usize,
+    _sockets: *mut c_int,
+    max_sockets: *mut usize,
+) -> c_int {
+    if unsafe { self::host(host) }.is_none() {
+        return -1;
+    }
+    // Clients reach the host through the in-process channel, not sockets
+    if !max_sockets.is_null() {
+        // Safety: the caller passes a valid pointer or null.
+        unsafe { *max_sockets = 0 };
+    }
+    0
+}
+
+unsafe extern "C" fn vsl_host_set_command_handler(
+    host: *mut VSLHost,
+    handler: vsl_host_command_handler,
+    userptr: *mut c_void,
+) -> c_int {
+    let Some(host) = (unsafe { self::host(host) }) else {
+        return -1;
+    };
+    host.handler = handler;
+    host.userptr = userptr;
+    0
+}
+
+/// Runs `host`'s command handler on `request`, returning the reply or the
+/// errno the client receives.
+///
+/// # Safety
+/// `ptr` must be the pointer `host` was obtained from.
+unsafe fn run_command(host: &Host, ptr: *mut VSLHost, request: &[u8]) -> Result<Vec<u8>, c_int> {
+    let handler = host.handler.ok_or(libc::ENOTSUP)?;
+    let mut reply = vec![0u8; VSL_COMMAND_MAX as usize];
+    // Safety: both buffers are valid for the sizes passed.
+    let ret = unsafe {
+        handler(
+            ptr,
+            request.as_ptr().cast(),
+            request.len(),
+            reply.as_mut_ptr().cast(),
+            reply.len(),
+            host.userptr,
+        )
+    };
+    if ret < 0 {
+        return Err(libc::EIO);
+    }
+    reply.truncate(ret as usize);
+    Ok(reply)
+}
+
+struct Client {
+    path: CString,
+    userptr: *mut c_void,
+    reconnect: bool,
+    timeout: f32,
+    connection: Option<(Arc<Channel>, u64)>,
+    /// Successful connections, the first one included
+    connects: i64,
+}
+
+impl Client {
+    /// Connects to the host at the client's path if not connected.
+    fn connect(&mut self) -> Option<(Arc<Channel>, u64)> {
+        if self.connection.is_none() {
+            let channel = channels().get(&self.path).cloned()?;
+            let queue = Queue::new()?;
+            let mut state = channel.lock();
+            let id = state.next_client;
+            state.next_client += 1;
+            state.pending += 1;
+            state.queues.insert(id, queue);
+            drop(state);
+            channel.changed.notify_all();
+            self.connection = Some((channel, id));
+            self.connects += 1;
+        }
+        self.connection.clone()
+    }
+
+    fn disconnect(&mut self) {
+        if let Some((channel, id)) = self.connection.take() {
+            channel.lock().queues.remove(&id);
+        }
+    }
+}
+
+/// # Safety
+/// `client` must be null or a client created by this module.
+unsafe fn client<'a>(client: *const VSLClient) -> Option<&'a mut Client> {
+    // Safety: forwarded from the caller.
+    let client = unsafe { (client as *mut Client).as_mut() };
+    if client.is_none() {
+        set_errno(libc::EINVAL);
+    }
+    client
+}
+
+unsafe extern "C" fn vsl_client_init(
+    path: *const c_char,
+    userptr: *mut c_void,
+    reconnect: bool,
+) -> *mut VSLClient {
+    if path.is_null() {
+        set_errno(libc::EINVAL);
+        return ptr::null_mut();
+    }
+    let mut client = Client {
+        // Safety: the caller passes a valid C string.
+        path: unsafe { CStr::from_ptr(path) }.to_owned(),
+        userptr,
+        reconnect,
+        timeout: 1.0,
+        connection: None,
+        connects: 0,
+    };
+    if client.connect().is_none() && !reconnect {
+        set_errno(libc::ECONNREFUSED);
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new(client)).cast()
+}
+
+unsafe extern "C" fn vsl_client_release(client: *mut VSLClient) {
+    if !client.is_null() {
+        // Safety: clients are boxed by this module and released once.
+        let mut client = unsafe { Box::from_raw(client as *mut Client) };
+        client.disconnect();
+    }
+}
+
+unsafe extern "C" fn vsl_client_disconnect(client: *mut VSLClient) {
+    if let Some(client) = unsafe { self::client(client) } {
+        client.reconnect = false;
+        client.disconnect();
+    }
+}
+
+unsafe extern "C" fn vsl_client_userptr(client: *mut VSLClient) -> *mut c_void {
+    unsafe { self::client(client) }.map_or(ptr::null_mut(), |client| client.userptr)
+}
+
+unsafe extern "C" fn vsl_client_path(client: *const VSLClient) -> *const c_char {
+    unsafe { self::client(client) }.map_or(ptr::null(), |client| client.path.as_ptr())
+}
+
+unsafe extern "C" fn vsl_client_set_timeout(client: *mut VSLClient, timeout: f32) {
+    if let Some(client) = unsafe { self::client(client) } {
+        client.timeout = timeout;
+    }
+}
+
+unsafe extern "C" fn vsl_client_fd(client: *mut VSLClient) -> c_int {
+    let Some(client) = (unsafe { self::client(client) }) else {
+        return -1;
+    };
+    let fd = client.connection.as_ref().and_then(|(channel, id)| {
+        let state = channel.lock();
+        state.queues.get(id).map(|queue| queue.notify.as_raw_fd())
+    });
+    fd.unwrap_or_else(|| {
+        set_errno(libc::ENOTCONN);
+        -1
+    })
+}
+
+unsafe extern "C" fn vsl_client_reconnects(client: *mut VSLClient) -> i64 {
+    unsafe { self::client(client) }.map_or(-1, |client| (client.connects - 1).max(0))
+}
+
+unsafe extern "C" fn vsl_client_discarded(client: *mut VSLClient) -> i64 {
+    // Locking and commands do not share a stream with the frame events, so
+    // no events are ever skipped.
+    unsafe { self::client(client) }.map_or(-1, |_| 0)
+}
+
+unsafe extern "C" fn vsl_client_send_command(
+    client: *mut VSLClient,
+    request: *const c_void,
+    request_size: usize,
+    reply: *mut c_void,
+    reply_capacity: usize,
+) -> c_int {
+    let Some(client) = (unsafe { self::client(client) }) else {
+        return -1;
+    };
+    if (request.is_null() && request_size > 0) || (reply.is_null() && reply_capacity > 0) {
+        set_errno(libc::EINVAL);
+        return -1;
+    }
+    if request_size > VSL_COMMAND_MAX as usize {
+        set_errno(libc::EMSGSIZE);
+        return -1;
+    }
+    let Some((channel, id)) = client.connection.clone() else {
+        set_errno(libc::ENOTCONN);
+        return -1;
+    };
+    let request = match request_size {
+        0 => Vec::new(),
+        // Safety: the caller passes request_size valid bytes.
+        _ => unsafe { std::slice::from_raw_parts(request.cast::<u8>(), request_size) }.to_vec(),
+    };
+    let deadline = now() + (f64::from(client.timeout) * 1e9) as i64;
+
+    let mut state = channel.lock();
+    state.commands.push_back((id, request));
+    state.pending += 1;
+    channel.changed.notify_all();
+    let result = loop {
+        if let Some(result) = state.replies.remove(&id) {
+            break result;
+        }
+        if state.closed {
+            set_errno(libc::ENOTCONN);
+            return -1;
+        }
+        let woken;
+        (state, woken) = wait_until(&channel, state, Some(deadline));
+        if !woken {
+            state.commands.retain(|(client, _)| *client != id);
+            set_errno(libc::ETIMEDOUT);
+            return -1;
+        }
+    };
+    drop(state);
+
+    match result {
+        Ok(bytes) => {
+            let len = bytes.len().min(reply_capacity);
+            // Safety: reply holds reply_capacity bytes.
+            unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), reply.cast::<u8>(), len) };
+            bytes.len() as c_int
+        }
+        Err(code) => {
+            set_errno(code);
+            -1
+        }
+    }
+}
+
+/// Removes expired events from the front of `queue` and returns the next.
+fn next_event(queue: &mut Queue) -> Option<&Event> {
+    let now = now();
+    let before = queue.events.len();
+    while queue
+        .events
+        .front()
+        .is_some_and(|event| event.info.expires > 0 && event.info.expires <= now)
+    {
+        queue.events.pop_front();
+    }
+    if queue.events.len() != before {
+        queue.signal();
+    }
+    queue.events.front()
+}
+
+unsafe extern "C" fn vsl_client_peek(client: *mut VSLClient, meta: *mut VSLFrameMeta) -> c_int {
+    let Some(client) = (unsafe { self::client(client) }) else {
+        return -1;
+    };
+    if client.connection.is_none() && !client.reconnect {
+        return 0;
+    }
+    let Some((channel, id)) = client.connect() else {
+        return 0;
+    };
+    let mut state = channel.lock();
+    let Some(event) = state.queues.get_mut(&id).and_then(next_event) else {
+        // The host went away
+        if state.closed {
+            drop(state);
+            client.disconnect();
+        }
+        return 0;
+    };
+    // Safety: the caller passes a valid meta pointer.
+    unsafe {
+        *meta = VSLFrameMeta {
+            serial: event.info.serial,
+            timestamp: event.info.timestamp,
+            pts: event.info.pts,
+            size: event.info.size,
+            fourcc: event.info.fourcc,
+        }
+    };
+    1
+}
+
+unsafe extern "C" fn vsl_frame_wait(client: *mut VSLClient, until: i64) -> *mut VSLFrame {
+    let Some(client) = (unsafe { self::client(client) }) else {
+        return ptr::null_mut();
+    };
+    let deadline = match until {
+        0 => now() + (f64::from(client.timeout) * 1e9) as i64,
+        until => until,
+    };
+
+    loop {
+        let Some((channel, id)) = client.connect() else {
+            if now() >= deadline {
+                set_errno(libc::ETIMEDOUT);
+                return ptr::null_mut();
+            }
+            std::thread::sleep(Duration::from_millis(10));
+            continue;
+        };
+
+        let mut state = channel.lock();
+        loop {
+            if let Some(queue) = state.queues.get_mut(&id) {
+                if next_event(queue).is_some() {
+                    let event = queue.events.pop_front().unwrap();
+                    queue.signal();
+                    let mut frame = Frame::new(event.info);
+                    frame.handle = event.fd.into_raw_fd();
+                    frame.memory = VSLFrameMemory_VSL_FRAME_MEMORY_EXTERNAL;
+                    frame.locked = Some(event.locked);
+                    return frame.into_raw();
+                }
+            }
+            if state.closed {
+                break;
+            }
+            let woken;
+            (state, woken) = wait_until(&channel, state, Some(deadline));
+            if !woken {
+                set_errno(libc::ETIMEDOUT);
+                return ptr::null_mut();
+            }
+        }
+
+        // The host went away
+        drop(state);
+        client.disconnect();
+        if !client.reconnect {
+            set_errno(libc::EPIPE);
+            return ptr::null_mut();
+        }
+    }
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Loading from a stale `VIDEOSTREAM_LIBRARY` path. Kept in its own test
+//! binary since the library is loaded once per process.
+
+#[test]
+fn test_init_falls_back_from_missing_library() {
+    let bogus = "/nonexistent/videostream/libvideostream.so";
+    // Safety: this is the only test in the binary, no other thread reads
+    // the environment.
+    unsafe { std::env::set_var("VIDEOSTREAM_LIBRARY", bogus) };
+    assert_eq!(videostream_sys::library_path(), bogus);
+
+    match videostream_sys::init() {
+        // libvideostream.so was found by the standard search
+        Ok(_) => assert!(videostream_sys::try_library().is_some()),
+        Err(err) => {
+            let msg = std::error::Error::source(&err)
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            assert!(msg.contains(bogus), "{}", msg);
+            assert!(msg.contains("libvideostream.so: "), "{}", msg);
+        }
+    }
+}
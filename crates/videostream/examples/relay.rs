@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Relays an H.264 stream as decoded frames without copying them.
+//!
+//! Receives encoded frames from a VSL socket, decodes them on the VPU and
+//! posts each decoded frame to a second socket. The posted frame is the
+//! decoder's own DMA buffer, so clients of the relay read what the VPU wrote.
+//!
+//! ```text
+//! cargo run --example relay -- /tmp/camera.sock /tmp/decoded.sock [frames]
+//! ```
+
+use std::{
+    fs::File, mem::ManuallyDrop, os::fd::FromRawFd, os::unix::fs::MetadataExt, time::Duration,
+};
+use videostream::{
+    client::{Client, Reconnect},
+    decoder::{Decoder, DecoderCodec},
+    host::Host,
+    timestamp,
+};
+
+/// Identifies the DmaBuf behind `fd`, which stays the same when the
+/// descriptor is duplicated.
+fn inode(fd: i32) -> std::io::Result<u64> {
+    // Safety: the descriptor is borrowed from a live frame and ManuallyDrop
+    // keeps `File` from closing it.
+    Ok(ManuallyDrop::new(unsafe { File::from_raw_fd(fd) })
+        .metadata()?
+        .ino())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let source = args
+        .next()
+        .unwrap_or_else(|| "/tmp/camera.sock".to_string());
+    let relay = args
+        .next()
+        .unwrap_or_else(|| "/tmp/decoded.sock".to_string());
+    let frame_count: usize = args.next().and_then(|s| s.parse().ok()).unwrap_or(100);
+
+    let client = Client::new(&source, Reconnect::Yes)?;
+    client.set_timeout(5.0)?;
+    let decoder = Decoder::create(DecoderCodec::H264, 30)?;
+    let host = Host::new(&relay)?;
+    println!("[RELAY] {} -> {}", source, relay);
+
+    let mut relayed = 0;
+    while relayed < frame_count {
+        let encoded = client.get_frame(0)?;
+        encoded.trylock()?;
+        let data = encoded.map()?.to_vec();
+        encoded.unlock()?;
+
+        let mut remaining = data.as_slice();
+        while !remaining.is_empty() {
+            let (_, consumed, decoded) = decoder.decode_frame(remaining)?;
+            if let Some(frame) = decoded {
+                let fd = frame.handle()?;
+                if relayed < 5 {
+                    println!(
+                        "[RELAY] frame {}: {}x{} dmabuf fd {} inode {}",
+                        relayed,
+                        frame.width()?,
+                        frame.height()?,
+                        fd,
+                        inode(fd)?
+                    );
+                }
+
+                // Ownership moves to the host, which returns the buffer to
+                // the decoder when the frame expires
+                let expires = timestamp()? + 100_000_000;
+                host.post(frame, expires, -1, encoded.pts()?, encoded.dts()?)?;
+                relayed += 1;
+            }
+            if consumed == 0 {
+                break;
+            }
+            remaining = &remaining[consumed.min(remaining.len())..];
+        }
+
+        // Expired frames are released back to the decoder
+        host.service_timeout(Duration::ZERO)?;
+    }
+
+    println!("[RELAY] relayed {} frames", relayed);
+    Ok(())
+}
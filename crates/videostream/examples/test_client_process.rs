@@ -1,5 +1,5 @@
 use std::time::Instant;
-use videostream::{client, timestamp};
+use videostream::{client, fourcc::FourCC, timestamp};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let socket_path = std::env::args()
@@ -37,16 +37,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 received += 1;
 
                 let before_metadata = Instant::now();
-                let size = frame.size().unwrap_or(0);
+                let size = frame.used_size().unwrap_or(0);
                 let metadata_duration = before_metadata.elapsed();
                 total_bytes += size as u64;
 
-                // Check if keyframe
-                if received > 0 {
-                    let avg_size = total_bytes / received as u64;
-                    if size as u64 > (avg_size * 3 / 2) {
-                        keyframes += 1;
-                    }
+                // Check if keyframe from the frame's NAL headers
+                if frame.is_keyframe(FourCC(*b"H264")).unwrap_or(false) {
+                    keyframes += 1;
                 }
 
                 let frame_interval = last_frame_time.elapsed();
@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use videostream::{camera, encoder, fourcc::FourCC, frame::Frame, host, timestamp};
 
 fn bitrate_to_profile(bitrate_kbps: u32) -> u8 {
@@ -73,8 +73,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         host.post(output_frame, expires, -1, -1, -1)?;
         let post_time = before_post.elapsed();
 
-        host.poll(100)?;
-        host.process()?;
+        host.service_timeout(Duration::from_millis(100))?;
 
         if i < 5 || i % 20 == 0 {
             println!(
@@ -98,9 +97,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Continue processing for a bit to let client catch up
     println!("[HOST] Waiting for client to finish receiving...");
     for _ in 0..100 {
-        host.poll(10)?;
-        host.process()?;
-        std::thread::sleep(std::time::Duration::from_millis(50));
+        host.service_timeout(Duration::from_millis(10))?;
+        std::thread::sleep(Duration::from_millis(50));
     }
 
     println!("[HOST] Done");
@@ -4,6 +4,8 @@
 use crate::{
     colorimetry::{ColorEncoding, ColorRange, ColorSpace, ColorTransfer},
     fourcc::FourCC,
+    frame::Rect,
+    v4l2::MemoryType,
     Error,
 };
 use dma_buf::DmaBuf;
@@ -11,6 +13,7 @@ use std::{
     ffi::{c_int, CString},
     fmt, io,
     os::fd::{BorrowedFd, RawFd},
+    time::{Duration, Instant},
 };
 use unix_ts::Timestamp;
 use videostream_sys as ffi;
@@ -38,6 +41,69 @@ impl fmt::Display for Mirror {
     }
 }
 
+/// Field order of the captured frames.
+///
+/// Interlaced sources deliver two fields per frame; requesting the order the
+/// source produces avoids combing artifacts from weaving fields in the wrong
+/// order.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Field {
+    /// Let the driver choose the field order
+    #[default]
+    Any,
+    /// Progressive frames, no fields
+    Progressive,
+    /// Interleaved fields, top field captured first
+    InterlacedTopFirst,
+    /// Interleaved fields, bottom field captured first
+    InterlacedBottomFirst,
+}
+
+const V4L2_FIELD_ANY: u32 = 0;
+const V4L2_FIELD_NONE: u32 = 1;
+const V4L2_FIELD_INTERLACED: u32 = 4;
+const V4L2_FIELD_INTERLACED_TB: u32 = 8;
+const V4L2_FIELD_INTERLACED_BT: u32 = 9;
+
+impl Field {
+    fn to_v4l2(self) -> u32 {
+        match self {
+            Field::Any => V4L2_FIELD_ANY,
+            Field::Progressive => V4L2_FIELD_NONE,
+            Field::InterlacedTopFirst => V4L2_FIELD_INTERLACED_TB,
+            Field::InterlacedBottomFirst => V4L2_FIELD_INTERLACED_BT,
+        }
+    }
+
+    /// Maps a negotiated `V4L2_FIELD_*` value, or `None` for field orders
+    /// without a variant (single fields, sequential or alternating fields).
+    fn from_v4l2(field: u32, height: i32) -> Option<Self> {
+        match field {
+            V4L2_FIELD_NONE => Some(Field::Progressive),
+            V4L2_FIELD_INTERLACED_TB => Some(Field::InterlacedTopFirst),
+            V4L2_FIELD_INTERLACED_BT => Some(Field::InterlacedBottomFirst),
+            // The order of V4L2_FIELD_INTERLACED depends on the video
+            // standard: bottom first for 525-line (480 visible) NTSC,
+            // top first otherwise.
+            V4L2_FIELD_INTERLACED if height == 480 => Some(Field::InterlacedBottomFirst),
+            V4L2_FIELD_INTERLACED => Some(Field::InterlacedTopFirst),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Field::Any => write!(f, "any"),
+            Field::Progressive => write!(f, "progressive"),
+            Field::InterlacedTopFirst => write!(f, "interlaced-tb"),
+            Field::InterlacedBottomFirst => write!(f, "interlaced-bt"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Camera {
     /// video device file for the camera
@@ -52,9 +118,18 @@ pub struct Camera {
     /// request camera pixel format, actual format may be different
     format: FourCC,
 
+    /// pixel formats to try in order, overrides `format` when not empty
+    formats: Vec<FourCC>,
+
     /// request mirroring mode, default is none.
     mirror: Mirror,
 
+    /// request field order, actual field order may be different
+    field: Field,
+
+    /// requested buffer memory type, `None` for the library default
+    memory: Option<MemoryType>,
+
     /// number of camera buffers to create
     num_buffers: i32,
 }
@@ -66,7 +141,10 @@ impl Camera {
             width: self.width,
             height: self.height,
             format: self.format,
+            formats: self.formats,
             mirror: self.mirror,
+            field: self.field,
+            memory: self.memory,
             num_buffers: self.num_buffers,
         }
     }
@@ -77,7 +155,10 @@ impl Camera {
             width,
             height,
             format: self.format,
+            formats: self.formats,
             mirror: self.mirror,
+            field: self.field,
+            memory: self.memory,
             num_buffers: self.num_buffers,
         }
     }
@@ -88,7 +169,32 @@ impl Camera {
             width: self.width,
             height: self.height,
             format,
+            formats: Vec::new(),
             mirror: self.mirror,
+            field: self.field,
+            memory: self.memory,
+            num_buffers: self.num_buffers,
+        }
+    }
+
+    /// Requests the first of `formats` that the device supports, in
+    /// priority order, replacing any [`with_format`](Self::with_format)
+    /// request.
+    ///
+    /// The device's formats are enumerated when the camera is opened and
+    /// [`CameraReader::format`] reports the one chosen. Opening fails with
+    /// [`Error::UnsupportedFormat`] for the first entry if the device
+    /// supports none of them. An empty list keeps the current format.
+    pub fn with_formats(self, formats: &[FourCC]) -> Camera {
+        Camera {
+            device: self.device,
+            width: self.width,
+            height: self.height,
+            format: formats.first().copied().unwrap_or(self.format),
+            formats: formats.to_vec(),
+            mirror: self.mirror,
+            field: self.field,
+            memory: self.memory,
             num_buffers: self.num_buffers,
         }
     }
@@ -99,7 +205,24 @@ impl Camera {
             width: self.width,
             height: self.height,
             format: self.format,
+            formats: self.formats,
             mirror,
+            field: self.field,
+            memory: self.memory,
+            num_buffers: self.num_buffers,
+        }
+    }
+
+    pub fn with_field(self, field: Field) -> Camera {
+        Camera {
+            device: self.device,
+            width: self.width,
+            height: self.height,
+            format: self.format,
+            formats: self.formats,
+            mirror: self.mirror,
+            field,
+            memory: self.memory,
             num_buffers: self.num_buffers,
         }
     }
@@ -110,11 +233,42 @@ impl Camera {
             width: self.width,
             height: self.height,
             format: self.format,
+            formats: self.formats,
             mirror: self.mirror,
+            field: self.field,
+            memory: self.memory,
             num_buffers,
         }
     }
 
+    /// Requests how capture buffers are shared, validated when the camera is
+    /// opened and its buffers are allocated.
+    ///
+    /// [`MemoryType::DmaBuf`] exports every buffer as a DMABUF for zero-copy
+    /// hand-off to the encoder and fails to open if the driver cannot export
+    /// one. [`MemoryType::Mmap`] skips the export for CPU-only access, such as
+    /// a USB webcam on x86, so [`CameraBuffer::rawfd`] is -1 and the
+    /// buffers cannot be passed on as DMABUFs or converted to a
+    /// [`Frame`](crate::frame::Frame). Buffers are always driver allocated,
+    /// so [`MemoryType::UserPtr`] is refused.
+    ///
+    /// Opening fails with [`Error::Io`] (`ENOTSUP`) if the driver cannot
+    /// export the buffers for [`MemoryType::DmaBuf`], or
+    /// [`Error::SymbolNotFound`] with a library older than 2.6.
+    pub fn with_memory(self, memory: MemoryType) -> Camera {
+        Camera {
+            device: self.device,
+            width: self.width,
+            height: self.height,
+            format: self.format,
+            formats: self.formats,
+            mirror: self.mirror,
+            field: self.field,
+            memory: Some(memory),
+            num_buffers: self.num_buffers,
+        }
+    }
+
     pub fn open(self) -> Result<CameraReader, Error> {
         CameraReader::init(self)
     }
@@ -123,33 +277,41 @@ impl Camera {
         let device_str_c = CString::new(self.device)?;
         let ptr = vsl!(vsl_camera_open_device(device_str_c.as_ptr()));
         if ptr.is_null() {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_camera_open_device");
             return Err(err.into());
         }
 
-        const MAX_FORMATS: usize = 20;
-        let mut formats: [u32; MAX_FORMATS] = [0; MAX_FORMATS];
-        let n_formats = match c_int::try_from(MAX_FORMATS) {
-            Ok(val) => val,
-            Err(err) => {
-                vsl!(vsl_camera_close_device(ptr));
-                return Err(err.into());
-            }
-        };
-
-        let cnt = vsl!(vsl_camera_enum_fmts(ptr, formats.as_mut_ptr(), n_formats));
+        let fmts = enum_formats(ptr);
         vsl!(vsl_camera_close_device(ptr));
+        fmts
+    }
+}
 
-        let u_cnt = usize::try_from(cnt)?;
-        let mut fmts: CameraFormats = CameraFormats::with_capacity(u_cnt);
+/// Lists the pixel formats of an opened camera device.
+fn enum_formats(ptr: *mut ffi::vsl_camera) -> Result<CameraFormats, Error> {
+    const MAX_FORMATS: usize = 20;
+    let mut formats: [u32; MAX_FORMATS] = [0; MAX_FORMATS];
+    let n_formats = c_int::try_from(MAX_FORMATS)?;
 
-        for i in 0..cnt {
-            let idx = usize::try_from(i)?;
-            fmts.push(FourCC::from(formats[idx]));
-        }
+    let cnt = vsl!(vsl_camera_enum_fmts(ptr, formats.as_mut_ptr(), n_formats));
+
+    let u_cnt = usize::try_from(cnt)?;
+    let mut fmts: CameraFormats = CameraFormats::with_capacity(u_cnt);
 
-        Ok(fmts)
+    for i in 0..cnt {
+        let idx = usize::try_from(i)?;
+        fmts.push(FourCC::from(formats[idx]));
     }
+
+    Ok(fmts)
+}
+
+/// Returns the first of `preferred` that appears in `supported`.
+fn negotiate_format(preferred: &[FourCC], supported: &[FourCC]) -> Option<FourCC> {
+    preferred
+        .iter()
+        .copied()
+        .find(|fourcc| supported.contains(fourcc))
 }
 
 impl Default for Camera {
@@ -159,7 +321,10 @@ impl Default for Camera {
             width: 1920,
             height: 1080,
             format: FourCC(*b"YUYV"),
+            formats: Vec::new(),
             mirror: Mirror::None,
+            field: Field::Any,
+            memory: None,
             num_buffers: 4,
         }
     }
@@ -169,6 +334,28 @@ pub fn create_camera() -> Camera {
     Camera::default()
 }
 
+/// Capture format requested from the driver and the format it negotiated,
+/// as `(width, height, fourcc)`, returned by [`CameraReader::negotiation`].
+///
+/// V4L2 drivers adjust an unsupported request to the nearest format they
+/// support instead of failing, so the two may differ.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Negotiation {
+    /// Format passed to the driver: the camera builder's resolution and the
+    /// fourcc chosen from [`Camera::with_formats`] or [`Camera::with_format`].
+    pub requested: (i32, i32, FourCC),
+    /// Format the driver actually configured.
+    pub actual: (i32, i32, FourCC),
+}
+
+impl Negotiation {
+    /// Returns `true` if the driver substituted a different resolution or
+    /// pixel format for the requested one.
+    pub fn is_substituted(&self) -> bool {
+        self.requested != self.actual
+    }
+}
+
 #[derive(Debug)]
 pub struct CameraReader {
     ptr: *mut ffi::vsl_camera,
@@ -176,6 +363,7 @@ pub struct CameraReader {
     height: i32,
     format: FourCC,
     mirror: Mirror,
+    requested: (i32, i32, FourCC),
 }
 
 impl CameraReader {
@@ -183,7 +371,7 @@ impl CameraReader {
         let device_str_c = CString::new(camera.device)?;
         let ptr = vsl!(vsl_camera_open_device(device_str_c.as_ptr()));
         if ptr.is_null() {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_camera_open_device");
             return Err(err.into());
         }
 
@@ -192,6 +380,51 @@ impl CameraReader {
         let mut num_buffers: c_int = camera.num_buffers;
         let mut format: u32 = camera.format.into();
 
+        if !camera.formats.is_empty() {
+            let supported = match enum_formats(ptr) {
+                Ok(supported) => supported,
+                Err(err) => {
+                    vsl!(vsl_camera_close_device(ptr));
+                    return Err(err);
+                }
+            };
+            match negotiate_format(&camera.formats, &supported) {
+                Some(fourcc) => format = fourcc.into(),
+                None => {
+                    vsl!(vsl_camera_close_device(ptr));
+                    return Err(Error::UnsupportedFormat(camera.format));
+                }
+            }
+        }
+
+        // Only requires a 2.6 library when a field order is requested
+        if camera.field != Field::Any {
+            let lib = ffi::init()?;
+            let Ok(set_field) = lib.vsl_camera_set_field.as_ref() else {
+                vsl!(vsl_camera_close_device(ptr));
+                return Err(Error::SymbolNotFound("vsl_camera_set_field"));
+            };
+            if unsafe { set_field(ptr, camera.field.to_v4l2()) } != 0 {
+                let err = crate::last_os_error("vsl_camera_set_field");
+                vsl!(vsl_camera_close_device(ptr));
+                return Err(err.into());
+            }
+        }
+
+        if let Some(memory) = camera.memory {
+            let lib = ffi::init()?;
+            let Ok(set_memory) = lib.vsl_camera_set_memory.as_ref() else {
+                vsl!(vsl_camera_close_device(ptr));
+                return Err(Error::SymbolNotFound("vsl_camera_set_memory"));
+            };
+            if unsafe { set_memory(ptr, memory as u32) } != 0 {
+                let err = crate::last_os_error("vsl_camera_set_memory");
+                vsl!(vsl_camera_close_device(ptr));
+                return Err(err.into());
+            }
+        }
+
+        let requested = (width, height, FourCC::from(format));
         if vsl!(vsl_camera_init_device(
             ptr,
             &mut width,
@@ -200,7 +433,7 @@ impl CameraReader {
             &mut format
         )) != 0
         {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_camera_init_device");
             return Err(err.into());
         }
 
@@ -210,8 +443,23 @@ impl CameraReader {
             height,
             format: FourCC::from(format),
             mirror: camera.mirror,
+            requested,
         };
 
+        let negotiation = cam.negotiation();
+        if negotiation.is_substituted() {
+            let (width, height, format) = negotiation.requested;
+            log::warn!(
+                "camera {}x{} {} requested, driver negotiated {}x{} {}",
+                width,
+                height,
+                format,
+                cam.width,
+                cam.height,
+                cam.format
+            );
+        }
+
         match cam.mirror {
             Mirror::None => {
                 cam.set_mirror_h(false)?;
@@ -236,7 +484,7 @@ impl CameraReader {
 
     pub fn start(&self) -> Result<(), Error> {
         if vsl!(vsl_camera_start_capturing(self.ptr)) != 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_camera_start_capturing");
             return Err(err.into());
         }
 
@@ -245,7 +493,7 @@ impl CameraReader {
 
     pub fn stop(&self) -> Result<(), Error> {
         if vsl!(vsl_camera_stop_capturing(self.ptr)) != 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_camera_stop_capturing");
             return Err(err.into());
         }
 
@@ -254,7 +502,7 @@ impl CameraReader {
 
     fn set_mirror_h(&self, enable: bool) -> Result<(), Error> {
         if vsl!(vsl_camera_mirror(self.ptr, enable)) != 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_camera_mirror");
             return Err(err.into());
         }
 
@@ -263,7 +511,7 @@ impl CameraReader {
 
     fn set_mirror_v(&self, enable: bool) -> Result<(), Error> {
         if vsl!(vsl_camera_mirror_v(self.ptr, enable)) != 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_camera_mirror_v");
             return Err(err.into());
         }
 
@@ -311,6 +559,18 @@ impl CameraReader {
         self.format
     }
 
+    /// Returns the format requested from the driver alongside the one it
+    /// negotiated.
+    ///
+    /// A warning is logged when the camera opens if the two differ; use
+    /// [`Negotiation::is_substituted`] to reject a substitution instead.
+    pub fn negotiation(&self) -> Negotiation {
+        Negotiation {
+            requested: self.requested,
+            actual: (self.width, self.height, self.format),
+        }
+    }
+
     /// Returns the negotiated color primaries (`color_space` in the
     /// EdgeFirst [`CameraFrame.msg`][msg] schema), captured from the
     /// V4L2 format at `init` time.
@@ -412,15 +672,131 @@ impl CameraReader {
         }))
     }
 
+    /// Returns the field order negotiated with the driver at `init` time.
+    ///
+    /// Drivers may substitute their own field order for the one requested
+    /// with [`Camera::with_field`], so check this before processing frames
+    /// as progressive. Returns `Ok(None)` if the driver reported a field
+    /// order without a [`Field`] variant, such as alternating or
+    /// sequential fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the loaded `libvideostream.so`
+    /// predates 2.6 and does not export `vsl_camera_field`.
+    pub fn field(&self) -> Result<Option<Field>, Error> {
+        let lib = ffi::init()?;
+        let field = lib
+            .vsl_camera_field
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_camera_field"))?;
+        Ok(Field::from_v4l2(unsafe { field(self.ptr) }, self.height))
+    }
+
+    /// Sets the sensor crop rectangle (region of interest) and returns the
+    /// rectangle actually applied by the driver.
+    ///
+    /// The rectangle is in sensor coordinates and must lie within the
+    /// driver's crop bounds. Drivers may round it to hardware constraints, so
+    /// callers should use the returned rectangle rather than `rect`.
+    /// Depending on the driver the cropped region is either delivered at its
+    /// own size or scaled to the configured resolution; [`width`](Self::width),
+    /// [`height`](Self::height) and the buffers' stride are refreshed from the
+    /// driver either way. Most drivers reject size changes while streaming,
+    /// so call this before [`start`](Self::start).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the loaded `libvideostream.so`
+    /// predates 2.6 and does not export `vsl_camera_set_selection`, or
+    /// [`Error::Io`] if the rectangle is empty or outside the crop bounds
+    /// (`EINVAL`) or the driver does not support cropping (`ENOTTY`).
+    pub fn set_crop(&mut self, rect: Rect) -> Result<Rect, Error> {
+        let lib = ffi::init()?;
+        let set_selection = lib
+            .vsl_camera_set_selection
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_camera_set_selection"))?;
+        let get_format = lib
+            .vsl_camera_get_format
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_camera_get_format"))?;
+        let mut rect: ffi::VSLRect = rect.into();
+        if unsafe { set_selection(self.ptr, &mut rect) } != 0 {
+            return Err(crate::last_os_error("vsl_camera_set_selection").into());
+        }
+
+        let (mut width, mut height): (c_int, c_int) = (0, 0);
+        if unsafe { get_format(self.ptr, &mut width, &mut height, std::ptr::null_mut()) } != 0 {
+            return Err(crate::last_os_error("vsl_camera_get_format").into());
+        }
+        self.width = width;
+        self.height = height;
+        Ok(rect.into())
+    }
+
+    /// Returns the current sensor crop rectangle.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the loaded `libvideostream.so`
+    /// predates 2.6 and does not export `vsl_camera_get_selection`, or
+    /// [`Error::Io`] if the driver does not support cropping (`ENOTTY`).
+    pub fn get_crop(&self) -> Result<Rect, Error> {
+        let lib = ffi::init()?;
+        let get_selection = lib
+            .vsl_camera_get_selection
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_camera_get_selection"))?;
+        let mut rect = ffi::VSLRect {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+        };
+        if unsafe { get_selection(self.ptr, &mut rect) } != 0 {
+            return Err(crate::last_os_error("vsl_camera_get_selection").into());
+        }
+        Ok(rect.into())
+    }
+
     pub fn read(&self) -> Result<CameraBuffer<'_>, Error> {
         let ptr = vsl!(vsl_camera_get_data(self.ptr));
         if ptr.is_null() {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_camera_get_data");
             return Err(err.into());
         }
 
         CameraBuffer::new(ptr, self)
     }
+
+    /// Reads the next buffer, returning `None` if the driver had none ready.
+    ///
+    /// Waits for the camera as [`read`](Self::read) does, but some drivers
+    /// signal readiness and then fail to dequeue with `EAGAIN`; this reports
+    /// that as `None` so poll-driven capture loops can simply try again.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with `ETIMEDOUT` if no frame arrived within two
+    /// seconds, or the driver's error if dequeuing fails otherwise.
+    pub fn try_read(&self) -> Result<Option<CameraBuffer<'_>>, Error> {
+        let ptr = vsl!(vsl_camera_get_data(self.ptr));
+        if ptr.is_null() {
+            return none_if_would_block(crate::last_os_error("vsl_camera_get_data"));
+        }
+
+        CameraBuffer::new(ptr, self).map(Some)
+    }
+}
+
+/// Maps a failed dequeue to `Ok(None)` when no buffer was ready.
+fn none_if_would_block<T>(err: io::Error) -> Result<Option<T>, Error> {
+    if err.kind() == io::ErrorKind::WouldBlock {
+        Ok(None)
+    } else {
+        Err(err.into())
+    }
 }
 
 impl Drop for CameraReader {
@@ -440,6 +816,8 @@ pub struct CameraBuffer<'a> {
     raw_fd: RawFd,
     ptr: *mut ffi::vsl_camera_buffer,
     parent: &'a CameraReader,
+    /// Position of the camera in the [`CameraGroup`] that read the buffer
+    source_id: Option<u32>,
 }
 
 impl CameraBuffer<'_> {
@@ -452,6 +830,7 @@ impl CameraBuffer<'_> {
             raw_fd: original_fd,
             ptr,
             parent,
+            source_id: None,
         })
     }
 
@@ -547,6 +926,15 @@ impl CameraBuffer<'_> {
         Ok(unsafe { lib.vsl_camera_buffer_sequence(self.ptr) })
     }
 
+    /// Returns the position of the camera in the [`CameraGroup`] that read
+    /// this buffer, or `None` for buffers read from a single camera.
+    ///
+    /// Frames created from the buffer carry it as their
+    /// [`source_id`](crate::frame::Frame::source_id).
+    pub fn source_id(&self) -> Option<u32> {
+        self.source_id
+    }
+
     pub fn width(&self) -> i32 {
         self.parent.width()
     }
@@ -574,12 +962,8 @@ impl Drop for CameraBuffer<'_> {
         if let Ok(lib) = ffi::init() {
             let ret = unsafe { lib.vsl_camera_release_buffer(self.parent.ptr, self.ptr) };
             if ret != 0 {
-                let err = std::io::Error::last_os_error();
-                log::warn!(
-                    "CameraBuffer::drop() - vsl_camera_release_buffer failed: ret={}, errno={:?}",
-                    ret,
-                    err
-                );
+                let err = crate::last_os_error("vsl_camera_release_buffer");
+                log::warn!("CameraBuffer::drop() - ret={}: {}", ret, err);
             } else {
                 log::trace!("CameraBuffer::drop() - buffer released successfully");
             }
@@ -601,11 +985,375 @@ impl fmt::Display for CameraBuffer<'_> {
     }
 }
 
+/// Several cameras captured together, for stereo and multi-camera rigs.
+///
+/// [`CameraGroup::read_synced`] returns one buffer per camera, advancing
+/// whichever camera lags until the capture timestamps agree within the
+/// group's tolerance. This aligns free-running sensors to the nearest frame
+/// but cannot make them expose at the same instant: true hardware sync
+/// depends on the sensors sharing a trigger or master/slave configuration.
+/// Without it, expect a residual skew of up to half a frame interval, which
+/// callers can measure with [`CameraGroup::skew`].
+///
+/// Timestamps are only comparable when every driver reports the same clock,
+/// normally `CLOCK_MONOTONIC`.
+///
+/// # Example
+///
+/// ```no_run
+/// use videostream::camera::{create_camera, CameraGroup};
+///
+/// let group = CameraGroup::open([
+///     create_camera().with_device("/dev/video0"),
+///     create_camera().with_device("/dev/video1"),
+/// ])?;
+/// group.start()?;
+/// let buffers = group.read_synced()?;
+/// for buffer in &buffers {
+///     println!("{} at {}", buffer, buffer.timestamp()?);
+/// }
+/// println!("skew {:?}", CameraGroup::skew(&buffers)?);
+/// # Ok::<(), videostream::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct CameraGroup {
+    readers: Vec<CameraReader>,
+    tolerance: Duration,
+    max_reads: usize,
+}
+
+impl CameraGroup {
+    /// Default timestamp tolerance for [`CameraGroup::read_synced`].
+    pub const DEFAULT_TOLERANCE: Duration = Duration::from_millis(5);
+
+    /// Opens every camera in order, closing those already opened if one
+    /// fails.
+    pub fn open(cameras: impl IntoIterator<Item = Camera>) -> Result<Self, Error> {
+        let readers = cameras
+            .into_iter()
+            .map(Camera::open)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_readers(readers))
+    }
+
+    /// Groups cameras that are already open.
+    pub fn from_readers(readers: Vec<CameraReader>) -> Self {
+        let max_reads = 4 * readers.len();
+        CameraGroup {
+            readers,
+            tolerance: Self::DEFAULT_TOLERANCE,
+            max_reads,
+        }
+    }
+
+    /// Sets the largest timestamp spread [`CameraGroup::read_synced`]
+    /// accepts before advancing the lagging camera.
+    pub fn with_tolerance(self, tolerance: Duration) -> Self {
+        CameraGroup { tolerance, ..self }
+    }
+
+    /// Sets how many extra frames [`CameraGroup::read_synced`] may read
+    /// while aligning before it returns the best set it has. Defaults to
+    /// four per camera.
+    pub fn with_max_reads(self, max_reads: usize) -> Self {
+        CameraGroup { max_reads, ..self }
+    }
+
+    pub fn readers(&self) -> &[CameraReader] {
+        &self.readers
+    }
+
+    pub fn len(&self) -> usize {
+        self.readers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.readers.is_empty()
+    }
+
+    /// Starts capturing on every camera.
+    pub fn start(&self) -> Result<(), Error> {
+        self.readers.iter().try_for_each(CameraReader::start)
+    }
+
+    /// Stops capturing on every camera, returning the first error after
+    /// attempting them all.
+    pub fn stop(&self) -> Result<(), Error> {
+        let mut result = Ok(());
+        for reader in &self.readers {
+            if let Err(err) = reader.stop() {
+                if result.is_ok() {
+                    result = Err(err);
+                }
+            }
+        }
+        result
+    }
+
+    /// Reads one buffer from each camera, in group order, aligned on their
+    /// capture timestamps.
+    ///
+    /// Each buffer records its camera's position in the group as its
+    /// [`source_id`](CameraBuffer::source_id), which frames created from it
+    /// carry to clients.
+    ///
+    /// While the spread between the oldest and newest timestamp exceeds the
+    /// tolerance, the oldest buffer is released and replaced by the next
+    /// frame from its camera, which also discards frames queued before the
+    /// call. If the cameras cannot be aligned within the read budget the
+    /// last set read is returned, which is not necessarily the closest one
+    /// since earlier buffers were already released; check
+    /// [`CameraGroup::skew`].
+    pub fn read_synced(&self) -> Result<Vec<CameraBuffer<'_>>, Error> {
+        let mut buffers = (0..self.readers.len())
+            .map(|index| self.read_camera(index))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut timestamps = buffers
+            .iter()
+            .map(CameraBuffer::timestamp)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for _ in 0..self.max_reads {
+            let Some(oldest) = lagging(&timestamps, self.tolerance) else {
+                break;
+            };
+            // Release the stale buffer before reading so cameras opened
+            // with few buffers do not run dry.
+            drop(buffers.remove(oldest));
+            let buffer = self.read_camera(oldest)?;
+            timestamps[oldest] = buffer.timestamp()?;
+            buffers.insert(oldest, buffer);
+        }
+
+        Ok(buffers)
+    }
+
+    /// Reads the next buffer of camera `index`, stamped with the index so
+    /// frames created from it identify their camera.
+    fn read_camera(&self, index: usize) -> Result<CameraBuffer<'_>, Error> {
+        let mut buffer = self.readers[index].read()?;
+        buffer.source_id = Some(u32::try_from(index)?);
+        Ok(buffer)
+    }
+
+    /// Returns the spread between the oldest and newest capture timestamp
+    /// of `buffers`.
+    pub fn skew(buffers: &[CameraBuffer<'_>]) -> Result<Duration, Error> {
+        let timestamps = buffers
+            .iter()
+            .map(CameraBuffer::timestamp)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(spread(&timestamps))
+    }
+}
+
+/// Returns the time between the oldest and newest timestamp.
+fn spread(timestamps: &[Timestamp]) -> Duration {
+    match (timestamps.iter().min(), timestamps.iter().max()) {
+        (Some(min), Some(max)) => {
+            let nanos = max.at_precision(9) - min.at_precision(9);
+            Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+        }
+        _ => Duration::ZERO,
+    }
+}
+
+/// Returns the index of the oldest timestamp if the spread exceeds
+/// `tolerance`.
+fn lagging(timestamps: &[Timestamp], tolerance: Duration) -> Option<usize> {
+    if spread(timestamps) <= tolerance {
+        return None;
+    }
+    timestamps
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, ts)| **ts)
+        .map(|(index, _)| index)
+}
+
+/// Restarts a camera whose capture stalls without reporting an error.
+///
+/// Some drivers occasionally stop delivering frames after hours of capture
+/// while every call still succeeds or merely times out. The watchdog reads
+/// through a [`CameraReader`] and, once no frame has arrived for the
+/// configured timeout, stops capture, closes the device and opens it again
+/// from the original [`Camera`] settings, logging each restart.
+///
+/// Stalls are noticed when a read gives up, which the library does after
+/// two seconds without a frame, so a timeout shorter than that is rounded
+/// up to it in practice.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use videostream::camera::{create_camera, CameraWatchdog};
+///
+/// let camera = create_camera().with_device("/dev/video3");
+/// let mut watchdog = CameraWatchdog::new(camera, Duration::from_secs(5))?;
+/// for _ in 0..1000 {
+///     let buffer = watchdog.read()?;
+///     println!("{}", buffer);
+/// }
+/// println!("restarted {} times", watchdog.restart_count());
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub struct CameraWatchdog {
+    watchdog: Watchdog<CameraReader>,
+}
+
+impl CameraWatchdog {
+    /// Opens and starts `camera`, restarting it whenever no frame arrives
+    /// within `timeout`.
+    pub fn new(camera: Camera, timeout: Duration) -> Result<Self, Error> {
+        let open = move || {
+            let reader = camera.clone().open()?;
+            reader.start()?;
+            Ok(reader)
+        };
+        Ok(CameraWatchdog {
+            watchdog: Watchdog::new(Box::new(open), timeout)?,
+        })
+    }
+
+    /// Reads the next buffer, restarting the camera first if it stalled.
+    ///
+    /// # Errors
+    ///
+    /// Returns the driver's error if dequeuing fails for another reason than
+    /// a timeout, or the error from reopening the camera. The next call
+    /// tries to reopen it again.
+    pub fn read(&mut self) -> Result<CameraBuffer<'_>, Error> {
+        let ptr = self.watchdog.next()?;
+        let reader = self.watchdog.source.as_ref().ok_or(Error::NullPointer)?;
+        CameraBuffer::new(ptr, reader)
+    }
+
+    /// Returns the current reader, or `None` if reopening it failed.
+    pub fn reader(&self) -> Option<&CameraReader> {
+        self.watchdog.source.as_ref()
+    }
+
+    /// Returns how many times the camera was restarted after a stall.
+    pub fn restart_count(&self) -> u64 {
+        self.watchdog.restarts
+    }
+
+    /// Returns how long a read waits for a frame before restarting the
+    /// camera.
+    pub fn timeout(&self) -> Duration {
+        self.watchdog.timeout
+    }
+}
+
+impl fmt::Debug for CameraWatchdog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CameraWatchdog")
+            .field("reader", &self.watchdog.source)
+            .field("timeout", &self.watchdog.timeout)
+            .field("restarts", &self.watchdog.restarts)
+            .finish()
+    }
+}
+
+/// Frame source supervised by a [`Watchdog`].
+trait Capture {
+    /// Dequeued frame, not tied to the lifetime of the source.
+    type Raw;
+
+    /// Dequeues the next frame, or `None` if none arrived in time.
+    fn dequeue(&self) -> Result<Option<Self::Raw>, Error>;
+
+    fn stop(&self) -> Result<(), Error>;
+}
+
+impl Capture for CameraReader {
+    type Raw = *mut ffi::vsl_camera_buffer;
+
+    fn dequeue(&self) -> Result<Option<Self::Raw>, Error> {
+        let ptr = vsl!(vsl_camera_get_data(self.ptr));
+        if !ptr.is_null() {
+            return Ok(Some(ptr));
+        }
+        let err = crate::last_os_error("vsl_camera_get_data");
+        if err.kind() == io::ErrorKind::TimedOut {
+            return Ok(None);
+        }
+        none_if_would_block(err)
+    }
+
+    fn stop(&self) -> Result<(), Error> {
+        CameraReader::stop(self)
+    }
+}
+
+type Opener<C> = Box<dyn FnMut() -> Result<C, Error>>;
+
+/// Restart logic of [`CameraWatchdog`], generic over the source so it can
+/// be tested without a camera.
+struct Watchdog<C> {
+    /// `None` after reopening the source failed
+    source: Option<C>,
+    open: Opener<C>,
+    timeout: Duration,
+    last_frame: Instant,
+    restarts: u64,
+}
+
+impl<C: Capture> Watchdog<C> {
+    fn new(mut open: Opener<C>, timeout: Duration) -> Result<Self, Error> {
+        let source = open()?;
+        Ok(Watchdog {
+            source: Some(source),
+            open,
+            timeout,
+            last_frame: Instant::now(),
+            restarts: 0,
+        })
+    }
+
+    fn next(&mut self) -> Result<C::Raw, Error> {
+        loop {
+            match &self.source {
+                None => self.reopen()?,
+                Some(_) if self.last_frame.elapsed() >= self.timeout => self.restart()?,
+                Some(source) => {
+                    if let Some(raw) = source.dequeue()? {
+                        self.last_frame = Instant::now();
+                        return Ok(raw);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Stops and closes the stalled source, then opens it again.
+    fn restart(&mut self) -> Result<(), Error> {
+        log::warn!(
+            "camera stalled, no frame for {:?}; restarting capture",
+            self.last_frame.elapsed()
+        );
+        if let Some(source) = self.source.take() {
+            if let Err(err) = source.stop() {
+                log::warn!("failed to stop stalled camera: {}", err);
+            }
+        }
+        self.restarts += 1;
+        self.reopen()
+    }
+
+    fn reopen(&mut self) -> Result<(), Error> {
+        self.source = Some((self.open)()?);
+        self.last_frame = Instant::now();
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serial_test::serial;
-    use std::time::Instant;
+    use std::{cell::Cell, rc::Rc};
 
     /// Get the camera device path from environment variable or use default.
     /// Set VSL_CAMERA_DEVICE to override (e.g., "/dev/video0" on i.MX8MP EVK).
@@ -613,6 +1361,154 @@ mod tests {
         std::env::var("VSL_CAMERA_DEVICE").unwrap_or_else(|_| "/dev/video3".to_string())
     }
 
+    #[test]
+    fn test_camera_group_alignment() {
+        let ts = |ms| Timestamp::from_millis(ms);
+        assert_eq!(spread(&[]), Duration::ZERO);
+        assert_eq!(spread(&[ts(1000)]), Duration::ZERO);
+        assert_eq!(
+            spread(&[ts(1033), ts(1000), ts(1016)]),
+            Duration::from_millis(33)
+        );
+
+        let tolerance = Duration::from_millis(5);
+        assert_eq!(lagging(&[], tolerance), None);
+        assert_eq!(lagging(&[ts(1000), ts(1004)], tolerance), None);
+        assert_eq!(lagging(&[ts(1033), ts(1000)], tolerance), Some(1));
+        assert_eq!(lagging(&[ts(1000), ts(1033), ts(1016)], tolerance), Some(0));
+    }
+
+    /// Source that delivers `frames` frames and then stalls.
+    struct MockCapture {
+        id: usize,
+        frames: Cell<usize>,
+        stopped: Rc<Cell<bool>>,
+    }
+
+    impl Capture for MockCapture {
+        type Raw = (usize, usize);
+
+        fn dequeue(&self) -> Result<Option<Self::Raw>, Error> {
+            let left = self.frames.get();
+            if left == 0 {
+                // A stalled driver blocks until its read timeout
+                std::thread::sleep(Duration::from_millis(5));
+                return Ok(None);
+            }
+            self.frames.set(left - 1);
+            Ok(Some((self.id, left)))
+        }
+
+        fn stop(&self) -> Result<(), Error> {
+            self.stopped.set(true);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_camera_watchdog_restarts_stalled_capture() {
+        let opened = Rc::new(Cell::new(0));
+        let stopped = Rc::new(Cell::new(false));
+        let open = {
+            let (opened, stopped) = (opened.clone(), stopped.clone());
+            move || {
+                let id = opened.get();
+                opened.set(id + 1);
+                // The first source stalls after three frames, the second
+                // keeps delivering
+                let frames = if id == 0 { 3 } else { usize::MAX };
+                Ok(MockCapture {
+                    id,
+                    frames: Cell::new(frames),
+                    stopped: stopped.clone(),
+                })
+            }
+        };
+
+        let mut watchdog = Watchdog::new(Box::new(open), Duration::from_millis(50)).unwrap();
+        let ids: Vec<usize> = (0..6).map(|_| watchdog.next().unwrap().0).collect();
+
+        assert_eq!(ids, [0, 0, 0, 1, 1, 1]);
+        assert_eq!(watchdog.restarts, 1);
+        assert_eq!(opened.get(), 2);
+        assert!(stopped.get(), "stalled source was not stopped");
+    }
+
+    #[ignore = "test requires camera hardware (run with --include-ignored to enable)"]
+    #[test]
+    #[serial]
+    fn test_camera_group_read_synced() -> Result<(), Error> {
+        // Set VSL_CAMERA_DEVICE2 to the second camera of the rig
+        let devices = [
+            get_camera_device(),
+            std::env::var("VSL_CAMERA_DEVICE2").unwrap_or_else(|_| "/dev/video4".to_string()),
+        ];
+        let group = CameraGroup::open(devices.iter().map(|device| {
+            create_camera()
+                .with_device(device)
+                .with_resolution(640, 480)
+        }))?
+        .with_tolerance(Duration::from_millis(20));
+        assert_eq!(group.len(), 2);
+        group.start()?;
+
+        for _ in 0..10 {
+            let buffers = group.read_synced()?;
+            assert_eq!(buffers.len(), 2);
+            let skew = CameraGroup::skew(&buffers)?;
+            println!(
+                "{} / {} skew {:?}",
+                buffers[0].timestamp()?,
+                buffers[1].timestamp()?,
+                skew
+            );
+            assert!(skew <= Duration::from_millis(20), "skew {:?}", skew);
+        }
+
+        group.stop()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_read_would_block() {
+        let empty = none_if_would_block::<()>(io::Error::from_raw_os_error(libc::EAGAIN));
+        assert!(matches!(empty, Ok(None)));
+
+        let err = none_if_would_block::<()>(io::Error::from_raw_os_error(libc::ETIMEDOUT));
+        assert!(matches!(err, Err(Error::Io(e)) if e.raw_os_error() == Some(libc::ETIMEDOUT)));
+    }
+
+    #[test]
+    fn test_negotiate_format() {
+        let nv12 = FourCC(*b"NV12");
+        let yuyv = FourCC(*b"YUYV");
+        let mjpg = FourCC(*b"MJPG");
+        assert_eq!(negotiate_format(&[nv12, yuyv], &[yuyv, nv12]), Some(nv12));
+        assert_eq!(negotiate_format(&[nv12, yuyv], &[mjpg, yuyv]), Some(yuyv));
+        assert_eq!(negotiate_format(&[nv12], &[mjpg, yuyv]), None);
+        assert_eq!(negotiate_format(&[], &[yuyv]), None);
+
+        let camera = create_camera().with_formats(&[nv12, yuyv]);
+        assert_eq!(camera.format, nv12);
+        assert_eq!(camera.with_format(mjpg).formats, Vec::new());
+    }
+
+    #[ignore = "test requires camera hardware (run with --include-ignored to enable)"]
+    #[test]
+    #[serial]
+    fn test_with_formats() -> Result<(), Error> {
+        let device = get_camera_device();
+        let preferred = [FourCC(*b"NV12"), FourCC(*b"YUYV")];
+        let cam = create_camera()
+            .with_device(&device)
+            .with_formats(&preferred)
+            .open()?;
+        println!("negotiated format {}", cam.format());
+        assert!(preferred.contains(&cam.format()));
+
+        Ok(())
+    }
+
     #[ignore = "test requires camera hardware (run with --include-ignored to enable)"]
     #[test]
     #[serial]
@@ -656,6 +1552,30 @@ mod tests {
         Ok(())
     }
 
+    #[ignore = "test requires camera hardware (run with --include-ignored to enable)"]
+    #[test]
+    #[serial]
+    fn test_negotiation_substitution() -> Result<(), Error> {
+        let device = get_camera_device();
+        // No sensor captures at 123x77, so the driver must substitute
+        let cam = create_camera()
+            .with_device(&device)
+            .with_resolution(123, 77)
+            .open()?;
+        let negotiation = cam.negotiation();
+        println!("{:?}", negotiation);
+        assert!(negotiation.is_substituted());
+        assert_eq!(negotiation.requested.0, 123);
+        assert_eq!(negotiation.requested.1, 77);
+        assert_eq!(
+            negotiation.actual,
+            (cam.width(), cam.height(), cam.format())
+        );
+        assert_ne!((negotiation.actual.0, negotiation.actual.1), (123, 77));
+
+        Ok(())
+    }
+
     /// Verifies that `CameraBuffer::bytes_per_line()` returns the
     /// driver-negotiated row stride (EDGEAI-1239). On Vivante/Mali-aligned
     /// capture drivers this is strictly >= width and may exceed `width * bpp`
@@ -721,63 +1641,172 @@ mod tests {
             let buf = cam.read()?;
 
             let now = Instant::now();
-            let dma = buf.dmabuf()?;
-            let mem = dma.memory_map().map_err(|e| {
-                Error::Io(io::Error::new(
-                    io::ErrorKind::Other,
-                    format!("DMA map error: {}", e),
-                ))
-            })?;
-            let stats = mem
-                .read(pixel_metrics_boxed, Some((buf.width(), buf.height())))
-                .map_err(|e| {
-                    Error::Io(io::Error::new(
-                        io::ErrorKind::Other,
-                        format!("DMA read error: {}", e),
-                    ))
-                })?;
+            let stats = crate::frame::Frame::try_from(&buf)?.luma_stats()?;
             let elapsed = now.elapsed();
 
             println!(
-                "camera y-component min {} max {} avg {} [elapsed: {:.2?}]",
-                stats.0, stats.1, stats.2, elapsed
+                "camera y-component min {} max {} avg {:.1} [elapsed: {:.2?}]",
+                stats.min, stats.max, stats.mean, elapsed
             );
         }
 
         Ok(())
     }
 
-    fn pixel_metrics_boxed(
-        img: &[u8],
-        dim: Option<(i32, i32)>,
-    ) -> Result<(u8, u8, u8), Box<dyn std::error::Error>> {
-        pixel_metrics(img, dim).map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
-    }
+    #[ignore = "test requires camera hardware (run with --include-ignored to enable)"]
+    #[test]
+    #[serial]
+    fn test_buffer_sequence() -> Result<(), Error> {
+        let device = get_camera_device();
+        println!("Using camera device: {}", device);
 
-    fn pixel_metrics(img: &[u8], dim: Option<(i32, i32)>) -> Result<(u8, u8, u8), Error> {
-        let width = dim.unwrap_or_default().0;
-        let height = dim.unwrap_or_default().1;
+        let cam = create_camera().with_device(&device).open()?;
+        cam.start()?;
 
-        let mut y_min = 255;
-        let mut y_max = 0;
-        let mut y_avg = 0;
+        // Each buffer is released before the next read, so the driver has
+        // a free buffer for every frame and should not drop any
+        let mut sequences = Vec::new();
+        for _ in 0..30 {
+            sequences.push(cam.read()?.sequence()?);
+        }
+        println!("sequences: {:?}", sequences);
 
-        for y in 0..height {
-            for x in (0..width).step_by(2) {
-                let y = img[(y * width + x) as usize];
-                if y < y_min {
-                    y_min = y;
-                }
-                if y > y_max {
-                    y_max = y;
-                }
-                y_avg += y as i32;
-            }
+        if sequences.iter().all(|&seq| seq == 0) {
+            println!("driver does not populate the sequence number, skipping");
+            return Ok(());
+        }
+        for pair in sequences.windows(2) {
+            assert_eq!(
+                pair[1],
+                pair[0].wrapping_add(1),
+                "driver dropped frames between consecutive reads"
+            );
         }
+        Ok(())
+    }
 
-        y_avg /= width * height / 2;
+    #[ignore = "test requires camera hardware (run with --include-ignored to enable)"]
+    #[test]
+    #[serial]
+    fn test_field_progressive() -> Result<(), Error> {
+        let device = get_camera_device();
+        println!("Using camera device: {}", device);
+
+        let cam = create_camera()
+            .with_device(&device)
+            .with_resolution(640, 480)
+            .with_field(Field::Progressive)
+            .open()?;
+
+        // Camera sensors are progressive; interlaced sources such as video
+        // decoders may substitute their native order.
+        let field = cam.field()?;
+        println!("requested progressive, negotiated {:?}", field);
+        assert_eq!(field, Some(Field::Progressive));
+        Ok(())
+    }
+
+    #[ignore = "test requires camera hardware (run with --include-ignored to enable)"]
+    #[test]
+    #[serial]
+    fn test_memory_dmabuf() -> Result<(), Error> {
+        let device = get_camera_device();
+        println!("Using camera device: {}", device);
+
+        let cam = create_camera()
+            .with_device(&device)
+            .with_resolution(640, 480)
+            .with_memory(MemoryType::DmaBuf)
+            .open()?;
+        cam.start()?;
+        let buf = cam.read()?;
+        assert!(buf.rawfd() >= 0, "DMABUF capture without a descriptor");
 
-        Ok((y_min, y_max, y_avg as u8))
+        // Buffers are always driver allocated
+        let err = create_camera()
+            .with_device(&device)
+            .with_memory(MemoryType::UserPtr)
+            .open()
+            .unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ENOTSUP));
+        Ok(())
+    }
+
+    #[ignore = "test requires a USB webcam (run with --include-ignored to enable)"]
+    #[test]
+    #[serial]
+    fn test_memory_mmap_webcam() -> Result<(), Error> {
+        let device =
+            std::env::var("VSL_USB_CAMERA_DEVICE").unwrap_or_else(|_| "/dev/video0".to_string());
+        println!("Using USB camera device: {}", device);
+
+        let cam = create_camera()
+            .with_device(&device)
+            .with_resolution(640, 480)
+            .with_memory(MemoryType::Mmap)
+            .open()?;
+        cam.start()?;
+        let buf = cam.read()?;
+        assert_eq!(buf.rawfd(), -1);
+        assert!(buf.length()? > 0);
+        Ok(())
+    }
+
+    #[ignore = "test requires camera hardware (run with --include-ignored to enable)"]
+    #[test]
+    #[serial]
+    fn test_set_crop() -> Result<(), Error> {
+        let device = get_camera_device();
+        println!("Using camera device: {}", device);
+
+        let mut cam = create_camera()
+            .with_device(&device)
+            .with_resolution(640, 480)
+            .open()?;
+        let (width, height) = (cam.width(), cam.height());
+
+        let requested = Rect::new(0, 0, width / 2, height / 2);
+        let applied = cam.set_crop(requested)?;
+        println!(
+            "requested crop {:?} applied {:?}, format {}x{}",
+            requested,
+            applied,
+            cam.width(),
+            cam.height()
+        );
+        assert!(applied.width > 0 && applied.width <= width);
+        assert!(applied.height > 0 && applied.height <= height);
+        assert_eq!(cam.get_crop()?, applied);
+
+        // The format is either the crop itself or the configured size the
+        // crop is scaled to
+        let size = (cam.width(), cam.height());
+        assert!(
+            size == (applied.width, applied.height) || size == (width, height),
+            "format {:?} is neither the crop {:?} nor {}x{}",
+            size,
+            applied,
+            width,
+            height
+        );
+
+        // An empty rectangle is rejected before reaching the driver.
+        assert!(matches!(
+            cam.set_crop(Rect::new(0, 0, 0, 0)),
+            Err(Error::Io(_))
+        ));
+
+        cam.start()?;
+        let buf = cam.read()?;
+        println!("captured {} crop {:?}", buf, applied);
+
+        // Buffers report the refreshed format
+        assert_eq!((buf.width(), buf.height()), size);
+        let stride = buf.bytes_per_line()? as usize;
+        assert!(stride > 0);
+        assert!(buf.length()? >= stride * size.1 as usize);
+
+        Ok(())
     }
 
     #[test]
@@ -795,6 +1824,7 @@ mod tests {
             .with_resolution(1920, 1080)
             .with_format(FourCC(*b"YUYV"))
             .with_mirror(Mirror::Horizontal)
+            .with_field(Field::Progressive)
             .with_buffers(8);
 
         // Camera struct should be configured
@@ -886,6 +1916,14 @@ mod tests {
                 Error::SymbolNotFound("vsl_camera_color_range"),
                 "vsl_camera_color_range",
             ),
+            (
+                Error::SymbolNotFound("vsl_camera_set_field"),
+                "vsl_camera_set_field",
+            ),
+            (
+                Error::SymbolNotFound("vsl_camera_field"),
+                "vsl_camera_field",
+            ),
         ];
         for (err, expected) in cases {
             let display = format!("{}", err);
@@ -898,6 +1936,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_field_v4l2() {
+        assert_eq!(Field::default(), Field::Any);
+        assert_eq!(format!("{}", Field::InterlacedTopFirst), "interlaced-tb");
+        for field in [
+            Field::Progressive,
+            Field::InterlacedTopFirst,
+            Field::InterlacedBottomFirst,
+        ] {
+            assert_eq!(Field::from_v4l2(field.to_v4l2(), 1080), Some(field));
+        }
+        assert_eq!(
+            Field::from_v4l2(V4L2_FIELD_INTERLACED, 480),
+            Some(Field::InterlacedBottomFirst)
+        );
+        assert_eq!(
+            Field::from_v4l2(V4L2_FIELD_INTERLACED, 576),
+            Some(Field::InterlacedTopFirst)
+        );
+        // V4L2_FIELD_ALTERNATE delivers one field per buffer
+        assert_eq!(Field::from_v4l2(7, 240), None);
+    }
+
     #[test]
     fn test_camera_default() {
         let camera = Camera::default();
@@ -1,11 +1,24 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright 2025 Au-Zone Technologies
 
-use crate::{frame::Frame, Error};
+use crate::{
+    fourcc::FourCC,
+    frame::{Frame, SharedFrame},
+    Error,
+};
 use std::{
-    ffi::{CStr, CString},
+    collections::VecDeque,
+    ffi::{c_void, CStr},
     io,
+    os::fd::{AsRawFd, RawFd},
     path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+        Arc, Condvar, Mutex, MutexGuard, PoisonError,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 use videostream_sys as ffi;
 
@@ -51,6 +64,93 @@ impl From<bool> for Reconnect {
     }
 }
 
+/// Metadata of the next queued frame, returned by [`Client::peek`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameMeta {
+    /// Frame serial number assigned by the host
+    pub serial: i64,
+    /// Host timestamp in nanoseconds when the frame was posted
+    pub timestamp: i64,
+    /// Presentation timestamp in nanoseconds, or -1 if unknown
+    pub pts: i64,
+    /// Size of the frame buffer in bytes
+    pub size: usize,
+    /// Frame pixel format
+    pub fourcc: FourCC,
+}
+
+/// Frame counters for a [`Client`], returned by [`Client::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClientStats {
+    /// Frames received from the host, including dropped ones
+    pub frames_received: u64,
+    /// Frames discarded by [`Client::get_latest_frame`] or by a full queue
+    /// (see [`Client::with_queue_depth`]) without being returned
+    pub frames_dropped: u64,
+    /// Frames currently buffered in the client queue
+    pub queue_depth: u64,
+    /// Most frames buffered in the client queue at once
+    pub queue_high_water: u64,
+    /// Frames the host posted that never reached this client, counted from
+    /// gaps in the frame serials
+    pub frames_missed: u64,
+    /// New host sessions since the client was created, either because it
+    /// reconnected or because the frame serials started over. The other
+    /// counters restart from zero with each session.
+    pub reconnects: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    received: AtomicU64,
+    dropped: AtomicU64,
+    high_water: AtomicU64,
+    missed: AtomicU64,
+    reconnects: AtomicU64,
+}
+
+impl Counters {
+    /// Clears the per-session counters, returning their previous values.
+    fn reset(&self) -> ClientStats {
+        ClientStats {
+            frames_received: self.received.swap(0, Ordering::Relaxed),
+            frames_dropped: self.dropped.swap(0, Ordering::Relaxed),
+            queue_depth: 0,
+            queue_high_water: self.high_water.swap(0, Ordering::Relaxed),
+            frames_missed: self.missed.swap(0, Ordering::Relaxed),
+            reconnects: self.reconnects.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Host session the client last received a frame from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Session {
+    /// `vsl_client_reconnects()` when the session started, or -1 if the
+    /// library cannot report it
+    reconnects: i64,
+    /// Serial of the last frame received, or 0 before the first frame
+    serial: i64,
+}
+
+impl Session {
+    /// Records a frame, returning how many serials were skipped since the
+    /// previous one, or `None` if the frame starts a new session.
+    fn update(&mut self, reconnects: i64, serial: i64) -> Option<u64> {
+        let previous = std::mem::replace(self, Session { reconnects, serial });
+        if reconnects != previous.reconnects || serial <= previous.serial {
+            return None;
+        }
+        if previous.serial == 0 {
+            return Some(0);
+        }
+        Some((serial - previous.serial - 1) as u64)
+    }
+}
+
+/// Callback registered with [`Client::on_reconnect`].
+type ReconnectCallback = Box<dyn Fn(&ClientStats) + Send + Sync>;
+
 /// Client structure for connecting to a VideoStream host.
 ///
 /// Provides functionality to subscribe to video frames published by a
@@ -67,6 +167,13 @@ impl From<bool> for Reconnect {
 /// ```
 pub struct Client {
     ptr: *mut ffi::VSLClient,
+    /// Cleared by [`disconnect`](Self::disconnect), as in the library
+    reconnect: AtomicBool,
+    counters: Counters,
+    queue_depth: Option<usize>,
+    queue: Mutex<VecDeque<Frame>>,
+    session: Mutex<Session>,
+    on_reconnect: Option<ReconnectCallback>,
 }
 
 unsafe impl Send for Client {}
@@ -77,7 +184,10 @@ impl std::fmt::Debug for Client {
         let path = self
             .path()
             .unwrap_or_else(|_| PathBuf::from("<invalid_path>"));
-        f.debug_struct("Client").field("path", &path).finish()
+        f.debug_struct("Client")
+            .field("path", &path)
+            .field("queue_depth", &self.queue_depth)
+            .finish()
     }
 }
 
@@ -86,7 +196,9 @@ impl Client {
     ///
     /// # Arguments
     ///
-    /// * `path` - UNIX socket path to connect to
+    /// * `path` - UNIX socket path to connect to, or `@` (or a NUL byte)
+    ///   followed by the name of a Linux abstract socket, see
+    ///   [`Host::new`](crate::host::Host::new)
     /// * `reconnect` - Whether to automatically reconnect on disconnect
     ///
     /// # Returns
@@ -107,24 +219,158 @@ impl Client {
     /// # Ok::<(), videostream::Error>(())
     /// ```
     pub fn new(path: &str, reconnect: Reconnect) -> Result<Self, Error> {
-        let path_str_c = CString::new(path)?;
+        let path_str_c = crate::host::socket_path_c(path.as_bytes())?;
         let ptr = vsl!(vsl_client_init(
             path_str_c.as_ptr(),
             std::ptr::null_mut(),
             reconnect.into()
         ));
         if ptr.is_null() {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_client_init");
             return Err(err.into());
         }
 
-        Ok(Client { ptr })
+        Ok(Client::from_ptr(ptr, reconnect.into()))
+    }
+
+    fn from_ptr(ptr: *mut ffi::VSLClient, reconnect: bool) -> Self {
+        let mut client = Client {
+            ptr,
+            reconnect: AtomicBool::new(reconnect),
+            counters: Counters::default(),
+            queue_depth: None,
+            queue: Mutex::new(VecDeque::new()),
+            session: Mutex::new(Session {
+                reconnects: -1,
+                serial: 0,
+            }),
+            on_reconnect: None,
+        };
+        let reconnects = client.reconnect_count();
+        client
+            .session
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .reconnects = reconnects;
+        client
+    }
+
+    /// Buffers up to `depth` frames in the client, discarding the oldest
+    /// when more arrive (a depth of zero is treated as one).
+    ///
+    /// Each [`get_frame`](Self::get_frame) first moves every frame waiting
+    /// on the socket into the queue and then returns the oldest one. A deep
+    /// queue lets a bursty consumer catch up on every frame at the cost of
+    /// latency, since the returned frame can be up to `depth` frames old; a
+    /// shallow queue keeps latency low but drops frames whenever the
+    /// consumer falls behind. Queued frames are not locked, so the host may
+    /// still expire them; keep `depth` times the frame interval below the
+    /// host's frame lifetime. Discarded frames are counted in
+    /// [`ClientStats::frames_dropped`].
+    ///
+    /// Without a queue depth frames wait on the socket until received and
+    /// are only bounded by the host expiring them.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::client::{Client, Reconnect};
+    ///
+    /// let client = Client::new("/tmp/video.sock", Reconnect::Yes)?.with_queue_depth(4);
+    /// let frame = client.get_frame(0)?;
+    /// let stats = client.stats();
+    /// println!("{} queued, {} dropped", stats.queue_depth, stats.frames_dropped);
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn with_queue_depth(mut self, depth: usize) -> Self {
+        self.queue_depth = Some(depth.max(1));
+        self
+    }
+
+    /// Calls `callback` when the client starts receiving from a new host
+    /// session.
+    ///
+    /// With [`Reconnect::Yes`] the client reconnects after the host restarts,
+    /// and the restarted host numbers its frames from 1 again. The client
+    /// detects the new session when it reconnects or when the frame serials
+    /// go backwards, and restarts [`ClientStats`] from zero so serial gaps
+    /// across the restart are not counted as missed frames. The callback
+    /// receives the counters of the session that ended (without
+    /// `queue_depth`) before the first frame of the new one is returned.
+    ///
+    /// The callback runs on the thread receiving the frame, possibly while
+    /// the client queue is locked, so it must not call back into the client.
+    /// Libraries before 2.6 cannot report reconnects, so only restarts that
+    /// reset the serials are detected.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::client::{Client, Reconnect};
+    ///
+    /// let client = Client::new("/tmp/video.sock", Reconnect::Yes)?.on_reconnect(|stats| {
+    ///     println!("host restarted after {} frames", stats.frames_received);
+    /// });
+    /// let frame = client.get_frame(0)?;
+    /// println!("{} host sessions", client.stats().reconnects + 1);
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn on_reconnect<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ClientStats) + Send + Sync + 'static,
+    {
+        self.on_reconnect = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns `vsl_client_reconnects()`, or -1 if the library predates 2.6.
+    fn reconnect_count(&self) -> i64 {
+        ffi::init()
+            .ok()
+            .and_then(|lib| lib.vsl_client_reconnects.as_ref().ok())
+            .map_or(-1, |reconnects| unsafe { reconnects(self.ptr) })
+    }
+
+    /// Returns `vsl_client_discarded()`, the frame events skipped while
+    /// waiting for lock, unlock and command replies, or 0 if the library
+    /// predates 2.6.
+    fn discarded(&self) -> u64 {
+        ffi::init()
+            .ok()
+            .and_then(|lib| lib.vsl_client_discarded.as_ref().ok())
+            .map_or(0, |discarded| unsafe { discarded(self.ptr) }.max(0) as u64)
+    }
+
+    /// Tracks the serial of a received frame, counting skipped serials as
+    /// missed and resetting the counters when a new host session starts.
+    fn track_session(&self, frame: &Frame) {
+        let Ok(serial) = frame.serial() else {
+            return;
+        };
+        let reconnects = self.reconnect_count();
+        let update = self
+            .session
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .update(reconnects, serial);
+        match update {
+            Some(missed) => {
+                self.counters.missed.fetch_add(missed, Ordering::Relaxed);
+            }
+            None => {
+                let previous = self.counters.reset();
+                self.counters.reconnects.fetch_add(1, Ordering::Relaxed);
+                if let Some(callback) = &self.on_reconnect {
+                    callback(&previous);
+                }
+            }
+        }
     }
 
     /// Disconnects from the host.
     ///
-    /// Closes the connection to the host server. If `Reconnect::Yes` was specified,
-    /// the client will attempt to reconnect on the next frame request.
+    /// Closes the connection to the host server. The client no longer
+    /// reconnects afterwards, even if `Reconnect::Yes` was specified.
     ///
     /// # Errors
     ///
@@ -142,6 +388,7 @@ impl Client {
     /// ```
     pub fn disconnect(&self) -> Result<(), Error> {
         vsl!(vsl_client_disconnect(self.ptr));
+        self.reconnect.store(false, Ordering::Relaxed);
         Ok(())
     }
 
@@ -267,21 +514,372 @@ impl Client {
     /// # Ok::<(), videostream::Error>(())
     /// ```
     pub fn get_frame(&self, until: i64) -> Result<Frame, Error> {
+        let Some(depth) = self.queue_depth else {
+            // Frames queued behind a keyframe by get_frame_from_keyframe()
+            if let Some(frame) = self.queue().pop_front() {
+                return Ok(frame);
+            }
+            return self.receive(until);
+        };
+
+        let mut queue = self.queue();
+        if queue.is_empty() {
+            let frame = self.receive(until)?;
+            queue.push_back(frame);
+        }
+
+        // Releasing a frame reads the unlock reply from the socket and
+        // discards frame events queued ahead of it, so evicted frames are
+        // held until the socket is drained, as in get_latest_frame().
+        let mut evicted = Vec::new();
+        while self.peek()?.is_some() {
+            queue.push_back(self.receive(0)?);
+            if queue.len() > depth {
+                evicted.extend(queue.pop_front());
+                self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.counters
+            .high_water
+            .fetch_max(queue.len() as u64, Ordering::Relaxed);
+
+        let frame = queue.pop_front().ok_or(Error::NullPointer)?;
+        drop(queue);
+        drop(evicted);
+        Ok(frame)
+    }
+
+    /// Receives the next frame from the socket, bypassing the queue.
+    fn receive(&self, until: i64) -> Result<Frame, Error> {
+        #[cfg(feature = "tracing")]
+        let span = crate::trace::timed_span!("get_frame");
+
         let frame = vsl!(vsl_frame_wait(self.ptr, until));
         if frame.is_null() {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_frame_wait");
             return Err(err.into());
         }
         // Safety: vsl_frame_wait transfers ownership of a new frame reference
         // to the caller on success. The null case is handled above; if
         // `from_raw` still rejects the pointer, surface it as an error rather
         // than panicking from this public API.
-        unsafe { Frame::from_raw(frame) }.ok_or(Error::NullPointer)
+        let mut frame = unsafe { Frame::from_raw(frame) }.ok_or(Error::NullPointer)?;
+        frame.load_plane_descriptors()?;
+        self.track_session(&frame);
+        self.counters.received.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "tracing")]
+        span.record_frame(&frame);
+        Ok(frame)
+    }
+
+    /// Returns the freshest frame, discarding any that are queued behind it.
+    ///
+    /// Waits for a frame as [`get_frame(0)`](Self::get_frame) does, then keeps
+    /// receiving while more frames are queued so only the newest is returned.
+    /// If that frame is already older than `max_age` (measured from the host
+    /// timestamp) it is discarded too and the wait continues, so a live
+    /// display always catches up instead of rendering a backlog. Discarded
+    /// frames are counted in [`ClientStats::frames_dropped`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6, or
+    /// [`Error::Io`] if receiving a frame fails or times out.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use videostream::client::{Client, Reconnect};
+    ///
+    /// let client = Client::new("/tmp/video.sock", Reconnect::Yes)?;
+    /// let frame = client.get_latest_frame(Duration::from_millis(50))?;
+    /// println!("showing frame {}", frame.serial()?);
+    /// println!("skipped {} frames", client.stats().frames_dropped);
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn get_latest_frame(&self, max_age: Duration) -> Result<Frame, Error> {
+        let max_age = i64::try_from(max_age.as_nanos()).unwrap_or(i64::MAX);
+        loop {
+            // Releasing a frame sends an unlock request whose reply is read
+            // from the same socket, consuming any frame events queued ahead
+            // of it, so skipped frames are only released once drained.
+            let mut skipped: Vec<Frame> = self.queue().drain(..).collect();
+            let mut frame = match skipped.pop() {
+                Some(frame) => frame,
+                None => self.receive(0)?,
+            };
+            while self.peek()?.is_some() {
+                skipped.push(std::mem::replace(&mut frame, self.receive(0)?));
+            }
+
+            let fresh = crate::timestamp()?.saturating_sub(frame.timestamp()?) <= max_age;
+            let dropped = skipped.len() as u64 + u64::from(!fresh);
+            self.counters.dropped.fetch_add(dropped, Ordering::Relaxed);
+            if fresh {
+                return Ok(frame);
+            }
+        }
+    }
+
+    /// Waits for the next keyframe, discarding the encoded frames ahead of it.
+    ///
+    /// A client joining a live H.264 or H.265 stream mid-GOP otherwise
+    /// receives predicted frames its decoder cannot use until the next IDR.
+    /// Frames are received as [`get_frame(0)`](Self::get_frame) does and
+    /// inspected with [`Frame::is_keyframe`] using each frame's own fourcc;
+    /// once a keyframe is returned, keep calling `get_frame` for the rest of
+    /// the stream. Frames already waiting behind the keyframe are held in
+    /// the client queue and returned by the following `get_frame` calls.
+    /// Discarded frames are counted in [`ClientStats::frames_dropped`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if a
+    /// frame is not H.264 or H.265, or [`Error::Io`] if receiving a frame
+    /// fails or times out.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::client::{Client, Reconnect};
+    ///
+    /// let client = Client::new("/tmp/h264.sock", Reconnect::Yes)?;
+    /// let first = client.get_frame_from_keyframe()?;
+    /// // feed `first` to the decoder, then continue with get_frame()
+    /// let next = client.get_frame(0)?;
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn get_frame_from_keyframe(&self) -> Result<Frame, Error> {
+        loop {
+            // Inspecting a frame locks it, and the lock reply discards
+            // frame events queued on the socket, so drain it first.
+            {
+                let mut queue = self.queue();
+                if queue.is_empty() {
+                    queue.push_back(self.receive(0)?);
+                }
+                while self.peek()?.is_some() {
+                    queue.push_back(self.receive(0)?);
+                }
+            }
+
+            let mut skipped = Vec::new();
+            while let Some(frame) = self.queue().pop_front() {
+                if frame.is_keyframe(FourCC::from(frame.fourcc()?))? {
+                    self.counters
+                        .dropped
+                        .fetch_add(skipped.len() as u64, Ordering::Relaxed);
+                    return Ok(frame);
+                }
+                skipped.push(frame);
+            }
+            self.counters
+                .dropped
+                .fetch_add(skipped.len() as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the frame counters accumulated by this client since the
+    /// current host session started (see [`on_reconnect`](Self::on_reconnect)).
+    pub fn stats(&self) -> ClientStats {
+        ClientStats {
+            frames_received: self.counters.received.load(Ordering::Relaxed),
+            frames_dropped: self.counters.dropped.load(Ordering::Relaxed),
+            queue_depth: self.queue().len() as u64,
+            queue_high_water: self.counters.high_water.load(Ordering::Relaxed),
+            frames_missed: self.counters.missed.load(Ordering::Relaxed),
+            reconnects: self.counters.reconnects.load(Ordering::Relaxed),
+        }
+    }
+
+    fn queue(&self) -> MutexGuard<'_, VecDeque<Frame>> {
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the metadata of the next queued frame without receiving it.
+    ///
+    /// Does not block: returns `Ok(None)` when no frame is queued or the
+    /// client is disconnected. A connection the host closed is closed here,
+    /// and a disconnected [`Reconnect::Yes`] client first tries once to
+    /// reconnect, as [`get_frame`](Self::get_frame) does. A following `get_frame(0)` returns
+    /// the peeked frame. The frame is not locked, so the host may expire it
+    /// before it is received; control messages and already expired frames
+    /// at the head of the queue are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6, or
+    /// [`Error::Io`] if reading the socket fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::client::{Client, Reconnect};
+    ///
+    /// let client = Client::new("/tmp/video.sock", Reconnect::Yes)?;
+    /// if let Some(meta) = client.peek()? {
+    ///     if meta.size < 4 * 1024 * 1024 {
+    ///         let frame = client.get_frame(0)?;
+    ///         assert_eq!(frame.serial()?, meta.serial);
+    ///     }
+    /// }
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn peek(&self) -> Result<Option<FrameMeta>, Error> {
+        let lib = ffi::init()?;
+        let peek = lib
+            .vsl_client_peek
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_client_peek"))?;
+
+        let mut meta = ffi::VSLFrameMeta {
+            serial: 0,
+            timestamp: 0,
+            pts: 0,
+            size: 0,
+            fourcc: 0,
+        };
+        match unsafe { peek(self.ptr, &mut meta) } {
+            -1 => Err(crate::last_os_error("vsl_client_peek").into()),
+            0 => Ok(None),
+            _ => Ok(Some(FrameMeta {
+                serial: meta.serial,
+                timestamp: meta.timestamp,
+                pts: meta.pts,
+                size: meta.size,
+                fourcc: FourCC::from(meta.fourcc),
+            })),
+        }
+    }
+
+    /// Sends a command to the host and returns its reply.
+    ///
+    /// The request is handled by the closure registered with
+    /// [`Host::set_command_handler`](crate::host::Host::set_command_handler).
+    /// Requests and replies are limited to 4096 bytes. Frames arriving while
+    /// waiting for the reply are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6, or
+    /// [`Error::Io`] with `EMSGSIZE` if the request is too large, `ENOTSUP`
+    /// if the host has no handler, `EIO` if the handler failed, `ETIMEDOUT`
+    /// if the host did not reply within the [`set_timeout`](Self::set_timeout)
+    /// timeout, or `ENOTCONN` if disconnected.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::client::{Client, Reconnect};
+    ///
+    /// let client = Client::new("/tmp/video.sock", Reconnect::Yes)?;
+    /// let reply = client.send_command(b"status")?;
+    /// println!("{}", String::from_utf8_lossy(&reply));
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn send_command(&self, request: &[u8]) -> Result<Vec<u8>, Error> {
+        let lib = ffi::init()?;
+        let send_command = lib
+            .vsl_client_send_command
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_client_send_command"))?;
+
+        let mut reply = vec![0u8; ffi::VSL_COMMAND_MAX as usize];
+        let ret = unsafe {
+            send_command(
+                self.ptr,
+                request.as_ptr() as *const c_void,
+                request.len(),
+                reply.as_mut_ptr() as *mut c_void,
+                reply.len(),
+            )
+        };
+        if ret < 0 {
+            return Err(crate::last_os_error("vsl_client_send_command").into());
+        }
+
+        reply.truncate(ret as usize);
+        Ok(reply)
+    }
+
+    /// Moves the client to a background thread that forwards its frames to
+    /// a bounded queue.
+    ///
+    /// The thread receives frames as they arrive and pushes them onto a
+    /// queue holding at most `capacity` frames (a capacity of zero is
+    /// treated as one). When the queue is full the oldest frame is
+    /// discarded, so a slow consumer sees the newest frames instead of
+    /// stalling the host. Discarded frames are counted by
+    /// [`FrameReceiver::dropped`], together with the frames the host sent
+    /// while a released frame was being unlocked, which the unlock reply
+    /// skips. A bounded `std::sync::mpsc` channel
+    /// blocks the sender when full rather than evicting, so the frames are
+    /// delivered through a [`FrameReceiver`] instead.
+    ///
+    /// The thread ends when the host hangs up or the receiver is dropped,
+    /// and its handle returns any error from receiving a frame. Frames must
+    /// be released before the receiver, as they are unlocked through the
+    /// client the receiver keeps alive.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::client::{Client, Reconnect};
+    ///
+    /// let client = Client::new("/tmp/video.sock", Reconnect::No)?;
+    /// let (handle, mut frames) = client.into_channel(4);
+    /// for frame in frames.by_ref().take(100) {
+    ///     println!("frame {}", frame.serial()?);
+    /// }
+    /// println!("dropped {} frames", frames.dropped());
+    /// drop(frames);
+    /// handle.join().expect("bridge thread panicked")?;
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn into_channel(self, capacity: usize) -> (JoinHandle<Result<(), Error>>, FrameReceiver) {
+        let bridge = Arc::new(Bridge {
+            state: Mutex::new(BridgeState::default()),
+            ready: Condvar::new(),
+            dropped: AtomicU64::new(0),
+            discarded: self.discarded(),
+            capacity: capacity.max(1),
+            client: self,
+        });
+
+        let worker = Arc::clone(&bridge);
+        let handle = thread::spawn(move || {
+            let result = worker.forward();
+            worker.state().closed = true;
+            worker.ready.notify_all();
+            result
+        });
+
+        (handle, FrameReceiver { bridge })
+    }
+}
+
+impl AsRawFd for Client {
+    /// Returns the socket connected to the host, or -1 while disconnected.
+    ///
+    /// The descriptor changes when the client reconnects, so it should be
+    /// requested again before each poll rather than stored.
+    fn as_raw_fd(&self) -> RawFd {
+        ffi::init()
+            .ok()
+            .and_then(|lib| lib.vsl_client_fd.as_ref().ok())
+            .map_or(-1, |client_fd| unsafe { client_fd(self.ptr) })
     }
 }
 
 impl Drop for Client {
     fn drop(&mut self) {
+        // Queued frames are unlocked through the client, so release them
+        // first.
+        self.queue
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
         // vsl_client_release handles full cleanup including socket close
         if let Ok(lib) = ffi::init() {
             unsafe {
@@ -291,89 +889,680 @@ impl Drop for Client {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::frame::Frame;
-    use crate::host::Host;
-    use crate::timestamp;
-    use std::ffi::CString;
-    use std::thread;
-    use std::time::Duration;
+/// How long the [`Client::into_channel`] thread waits for a frame before
+/// checking whether its receiver was dropped.
+const BRIDGE_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
-    /// Helper to create a unique socket path for each test.
-    /// Uses process ID and thread ID to ensure uniqueness across parallel test runs.
-    fn test_socket_path(name: &str) -> String {
-        format!(
-            "/tmp/vsl_test_{}_{}_{:?}.sock",
-            name,
-            std::process::id(),
-            std::thread::current().id()
-        )
+#[derive(Default)]
+struct BridgeState {
+    frames: VecDeque<Frame>,
+    closed: bool,
+}
+
+/// State shared by the [`Client::into_channel`] thread and its
+/// [`FrameReceiver`].
+struct Bridge {
+    state: Mutex<BridgeState>,
+    ready: Condvar,
+    dropped: AtomicU64,
+    /// `Client::discarded()` when the bridge started
+    discarded: u64,
+    capacity: usize,
+    // Declared last so queued frames are released before the client that
+    // unlocks them.
+    client: Client,
+}
+
+impl Bridge {
+    fn state(&self) -> MutexGuard<'_, BridgeState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
     }
 
-    /// Small delay to ensure host socket is ready for connections.
-    /// Required because socket creation (bind + listen) is not atomic.
-    const HOST_READY_DELAY: Duration = Duration::from_millis(5);
+    /// Receives frames until the host hangs up or the receiver is dropped.
+    fn forward(self: &Arc<Self>) -> Result<(), Error> {
+        let wait = BRIDGE_POLL_INTERVAL.as_millis() as libc::c_int;
+        // Releasing a frame reads the unlock reply from the socket and
+        // discards frame events queued ahead of it, so evicted frames are
+        // held until the socket is drained, as in get_latest_frame().
+        let mut evicted = Vec::new();
+        while Arc::strong_count(self) > 1 {
+            let mut pfd = libc::pollfd {
+                fd: self.client.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let ret = unsafe { libc::poll(&mut pfd, 1, wait) };
+            if ret < 0 {
+                let err = crate::last_os_error("poll");
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err.into());
+            }
 
-    #[test]
-    fn test_client_debug() {
-        let socket_path = test_socket_path("client_debug");
+            if pfd.revents == 0 {
+                continue;
+            }
+            if self.client.peek()?.is_some() {
+                let frame = self.client.get_frame(0)?;
+                let mut state = self.state();
+                if state.frames.len() >= self.capacity {
+                    evicted.extend(state.frames.pop_front());
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                state.frames.push_back(frame);
+                drop(state);
+                self.ready.notify_one();
+            } else if pfd.revents & (libc::POLLHUP | libc::POLLERR) != 0 {
+                return Ok(());
+            }
 
-        // Create a host first so the client can connect
-        let host = Host::new(&socket_path).unwrap();
-        thread::sleep(HOST_READY_DELAY);
+            if self.client.peek()?.is_none() {
+                evicted.clear();
+            }
+        }
+        Ok(())
+    }
+}
 
-        // Now create a client that connects to the host
-        let client = Client::new(&socket_path, Reconnect::No).unwrap();
-        let debug_str = format!("{:?}", client);
+/// Receiving end of the queue created by [`Client::into_channel`].
+///
+/// Behaves like a `std::sync::mpsc::Receiver<Frame>` fed by a sender that
+/// discards the oldest frame instead of blocking when the queue is full.
+/// Iterating yields frames until the bridge thread ends and the queue is
+/// empty.
+pub struct FrameReceiver {
+    bridge: Arc<Bridge>,
+}
 
-        assert!(debug_str.contains("Client"));
-        assert!(debug_str.contains(&socket_path));
+impl FrameReceiver {
+    /// Waits for the next frame, returning `None` once the bridge thread has
+    /// ended and all queued frames were received.
+    pub fn recv(&self) -> Option<Frame> {
+        let mut state = self.bridge.state();
+        loop {
+            if let Some(frame) = state.frames.pop_front() {
+                return Some(frame);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self
+                .bridge
+                .ready
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
 
-        drop(client);
-        drop(host);
+    /// Returns the next frame if one is queued, without waiting.
+    pub fn try_recv(&self) -> Option<Frame> {
+        self.bridge.state().frames.pop_front()
     }
 
-    #[test]
-    fn test_client_get_userptr() {
-        let socket_path = test_socket_path("client_userptr");
+    /// Waits up to `timeout` for the next frame, returning `None` on timeout
+    /// or once the bridge thread has ended and the queue is empty.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Frame> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.bridge.state();
+        loop {
+            if let Some(frame) = state.frames.pop_front() {
+                return Some(frame);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if state.closed || remaining.is_zero() {
+                return None;
+            }
+            state = self
+                .bridge
+                .ready
+                .wait_timeout(state, remaining)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
 
-        // Create a host first so the client can connect
-        let host = Host::new(&socket_path).unwrap();
-        thread::sleep(HOST_READY_DELAY);
+    /// Returns `true` once the bridge thread has ended. Frames queued before
+    /// it ended can still be received.
+    pub fn is_closed(&self) -> bool {
+        self.bridge.state().closed
+    }
 
-        // Test 1: Client created with null userptr should return None
-        let client_none = Client::new(&socket_path, Reconnect::No).unwrap();
-        let userptr_none = client_none.userptr().unwrap();
-        assert_eq!(
-            userptr_none, None,
-            "Client with null userptr should return None"
-        );
-        drop(client_none);
+    /// Returns the number of frames discarded because the queue was full,
+    /// plus the frames skipped by the unlock replies of released frames.
+    pub fn dropped(&self) -> u64 {
+        let skipped = self
+            .bridge
+            .client
+            .discarded()
+            .saturating_sub(self.bridge.discarded);
+        self.bridge.dropped.load(Ordering::Relaxed) + skipped
+    }
+}
 
-        // Test 2: Client created with a userptr should return Some(ptr)
-        // Create a CString to use as user data
-        let user_data = CString::new("test_user_data").unwrap();
-        let user_data_ptr = user_data.as_ptr() as *mut std::os::raw::c_void;
+impl Iterator for FrameReceiver {
+    type Item = Frame;
 
-        // Create client with userptr using FFI directly since Client::new doesn't expose it
-        let path_str_c = CString::new(socket_path.clone()).unwrap();
-        let lib = videostream_sys::init().unwrap();
-        let ptr = unsafe { lib.vsl_client_init(path_str_c.as_ptr(), user_data_ptr, false) };
-        assert!(!ptr.is_null(), "Client initialization should succeed");
+    fn next(&mut self) -> Option<Frame> {
+        self.recv()
+    }
+}
 
-        let client_some = Client { ptr };
-        let userptr_some = client_some.userptr().unwrap();
-        assert!(
-            userptr_some.is_some(),
-            "Client with userptr should return Some"
-        );
-        assert_eq!(
-            userptr_some.unwrap(),
-            user_data_ptr,
-            "Userptr should match what was set"
-        );
+impl std::fmt::Debug for FrameReceiver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.bridge.state();
+        f.debug_struct("FrameReceiver")
+            .field("client", &self.bridge.client)
+            .field("queued", &state.frames.len())
+            .field("closed", &state.closed)
+            .field("dropped", &self.dropped())
+            .finish()
+    }
+}
+
+/// State shared by the [`BroadcastClient`] thread, its handle and the frames
+/// it hands out.
+struct Broadcast {
+    subscribers: Mutex<Vec<SyncSender<Arc<SharedFrame>>>>,
+    capacity: usize,
+    dropped: AtomicU64,
+    /// Frames the host expired before they could be locked
+    expired: AtomicU64,
+    /// `Client::discarded()` when the broadcast started
+    discarded: u64,
+    stop: AtomicBool,
+    client: Client,
+}
+
+impl Broadcast {
+    fn subscribers(&self) -> MutexGuard<'_, Vec<SyncSender<Arc<SharedFrame>>>> {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Hands `frame` to every subscriber, keeping it in `unsent` if none
+    /// took it.
+    fn send(&self, frame: Arc<SharedFrame>, unsent: &mut Vec<Arc<SharedFrame>>) {
+        self.subscribers()
+            .retain(|subscriber| match subscriber.try_send(Arc::clone(&frame)) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            });
+        if Arc::strong_count(&frame) == 1 {
+            unsent.push(frame);
+        }
+    }
+
+    /// Receives frames and hands each to every subscriber until the host
+    /// hangs up or the [`BroadcastClient`] is dropped.
+    fn forward(self: &Arc<Self>) -> Result<(), Error> {
+        let wait = BRIDGE_POLL_INTERVAL.as_millis() as libc::c_int;
+        // Frames no subscriber took are held until the socket is drained, as
+        // in Bridge::forward().
+        let mut unsent = Vec::new();
+        while !self.stop.load(Ordering::Relaxed) {
+            let mut pfd = libc::pollfd {
+                fd: self.client.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let ret = unsafe { libc::poll(&mut pfd, 1, wait) };
+            if ret < 0 {
+                let err = crate::last_os_error("poll");
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err.into());
+            }
+
+            if pfd.revents == 0 {
+                continue;
+            }
+            if self.client.peek()?.is_some() {
+                // Locking a frame reads the reply from the socket and
+                // discards the frame events queued ahead of it, so receive
+                // every pending frame before locking any.
+                let mut received = vec![self.client.get_frame(0)?];
+                while self.client.peek()?.is_some() {
+                    received.push(self.client.get_frame(0)?);
+                }
+                for frame in received {
+                    // Subscribers may outlive the BroadcastClient, so each
+                    // frame keeps the client that unlocks it alive.
+                    let owner: Arc<dyn std::any::Any + Send + Sync> = Arc::clone(self) as _;
+                    let frame = match SharedFrame::with_owner(frame, owner) {
+                        Ok(frame) => Arc::new(frame),
+                        // The host expired the frame before it was locked
+                        Err(err) if err.raw_os_error() == Some(libc::EEXIST) => {
+                            self.expired.fetch_add(1, Ordering::Relaxed);
+                            continue;
+                        }
+                        Err(err) => return Err(err),
+                    };
+                    self.send(frame, &mut unsent);
+                }
+            } else if pfd.revents & (libc::POLLHUP | libc::POLLERR) != 0 {
+                return Ok(());
+            }
+
+            if self.client.peek()?.is_none() {
+                unsent.clear();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Shares each frame received by one [`Client`] between many subscribers.
+///
+/// A background thread receives every frame once, maps it and hands an
+/// `Arc<SharedFrame>` to each subscriber, so N consumers in one process cost
+/// a single receive instead of N clients. [`SharedFrame`] only exposes the
+/// frame data and metadata, since [`Frame`] cannot be read and written from
+/// several threads at once.
+///
+/// Each subscriber has its own queue of `capacity` frames; a subscriber
+/// whose queue is full misses the frame, counted in
+/// [`dropped()`](Self::dropped), while the others still receive it. Held
+/// frames keep their host buffer locked, so consumers should drop them
+/// promptly; releasing one reads the unlock reply on the client socket and
+/// skips frames the broadcast thread has not received yet, which
+/// [`dropped()`](Self::dropped) counts as well. A subscriber's receiver disconnects once the thread ends, when
+/// the host hangs up, the client fails or the `BroadcastClient` is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::thread;
+/// use videostream::client::{BroadcastClient, Client, Reconnect};
+///
+/// let client = Client::new("/tmp/video.sock", Reconnect::Yes)?;
+/// let broadcast = BroadcastClient::new(client, 4);
+///
+/// let workers: Vec<_> = (0..3)
+///     .map(|n| {
+///         let frames = broadcast.subscribe();
+///         thread::spawn(move || {
+///             for frame in frames.iter().take(100) {
+///                 println!("worker {} frame {}", n, frame.serial().unwrap());
+///             }
+///         })
+///     })
+///     .collect();
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub struct BroadcastClient {
+    broadcast: Arc<Broadcast>,
+    handle: Option<JoinHandle<Result<(), Error>>>,
+}
+
+impl BroadcastClient {
+    /// Starts receiving from `client` on a background thread.
+    ///
+    /// `capacity` is the number of frames queued per subscriber, at least
+    /// one. Frames received before the first [`subscribe()`](Self::subscribe)
+    /// are discarded.
+    pub fn new(client: Client, capacity: usize) -> Self {
+        let broadcast = Arc::new(Broadcast {
+            subscribers: Mutex::new(Vec::new()),
+            capacity: capacity.max(1),
+            dropped: AtomicU64::new(0),
+            expired: AtomicU64::new(0),
+            discarded: client.discarded(),
+            stop: AtomicBool::new(false),
+            client,
+        });
+
+        let worker = Arc::clone(&broadcast);
+        let handle = thread::spawn(move || {
+            let result = worker.forward();
+            // Disconnect the subscribers
+            worker.subscribers().clear();
+            result
+        });
+
+        BroadcastClient {
+            broadcast,
+            handle: Some(handle),
+        }
+    }
+
+    /// Adds a subscriber receiving every frame from now on.
+    ///
+    /// Dropping the receiver unsubscribes it. The receiver is disconnected
+    /// once the broadcast thread ends.
+    pub fn subscribe(&self) -> Receiver<Arc<SharedFrame>> {
+        let (sender, receiver) = mpsc::sync_channel(self.broadcast.capacity);
+        let mut subscribers = self.broadcast.subscribers();
+        if !self.is_finished() {
+            subscribers.push(sender);
+        }
+        receiver
+    }
+
+    /// Returns the number of connected subscribers.
+    pub fn subscribers(&self) -> usize {
+        self.broadcast.subscribers().len()
+    }
+
+    /// Returns the number of frames missed by subscribers whose queue was
+    /// full, summed over all subscribers, plus the frames no subscriber
+    /// received: those the host expired before they were locked and those
+    /// skipped by the lock and unlock replies.
+    pub fn dropped(&self) -> u64 {
+        let broadcast = &self.broadcast;
+        let skipped = broadcast
+            .client
+            .discarded()
+            .saturating_sub(broadcast.discarded);
+        broadcast.dropped.load(Ordering::Relaxed)
+            + broadcast.expired.load(Ordering::Relaxed)
+            + skipped
+    }
+
+    /// Returns `true` once the broadcast thread has ended.
+    pub fn is_finished(&self) -> bool {
+        self.handle.as_ref().map_or(true, JoinHandle::is_finished)
+    }
+
+    /// Returns the client frames are received from, for its
+    /// [`stats()`](Client::stats) and [`path()`](Client::path).
+    pub fn client(&self) -> &Client {
+        &self.broadcast.client
+    }
+
+    /// Stops the broadcast thread and returns how it ended.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that ended the thread early, such as [`Error::Io`]
+    /// when receiving a frame failed.
+    pub fn stop(mut self) -> Result<(), Error> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<(), Error> {
+        self.broadcast.stop.store(true, Ordering::Relaxed);
+        match self.handle.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for BroadcastClient {
+    fn drop(&mut self) {
+        if let Err(err) = self.join() {
+            log::debug!("broadcast thread ended with {}", err);
+        }
+    }
+}
+
+impl std::fmt::Debug for BroadcastClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BroadcastClient")
+            .field("client", &self.broadcast.client)
+            .field("subscribers", &self.subscribers())
+            .field("capacity", &self.broadcast.capacity)
+            .field("dropped", &self.dropped())
+            .finish()
+    }
+}
+
+/// The index of a [`ClientSet`] client paired with the frame it received or
+/// the error it reported, returned by [`ClientSet::poll`].
+pub type ClientFrame = (usize, Result<Frame, Error>);
+
+/// How often [`ClientSet::poll`] retries disconnected clients that
+/// reconnect.
+const RECONNECT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Receives frames from several [`Client`]s on one thread.
+///
+/// Polls the sockets of all clients together and returns frames from
+/// whichever are ready, tagged with the client's position in the set, so a
+/// single thread can follow many streams. When a host hangs up, clients
+/// created with [`Reconnect::Yes`] keep trying to reconnect on later polls,
+/// while the others report the hangup once and are skipped afterwards.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use videostream::client::{Client, ClientSet, Reconnect};
+///
+/// let mut set = ClientSet::new();
+/// set.push(Client::new("/tmp/front.sock", Reconnect::No)?);
+/// set.push(Client::new("/tmp/rear.sock", Reconnect::No)?);
+///
+/// for (index, frame) in set.poll(Duration::from_millis(100))? {
+///     match frame {
+///         Ok(frame) => println!("client {} frame {}", index, frame.serial()?),
+///         Err(err) => eprintln!("client {}: {}", index, err),
+///     }
+/// }
+/// # Ok::<(), videostream::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct ClientSet {
+    clients: Vec<Client>,
+}
+
+impl ClientSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        ClientSet::default()
+    }
+
+    /// Adds a client and returns its index in poll results.
+    pub fn push(&mut self, client: Client) -> usize {
+        self.clients.push(client);
+        self.clients.len() - 1
+    }
+
+    /// Returns the client at `index`.
+    pub fn get(&self, index: usize) -> Option<&Client> {
+        self.clients.get(index)
+    }
+
+    /// Returns the number of clients in the set.
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Returns `true` if the set has no clients.
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    /// Waits up to `timeout` for frames and returns one from each ready
+    /// client as `(index, frame)` pairs.
+    ///
+    /// Returns an empty vector on timeout. Control messages and expired
+    /// frames wake the poll but are discarded, and waiting continues until
+    /// a frame arrives or the timeout passes. A client that fails to
+    /// receive its frame, or whose host hung up without [`Reconnect::Yes`],
+    /// reports the error in its own entry without affecting the frames of
+    /// the other clients. Disconnected [`Reconnect::Yes`] clients are
+    /// retried every 100 ms while waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if polling fails.
+    pub fn poll(&self, timeout: Duration) -> Result<Vec<ClientFrame>, Error> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let mut fds: Vec<libc::pollfd> = self
+                .clients
+                .iter()
+                .map(|client| libc::pollfd {
+                    // poll() ignores negative descriptors
+                    fd: client.as_raw_fd(),
+                    events: libc::POLLIN,
+                    revents: 0,
+                })
+                .collect();
+            // Disconnected clients have no descriptor to wake the poll, so
+            // those that reconnect are retried on a timer instead.
+            let retry: Vec<bool> = self
+                .clients
+                .iter()
+                .zip(&fds)
+                .map(|(client, pfd)| pfd.fd < 0 && client.reconnect.load(Ordering::Relaxed))
+                .collect();
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let wait = match retry.contains(&true) {
+                true => remaining.min(RECONNECT_INTERVAL),
+                false => remaining,
+            };
+            let wait = wait.as_millis().min(i32::MAX as u128) as libc::c_int;
+            let ret = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, wait) };
+            if ret < 0 {
+                let err = crate::last_os_error("poll");
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err.into());
+            }
+
+            let mut frames = Vec::new();
+            for (index, pfd) in fds.iter().enumerate() {
+                if pfd.revents == 0 && !retry[index] {
+                    continue;
+                }
+                let client = &self.clients[index];
+                // peek() reconnects clients that reconnect and closes the
+                // connection once the host hangs up.
+                match client.peek() {
+                    Ok(Some(_)) => frames.push((index, client.get_frame(0))),
+                    Ok(None) => {
+                        let mut closed = pfd.fd >= 0 && client.as_raw_fd() < 0;
+                        if !closed && pfd.revents & (libc::POLLHUP | libc::POLLERR) != 0 {
+                            // Still open after an error, so it would wake
+                            // every poll.
+                            if let Err(err) = client.disconnect() {
+                                frames.push((index, Err(err)));
+                                continue;
+                            }
+                            closed = true;
+                        }
+                        if closed && !client.reconnect.load(Ordering::Relaxed) {
+                            let err = io::Error::from_raw_os_error(libc::ENOTCONN);
+                            frames.push((index, Err(err.into())));
+                        }
+                    }
+                    Err(err) => frames.push((index, Err(err))),
+                }
+            }
+
+            if !frames.is_empty() || remaining.is_zero() {
+                return Ok(frames);
+            }
+        }
+    }
+}
+
+impl FromIterator<Client> for ClientSet {
+    fn from_iter<I: IntoIterator<Item = Client>>(iter: I) -> Self {
+        ClientSet {
+            clients: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::{Frame, Rect};
+    use crate::host::Host;
+    use crate::timestamp;
+    use std::ffi::CString;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Helper to create a unique socket path for each test.
+    /// Uses process ID and thread ID to ensure uniqueness across parallel test runs.
+    fn test_socket_path(name: &str) -> String {
+        format!(
+            "/tmp/vsl_test_{}_{}_{:?}.sock",
+            name,
+            std::process::id(),
+            std::thread::current().id()
+        )
+    }
+
+    /// Small delay to ensure host socket is ready for connections.
+    /// Required because socket creation (bind + listen) is not atomic.
+    const HOST_READY_DELAY: Duration = Duration::from_millis(5);
+
+    #[test]
+    fn test_client_debug() {
+        let socket_path = test_socket_path("client_debug");
+
+        // Create a host first so the client can connect
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        // Now create a client that connects to the host
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        let debug_str = format!("{:?}", client);
+
+        assert!(debug_str.contains("Client"));
+        assert!(debug_str.contains(&socket_path));
+
+        drop(client);
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_get_userptr() {
+        let socket_path = test_socket_path("client_userptr");
+
+        // Create a host first so the client can connect
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        // Test 1: Client created with null userptr should return None
+        let client_none = Client::new(&socket_path, Reconnect::No).unwrap();
+        let userptr_none = client_none.userptr().unwrap();
+        assert_eq!(
+            userptr_none, None,
+            "Client with null userptr should return None"
+        );
+        drop(client_none);
+
+        // Test 2: Client created with a userptr should return Some(ptr)
+        // Create a CString to use as user data
+        let user_data = CString::new("test_user_data").unwrap();
+        let user_data_ptr = user_data.as_ptr() as *mut std::os::raw::c_void;
+
+        // Create client with userptr using FFI directly since Client::new doesn't expose it
+        let path_str_c = CString::new(socket_path.clone()).unwrap();
+        let lib = videostream_sys::init().unwrap();
+        let ptr = unsafe { lib.vsl_client_init(path_str_c.as_ptr(), user_data_ptr, false) };
+        assert!(!ptr.is_null(), "Client initialization should succeed");
+
+        let client_some = Client::from_ptr(ptr, false);
+        let userptr_some = client_some.userptr().unwrap();
+        assert!(
+            userptr_some.is_some(),
+            "Client with userptr should return Some"
+        );
+        assert_eq!(
+            userptr_some.unwrap(),
+            user_data_ptr,
+            "Userptr should match what was set"
+        );
 
         // Keep user_data alive until client is dropped
         drop(client_some);
@@ -437,7 +1626,7 @@ mod tests {
         thread::sleep(Duration::from_millis(10));
 
         // Process any pending connections on host
-        let _ = host.poll(0);
+        let _ = host.service_timeout(Duration::ZERO);
 
         // Create and allocate a frame
         let frame = Frame::new(640, 480, 0, "RGB3").unwrap();
@@ -457,7 +1646,7 @@ mod tests {
         host.post(frame, expires, -1, -1, -1).unwrap();
 
         // Process the posted frame
-        let _ = host.poll(100);
+        let _ = host.service_timeout(Duration::from_millis(100));
 
         // Try to receive the frame on the client side
         // Use a short timeout since frame should be available
@@ -475,6 +1664,980 @@ mod tests {
         drop(host);
     }
 
+    #[test]
+    fn test_client_capture_time_roundtrip() {
+        let socket_path = test_socket_path("capture_time");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        // Accept the client connection before posting
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame.alloc(None).unwrap();
+
+        let now = timestamp().unwrap();
+        let capture_time = now - 5_000_000;
+        frame.set_capture_time(capture_time).unwrap();
+        host.post(frame, now + 1_000_000_000, -1, -1, -1).unwrap();
+
+        let received = client.get_frame(0).unwrap();
+        assert_eq!(received.capture_time().unwrap(), capture_time);
+        assert!(received.timestamp().unwrap() - received.capture_time().unwrap() >= 5_000_000);
+
+        drop(received);
+        drop(client);
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_keyframe_flag_roundtrip() {
+        let socket_path = test_socket_path("keyframe_flag");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        // Accept the client connection before posting
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        let expires = timestamp().unwrap() + 1_000_000_000;
+        for keyframe in [true, false] {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            host.post_builder(frame, expires)
+                .pts(0)
+                .keyframe(keyframe)
+                .post()
+                .unwrap();
+        }
+
+        let first = client.get_frame(0).unwrap();
+        let second = client.get_frame(0).unwrap();
+        assert!(first.is_keyframe_flag().unwrap());
+        assert_eq!(first.pts().unwrap(), 0);
+        assert!(!second.is_keyframe_flag().unwrap());
+
+        drop((first, second));
+        drop(client);
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_source_id_roundtrip() {
+        let socket_path = test_socket_path("source_id");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        // Accept the client connection before posting
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        // Two cameras interleaved on one stream, then an unstamped frame
+        let expires = timestamp().unwrap() + 1_000_000_000;
+        let sources = [Some(0), Some(1), Some(0), Some(u32::MAX), None];
+        for source in sources {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            assert_eq!(frame.source_id().unwrap(), None);
+            if let Some(id) = source {
+                frame.set_source_id(id).unwrap();
+            }
+            host.post(frame, expires, -1, -1, -1).unwrap();
+        }
+
+        let received: Vec<Frame> = sources
+            .iter()
+            .map(|_| client.get_frame(0).unwrap())
+            .collect();
+        for (frame, source) in received.iter().zip(sources) {
+            assert_eq!(frame.source_id().unwrap(), source);
+        }
+
+        // Shared frames carry the source too
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame.alloc(None).unwrap();
+        frame.set_source_id(3).unwrap();
+        host.post_ref(&frame, expires, -1, -1, -1).unwrap();
+        let shared = client.get_frame(0).unwrap();
+        assert_eq!(shared.source_id().unwrap(), Some(3));
+
+        drop((received, shared));
+        drop(client);
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_display_rect_roundtrip() {
+        let socket_path = test_socket_path("display_rect");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        // Accept the client connection before posting
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        let expires = timestamp().unwrap() + 1_000_000_000;
+        let rect = Rect::new(2, 4, 60, 40);
+        for display_rect in [Some(rect), None] {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            assert_eq!(frame.display_rect().unwrap(), None);
+            if let Some(rect) = display_rect {
+                assert!(frame.set_display_rect(Rect::new(8, 0, 60, 40)).is_err());
+                frame.set_display_rect(rect).unwrap();
+            }
+            host.post(frame, expires, -1, -1, -1).unwrap();
+        }
+
+        let first = client.get_frame(0).unwrap();
+        let second = client.get_frame(0).unwrap();
+        assert_eq!(first.display_rect().unwrap(), Some(rect));
+        assert_eq!(second.display_rect().unwrap(), None);
+
+        drop((first, second));
+        drop(client);
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_plane_layout_roundtrip() {
+        use crate::frame::{PlaneInfo, PlaneInput};
+        use std::os::fd::BorrowedFd;
+
+        let socket_path = test_socket_path("plane_layout");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        // Accept the client connection before posting
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        // NV12 with a padded stride and a gap before the chroma plane, so
+        // the layout cannot be derived from the frame stride
+        let (width, height, stride) = (64, 48, 80);
+        let backing = Frame::new(64, 160, 0, "GREY").unwrap();
+        backing.alloc(None).unwrap();
+        // Safety: the handle stays open for as long as `backing` is alive.
+        let fd = unsafe { BorrowedFd::borrow_raw(backing.handle().unwrap()) };
+        let mut frame = Frame::from_planes(
+            &[
+                PlaneInput {
+                    fd,
+                    offset: 4096,
+                    stride,
+                    size: stride * height,
+                },
+                PlaneInput {
+                    fd,
+                    offset: 8192,
+                    stride,
+                    size: stride * height / 2,
+                },
+            ],
+            width as u32,
+            height as u32,
+            "NV12",
+        )
+        .unwrap();
+        for (index, value) in [(0, 0x10), (1, 0x80)] {
+            for row in frame.plane_mut(index).unwrap().rows_mut() {
+                row.fill(value);
+            }
+        }
+
+        let expected: Vec<PlaneInfo> = (0..2).map(|i| frame.plane(i).unwrap()).collect();
+        assert_eq!(frame.plane_descriptors().unwrap(), expected);
+
+        let expires = timestamp().unwrap() + 1_000_000_000;
+        host.post_ref(&frame, expires, -1, -1, -1).unwrap();
+
+        let received = client.get_frame(0).unwrap();
+        assert_eq!(received.plane_descriptors().unwrap(), expected);
+        assert_eq!(received.plane_count(), 2);
+        assert_eq!(received.plane(1), Some(expected[1]));
+        for (index, value) in [(0, 0x10), (1, 0x80)] {
+            let plane = received.map_plane(index).unwrap();
+            assert_eq!(plane.len(), expected[index].size);
+            assert!(plane.iter().all(|&byte| byte == value));
+        }
+
+        // Frames without a layout report none
+        let plain = Frame::new(64, 48, 0, "RGB3").unwrap();
+        plain.alloc(None).unwrap();
+        host.post(plain, expires, -1, -1, -1).unwrap();
+        let received_plain = client.get_frame(0).unwrap();
+        assert!(received_plain.plane_descriptors().unwrap().is_empty());
+        assert_eq!(received_plain.plane_count(), 0);
+
+        drop((received, received_plain));
+        drop(client);
+        drop(host);
+    }
+
+    #[test]
+    fn test_session_update() {
+        let mut session = Session {
+            reconnects: 0,
+            serial: 0,
+        };
+        assert_eq!(session.update(0, 5), Some(0));
+        assert_eq!(session.update(0, 6), Some(0));
+        assert_eq!(session.update(0, 9), Some(2));
+
+        // A restarted host numbers its frames from 1 again
+        assert_eq!(session.update(0, 1), None);
+        assert_eq!(session.update(0, 2), Some(0));
+
+        // Reconnecting starts a new session even if the serials move on
+        assert_eq!(session.update(1, 1_000_000), None);
+        assert_eq!(session.update(1, 1_000_001), Some(0));
+
+        // Without reconnect counts only serial resets are detected
+        let mut session = Session {
+            reconnects: -1,
+            serial: 0,
+        };
+        assert_eq!(session.update(-1, 100), Some(0));
+        assert_eq!(session.update(-1, 3), None);
+    }
+
+    #[test]
+    fn test_client_host_restart() {
+        use std::sync::mpsc;
+
+        let socket_path = test_socket_path("host_restart");
+        let post = |host: &Host| {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            let expires = timestamp().unwrap() + 1_000_000_000;
+            host.post(frame, expires, -1, -1, -1).unwrap();
+        };
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let restarts = Arc::new(Mutex::new(Vec::new()));
+        let client = {
+            let restarts = Arc::clone(&restarts);
+            Client::new(&socket_path, Reconnect::Yes)
+                .unwrap()
+                .on_reconnect(move |stats| restarts.lock().unwrap().push(*stats))
+        };
+        client.set_timeout(1.0).unwrap();
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+        for _ in 0..20 {
+            post(&host);
+        }
+
+        let (first_session, first_stats) = mpsc::channel();
+        let client_thread = thread::spawn(move || {
+            // Releasing a frame discards the frame events queued behind
+            // it, so hold them until all are received
+            let frames: Vec<Frame> = (0..20).map(|_| client.get_frame(0).unwrap()).collect();
+            drop(frames);
+            first_session.send(client.stats()).unwrap();
+
+            let deadline = Instant::now() + Duration::from_secs(10);
+            while client.stats().reconnects == 0 && Instant::now() < deadline {
+                let _ = client.get_frame(0);
+            }
+            let serial = client.get_frame(0).and_then(|frame| frame.serial());
+            (serial, client.stats())
+        });
+
+        let before = loop {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+            match first_stats.try_recv() {
+                Ok(stats) => break stats,
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(err) => panic!("client thread failed: {}", err),
+            }
+        };
+        assert_eq!(before.frames_received, 20);
+        assert_eq!(before.frames_missed, 0);
+        assert_eq!(before.reconnects, 0);
+
+        drop(host);
+        let host = Host::new(&socket_path).unwrap();
+        while !client_thread.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+            post(&host);
+        }
+        let (serial, after) = client_thread.join().unwrap();
+
+        // Whichever serial the second host reached, the jump from the first
+        // host's serials is not counted as missed and the counters restarted
+        assert!(serial.is_ok(), "{:?}", serial);
+        assert_eq!(after.reconnects, 1);
+        assert_eq!(after.frames_missed, 0);
+        assert!(after.frames_received <= 2, "{:?}", after);
+        assert_eq!(*restarts.lock().unwrap(), vec![before]);
+
+        drop(host);
+    }
+
+    /// Posts an H.264 frame holding a single NAL unit of `nal_type`.
+    fn post_h264(host: &Host, nal_type: u8, expires: i64) {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+
+        let nal = [0, 0, 0, 1, 0x60 | nal_type, 0x88];
+        let path = format!("./temp_client_h264_{}.bin", std::process::id());
+        let mut file = std::fs::File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(&nal).unwrap();
+        let frame = Frame::new(64, 1, 64, "H264").unwrap();
+        frame.attach(file.as_raw_fd(), nal.len(), 0).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        host.post(frame, expires, -1, -1, -1).unwrap();
+    }
+
+    #[test]
+    fn test_client_get_frame_from_keyframe() {
+        let socket_path = test_socket_path("from_keyframe");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        // Accept the client connection before posting
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        // Join mid-GOP: two P slices, then an IDR and its P slice
+        let expires = timestamp().unwrap() + 1_000_000_000;
+        for nal_type in [1, 1, 5, 1] {
+            post_h264(&host, nal_type, expires);
+        }
+
+        // Reading frames locks them, which needs the host to answer, so the
+        // client runs on its own thread while the host is serviced here.
+        let client_thread = thread::spawn(move || {
+            let first = client.get_frame_from_keyframe().unwrap();
+            assert!(first.is_keyframe(FourCC(*b"H264")).unwrap());
+            let next = client.get_frame(0).unwrap();
+            assert!(!next.is_keyframe(FourCC(*b"H264")).unwrap());
+            (
+                first.serial().unwrap(),
+                next.serial().unwrap(),
+                client.stats(),
+            )
+        });
+        while !client_thread.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        let (first, next, stats) = client_thread.join().unwrap();
+
+        // Host serials start at 1, so the IDR is serial 3
+        assert_eq!((first, next), (3, 4));
+        assert_eq!(stats.frames_dropped, 2);
+
+        drop(host);
+    }
+
+    /// Joins an encoded camera stream after its IDR and expects the first
+    /// delivered frame to be the next stream's IDR.
+    #[ignore = "test requires camera and VPU hardware"]
+    #[test]
+    fn test_client_get_frame_from_keyframe_encoded() {
+        use crate::camera::create_camera;
+        use crate::encoder::{Encoder, VSLEncoderProfileEnum, VSLRect};
+
+        let camera = create_camera()
+            .with_device("/dev/video3")
+            .with_resolution(1280, 720)
+            .with_format(FourCC(*b"NV12"))
+            .open()
+            .unwrap();
+        camera.start().unwrap();
+        let (width, height) = (camera.width(), camera.height());
+        let crop = VSLRect::new(0, 0, width, height);
+        let encode = |encoder: &Encoder| {
+            let buffer = camera.read().unwrap();
+            let output = encoder.new_output_frame(width, height, -1, -1, -1).unwrap();
+            let mut keyframe = 0;
+            encoder
+                .encode_camera_buffer(&buffer, &output, &crop, &mut keyframe)
+                .unwrap();
+            (output, keyframe != 0)
+        };
+        let create = || {
+            Encoder::create(
+                VSLEncoderProfileEnum::Kbps5000 as u32,
+                u32::from_le_bytes(*b"H264"),
+                30,
+            )
+            .unwrap()
+        };
+
+        let socket_path = test_socket_path("from_keyframe_encoded");
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(2.0).unwrap();
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        // The tail of one GOP, then a fresh encoder starting with an IDR
+        let expires = timestamp().unwrap() + 5_000_000_000;
+        let mid_gop = create();
+        let (idr, keyframe) = encode(&mid_gop);
+        assert!(keyframe);
+        drop(idr);
+        for _ in 0..5 {
+            let (frame, keyframe) = encode(&mid_gop);
+            if !keyframe {
+                host.post(frame, expires, -1, -1, -1).unwrap();
+            }
+        }
+        let (frame, keyframe) = encode(&create());
+        assert!(keyframe);
+        host.post(frame, expires, -1, -1, -1).unwrap();
+
+        let client_thread = thread::spawn(move || {
+            let first = client.get_frame_from_keyframe().unwrap();
+            first.is_keyframe(FourCC(*b"H264")).unwrap()
+        });
+        while !client_thread.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        assert!(client_thread.join().unwrap());
+
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_peek() {
+        let socket_path = test_socket_path("peek");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        // Accept the client connection before posting
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        assert_eq!(client.peek().unwrap(), None);
+
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame.alloc(None).unwrap();
+        let now = timestamp().unwrap();
+        host.post(frame, now + 1_000_000_000, 42, -1, -1).unwrap();
+
+        // Give the event time to arrive, peek never blocks
+        let mut meta = None;
+        for _ in 0..100 {
+            meta = client.peek().unwrap();
+            if meta.is_some() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        let meta = meta.expect("frame event should be queued");
+        assert_eq!(meta.size, 64 * 48 * 3);
+        assert_eq!(meta.fourcc, FourCC(*b"RGB3"));
+
+        // Peeking again does not consume the frame
+        assert_eq!(client.peek().unwrap(), Some(meta));
+
+        let received = client.get_frame(0).unwrap();
+        assert_eq!(received.serial().unwrap(), meta.serial);
+        assert_eq!(received.timestamp().unwrap(), meta.timestamp);
+
+        drop(received);
+        drop(client);
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_get_latest_frame() {
+        let socket_path = test_socket_path("latest");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        // Accept the client connection before posting
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        let now = timestamp().unwrap();
+        for _ in 0..5 {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            host.post(frame, now + 5_000_000_000, -1, -1, -1).unwrap();
+        }
+
+        // Releasing frames needs the host to answer unlock requests, so the
+        // client runs on its own thread while the host is serviced here.
+        let client_thread = thread::spawn(move || {
+            let serial = client
+                .get_latest_frame(Duration::from_secs(5))
+                .unwrap()
+                .serial()
+                .unwrap();
+            (serial, client.stats())
+        });
+        while !client_thread.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        let (serial, stats) = client_thread.join().unwrap();
+
+        // Host serials start at 1, so the newest of five frames is serial 5
+        assert_eq!(serial, 5);
+        assert_eq!(stats.frames_received, 5);
+        assert_eq!(stats.frames_dropped, 4);
+
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_queue_depth() {
+        let socket_path = test_socket_path("queue_depth");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No)
+            .unwrap()
+            .with_queue_depth(2);
+        client.set_timeout(1.0).unwrap();
+
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        let now = timestamp().unwrap();
+        for _ in 0..5 {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            host.post(frame, now + 5_000_000_000, -1, -1, -1).unwrap();
+        }
+
+        let client_thread = thread::spawn(move || {
+            // Give all five events time to arrive before the first receive
+            for _ in 0..100 {
+                if client.peek().unwrap().is_some() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+            thread::sleep(Duration::from_millis(50));
+
+            let first = client.get_frame(0).unwrap();
+            let queued = client.stats();
+            let second = client.get_frame(0).unwrap();
+            let serials = [first.serial().unwrap(), second.serial().unwrap()];
+            drop((first, second));
+            (serials, queued, client.stats())
+        });
+        while !client_thread.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        let (serials, queued, stats) = client_thread.join().unwrap();
+
+        // The oldest three of five frames were dropped
+        assert_eq!(serials, [4, 5]);
+        assert_eq!(queued.queue_depth, 1);
+        assert_eq!(stats.queue_depth, 0);
+        assert_eq!(stats.queue_high_water, 2);
+        assert_eq!(stats.frames_received, 5);
+        assert_eq!(stats.frames_dropped, 3);
+
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_into_channel() {
+        let socket_path = test_socket_path("channel");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        let now = timestamp().unwrap();
+        for _ in 0..5 {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            host.post(frame, now + 5_000_000_000, -1, -1, -1).unwrap();
+        }
+
+        let (bridge, frames) = client.into_channel(2);
+        let consumer = thread::spawn(move || {
+            // Hold received frames until the end so releasing them cannot
+            // discard events the bridge has not read yet.
+            let mut received = Vec::new();
+            while let Some(frame) = frames.recv_timeout(Duration::from_secs(5)) {
+                let serial = frame.serial().unwrap();
+                received.push(frame);
+                if serial == 5 {
+                    break;
+                }
+            }
+            let serials: Vec<i64> = received.iter().map(|f| f.serial().unwrap()).collect();
+            let dropped = frames.dropped();
+            drop(received);
+            (serials, dropped)
+        });
+        // The receiver is dropped with the consumer, which ends the bridge;
+        // keep answering unlock requests until both threads are done.
+        while !consumer.is_finished() || !bridge.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        let (serials, dropped) = consumer.join().unwrap();
+        assert!(bridge.join().unwrap().is_ok());
+
+        assert!(serials.windows(2).all(|w| w[0] < w[1]), "{:?}", serials);
+        assert_eq!(serials.last(), Some(&5));
+        assert_eq!(serials.len() as u64 + dropped, 5);
+
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_into_channel_release() {
+        let socket_path = test_socket_path("channel_release");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        // Receive and release the first frame with the other four queued
+        // behind it: the unlock reply skips them.
+        let now = timestamp().unwrap();
+        for _ in 0..5 {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            host.post(frame, now + 5_000_000_000, -1, -1, -1).unwrap();
+        }
+        let client_thread = thread::spawn(move || {
+            for _ in 0..100 {
+                if client.peek().unwrap().is_some() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+            thread::sleep(Duration::from_millis(50));
+            drop(client.get_frame(0).unwrap());
+            let discarded = client.discarded();
+            (client, discarded)
+        });
+        while !client_thread.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        let (client, discarded) = client_thread.join().unwrap();
+        assert_eq!(discarded, 4);
+
+        // Frames released as soon as they are received may skip events the
+        // bridge has not read yet, which dropped() counts.
+        for _ in 0..5 {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            host.post(frame, now + 5_000_000_000, -1, -1, -1).unwrap();
+        }
+        let (bridge, frames) = client.into_channel(2);
+        let consumer = thread::spawn(move || {
+            let mut received = 0;
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while received + frames.dropped() < 5 && Instant::now() < deadline {
+                if let Some(frame) = frames.recv_timeout(Duration::from_millis(10)) {
+                    drop(frame);
+                    received += 1;
+                }
+            }
+            (received, frames.dropped())
+        });
+        while !consumer.is_finished() || !bridge.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        let (received, dropped) = consumer.join().unwrap();
+        assert!(bridge.join().unwrap().is_ok());
+        assert_eq!(received + dropped, 5);
+
+        drop(host);
+    }
+
+    #[test]
+    fn test_shared_frame_locks_client_frame() {
+        let socket_path = test_socket_path("shared_lock");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame.alloc(None).unwrap();
+        host.post(frame, timestamp().unwrap() + 50_000_000, -1, -1, -1)
+            .unwrap();
+        let client_thread = thread::spawn(move || {
+            let frame = client.get_frame(0).unwrap();
+            (SharedFrame::new(frame).unwrap(), client)
+        });
+        while !client_thread.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        let (shared, client) = client_thread.join().unwrap();
+
+        // The held frame outlives its expiry time
+        thread::sleep(Duration::from_millis(100));
+        host.service_timeout(Duration::from_millis(10)).unwrap();
+        assert_eq!(host.drop_stats().unwrap().expired, 0);
+        assert_eq!(shared.data().len(), 64 * 48 * 3);
+
+        let release = thread::spawn(move || drop(shared));
+        while !release.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        release.join().unwrap();
+
+        drop(client);
+        drop(host);
+    }
+
+    #[test]
+    fn test_broadcast_client() {
+        let socket_path = test_socket_path("broadcast");
+
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        let broadcast = BroadcastClient::new(client, 8);
+        let subscribers: Vec<_> = (0..3)
+            .map(|_| {
+                let frames = broadcast.subscribe();
+                thread::spawn(move || {
+                    // Hold received frames until the end so releasing them
+                    // cannot discard events the broadcast has not read yet.
+                    let mut received = Vec::new();
+                    while let Ok(frame) = frames.recv_timeout(Duration::from_secs(5)) {
+                        let serial = frame.serial().unwrap();
+                        assert_eq!(frame.data()[0], serial as u8);
+                        received.push(frame);
+                        if serial == 5 {
+                            break;
+                        }
+                    }
+                    received
+                        .iter()
+                        .map(|f| (f.serial().unwrap(), Arc::as_ptr(f) as usize))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        assert_eq!(broadcast.subscribers(), 3);
+
+        let now = timestamp().unwrap();
+        for serial in 1..=5u8 {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            frame.mmap_mut().unwrap()[0] = serial;
+            host.post(frame, now + 5_000_000_000, -1, -1, -1).unwrap();
+        }
+
+        // Keep answering unlock requests until every subscriber is done.
+        while subscribers.iter().any(|s| !s.is_finished()) {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        let received: Vec<_> = subscribers.into_iter().map(|s| s.join().unwrap()).collect();
+
+        // Every subscriber saw the same frames, shared rather than copied
+        let serials: Vec<i64> = received[0].iter().map(|&(serial, _)| serial).collect();
+        assert_eq!(serials, [1, 2, 3, 4, 5]);
+        assert_eq!(received[1], received[0]);
+        assert_eq!(received[2], received[0]);
+        assert_eq!(broadcast.dropped(), 0);
+
+        // Subscribers that went away are pruned on the next frame
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame.alloc(None).unwrap();
+        host.post(frame, now + 5_000_000_000, -1, -1, -1).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while broadcast.subscribers() > 0 && Instant::now() < deadline {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        assert_eq!(broadcast.subscribers(), 0);
+
+        assert!(broadcast.stop().is_ok());
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_send_command() {
+        let socket_path = test_socket_path("command");
+
+        let mut host = Host::new(&socket_path).unwrap();
+        host.set_command_handler(|req| {
+            let mut reply = b"echo:".to_vec();
+            reply.extend_from_slice(req);
+            reply
+        })
+        .unwrap();
+        thread::sleep(HOST_READY_DELAY);
+
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        let client_thread = thread::spawn(move || {
+            let reply = client.send_command(b"ping");
+            let oversized = client.send_command(&[0u8; 4097]);
+            (reply, oversized)
+        });
+        while !client_thread.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        let (reply, oversized) = client_thread.join().unwrap();
+
+        assert_eq!(reply.unwrap(), b"echo:ping");
+        // Requests over VSL_COMMAND_MAX are rejected before sending
+        assert!(matches!(oversized, Err(Error::Io(_))));
+
+        // An unserviced host never replies, so the client timeout applies
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(0.2).unwrap();
+        let start = Instant::now();
+        let err = client.send_command(b"ping").unwrap_err();
+        assert!(matches!(&err, Error::Io(e) if e.kind() == io::ErrorKind::TimedOut));
+        assert!(start.elapsed() < Duration::from_millis(900));
+
+        drop(host);
+    }
+
+    #[test]
+    fn test_client_set_poll() {
+        let paths = [test_socket_path("set_a"), test_socket_path("set_b")];
+        let hosts: Vec<Host> = paths.iter().map(|p| Host::new(p).unwrap()).collect();
+        thread::sleep(HOST_READY_DELAY);
+
+        let set: ClientSet = paths
+            .iter()
+            .map(|p| Client::new(p, Reconnect::No).unwrap())
+            .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.get(0).unwrap().as_raw_fd() >= 0);
+
+        for host in &hosts {
+            host.service_timeout(Duration::from_millis(100)).unwrap();
+        }
+
+        // Nothing posted yet
+        assert!(set.poll(Duration::from_millis(20)).unwrap().is_empty());
+
+        // Each host posts a frame with its own width
+        let now = timestamp().unwrap();
+        for (host, width) in hosts.iter().zip([64, 32]) {
+            let frame = Frame::new(width, 16, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            host.post(frame, now + 5_000_000_000, -1, -1, -1).unwrap();
+        }
+
+        // Releasing received frames needs the hosts to answer unlock
+        // requests, so the set is polled on its own thread.
+        let set_thread = thread::spawn(move || {
+            let mut widths = Vec::new();
+            while widths.len() < 2 {
+                for (index, frame) in set.poll(Duration::from_secs(2)).unwrap() {
+                    widths.push((index, frame.unwrap().width().unwrap()));
+                }
+            }
+            widths.sort();
+            widths
+        });
+        while !set_thread.is_finished() {
+            for host in &hosts {
+                host.service_timeout(Duration::from_millis(5)).unwrap();
+            }
+        }
+
+        assert_eq!(set_thread.join().unwrap(), vec![(0, 64), (1, 32)]);
+    }
+
+    #[test]
+    fn test_client_set_hangup() {
+        let paths = [
+            test_socket_path("set_hangup"),
+            test_socket_path("set_retry"),
+        ];
+        let hosts: Vec<Host> = paths.iter().map(|p| Host::new(p).unwrap()).collect();
+        thread::sleep(HOST_READY_DELAY);
+
+        let mut set = ClientSet::new();
+        set.push(Client::new(&paths[0], Reconnect::No).unwrap());
+        set.push(Client::new(&paths[1], Reconnect::Yes).unwrap());
+        set.get(1).unwrap().set_timeout(1.0).unwrap();
+        for host in &hosts {
+            host.service_timeout(Duration::from_millis(100)).unwrap();
+        }
+
+        // Only the client that does not reconnect reports the hangup, once
+        drop(hosts);
+        let results = set.poll(Duration::from_millis(200)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, 0);
+        let err = results[0].1.as_ref().unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ENOTCONN));
+        assert!(set.poll(Duration::from_millis(200)).unwrap().is_empty());
+
+        // The other client reconnects once its host is back
+        let host = Host::new(&paths[1]).unwrap();
+        let set_thread = thread::spawn(move || loop {
+            let results = set.poll(Duration::from_secs(5)).unwrap();
+            if let Some((index, frame)) = results.into_iter().next() {
+                return (index, frame.map(|frame| frame.width().unwrap()));
+            }
+        });
+        while !set_thread.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+            let frame = Frame::new(32, 16, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            host.post(frame, timestamp().unwrap() + 1_000_000_000, -1, -1, -1)
+                .unwrap();
+            thread::sleep(Duration::from_millis(20));
+        }
+        let (index, width) = set_thread.join().unwrap();
+        assert_eq!((index, width.unwrap()), (1, 32));
+    }
+
     #[test]
     fn test_client_disconnect() {
         let socket_path = test_socket_path("client_disconnect");
@@ -534,8 +2697,7 @@ mod tests {
         assert!(path.to_str().unwrap().contains("reconnect_yes"));
 
         // Process the connection
-        let _ = host.poll(10);
-        let _ = host.process();
+        let _ = host.service_timeout(Duration::from_millis(10));
 
         drop(client);
         drop(host);
@@ -585,8 +2747,7 @@ mod tests {
 
         // Service connections for up to 5 seconds
         for _ in 0..500 {
-            let _ = host.poll(10);
-            let _ = host.process();
+            let _ = host.service_timeout(Duration::from_millis(10));
         }
 
         let client = client_thread.join().unwrap();
@@ -632,16 +2793,14 @@ mod tests {
 
         // Wait for client to connect
         while !client_connected.load(Ordering::SeqCst) {
-            let _ = host.poll(10);
-            let _ = host.process();
+            let _ = host.service_timeout(Duration::from_millis(10));
         }
 
         // Give connection time to establish
         thread::sleep(HOST_READY_DELAY);
 
         // Process pending connections
-        let _ = host.poll(10);
-        let _ = host.process();
+        let _ = host.service_timeout(Duration::from_millis(10));
 
         // Create and post a frame
         let frame = Frame::new(320, 240, 0, "RGB3").unwrap();
@@ -661,8 +2820,7 @@ mod tests {
 
         // Keep processing until client receives frame
         for _ in 0..100 {
-            let _ = host.poll(10);
-            let _ = host.process();
+            let _ = host.service_timeout(Duration::from_millis(10));
         }
 
         // Check client received the frame
@@ -0,0 +1,250 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+use crate::{
+    fourcc::FourCC,
+    v4l2::{DeviceEnumerator, DeviceType},
+    Error,
+};
+use std::fmt;
+
+/// Compressed video codecs probed by [`capabilities`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Codec {
+    H264,
+    Hevc,
+    Mjpg,
+    Vp8,
+    Vp9,
+}
+
+impl Codec {
+    /// All probed codecs, in the order reported by [`CodecCaps::iter`].
+    pub const ALL: [Codec; 5] = [
+        Codec::H264,
+        Codec::Hevc,
+        Codec::Mjpg,
+        Codec::Vp8,
+        Codec::Vp9,
+    ];
+
+    /// Returns the V4L2 fourcc of the codec's bitstream format.
+    pub fn fourcc(self) -> FourCC {
+        match self {
+            Codec::H264 => FourCC(*b"H264"),
+            Codec::Hevc => FourCC(*b"HEVC"),
+            Codec::Mjpg => FourCC(*b"MJPG"),
+            Codec::Vp8 => FourCC(*b"VP80"),
+            Codec::Vp9 => FourCC(*b"VP90"),
+        }
+    }
+
+    /// Returns the codec carried by a bitstream fourcc, accepting the
+    /// aliases drivers report (`AVC1` for H.264, `JPEG` for Motion JPEG).
+    pub fn from_fourcc(fourcc: FourCC) -> Option<Codec> {
+        match &fourcc.0 {
+            b"H264" | b"AVC1" => Some(Codec::H264),
+            b"HEVC" | b"H265" => Some(Codec::Hevc),
+            b"MJPG" | b"JPEG" => Some(Codec::Mjpg),
+            b"VP80" => Some(Codec::Vp8),
+            b"VP90" => Some(Codec::Vp9),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Codec::H264 => write!(f, "H.264"),
+            Codec::Hevc => write!(f, "HEVC"),
+            Codec::Mjpg => write!(f, "MJPEG"),
+            Codec::Vp8 => write!(f, "VP8"),
+            Codec::Vp9 => write!(f, "VP9"),
+        }
+    }
+}
+
+/// Hardware encode and decode support for one codec.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CodecSupport {
+    /// A V4L2 encoder produces this codec
+    pub encode: bool,
+    /// A V4L2 decoder accepts this codec
+    pub decode: bool,
+}
+
+/// Per-codec hardware support, returned by [`capabilities`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CodecCaps {
+    pub h264: CodecSupport,
+    pub hevc: CodecSupport,
+    pub mjpg: CodecSupport,
+    pub vp8: CodecSupport,
+    pub vp9: CodecSupport,
+}
+
+impl CodecCaps {
+    /// Returns the support for `codec`.
+    pub fn get(&self, codec: Codec) -> CodecSupport {
+        match codec {
+            Codec::H264 => self.h264,
+            Codec::Hevc => self.hevc,
+            Codec::Mjpg => self.mjpg,
+            Codec::Vp8 => self.vp8,
+            Codec::Vp9 => self.vp9,
+        }
+    }
+
+    fn get_mut(&mut self, codec: Codec) -> &mut CodecSupport {
+        match codec {
+            Codec::H264 => &mut self.h264,
+            Codec::Hevc => &mut self.hevc,
+            Codec::Mjpg => &mut self.mjpg,
+            Codec::Vp8 => &mut self.vp8,
+            Codec::Vp9 => &mut self.vp9,
+        }
+    }
+
+    /// Iterates over every probed codec with its support.
+    pub fn iter(&self) -> impl Iterator<Item = (Codec, CodecSupport)> + '_ {
+        Codec::ALL.into_iter().map(|codec| (codec, self.get(codec)))
+    }
+
+    /// Returns the codecs with hardware encode support.
+    pub fn encoders(&self) -> Vec<Codec> {
+        self.iter()
+            .filter(|(_, support)| support.encode)
+            .map(|(codec, _)| codec)
+            .collect()
+    }
+
+    /// Returns the codecs with hardware decode support.
+    pub fn decoders(&self) -> Vec<Codec> {
+        self.iter()
+            .filter(|(_, support)| support.decode)
+            .map(|(codec, _)| codec)
+            .collect()
+    }
+
+    /// Records the bitstream formats of one device. Encoders produce the
+    /// bitstream on their capture queue and decoders accept it on their
+    /// output queue.
+    fn add_device(&mut self, device_type: DeviceType, formats: impl IntoIterator<Item = FourCC>) {
+        for codec in formats.into_iter().filter_map(Codec::from_fourcc) {
+            match device_type {
+                DeviceType::Encoder => self.get_mut(codec).encode = true,
+                DeviceType::Decoder => self.get_mut(codec).decode = true,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Probes the V4L2 encoders and decoders for the codecs each supports.
+///
+/// Unlike [`encoder::is_available`](crate::encoder::is_available) and
+/// [`decoder::is_available`](crate::decoder::is_available), which only report
+/// whether the library was built with codec support, this reports per codec
+/// whether a hardware encoder and decoder are present, so a board with an
+/// H.264 encoder and only an HEVC decoder is described accurately.
+///
+/// Only V4L2 memory-to-memory devices are scanned. Codecs served by the
+/// Hantro `vpu_wrapper` backend or the `openh264` software encoder are not
+/// reported.
+///
+/// # Errors
+///
+/// Returns an error if the library cannot be loaded or predates the V4L2
+/// device enumeration API.
+///
+/// # Example
+///
+/// ```no_run
+/// use videostream::codec::{self, Codec};
+///
+/// let caps = codec::capabilities()?;
+/// for (codec, support) in caps.iter() {
+///     println!("{}: encode={} decode={}", codec, support.encode, support.decode);
+/// }
+/// if !caps.get(Codec::Hevc).encode {
+///     println!("HEVC encoding is not available");
+/// }
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub fn capabilities() -> Result<CodecCaps, Error> {
+    let devices = DeviceEnumerator::enumerate_types(&[DeviceType::Encoder, DeviceType::Decoder])?;
+    let mut caps = CodecCaps::default();
+    for device in &devices {
+        let formats = match device.device_type() {
+            DeviceType::Encoder => device.capture_formats(),
+            _ => device.output_formats(),
+        };
+        caps.add_device(device.device_type(), formats.iter().map(|fmt| fmt.fourcc));
+    }
+    Ok(caps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codec_caps_shape() {
+        let caps = CodecCaps::default();
+        let codecs: Vec<Codec> = caps.iter().map(|(codec, _)| codec).collect();
+        assert_eq!(codecs, Codec::ALL);
+        assert!(caps
+            .iter()
+            .all(|(_, support)| support == CodecSupport::default()));
+        assert!(caps.encoders().is_empty());
+        assert!(caps.decoders().is_empty());
+
+        for codec in Codec::ALL {
+            assert_eq!(Codec::from_fourcc(codec.fourcc()), Some(codec));
+        }
+        assert_eq!(Codec::from_fourcc(FourCC(*b"AVC1")), Some(Codec::H264));
+        assert_eq!(Codec::from_fourcc(FourCC(*b"NV12")), None);
+        assert_eq!(Codec::Hevc.to_string(), "HEVC");
+    }
+
+    #[test]
+    fn test_codec_caps_encode_decode_split() {
+        let mut caps = CodecCaps::default();
+        // H.264 encoder that also lists a raw format, HEVC-only decoder
+        caps.add_device(DeviceType::Encoder, [FourCC(*b"H264"), FourCC(*b"NV12")]);
+        caps.add_device(DeviceType::Decoder, [FourCC(*b"HEVC")]);
+        // Formats of other device types are ignored
+        caps.add_device(DeviceType::Camera, [FourCC(*b"MJPG")]);
+
+        assert_eq!(
+            caps.h264,
+            CodecSupport {
+                encode: true,
+                decode: false
+            }
+        );
+        assert_eq!(
+            caps.get(Codec::Hevc),
+            CodecSupport {
+                encode: false,
+                decode: true
+            }
+        );
+        assert_eq!(caps.mjpg, CodecSupport::default());
+        assert_eq!(caps.encoders(), vec![Codec::H264]);
+        assert_eq!(caps.decoders(), vec![Codec::Hevc]);
+    }
+
+    #[test]
+    #[ignore = "test requires V4L2 codec hardware"]
+    fn test_capabilities() {
+        let caps = capabilities().unwrap();
+        println!("{:<6} {:<7} {:<7}", "codec", "encode", "decode");
+        for (codec, support) in caps.iter() {
+            println!("{:<6} {:<7} {:<7}", codec, support.encode, support.decode);
+        }
+        assert!(!caps.encoders().is_empty() || !caps.decoders().is_empty());
+    }
+}
@@ -0,0 +1,491 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Pure-Rust pixel format converters.
+//!
+//! These run on the CPU and need neither G2D nor a DmaBuf, which makes them
+//! the software fallback of [`Frame::copy_to`](crate::frame::Frame::copy_to)
+//! and [`Frame::to_format`](crate::frame::Frame::to_format), and a reference
+//! for testing the hardware paths on a development host.
+//!
+//! NV12 buffers hold the luma plane followed directly by the interleaved
+//! CbCr plane, both using the same row stride. YUYV buffers hold one
+//! `Y0 U Y1 V` macropixel per two pixels. Odd widths and heights are
+//! handled by repeating the last column or row.
+
+use crate::{fourcc::FourCC, Error};
+use std::io;
+
+fn invalid(msg: String) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg))
+}
+
+/// Bytes spanned by `rows` rows of `row` bytes each, `stride` bytes apart.
+pub(crate) fn span(rows: usize, stride: usize, row: usize) -> usize {
+    match rows {
+        0 => 0,
+        n => (n - 1) * stride + row,
+    }
+}
+
+/// Checks that a buffer of `len` bytes and `stride` holds `rows` rows of
+/// `row` bytes starting at `offset`, returning the bytes of pixel data.
+fn check(
+    what: &str,
+    len: usize,
+    (offset, stride): (usize, usize),
+    rows: usize,
+    row: usize,
+) -> Result<usize, Error> {
+    if rows > 0 && stride < row {
+        return Err(invalid(format!(
+            "{} stride {} is smaller than a row of {} bytes",
+            what, stride, row
+        )));
+    }
+    let needed = offset + span(rows, stride, row);
+    if len < needed {
+        return Err(invalid(format!(
+            "{} buffer is {} bytes, needs {}",
+            what, len, needed
+        )));
+    }
+    Ok(rows * row)
+}
+
+/// The size of an image and where its rows lie within its buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Geometry {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    /// Byte offset and row stride of each plane; only semi-planar layouts
+    /// use the second, for the interleaved chroma.
+    pub(crate) planes: [(usize, usize); 2],
+}
+
+impl Geometry {
+    /// Rows `stride` bytes apart from the start of the buffer, with the
+    /// chroma rows of semi-planar layouts directly after the luma rows.
+    pub(crate) fn contiguous(width: usize, height: usize, stride: usize) -> Geometry {
+        Geometry {
+            width,
+            height,
+            planes: [(0, stride), (stride * height, stride)],
+        }
+    }
+}
+
+/// Pixel layouts handled by the software conversions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Layout {
+    /// 4:2:2 macropixels with the byte offsets of both lumas, U and V
+    Packed422 { y: [usize; 2], u: usize, v: usize },
+    /// 4:2:0 luma plane and interleaved chroma plane at offsets U and V
+    SemiPlanar { u: usize, v: usize },
+    /// Packed RGB with the byte offsets of red and blue
+    Rgb { bpp: usize, r: usize, b: usize },
+    /// Full-range grayscale, 8-bit or 16-bit little-endian of which only
+    /// the high byte is used
+    Grey { bpp: usize },
+}
+
+impl Layout {
+    const NV12: Layout = Layout::SemiPlanar { u: 0, v: 1 };
+    const YUYV: Layout = Layout::Packed422 {
+        y: [0, 2],
+        u: 1,
+        v: 3,
+    };
+
+    pub(crate) fn of(fourcc: u32) -> Option<Layout> {
+        Some(match &FourCC::from(fourcc).0 {
+            b"YUYV" | b"YUY2" => Layout::YUYV,
+            b"UYVY" => Layout::Packed422 {
+                y: [1, 3],
+                u: 0,
+                v: 2,
+            },
+            b"NV12" => Layout::NV12,
+            b"NV21" => Layout::SemiPlanar { u: 1, v: 0 },
+            b"RGB3" => Layout::Rgb { bpp: 3, r: 0, b: 2 },
+            b"BGR3" => Layout::Rgb { bpp: 3, r: 2, b: 0 },
+            b"RGBA" | b"RGBX" => Layout::Rgb { bpp: 4, r: 0, b: 2 },
+            b"BGRA" | b"BGRX" => Layout::Rgb { bpp: 4, r: 2, b: 0 },
+            b"GREY" => Layout::Grey { bpp: 1 },
+            b"Y16 " => Layout::Grey { bpp: 2 },
+            _ => return None,
+        })
+    }
+
+    /// Grayscale pixels are read and written as RGB with equal channels.
+    pub(crate) fn is_rgb(self) -> bool {
+        matches!(self, Layout::Rgb { .. } | Layout::Grey { .. })
+    }
+
+    /// Bytes of pixel data in a row of the first plane.
+    pub(crate) fn row(self, width: usize) -> usize {
+        match self {
+            Layout::Packed422 { .. } => (width + 1) / 2 * 4,
+            Layout::SemiPlanar { .. } => width,
+            Layout::Rgb { bpp, .. } | Layout::Grey { bpp } => width * bpp,
+        }
+    }
+
+    /// Checks that a buffer of `len` bytes holds an image of this layout,
+    /// returning the bytes of pixel data it covers.
+    pub(crate) fn check(self, what: &str, len: usize, geometry: &Geometry) -> Result<usize, Error> {
+        let Geometry {
+            width,
+            height,
+            planes,
+        } = *geometry;
+        let bytes = check(what, len, planes[0], height, self.row(width))?;
+        if let Layout::SemiPlanar { .. } = self {
+            let chroma = check(what, len, planes[1], (height + 1) / 2, (width + 1) / 2 * 2)?;
+            return Ok(bytes + chroma);
+        }
+        Ok(bytes)
+    }
+
+    /// Reads every pixel, as YUV or RGB triplets depending on the layout.
+    pub(crate) fn read(self, data: &[u8], geometry: &Geometry) -> Vec<[u8; 3]> {
+        let Geometry {
+            width,
+            height,
+            planes: [(base, stride), (chroma, chroma_stride)],
+        } = *geometry;
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in 0..height {
+            let line = base + row * stride;
+            for col in 0..width {
+                pixels.push(match self {
+                    Layout::Packed422 { y, u, v } => {
+                        let at = line + col / 2 * 4;
+                        [data[at + y[col % 2]], data[at + u], data[at + v]]
+                    }
+                    Layout::SemiPlanar { u, v } => {
+                        let at = chroma + row / 2 * chroma_stride + col / 2 * 2;
+                        [data[line + col], data[at + u], data[at + v]]
+                    }
+                    Layout::Rgb { bpp, r, b } => {
+                        let at = line + col * bpp;
+                        [data[at + r], data[at + 1], data[at + b]]
+                    }
+                    Layout::Grey { bpp } => {
+                        let y = data[line + col * bpp + bpp - 1];
+                        [y, y, y]
+                    }
+                });
+            }
+        }
+        pixels
+    }
+
+    /// Reads the luma of one pixel, converting from RGB if needed.
+    pub(crate) fn luma(self, data: &[u8], geometry: &Geometry, row: usize, col: usize) -> u8 {
+        let (base, stride) = geometry.planes[0];
+        let line = base + row * stride;
+        match self {
+            Layout::Packed422 { y, .. } => data[line + col / 2 * 4 + y[col % 2]],
+            Layout::SemiPlanar { .. } => data[line + col],
+            Layout::Rgb { bpp, r, b } => {
+                let at = line + col * bpp;
+                rgb_to_yuv([data[at + r], data[at + 1], data[at + b]])[0]
+            }
+            Layout::Grey { bpp } => data[line + col * bpp + bpp - 1],
+        }
+    }
+
+    /// Writes `pixels` read from a layout that is RGB when `rgb` is set.
+    /// Chroma is the average of the pixels each sample covers.
+    pub(crate) fn write(self, pixels: &[[u8; 3]], rgb: bool, data: &mut [u8], geometry: &Geometry) {
+        let Geometry {
+            width,
+            height,
+            planes: [(base, stride), (chroma, chroma_stride)],
+        } = *geometry;
+        let converted: Vec<[u8; 3]>;
+        let pixels = match (rgb, self.is_rgb()) {
+            (false, true) => {
+                converted = pixels.iter().map(|&px| yuv_to_rgb(px)).collect();
+                &converted
+            }
+            (true, false) => {
+                converted = pixels.iter().map(|&px| rgb_to_yuv(px)).collect();
+                &converted
+            }
+            _ => pixels,
+        };
+        // Odd widths and heights repeat the last column or row
+        let px = |row: usize, col: usize| pixels[row.min(height - 1) * width + col.min(width - 1)];
+        let average = |block: &[[u8; 3]], channel: usize| {
+            let sum: usize = block.iter().map(|px| px[channel] as usize).sum();
+            ((sum + block.len() / 2) / block.len()) as u8
+        };
+        for row in 0..height {
+            let line = base + row * stride;
+            match self {
+                Layout::Packed422 { y, u, v } => {
+                    for cx in 0..(width + 1) / 2 {
+                        let at = line + cx * 4;
+                        let pair = [px(row, cx * 2), px(row, cx * 2 + 1)];
+                        data[at + y[0]] = pair[0][0];
+                        data[at + y[1]] = pair[1][0];
+                        data[at + u] = average(&pair, 1);
+                        data[at + v] = average(&pair, 2);
+                    }
+                }
+                Layout::SemiPlanar { u, v } => {
+                    for col in 0..width {
+                        data[line + col] = px(row, col)[0];
+                    }
+                    if row % 2 == 0 {
+                        let line = chroma + row / 2 * chroma_stride;
+                        for cx in 0..(width + 1) / 2 {
+                            let (col, at) = (cx * 2, line + cx * 2);
+                            let block = [
+                                px(row, col),
+                                px(row, col + 1),
+                                px(row + 1, col),
+                                px(row + 1, col + 1),
+                            ];
+                            data[at + u] = average(&block, 1);
+                            data[at + v] = average(&block, 2);
+                        }
+                    }
+                }
+                Layout::Rgb { bpp, r, b } => {
+                    for col in 0..width {
+                        let at = line + col * bpp;
+                        let [red, green, blue] = px(row, col);
+                        data[at + r] = red;
+                        data[at + 1] = green;
+                        data[at + b] = blue;
+                        if bpp == 4 {
+                            data[at + 3] = 0xff;
+                        }
+                    }
+                }
+                Layout::Grey { bpp } => {
+                    for col in 0..width {
+                        let at = line + col * bpp;
+                        let [r, g, b] = px(row, col);
+                        // 16-bit samples repeat the byte to span the range
+                        data[at..at + bpp].fill(grey(r, g, b));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Converts an image between two layouts after checking both buffers,
+/// returning the bytes of pixel data written.
+pub(crate) fn convert(
+    (from, src, src_geometry): (Layout, &[u8], &Geometry),
+    (to, dst, dst_geometry): (Layout, &mut [u8], &Geometry),
+) -> Result<usize, Error> {
+    from.check("source", src.len(), src_geometry)?;
+    let bytes = to.check("target", dst.len(), dst_geometry)?;
+    let pixels = from.read(src, src_geometry);
+    to.write(&pixels, from.is_rgb(), dst, dst_geometry);
+    Ok(bytes)
+}
+
+/// BT.601 limited-range YUV to RGB.
+pub(crate) fn yuv_to_rgb([y, u, v]: [u8; 3]) -> [u8; 3] {
+    let c = 298 * (y as i32 - 16);
+    let (d, e) = (u as i32 - 128, v as i32 - 128);
+    let clamp = |x: i32| ((x + 128) >> 8).clamp(0, 255) as u8;
+    [
+        clamp(c + 409 * e),
+        clamp(c - 100 * d - 208 * e),
+        clamp(c + 516 * d),
+    ]
+}
+
+/// RGB to BT.601 limited-range YUV, as the software encoder converts.
+pub(crate) fn rgb_to_yuv([r, g, b]: [u8; 3]) -> [u8; 3] {
+    let (r, g, b) = (r as i32, g as i32, b as i32);
+    [
+        (((66 * r + 129 * g + 25 * b + 128) >> 8) + 16) as u8,
+        (((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128) as u8,
+        (((112 * r - 94 * g - 18 * b + 128) >> 8) + 128) as u8,
+    ]
+}
+
+/// Full-range BT.601 luma of an RGB pixel.
+pub(crate) fn grey(r: u8, g: u8, b: u8) -> u8 {
+    ((77 * r as u32 + 150 * g as u32 + 29 * b as u32) >> 8) as u8
+}
+
+/// Converts an NV12 image to YUYV.
+///
+/// `src_stride` is the row stride of both NV12 planes and `dst_stride` the
+/// row stride of the YUYV output, in bytes. Each chroma sample is shared by
+/// the two rows it covers.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if a stride
+/// is shorter than a row or a buffer is too small for the image.
+///
+/// # Example
+///
+/// ```
+/// use videostream::convert::nv12_to_yuyv;
+///
+/// let (width, height) = (4, 2);
+/// let nv12 = [16u8; 12];
+/// let mut yuyv = [0u8; 16];
+/// nv12_to_yuyv(&nv12, width, &mut yuyv, width * 2, width, height)?;
+/// assert!(yuyv.iter().all(|&b| b == 16));
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub fn nv12_to_yuyv(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    width: usize,
+    height: usize,
+) -> Result<(), Error> {
+    convert(
+        (
+            Layout::NV12,
+            src,
+            &Geometry::contiguous(width, height, src_stride),
+        ),
+        (
+            Layout::YUYV,
+            dst,
+            &Geometry::contiguous(width, height, dst_stride),
+        ),
+    )?;
+    Ok(())
+}
+
+/// Converts a YUYV image to NV12.
+///
+/// `src_stride` is the row stride of the YUYV input and `dst_stride` the
+/// row stride of both NV12 planes, in bytes. Each chroma sample is the
+/// average of the two rows it covers.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if a stride
+/// is shorter than a row or a buffer is too small for the image.
+///
+/// # Example
+///
+/// ```
+/// use videostream::convert::yuyv_to_nv12;
+///
+/// let (width, height) = (4, 2);
+/// let yuyv = [128u8; 16];
+/// let mut nv12 = [0u8; 12];
+/// yuyv_to_nv12(&yuyv, width * 2, &mut nv12, width, width, height)?;
+/// assert!(nv12.iter().all(|&b| b == 128));
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub fn yuyv_to_nv12(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    width: usize,
+    height: usize,
+) -> Result<(), Error> {
+    convert(
+        (
+            Layout::YUYV,
+            src,
+            &Geometry::contiguous(width, height, src_stride),
+        ),
+        (
+            Layout::NV12,
+            dst,
+            &Geometry::contiguous(width, height, dst_stride),
+        ),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a YUYV test image with a luma ramp and smoothly varying chroma.
+    fn yuyv_image(width: usize, height: usize, stride: usize) -> Vec<u8> {
+        let mut data = vec![0xAA; stride * height];
+        for row in 0..height {
+            for cx in 0..(width + 1) / 2 {
+                let at = row * stride + cx * 4;
+                data[at] = (16 + row * 8 + cx * 4) as u8;
+                data[at + 1] = (64 + cx * 6 + row * 2) as u8;
+                data[at + 2] = (18 + row * 8 + cx * 4) as u8;
+                data[at + 3] = (192 - cx * 5 - row * 3) as u8;
+            }
+        }
+        data
+    }
+
+    fn round_trip(width: usize, height: usize, yuyv_stride: usize, nv12_stride: usize) {
+        let src = yuyv_image(width, height, yuyv_stride);
+        let mut nv12 = vec![0; nv12_stride * (height + (height + 1) / 2)];
+        yuyv_to_nv12(&src, yuyv_stride, &mut nv12, nv12_stride, width, height).unwrap();
+        let mut back = vec![0; yuyv_stride * height];
+        nv12_to_yuyv(&nv12, nv12_stride, &mut back, yuyv_stride, width, height).unwrap();
+
+        for row in 0..height {
+            for col in 0..(width + 1) / 2 * 4 {
+                if col % 4 == 2 && col / 2 >= width {
+                    continue;
+                }
+                let (a, b) = (src[row * yuyv_stride + col], back[row * yuyv_stride + col]);
+                if col % 2 == 0 {
+                    assert_eq!(a, b, "luma at row {} byte {}", row, col);
+                } else {
+                    // Chroma is averaged over two rows, which differ by at
+                    // most 3 in the test image.
+                    assert!(a.abs_diff(b) <= 2, "chroma at row {} byte {}", row, col);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip(16, 8, 32, 16);
+    }
+
+    #[test]
+    fn test_round_trip_padded_strides() {
+        round_trip(12, 6, 32, 16);
+    }
+
+    #[test]
+    fn test_round_trip_odd_size() {
+        round_trip(7, 5, 16, 8);
+    }
+
+    #[test]
+    fn test_nv12_chroma_shared_by_rows() {
+        let (width, height) = (2, 2);
+        let nv12 = [10, 20, 30, 40, 100, 200];
+        let mut yuyv = [0; 8];
+        nv12_to_yuyv(&nv12, width, &mut yuyv, width * 2, width, height).unwrap();
+        assert_eq!(yuyv, [10, 100, 20, 200, 30, 100, 40, 200]);
+    }
+
+    #[test]
+    fn test_buffer_too_small() {
+        let mut nv12 = [0; 11];
+        let err = yuyv_to_nv12(&[0; 16], 8, &mut nv12, 4, 4, 2).unwrap_err();
+        assert!(matches!(err, Error::Io(e) if e.kind() == io::ErrorKind::InvalidInput));
+
+        let mut yuyv = [0; 16];
+        let err = nv12_to_yuyv(&[0; 12], 4, &mut yuyv, 6, 4, 2).unwrap_err();
+        assert!(matches!(err, Error::Io(e) if e.kind() == io::ErrorKind::InvalidInput));
+    }
+}
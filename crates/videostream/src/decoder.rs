@@ -27,10 +27,13 @@
 //! # Ok::<(), videostream::Error>(())
 //! ```
 
-use crate::{encoder::VSLRect, frame::Frame, Error};
+use crate::{codec::Codec, encoder::VSLRect, fourcc::FourCC, frame::Frame, Error};
 use std::{
+    cell::Cell,
+    cmp::Ordering,
+    collections::BinaryHeap,
     ffi::{c_int, c_void},
-    io,
+    fmt, io,
     ptr::null_mut,
 };
 use videostream_sys::{
@@ -38,19 +41,38 @@ use videostream_sys::{
     VSLDecoderRetCode_VSL_DEC_INIT_INFO,
 };
 
+mod file;
+
+pub use file::FileDecoder;
+
 /// Hardware video decoder instance.
 ///
 /// The decoder processes H.264 or H.265 NAL units and produces decoded frames.
 /// It automatically selects the best available backend (V4L2 or Hantro) unless
 /// explicitly specified via [`Decoder::create_ex`].
+///
+/// A `Decoder` is `Send` but not `Sync`: it can be moved to another thread,
+/// but a stream must be decoded from one thread at a time. Wrap it in a
+/// [`Mutex`](std::sync::Mutex) to share it, as
+/// [`SharedEncoder`](crate::encoder::SharedEncoder) does for encoders.
 pub struct Decoder {
     ptr: *mut ffi::VSLDecoder,
+    codec: DecoderCodec,
+    fps: c_int,
+    backend: CodecBackend,
+    output: FourCC,
+    decoded: Cell<Option<FourCC>>,
 }
 
+/// Pixel format of decoded frames unless another is requested.
+const NV12: FourCC = FourCC(*b"NV12");
+
 /// Video codec type for hardware decoder.
 ///
 /// Specifies which video compression standard to use for decoding.
-/// Both codecs are supported via hardware acceleration on i.MX8.
+/// H.264 and HEVC are supported via hardware acceleration on i.MX8. MJPEG,
+/// VP8 and VP9 are only decoded by V4L2 devices that report the format; see
+/// [`codec::capabilities`](crate::codec::capabilities) to check beforehand.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u32)]
 pub enum DecoderCodec {
@@ -65,6 +87,17 @@ pub enum DecoderCodec {
     /// Next-generation standard providing approximately 50% better compression
     /// than H.264 at equivalent quality.
     HEVC = ffi::VSLDecoderCodec_VSL_DEC_HEVC,
+
+    /// Motion JPEG, a sequence of independently coded JPEG images.
+    ///
+    /// Common on USB cameras. Every frame is a keyframe.
+    MJPG = ffi::VSLDecoderCodec_VSL_DEC_MJPG,
+
+    /// VP8 codec (RFC 6386).
+    VP8 = ffi::VSLDecoderCodec_VSL_DEC_VP8,
+
+    /// VP9 codec.
+    VP9 = ffi::VSLDecoderCodec_VSL_DEC_VP9,
 }
 
 impl DecoderCodec {
@@ -74,6 +107,30 @@ impl DecoderCodec {
         match self {
             DecoderCodec::H264 => fourcc(b"H264"),
             DecoderCodec::HEVC => fourcc(b"HEVC"),
+            DecoderCodec::MJPG => fourcc(b"MJPG"),
+            DecoderCodec::VP8 => fourcc(b"VP80"),
+            DecoderCodec::VP9 => fourcc(b"VP90"),
+        }
+    }
+
+    /// Error for a failed decoder creation, distinguishing a codec the
+    /// hardware cannot decode from missing hardware.
+    fn create_error(self) -> Error {
+        match io::Error::last_os_error().raw_os_error() {
+            Some(libc::ENOTSUP) => Error::UnsupportedCodec(self.into()),
+            _ => Error::HardwareNotAvailable("VPU decoder"),
+        }
+    }
+}
+
+impl From<DecoderCodec> for Codec {
+    fn from(codec: DecoderCodec) -> Self {
+        match codec {
+            DecoderCodec::H264 => Codec::H264,
+            DecoderCodec::HEVC => Codec::Hevc,
+            DecoderCodec::MJPG => Codec::Mjpg,
+            DecoderCodec::VP8 => Codec::Vp8,
+            DecoderCodec::VP9 => Codec::Vp9,
         }
     }
 }
@@ -166,6 +223,145 @@ pub fn is_backend_selection_available() -> Result<bool, Error> {
     Ok(lib.vsl_decoder_create_ex.is_ok())
 }
 
+/// Decoder settings beyond codec and frame rate, built up with `with_*`
+/// calls and applied by [`create`](Self::create).
+///
+/// [`Decoder::create`] is equivalent to a default `DecoderConfig`.
+///
+/// # Example
+///
+/// ```no_run
+/// use videostream::decoder::{DecoderCodec, DecoderConfig};
+///
+/// let decoder = DecoderConfig::new(DecoderCodec::H264, 30)
+///     .with_output_buffers(4)
+///     .with_low_latency(true)
+///     .create()?;
+/// # Ok::<(), videostream::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecoderConfig {
+    codec: DecoderCodec,
+    fps: c_int,
+    num_output_buffers: Option<u32>,
+    low_latency: bool,
+}
+
+impl DecoderConfig {
+    /// Largest output buffer pool accepted by
+    /// [`with_output_buffers`](Self::with_output_buffers).
+    pub const MAX_OUTPUT_BUFFERS: u32 = 8;
+
+    /// Settings for a `codec` decoder with the backend defaults. See
+    /// [`Decoder::create`] for the meaning of `fps`.
+    pub fn new(codec: DecoderCodec, fps: c_int) -> Self {
+        DecoderConfig {
+            codec,
+            fps,
+            num_output_buffers: None,
+            low_latency: false,
+        }
+    }
+
+    /// Size the pool of decoded frame buffers, from 1 to
+    /// [`MAX_OUTPUT_BUFFERS`](Self::MAX_OUTPUT_BUFFERS).
+    ///
+    /// This bounds how many decoded frames can be retained at once (see
+    /// the retention contract on [`Decoder::create`]). The driver may raise
+    /// the count to the minimum the stream needs. Only the V4L2 backend
+    /// can size its pool; the Hantro backend keeps its own with a warning.
+    pub fn with_output_buffers(mut self, count: u32) -> Self {
+        self.num_output_buffers = Some(count);
+        self
+    }
+
+    /// Return frames as soon as they are decoded instead of buffering them
+    /// for B-frame reordering.
+    ///
+    /// Removes the reorder delay for streams without B-frames, such as
+    /// those from [`Encoder`](crate::encoder::Encoder); streams with
+    /// B-frames are then returned in decode order. Needs the V4L2 backend
+    /// and a driver with the display delay controls; otherwise a warning is
+    /// logged and the decoder keeps its default buffering.
+    pub fn with_low_latency(mut self, enabled: bool) -> Self {
+        self.low_latency = enabled;
+        self
+    }
+
+    /// Returns the input codec.
+    pub fn codec(&self) -> DecoderCodec {
+        self.codec
+    }
+
+    /// Returns the frame-rate hint.
+    pub fn fps(&self) -> c_int {
+        self.fps
+    }
+
+    /// Returns the requested output buffer count, or `None` for the
+    /// backend default.
+    pub fn num_output_buffers(&self) -> Option<u32> {
+        self.num_output_buffers
+    }
+
+    /// Returns whether low-latency decoding is requested.
+    pub fn low_latency(&self) -> bool {
+        self.low_latency
+    }
+
+    /// Create a decoder with these settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] for an
+    /// output buffer count outside 1 to
+    /// [`MAX_OUTPUT_BUFFERS`](Self::MAX_OUTPUT_BUFFERS), otherwise the same
+    /// errors as [`Decoder::create`].
+    pub fn create(&self) -> Result<Decoder, Error> {
+        if let Some(count) = self.num_output_buffers {
+            if !(1..=Self::MAX_OUTPUT_BUFFERS).contains(&count) {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "output buffer count {} is not between 1 and {}",
+                        count,
+                        Self::MAX_OUTPUT_BUFFERS
+                    ),
+                )));
+            }
+        }
+
+        let decoder = Decoder::create(self.codec, self.fps)?;
+        let lib = ffi::init()?;
+        if let Some(count) = self.num_output_buffers {
+            let applied = match lib.vsl_decoder_set_output_buffers.as_ref() {
+                Ok(set_buffers) => (unsafe { set_buffers(decoder.ptr, count as c_int) }) == 0,
+                Err(_) => false,
+            };
+            if !applied {
+                log::warn!(
+                    "{} cannot use {} output buffers, keeping its default",
+                    decoder,
+                    count
+                );
+            }
+        }
+        if self.low_latency {
+            let applied = match lib.vsl_decoder_set_low_latency.as_ref() {
+                Ok(set_low_latency) => (unsafe { set_low_latency(decoder.ptr, 1) }) == 0,
+                Err(_) => false,
+            };
+            if !applied {
+                log::warn!(
+                    "{} has no low-latency mode, frames may be held for reordering",
+                    decoder
+                );
+            }
+        }
+        Ok(decoder)
+    }
+}
+
 impl Decoder {
     /// Create a new decoder instance with automatic backend selection.
     ///
@@ -188,13 +384,14 @@ impl Decoder {
     /// recycle that frame's backing dma-buf slot. Dropping a `Frame` returns
     /// its slot to the pool immediately. The number of frames that may be
     /// retained concurrently is bounded by the backend's internal pool size
-    /// (currently fixed); over-retention will stall the decoder when the
-    /// pool runs out of free slots.
+    /// (see [`DecoderConfig::with_output_buffers`]); over-retention will
+    /// stall the decoder when the pool runs out of free slots.
     ///
     /// # Errors
     ///
     /// Returns `Error::SymbolNotFound` if the library was compiled without VPU support.
     /// Returns `Error::HardwareNotAvailable` if the VPU hardware is not present.
+    /// Returns `Error::UnsupportedCodec` if no hardware decoder supports `codec`.
     /// Returns `Error::NullPointer` if the decoder creation fails for other reasons.
     ///
     /// # Example
@@ -215,9 +412,16 @@ impl Decoder {
         let ptr = unsafe { lib.vsl_decoder_create(codec as ffi::VSLDecoderCodec, fps) };
 
         if ptr.is_null() {
-            Err(Error::HardwareNotAvailable("VPU decoder"))
+            Err(codec.create_error())
         } else {
-            Ok(Decoder { ptr })
+            Ok(Decoder {
+                ptr,
+                codec,
+                fps,
+                backend: CodecBackend::Auto,
+                output: NV12,
+                decoded: Cell::new(None),
+            })
         }
     }
 
@@ -234,6 +438,7 @@ impl Decoder {
     ///
     /// Returns `Error::SymbolNotFound` if the library doesn't support backend selection.
     /// Returns `Error::HardwareNotAvailable` if the specified backend is unavailable.
+    /// Returns `Error::UnsupportedCodec` if the backend cannot decode `codec`.
     ///
     /// # Example
     ///
@@ -260,10 +465,98 @@ impl Decoder {
         };
 
         if ptr.is_null() {
-            Err(Error::HardwareNotAvailable("VPU decoder"))
+            Err(codec.create_error())
         } else {
-            Ok(Decoder { ptr })
+            Ok(Decoder {
+                ptr,
+                codec,
+                fps,
+                backend,
+                output: NV12,
+                decoded: Cell::new(None),
+            })
+        }
+    }
+
+    /// Create a decoder that returns frames in `output`, such as `RGB3` or
+    /// `BGR3`, instead of NV12.
+    ///
+    /// The V4L2 backend is asked to decode directly into `output`, saving
+    /// the separate conversion pass. When the backend or the VPU cannot
+    /// produce it, frames are decoded to NV12 and converted with
+    /// [`Frame::to_format`], with a warning logged. Either way
+    /// [`decode_frame`](Self::decode_frame) returns frames in `output`;
+    /// [`decoded_format`](Self::decoded_format) reports which format the
+    /// hardware actually produced.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Decoder::create`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{
+    ///     decoder::{Decoder, DecoderCodec},
+    ///     fourcc::FourCC,
+    /// };
+    ///
+    /// let decoder = Decoder::create_with_output(DecoderCodec::H264, 30, FourCC(*b"RGB3"))?;
+    /// assert_eq!(decoder.output_format(), FourCC(*b"RGB3"));
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn create_with_output(
+        codec: DecoderCodec,
+        fps: c_int,
+        output: FourCC,
+    ) -> Result<Self, Error> {
+        let mut decoder = Decoder::create(codec, fps)?;
+        decoder.output = output;
+        if output == NV12 {
+            return Ok(decoder);
+        }
+
+        let lib = ffi::init()?;
+        let requested = match lib.vsl_decoder_set_output_fourcc.as_ref() {
+            Ok(set_output) => (unsafe { set_output(decoder.ptr, output.into()) }) == 0,
+            Err(_) => false,
+        };
+        if !requested {
+            log::warn!(
+                "{} cannot output {}, converting decoded NV12 frames",
+                decoder,
+                output
+            );
         }
+        Ok(decoder)
+    }
+
+    /// Returns the pixel format of frames returned by
+    /// [`decode_frame`](Self::decode_frame).
+    pub fn output_format(&self) -> FourCC {
+        self.output
+    }
+
+    /// Returns the pixel format the decoder produced before any conversion
+    /// to [`output_format`](Self::output_format), or `None` until the first
+    /// frame is decoded.
+    pub fn decoded_format(&self) -> Option<FourCC> {
+        self.decoded.get()
+    }
+
+    /// Returns the input codec the decoder was created with.
+    pub fn codec(&self) -> DecoderCodec {
+        self.codec
+    }
+
+    /// Returns the frame-rate hint the decoder was created with.
+    pub fn fps(&self) -> c_int {
+        self.fps
+    }
+
+    /// Returns the backend requested at creation.
+    pub fn backend(&self) -> CodecBackend {
+        self.backend
     }
 
     /// Returns the width of decoded frames in pixels.
@@ -310,9 +603,35 @@ impl Decoder {
     ///
     /// A tuple containing:
     /// * `DecodeReturnCode` - Status of the decode operation
-    /// * `usize` - Number of bytes consumed from the input data
+    /// * `usize` - Number of bytes consumed from the input data, at most
+    ///   `data.len()`
     /// * `Option<Frame>` - Decoded frame if available
     ///
+    /// # Partial input
+    ///
+    /// A call may consume only part of `data`: the Hantro backend stops
+    /// after one frame, and the V4L2 backend after as much as fits in one
+    /// input buffer. When fewer than `data.len()` bytes are consumed, call
+    /// again with the remainder, `&data[consumed..]`, until it is empty.
+    /// Nothing is buffered on the caller's behalf, so dropping the remainder
+    /// loses that data. A call may also return a frame decoded from earlier
+    /// input while consuming nothing; resubmit the same slice. A call that
+    /// consumes nothing and returns no frame means the decoder cannot
+    /// accept more input, typically because every output buffer is held by
+    /// the caller. [`decode_all`](Self::decode_all) implements this loop.
+    ///
+    /// # Zero-copy output
+    ///
+    /// On the V4L2 backend the decoded frame wraps one of the decoder's DMA
+    /// buffers, so it can be passed straight to
+    /// [`Host::post`](crate::host::Host::post) to publish decoded video
+    /// without a copy. The buffer returns to the decoder when the frame is
+    /// released, for a posted frame when the host expires it, so holding more
+    /// frames than the decoder has buffers stalls decoding (see
+    /// [`DecoderConfig::with_output_buffers`]). Frames stay valid after the
+    /// decoder is dropped. A frame whose format differs from the decoder's
+    /// output format is converted into a new buffer.
+    ///
     /// # Errors
     ///
     /// Returns an error if the decoder encounters an unrecoverable error.
@@ -346,6 +665,11 @@ impl Decoder {
         &self,
         data: &[u8],
     ) -> Result<(DecodeReturnCode, usize, Option<Frame>), Error> {
+        #[cfg(feature = "tracing")]
+        let span = crate::trace::timed_span!("decode");
+        #[cfg(feature = "tracing")]
+        span.record_size(data.len());
+
         let lib = ffi::init()?;
 
         if lib.vsl_decode_frame.is_err() {
@@ -377,6 +701,10 @@ impl Decoder {
                 "Decoder Error",
             )));
         }
+        let output_frame = match output_frame {
+            Some(frame) => Some(self.convert_output(frame)?),
+            None => None,
+        };
         let mut return_msg = DecodeReturnCode::Success;
         if ret_code & VSLDecoderRetCode_VSL_DEC_FRAME_DEC > 0 {
             return_msg = DecodeReturnCode::FrameDecoded;
@@ -386,12 +714,111 @@ impl Decoder {
             return_msg = DecodeReturnCode::Initialized;
         }
 
-        Ok((return_msg, bytes_used, output_frame))
+        Ok((return_msg, bytes_used.min(data.len()), output_frame))
+    }
+
+    /// Decodes all of `data`, returning every frame output along the way.
+    ///
+    /// Resubmits the unconsumed remainder to
+    /// [`decode_frame`](Self::decode_frame) until the whole buffer is
+    /// consumed, as described under its partial input rules, so `data` may
+    /// hold any number of access units. Frames the decoder still buffers
+    /// for reordering are returned by later calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`decode_frame`](Self::decode_frame),
+    /// or [`Error::Io`] with [`io::ErrorKind::WouldBlock`] if the decoder
+    /// stops consuming input, for example because the caller still holds
+    /// every output buffer.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::decoder::{Decoder, DecoderCodec};
+    ///
+    /// let decoder = Decoder::create(DecoderCodec::H264, 30)?;
+    /// let recording = std::fs::read("clip.h264")?;
+    /// for frame in decoder.decode_all(&recording)? {
+    ///     println!("{}x{}", frame.width()?, frame.height()?);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn decode_all(&self, mut data: &[u8]) -> Result<Vec<Frame>, Error> {
+        let mut frames = Vec::new();
+        let mut stalled = 0;
+        while !data.is_empty() {
+            let (_, consumed, frame) = self.decode_frame(data)?;
+            if consumed == 0 && frame.is_none() {
+                // The V4L2 backend may need one call to start streaming
+                stalled += 1;
+                if stalled > 1 {
+                    return Err(Error::Io(io::Error::new(
+                        io::ErrorKind::WouldBlock,
+                        format!(
+                            "decoder stopped consuming input with {} bytes left",
+                            data.len()
+                        ),
+                    )));
+                }
+            } else {
+                stalled = 0;
+            }
+            frames.extend(frame);
+            data = &data[consumed..];
+        }
+        Ok(frames)
+    }
+}
+
+impl Decoder {
+    /// Converts a decoded frame to the requested output format when the
+    /// hardware produced another.
+    fn convert_output(&self, frame: Frame) -> Result<Frame, Error> {
+        let decoded = FourCC::from(frame.fourcc()?);
+        if self.decoded.replace(Some(decoded)).is_none() && decoded != self.output {
+            log::warn!(
+                "{} produced {} frames, converting to {}",
+                self,
+                decoded,
+                self.output
+            );
+        }
+        if decoded == self.output {
+            Ok(frame)
+        } else {
+            frame.to_format(self.output)
+        }
+    }
+}
+
+impl fmt::Debug for Decoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decoder")
+            .field("codec", &self.codec)
+            .field("fps", &self.fps)
+            .field("backend", &self.backend)
+            .field("output", &self.output)
+            .finish()
+    }
+}
+
+impl fmt::Display for Decoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} decoder, {} fps", self.codec, self.fps)?;
+        if self.backend != CodecBackend::Auto {
+            write!(f, " ({:?})", self.backend)?;
+        }
+        Ok(())
     }
 }
 
 impl Drop for Decoder {
     fn drop(&mut self) {
+        if self.ptr.is_null() {
+            return;
+        }
+
         if let Ok(lib) = ffi::init() {
             if lib.vsl_decoder_release.is_ok() {
                 unsafe {
@@ -405,6 +832,140 @@ impl Drop for Decoder {
 // Safety: Decoder uses a thread-safe C API
 unsafe impl Send for Decoder {}
 
+/// Restores presentation order for frames delivered in decode order.
+///
+/// Streams with B-frames are decoded, and published by hosts, in an order
+/// that differs from their presentation timestamps. The buffer holds up to
+/// `depth` frames and releases the one with the lowest PTS once more
+/// arrive, so display order is correct as long as no frame is displaced by
+/// more than `depth` positions. A depth of 0 passes frames straight
+/// through; the number of B-frames between reference frames is usually
+/// enough.
+///
+/// Frames with equal PTS are released in arrival order. A frame arriving
+/// after a later-PTS frame was already released is still returned, out of
+/// order; increase the depth if that happens.
+///
+/// # Example
+///
+/// ```no_run
+/// use videostream::decoder::{Decoder, DecoderCodec, ReorderBuffer};
+///
+/// let decoder = Decoder::create(DecoderCodec::H264, 30)?;
+/// let mut reorder = ReorderBuffer::new(2);
+/// # let packets: Vec<Vec<u8>> = Vec::new();
+/// for packet in &packets {
+///     if let (_, _, Some(frame)) = decoder.decode_frame(packet)? {
+///         if let Some(frame) = reorder.push_frame(frame)? {
+///             println!("display pts {}", frame.pts()?);
+///         }
+///     }
+/// }
+/// for frame in reorder.drain() {
+///     println!("display pts {}", frame.pts()?);
+/// }
+/// # Ok::<(), videostream::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct ReorderBuffer<T = Frame> {
+    depth: usize,
+    pending: BinaryHeap<Pending<T>>,
+    arrivals: u64,
+}
+
+impl<T> ReorderBuffer<T> {
+    /// Creates a buffer releasing frames once more than `depth` are held.
+    pub fn new(depth: usize) -> Self {
+        ReorderBuffer {
+            depth,
+            pending: BinaryHeap::with_capacity(depth + 1),
+            arrivals: 0,
+        }
+    }
+
+    /// Adds `item` presented at `pts`, returning the lowest-PTS item held
+    /// once the buffer exceeds its depth.
+    pub fn push(&mut self, pts: i64, item: T) -> Option<T> {
+        self.pending.push(Pending {
+            pts,
+            arrival: self.arrivals,
+            item,
+        });
+        self.arrivals += 1;
+        if self.pending.len() > self.depth {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Removes the lowest-PTS item regardless of depth, for flushing at the
+    /// end of a stream or before a seek.
+    pub fn pop(&mut self) -> Option<T> {
+        self.pending.pop().map(|pending| pending.item)
+    }
+
+    /// Removes every held item in presentation order.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(move || self.pop())
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of items held.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl ReorderBuffer<Frame> {
+    /// Adds `frame` at its [`Frame::pts`], see [`ReorderBuffer::push`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame's PTS cannot be read; the frame is
+    /// dropped.
+    pub fn push_frame(&mut self, frame: Frame) -> Result<Option<Frame>, Error> {
+        let pts = frame.pts()?;
+        Ok(self.push(pts, frame))
+    }
+}
+
+/// Held item ordered so the max-heap yields the lowest PTS, then the
+/// earliest arrival.
+#[derive(Debug)]
+struct Pending<T> {
+    pts: i64,
+    arrival: u64,
+    item: T,
+}
+
+impl<T> Ord for Pending<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.pts, other.arrival).cmp(&(self.pts, self.arrival))
+    }
+}
+
+impl<T> PartialOrd for Pending<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> PartialEq for Pending<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.pts, self.arrival) == (other.pts, other.arrival)
+    }
+}
+
+impl<T> Eq for Pending<T> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -413,6 +974,103 @@ mod tests {
     fn test_decoder_codec_values() {
         assert_eq!(DecoderCodec::H264 as u32, 0);
         assert_eq!(DecoderCodec::HEVC as u32, 1);
+        assert_eq!(DecoderCodec::MJPG as u32, 2);
+        assert_eq!(DecoderCodec::VP8 as u32, 3);
+        assert_eq!(DecoderCodec::VP9 as u32, 4);
+    }
+
+    #[test]
+    fn test_decoder_codec_mapping() {
+        let codecs = [
+            (DecoderCodec::H264, Codec::H264),
+            (DecoderCodec::HEVC, Codec::Hevc),
+            (DecoderCodec::MJPG, Codec::Mjpg),
+            (DecoderCodec::VP8, Codec::Vp8),
+            (DecoderCodec::VP9, Codec::Vp9),
+        ];
+        for (decoder_codec, codec) in codecs {
+            assert_eq!(Codec::from(decoder_codec), codec);
+            assert_eq!(
+                FourCC::from(decoder_codec.to_fourcc()),
+                codec.fourcc(),
+                "{:?}",
+                decoder_codec
+            );
+        }
+    }
+
+    #[test]
+    fn test_decoder_debug_display() {
+        let mut decoder = Decoder {
+            ptr: null_mut(),
+            codec: DecoderCodec::HEVC,
+            fps: 30,
+            backend: CodecBackend::Auto,
+            output: NV12,
+            decoded: Cell::new(None),
+        };
+        let debug = format!("{:?}", decoder);
+        assert!(debug.contains("HEVC"), "{}", debug);
+        assert!(debug.contains("fps: 30"), "{}", debug);
+        assert_eq!(decoder.to_string(), "HEVC decoder, 30 fps");
+
+        decoder.backend = CodecBackend::Hantro;
+        assert_eq!(decoder.to_string(), "HEVC decoder, 30 fps (Hantro)");
+    }
+
+    #[test]
+    fn test_reorder_buffer() {
+        // I P B B P B B in decode order, 40 ms apart in presentation order
+        let decode_order = [0, 120, 40, 80, 240, 160, 200];
+        let mut reorder = ReorderBuffer::new(2);
+        let mut shown = Vec::new();
+        for pts in decode_order {
+            shown.extend(reorder.push(pts, pts));
+            assert!(reorder.len() <= 2);
+        }
+        shown.extend(reorder.drain());
+        assert_eq!(shown, [0, 40, 80, 120, 160, 200, 240]);
+        assert!(reorder.is_empty());
+
+        // A frame displaced beyond the depth comes out late
+        let mut reorder = ReorderBuffer::new(1);
+        let mut shown: Vec<i64> = [0, 40, 80, 20]
+            .into_iter()
+            .filter_map(|pts| reorder.push(pts, pts))
+            .collect();
+        shown.extend(reorder.drain());
+        assert_eq!(shown, [0, 40, 20, 80]);
+
+        // Depth 0 passes through, equal PTS keep arrival order
+        let mut reorder = ReorderBuffer::new(0);
+        assert_eq!(reorder.push(80, "a"), Some("a"));
+        let mut reorder = ReorderBuffer::new(3);
+        for (pts, item) in [(40, "b"), (0, "a"), (40, "c")] {
+            assert_eq!(reorder.push(pts, item), None);
+        }
+        assert_eq!(reorder.drain().collect::<Vec<_>>(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_decoder_config() {
+        let config = DecoderConfig::new(DecoderCodec::HEVC, 25);
+        assert_eq!(config.codec(), DecoderCodec::HEVC);
+        assert_eq!(config.fps(), 25);
+        assert_eq!(config.num_output_buffers(), None);
+        assert!(!config.low_latency());
+
+        let config = config.with_output_buffers(4).with_low_latency(true);
+        assert_eq!(config.num_output_buffers(), Some(4));
+        assert!(config.low_latency());
+
+        for count in [0, DecoderConfig::MAX_OUTPUT_BUFFERS + 1] {
+            let err = config.with_output_buffers(count).create().unwrap_err();
+            assert!(
+                matches!(&err, Error::Io(e) if e.kind() == io::ErrorKind::InvalidInput),
+                "{}",
+                err
+            );
+        }
     }
 
     #[test]
@@ -562,4 +1220,289 @@ mod tests {
         let decoder = Decoder::create_ex(DecoderCodec::H264, 30, CodecBackend::Hantro);
         assert!(decoder.is_ok());
     }
+
+    /// Decodes a VPU encoded frame into RGB, natively or by conversion.
+    #[ignore = "test requires VPU hardware"]
+    #[test]
+    fn test_decoder_rgb_output() {
+        use crate::encoder::{Encoder, VSLEncoderProfileEnum, VSLRect};
+
+        let (width, height) = (640, 480);
+        let rgb = FourCC(*b"RGB3");
+        let encoder = Encoder::create(
+            VSLEncoderProfileEnum::Kbps5000 as u32,
+            u32::from_le_bytes(*b"H264"),
+            30,
+        )
+        .unwrap();
+        let decoder = Decoder::create_with_output(DecoderCodec::H264, 30, rgb).unwrap();
+        assert_eq!(decoder.output_format(), rgb);
+        let crop = VSLRect::new(0, 0, width, height);
+
+        let mut decoded = None;
+        for _ in 0..30 {
+            let source = Frame::new(width as u32, height as u32, 0, "NV12").unwrap();
+            source.alloc(None).unwrap();
+            let output = encoder.new_output_frame(width, height, -1, -1, -1).unwrap();
+            let mut keyframe = 0;
+            let size = unsafe { encoder.frame(&source, &output, &crop, &mut keyframe) }.unwrap();
+            let data = output.map().unwrap()[..size as usize].to_vec();
+
+            if let (_, _, Some(frame)) = decoder.decode_frame(&data).unwrap() {
+                decoded = Some(frame);
+                break;
+            }
+        }
+        let frame = decoded.expect("decoder produced no frame");
+
+        assert_eq!(FourCC::from(frame.fourcc().unwrap()), rgb);
+        println!("decoder produced {:?}", decoder.decoded_format());
+        assert!(decoder.decoded_format().is_some());
+    }
+
+    /// Posts a decoded frame to a host and expects the client to receive
+    /// the decoder's own DMA buffer, compared by inode since the descriptor
+    /// is duplicated when passed over the socket.
+    #[ignore = "test requires VPU hardware"]
+    #[test]
+    fn test_decoder_output_post_zero_copy() {
+        use crate::client::{Client, Reconnect};
+        use crate::encoder::{Encoder, VSLEncoderProfileEnum};
+        use crate::host::Host;
+        use std::{
+            fs::File, mem::ManuallyDrop, os::fd::FromRawFd, os::unix::fs::MetadataExt, thread,
+            time::Duration,
+        };
+
+        let inode = |fd: i32| {
+            // Safety: the descriptor is borrowed from a live frame and
+            // ManuallyDrop keeps `File` from closing it.
+            ManuallyDrop::new(unsafe { File::from_raw_fd(fd) })
+                .metadata()
+                .unwrap()
+                .ino()
+        };
+
+        let (width, height) = (640, 480);
+        let encoder = Encoder::create(
+            VSLEncoderProfileEnum::Kbps5000 as u32,
+            u32::from_le_bytes(*b"H264"),
+            30,
+        )
+        .unwrap();
+        let crop = VSLRect::new(0, 0, width, height);
+        let decoder = Decoder::create(DecoderCodec::H264, 30).unwrap();
+        let decoded = (0..30)
+            .find_map(|_| {
+                let source = Frame::new(width as u32, height as u32, 0, "NV12").unwrap();
+                source.alloc(None).unwrap();
+                let output = encoder.new_output_frame(width, height, -1, -1, -1).unwrap();
+                let mut keyframe = 0;
+                let size =
+                    unsafe { encoder.frame(&source, &output, &crop, &mut keyframe) }.unwrap();
+                let data = output.map().unwrap()[..size as usize].to_vec();
+                let (_, _, frame) = decoder.decode_frame(&data).unwrap();
+                frame
+            })
+            .expect("decoder produced no frame");
+        let decoded_inode = inode(decoded.handle().unwrap());
+
+        let socket_path = format!("/tmp/vsl_test_decoder_post_{}.sock", std::process::id());
+        let host = Host::new(&socket_path).unwrap();
+        thread::sleep(Duration::from_millis(5));
+        let client = Client::new(&socket_path, Reconnect::No).unwrap();
+        client.set_timeout(1.0).unwrap();
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+
+        // The decoded frame is posted as is, without a copy into a new buffer
+        let expires = crate::timestamp().unwrap() + 1_000_000_000;
+        host.post(decoded, expires, -1, -1, -1).unwrap();
+
+        let client_thread = thread::spawn(move || {
+            let frame = client.get_frame(0).unwrap();
+            inode(frame.handle().unwrap())
+        });
+        while !client_thread.is_finished() {
+            host.service_timeout(Duration::from_millis(10)).unwrap();
+        }
+        assert_eq!(client_thread.join().unwrap(), decoded_inode);
+
+        // The posted frame keeps its buffer after the decoder is released
+        drop(decoder);
+        drop(host);
+    }
+
+    /// Feeds the same encoded stream to a default and a low-latency decoder
+    /// and expects the low-latency one to return frames sooner.
+    #[ignore = "test requires VPU hardware"]
+    #[test]
+    fn test_decoder_low_latency() {
+        use crate::encoder::{Encoder, VSLEncoderProfileEnum, VSLRect};
+        use std::time::{Duration, Instant};
+
+        let (width, height) = (640, 480);
+        let encoder = Encoder::create(
+            VSLEncoderProfileEnum::Kbps5000 as u32,
+            u32::from_le_bytes(*b"H264"),
+            30,
+        )
+        .unwrap();
+        let crop = VSLRect::new(0, 0, width, height);
+        let packets: Vec<Vec<u8>> = (0..30)
+            .map(|_| {
+                let source = Frame::new(width as u32, height as u32, 0, "NV12").unwrap();
+                source.alloc(None).unwrap();
+                let output = encoder.new_output_frame(width, height, -1, -1, -1).unwrap();
+                let mut keyframe = 0;
+                let size =
+                    unsafe { encoder.frame(&source, &output, &crop, &mut keyframe) }.unwrap();
+                let data = output.map().unwrap()[..size as usize].to_vec();
+                data
+            })
+            .collect();
+
+        // Returns the packets fed before the first frame and the mean time
+        // from feeding a packet to receiving a frame.
+        let measure = |config: DecoderConfig| {
+            let decoder = config.create().unwrap();
+            let (mut first, mut frames, mut total) = (None, 0u32, Duration::ZERO);
+            for (i, packet) in packets.iter().enumerate() {
+                let start = Instant::now();
+                let mut data = packet.as_slice();
+                while !data.is_empty() {
+                    let (_, consumed, frame) = decoder.decode_frame(data).unwrap();
+                    if frame.is_some() {
+                        first.get_or_insert(i);
+                        frames += 1;
+                        total += start.elapsed();
+                    }
+                    if consumed == 0 {
+                        break;
+                    }
+                    data = &data[consumed.min(data.len())..];
+                }
+            }
+            assert!(frames > 0, "{:?} decoded no frames", config);
+            (first.unwrap(), total / frames)
+        };
+
+        let (default_delay, default_latency) = measure(DecoderConfig::new(DecoderCodec::H264, 30));
+        let (low_delay, low_latency) = measure(
+            DecoderConfig::new(DecoderCodec::H264, 30)
+                .with_output_buffers(4)
+                .with_low_latency(true),
+        );
+        println!(
+            "first frame after {} vs {} packets, {:?} vs {:?} per frame",
+            default_delay, low_delay, default_latency, low_latency
+        );
+        assert!(low_delay <= default_delay);
+        assert!(low_latency <= default_latency);
+    }
+
+    /// Decodes a VPU encoded NV12 frame on the V4L2 (MPLANE) decoder and
+    /// wraps the output as separate luma and chroma planes.
+    #[ignore = "test requires VPU hardware"]
+    #[test]
+    fn test_decoder_output_planes() {
+        use crate::encoder::{Encoder, VSLEncoderProfileEnum, VSLRect};
+        use crate::frame::PlaneInput;
+        use std::os::fd::BorrowedFd;
+
+        let (width, height) = (640, 480);
+        let encoder = Encoder::create(
+            VSLEncoderProfileEnum::Kbps5000 as u32,
+            u32::from_le_bytes(*b"H264"),
+            30,
+        )
+        .unwrap();
+        let decoder = Decoder::create_ex(DecoderCodec::H264, 30, CodecBackend::V4L2).unwrap();
+        let crop = VSLRect::new(0, 0, width, height);
+
+        let mut decoded = None;
+        for _ in 0..30 {
+            let source = Frame::new(width as u32, height as u32, 0, "NV12").unwrap();
+            source.alloc(None).unwrap();
+            let output = encoder.new_output_frame(width, height, -1, -1, -1).unwrap();
+            let mut keyframe = 0;
+            let size = unsafe { encoder.frame(&source, &output, &crop, &mut keyframe) }.unwrap();
+            let data = output.map().unwrap()[..size as usize].to_vec();
+
+            if let (_, _, Some(frame)) = decoder.decode_frame(&data).unwrap() {
+                decoded = Some(frame);
+                break;
+            }
+        }
+        let frame = decoded.expect("decoder produced no frame");
+
+        // NV12 frames report the luma stride plus half of it
+        let stride = frame.stride().unwrap() as usize * 2 / 3;
+        let rows = frame.height().unwrap() as usize;
+        // Safety: the handle stays open for as long as `frame` is alive.
+        let fd = unsafe { BorrowedFd::borrow_raw(frame.handle().unwrap()) };
+        let planes = Frame::from_planes(
+            &[
+                PlaneInput {
+                    fd,
+                    offset: 0,
+                    stride,
+                    size: stride * rows,
+                },
+                PlaneInput {
+                    fd,
+                    offset: stride * rows,
+                    stride,
+                    size: stride * rows / 2,
+                },
+            ],
+            frame.width().unwrap() as u32,
+            rows as u32,
+            "NV12",
+        )
+        .unwrap();
+
+        assert_eq!(planes.plane_count(), 2);
+        assert_eq!(planes.map_plane(0).unwrap().len(), stride * rows);
+        assert_eq!(planes.map_plane(1).unwrap().len(), stride * rows / 2);
+    }
+
+    /// 64x64 mid-grey baseline JPEG (4:2:0) whose blocks carry only a zero
+    /// DC coefficient, coded with one-symbol Huffman tables.
+    const GREY_JPEG: [u8; 170] = [
+        0xff, 0xd8, 0xff, 0xdb, 0x00, 0x43, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0xff, 0xc0, 0x00, 0x11,
+        0x08, 0x00, 0x40, 0x00, 0x40, 0x03, 0x01, 0x22, 0x00, 0x02, 0x11, 0x00, 0x03, 0x11, 0x00,
+        0xff, 0xc4, 0x00, 0x26, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xda, 0x00, 0x0c, 0x03,
+        0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x00, 0x3f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0xff, 0xd9,
+    ];
+
+    /// Decodes a Motion JPEG frame where a V4L2 decoder supports the format.
+    #[ignore = "test requires VPU hardware"]
+    #[test]
+    fn test_decoder_mjpeg() {
+        let decoder = match Decoder::create(DecoderCodec::MJPG, 30) {
+            Err(Error::UnsupportedCodec(codec)) => {
+                println!("skipping: {} decoding not supported by hardware", codec);
+                return;
+            }
+            result => result.unwrap(),
+        };
+
+        // Decoders may need the image more than once before the first frame
+        let frame = (0..10)
+            .find_map(|_| decoder.decode_frame(&GREY_JPEG).unwrap().2)
+            .expect("decoder produced no frame");
+
+        assert_eq!(frame.width().unwrap(), 64);
+        assert_eq!(frame.height().unwrap(), 64);
+        let luma = frame.map().unwrap()[0];
+        assert!(luma.abs_diff(128) <= 2, "luma {}", luma);
+    }
 }
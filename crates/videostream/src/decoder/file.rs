@@ -0,0 +1,375 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Random access decoding of recorded H.264 and H.265 bitstreams.
+//!
+//! The file is split into frames and its keyframes indexed when it is
+//! opened, so a seek only decodes from the nearest keyframe at or before the
+//! target, or continues from the current position when that is closer.
+
+use super::{Decoder, DecoderConfig};
+use crate::{
+    frame::Frame,
+    nal::{self, Codec},
+    Error,
+};
+use std::{fmt, fs, io, ops::Range, path::Path};
+
+/// Decoder for a raw Annex-B file that can seek to any frame.
+///
+/// Frames are numbered in decoding order from 0 and presented at a constant
+/// frame rate, so frame `i` has the PTS `i * 1e9 / fps` nanoseconds. This
+/// matches the raw `.h264` and `.h265` recordings made by `videostream
+/// record`. Streams with B-frames, whose presentation order differs from
+/// their decoding order, are not supported.
+///
+/// A seek backwards, or forwards past another keyframe, restarts decoding at
+/// the keyframe preceding the target; a seek a few frames ahead continues
+/// from the current position, so stepping through frames decodes each one
+/// once.
+///
+/// # Example
+///
+/// ```no_run
+/// use videostream::decoder::{DecoderCodec, DecoderConfig, FileDecoder};
+///
+/// let mut file = FileDecoder::open("clip.h264", DecoderConfig::new(DecoderCodec::H264, 30))?;
+/// println!("{} frames, keyframes at {:?}", file.frame_count(), file.keyframes());
+/// // Frame shown 2.5 seconds in
+/// let frame = file.seek(2_500_000_000)?;
+/// println!("{}x{}", frame.width()?, frame.height()?);
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub struct FileDecoder {
+    config: DecoderConfig,
+    codec: Codec,
+    data: Vec<u8>,
+    /// NAL units of each frame in decoding order, as ranges of `data`
+    /// without start codes
+    frames: Vec<Vec<Range<usize>>>,
+    keyframes: Vec<usize>,
+    decoder: Option<Decoder>,
+    /// Next frame to pass to the decoder
+    next_input: usize,
+    /// Frame the decoder will output next
+    next_output: usize,
+    frames_decoded: u64,
+}
+
+impl FileDecoder {
+    /// Reads and indexes the bitstream file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read and
+    /// [`Error::UnsupportedCodec`] unless `config` selects H.264 or HEVC.
+    pub fn open(path: impl AsRef<Path>, config: DecoderConfig) -> Result<Self, Error> {
+        Self::from_bytes(fs::read(path)?, config)
+    }
+
+    /// Indexes a bitstream held in memory.
+    ///
+    /// The decoder itself is created on the first seek.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedCodec`] unless `config` selects H.264 or
+    /// HEVC.
+    pub fn from_bytes(data: Vec<u8>, config: DecoderConfig) -> Result<Self, Error> {
+        let codec = Codec::from(config.codec());
+        if !codec.is_annex_b() {
+            return Err(Error::UnsupportedCodec(codec));
+        }
+
+        let mut frames: Vec<Vec<Range<usize>>> = Vec::new();
+        let mut keyframes = Vec::new();
+        // Whether the last frame has its picture yet
+        let mut has_picture = false;
+        for unit in nal::parse_annex_b(&data) {
+            let start = unit.as_ptr() as usize - data.as_ptr() as usize;
+            if has_picture && starts_frame(codec, unit) {
+                has_picture = false;
+                frames.push(Vec::new());
+            }
+            if frames.is_empty() {
+                frames.push(Vec::new());
+            }
+            if codec.is_idr(unit) && keyframes.last() != Some(&(frames.len() - 1)) {
+                keyframes.push(frames.len() - 1);
+            }
+            has_picture |= is_vcl(codec, unit);
+            frames
+                .last_mut()
+                .expect("a frame was pushed")
+                .push(start..start + unit.len());
+        }
+
+        Ok(FileDecoder {
+            config,
+            codec,
+            data,
+            frames,
+            keyframes,
+            decoder: None,
+            next_input: 0,
+            next_output: 0,
+            frames_decoded: 0,
+        })
+    }
+
+    /// Returns the number of frames in the file.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the indices of the keyframes (IDR frames) in the file.
+    pub fn keyframes(&self) -> &[usize] {
+        &self.keyframes
+    }
+
+    /// Returns how many frames have been passed to the decoder since the
+    /// file was opened, counting frames decoded again after a seek.
+    pub fn frames_decoded(&self) -> u64 {
+        self.frames_decoded
+    }
+
+    /// Decodes the frame presented at `pts` nanoseconds, the last frame
+    /// starting at or before it.
+    ///
+    /// # Errors
+    ///
+    /// As for [`seek_frame`](Self::seek_frame), and [`Error::Io`] with
+    /// [`io::ErrorKind::InvalidInput`] for a negative PTS.
+    pub fn seek(&mut self, pts: i64) -> Result<Frame, Error> {
+        if pts < 0 {
+            return Err(invalid_input(format!("PTS {} is negative", pts)));
+        }
+        let index = i128::from(pts) * i128::from(self.config.fps()) / 1_000_000_000;
+        self.seek_frame(usize::try_from(index)?)
+    }
+
+    /// Decodes frame `index`, counted in decoding order from 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] for a
+    /// frame past the end of the file or before its first keyframe, with
+    /// [`io::ErrorKind::UnexpectedEof`] if the decoder still holds the frame
+    /// when the file ends, and any error from creating the decoder or
+    /// decoding.
+    pub fn seek_frame(&mut self, index: usize) -> Result<Frame, Error> {
+        if index >= self.frames.len() {
+            return Err(invalid_input(format!(
+                "frame {} is past the {} frames of the file",
+                index,
+                self.frames.len()
+            )));
+        }
+        let Some(keyframe) = self
+            .keyframes
+            .partition_point(|&key| key <= index)
+            .checked_sub(1)
+            .map(|i| self.keyframes[i])
+        else {
+            return Err(invalid_input(format!(
+                "frame {} precedes the first keyframe",
+                index
+            )));
+        };
+
+        if self.decoder.is_none() || index < self.next_output || keyframe > self.next_input {
+            self.restart(keyframe)?;
+        }
+
+        while self.next_input < self.frames.len() {
+            let input = self.next_input;
+            self.next_input += 1;
+            let mut target = None;
+            for frame in self.decode(input)? {
+                if self.next_output == index {
+                    target = Some(frame);
+                }
+                self.next_output += 1;
+            }
+            if let Some(frame) = target {
+                return Ok(frame);
+            }
+        }
+
+        Err(Error::Io(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!(
+                "decoder did not output frame {} by the end of the file",
+                index
+            ),
+        )))
+    }
+
+    /// Starts a new decoder at `keyframe`, first giving it the parameter
+    /// sets that apply when the keyframe does not carry its own.
+    fn restart(&mut self, keyframe: usize) -> Result<(), Error> {
+        // Release the previous decoder before opening the device again
+        self.decoder = None;
+        let decoder = self.config.create()?;
+
+        if !self.frames[keyframe].iter().any(|unit| self.is_sps(unit)) {
+            let param_sets: Vec<u8> = self.frames[..keyframe]
+                .iter()
+                .rev()
+                .find(|frame| frame.iter().any(|unit| self.is_sps(unit)))
+                .into_iter()
+                .flatten()
+                .filter(|unit| self.is_param_set(unit))
+                .flat_map(|unit| annexb(&self.data[unit.clone()]))
+                .collect();
+            decoder.decode_all(&param_sets)?;
+        }
+
+        self.decoder = Some(decoder);
+        self.next_input = keyframe;
+        self.next_output = keyframe;
+        Ok(())
+    }
+
+    /// Passes frame `index` to the decoder, returning the frames it output.
+    fn decode(&mut self, index: usize) -> Result<Vec<Frame>, Error> {
+        let data: Vec<u8> = self.frames[index]
+            .iter()
+            .flat_map(|unit| annexb(&self.data[unit.clone()]))
+            .collect();
+        let decoder = self.decoder.as_ref().ok_or(Error::NullPointer)?;
+        self.frames_decoded += 1;
+        decoder.decode_all(&data)
+    }
+
+    fn is_sps(&self, unit: &Range<usize>) -> bool {
+        self.codec.is_sps(&self.data[unit.clone()])
+    }
+
+    fn is_param_set(&self, unit: &Range<usize>) -> bool {
+        self.codec.is_parameter_set(&self.data[unit.clone()])
+    }
+}
+
+impl fmt::Debug for FileDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileDecoder")
+            .field("config", &self.config)
+            .field("frames", &self.frames.len())
+            .field("keyframes", &self.keyframes.len())
+            .field("next_output", &self.next_output)
+            .finish()
+    }
+}
+
+fn invalid_input(message: String) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidInput, message))
+}
+
+/// Returns the NAL unit with a start code.
+fn annexb(unit: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    nal::START_CODE.into_iter().chain(unit.iter().copied())
+}
+
+/// Returns `true` if `unit` carries coded picture data.
+fn is_vcl(codec: Codec, unit: &[u8]) -> bool {
+    match (codec, codec.nal_type(unit)) {
+        (Codec::H264, Some(nal_type)) => (1..=5).contains(&nal_type),
+        (Codec::Hevc, Some(nal_type)) => nal_type < 32,
+        _ => false,
+    }
+}
+
+/// Returns `true` if `unit` begins a new frame after a frame that already
+/// has its picture: the first slice of a picture, or a NAL unit that may
+/// only precede one (ITU-T H.264 7.4.1.2.3, H.265 7.4.2.4.4).
+fn starts_frame(codec: Codec, unit: &[u8]) -> bool {
+    if is_vcl(codec, unit) {
+        // first_mb_in_slice is 0, or first_slice_segment_in_pic_flag is set
+        let header_len = match codec {
+            Codec::H264 => 1,
+            Codec::Hevc => 2,
+            _ => return false,
+        };
+        return unit.get(header_len).is_some_and(|byte| byte & 0x80 != 0);
+    }
+    match (codec, codec.nal_type(unit)) {
+        // SEI, SPS, PPS, access unit delimiter, prefix and reserved types
+        (Codec::H264, Some(nal_type)) => matches!(nal_type, 6..=9 | 14..=18),
+        // VPS, SPS, PPS, access unit delimiter, prefix SEI and reserved types
+        (Codec::Hevc, Some(nal_type)) => matches!(nal_type, 32..=35 | 39 | 41..=44),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::DecoderCodec;
+
+    /// H.264 NAL unit of `nal_type`, a slice starting a picture when
+    /// `first` is set.
+    fn h264(nal_type: u8, first: bool) -> Vec<u8> {
+        vec![
+            0,
+            0,
+            0,
+            1,
+            0x60 | nal_type,
+            if first { 0x88 } else { 0x12 },
+            0x80,
+        ]
+    }
+
+    #[test]
+    fn test_index_frames() {
+        let mut stream = Vec::new();
+        // IDR with parameter sets, a P-frame in two slices, then an IDR
+        for (nal_type, first) in [(7, true), (8, true), (5, true), (1, true), (1, false)] {
+            stream.extend(h264(nal_type, first));
+        }
+        stream.extend(h264(9, true));
+        stream.extend(h264(5, true));
+        stream.extend(h264(1, true));
+
+        let config = DecoderConfig::new(DecoderCodec::H264, 30);
+        let file = FileDecoder::from_bytes(stream, config).unwrap();
+        assert_eq!(file.frame_count(), 4);
+        assert_eq!(file.keyframes(), [0, 2]);
+        let units: Vec<usize> = file.frames.iter().map(Vec::len).collect();
+        assert_eq!(units, [3, 2, 2, 1]);
+
+        let config = DecoderConfig::new(DecoderCodec::MJPG, 30);
+        assert!(matches!(
+            FileDecoder::from_bytes(Vec::new(), config),
+            Err(Error::UnsupportedCodec(_))
+        ));
+    }
+
+    #[test]
+    fn test_seek_out_of_range() {
+        let mut stream = h264(1, true);
+        stream.extend(h264(5, true));
+        let config = DecoderConfig::new(DecoderCodec::H264, 30);
+        let mut file = FileDecoder::from_bytes(stream, config).unwrap();
+
+        let kind = |err: Error| match err {
+            Error::Io(err) => err.kind(),
+            other => panic!("unexpected error {}", other),
+        };
+        // Frame 0 has no keyframe to start from
+        assert_eq!(
+            kind(file.seek_frame(0).unwrap_err()),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            kind(file.seek_frame(2).unwrap_err()),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            kind(file.seek(-1).unwrap_err()),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(file.frames_decoded(), 0);
+    }
+}
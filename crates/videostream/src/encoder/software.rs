@@ -0,0 +1,413 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Software H.264 encoder backed by openh264.
+//!
+//! Used by [`super::Encoder`] when no hardware encoder is available or when
+//! [`super::CodecBackend::Software`] is requested. Source frames are converted
+//! to I420 on the CPU, encoded with the openh264 baseline profile encoder and
+//! the resulting Annex-B bitstream is placed in an anonymous shared memory
+//! buffer attached to the destination frame.
+
+use crate::{frame::Frame, Error};
+use openh264::{
+    encoder::{BitRate, EncoderConfig, FrameRate, FrameType, IntraFramePeriod},
+    formats::YUVBuffer,
+    OpenH264API,
+};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    os::{fd::AsRawFd, raw::c_int, unix::fs::OpenOptionsExt},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use videostream_sys as ffi;
+
+/// Default keyframe interval in frames, a two second GOP at 30 fps.
+const INTRA_PERIOD: u32 = 60;
+
+static BUFFER_ID: AtomicUsize = AtomicUsize::new(0);
+
+pub(super) struct SoftwareEncoder {
+    encoder: openh264::encoder::Encoder,
+    /// Frames from one IDR to the next, forced by [`Self::encode`] since
+    /// openh264 cannot change its intra period once initialized.
+    gop_size: u32,
+    /// Frames encoded since the last keyframe.
+    since_keyframe: u32,
+}
+
+impl SoftwareEncoder {
+    pub(super) fn new(profile: u32, output_fourcc: u32, fps: c_int) -> Result<Self, Error> {
+        if output_fourcc != u32::from_le_bytes(*b"H264") {
+            return Err(Error::HardwareNotAvailable(
+                "software encoder only supports H264",
+            ));
+        }
+
+        let fps = if fps > 0 { fps } else { 30 };
+        let config = EncoderConfig::new()
+            .bitrate(BitRate::from_bps(profile_to_bitrate(profile)))
+            .max_frame_rate(FrameRate::from_hz(fps as f32))
+            // No periodic IDR, encode() forces them every gop_size frames.
+            .intra_frame_period(IntraFramePeriod::auto())
+            // Every input must produce an output frame, as with the hardware
+            // encoders, at the cost of looser rate control.
+            .skip_frames(false);
+
+        let encoder =
+            openh264::encoder::Encoder::with_api_config(OpenH264API::from_source(), config)
+                .map_err(other)?;
+
+        Ok(SoftwareEncoder {
+            encoder,
+            gop_size: INTRA_PERIOD,
+            since_keyframe: 0,
+        })
+    }
+
+    pub(super) fn gop_size(&self) -> u32 {
+        self.gop_size
+    }
+
+    /// Sets the keyframe interval, counted from the last keyframe.
+    pub(super) fn set_gop_size(&mut self, frames: u32) -> Result<(), Error> {
+        if frames == 0 || frames > i32::MAX as u32 {
+            return Err(io::Error::from_raw_os_error(libc::EINVAL).into());
+        }
+        self.gop_size = frames;
+        Ok(())
+    }
+
+    pub(super) fn new_output_frame(&self, width: c_int, height: c_int) -> Result<Frame, Error> {
+        Frame::new(
+            width.try_into()?,
+            height.try_into()?,
+            width.try_into()?,
+            "H264",
+        )
+    }
+
+    /// Encodes `source` into `destination`, returning the encoded size in
+    /// bytes and whether the frame is a keyframe.
+    pub(super) fn encode(
+        &mut self,
+        source: &Frame,
+        destination: &Frame,
+    ) -> Result<(usize, bool), Error> {
+        let yuv = to_i420(source)?;
+        if self.since_keyframe >= self.gop_size {
+            self.encoder.force_intra_frame();
+        }
+        let bitstream = self.encoder.encode(&yuv).map_err(other)?;
+        let keyframe = matches!(bitstream.frame_type(), FrameType::IDR | FrameType::I);
+        let data = bitstream.to_vec();
+        self.since_keyframe = if keyframe { 1 } else { self.since_keyframe + 1 };
+
+        if data.is_empty() {
+            return Ok((0, false));
+        }
+
+        let file = anonymous_buffer(&data)?;
+        destination.attach(file.as_raw_fd(), data.len(), 0)?;
+        destination.set_capture_time(source.capture_time()?)?;
+        if let Some(id) = source.source_id()? {
+            destination.set_source_id(id)?;
+        }
+
+        Ok((data.len(), keyframe))
+    }
+}
+
+/// Maps an encoder profile onto a target bitrate, mirroring the C library.
+fn profile_to_bitrate(profile: u32) -> u32 {
+    match profile {
+        ffi::vsl_encode_profile_VSL_ENCODE_PROFILE_25000_KBPS => 25_000_000,
+        ffi::vsl_encode_profile_VSL_ENCODE_PROFILE_50000_KBPS => 50_000_000,
+        ffi::vsl_encode_profile_VSL_ENCODE_PROFILE_100000_KBPS => 100_000_000,
+        _ => 5_000_000,
+    }
+}
+
+fn other(err: openh264::Error) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::Other, err.to_string()))
+}
+
+/// Writes `data` into an unlinked file under /dev/shm so each output frame
+/// owns its own buffer, as with the hardware encoders.
+fn anonymous_buffer(data: &[u8]) -> Result<File, Error> {
+    let path = format!(
+        "/dev/shm/vsl-openh264-{}-{}",
+        std::process::id(),
+        BUFFER_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&path)?;
+    fs::remove_file(&path)?;
+    file.write_all(data)?;
+    Ok(file)
+}
+
+/// Converts the source frame into a tightly packed I420 buffer.
+///
+/// Planar and semi-planar formats are expected with tightly packed planes,
+/// packed formats honour the frame stride.
+fn to_i420(source: &Frame) -> Result<YUVBuffer, Error> {
+    let fourcc = source.fourcc()?;
+    let width = usize::try_from(source.width()?)?;
+    let height = usize::try_from(source.height()?)?;
+    let stride = usize::try_from(source.stride()?)?;
+
+    if width == 0 || height == 0 || width % 2 != 0 || height % 2 != 0 {
+        return Err(invalid(format!(
+            "software encoder requires even, non-zero dimensions, got {}x{}",
+            width, height
+        )));
+    }
+
+    let map = source.map()?;
+    let data: &[u8] = &map;
+    let luma = width * height;
+    let chroma = luma / 4;
+    let mut yuv = vec![0u8; luma + 2 * chroma];
+    let (y, uv) = yuv.split_at_mut(luma);
+    let (u, v) = uv.split_at_mut(chroma);
+
+    match &fourcc.to_le_bytes() {
+        b"I420" | b"YV12" => {
+            need(data, luma + 2 * chroma)?;
+            y.copy_from_slice(&data[..luma]);
+            let (first, second) = data[luma..luma + 2 * chroma].split_at(chroma);
+            if &fourcc.to_le_bytes() == b"I420" {
+                u.copy_from_slice(first);
+                v.copy_from_slice(second);
+            } else {
+                v.copy_from_slice(first);
+                u.copy_from_slice(second);
+            }
+        }
+        b"NV12" | b"NV21" => {
+            need(data, luma + 2 * chroma)?;
+            y.copy_from_slice(&data[..luma]);
+            let (cb, cr) = if &fourcc.to_le_bytes() == b"NV12" {
+                (0, 1)
+            } else {
+                (1, 0)
+            };
+            for (i, pair) in data[luma..luma + 2 * chroma].chunks_exact(2).enumerate() {
+                u[i] = pair[cb];
+                v[i] = pair[cr];
+            }
+        }
+        b"YUYV" | b"YUY2" | b"UYVY" => {
+            let stride = stride.max(width * 2);
+            need(data, stride * (height - 1) + width * 2)?;
+            let (luma_at, cb, cr) = if &fourcc.to_le_bytes() == b"UYVY" {
+                (1, 0, 2)
+            } else {
+                (0, 1, 3)
+            };
+            for row in 0..height {
+                let line = &data[row * stride..row * stride + width * 2];
+                for (col, px) in line.chunks_exact(2).enumerate() {
+                    y[row * width + col] = px[luma_at];
+                }
+                if row % 2 == 0 {
+                    let base = (row / 2) * (width / 2);
+                    for (col, macro_px) in line.chunks_exact(4).enumerate() {
+                        u[base + col] = macro_px[cb];
+                        v[base + col] = macro_px[cr];
+                    }
+                }
+            }
+        }
+        b"RGB3" | b"BGR3" | b"RGBA" | b"RGBX" | b"BGRA" | b"BGRX" => {
+            let bytes = fourcc.to_le_bytes();
+            let bpp = if bytes[3] == b'3' { 3 } else { 4 };
+            let (r, b) = if bytes[0] == b'R' { (0, 2) } else { (2, 0) };
+            let stride = stride.max(width * bpp);
+            need(data, stride * (height - 1) + width * bpp)?;
+            for row in 0..height {
+                let line = &data[row * stride..row * stride + width * bpp];
+                for (col, px) in line.chunks_exact(bpp).enumerate() {
+                    let [luma, cb, cr] = crate::convert::rgb_to_yuv([px[r], px[1], px[b]]);
+                    y[row * width + col] = luma;
+                    if row % 2 == 0 && col % 2 == 0 {
+                        let i = (row / 2) * (width / 2) + col / 2;
+                        u[i] = cb;
+                        v[i] = cr;
+                    }
+                }
+            }
+        }
+        _ => {
+            return Err(invalid(format!(
+                "unsupported fourcc {} for software encoder",
+                String::from_utf8_lossy(&fourcc.to_le_bytes())
+            )))
+        }
+    }
+
+    Ok(YUVBuffer::from_vec(yuv, width, height))
+}
+
+fn need(data: &[u8], len: usize) -> Result<(), Error> {
+    if data.len() < len {
+        return Err(invalid(format!(
+            "frame buffer is {} bytes, software encoder needs {}",
+            data.len(),
+            len
+        )));
+    }
+    Ok(())
+}
+
+fn invalid(msg: String) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::{CodecBackend, Encoder, EntropyMode, VSLEncoderProfileEnum, VSLRect};
+    use crate::frame::Rect;
+
+    /// Returns the NAL unit types found in an Annex-B bitstream.
+    fn nal_types(data: &[u8]) -> Vec<u8> {
+        data.windows(4)
+            .filter(|w| w[0] == 0 && w[1] == 0 && w[2] == 1)
+            .map(|w| w[3] & 0x1f)
+            .collect()
+    }
+
+    #[test]
+    fn test_profile_to_bitrate() {
+        assert_eq!(
+            profile_to_bitrate(VSLEncoderProfileEnum::Auto as u32),
+            5_000_000
+        );
+        assert_eq!(
+            profile_to_bitrate(VSLEncoderProfileEnum::Kbps25000 as u32),
+            25_000_000
+        );
+        assert_eq!(
+            profile_to_bitrate(VSLEncoderProfileEnum::Kbps100000 as u32),
+            100_000_000
+        );
+    }
+
+    #[test]
+    fn test_software_encode_h264() {
+        // 180 rows are coded as 192, so the SPS carries a cropping window
+        let (width, height) = (320, 180);
+        let encoder = Encoder::create_ex(
+            VSLEncoderProfileEnum::Auto as u32,
+            u32::from_le_bytes(*b"H264"),
+            30,
+            CodecBackend::Software,
+        )
+        .unwrap();
+        let crop = VSLRect::new(0, 0, 0, 0);
+
+        let mut nals = Vec::new();
+        let mut keyframes = 0;
+        for i in 0..5u8 {
+            let source = Frame::new(width, height, 0, "NV12").unwrap();
+            source.alloc(None).unwrap();
+            for (n, px) in source.mmap_mut().unwrap().iter_mut().enumerate() {
+                *px = (n as u8).wrapping_add(i.wrapping_mul(8));
+            }
+
+            let output = encoder
+                .new_output_frame(width as c_int, height as c_int, -1, -1, -1)
+                .unwrap();
+            let mut keyframe: c_int = 0;
+            let size = unsafe { encoder.frame(&source, &output, &crop, &mut keyframe) }.unwrap();
+            assert!(size > 0);
+            assert_eq!(output.size().unwrap(), size);
+            assert_eq!(
+                output.display_rect().unwrap(),
+                Some(Rect::new(0, 0, width as i32, height as i32))
+            );
+
+            keyframes += keyframe;
+            nals.extend(nal_types(&output.map().unwrap()));
+        }
+
+        assert!(nals.contains(&7), "missing SPS in {:?}", nals);
+        assert!(nals.contains(&8), "missing PPS in {:?}", nals);
+        assert!(nals.contains(&5), "missing IDR in {:?}", nals);
+        assert!(keyframes >= 1);
+    }
+
+    #[test]
+    fn test_software_entropy_mode() {
+        let (width, height) = (64, 64);
+        let encoder = Encoder::create_ex(
+            VSLEncoderProfileEnum::Auto as u32,
+            u32::from_le_bytes(*b"H264"),
+            30,
+            CodecBackend::Software,
+        )
+        .unwrap();
+        // Baseline profile has no CABAC
+        let err = encoder.set_entropy_mode(EntropyMode::Cabac).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+        encoder.set_entropy_mode(EntropyMode::Cavlc).unwrap();
+
+        let source = Frame::new(width, height, 0, "NV12").unwrap();
+        source.alloc(None).unwrap();
+        let output = encoder
+            .new_output_frame(width as c_int, height as c_int, -1, -1, -1)
+            .unwrap();
+        let crop = VSLRect::new(0, 0, 0, 0);
+        let size = unsafe { encoder.frame(&source, &output, &crop, std::ptr::null_mut()) }.unwrap();
+        let data = &output.map().unwrap()[..size as usize];
+        assert_eq!(crate::nal::h264_cabac(data), Some(false));
+    }
+
+    #[test]
+    fn test_software_set_gop_size() {
+        let (width, height) = (64, 64);
+        let encoder = Encoder::create_ex(
+            VSLEncoderProfileEnum::Auto as u32,
+            u32::from_le_bytes(*b"H264"),
+            30,
+            CodecBackend::Software,
+        )
+        .unwrap();
+        assert_eq!(encoder.gop_size().unwrap(), INTRA_PERIOD);
+        encoder.set_gop_size(4).unwrap();
+        assert!(encoder.set_gop_size(0).is_err());
+        let crop = VSLRect::new(0, 0, 0, 0);
+
+        let mut keyframes = Vec::new();
+        for i in 0..20 {
+            if i == 10 {
+                encoder.set_gop_size(3).unwrap();
+            }
+            let source = Frame::new(width, height, 0, "NV12").unwrap();
+            source.alloc(None).unwrap();
+            let output = encoder
+                .new_output_frame(width as c_int, height as c_int, -1, -1, -1)
+                .unwrap();
+            let mut keyframe: c_int = 0;
+            unsafe { encoder.frame(&source, &output, &crop, &mut keyframe) }.unwrap();
+            if keyframe != 0 {
+                keyframes.push(i);
+            }
+        }
+
+        // The interval counts from the last keyframe at 8, not the change
+        assert_eq!(keyframes, [0, 4, 8, 11, 14, 17]);
+    }
+
+    #[test]
+    fn test_software_encode_rejects_hevc() {
+        assert!(SoftwareEncoder::new(0, u32::from_le_bytes(*b"HEVC"), 30).is_err());
+    }
+}
@@ -4,6 +4,7 @@
 // Taken from https://docs.rs/crate/four-cc/latest and adapted to handle endianess.
 #![forbid(unsafe_code)]
 
+use crate::frame::FrameAlignment;
 use core::{fmt, result::Result};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -37,6 +38,102 @@ impl FourCC {
     pub fn from_u32(val: u32) -> Self {
         FourCC::from(val)
     }
+
+    /// Returns the canonical code: characters packed little-endian, the
+    /// first in the least significant byte.
+    ///
+    /// This is the `v4l2_fourcc()` / DRM packing used by the C library and
+    /// by every `u32` FourCC in this crate, so `FourCC(*b"YUYV")` is
+    /// `0x56595559`. Unlike [`as_u32`](Self::as_u32) the result does not
+    /// depend on the host byte order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use videostream::fourcc::FourCC;
+    ///
+    /// assert_eq!(FourCC(*b"YUYV").normalized(), 0x5659_5559);
+    /// ```
+    pub const fn normalized(self) -> u32 {
+        u32::from_le_bytes(self.0)
+    }
+
+    /// Returns the code packed big-endian, the first character in the most
+    /// significant byte, as used by QuickTime/MP4 box types and
+    /// multi-character C literals such as `'YUYV'`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use videostream::fourcc::FourCC;
+    ///
+    /// assert_eq!(FourCC(*b"YUYV").as_be_u32(), 0x5955_5956);
+    /// ```
+    pub const fn as_be_u32(self) -> u32 {
+        u32::from_be_bytes(self.0)
+    }
+
+    /// Creates a FourCC from a code packed big-endian, the inverse of
+    /// [`as_be_u32`](Self::as_be_u32).
+    ///
+    /// Passing such a value to [`from_u32`](Self::from_u32) instead yields
+    /// the characters reversed (`"VYUY"` for `'YUYV'`).
+    pub const fn from_be_u32(val: u32) -> Self {
+        FourCC(val.to_be_bytes())
+    }
+
+    /// Creates a FourCC from the bytes of a big-endian packed code, the
+    /// layout of an MP4 box type, so that
+    /// `from_be_bytes(b) == from_be_u32(u32::from_be_bytes(b))`.
+    ///
+    /// The first byte is the first character. The bytes of a canonical
+    /// [`normalized`](Self::normalized) code sent with `htonl()` are read
+    /// with [`from_u32`](Self::from_u32)`(u32::from_be_bytes(bytes))`
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use videostream::fourcc::FourCC;
+    ///
+    /// assert_eq!(FourCC::from_be_bytes(*b"avc1"), FourCC(*b"avc1"));
+    /// let wire = FourCC(*b"YUYV").normalized().to_be_bytes();
+    /// assert_eq!(FourCC::from_u32(u32::from_be_bytes(wire)), FourCC(*b"YUYV"));
+    /// ```
+    pub const fn from_be_bytes(bytes: [u8; 4]) -> Self {
+        FourCC::from_be_u32(u32::from_be_bytes(bytes))
+    }
+
+    /// Average bytes per pixel for uncompressed formats.
+    ///
+    /// Planar and semi-planar formats report the average across all planes:
+    /// 1.5 for 8-bit 4:2:0 (`NV12`, `I420`, ...) and 3.0 for 4:2:0 with
+    /// 16-bit samples (`P010`, `P016`). Grayscale is 1.0 for 8-bit `GREY`
+    /// and 2.0 for 16-bit `Y16 `. Returns `None` for compressed or unknown
+    /// formats.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use videostream::fourcc::FourCC;
+    ///
+    /// assert_eq!(FourCC(*b"RGBA").bytes_per_pixel(), Some(4.0));
+    /// assert_eq!(FourCC(*b"NV12").bytes_per_pixel(), Some(1.5));
+    /// assert_eq!(FourCC(*b"H264").bytes_per_pixel(), None);
+    /// ```
+    pub fn bytes_per_pixel(self) -> Option<f32> {
+        match &self.0 {
+            b"GREY" => Some(1.0),
+            b"Y16 " => Some(2.0),
+            b"NV12" | b"NV21" | b"I420" | b"YV12" => Some(1.5),
+            b"NV16" | b"NV61" => Some(2.0),
+            b"YUYV" | b"YUY2" | b"YVYU" | b"UYVY" | b"VYUY" => Some(2.0),
+            b"RGB3" | b"BGR3" => Some(3.0),
+            b"P010" | b"P016" => Some(3.0),
+            b"RGBA" | b"BGRA" | b"RGBX" | b"BGRX" => Some(4.0),
+            _ => None,
+        }
+    }
 }
 
 impl From<&[u8; 4]> for FourCC {
@@ -113,6 +210,44 @@ impl fmt::Debug for FourCC {
     }
 }
 
+/// Returns the geometry alignment required for frames of the given format.
+///
+/// The values reflect the strictest common requirement of the i.MX VPU and
+/// G2D blocks for each format family:
+///
+/// | Formats | Width | Height | Stride |
+/// |---------|-------|--------|--------|
+/// | `NV12`, `NV21`, `I420`, `YV12` | 16 | 2 | 8 |
+/// | `NV16`, `NV61` | 16 | 1 | 8 |
+/// | `YUYV`, `YUY2`, `YVYU`, `UYVY`, `VYUY` | 2 | 1 | 4 |
+/// | `RGB3`, `BGR3` | 1 | 1 | 3 |
+/// | `RGBA`, `BGRA`, `RGBX`, `BGRX` | 1 | 1 | 4 |
+///
+/// The stride column applies to the library's stride convention, which for
+/// semi-planar formats is the combined luma and chroma bytes per row
+/// (`width * 3 / 2`). Unknown formats impose no constraint.
+///
+/// # Example
+///
+/// ```
+/// use videostream::fourcc::{required_alignment, FourCC};
+///
+/// let align = required_alignment(FourCC(*b"NV12"));
+/// assert_eq!(align.width_align, 16);
+/// assert_eq!(align.height_align, 2);
+/// ```
+pub fn required_alignment(fourcc: FourCC) -> FrameAlignment {
+    match &fourcc.0 {
+        b"NV12" | b"NV21" | b"I420" | b"YV12" => FrameAlignment::new(16, 2, 8),
+        b"NV16" | b"NV61" => FrameAlignment::new(16, 1, 8),
+        b"YUYV" | b"YUY2" | b"YVYU" | b"UYVY" | b"VYUY" => FrameAlignment::new(2, 1, 4),
+        b"RGB3" | b"BGR3" => FrameAlignment::new(1, 1, 3),
+        b"RGBA" | b"BGRA" | b"RGBX" | b"BGRX" => FrameAlignment::new(1, 1, 4),
+        b"Y16 " => FrameAlignment::new(1, 1, 2),
+        _ => FrameAlignment::default(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,6 +279,47 @@ mod tests {
         assert_eq!(val, 0x56595559);
     }
 
+    #[test]
+    fn test_fourcc_normalized() {
+        assert_eq!(FourCC(*b"YUYV").normalized(), 0x56595559);
+        assert_eq!(
+            FourCC::from(FourCC(*b"NV12").normalized()),
+            FourCC(*b"NV12")
+        );
+    }
+
+    #[test]
+    fn test_fourcc_big_endian_roundtrip() {
+        let yuyv = FourCC(*b"YUYV");
+        assert_eq!(yuyv.as_be_u32(), 0x59555956);
+        assert_eq!(FourCC::from_be_u32(yuyv.as_be_u32()), yuyv);
+        assert_eq!(FourCC::from_be_bytes(yuyv.as_be_u32().to_be_bytes()), yuyv);
+        assert_eq!(FourCC::from(yuyv.normalized()), yuyv);
+        assert_eq!(
+            FourCC::from_u32(u32::from_be_bytes(yuyv.normalized().to_be_bytes())),
+            yuyv
+        );
+    }
+
+    #[test]
+    fn test_fourcc_mixed_endian_mismatch() {
+        let yuyv = FourCC(*b"YUYV");
+        // A big-endian code read as canonical comes out reversed
+        assert_eq!(FourCC::from(yuyv.as_be_u32()), FourCC(*b"VYUY"));
+        assert_ne!(FourCC::from_be_u32(yuyv.normalized()), yuyv);
+        assert_ne!(FourCC::from_be_bytes(yuyv.normalized().to_be_bytes()), yuyv);
+    }
+
+    #[test]
+    fn test_fourcc_from_be_bytes_matches_from_be_u32() {
+        for bytes in [*b"YUYV", *b"avc1", *b"NV12", [0x01, 0x02, 0x03, 0x04]] {
+            assert_eq!(
+                FourCC::from_be_bytes(bytes),
+                FourCC::from_be_u32(u32::from_be_bytes(bytes))
+            );
+        }
+    }
+
     #[test]
     fn test_fourcc_roundtrip() {
         let original = FourCC(*b"NV12");
@@ -228,6 +404,42 @@ mod tests {
         assert_eq!(original, cloned);
     }
 
+    #[test]
+    fn test_required_alignment_nv12() {
+        let align = required_alignment(FourCC(*b"NV12"));
+        assert_eq!(align.width_align, 16);
+        assert_eq!(align.height_align, 2);
+        assert_eq!(align.stride_align, 8);
+
+        assert!(align.is_aligned(1920, 1080, 1920 * 3 / 2));
+        assert!(!align.is_aligned(1920, 1081, 1920 * 3 / 2));
+        assert!(align.validate(1918, 1080, 2877).is_err());
+    }
+
+    #[test]
+    fn test_required_alignment_unknown() {
+        let align = required_alignment(FourCC(*b"ABCD"));
+        assert_eq!(align, FrameAlignment::default());
+        assert!(align.is_aligned(1, 1, 1));
+    }
+
+    #[test]
+    fn test_bytes_per_pixel() {
+        assert_eq!(FourCC(*b"YUYV").bytes_per_pixel(), Some(2.0));
+        assert_eq!(FourCC(*b"NV12").bytes_per_pixel(), Some(1.5));
+        assert_eq!(FourCC(*b"I420").bytes_per_pixel(), Some(1.5));
+        assert_eq!(FourCC(*b"YV12").bytes_per_pixel(), Some(1.5));
+        assert_eq!(FourCC(*b"RGB3").bytes_per_pixel(), Some(3.0));
+        assert_eq!(FourCC(*b"RGBA").bytes_per_pixel(), Some(4.0));
+        assert_eq!(FourCC(*b"BGRA").bytes_per_pixel(), Some(4.0));
+        assert_eq!(FourCC(*b"P010").bytes_per_pixel(), Some(3.0));
+        assert_eq!(FourCC(*b"P016").bytes_per_pixel(), Some(3.0));
+        assert_eq!(FourCC(*b"GREY").bytes_per_pixel(), Some(1.0));
+        assert_eq!(FourCC(*b"Y16 ").bytes_per_pixel(), Some(2.0));
+        assert_eq!(FourCC(*b"H264").bytes_per_pixel(), None);
+        assert_eq!(FourCC(*b"ABCD").bytes_per_pixel(), None);
+    }
+
     #[test]
     fn test_fourcc_copy() {
         let original = FourCC(*b"NV12");
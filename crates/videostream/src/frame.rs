@@ -1,13 +1,25 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright 2025 Au-Zone Technologies
 
-use crate::{camera::CameraBuffer, Error};
+use crate::{
+    camera::CameraBuffer,
+    convert::{self, Geometry, Layout},
+    fourcc::FourCC,
+    Error,
+};
 use std::{
+    any::Any,
+    cell::Cell,
     ffi::{CStr, CString},
     io,
-    os::fd::{AsRawFd, RawFd},
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut, Range},
+    os::fd::{AsRawFd, BorrowedFd, RawFd},
     path::Path,
     ptr, slice,
+    sync::Arc,
+    time::{Duration, SystemTime},
 };
 use videostream_sys as ffi;
 
@@ -100,6 +112,172 @@ impl From<Rect> for (i32, i32, i32, i32) {
     }
 }
 
+/// Clockwise rotation applied by [`Frame::rotate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    /// Rotate 90 degrees clockwise; width and height are swapped
+    Rotate90,
+    /// Rotate 180 degrees
+    Rotate180,
+    /// Rotate 270 degrees clockwise; width and height are swapped
+    Rotate270,
+}
+
+impl Rotation {
+    /// Returns the clockwise rotation in degrees.
+    pub fn degrees(self) -> i32 {
+        match self {
+            Rotation::Rotate90 => 90,
+            Rotation::Rotate180 => 180,
+            Rotation::Rotate270 => 270,
+        }
+    }
+}
+
+/// Memory backing a frame, returned by [`Frame::memory_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameMemory {
+    /// DmaBuf allocated from a DMA heap; CPU access needs [`Frame::sync`]
+    DmaBuf,
+    /// POSIX shared memory, always coherent
+    Shm,
+    /// Descriptor attached with [`Frame::attach`] or received from a host;
+    /// [`Frame::sync`] does nothing for these frames
+    Attached,
+}
+
+/// Placement of a source frame letterboxed by [`Frame::letterbox_to`].
+///
+/// The source is scaled by `scale` to `width`x`height` and placed at
+/// (`offset_x`, `offset_y`) in the target; the rest of the target is padding.
+/// Use [`LetterboxInfo::to_source`] to map coordinates in the target, such
+/// as detection boxes from a model, back to the original frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LetterboxInfo {
+    /// Scale factor applied to both source dimensions
+    pub scale: f32,
+    /// Left edge of the scaled image in the target
+    pub offset_x: i32,
+    /// Top edge of the scaled image in the target
+    pub offset_y: i32,
+    /// Width of the scaled image in the target
+    pub width: i32,
+    /// Height of the scaled image in the target
+    pub height: i32,
+}
+
+impl LetterboxInfo {
+    /// Computes the aspect-preserving placement of a `src_width`x`src_height`
+    /// image centered in a `dst_width`x`dst_height` target.
+    pub fn new(src_width: i32, src_height: i32, dst_width: i32, dst_height: i32) -> Self {
+        let scale =
+            (dst_width as f32 / src_width as f32).min(dst_height as f32 / src_height as f32);
+        let width = ((src_width as f32 * scale).round() as i32).clamp(1, dst_width);
+        let height = ((src_height as f32 * scale).round() as i32).clamp(1, dst_height);
+        LetterboxInfo {
+            scale,
+            offset_x: (dst_width - width) / 2,
+            offset_y: (dst_height - height) / 2,
+            width,
+            height,
+        }
+    }
+
+    /// Maps a point in target coordinates back to source coordinates.
+    pub fn to_source(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (x - self.offset_x as f32) / self.scale,
+            (y - self.offset_y as f32) / self.scale,
+        )
+    }
+}
+
+/// Alignment requirements for a frame's geometry.
+///
+/// Hardware blocks such as the VPU and G2D reject buffers whose width,
+/// height or stride are not multiples of format-specific values, usually
+/// with an unhelpful `EINVAL`. This structure describes those multiples so
+/// callers can validate frame geometry up front.
+///
+/// Use [`Frame::alignment`] for an existing frame or
+/// [`required_alignment`](crate::fourcc::required_alignment) for a format.
+///
+/// # Example
+///
+/// ```
+/// use videostream::{fourcc::{required_alignment, FourCC}, frame::FrameAlignment};
+///
+/// let align = required_alignment(FourCC(*b"NV12"));
+/// assert!(align.is_aligned(1920, 1080, 2880));
+/// assert!(!align.is_aligned(1918, 1080, 2877));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameAlignment {
+    /// Width must be a multiple of this many pixels
+    pub width_align: u32,
+    /// Height must be a multiple of this many rows
+    pub height_align: u32,
+    /// Stride must be a multiple of this many bytes
+    pub stride_align: u32,
+}
+
+impl FrameAlignment {
+    /// Creates a new alignment descriptor.
+    ///
+    /// Zero values are treated as 1 (no constraint).
+    pub fn new(width_align: u32, height_align: u32, stride_align: u32) -> Self {
+        FrameAlignment {
+            width_align: width_align.max(1),
+            height_align: height_align.max(1),
+            stride_align: stride_align.max(1),
+        }
+    }
+
+    /// Returns `true` if the given geometry satisfies every constraint.
+    pub fn is_aligned(&self, width: u32, height: u32, stride: u32) -> bool {
+        width % self.width_align == 0
+            && height % self.height_align == 0
+            && stride % self.stride_align == 0
+    }
+
+    /// Validates the given geometry, describing the first violated constraint.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] naming the
+    /// offending dimension and the required multiple.
+    pub fn validate(&self, width: u32, height: u32, stride: u32) -> Result<(), Error> {
+        let check = |name: &str, value: u32, align: u32| {
+            if value % align != 0 {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} {} is not a multiple of {}", name, value, align),
+                )));
+            }
+            Ok(())
+        };
+        check("width", width, self.width_align)?;
+        check("height", height, self.height_align)?;
+        check("stride", stride, self.stride_align)
+    }
+}
+
+impl Default for FrameAlignment {
+    fn default() -> Self {
+        FrameAlignment::new(1, 1, 1)
+    }
+}
+
+impl std::fmt::Display for FrameAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "width%{} height%{} stride%{}",
+            self.width_align, self.height_align, self.stride_align
+        )
+    }
+}
+
 /// The Frame structure handles the frame and underlying framebuffer.  A frame
 /// can be an image or a single video frame, the distinction is not considered.
 ///
@@ -120,6 +298,86 @@ impl From<Rect> for (i32, i32, i32, i32) {
 /// ```
 pub struct Frame {
     ptr: *mut ffi::VSLFrame,
+    /// Number of live [`FrameMap`] guards sharing the mapping.
+    maps: Cell<usize>,
+    /// Plane layout for frames built by [`Frame::from_planes`] or received
+    /// with plane descriptors.
+    planes: Vec<Plane>,
+}
+
+/// Describes one plane of a multiplanar buffer for [`Frame::from_planes`].
+///
+/// Planes may live in separate DmaBufs (V4L2 MPLANE formats such as `NM12`)
+/// or at different offsets of the same buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct PlaneInput<'fd> {
+    /// Buffer holding the plane; duplicated, so it may be closed afterwards
+    pub fd: BorrowedFd<'fd>,
+    /// Byte offset of the plane within `fd`
+    pub offset: usize,
+    /// Row stride of the plane in bytes
+    pub stride: usize,
+    /// Size of the plane in bytes
+    pub size: usize,
+}
+
+/// Layout of one plane of a frame, returned by [`Frame::plane`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaneInfo {
+    /// Byte offset of the plane within its buffer
+    pub offset: usize,
+    /// Row stride of the plane in bytes
+    pub stride: usize,
+    /// Size of the plane in bytes
+    pub size: usize,
+}
+
+/// Luma statistics of a frame, returned by [`Frame::luma_stats`].
+///
+/// Luma is BT.601 limited range (16-235 for video levels), read directly from
+/// YUV formats and computed from RGB.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LumaStats {
+    /// Smallest luma value in the frame
+    pub min: u8,
+    /// Largest luma value in the frame
+    pub max: u8,
+    /// Average luma value
+    pub mean: f32,
+    /// Number of pixels at each luma value
+    pub histogram: [u32; 256],
+}
+
+impl LumaStats {
+    fn from_histogram(histogram: [u32; 256]) -> Self {
+        let total: u64 = histogram.iter().map(|&n| u64::from(n)).sum();
+        let sum: u64 = (0u64..)
+            .zip(histogram.iter())
+            .map(|(value, &n)| value * u64::from(n))
+            .sum();
+        let min = histogram.iter().position(|&n| n > 0).unwrap_or(0);
+        let max = histogram.iter().rposition(|&n| n > 0).unwrap_or(0);
+        LumaStats {
+            min: min as u8,
+            max: max as u8,
+            mean: if total == 0 {
+                0.0
+            } else {
+                (sum as f64 / total as f64) as f32
+            },
+            histogram,
+        }
+    }
+}
+
+/// A plane and where its bytes are mapped from.
+struct Plane {
+    info: PlaneInfo,
+    /// Separate buffer holding the plane, or `None` when the plane lives in
+    /// the frame's own buffer.
+    buffer: Option<Frame>,
+    /// Byte range of the plane within the mapping of its buffer.
+    range: Range<usize>,
 }
 
 unsafe impl Send for Frame {}
@@ -142,10 +400,22 @@ impl std::fmt::Debug for Frame {
 }
 
 impl Frame {
+    /// Creates a frame without a buffer; call [`Frame::alloc`] or
+    /// [`Frame::attach`] before accessing its data.
+    ///
+    /// A `stride` of 0 derives the row size from the format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidFourCC`] if `fourcc_str` is not four printable
+    /// ASCII characters, and [`Error::UnsupportedFormat`] if `stride` is 0
+    /// and the library does not know the format's row size. Formats are not
+    /// otherwise validated, so an unknown format with an explicit stride is
+    /// accepted.
     pub fn new(width: u32, height: u32, stride: u32, fourcc_str: &str) -> Result<Self, Error> {
         let buf = fourcc_str.as_bytes();
-        if buf.len() != 4 {
-            return Err(Error::NullPointer); // Invalid fourcc is a library error
+        if buf.len() != 4 || !buf.iter().all(|&b| b == b' ' || b.is_ascii_graphic()) {
+            return Err(Error::InvalidFourCC(fourcc_str.to_string()));
         }
         let mut fourcc: u32 = 0;
         for (i, &byte) in buf.iter().enumerate() {
@@ -163,11 +433,428 @@ impl Frame {
 
         if ptr.is_null() {
             let err = io::Error::last_os_error();
-            return Err(Error::Io(err));
+            // Without a stride the library must know the format's row size
+            if err.raw_os_error() == Some(libc::ENOTSUP) {
+                return Err(Error::UnsupportedFormat(FourCC::from(fourcc)));
+            }
+            return Err(crate::call_error("vsl_frame_init", err).into());
+        }
+        // Safety: vsl_frame_init returns a new frame owned by the caller.
+        unsafe { Frame::from_raw(ptr) }.ok_or(Error::NullPointer)
+    }
+
+    /// Wraps a multiplanar buffer, such as V4L2 MPLANE capture or decoder
+    /// output with one DmaBuf per plane.
+    ///
+    /// The library addresses a frame through a single buffer, so host,
+    /// encoder and [`copy_to`](Self::copy_to) operations see the shared
+    /// buffer when every plane is in the same `fd`, and only the first plane
+    /// otherwise. Each plane remains accessible through
+    /// [`map_plane`](Self::map_plane).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if `planes`
+    /// is empty, or if a descriptor cannot be attached.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::os::fd::AsFd;
+    /// use videostream::frame::{Frame, PlaneInput};
+    ///
+    /// # let (luma, chroma) = (std::fs::File::open("/dev/null")?, std::fs::File::open("/dev/null")?);
+    /// let frame = Frame::from_planes(
+    ///     &[
+    ///         PlaneInput { fd: luma.as_fd(), offset: 0, stride: 1920, size: 1920 * 1080 },
+    ///         PlaneInput { fd: chroma.as_fd(), offset: 0, stride: 1920, size: 1920 * 540 },
+    ///     ],
+    ///     1920,
+    ///     1080,
+    ///     "NV12",
+    /// )?;
+    /// let uv = frame.map_plane(1)?;
+    /// println!("first chroma sample: {}", uv[0]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_planes(
+        planes: &[PlaneInput<'_>],
+        width: u32,
+        height: u32,
+        fourcc: &str,
+    ) -> Result<Self, Error> {
+        let first = planes.first().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "at least one plane is required",
+            )
+        })?;
+        let fd = first.fd.as_raw_fd();
+        let shared = planes.iter().all(|plane| plane.fd.as_raw_fd() == fd);
+
+        // Attach the span covering every plane when they share one buffer.
+        let primary = if shared { planes } else { &planes[..1] };
+        let base = primary.iter().map(|p| p.offset).min().unwrap_or(0);
+        let end = primary.iter().map(|p| p.offset + p.size).max().unwrap_or(0);
+
+        // The frame stride follows the library convention, see `stride`.
+        let stride = fourcc
+            .as_bytes()
+            .try_into()
+            .map_or(first.stride, |code: [u8; 4]| {
+                frame_stride(FourCC(code), first.stride)
+            });
+        let mut frame = Frame::new(width, height, stride.try_into()?, fourcc)?;
+        frame.attach(fd, end - base, base)?;
+
+        for (index, input) in planes.iter().enumerate() {
+            let info = PlaneInfo {
+                offset: input.offset,
+                stride: input.stride,
+                size: input.size,
+            };
+            let plane = if shared || index == 0 {
+                let start = input.offset - base;
+                Plane {
+                    info,
+                    buffer: None,
+                    range: start..start + input.size,
+                }
+            } else {
+                // Map from the start of the buffer as plane offsets need not
+                // be page aligned.
+                let buffer = Frame::new(width, height, input.stride.try_into()?, fourcc)?;
+                buffer.attach(input.fd.as_raw_fd(), input.offset + input.size, 0)?;
+                Plane {
+                    info,
+                    buffer: Some(buffer),
+                    range: input.offset..input.offset + input.size,
+                }
+            };
+            frame.planes.push(plane);
+        }
+
+        // Publish the layout so clients can locate planes in the shared buffer
+        if shared {
+            let layout: Vec<PlaneInfo> = frame.planes.iter().map(|plane| plane.info).collect();
+            match frame.set_plane_descriptors(&layout) {
+                Ok(()) | Err(Error::SymbolNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(frame)
+    }
+
+    /// Returns the number of planes described for this frame.
+    ///
+    /// Frames built by [`from_planes`](Self::from_planes) report one entry
+    /// per plane. Other frames in planar or semi-planar formats (`NV12`,
+    /// `NV21`, `NV16`, `NV61`, `I420`, `YV12`) have their planes stored back
+    /// to back, located from the frame stride as described on
+    /// [`stride`](Self::stride). Packed formats report 0 and are accessed as
+    /// a single buffer with [`map`](Self::map).
+    pub fn plane_count(&self) -> usize {
+        match self.planes.len() {
+            0 => self.derived_planes().len(),
+            count => count,
+        }
+    }
+
+    /// Returns the layout of plane `index`, or `None` if out of range.
+    pub fn plane(&self, index: usize) -> Option<PlaneInfo> {
+        if self.planes.is_empty() {
+            return self.derived_planes().get(index).map(|plane| plane.info);
+        }
+        self.planes.get(index).map(|plane| plane.info)
+    }
+
+    /// Planes of a planar frame without a published layout, stored back to
+    /// back from the start of its buffer with the luma stride taken from the
+    /// frame stride. Empty for packed formats and frames with a layout.
+    fn derived_planes(&self) -> Vec<Plane> {
+        if !self.planes.is_empty() {
+            return Vec::new();
+        }
+        let (Ok(fourcc), Ok(stride), Ok(height)) = (self.fourcc(), self.stride(), self.height())
+        else {
+            return Vec::new();
+        };
+        let base = self.offset().unwrap_or(0);
+        let stride = luma_stride(stride.max(0) as usize);
+        plane_layout(FourCC::from(fourcc), stride, height.max(0) as usize)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(offset, stride, size)| Plane {
+                info: PlaneInfo {
+                    offset: base + offset,
+                    stride,
+                    size,
+                },
+                buffer: None,
+                range: offset..offset + size,
+            })
+            .collect()
+    }
+
+    /// Returns the byte range and stride of each plane within the frame's
+    /// own mapping, or `None` when a plane lives in a separate buffer.
+    /// Packed formats have no planes.
+    fn mapped_planes(&self) -> Option<Vec<(Range<usize>, usize)>> {
+        if self.planes.is_empty() {
+            return Some(
+                self.derived_planes()
+                    .into_iter()
+                    .map(|plane| (plane.range, plane.info.stride))
+                    .collect(),
+            );
+        }
+        self.planes
+            .iter()
+            .map(|plane| {
+                plane
+                    .buffer
+                    .is_none()
+                    .then(|| (plane.range.clone(), plane.info.stride))
+            })
+            .collect()
+    }
+
+    /// Returns the plane layout carried with the frame.
+    ///
+    /// Frames built by [`from_planes`](Self::from_planes) over a single
+    /// buffer publish their layout, which a [`Host`](crate::host::Host)
+    /// forwards with the frame. Clients therefore see the producer's plane
+    /// offsets and strides rather than defaults derived from the frame
+    /// stride, and [`plane`](Self::plane) and [`map_plane`](Self::map_plane)
+    /// work on received frames. Offsets count from the start of the buffer,
+    /// as in [`PlaneInfo`]. Frames without a layout return an empty vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the loaded `libvideostream.so`
+    /// predates 2.6 and does not export `vsl_frame_planes`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::client::{Client, Reconnect};
+    ///
+    /// let client = Client::new("/tmp/nv12.vsl", Reconnect::No)?;
+    /// let frame = client.get_frame(0)?;
+    /// for (index, plane) in frame.plane_descriptors()?.iter().enumerate() {
+    ///     println!("plane {}: {} bytes at {}", index, plane.size, plane.offset);
+    /// }
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn plane_descriptors(&self) -> Result<Vec<PlaneInfo>, Error> {
+        let lib = ffi::init()?;
+        let planes = lib
+            .vsl_frame_planes
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_planes"))?;
+        let mut descriptors = [ffi::VSLFramePlane {
+            offset: 0,
+            stride: 0,
+            size: 0,
+        }; ffi::VSL_FRAME_MAX_PLANES as usize];
+        let count = unsafe {
+            planes(
+                self.ptr,
+                descriptors.as_mut_ptr(),
+                descriptors.len() as std::os::raw::c_int,
+            )
+        };
+        if count < 0 {
+            return Err(crate::last_os_error("vsl_frame_planes").into());
+        }
+        Ok(descriptors[..(count as usize).min(descriptors.len())]
+            .iter()
+            .map(|plane| PlaneInfo {
+                offset: plane.offset,
+                stride: plane.stride.max(0) as usize,
+                size: plane.size,
+            })
+            .collect())
+    }
+
+    /// Attaches `planes` to the frame so they travel with it to clients.
+    pub(crate) fn set_plane_descriptors(&self, planes: &[PlaneInfo]) -> Result<(), Error> {
+        let lib = ffi::init()?;
+        let set_planes = lib
+            .vsl_frame_set_planes
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_set_planes"))?;
+        let descriptors = planes
+            .iter()
+            .map(|plane| {
+                Ok(ffi::VSLFramePlane {
+                    offset: plane.offset,
+                    stride: plane.stride.try_into()?,
+                    size: plane.size,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let ret = unsafe {
+            set_planes(
+                self.ptr,
+                descriptors.as_ptr(),
+                descriptors.len().try_into()?,
+            )
+        };
+        if ret != 0 {
+            return Err(crate::last_os_error("vsl_frame_set_planes").into());
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the plane views of a received frame from its descriptors.
+    ///
+    /// Libraries before 2.6 carry no descriptors and leave the frame without
+    /// planes.
+    pub(crate) fn load_plane_descriptors(&mut self) -> Result<(), Error> {
+        let descriptors = match self.plane_descriptors() {
+            Ok(descriptors) => descriptors,
+            Err(Error::SymbolNotFound(_)) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let base = self.offset()?;
+        self.planes = descriptors
+            .into_iter()
+            .map(|info| {
+                let start = info.offset.saturating_sub(base);
+                Plane {
+                    info,
+                    buffer: None,
+                    range: start..start + info.size,
+                }
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Maps plane `index` and returns a guard over its bytes.
+    ///
+    /// Planes sharing the frame's buffer are views into the same mapping as
+    /// [`map`](Self::map), so the frame's mapping rules apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if `index`
+    /// is out of range, or [`Error::NullPointer`] if the mapping fails.
+    pub fn map_plane(&self, index: usize) -> Result<PlaneMap<'_>, Error> {
+        let (map, _, range) = self.plane_mapping(index)?;
+        Ok(PlaneMap { map, range })
+    }
+
+    /// Maps plane `index` for writing and returns a guard over its bytes.
+    ///
+    /// The write-side counterpart of [`map_plane`](Self::map_plane), used to
+    /// produce planar frames in software such as test patterns or overlays.
+    /// [`PlaneMut::rows_mut`] walks the plane row by row using its stride.
+    ///
+    /// The view borrows the frame mutably, so only one plane can be written
+    /// at a time and no other view of the frame can be held while writing.
+    /// Callers must still not write to a frame a client may be reading.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`map_plane`](Self::map_plane).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use videostream::frame::Frame;
+    /// # fn fill(frame: &mut Frame) -> Result<(), videostream::Error> {
+    /// // Horizontal luma gradient on an NV12 frame
+    /// let mut luma = frame.plane_mut(0)?;
+    /// for row in luma.rows_mut() {
+    ///     for (x, pixel) in row.iter_mut().enumerate() {
+    ///         *pixel = x as u8;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Holding two writable views of the frame at once is rejected by the
+    /// compiler:
+    ///
+    /// ```compile_fail
+    /// # use videostream::frame::Frame;
+    /// # fn fill(frame: &mut Frame) -> Result<(), videostream::Error> {
+    /// let mut luma = frame.plane_mut(0)?;
+    /// let mut chroma = frame.plane_mut(1)?; // ERROR: cannot borrow `*frame` as mutable more than once
+    /// luma[0] = chroma[0];
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn plane_mut(&mut self, index: usize) -> Result<PlaneMut<'_>, Error> {
+        let (map, info, range) = self.plane_mapping(index)?;
+        Ok(PlaneMut {
+            map,
+            range,
+            stride: info.stride,
+        })
+    }
+
+    /// Maps the buffer holding plane `index`, checking the plane fits.
+    /// Returns the mapping, the plane layout and its byte range within the
+    /// mapping.
+    fn plane_mapping(
+        &self,
+        index: usize,
+    ) -> Result<(FrameMap<'_>, PlaneInfo, Range<usize>), Error> {
+        let derived = self.derived_planes();
+        let planes = if self.planes.is_empty() {
+            &derived
+        } else {
+            &self.planes
+        };
+        let plane = planes.get(index).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("plane {} out of range ({} planes)", index, planes.len()),
+            )
+        })?;
+        let map = match self
+            .planes
+            .get(index)
+            .and_then(|plane| plane.buffer.as_ref())
+        {
+            Some(buffer) => buffer.map()?,
+            None => self.map()?,
+        };
+        if map.len() < plane.range.end {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "plane {} ends at byte {} beyond the {} byte mapping",
+                    index,
+                    plane.range.end,
+                    map.len()
+                ),
+            )));
         }
-        Ok(Frame { ptr })
+        Ok((map, plane.info, plane.range.clone()))
     }
 
+    /// Allocates a buffer for the frame.
+    ///
+    /// `path` selects the backing memory: a DMA heap such as
+    /// `/dev/dma_heap/linux,cma`, or a shared memory object name such as
+    /// `/frame0`. With `None` the first available DMA heap is used, falling
+    /// back to shared memory.
+    ///
+    /// The buffer contents are not guaranteed to be zeroed. Buffers from a
+    /// shared DMA heap may hold stale data from a previous user, possibly
+    /// another process or container; use [`alloc_zeroed`](Self::alloc_zeroed)
+    /// when the frame may be shared before it is fully written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if `path` is not valid UTF-8 or the allocation
+    /// fails.
     pub fn alloc(&self, path: Option<&Path>) -> Result<(), Error> {
         // Hold the CString on the stack so it drops after vsl_frame_alloc
         // returns. The previous implementation used into_raw without a
@@ -185,11 +872,44 @@ impl Frame {
 
         let ret = vsl!(vsl_frame_alloc(self.ptr, path_ptr)) as i32;
         if ret != 0 {
-            return Err(io::Error::last_os_error().into());
+            return Err(crate::last_os_error("vsl_frame_alloc").into());
         }
         Ok(())
     }
 
+    /// Allocates a buffer for the frame as [`alloc`](Self::alloc) does, then
+    /// fills it with zeros.
+    ///
+    /// Prevents stale contents of recycled DMA heap memory from being shown
+    /// or leaked to other processes before the frame is written. The buffer
+    /// is cleared through a CPU mapping, which costs a full write of the
+    /// frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the allocation or cache synchronization
+    /// fails, or [`Error::NullPointer`] if the buffer cannot be mapped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::Frame;
+    ///
+    /// let frame = Frame::new(640, 480, 0, "RGB3")?;
+    /// frame.alloc_zeroed(None)?;
+    /// assert!(frame.map()?.iter().all(|&b| b == 0));
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn alloc_zeroed(&self, path: Option<&Path>) -> Result<(), Error> {
+        self.alloc(path)?;
+        self.sync(true, libc::O_WRONLY)?;
+        let filled = self.mmap_mut().map(|data| data.fill(0));
+        // The frame was just allocated, so no FrameMap can hold the mapping
+        vsl!(vsl_frame_munmap(self.ptr));
+        self.sync(false, libc::O_WRONLY)?;
+        filled
+    }
+
     /// Constructs a [`Frame`] from a raw `VSLFrame` pointer, taking ownership.
     ///
     /// Returns `None` if `ptr` is null. On `Some`, the returned `Frame` owns
@@ -210,6 +930,10 @@ impl Frame {
     ///   through the returned `Frame` or through a non-owning pointer
     ///   obtained from [`Frame::as_ptr`].
     ///
+    /// Debug builds track the pointers owned by a `Frame` and panic when a
+    /// pointer is wrapped while already owned, before it could be released
+    /// twice.
+    ///
     /// # Example
     ///
     /// The function is `unsafe`, so it cannot be called from safe code:
@@ -224,7 +948,127 @@ impl Frame {
         if ptr.is_null() {
             return None;
         }
-        Some(Frame { ptr })
+        #[cfg(debug_assertions)]
+        assert!(
+            owned::insert(ptr),
+            "Frame::from_raw: VSLFrame {:p} is already owned by a Frame",
+            ptr
+        );
+        Some(Frame {
+            ptr,
+            maps: Cell::new(0),
+            planes: Vec::new(),
+        })
+    }
+
+    /// Adopts a `VSLFrame` created by other libvideostream-based C code,
+    /// taking ownership of its reference.
+    ///
+    /// Same as [`Frame::from_raw`], but reports a null pointer as
+    /// [`Error::NullPointer`] so it composes with `?`. The returned `Frame`
+    /// calls `vsl_frame_release` when dropped. To use a frame whose reference
+    /// the C code keeps, see [`Frame::borrow_raw`].
+    ///
+    /// # Safety
+    ///
+    /// Same as [`Frame::from_raw`]: `ptr` must carry a frame reference the
+    /// caller owns, no other [`Frame`] may own it, and the C code must not
+    /// release it or use it after the returned `Frame` is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NullPointer`] if `ptr` is null.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{frame::Frame, Error};
+    /// use videostream_sys as ffi;
+    ///
+    /// /// Called with a new frame reference that the C pipeline hands over.
+    /// fn on_frame(ptr: *mut ffi::VSLFrame) -> Result<(), Error> {
+    ///     let frame = unsafe { Frame::adopt(ptr) }?;
+    ///     println!("{}x{}", frame.width()?, frame.height()?);
+    ///     Ok(())
+    ///     // The frame is released here
+    /// }
+    /// ```
+    pub unsafe fn adopt(ptr: *mut ffi::VSLFrame) -> Result<Self, Error> {
+        unsafe { Frame::from_raw(ptr) }.ok_or(Error::NullPointer)
+    }
+
+    /// Borrows a `VSLFrame` owned by other libvideostream-based C code
+    /// without taking ownership.
+    ///
+    /// The returned [`BorrowedFrame`] dereferences to [`Frame`] but does not
+    /// call `vsl_frame_release` when dropped, so the C code keeps its
+    /// reference and remains responsible for releasing it.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must point to a valid `VSLFrame` for the whole lifetime `'a`.
+    /// - The owner must not release the frame while the `BorrowedFrame` is
+    ///   alive.
+    /// - Frames borrowed from another thread must not be used concurrently
+    ///   with the owner, as `VSLFrame` is not thread-safe.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NullPointer`] if `ptr` is null.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::Frame;
+    /// use videostream_sys as ffi;
+    ///
+    /// /// Called with a frame that the C pipeline keeps ownership of.
+    /// pub extern "C" fn inspect_frame(ptr: *mut ffi::VSLFrame) -> i64 {
+    ///     // The caller keeps ownership of `ptr`
+    ///     match unsafe { Frame::borrow_raw(ptr) } {
+    ///         Ok(frame) => frame.serial().unwrap_or(-1),
+    ///         Err(_) => -1,
+    ///     }
+    /// }
+    /// ```
+    pub unsafe fn borrow_raw<'a>(ptr: *mut ffi::VSLFrame) -> Result<BorrowedFrame<'a>, Error> {
+        if ptr.is_null() {
+            return Err(Error::NullPointer);
+        }
+        Ok(BorrowedFrame {
+            frame: ManuallyDrop::new(Frame {
+                ptr,
+                maps: Cell::new(0),
+                planes: Vec::new(),
+            }),
+            _owner: PhantomData,
+        })
+    }
+
+    /// Releases the frame now instead of when it goes out of scope.
+    ///
+    /// Same as dropping the frame, but explicit where a buffer is handed
+    /// back early, such as before waiting for the next frame. The frame is
+    /// consumed, so the compiler rejects any later use:
+    ///
+    /// ```compile_fail
+    /// # use videostream::frame::Frame;
+    /// let frame = Frame::new(640, 480, 0, "RGB3")?;
+    /// frame.release();
+    /// frame.width()?; // ERROR: borrow of moved value: `frame`
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn release(self) {
+        drop(self);
+    }
+
+    /// Gives up ownership of the frame reference without releasing it, for
+    /// C APIs that take ownership such as `vsl_host_post`.
+    pub(crate) fn into_raw(mut self) -> *mut ffi::VSLFrame {
+        let ptr = std::mem::replace(&mut self.ptr, std::ptr::null_mut());
+        #[cfg(debug_assertions)]
+        owned::remove(ptr);
+        ptr
     }
 
     /// Attempts to acquire a read lock on the frame.
@@ -258,15 +1102,17 @@ impl Frame {
     ///
     /// // Lock before reading
     /// frame.trylock()?;
-    /// let data = frame.mmap()?;
-    /// println!("Frame size: {} bytes", data.len());
+    /// {
+    ///     let data = frame.map()?;
+    ///     println!("Frame size: {} bytes", data.len());
+    /// }
     /// frame.unlock()?;
     /// # Ok::<(), videostream::Error>(())
     /// ```
     pub fn trylock(&self) -> Result<(), Error> {
         let ret = vsl!(vsl_frame_trylock(self.ptr));
         if ret != 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_frame_trylock");
             return Err(err.into());
         }
         Ok(())
@@ -279,7 +1125,9 @@ impl Frame {
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Io`] if the unlock fails.
+    /// Returns [`Error::Io`] if the unlock fails, or with
+    /// [`io::ErrorKind::InvalidInput`] while a [`FrameMap`] is alive, since
+    /// unlocking a client frame releases its mapping.
     ///
     /// # Example
     ///
@@ -294,8 +1142,14 @@ impl Frame {
     /// # Ok::<(), videostream::Error>(())
     /// ```
     pub fn unlock(&self) -> Result<(), Error> {
+        if self.maps.get() > 0 {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot unlock a frame while a FrameMap is alive",
+            )));
+        }
         if vsl!(vsl_frame_unlock(self.ptr)) as i32 == -1 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_frame_unlock");
             return Err(err.into());
         }
         Ok(())
@@ -305,6 +1159,8 @@ impl Frame {
     ///
     /// Required when using DmaBuf frames to ensure memory coherency between
     /// CPU and hardware accelerators (G2D, VPU). Call before/after hardware access.
+    /// Frames whose [`memory_type`](Self::memory_type) is not
+    /// [`FrameMemory::DmaBuf`] need no synchronization and this does nothing.
     ///
     /// # Arguments
     ///
@@ -332,18 +1188,62 @@ impl Frame {
     /// ```
     pub fn sync(&self, enable: bool, mode: i32) -> Result<(), Error> {
         let ret = vsl!(vsl_frame_sync(self.ptr, enable as i32, mode));
-        if ret >= 0 {
-            let err = io::Error::last_os_error();
+        if ret < 0 {
+            let err = crate::last_os_error("vsl_frame_sync");
             return Err(err.into());
         }
         Ok(())
     }
 
-    /// Returns the IPC host-assigned serial number for this frame.
+    /// Returns the kind of memory backing this frame.
     ///
-    /// Host-side monotonic counter incremented for each frame posted by a
-    /// [`Host`](crate::host::Host) to its subscribed clients, allowing
-    /// clients to detect dropped frames at the IPC boundary.
+    /// [`Frame::alloc`] reports [`FrameMemory::DmaBuf`] or
+    /// [`FrameMemory::Shm`] depending on where the buffer was allocated,
+    /// while attached frames and frames received from a host report
+    /// [`FrameMemory::Attached`]. Only DmaBuf frames need [`Frame::sync`]
+    /// around CPU access.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if the
+    /// frame has no buffer yet, and [`Error::SymbolNotFound`] if the loaded
+    /// `libvideostream.so` predates 2.6 and does not export
+    /// `vsl_frame_memory`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::{Frame, FrameMemory};
+    ///
+    /// let frame = Frame::new(640, 480, 0, "RGB3")?;
+    /// frame.alloc(None)?;
+    /// if frame.memory_type()? == FrameMemory::DmaBuf {
+    ///     frame.sync(true, 0)?;
+    /// }
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn memory_type(&self) -> Result<FrameMemory, Error> {
+        let lib = ffi::init()?;
+        let memory = lib
+            .vsl_frame_memory
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_memory"))?;
+        match unsafe { memory(self.ptr) } {
+            ffi::VSLFrameMemory_VSL_FRAME_MEMORY_DMABUF => Ok(FrameMemory::DmaBuf),
+            ffi::VSLFrameMemory_VSL_FRAME_MEMORY_SHM => Ok(FrameMemory::Shm),
+            ffi::VSLFrameMemory_VSL_FRAME_MEMORY_EXTERNAL => Ok(FrameMemory::Attached),
+            _ => Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frame has no buffer",
+            ))),
+        }
+    }
+
+    /// Returns the IPC host-assigned serial number for this frame.
+    ///
+    /// Host-side monotonic counter incremented for each frame posted by a
+    /// [`Host`](crate::host::Host) to its subscribed clients, allowing
+    /// clients to detect dropped frames at the IPC boundary.
     ///
     /// This is **not** the V4L2 / libcamera capture-source sequence
     /// number — for that, see
@@ -393,6 +1293,30 @@ impl Frame {
         Ok(vsl!(vsl_frame_timestamp(self.ptr)))
     }
 
+    /// Returns the frame timestamp as the time since boot.
+    ///
+    /// Same clock as [`timestamp()`](Self::timestamp), converted to a
+    /// [`Duration`] so it can be compared with other `CLOCK_MONOTONIC` times.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TryFromInt`] if the timestamp is negative, and
+    /// [`Error::LibraryNotLoaded`] if `libvideostream.so` cannot be loaded.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::Frame;
+    ///
+    /// let frame = Frame::new(640, 480, 0, "RGB3")?;
+    /// let since_boot = frame.monotonic_instant()?;
+    /// println!("Frame captured {:?} after boot", since_boot);
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn monotonic_instant(&self) -> Result<Duration, Error> {
+        nanos_to_duration(self.timestamp()?)
+    }
+
     /// Returns the frame duration in nanoseconds.
     ///
     /// Duration represents the display time for this frame (e.g., 33ms for 30fps).
@@ -459,6 +1383,265 @@ impl Frame {
         Ok(vsl!(vsl_frame_dts(self.ptr)))
     }
 
+    /// Returns the capture timestamp in nanoseconds, or 0 if not set.
+    ///
+    /// The capture time travels with the frame through the host, client and
+    /// encoder so consumers can measure glass-to-glass latency as
+    /// `timestamp()? - capture_time()?`. Frames created from a
+    /// [`CameraBuffer`] are stamped automatically with the driver's capture
+    /// time. Uses the same `CLOCK_MONOTONIC` time base as
+    /// [`crate::timestamp`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{client::{Client, Reconnect}, Error};
+    ///
+    /// let client = Client::new("/tmp/camera.sock", Reconnect::Yes)?;
+    /// let frame = client.get_frame(0)?;
+    /// let capture_time = frame.capture_time()?;
+    /// if capture_time != 0 {
+    ///     let latency = frame.timestamp()? - capture_time;
+    ///     println!("capture to host: {} us", latency / 1000);
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn capture_time(&self) -> Result<i64, Error> {
+        let lib = ffi::init()?;
+        let capture_time = lib
+            .vsl_frame_capture_time
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_capture_time"))?;
+        Ok(unsafe { capture_time(self.ptr) })
+    }
+
+    /// Sets the capture timestamp in nanoseconds (`CLOCK_MONOTONIC`).
+    ///
+    /// Must be called before the frame is posted to a [`crate::host::Host`]
+    /// for clients to receive it. Pass 0 to clear.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6.
+    pub fn set_capture_time(&self, capture_time: i64) -> Result<(), Error> {
+        let lib = ffi::init()?;
+        let set_capture_time = lib
+            .vsl_frame_set_capture_time
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_set_capture_time"))?;
+        unsafe { set_capture_time(self.ptr, capture_time) };
+        Ok(())
+    }
+
+    /// Returns whether the producer flagged this frame as a keyframe.
+    ///
+    /// The flag travels with the frame from host to client, so a client
+    /// joining an encoded stream mid-way can skip frames until a decode entry
+    /// point. Unlike [`is_keyframe`](Self::is_keyframe) it does not parse the
+    /// bitstream and is only as accurate as the producer; frames posted
+    /// without it report `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{client::{Client, Reconnect}, Error};
+    ///
+    /// let client = Client::new("/tmp/encoded.sock", Reconnect::Yes)?;
+    /// let frame = loop {
+    ///     let frame = client.get_frame(0)?;
+    ///     if frame.is_keyframe_flag()? {
+    ///         break frame;
+    ///     }
+    /// };
+    /// println!("decoding from frame {}", frame.serial()?);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn is_keyframe_flag(&self) -> Result<bool, Error> {
+        let lib = ffi::init()?;
+        let keyframe = lib
+            .vsl_frame_keyframe
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_keyframe"))?;
+        Ok(unsafe { keyframe(self.ptr) } != 0)
+    }
+
+    /// Flags the frame as a keyframe, or clears the flag.
+    ///
+    /// Must be called before the frame is posted to a [`crate::host::Host`]
+    /// for clients to receive it; see also
+    /// [`PostBuilder::keyframe`](crate::host::PostBuilder::keyframe).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6.
+    pub fn set_keyframe_flag(&self, keyframe: bool) -> Result<(), Error> {
+        let lib = ffi::init()?;
+        let set_keyframe = lib
+            .vsl_frame_set_keyframe
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_set_keyframe"))?;
+        unsafe { set_keyframe(self.ptr, std::os::raw::c_int::from(keyframe)) };
+        Ok(())
+    }
+
+    /// Returns the identifier of the source that produced the frame, or
+    /// `None` if the producer did not set one.
+    ///
+    /// Multi-camera pipelines that publish every camera on one stream stamp
+    /// each frame with its camera index, so receivers can demultiplex the
+    /// combined stream. Frames created from a [`CameraBuffer`] read through
+    /// [`CameraGroup`](crate::camera::CameraGroup) are stamped with the
+    /// camera's position in the group. The identifier travels with the frame
+    /// through the host, client and encoder.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{client::{Client, Reconnect}, Error};
+    ///
+    /// let client = Client::new("/tmp/cameras.sock", Reconnect::Yes)?;
+    /// let frame = client.get_frame(0)?;
+    /// match frame.source_id()? {
+    ///     Some(camera) => println!("frame {} from camera {}", frame.serial()?, camera),
+    ///     None => println!("frame {} from an unknown source", frame.serial()?),
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn source_id(&self) -> Result<Option<u32>, Error> {
+        let lib = ffi::init()?;
+        let source_id = lib
+            .vsl_frame_source_id
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_source_id"))?;
+        let mut id = 0;
+        match unsafe { source_id(self.ptr, &mut id) } {
+            0 => Ok(None),
+            1 => Ok(Some(id)),
+            _ => Err(crate::last_os_error("vsl_frame_source_id").into()),
+        }
+    }
+
+    /// Sets the identifier of the source that produced the frame, such as
+    /// the index of its camera.
+    ///
+    /// Must be called before the frame is posted to a [`crate::host::Host`]
+    /// for clients to receive it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6.
+    pub fn set_source_id(&self, id: u32) -> Result<(), Error> {
+        let lib = ffi::init()?;
+        let set_source_id = lib
+            .vsl_frame_set_source_id
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_set_source_id"))?;
+        unsafe { set_source_id(self.ptr, id) };
+        Ok(())
+    }
+
+    /// Returns the region of the frame intended for display, or `None` if
+    /// the producer did not set one.
+    ///
+    /// Encoders pad frames to their block size, so a 1080p stream is coded
+    /// as 1920x1088 with the extra rows cropped on display. The rectangle
+    /// travels with the frame from host to client; encoded frames get it
+    /// from the SPS cropping window.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{client::{Client, Reconnect}, Error};
+    ///
+    /// let client = Client::new("/tmp/camera.sock", Reconnect::Yes)?;
+    /// let frame = client.get_frame(0)?;
+    /// if let Some(rect) = frame.display_rect()? {
+    ///     println!("showing {}x{} at {},{}", rect.width, rect.height, rect.x, rect.y);
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn display_rect(&self) -> Result<Option<Rect>, Error> {
+        let lib = ffi::init()?;
+        let display_rect = lib
+            .vsl_frame_display_rect
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_display_rect"))?;
+        let mut rect = ffi::VSLRect {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+        };
+        match unsafe { display_rect(self.ptr, &mut rect) } {
+            -1 => Err(crate::last_os_error("vsl_frame_display_rect").into()),
+            0 => Ok(None),
+            _ => Ok(Some(rect.into())),
+        }
+    }
+
+    /// Sets the region of the frame intended for display.
+    ///
+    /// Must be called before the frame is posted to a [`crate::host::Host`]
+    /// for clients to receive it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the rectangle is empty or extends outside
+    /// the frame, and [`Error::SymbolNotFound`] if the library predates 2.6.
+    pub fn set_display_rect(&self, rect: Rect) -> Result<(), Error> {
+        let lib = ffi::init()?;
+        let set_display_rect = lib
+            .vsl_frame_set_display_rect
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_set_display_rect"))?;
+        let rect = ffi::VSLRect::from(rect);
+        if unsafe { set_display_rect(self.ptr, &rect) } == -1 {
+            return Err(crate::last_os_error("vsl_frame_set_display_rect").into());
+        }
+        Ok(())
+    }
+
+    /// Returns the wall-clock time the frame was captured, or `None` if no
+    /// capture time was set.
+    ///
+    /// The monotonic [`capture_time()`](Self::capture_time) is shifted by the
+    /// current offset between `CLOCK_MONOTONIC` and the system clock, so the
+    /// result moves if the system clock is stepped after capture.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{client::{Client, Reconnect}, Error};
+    ///
+    /// let client = Client::new("/tmp/camera.sock", Reconnect::Yes)?;
+    /// let frame = client.get_frame(0)?;
+    /// if let Some(captured) = frame.wall_time() {
+    ///     println!("captured at {:?}", captured);
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn wall_time(&self) -> Option<SystemTime> {
+        let capture_time = self.capture_time().ok().filter(|&t| t > 0)?;
+        let now = crate::timestamp().ok()?;
+        monotonic_to_system(capture_time, now, SystemTime::now())
+    }
+
     /// Returns the expiration timestamp in nanoseconds.
     ///
     /// Frames are automatically released by the host when they expire. Set via
@@ -530,11 +1713,88 @@ impl Frame {
         Ok(vsl!(vsl_frame_size(self.ptr)) as i32)
     }
 
+    /// Returns the number of valid bytes in the frame buffer.
+    ///
+    /// Encoders set this to the size of the encoded data and, as in earlier
+    /// releases, also report it from [`size()`](Self::size). Producers
+    /// posting a partially filled buffer set it with
+    /// [`set_used_size()`](Self::set_used_size) while `size()` stays the
+    /// buffer capacity. For raw frames both are equal. Clients receive the
+    /// value with the frame.
+    ///
+    /// Libraries before 2.6 do not export `vsl_frame_used_size`, so this
+    /// falls back to `size()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::client::{Client, Reconnect};
+    ///
+    /// let client = Client::new("/tmp/encoded.vsl", Reconnect::No)?;
+    /// let frame = client.get_frame(0)?;
+    /// println!("{} of {} bytes used", frame.used_size()?, frame.size()?);
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn used_size(&self) -> Result<usize, Error> {
+        let lib = ffi::init()?;
+        match lib.vsl_frame_used_size.as_ref() {
+            Ok(used_size) => Ok(unsafe { used_size(self.ptr) }),
+            Err(_) => Ok(usize::try_from(self.size()?)?),
+        }
+    }
+
+    /// Sets the number of valid bytes in the frame buffer.
+    ///
+    /// Call before posting a frame with a variable-size payload so clients
+    /// read only the valid data from [`used_size()`](Self::used_size). Pass
+    /// 0 to mark the whole buffer valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6, or
+    /// [`Error::Io`] if `used` exceeds [`size()`](Self::size).
+    pub fn set_used_size(&self, used: usize) -> Result<(), Error> {
+        let lib = ffi::init()?;
+        let set_used_size = lib
+            .vsl_frame_set_used_size
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_set_used_size"))?;
+        if unsafe { set_used_size(self.ptr, used) } != 0 {
+            return Err(crate::last_os_error("vsl_frame_set_used_size").into());
+        }
+        Ok(())
+    }
+
+    /// Returns the byte offset of the frame data within its buffer.
+    ///
+    /// Non-zero only for frames attached with an offset through
+    /// [`Frame::attach`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the loaded `libvideostream.so`
+    /// predates 2.6 and does not export `vsl_frame_offset`.
+    pub fn offset(&self) -> Result<usize, Error> {
+        let lib = ffi::init()?;
+        let offset = lib
+            .vsl_frame_offset
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_frame_offset"))?;
+        Ok(unsafe { offset(self.ptr) })
+    }
+
     /// Returns the stride in bytes of the video frame.
     ///
     /// Stride is the number of bytes from the start of one row to the next.
     /// May be larger than width*bytes_per_pixel due to alignment requirements.
     ///
+    /// Planar and semi-planar YUV formats (`NV12`, `NV21`, `NV16`, `NV61`,
+    /// `I420`, `YV12`) follow the library's convention of the luma row
+    /// stride plus half of it, `width * 3 / 2` for tightly packed planes.
+    /// Frames from [`from_planes`](Self::from_planes), camera buffers and the
+    /// V4L2 decoder report their padded luma stride this way too; the stride
+    /// of each plane is available from [`plane`](Self::plane).
+    ///
     /// # Returns
     ///
     /// Returns the row stride in bytes.
@@ -556,6 +1816,38 @@ impl Frame {
         Ok(vsl!(vsl_frame_stride(self.ptr)) as i32)
     }
 
+    /// Returns the geometry alignment required by this frame's pixel format.
+    ///
+    /// Derived from the frame's FOURCC via
+    /// [`required_alignment`](crate::fourcc::required_alignment). Use
+    /// [`FrameAlignment::validate`] against [`Frame::width`],
+    /// [`Frame::height`] and [`Frame::stride`] to catch buffers that the
+    /// VPU or G2D would reject.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if `vsl_frame_fourcc` is not exported by
+    /// the loaded library.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::Frame;
+    ///
+    /// let frame = Frame::new(1920, 1080, 0, "NV12")?;
+    /// let align = frame.alignment()?;
+    /// align.validate(
+    ///     frame.width()? as u32,
+    ///     frame.height()? as u32,
+    ///     frame.stride()? as u32,
+    /// )?;
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn alignment(&self) -> Result<FrameAlignment, Error> {
+        let fourcc = crate::fourcc::FourCC::from(self.fourcc()?);
+        Ok(crate::fourcc::required_alignment(fourcc))
+    }
+
     /// Returns the file descriptor handle for this frame's buffer.
     ///
     /// For DmaBuf frames, this is the DmaBuf file descriptor. For shared memory,
@@ -619,6 +1911,15 @@ impl Frame {
         Ok(Some(c_str.to_str().unwrap_or("unknown")))
     }
 
+    /// Maps the frame into memory and returns the data as a slice.
+    ///
+    /// The slice is not tied to the mapping's lifetime: calling
+    /// [`munmap()`](Self::munmap) or [`unlock()`](Self::unlock) while holding
+    /// it leaves a dangling reference. Use [`map()`](Self::map) instead.
+    #[deprecated(
+        since = "2.6.0",
+        note = "use `Frame::map()`, which keeps the mapping alive for the guard's lifetime"
+    )]
     #[allow(clippy::result_unit_err)]
     pub fn mmap(&self) -> Result<&[u8], Error> {
         let ptr = vsl!(vsl_frame_mmap(self.ptr, std::ptr::null_mut::<usize>()));
@@ -646,11 +1947,74 @@ impl Frame {
         Ok(unsafe { slice::from_raw_parts_mut(ptr as *mut u8, size) })
     }
 
+    /// Releases the frame's memory mapping.
+    ///
+    /// Does nothing while a [`FrameMap`] is alive; the last guard to drop
+    /// releases the mapping instead.
+    #[deprecated(
+        since = "2.6.0",
+        note = "use `Frame::map()`, which unmaps when the guard is dropped"
+    )]
     pub fn munmap(&self) -> Result<(), Error> {
+        if self.maps.get() > 0 {
+            log::warn!("Frame::munmap ignored while a FrameMap is alive");
+            return Ok(());
+        }
         vsl!(vsl_frame_munmap(self.ptr));
         Ok(())
     }
 
+    /// Maps the frame into memory and returns a guard over the data.
+    ///
+    /// The guard dereferences to `&[u8]` and releases the mapping when the
+    /// last guard for this frame is dropped, so the data cannot outlive the
+    /// mapping. For client frames, lock with [`trylock()`](Self::trylock)
+    /// first; [`unlock()`](Self::unlock) is refused while a guard is alive.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NullPointer`] if the frame has no buffer or the
+    /// mapping fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::Frame;
+    ///
+    /// let frame = Frame::new(640, 480, 0, "RGB3")?;
+    /// frame.alloc(None)?;
+    ///
+    /// let data = frame.map()?;
+    /// println!("first pixel: {:?}", &data[..3]);
+    /// // The mapping is released when `data` goes out of scope.
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn map(&self) -> Result<FrameMap<'_>, Error> {
+        let ptr = vsl!(vsl_frame_mmap(self.ptr, std::ptr::null_mut::<usize>()));
+        let size = self.size()?;
+        if ptr.is_null() || size <= 0 {
+            return Err(Error::NullPointer);
+        }
+        self.maps.set(self.maps.get() + 1);
+        Ok(FrameMap {
+            frame: self,
+            data: ptr as *const u8,
+            len: size as usize,
+        })
+    }
+
+    /// Releases one mapping reference taken by [`map()`](Self::map),
+    /// unmapping the frame with the last.
+    fn release_map(&self) {
+        let maps = self.maps.get() - 1;
+        self.maps.set(maps);
+        if maps == 0 {
+            if let Ok(lib) = ffi::init() {
+                unsafe { lib.vsl_frame_munmap(self.ptr) };
+            }
+        }
+    }
+
     /// Attaches an existing file descriptor to this frame.
     ///
     /// Associates an existing buffer (file, DmaBuf, or shared memory) with this frame
@@ -659,12 +2023,20 @@ impl Frame {
     /// # Arguments
     ///
     /// * `fd` - File descriptor to attach
-    /// * `size` - Size of the buffer in bytes
-    /// * `offset` - Offset into the buffer (usually 0)
+    /// * `size` - Size of the frame data in bytes, starting at `offset`
+    /// * `offset` - Offset of the frame data into the buffer (usually 0)
+    ///
+    /// A nonzero `offset` selects a frame inside a larger buffer, such as a
+    /// packed multi-frame file or a sub-allocated heap. [`Frame::map`] then
+    /// returns exactly `size` bytes starting at `offset`. The offset need not
+    /// be page aligned for CPU access, but hardware paths that import the
+    /// descriptor directly (V4L2 codecs) ignore it, so such frames should
+    /// start at offset 0.
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Io`] if the attachment fails.
+    /// Returns [`Error::Io`] if the attachment fails, including
+    /// `EINVAL` when `offset + size` runs past the end of a file or DmaBuf.
     ///
     /// # Example
     ///
@@ -689,7 +2061,7 @@ impl Frame {
         let ret = vsl!(vsl_frame_attach(self.ptr, fd, size, offset));
         log::debug!("vsl_frame_attach returned: {}", ret);
         if ret < 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_frame_attach");
             log::error!("Frame::attach failed with ret={}, errno: {:?}", ret, err);
             return Err(err.into());
         }
@@ -800,6 +2172,11 @@ impl Frame {
     ///
     /// Copy sequence: 1) Crop source, 2) Convert format, 3) Scale to target size.
     ///
+    /// Uncropped copies between frames of the same format and size skip the
+    /// pipeline and go through [`Frame::copy_exact`]. Uncropped copies of the
+    /// same size between the formats [`Frame::to_format`] lists fall back to
+    /// a conversion in software when the pipeline is unavailable.
+    ///
     /// # Arguments
     ///
     /// * `target` - Destination frame (receives copied data)
@@ -811,7 +2188,11 @@ impl Frame {
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Io`] if the copy operation fails.
+    /// Returns [`Error::ConversionUnsupported`] if the library reports the
+    /// format pair unsupported (`ENOTSUP`) and the software fallback does not
+    /// handle it either, and [`Error::Io`] if the copy otherwise fails,
+    /// including `EINVAL` for invalid arguments such as a crop rectangle
+    /// outside the source.
     ///
     /// # Warning
     ///
@@ -838,6 +2219,15 @@ impl Frame {
     /// # Ok::<(), videostream::Error>(())
     /// ```
     pub fn copy_to(&self, target: &Frame, crop: Option<&Rect>) -> Result<i32, Error> {
+        #[cfg(feature = "tracing")]
+        let span = crate::trace::timed_span!("copy_to");
+        #[cfg(feature = "tracing")]
+        span.record_frame(self);
+
+        if crop.is_none() && self.same_format(target)? {
+            return Ok(i32::try_from(self.copy_exact(target)?)?);
+        }
+
         let crop_ffi: Option<ffi::VSLRect> = crop.map(|r| (*r).into());
         let crop_ptr = crop_ffi
             .as_ref()
@@ -845,40 +2235,1138 @@ impl Frame {
         let ret = vsl!(vsl_frame_copy(target.ptr, self.ptr, crop_ptr));
         if ret < 0 {
             let err = io::Error::last_os_error();
-            return Err(err.into());
+            if crop.is_none() {
+                if let Some(bytes) = self.copy_software(target)? {
+                    log::debug!("frame copy unavailable, converted in software: {}", err);
+                    return Ok(i32::try_from(bytes)?);
+                }
+            }
+            // Only a pair the library reports unsupported and the software
+            // conversions cannot handle either is unsupported as such; EINVAL
+            // is a bad argument such as a crop outside the source.
+            let (from, to) = (self.fourcc()?, target.fourcc()?);
+            if from != to
+                && err.raw_os_error() == Some(libc::ENOTSUP)
+                && (Layout::of(from).is_none() || Layout::of(to).is_none())
+            {
+                return Err(Error::ConversionUnsupported {
+                    from: FourCC::from(from),
+                    to: FourCC::from(to),
+                });
+            }
+            return Err(crate::call_error("vsl_frame_copy", err).into());
         }
         Ok(ret)
     }
 
-    /// Returns a non-owning raw pointer to the underlying `VSLFrame`.
-    ///
-    /// The returned pointer is borrowed and valid only for the lifetime of
-    /// `&self`. The caller **must not** pass it to `vsl_frame_release` or
-    /// use it to construct another [`Frame`] via [`Frame::from_raw`] —
-    /// doing so would cause a double-free.
-    pub fn as_ptr(&self) -> *mut ffi::VSLFrame {
-        self.ptr
+    /// Converts between frames of the same size with
+    /// [`convert`](crate::convert). Returns the number of bytes written, or
+    /// `None` if the software conversions do not handle either format or a
+    /// semi-planar plane lives in a separate buffer.
+    fn copy_software(&self, target: &Frame) -> Result<Option<usize>, Error> {
+        if (self.width()?, self.height()?) != (target.width()?, target.height()?) {
+            return Ok(None);
+        }
+        let (Ok(Some(from)), Ok(Some(to))) = (self.layout(), target.layout()) else {
+            return Ok(None);
+        };
+
+        let dst = target.mmap_mut()?;
+        let result =
+            self.with_locked(|src| convert::convert((from.0, src, &from.1), (to.0, dst, &to.1)));
+        if target.maps.get() == 0 {
+            vsl!(vsl_frame_munmap(target.ptr));
+        }
+        result.map(Some)
     }
-}
 
-impl TryFrom<&CameraBuffer<'_>> for Frame {
-    type Error = Error;
+    /// Returns the software conversion layout of the frame and where its
+    /// rows lie within the mapping, or `None` for formats the conversions do
+    /// not handle. Semi-planar frames use their plane offsets and strides.
+    fn layout(&self) -> Result<Option<(Layout, Geometry)>, Error> {
+        let fourcc = self.fourcc()?;
+        let Some(layout) = Layout::of(fourcc) else {
+            return Ok(None);
+        };
+        let width = self.width()?.max(0) as usize;
+        let height = self.height()?.max(0) as usize;
+        let planes = match layout {
+            Layout::SemiPlanar { .. } => self.nv12_planes().ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "{} frame planes are not in one buffer",
+                        FourCC::from(fourcc)
+                    ),
+                ))
+            })?,
+            _ => {
+                let stride = self.stride()?.max(0) as usize;
+                [(0, stride.max(layout.row(width))), (0, 0)]
+            }
+        };
+        Ok(Some((
+            layout,
+            Geometry {
+                width,
+                height,
+                planes,
+            },
+        )))
+    }
 
-    fn try_from(buf: &CameraBuffer<'_>) -> Result<Self, Self::Error> {
-        log::debug!(
-            "Frame::try_from CameraBuffer: {}x{} format={} fd={}",
-            buf.width(),
-            buf.height(),
+    /// Returns `(offset, stride)` of the luma and chroma planes of an NV12
+    /// frame within its mapping, or `None` when a plane lives in a separate
+    /// buffer. See [`plane_count`](Self::plane_count).
+    fn nv12_planes(&self) -> Option<[(usize, usize); 2]> {
+        match self.mapped_planes()?.as_slice() {
+            [(luma, luma_stride), (chroma, chroma_stride), ..] => {
+                Some([(luma.start, *luma_stride), (chroma.start, *chroma_stride)])
+            }
+            _ => None,
+        }
+    }
+
+    /// Copies this frame into a target of the same format and size without
+    /// conversion or scaling.
+    ///
+    /// Rows are copied one by one when the strides differ, plane by plane
+    /// for planar formats, so padding in either frame is preserved;
+    /// otherwise the buffer is copied in one piece. The source is locked for
+    /// the duration of the copy if it belongs to a client. Returns the number
+    /// of bytes copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if the
+    /// frames differ in format or size, or the target buffer is too small,
+    /// and [`Error::NullPointer`] if either frame cannot be mapped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::Frame;
+    ///
+    /// let source = Frame::new(1920, 1080, 0, "NV12")?;
+    /// source.alloc(None)?;
+    ///
+    /// let target = Frame::new(1920, 1080, 0, "NV12")?;
+    /// target.alloc(None)?;
+    ///
+    /// source.copy_exact(&target)?;
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn copy_exact(&self, target: &Frame) -> Result<usize, Error> {
+        #[cfg(feature = "tracing")]
+        let span = crate::trace::timed_span!("copy_exact");
+        #[cfg(feature = "tracing")]
+        span.record_frame(self);
+
+        let invalid = |msg: String| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        if !self.same_format(target)? {
+            return Err(invalid(format!(
+                "cannot copy {}x{} {} into {}x{} {}",
+                self.width()?,
+                self.height()?,
+                FourCC::from(self.fourcc()?),
+                target.width()?,
+                target.height()?,
+                FourCC::from(target.fourcc()?)
+            )));
+        }
+        if self.ptr == target.ptr {
+            return Ok(0);
+        }
+        let (from, to) = (self.row_spans()?, target.row_spans()?);
+
+        let dst = target.mmap_mut()?;
+        let result = self.with_locked(|src| match (from, to) {
+            (Some(from), Some(to)) if from != to => copy_rows(src, &from, dst, &to),
+            _ => {
+                if dst.len() < src.len() {
+                    return Err(invalid(format!(
+                        "target buffer is {} bytes, source needs {}",
+                        dst.len(),
+                        src.len()
+                    )));
+                }
+                dst[..src.len()].copy_from_slice(src);
+                Ok(src.len())
+            }
+        });
+        if target.maps.get() == 0 {
+            vsl!(vsl_frame_munmap(target.ptr));
+        }
+        result
+    }
+
+    /// Returns where the rows of each plane lie within the frame's mapping,
+    /// or a single span for packed formats. Returns `None` for formats
+    /// without a known row size and for planes in separate buffers.
+    fn row_spans(&self) -> Result<Option<Vec<RowSpan>>, Error> {
+        let fourcc = self.fourcc()?;
+        let width = self.width()?.max(0) as usize;
+        let height = self.height()?.max(0) as usize;
+        if let Some(planes) = plane_rows(FourCC::from(fourcc), width, height) {
+            let Some(mapped) = self.mapped_planes() else {
+                return Ok(None);
+            };
+            if mapped.len() < planes.len() {
+                return Ok(None);
+            }
+            let spans = mapped
+                .into_iter()
+                .zip(planes)
+                .map(|((range, stride), (rows, row))| RowSpan {
+                    offset: range.start,
+                    stride,
+                    rows,
+                    row,
+                })
+                .collect();
+            return Ok(Some(spans));
+        }
+        let Some(row) = packed_row_bytes(fourcc, width) else {
+            return Ok(None);
+        };
+        Ok(Some(vec![RowSpan {
+            offset: 0,
+            stride: (self.stride()?.max(0) as usize).max(row),
+            rows: height,
+            row,
+        }]))
+    }
+
+    /// Returns `true` if `other` has the same fourcc and dimensions.
+    fn same_format(&self, other: &Frame) -> Result<bool, Error> {
+        Ok(self.fourcc()? == other.fourcc()?
+            && self.width()? == other.width()?
+            && self.height()? == other.height()?)
+    }
+
+    /// Returns a copy of this frame converted to `fourcc`, at the same
+    /// resolution.
+    ///
+    /// Allocates the target with [`Frame::alloc`] and converts through
+    /// [`Frame::copy_to`], which falls back to a conversion in software when
+    /// the copy is unavailable. The software path handles YUYV, UYVY, NV12, NV21,
+    /// the packed RGB formats and grayscale (`GREY`, `Y16 `), using BT.601
+    /// limited-range coefficients between YUV and RGB. Grayscale is full
+    /// range and becomes RGB with equal channels.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidFourCC`] or [`Error::UnsupportedFormat`] if
+    /// `fourcc` cannot be allocated, [`Error::Io`] if the allocation fails,
+    /// and the errors of [`Frame::copy_to`] if the conversion fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{fourcc::FourCC, frame::Frame};
+    ///
+    /// let camera = Frame::new(1920, 1080, 0, "YUYV")?;
+    /// camera.alloc(None)?;
+    ///
+    /// let nv12 = camera.to_format(FourCC(*b"NV12"))?;
+    /// assert_eq!(nv12.width()?, 1920);
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn to_format(&self, fourcc: FourCC) -> Result<Frame, Error> {
+        let target = Frame::new(
+            u32::try_from(self.width()?)?,
+            u32::try_from(self.height()?)?,
+            0,
+            &fourcc.to_string(),
+        )?;
+        target.alloc(None)?;
+        self.copy_to(&target, None)?;
+        Ok(target)
+    }
+
+    /// Rotates this frame clockwise into `target`.
+    ///
+    /// Uses G2D when both frames are DmaBuf backed and the library can
+    /// rotate them, otherwise rotates in software. The target must have the
+    /// same format, with width and height swapped for
+    /// [`Rotation::Rotate90`] and [`Rotation::Rotate270`]. Software rotation
+    /// supports the packed RGB formats and `GREY`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if the
+    /// target geometry or format does not match, or the format cannot be
+    /// rotated in software, and [`Error::NullPointer`] if either frame
+    /// cannot be mapped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::{Frame, Rotation};
+    ///
+    /// let source = Frame::new(1920, 1080, 0, "RGBA")?;
+    /// source.alloc(None)?;
+    ///
+    /// let target = Frame::new(1080, 1920, 0, "RGBA")?;
+    /// target.alloc(None)?;
+    ///
+    /// source.rotate(&target, Rotation::Rotate90)?;
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn rotate(&self, target: &Frame, rotation: Rotation) -> Result<(), Error> {
+        let fourcc = self.fourcc()?;
+        let (width, height) = (self.width()?, self.height()?);
+        let expected = match rotation {
+            Rotation::Rotate180 => (width, height),
+            Rotation::Rotate90 | Rotation::Rotate270 => (height, width),
+        };
+        if target.fourcc()? != fourcc || (target.width()?, target.height()?) != expected {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "rotating {}x{} {} by {} needs a {}x{} {} target",
+                    width,
+                    height,
+                    FourCC::from(fourcc),
+                    rotation.degrees(),
+                    expected.0,
+                    expected.1,
+                    FourCC::from(fourcc)
+                ),
+            )));
+        }
+
+        let lib = ffi::init()?;
+        if let Ok(rotate) = lib.vsl_frame_rotate.as_ref() {
+            if unsafe { rotate(target.ptr, self.ptr, rotation.degrees()) } == 0 {
+                return Ok(());
+            }
+            log::debug!(
+                "G2D rotation unavailable, rotating in software: {}",
+                io::Error::last_os_error()
+            );
+        }
+
+        self.rotate_software(target, rotation)
+    }
+
+    fn rotate_software(&self, target: &Frame, rotation: Rotation) -> Result<(), Error> {
+        let fourcc = self.fourcc()?;
+        let bpp = pixel_bytes(fourcc).ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "unsupported fourcc {} for software rotation",
+                    FourCC::from(fourcc)
+                ),
+            ))
+        })?;
+        let width = self.width()?.max(0) as usize;
+        let height = self.height()?.max(0) as usize;
+        let src_stride = (self.stride()?.max(0) as usize).max(width * bpp);
+        let dst_width = target.width()?.max(0) as usize;
+        let dst_stride = (target.stride()?.max(0) as usize).max(dst_width * bpp);
+
+        let rows = target.height()?.max(0) as usize;
+        let dst = target.mmap_mut()?;
+        let result = self.with_locked(|src| {
+            let needed = |rows: usize, stride: usize, row: usize| match rows {
+                0 => 0,
+                n => (n - 1) * stride + row,
+            };
+            if src.len() < needed(height, src_stride, width * bpp)
+                || dst.len() < needed(rows, dst_stride, dst_width * bpp)
+            {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "frame buffer too small for its geometry",
+                )));
+            }
+            for y in 0..height {
+                let line = &src[y * src_stride..y * src_stride + width * bpp];
+                for (x, px) in line.chunks_exact(bpp).enumerate() {
+                    let (dx, dy) = match rotation {
+                        Rotation::Rotate90 => (height - 1 - y, x),
+                        Rotation::Rotate180 => (width - 1 - x, height - 1 - y),
+                        Rotation::Rotate270 => (y, width - 1 - x),
+                    };
+                    let at = dy * dst_stride + dx * bpp;
+                    dst[at..at + bpp].copy_from_slice(px);
+                }
+            }
+            Ok(())
+        });
+        if target.maps.get() == 0 {
+            vsl!(vsl_frame_munmap(target.ptr));
+        }
+        result
+    }
+
+    /// Scales this frame into `target` preserving its aspect ratio, centered
+    /// and padded with `pad_color`.
+    ///
+    /// The scaled region is produced with [`Frame::copy_to`] into an
+    /// intermediate frame, falling back to nearest-neighbour scaling in
+    /// software when the copy is unavailable. Both frames must have the same
+    /// format, one of the packed RGB formats or `GREY`. `pad_color` is RGB;
+    /// alpha channels are padded opaque and `GREY` uses its luma.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if the
+    /// formats differ, the format is unsupported or either frame is empty,
+    /// and [`Error::NullPointer`] if either frame cannot be mapped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::Frame;
+    ///
+    /// let source = Frame::new(1920, 1080, 0, "RGB3")?;
+    /// source.alloc(None)?;
+    ///
+    /// let input = Frame::new(640, 640, 0, "RGB3")?;
+    /// input.alloc(None)?;
+    ///
+    /// let info = source.letterbox_to(&input, [114, 114, 114])?;
+    /// // The center of the model input maps back to the source center
+    /// let (x, y) = info.to_source(320.0, 320.0);
+    /// assert!((x - 960.0).abs() < 0.5 && (y - 540.0).abs() < 0.5);
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn letterbox_to(&self, target: &Frame, pad_color: [u8; 3]) -> Result<LetterboxInfo, Error> {
+        let fourcc = self.fourcc()?;
+        let invalid = |msg: String| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        if target.fourcc()? != fourcc {
+            return Err(invalid(format!(
+                "cannot letterbox {} into {}",
+                FourCC::from(fourcc),
+                FourCC::from(target.fourcc()?)
+            )));
+        }
+        let bpp = pixel_bytes(fourcc).ok_or_else(|| {
+            invalid(format!(
+                "unsupported fourcc {} for letterboxing",
+                FourCC::from(fourcc)
+            ))
+        })?;
+        let (src_width, src_height) = (self.width()?, self.height()?);
+        let (dst_width, dst_height) = (target.width()?, target.height()?);
+        if src_width <= 0 || src_height <= 0 || dst_width <= 0 || dst_height <= 0 {
+            return Err(invalid(format!(
+                "cannot letterbox {}x{} into {}x{}",
+                src_width, src_height, dst_width, dst_height
+            )));
+        }
+
+        let info = LetterboxInfo::new(src_width, src_height, dst_width, dst_height);
+        let scaled = Frame::new(
+            info.width as u32,
+            info.height as u32,
+            0,
+            &FourCC::from(fourcc).to_string(),
+        )?;
+        let scaled = match scaled.alloc(None).and_then(|_| self.copy_to(&scaled, None)) {
+            Ok(_) => Some(scaled),
+            Err(err) => {
+                log::debug!("frame copy unavailable, letterboxing in software: {}", err);
+                None
+            }
+        };
+
+        let pad = pad_pixel(fourcc, pad_color);
+        let pad = &pad[..bpp];
+        let dst_stride = (target.stride()?.max(0) as usize).max(dst_width as usize * bpp);
+        let (x0, y0) = (info.offset_x as usize, info.offset_y as usize);
+        let (w, h) = (info.width as usize, info.height as usize);
+
+        let dst = target.mmap_mut()?;
+        let result = (|| {
+            if dst.len() < (dst_height as usize - 1) * dst_stride + dst_width as usize * bpp {
+                return Err(invalid(
+                    "frame buffer too small for its geometry".to_string(),
+                ));
+            }
+            for y in 0..dst_height as usize {
+                let row = &mut dst[y * dst_stride..y * dst_stride + dst_width as usize * bpp];
+                for (x, px) in row.chunks_exact_mut(bpp).enumerate() {
+                    if y < y0 || y >= y0 + h || x < x0 || x >= x0 + w {
+                        px.copy_from_slice(pad);
+                    }
+                }
+            }
+
+            let (source, sw, sh) = match &scaled {
+                Some(scaled) => (scaled, w, h),
+                None => (self, src_width as usize, src_height as usize),
+            };
+            let src_stride = (source.stride()?.max(0) as usize).max(sw * bpp);
+            source.with_locked(|src| {
+                if src.len() < (sh - 1) * src_stride + sw * bpp {
+                    return Err(invalid(
+                        "frame buffer too small for its geometry".to_string(),
+                    ));
+                }
+                for y in 0..h {
+                    let sy = y * sh / h;
+                    let at = (y0 + y) * dst_stride + x0 * bpp;
+                    let line = &mut dst[at..at + w * bpp];
+                    if sw == w {
+                        line.copy_from_slice(&src[sy * src_stride..sy * src_stride + w * bpp]);
+                        continue;
+                    }
+                    for (x, px) in line.chunks_exact_mut(bpp).enumerate() {
+                        let from = sy * src_stride + (x * sw / w) * bpp;
+                        px.copy_from_slice(&src[from..from + bpp]);
+                    }
+                }
+                Ok(())
+            })
+        })();
+        if target.maps.get() == 0 {
+            vsl!(vsl_frame_munmap(target.ptr));
+        }
+        result.map(|_| info)
+    }
+
+    /// Returns `true` if this encoded frame contains an IDR picture.
+    ///
+    /// Parses the frame's Annex-B NAL unit headers for `codec` (`H264`,
+    /// `H265` or `HEVC`) rather than guessing from the frame size. Client
+    /// frames are locked while the data is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] for
+    /// other codecs, or if the frame cannot be locked, and
+    /// [`Error::NullPointer`] if the frame cannot be mapped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{client::{Client, Reconnect}, fourcc::FourCC};
+    ///
+    /// let client = Client::new("/tmp/video.sock", Reconnect::Yes)?;
+    /// let frame = client.get_frame(0)?;
+    /// if frame.is_keyframe(FourCC(*b"H264"))? {
+    ///     println!("keyframe {}", frame.serial()?);
+    /// }
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn is_keyframe(&self, codec: FourCC) -> Result<bool, Error> {
+        let codec = crate::nal::Codec::from_fourcc(codec)
+            .filter(|codec| codec.is_annex_b())
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unsupported codec {} for keyframe detection", codec),
+                ))
+            })?;
+        let used = self.used_size()?;
+        self.with_locked(|data| {
+            Ok(crate::nal::contains_idr(
+                codec,
+                &data[..used.min(data.len())],
+            ))
+        })
+    }
+
+    /// Copies the frame's pixel data into an owned buffer.
+    ///
+    /// Frames received through a [`Client`](crate::client::Client) are locked
+    /// for the duration of the copy and unlocked (and unmapped) afterwards, so
+    /// the host cannot recycle the buffer mid-copy. Locally allocated frames
+    /// have no host to lock against and are copied directly. The returned
+    /// buffer is [`Frame::size`] bytes, including any row padding; use
+    /// [`Frame::to_vec_packed`] to strip it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the frame cannot be locked (for example it
+    /// has already expired on the host) or [`Error::NullPointer`] if the
+    /// frame cannot be mapped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::Frame;
+    ///
+    /// let frame = Frame::new(640, 480, 0, "RGB3")?;
+    /// frame.alloc(None)?;
+    /// let pixels = frame.to_vec()?;
+    /// std::thread::spawn(move || println!("{} bytes", pixels.len()));
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn to_vec(&self) -> Result<Vec<u8>, Error> {
+        self.with_locked(|data| Ok(data.to_vec()))
+    }
+
+    /// Copies the frame's pixel data into an owned buffer without row padding.
+    ///
+    /// Each of the frame's [`Frame::height`] rows is copied from its
+    /// [`Frame::stride`] offset, keeping only the bytes covered by the
+    /// frame's width. Semi-planar and planar YUV formats are copied plane by
+    /// plane, each row at its plane's stride, giving the tightly packed planes
+    /// one after another. When the frame carries no padding this is
+    /// equivalent to [`Frame::to_vec`] truncated to the packed size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if the
+    /// frame's FOURCC has no known row size (such as compressed formats), its
+    /// planes live in separate buffers, or the mapped buffer is smaller than
+    /// its geometry implies. Locking and
+    /// mapping errors are the same as for [`Frame::to_vec`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::frame::Frame;
+    ///
+    /// let frame = Frame::new(640, 480, 0, "RGB3")?;
+    /// frame.alloc(None)?;
+    /// let pixels = frame.to_vec_packed()?;
+    /// assert_eq!(pixels.len(), 640 * 480 * 3);
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn to_vec_packed(&self) -> Result<Vec<u8>, Error> {
+        let fourcc = self.fourcc()?;
+        let spans = self.row_spans()?.ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "unsupported {} frame layout for packed copy",
+                    crate::fourcc::FourCC::from(fourcc)
+                ),
+            ))
+        })?;
+        let mut offset = 0;
+        let packed: Vec<RowSpan> = spans
+            .iter()
+            .map(|span| {
+                let packed = RowSpan {
+                    offset,
+                    stride: span.row,
+                    ..*span
+                };
+                offset += span.rows * span.row;
+                packed
+            })
+            .collect();
+
+        self.with_locked(|data| {
+            let mut out = vec![0u8; offset];
+            copy_rows(data, &spans, &mut out, &packed)?;
+            Ok(out)
+        })
+    }
+
+    /// Computes the luma minimum, maximum, mean and histogram of the frame.
+    ///
+    /// Luma is read from the Y samples of YUYV, UYVY, NV12 and NV21 frames,
+    /// from grayscale frames (the high byte of `Y16 ` samples) and computed
+    /// with the BT.601 weights for RGB formats, so statistics
+    /// are comparable across formats. Row padding is skipped using the
+    /// frame's [`stride`](Self::stride). Client frames are locked while they
+    /// are read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] for formats
+    /// without luma support or a buffer smaller than the frame geometry.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{client::{Client, Reconnect}, Error};
+    ///
+    /// let client = Client::new("/tmp/camera.sock", Reconnect::Yes)?;
+    /// let frame = client.get_frame(0)?;
+    /// let stats = frame.luma_stats()?;
+    /// println!("luma {}..{} mean {:.1}", stats.min, stats.max, stats.mean);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn luma_stats(&self) -> Result<LumaStats, Error> {
+        let fourcc = self.fourcc()?;
+        let (layout, geometry) = self.layout()?.ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "unsupported fourcc {} for luma statistics",
+                    FourCC::from(fourcc)
+                ),
+            ))
+        })?;
+
+        self.with_locked(|data| {
+            layout.check("frame", data.len(), &geometry)?;
+            let mut histogram = [0u32; 256];
+            for row in 0..geometry.height {
+                for col in 0..geometry.width {
+                    histogram[layout.luma(data, &geometry, row, col) as usize] += 1;
+                }
+            }
+            Ok(LumaStats::from_histogram(histogram))
+        })
+    }
+
+    /// Writes the frame to `path` as a binary (P6) PPM image for debugging.
+    ///
+    /// Converts to RGB in software like [`to_format`](Self::to_format), so
+    /// it supports the same formats and needs no image library; any image
+    /// viewer opens the result. Row padding is skipped using the frame's
+    /// [`stride`](Self::stride) and client frames are locked while read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ConversionUnsupported`] for formats the software
+    /// conversion does not read, and [`Error::Io`] if the buffer is smaller
+    /// than the frame geometry or writing the file fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use videostream::{client::{Client, Reconnect}, Error};
+    ///
+    /// let client = Client::new("/tmp/camera.sock", Reconnect::Yes)?;
+    /// client.get_frame(0)?.write_ppm(Path::new("/tmp/frame.ppm"))?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn write_ppm(&self, path: &Path) -> Result<(), Error> {
+        let fourcc = self.fourcc()?;
+        let (layout, geometry) = self.layout()?.ok_or(Error::ConversionUnsupported {
+            from: FourCC::from(fourcc),
+            to: FourCC(*b"RGB3"),
+        })?;
+        let (width, height) = (geometry.width, geometry.height);
+
+        let pixels = self.with_locked(|data| {
+            layout.check("frame", data.len(), &geometry)?;
+            Ok(layout.read(data, &geometry))
+        })?;
+
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        image.reserve(pixels.len() * 3);
+        for px in pixels {
+            let rgb = if layout.is_rgb() {
+                px
+            } else {
+                convert::yuv_to_rgb(px)
+            };
+            image.extend_from_slice(&rgb);
+        }
+        std::fs::write(path, image)?;
+        Ok(())
+    }
+
+    /// Runs `f` over the mapped frame data, holding the client lock if the
+    /// frame belongs to a client.
+    fn with_locked<T>(&self, f: impl FnOnce(&[u8]) -> Result<T, Error>) -> Result<T, Error> {
+        // vsl_frame_trylock fails with EINVAL for frames without a client,
+        // which have no host-side lifetime to protect.
+        let locked = match self.trylock() {
+            Ok(()) => true,
+            Err(Error::Io(err)) if err.kind() == io::ErrorKind::InvalidInput => false,
+            Err(err) => return Err(err),
+        };
+        let result = self.map().and_then(|data| f(&data));
+        if locked {
+            self.unlock()?;
+        }
+        result
+    }
+
+    /// Returns a non-owning raw pointer to the underlying `VSLFrame`.
+    ///
+    /// The returned pointer is borrowed and valid only for the lifetime of
+    /// `&self`. The caller **must not** pass it to `vsl_frame_release` or
+    /// use it to construct another [`Frame`] via [`Frame::from_raw`] —
+    /// doing so would cause a double-free.
+    pub fn as_ptr(&self) -> *mut ffi::VSLFrame {
+        self.ptr
+    }
+}
+
+/// Non-owning view of a `VSLFrame` owned by C code, returned by
+/// [`Frame::borrow_raw`].
+///
+/// Dereferences to [`Frame`] and leaves the frame reference untouched when
+/// dropped.
+pub struct BorrowedFrame<'a> {
+    frame: ManuallyDrop<Frame>,
+    _owner: PhantomData<&'a ffi::VSLFrame>,
+}
+
+impl Deref for BorrowedFrame<'_> {
+    type Target = Frame;
+
+    fn deref(&self) -> &Frame {
+        &self.frame
+    }
+}
+
+impl std::fmt::Debug for BorrowedFrame<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BorrowedFrame").field(&*self.frame).finish()
+    }
+}
+
+/// Read-only view of a frame's mapped memory returned by [`Frame::map`].
+///
+/// Borrows the frame, so the frame cannot be released while the view is
+/// alive, and unmaps the frame when the last view is dropped.
+pub struct FrameMap<'a> {
+    frame: &'a Frame,
+    data: *const u8,
+    len: usize,
+}
+
+impl Deref for FrameMap<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // Safety: the mapping stays valid until the last FrameMap for the
+        // frame is dropped, and `len` is the frame size at mapping time.
+        unsafe { slice::from_raw_parts(self.data, self.len) }
+    }
+}
+
+impl AsRef<[u8]> for FrameMap<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+/// Read-only view of one plane returned by [`Frame::map_plane`].
+pub struct PlaneMap<'a> {
+    map: FrameMap<'a>,
+    range: Range<usize>,
+}
+
+impl Deref for PlaneMap<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.map[self.range.clone()]
+    }
+}
+
+impl AsRef<[u8]> for PlaneMap<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+/// Writable view of one plane returned by [`Frame::plane_mut`].
+pub struct PlaneMut<'a> {
+    map: FrameMap<'a>,
+    range: Range<usize>,
+    stride: usize,
+}
+
+impl PlaneMut<'_> {
+    /// Returns the row stride of the plane in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the rows of the plane, each `stride` bytes long except
+    /// possibly the last.
+    pub fn rows_mut(&mut self) -> slice::ChunksMut<'_, u8> {
+        let stride = self.stride.max(1);
+        self.chunks_mut(stride)
+    }
+}
+
+impl Deref for PlaneMut<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.map[self.range.clone()]
+    }
+}
+
+impl DerefMut for PlaneMut<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // Safety: the frame is mapped writable and stays mapped while `map`
+        // is alive; `range` was checked against the mapping length. The view
+        // holds the frame's only borrow, taken by `Frame::plane_mut(&mut
+        // self)`, so no other view aliases these bytes.
+        unsafe {
+            let data = self.map.data.cast_mut().add(self.range.start);
+            slice::from_raw_parts_mut(data, self.range.len())
+        }
+    }
+}
+
+impl AsRef<[u8]> for PlaneMut<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AsMut<[u8]> for PlaneMut<'_> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+impl std::fmt::Debug for PlaneMut<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PlaneMut")
+            .field("range", &self.range)
+            .field("stride", &self.stride)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for PlaneMap<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PlaneMap")
+            .field("range", &self.range)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for FrameMap<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameMap")
+            .field("data", &self.data)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl Drop for FrameMap<'_> {
+    fn drop(&mut self) {
+        self.frame.release_map();
+    }
+}
+
+/// A frame shared read-only between threads, as handed out by
+/// [`BroadcastClient`](crate::client::BroadcastClient).
+///
+/// [`Frame`] is `Send` but not `Sync`: its setters write the underlying frame
+/// through `&self` and its mappings are counted without synchronization. A
+/// `SharedFrame` maps the frame once when it is created and only exposes the
+/// data and the metadata getters, so an `Arc<SharedFrame>` can be read from
+/// any number of threads. Client frames are locked for the lifetime of the
+/// `SharedFrame` so the host cannot recycle their buffer while it is read.
+/// The frame is unlocked and released when the last reference is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::{sync::Arc, thread};
+/// use videostream::frame::{Frame, SharedFrame};
+///
+/// let frame = Frame::new(640, 480, 0, "RGB3")?;
+/// frame.alloc(None)?;
+/// let shared = Arc::new(SharedFrame::new(frame)?);
+///
+/// let worker = Arc::clone(&shared);
+/// thread::spawn(move || println!("{} bytes", worker.data().len()))
+///     .join()
+///     .unwrap();
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub struct SharedFrame {
+    frame: Frame,
+    data: *const u8,
+    len: usize,
+    locked: bool,
+    /// Keeps the source of the frame, such as the client that unlocks it,
+    /// alive until the frame is released.
+    _owner: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+// Safety: only `&self` getters reading the frame are exposed, and the mapping
+// is created before sharing and released in `drop`.
+unsafe impl Send for SharedFrame {}
+unsafe impl Sync for SharedFrame {}
+
+impl SharedFrame {
+    /// Locks `frame` if it belongs to a client, maps it and wraps it for
+    /// sharing between threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the client frame cannot be locked, with
+    /// `EEXIST` once the host has expired it, and [`Error::NullPointer`] if
+    /// the frame has no buffer or the mapping fails.
+    pub fn new(frame: Frame) -> Result<Self, Error> {
+        // vsl_frame_trylock fails with EINVAL for frames without a client,
+        // as in Frame::with_locked().
+        let locked = match frame.trylock() {
+            Ok(()) => true,
+            Err(Error::Io(err)) if err.kind() == io::ErrorKind::InvalidInput => false,
+            Err(err) => return Err(err),
+        };
+        let map = match frame.map() {
+            Ok(map) => map,
+            Err(err) => {
+                if locked {
+                    let _ = frame.unlock();
+                }
+                return Err(err);
+            }
+        };
+        let (data, len) = (map.data, map.len);
+        // The mapping reference is released in drop
+        mem::forget(map);
+        Ok(SharedFrame {
+            frame,
+            data,
+            len,
+            locked,
+            _owner: None,
+        })
+    }
+
+    /// Wraps `frame`, keeping `owner` alive until the frame is released.
+    pub(crate) fn with_owner(
+        frame: Frame,
+        owner: Arc<dyn Any + Send + Sync>,
+    ) -> Result<Self, Error> {
+        let mut shared = Self::new(frame)?;
+        shared._owner = Some(owner);
+        Ok(shared)
+    }
+
+    /// Returns the frame data, mapped for the lifetime of the `SharedFrame`.
+    pub fn data(&self) -> &[u8] {
+        // Safety: the mapping stays valid until drop, and `len` is the frame
+        // size at mapping time.
+        unsafe { slice::from_raw_parts(self.data, self.len) }
+    }
+
+    /// Returns the frame serial, see [`Frame::serial`].
+    pub fn serial(&self) -> Result<i64, Error> {
+        self.frame.serial()
+    }
+
+    /// Returns the host timestamp, see [`Frame::timestamp`].
+    pub fn timestamp(&self) -> Result<i64, Error> {
+        self.frame.timestamp()
+    }
+
+    /// Returns the frame duration, see [`Frame::duration`].
+    pub fn duration(&self) -> Result<i64, Error> {
+        self.frame.duration()
+    }
+
+    /// Returns the presentation timestamp, see [`Frame::pts`].
+    pub fn pts(&self) -> Result<i64, Error> {
+        self.frame.pts()
+    }
+
+    /// Returns the decode timestamp, see [`Frame::dts`].
+    pub fn dts(&self) -> Result<i64, Error> {
+        self.frame.dts()
+    }
+
+    /// Returns the capture time, see [`Frame::capture_time`].
+    pub fn capture_time(&self) -> Result<i64, Error> {
+        self.frame.capture_time()
+    }
+
+    /// Returns the keyframe flag, see [`Frame::is_keyframe_flag`].
+    pub fn is_keyframe_flag(&self) -> Result<bool, Error> {
+        self.frame.is_keyframe_flag()
+    }
+
+    /// Returns the display region, see [`Frame::display_rect`].
+    pub fn display_rect(&self) -> Result<Option<Rect>, Error> {
+        self.frame.display_rect()
+    }
+
+    /// Returns the pixel format, see [`Frame::fourcc`].
+    pub fn fourcc(&self) -> Result<u32, Error> {
+        self.frame.fourcc()
+    }
+
+    /// Returns the width in pixels, see [`Frame::width`].
+    pub fn width(&self) -> Result<i32, Error> {
+        self.frame.width()
+    }
+
+    /// Returns the height in pixels, see [`Frame::height`].
+    pub fn height(&self) -> Result<i32, Error> {
+        self.frame.height()
+    }
+
+    /// Returns the row stride in bytes, see [`Frame::stride`].
+    pub fn stride(&self) -> Result<i32, Error> {
+        self.frame.stride()
+    }
+
+    /// Returns the size of the frame data in bytes, see [`Frame::size`].
+    pub fn size(&self) -> Result<i32, Error> {
+        self.frame.size()
+    }
+
+    /// Returns the number of valid bytes, see [`Frame::used_size`].
+    pub fn used_size(&self) -> Result<usize, Error> {
+        self.frame.used_size()
+    }
+}
+
+impl Drop for SharedFrame {
+    fn drop(&mut self) {
+        self.frame.release_map();
+        if self.locked {
+            if let Err(err) = self.frame.unlock() {
+                log::debug!("SharedFrame::drop: unlock failed: {}", err);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for SharedFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedFrame")
+            .field("frame", &self.frame)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl TryFrom<&CameraBuffer<'_>> for Frame {
+    type Error = Error;
+
+    fn try_from(buf: &CameraBuffer<'_>) -> Result<Self, Self::Error> {
+        log::debug!(
+            "Frame::try_from CameraBuffer: {}x{} format={} fd={}",
+            buf.width(),
+            buf.height(),
             buf.format(),
             buf.fd().as_raw_fd()
         );
 
-        let frame = Frame::new(
-            buf.width().try_into().unwrap(),
-            buf.height().try_into().unwrap(),
-            0,
-            buf.format().to_string().as_str(),
-        )?;
+        let width: u32 = buf.width().try_into()?;
+        let height: u32 = buf.height().try_into()?;
+        let format = buf.format().to_string();
+
+        // Planar formats are described plane by plane using the driver's
+        // padded stride, which libraries before 2.5 do not report.
+        let stride = buf.bytes_per_line().unwrap_or(0) as usize;
+        if let Some(layout) = plane_layout(buf.format(), stride, height as usize) {
+            let planes: Vec<PlaneInput<'_>> = layout
+                .into_iter()
+                .map(|(offset, stride, size)| PlaneInput {
+                    fd: buf.fd(),
+                    offset,
+                    stride,
+                    size,
+                })
+                .collect();
+            let frame = Frame::from_planes(&planes, width, height, &format)?;
+            frame.stamp_camera(buf)?;
+            return Ok(frame);
+        }
+
+        let frame = Frame::new(width, height, 0, &format)?;
 
         log::debug!(
             "Frame created successfully, attempting attach with fd={}",
@@ -894,13 +3382,212 @@ impl TryFrom<&CameraBuffer<'_>> for Frame {
                 return Err(e);
             }
         }
+
+        frame.stamp_camera(buf)?;
         Ok(frame)
     }
 }
 
+/// Returns the frame stride for a buffer of `fourcc` whose first plane has
+/// rows `plane_stride` bytes apart, following the library convention
+/// described on [`Frame::stride`].
+fn frame_stride(fourcc: FourCC, plane_stride: usize) -> usize {
+    match &fourcc.0 {
+        b"NV12" | b"NV21" | b"NV16" | b"NV61" | b"I420" | b"YU12" | b"YV12" => {
+            plane_stride + (plane_stride >> 1)
+        }
+        _ => plane_stride,
+    }
+}
+
+/// Returns the luma row stride of a planar or semi-planar frame from its
+/// frame stride, the inverse of [`frame_stride`].
+fn luma_stride(frame_stride: usize) -> usize {
+    frame_stride - frame_stride / 3
+}
+
+/// Rows of one plane, or of a whole packed frame, within a mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RowSpan {
+    /// Byte offset of the first row
+    offset: usize,
+    /// Bytes from the start of one row to the next
+    stride: usize,
+    /// Number of rows
+    rows: usize,
+    /// Bytes of pixel data in each row
+    row: usize,
+}
+
+impl RowSpan {
+    /// Returns `true` if the rows lie within `len` bytes without overlapping.
+    fn fits(&self, len: usize) -> bool {
+        (self.rows <= 1 || self.stride >= self.row)
+            && len >= self.offset + convert::span(self.rows, self.stride, self.row)
+    }
+
+    /// Returns the byte range of row `index`.
+    fn row(&self, index: usize) -> Range<usize> {
+        let start = self.offset + index * self.stride;
+        start..start + self.row
+    }
+}
+
+/// Copies the rows of each span in `from` to the matching span in `to`,
+/// returning the number of bytes copied.
+fn copy_rows(src: &[u8], from: &[RowSpan], dst: &mut [u8], to: &[RowSpan]) -> Result<usize, Error> {
+    let mut copied = 0;
+    for (from, to) in from.iter().zip(to) {
+        if !from.fits(src.len()) || !to.fits(dst.len()) {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frame buffer too small for its geometry",
+            )));
+        }
+        for index in 0..from.rows {
+            dst[to.row(index)].copy_from_slice(&src[from.row(index)]);
+        }
+        copied += from.rows * from.row;
+    }
+    Ok(copied)
+}
+
+/// Returns `(rows, row bytes)` for each plane of a `width` x `height` frame
+/// in a planar or semi-planar format, or `None` for other formats.
+fn plane_rows(fourcc: FourCC, width: usize, height: usize) -> Option<Vec<(usize, usize)>> {
+    let (chroma_width, chroma_height) = ((width + 1) / 2, (height + 1) / 2);
+    match &fourcc.0 {
+        b"NV12" | b"NV21" => Some(vec![(height, width), (chroma_height, chroma_width * 2)]),
+        b"NV16" | b"NV61" => Some(vec![(height, width), (height, chroma_width * 2)]),
+        b"I420" | b"YU12" | b"YV12" => Some(vec![
+            (height, width),
+            (chroma_height, chroma_width),
+            (chroma_height, chroma_width),
+        ]),
+        _ => None,
+    }
+}
+
+/// Returns `(offset, stride, size)` for each plane of a planar or
+/// semi-planar format stored back to back in one buffer, or `None` for
+/// packed formats and an unknown stride.
+fn plane_layout(
+    fourcc: FourCC,
+    stride: usize,
+    height: usize,
+) -> Option<Vec<(usize, usize, usize)>> {
+    if stride == 0 {
+        return None;
+    }
+    let luma = stride * height;
+    match &fourcc.0 {
+        b"NV12" | b"NV21" => {
+            let chroma = stride * ((height + 1) / 2);
+            Some(vec![(0, stride, luma), (luma, stride, chroma)])
+        }
+        b"NV16" | b"NV61" => Some(vec![(0, stride, luma), (luma, stride, luma)]),
+        b"I420" | b"YU12" | b"YV12" => {
+            let chroma_stride = (stride + 1) / 2;
+            let chroma = chroma_stride * ((height + 1) / 2);
+            Some(vec![
+                (0, stride, luma),
+                (luma, chroma_stride, chroma),
+                (luma + chroma, chroma_stride, chroma),
+            ])
+        }
+        _ => None,
+    }
+}
+
+impl Frame {
+    /// Stamps the driver capture time of `buf` for end-to-end latency
+    /// measurement, and its camera index when read through a
+    /// [`CameraGroup`](crate::camera::CameraGroup); older libraries without
+    /// these APIs are tolerated.
+    pub(crate) fn stamp_camera(&self, buf: &CameraBuffer<'_>) -> Result<(), Error> {
+        let ts = buf.timestamp()?;
+        let capture_time = ts.seconds() * 1_000_000_000 + i64::from(ts.subsec(9));
+        match self.set_capture_time(capture_time) {
+            Ok(()) | Err(Error::SymbolNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+        match buf.source_id().map(|id| self.set_source_id(id)) {
+            None | Some(Ok(())) | Some(Err(Error::SymbolNotFound(_))) => Ok(()),
+            Some(Err(e)) => Err(e),
+        }
+    }
+}
+
+impl TryFrom<&Frame> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(frame: &Frame) -> Result<Self, Self::Error> {
+        frame.to_vec()
+    }
+}
+
+/// Converts a non-negative nanosecond count to a [`Duration`].
+fn nanos_to_duration(nanos: i64) -> Result<Duration, Error> {
+    Ok(Duration::from_nanos(u64::try_from(nanos)?))
+}
+
+/// Maps the monotonic time `nanos` to the system clock, given the
+/// monotonic time `now` that corresponds to `system_now`.
+fn monotonic_to_system(nanos: i64, now: i64, system_now: SystemTime) -> Option<SystemTime> {
+    let age = now.checked_sub(nanos)?;
+    if age >= 0 {
+        system_now.checked_sub(Duration::from_nanos(age as u64))
+    } else {
+        system_now.checked_add(Duration::from_nanos(age.unsigned_abs()))
+    }
+}
+
+/// Bytes per row of unpadded pixel data for packed formats. Returns `None`
+/// for planar formats, which [`plane_rows`] describes, and for formats
+/// without a fixed row size.
+fn packed_row_bytes(fourcc: u32, width: usize) -> Option<usize> {
+    match &crate::fourcc::FourCC::from(fourcc).0 {
+        b"RGBA" | b"RGBX" | b"BGRA" | b"BGRX" => Some(width * 4),
+        b"RGB3" | b"BGR3" => Some(width * 3),
+        b"YUYV" | b"YUY2" | b"YVYU" | b"UYVY" | b"VYUY" => Some(width * 2),
+        b"GREY" => Some(width),
+        b"Y16 " => Some(width * 2),
+        _ => None,
+    }
+}
+
+/// Bytes per pixel for formats that can be rotated pixel by pixel.
+fn pixel_bytes(fourcc: u32) -> Option<usize> {
+    match &FourCC::from(fourcc).0 {
+        b"RGBA" | b"RGBX" | b"BGRA" | b"BGRX" => Some(4),
+        b"RGB3" | b"BGR3" => Some(3),
+        b"GREY" => Some(1),
+        b"Y16 " => Some(2),
+        _ => None,
+    }
+}
+
+/// Returns `rgb` as a pixel of `fourcc`, padded to four bytes.
+fn pad_pixel(fourcc: u32, rgb: [u8; 3]) -> [u8; 4] {
+    let [r, g, b] = rgb;
+    match &FourCC::from(fourcc).0 {
+        b"BGRA" | b"BGRX" | b"BGR3" => [b, g, r, 0xff],
+        b"GREY" => [convert::grey(r, g, b), 0, 0, 0],
+        _ => [r, g, b, 0xff],
+    }
+}
+
 impl Drop for Frame {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
+            // A pointer missing from the registry was released through
+            // another Frame; releasing it again would be a double free.
+            #[cfg(debug_assertions)]
+            assert!(
+                owned::remove(self.ptr),
+                "Frame::drop: VSLFrame {:p} was already released",
+                self.ptr
+            );
             log::trace!("Frame::drop() - releasing frame ptr={:?}", self.ptr);
             if let Ok(lib) = ffi::init() {
                 unsafe {
@@ -912,6 +3599,43 @@ impl Drop for Frame {
     }
 }
 
+/// Debug-build registry of the `VSLFrame` pointers owned by a [`Frame`],
+/// catching misuse of [`Frame::from_raw`] that would release a frame twice.
+#[cfg(debug_assertions)]
+mod owned {
+    use std::{
+        collections::BTreeSet,
+        sync::{Mutex, PoisonError},
+    };
+    use videostream_sys as ffi;
+
+    static OWNED: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+
+    /// Records `ptr` as owned, returning `false` if it already was.
+    pub(super) fn insert(ptr: *mut ffi::VSLFrame) -> bool {
+        OWNED
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(ptr as usize)
+    }
+
+    /// Forgets `ptr`, returning `false` if it was not owned.
+    pub(super) fn remove(ptr: *mut ffi::VSLFrame) -> bool {
+        OWNED
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&(ptr as usize))
+    }
+
+    #[cfg(test)]
+    pub(super) fn contains(ptr: *mut ffi::VSLFrame) -> bool {
+        OWNED
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(&(ptr as usize))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -926,6 +3650,7 @@ mod tests {
     const INVALID_HANDLE: i32 = -1;
 
     #[test]
+    #[allow(deprecated)]
     fn frame() {
         //let fourcc = 0x33424752 as u32; //Hex for RGB3
         let frame = Frame::new(640, 480, 0, "RGB3").unwrap();
@@ -968,6 +3693,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn attach_file() {
         let frame = Frame::new(640, 480, 0, "RGB3").unwrap();
 
@@ -1003,6 +3729,400 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_frame_to_vec() {
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame.alloc(None).unwrap();
+
+        let mut rng = rand::rng();
+        for elem in frame.mmap_mut().unwrap().iter_mut() {
+            *elem = rng.random();
+        }
+
+        let data = frame.to_vec().unwrap();
+        assert_eq!(data.len(), frame.size().unwrap() as usize);
+        assert_eq!(data.as_slice(), &*frame.map().unwrap());
+
+        let owned = Vec::<u8>::try_from(&frame).unwrap();
+        assert_eq!(owned, data);
+
+        // No padding, so the packed copy is identical.
+        assert_eq!(frame.to_vec_packed().unwrap(), data);
+    }
+
+    #[test]
+    fn test_frame_to_vec_packed() {
+        let (width, height, stride) = (64usize, 48usize, 256usize);
+        let frame = Frame::new(width as u32, height as u32, stride as u32, "RGB3").unwrap();
+
+        let mut padded = vec![0u8; stride * height];
+        let mut rng = rand::rng();
+        for elem in &mut padded {
+            *elem = rng.random();
+        }
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open("./temp_packed.bin")
+            .unwrap();
+        file.write_all(&padded).unwrap();
+        frame.attach(file.as_raw_fd(), padded.len(), 0).unwrap();
+
+        assert_eq!(frame.to_vec().unwrap(), padded);
+
+        let packed = frame.to_vec_packed().unwrap();
+        assert_eq!(packed.len(), width * 3 * height);
+        for (row, line) in packed.chunks(width * 3).enumerate() {
+            assert_eq!(line, &padded[row * stride..row * stride + width * 3]);
+        }
+
+        fs::remove_file("./temp_packed.bin").unwrap();
+    }
+
+    #[test]
+    fn test_frame_map_guard() {
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+
+        let mut expect = vec![0u8; 64 * 48 * 3];
+        let mut rng = rand::rng();
+        for elem in &mut expect {
+            *elem = rng.random();
+        }
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open("./temp_map.bin")
+            .unwrap();
+        file.write_all(&expect).unwrap();
+        frame.attach(file.as_raw_fd(), expect.len(), 0).unwrap();
+
+        let path = fs::canonicalize("./temp_map.bin").unwrap();
+        let mapped = || {
+            fs::read_to_string("/proc/self/maps")
+                .unwrap()
+                .contains(path.to_str().unwrap())
+        };
+
+        let first = frame.map().unwrap();
+        let second = frame.map().unwrap();
+        assert_eq!(&*first, expect.as_slice());
+        assert!(mapped());
+
+        // Unlocking would release the mapping under a live guard.
+        assert!(frame.unlock().is_err());
+
+        // The mapping outlives the first guard while the second is alive.
+        drop(first);
+        assert!(mapped());
+        assert_eq!(second.as_ref(), expect.as_slice());
+
+        drop(second);
+        assert!(!mapped());
+
+        fs::remove_file("./temp_map.bin").unwrap();
+    }
+
+    #[test]
+    fn test_shared_frame() {
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+
+        let expect: Vec<u8> = (0..64 * 48 * 3).map(|n| n as u8).collect();
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open("./temp_shared.bin")
+            .unwrap();
+        file.write_all(&expect).unwrap();
+        frame.attach(file.as_raw_fd(), expect.len(), 0).unwrap();
+
+        let path = fs::canonicalize("./temp_shared.bin").unwrap();
+        let mapped = || {
+            fs::read_to_string("/proc/self/maps")
+                .unwrap()
+                .contains(path.to_str().unwrap())
+        };
+
+        let shared = Arc::new(SharedFrame::new(frame).unwrap());
+        assert!(mapped());
+        let readers: Vec<_> = (0..3)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                std::thread::spawn(move || {
+                    assert_eq!(shared.width().unwrap(), 64);
+                    assert_eq!(shared.fourcc().unwrap(), u32::from_le_bytes(*b"RGB3"));
+                    shared.data().to_vec()
+                })
+            })
+            .collect();
+        for reader in readers {
+            assert_eq!(reader.join().unwrap(), expect);
+        }
+
+        // The last reference unmaps and releases the frame
+        drop(shared);
+        assert!(!mapped());
+
+        fs::remove_file("./temp_shared.bin").unwrap();
+    }
+
+    #[test]
+    fn test_frame_to_vec_packed_nv12() {
+        // 4x2 NV12 with 8-byte luma rows: Y at 0, UV at 16
+        let frame = Frame::new(4, 2, 12, "NV12").unwrap();
+        let mut padded = vec![0xEEu8; 24];
+        padded[0..4].copy_from_slice(&[10, 20, 30, 40]);
+        padded[8..12].copy_from_slice(&[50, 60, 70, 80]);
+        padded[16..20].copy_from_slice(&[100, 200, 110, 210]);
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open("./temp_packed_nv12.bin")
+            .unwrap();
+        file.write_all(&padded).unwrap();
+        frame.attach(file.as_raw_fd(), padded.len(), 0).unwrap();
+
+        let tight = [10, 20, 30, 40, 50, 60, 70, 80, 100, 200, 110, 210];
+        assert_eq!(frame.to_vec_packed().unwrap(), tight);
+
+        // The same-format copy_to path keeps the planes apart as well
+        let target = Frame::new(4, 2, 0, "NV12").unwrap();
+        target.alloc(None).unwrap();
+        assert_eq!(frame.copy_to(&target, None).unwrap(), 12);
+        assert_eq!(&*target.map().unwrap(), &tight);
+        assert_eq!(target.to_vec_packed().unwrap(), tight);
+
+        drop(frame);
+        fs::remove_file("./temp_packed_nv12.bin").unwrap();
+    }
+
+    #[test]
+    fn test_frame_to_vec_packed_unsupported() {
+        let frame = Frame::new(64, 48, 64, "H264").unwrap();
+        assert!(frame.to_vec_packed().is_err());
+    }
+
+    #[test]
+    fn test_frame_attach_offset() {
+        const OFFSET: usize = 4096;
+        let pattern: Vec<u8> = (0..64 * 48 * 3).map(|i| (i % 251) as u8).collect();
+
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open("./temp_attach_offset.bin")
+            .unwrap();
+        file.write_all(&[0xAA; OFFSET]).unwrap();
+        file.write_all(&pattern).unwrap();
+        file.write_all(&[0xBB; 100]).unwrap();
+
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame
+            .attach(file.as_raw_fd(), pattern.len(), OFFSET)
+            .unwrap();
+        assert_eq!(frame.offset().unwrap(), OFFSET);
+        assert_eq!(frame.map().unwrap().as_ref(), pattern.as_slice());
+
+        // Offsets that are not page aligned map just as well.
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame
+            .attach(file.as_raw_fd(), pattern.len() - 100, OFFSET + 100)
+            .unwrap();
+        assert_eq!(frame.map().unwrap().as_ref(), &pattern[100..]);
+
+        // The range must stay within the file.
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        assert!(frame
+            .attach(file.as_raw_fd(), pattern.len() + 101, OFFSET)
+            .is_err());
+
+        fs::remove_file("./temp_attach_offset.bin").unwrap();
+    }
+
+    #[test]
+    fn test_nanos_to_duration() {
+        assert_eq!(nanos_to_duration(0).unwrap(), Duration::ZERO);
+        assert_eq!(
+            nanos_to_duration(1_500_000_001).unwrap(),
+            Duration::new(1, 500_000_001)
+        );
+        assert!(matches!(nanos_to_duration(-1), Err(Error::TryFromInt(_))));
+    }
+
+    #[test]
+    fn test_monotonic_to_system() {
+        let epoch = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        assert_eq!(
+            monotonic_to_system(2_000_000_000, 5_000_000_000, epoch),
+            Some(epoch - Duration::from_secs(3))
+        );
+        assert_eq!(
+            monotonic_to_system(6_500_000_000, 5_000_000_000, epoch),
+            Some(epoch + Duration::from_millis(1_500))
+        );
+    }
+
+    #[test]
+    fn test_frame_wall_time() {
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        assert_eq!(frame.wall_time(), None);
+
+        frame
+            .set_capture_time(crate::timestamp().unwrap() - 1_000_000_000)
+            .unwrap();
+        let age = SystemTime::now()
+            .duration_since(frame.wall_time().unwrap())
+            .unwrap();
+        assert!(age >= Duration::from_millis(900), "{:?}", age);
+        assert!(age < Duration::from_secs(2), "{:?}", age);
+    }
+
+    #[test]
+    fn test_frame_monotonic_instant() {
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        let ts = frame.timestamp().unwrap();
+        assert_eq!(
+            frame.monotonic_instant().unwrap(),
+            Duration::from_nanos(ts as u64)
+        );
+    }
+
+    #[test]
+    fn test_write_ppm_rgb() {
+        let (width, height) = (5usize, 3usize);
+        let frame = Frame::new(width as u32, height as u32, 0, "RGB3").unwrap();
+        frame.alloc(None).unwrap();
+        {
+            let data = frame.mmap_mut().unwrap();
+            for (y, row) in data.chunks_mut(width * 3).take(height).enumerate() {
+                for x in 0..width {
+                    row[x * 3..x * 3 + 3].copy_from_slice(&[x as u8, y as u8, 7]);
+                }
+            }
+        }
+
+        let path = std::env::temp_dir().join(format!("vsl_write_ppm_{}.ppm", std::process::id()));
+        frame.write_ppm(&path).unwrap();
+        let image = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let header = b"P6\n5 3\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        let header = String::from_utf8_lossy(&image[..header.len()]).into_owned();
+        let dims: Vec<usize> = header
+            .split_whitespace()
+            .skip(1)
+            .map(|field| field.parse().unwrap())
+            .collect();
+        assert_eq!(dims, [width, height, 255]);
+
+        let body = &image[header.len()..];
+        assert_eq!(body.len(), width * height * 3);
+        assert_eq!(&body[..6], &[0, 0, 7, 1, 0, 7]);
+        assert_eq!(&body[body.len() - 3..], &[4, 2, 7]);
+
+        let nv12 = Frame::new(64, 48, 0, "I420").unwrap();
+        assert!(matches!(
+            nv12.write_ppm(&path),
+            Err(Error::ConversionUnsupported { .. })
+        ));
+    }
+
+    #[test]
+    fn test_luma_stats_constant() {
+        let frame = Frame::new(64, 48, 0, "YUYV").unwrap();
+        frame.alloc(None).unwrap();
+        frame.mmap_mut().unwrap().fill(128);
+
+        let stats = frame.luma_stats().unwrap();
+        assert_eq!((stats.min, stats.max, stats.mean), (128, 128, 128.0));
+        assert_eq!(stats.histogram[128], 64 * 48);
+        assert_eq!(stats.histogram.iter().sum::<u32>(), 64 * 48);
+    }
+
+    #[test]
+    fn test_luma_stats_gradient() {
+        // NV12 luma ramps 0..=255 across each row; chroma must be ignored
+        let frame = Frame::new(256, 4, 0, "NV12").unwrap();
+        frame.alloc(None).unwrap();
+        let data = frame.mmap_mut().unwrap();
+        let (luma, chroma) = data.split_at_mut(256 * 4);
+        for row in luma.chunks_mut(256) {
+            for (x, y) in row.iter_mut().enumerate() {
+                *y = x as u8;
+            }
+        }
+        chroma.fill(0xff);
+
+        let stats = frame.luma_stats().unwrap();
+        assert_eq!((stats.min, stats.max), (0, 255));
+        assert_eq!(stats.mean, 127.5);
+        assert!(stats.histogram.iter().all(|&n| n == 4));
+    }
+
+    #[test]
+    fn test_luma_stats_rgb_padded_stride() {
+        // Rows padded to 40 bytes; the padding must not be counted
+        let mut data = vec![0u8; 40 * 4];
+        for row in data.chunks_mut(40) {
+            row[..8 * 3].fill(255);
+            row[8 * 3..].fill(0xAA);
+        }
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open("./temp_luma_stats.bin")
+            .unwrap();
+        file.write_all(&data).unwrap();
+        let frame = Frame::new(8, 4, 40, "RGB3").unwrap();
+        frame.attach(file.as_raw_fd(), data.len(), 0).unwrap();
+
+        let stats = frame.luma_stats().unwrap();
+        // White in BT.601 limited range
+        assert_eq!((stats.min, stats.max), (235, 235));
+        assert_eq!(stats.histogram[235], 8 * 4);
+
+        fs::remove_file("./temp_luma_stats.bin").unwrap();
+    }
+
+    #[test]
+    fn test_luma_stats_grey() {
+        let frame = Frame::new(640, 480, 0, "GREY").unwrap();
+        frame.alloc(None).unwrap();
+        assert_eq!(frame.stride().unwrap(), 640);
+        assert_eq!(frame.size().unwrap(), 640 * 480);
+        frame.mmap_mut().unwrap().fill(200);
+
+        let stats = frame.luma_stats().unwrap();
+        assert_eq!((stats.min, stats.max), (200, 200));
+
+        // Y16 is read from the high byte of each little-endian sample
+        let frame = Frame::new(640, 480, 0, "Y16 ").unwrap();
+        frame.alloc(None).unwrap();
+        assert_eq!(frame.size().unwrap(), 640 * 480 * 2);
+        for sample in frame.mmap_mut().unwrap().chunks_exact_mut(2) {
+            sample.copy_from_slice(&0x40ffu16.to_le_bytes());
+        }
+        assert_eq!(frame.luma_stats().unwrap().max, 0x40);
+    }
+
+    #[test]
+    fn test_luma_stats_unsupported() {
+        let frame = Frame::new(64, 48, 64, "H264").unwrap();
+        assert!(frame.luma_stats().is_err());
+    }
+
     #[test]
     fn bad_attach() {
         let frame = Frame::new(640, 480, 0, "RGB3").unwrap();
@@ -1176,16 +4296,37 @@ mod tests {
     }
 
     #[test]
-    fn test_frame_invalid_fourcc_length_short() {
-        // FourCC must be exactly 4 characters
-        let result = Frame::new(640, 480, 0, "RGB");
-        assert!(result.is_err());
+    fn test_frame_invalid_fourcc_length_short() {
+        // FourCC must be exactly 4 characters
+        let result = Frame::new(640, 480, 0, "RGB");
+        assert!(matches!(result, Err(Error::InvalidFourCC(s)) if s == "RGB"));
+    }
+
+    #[test]
+    fn test_frame_invalid_fourcc_length_long() {
+        let result = Frame::new(640, 480, 0, "RGB32");
+        assert!(matches!(result, Err(Error::InvalidFourCC(_))));
+    }
+
+    #[test]
+    fn test_frame_invalid_fourcc_charset() {
+        let result = Frame::new(640, 480, 0, "RG\n3");
+        assert!(matches!(result, Err(Error::InvalidFourCC(_))));
+        // Four bytes, but not four characters
+        let result = Frame::new(640, 480, 0, "RGé");
+        assert!(matches!(result, Err(Error::InvalidFourCC(_))));
     }
 
     #[test]
-    fn test_frame_invalid_fourcc_length_long() {
-        let result = Frame::new(640, 480, 0, "RGB32");
-        assert!(result.is_err());
+    fn test_frame_unsupported_format() {
+        // Well-formed but unknown: the library cannot derive a stride
+        let result = Frame::new(640, 480, 0, "ZZZZ");
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedFormat(fourcc)) if fourcc == FourCC(*b"ZZZZ")
+        ));
+        // With an explicit stride the library accepts any format
+        assert!(Frame::new(640, 480, 640, "ZZZZ").is_ok());
     }
 
     #[test]
@@ -1227,6 +4368,128 @@ mod tests {
         assert!(dts.is_ok());
     }
 
+    #[test]
+    fn test_frame_capture_time() {
+        let frame = Frame::new(640, 480, 0, "RGB3").unwrap();
+        assert_eq!(frame.capture_time().unwrap(), 0);
+
+        frame.set_capture_time(1_234_567_890).unwrap();
+        assert_eq!(frame.capture_time().unwrap(), 1_234_567_890);
+
+        frame.set_capture_time(0).unwrap();
+        assert_eq!(frame.capture_time().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_frame_used_size() {
+        let frame = Frame::new(640, 480, 0, "RGB3").unwrap();
+        frame.alloc(None).unwrap();
+        let size = frame.size().unwrap() as usize;
+        assert_eq!(frame.used_size().unwrap(), size);
+
+        frame.set_used_size(1234).unwrap();
+        assert_eq!(frame.used_size().unwrap(), 1234);
+        assert_eq!(frame.size().unwrap() as usize, size);
+
+        assert!(frame.set_used_size(size + 1).is_err());
+        assert_eq!(frame.used_size().unwrap(), 1234);
+
+        frame.set_used_size(0).unwrap();
+        assert_eq!(frame.used_size().unwrap(), size);
+    }
+
+    #[test]
+    fn test_frame_alloc_zeroed() {
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame.alloc_zeroed(None).unwrap();
+        let data = frame.map().unwrap();
+        assert_eq!(data.len(), 64 * 48 * 3);
+        assert!(data.iter().all(|&b| b == 0));
+        drop(data);
+
+        // The mapping used for clearing is released, so the frame can be
+        // written and mapped again as usual
+        frame.mmap_mut().unwrap().fill(0xA5);
+        assert!(frame.map().unwrap().iter().all(|&b| b == 0xA5));
+    }
+
+    #[test]
+    fn test_frame_release() {
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        let ptr = frame.as_ptr();
+        #[cfg(debug_assertions)]
+        assert!(owned::contains(ptr));
+        frame.release();
+        #[cfg(debug_assertions)]
+        assert!(!owned::contains(ptr));
+
+        // A wrapped frame released early is not released again on drop
+        let lib = ffi::init().unwrap();
+        let fourcc = u32::from_le_bytes(*b"RGB3");
+        let raw = unsafe { lib.vsl_frame_init(64, 48, 0, fourcc, std::ptr::null_mut(), None) };
+        let wrapped = unsafe { Frame::from_raw(raw) }.unwrap();
+        wrapped.release();
+        #[cfg(debug_assertions)]
+        assert!(!owned::contains(raw));
+    }
+
+    #[test]
+    fn test_frame_adopt() {
+        let lib = ffi::init().unwrap();
+        let fourcc = u32::from_le_bytes(*b"RGB3");
+        let raw = unsafe { lib.vsl_frame_init(64, 48, 0, fourcc, std::ptr::null_mut(), None) };
+        let frame = unsafe { Frame::adopt(raw) }.unwrap();
+        assert_eq!(frame.as_ptr(), raw);
+        assert_eq!(frame.width().unwrap(), 64);
+        #[cfg(debug_assertions)]
+        assert!(owned::contains(raw));
+        drop(frame);
+        #[cfg(debug_assertions)]
+        assert!(!owned::contains(raw));
+
+        let err = unsafe { Frame::adopt(std::ptr::null_mut()) }.unwrap_err();
+        assert!(matches!(err, Error::NullPointer));
+    }
+
+    #[test]
+    fn test_frame_borrow_raw() {
+        let lib = ffi::init().unwrap();
+        let fourcc = u32::from_le_bytes(*b"RGB3");
+        let raw = unsafe { lib.vsl_frame_init(64, 48, 0, fourcc, std::ptr::null_mut(), None) };
+        assert!(!raw.is_null());
+
+        // Dropping the borrow leaves the frame alive and unowned
+        {
+            let borrowed = unsafe { Frame::borrow_raw(raw) }.unwrap();
+            borrowed.alloc(None).unwrap();
+            borrowed.mmap_mut().unwrap().fill(0x5A);
+            assert_eq!(borrowed.height().unwrap(), 48);
+        }
+        #[cfg(debug_assertions)]
+        assert!(!owned::contains(raw));
+
+        {
+            let borrowed = unsafe { Frame::borrow_raw(raw) }.unwrap();
+            assert!(borrowed.map().unwrap().iter().all(|&b| b == 0x5A));
+        }
+
+        // The C owner still holds the only reference
+        unsafe { lib.vsl_frame_release(raw) };
+
+        let err = unsafe { Frame::borrow_raw(std::ptr::null_mut()) }.unwrap_err();
+        assert!(matches!(err, Error::NullPointer));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "already owned by a Frame")]
+    fn test_frame_from_raw_twice() {
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        // The second wrap panics before a second owner exists, so the
+        // frame is still released only once while unwinding.
+        let _alias = unsafe { Frame::from_raw(frame.as_ptr()) };
+    }
+
     #[test]
     fn test_frame_trylock_unlock() {
         let frame = Frame::new(640, 480, 0, "RGB3").unwrap();
@@ -1260,6 +4523,37 @@ mod tests {
         assert!(expires.is_ok());
     }
 
+    #[test]
+    fn test_frame_memory_type() {
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        assert!(frame.memory_type().is_err());
+
+        frame.alloc(None).unwrap();
+        let memory = frame.memory_type().unwrap();
+        if frame.path().unwrap().is_some_and(|p| p.starts_with("/dev")) {
+            assert_eq!(memory, FrameMemory::DmaBuf);
+        } else {
+            assert_eq!(memory, FrameMemory::Shm);
+        }
+        frame.sync(true, 0).unwrap();
+        frame.sync(false, 0).unwrap();
+
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open("./temp_memory_type.bin")
+            .unwrap();
+        file.write_all(&[0u8; 64 * 48 * 3]).unwrap();
+        let attached = Frame::new(64, 48, 0, "RGB3").unwrap();
+        attached.attach(file.as_raw_fd(), 64 * 48 * 3, 0).unwrap();
+        assert_eq!(attached.memory_type().unwrap(), FrameMemory::Attached);
+        attached.sync(true, 0).unwrap();
+
+        fs::remove_file("./temp_memory_type.bin").unwrap();
+    }
+
     #[test]
     fn test_frame_handle_before_alloc() {
         let frame = Frame::new(640, 480, 0, "RGB3").unwrap();
@@ -1296,11 +4590,789 @@ mod tests {
         assert!(paddr.is_ok());
     }
 
+    #[ignore = "test requires DMA heap hardware"]
+    #[test]
+    fn test_frame_alignment_dmabuf() {
+        let frame = Frame::new(1920, 1080, 0, "NV12").unwrap();
+        frame.alloc(None).unwrap();
+
+        let align = frame.alignment().unwrap();
+        assert_eq!(align, crate::fourcc::required_alignment(b"NV12".into()));
+        align
+            .validate(
+                frame.width().unwrap() as u32,
+                frame.height().unwrap() as u32,
+                frame.stride().unwrap() as u32,
+            )
+            .unwrap();
+    }
+
     #[test]
+    #[allow(deprecated)]
     fn test_frame_mmap_before_alloc() {
         let frame = Frame::new(640, 480, 0, "RGB3").unwrap();
         // mmap should fail before allocation
         let result = frame.mmap();
         assert!(result.is_err());
     }
+
+    /// Borrows the descriptor of an allocated frame.
+    fn borrow_handle(frame: &Frame) -> BorrowedFd<'_> {
+        // Safety: the handle stays open for as long as `frame` is alive.
+        unsafe { BorrowedFd::borrow_raw(frame.handle().unwrap()) }
+    }
+
+    #[test]
+    fn test_from_planes_shared_buffer() {
+        let (width, height) = (64usize, 48usize);
+        let buffer = Frame::new(width as u32, height as u32, 0, "NV12").unwrap();
+        buffer.alloc(None).unwrap();
+        {
+            let data = buffer.mmap_mut().unwrap();
+            data[..width * height].fill(0x10);
+            data[width * height..].fill(0x80);
+        }
+
+        let fd = borrow_handle(&buffer);
+        let frame = Frame::from_planes(
+            &[
+                PlaneInput {
+                    fd,
+                    offset: 0,
+                    stride: width,
+                    size: width * height,
+                },
+                PlaneInput {
+                    fd,
+                    offset: width * height,
+                    stride: width,
+                    size: width * height / 2,
+                },
+            ],
+            width as u32,
+            height as u32,
+            "NV12",
+        )
+        .unwrap();
+
+        assert_eq!(frame.plane_count(), 2);
+        assert_eq!(frame.size().unwrap() as usize, width * height * 3 / 2);
+        // Same stride convention as a frame allocated by the library
+        assert_eq!(frame.stride().unwrap(), buffer.stride().unwrap());
+        assert_eq!(frame.stride().unwrap() as usize, width * 3 / 2);
+        assert_eq!(
+            frame.plane(1),
+            Some(PlaneInfo {
+                offset: width * height,
+                stride: width,
+                size: width * height / 2,
+            })
+        );
+
+        let luma = frame.map_plane(0).unwrap();
+        let chroma = frame.map_plane(1).unwrap();
+        assert_eq!(luma.len(), width * height);
+        assert_eq!(chroma.len(), width * height / 2);
+        assert!(luma.iter().all(|&b| b == 0x10));
+        assert!(chroma.iter().all(|&b| b == 0x80));
+    }
+
+    #[test]
+    fn test_plane_mut_gradient() {
+        let (width, height) = (64usize, 48usize);
+        let stride = width + 16;
+        let buffer = Frame::new(stride as u32, height as u32, 0, "NV12").unwrap();
+        buffer.alloc(None).unwrap();
+
+        let fd = borrow_handle(&buffer);
+        let layout = plane_layout(FourCC(*b"NV12"), stride, height).unwrap();
+        let planes: Vec<PlaneInput<'_>> = layout
+            .into_iter()
+            .map(|(offset, stride, size)| PlaneInput {
+                fd,
+                offset,
+                stride,
+                size,
+            })
+            .collect();
+        let mut frame = Frame::from_planes(&planes, width as u32, height as u32, "NV12").unwrap();
+        assert_eq!(frame.stride().unwrap() as usize, stride * 3 / 2);
+
+        let gradient = |index: usize, x: usize, y: usize| (index * 100 + x + y) as u8;
+        for index in 0..frame.plane_count() {
+            let mut plane = frame.plane_mut(index).unwrap();
+            assert_eq!(plane.stride(), stride);
+            for (y, row) in plane.rows_mut().enumerate() {
+                for (x, byte) in row.iter_mut().enumerate() {
+                    *byte = gradient(index, x, y);
+                }
+            }
+        }
+
+        for index in 0..frame.plane_count() {
+            let plane = frame.map_plane(index).unwrap();
+            assert_eq!(plane.len(), frame.plane(index).unwrap().size);
+            for (y, row) in plane.chunks(stride).enumerate() {
+                for (x, &byte) in row.iter().enumerate() {
+                    assert_eq!(
+                        byte,
+                        gradient(index, x, y),
+                        "plane {} at {},{}",
+                        index,
+                        x,
+                        y
+                    );
+                }
+            }
+        }
+        assert!(frame.plane_mut(2).is_err());
+    }
+
+    #[test]
+    fn test_from_planes_separate_buffers() {
+        let (width, height) = (64usize, 48usize);
+        // Shared memory frames are named per thread unless given a path
+        let pid = std::process::id();
+        let luma_buffer = Frame::new(width as u32, height as u32, 0, "RGB3").unwrap();
+        luma_buffer
+            .alloc(Some(Path::new(&format!("/vsl_planes_luma_{}", pid))))
+            .unwrap();
+        luma_buffer.mmap_mut().unwrap().fill(0x10);
+
+        // Chroma starts at an offset that is not page aligned
+        let chroma_buffer = Frame::new(width as u32, height as u32, 0, "RGB3").unwrap();
+        chroma_buffer
+            .alloc(Some(Path::new(&format!("/vsl_planes_chroma_{}", pid))))
+            .unwrap();
+        {
+            let data = chroma_buffer.mmap_mut().unwrap();
+            data[..100].fill(0);
+            data[100..100 + width * height / 2].fill(0x80);
+        }
+
+        let frame = Frame::from_planes(
+            &[
+                PlaneInput {
+                    fd: borrow_handle(&luma_buffer),
+                    offset: 0,
+                    stride: width,
+                    size: width * height,
+                },
+                PlaneInput {
+                    fd: borrow_handle(&chroma_buffer),
+                    offset: 100,
+                    stride: width,
+                    size: width * height / 2,
+                },
+            ],
+            width as u32,
+            height as u32,
+            "NM12",
+        )
+        .unwrap();
+
+        // The frame's own buffer is the first plane
+        assert_eq!(frame.size().unwrap() as usize, width * height);
+        assert!(frame.map_plane(0).unwrap().iter().all(|&b| b == 0x10));
+        let chroma = frame.map_plane(1).unwrap();
+        assert_eq!(chroma.len(), width * height / 2);
+        assert!(chroma.iter().all(|&b| b == 0x80));
+    }
+
+    #[test]
+    fn test_from_planes_invalid() {
+        assert!(Frame::from_planes(&[], 64, 48, "NV12").is_err());
+
+        // Packed formats have no planes
+        let frame = Frame::new(64, 48, 0, "YUYV").unwrap();
+        frame.alloc(None).unwrap();
+        assert_eq!(frame.plane_count(), 0);
+        assert_eq!(frame.plane(0), None);
+        assert!(frame.map_plane(0).is_err());
+    }
+
+    #[test]
+    fn test_derived_planes() {
+        let (width, height) = (64usize, 48usize);
+        let mut frame = Frame::new(width as u32, height as u32, 0, "NV12").unwrap();
+        frame.alloc(None).unwrap();
+        assert_eq!(frame.plane_count(), 2);
+        assert_eq!(
+            frame.plane(1),
+            Some(PlaneInfo {
+                offset: width * height,
+                stride: width,
+                size: width * height / 2,
+            })
+        );
+        assert_eq!(frame.plane(2), None);
+
+        for (index, value) in [(0, 0x10), (1, 0x80)] {
+            for row in frame.plane_mut(index).unwrap().rows_mut() {
+                row.fill(value);
+            }
+        }
+        let data = frame.map().unwrap();
+        assert!(data[..width * height].iter().all(|&b| b == 0x10));
+        assert!(data[width * height..].iter().all(|&b| b == 0x80));
+    }
+
+    #[test]
+    fn test_plane_layout() {
+        // 1280 wide NV12 with a 1344 byte padded stride
+        assert_eq!(
+            plane_layout(FourCC(*b"NV12"), 1344, 720),
+            Some(vec![(0, 1344, 1344 * 720), (1344 * 720, 1344, 1344 * 360)])
+        );
+        assert_eq!(
+            plane_layout(FourCC(*b"I420"), 640, 480),
+            Some(vec![
+                (0, 640, 640 * 480),
+                (640 * 480, 320, 320 * 240),
+                (640 * 480 + 320 * 240, 320, 320 * 240),
+            ])
+        );
+        assert_eq!(plane_layout(FourCC(*b"YUYV"), 1280, 720), None);
+        assert_eq!(plane_layout(FourCC(*b"NV12"), 0, 720), None);
+    }
+
+    #[test]
+    fn test_rotate_90() {
+        // 2x3 source, pixel n is [10n, 10n + 1, 10n + 2]:
+        //   P0 P1
+        //   P2 P3
+        //   P4 P5
+        let pid = std::process::id();
+        let source = Frame::new(2, 3, 0, "RGB3").unwrap();
+        source
+            .alloc(Some(Path::new(&format!("/vsl_rotate_source_{}", pid))))
+            .unwrap();
+        {
+            let stride = source.stride().unwrap() as usize;
+            let data = source.mmap_mut().unwrap();
+            for n in 0..6u8 {
+                let at = (n as usize / 2) * stride + (n as usize % 2) * 3;
+                data[at..at + 3].copy_from_slice(&[10 * n, 10 * n + 1, 10 * n + 2]);
+            }
+        }
+
+        let target = Frame::new(3, 2, 0, "RGB3").unwrap();
+        target
+            .alloc(Some(Path::new(&format!("/vsl_rotate_target_{}", pid))))
+            .unwrap();
+        source.rotate(&target, Rotation::Rotate90).unwrap();
+
+        // Rotated clockwise:
+        //   P4 P2 P0
+        //   P5 P3 P1
+        let pixel = |n: u8| [10 * n, 10 * n + 1, 10 * n + 2];
+        let expected: Vec<u8> = [4, 2, 0, 5, 3, 1].into_iter().flat_map(pixel).collect();
+        assert_eq!(target.to_vec_packed().unwrap(), expected);
+
+        // Geometry must be swapped for 90 degrees
+        let same = Frame::new(2, 3, 0, "RGB3").unwrap();
+        same.alloc(Some(Path::new(&format!("/vsl_rotate_same_{}", pid))))
+            .unwrap();
+        assert!(source.rotate(&same, Rotation::Rotate90).is_err());
+        assert!(source.rotate(&target, Rotation::Rotate180).is_err());
+    }
+
+    #[test]
+    fn test_frame_copy_exact() {
+        let pid = std::process::id();
+        let alloc = |name: &str| {
+            let frame = Frame::new(8, 4, 0, "RGB3").unwrap();
+            frame
+                .alloc(Some(Path::new(&format!(
+                    "/vsl_copy_exact_{}_{}",
+                    name, pid
+                ))))
+                .unwrap();
+            frame
+        };
+
+        let source = alloc("src");
+        for (i, byte) in source.mmap_mut().unwrap().iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+
+        // Same stride copies the whole buffer
+        let same = alloc("same");
+        assert_eq!(source.copy_exact(&same).unwrap(), 8 * 4 * 3);
+        assert_eq!(&*same.map().unwrap(), &*source.map().unwrap());
+
+        // A padded target receives each row at its own stride
+        let padded = Frame::new(8, 4, 32, "RGB3").unwrap();
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open("./temp_copy_exact.bin")
+            .unwrap();
+        file.set_len(32 * 4).unwrap();
+        padded.attach(file.as_raw_fd(), 32 * 4, 0).unwrap();
+        assert_eq!(source.copy_exact(&padded).unwrap(), 8 * 4 * 3);
+        assert_eq!(padded.to_vec_packed().unwrap(), source.to_vec().unwrap());
+
+        // copy_to takes the same path for identical formats, where the
+        // conversion pipeline is unavailable in software-only builds
+        let start = std::time::Instant::now();
+        assert_eq!(source.copy_to(&same, None).unwrap(), 8 * 4 * 3);
+        println!("same-format copy_to took {:?}", start.elapsed());
+
+        let other = Frame::new(8, 4, 0, "RGBA").unwrap();
+        other.alloc(None).unwrap();
+        let err = source.copy_exact(&other).unwrap_err();
+        assert!(matches!(err, Error::Io(e) if e.kind() == io::ErrorKind::InvalidInput));
+
+        drop(padded);
+        fs::remove_file("./temp_copy_exact.bin").unwrap();
+    }
+
+    #[test]
+    fn test_frame_copy_exact_padded_nv12() {
+        // 4x2 NV12 with 8-byte luma rows: Y at 0, UV at 16
+        let padded = Frame::new(4, 2, 12, "NV12").unwrap();
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open("./temp_copy_exact_nv12.bin")
+            .unwrap();
+        file.set_len(24).unwrap();
+        padded.attach(file.as_raw_fd(), 24, 0).unwrap();
+        {
+            let map = padded.mmap_mut().unwrap();
+            map.fill(0xEE);
+            map[0..4].copy_from_slice(&[10, 20, 30, 40]);
+            map[8..12].copy_from_slice(&[50, 60, 70, 80]);
+            map[16..20].copy_from_slice(&[100, 200, 110, 210]);
+        }
+
+        let tight = Frame::new(4, 2, 0, "NV12").unwrap();
+        tight.alloc(None).unwrap();
+        assert_eq!(padded.copy_exact(&tight).unwrap(), 12);
+        assert_eq!(
+            &*tight.map().unwrap(),
+            &[10, 20, 30, 40, 50, 60, 70, 80, 100, 200, 110, 210]
+        );
+
+        // Copying back leaves the padding untouched
+        padded.mmap_mut().unwrap().fill(0xEE);
+        assert_eq!(tight.copy_exact(&padded).unwrap(), 12);
+        let map = padded.map().unwrap();
+        assert_eq!(&map[0..8], &[10, 20, 30, 40, 0xEE, 0xEE, 0xEE, 0xEE]);
+        assert_eq!(&map[8..16], &[50, 60, 70, 80, 0xEE, 0xEE, 0xEE, 0xEE]);
+        assert_eq!(&map[16..24], &[100, 200, 110, 210, 0xEE, 0xEE, 0xEE, 0xEE]);
+        drop(map);
+
+        drop(padded);
+        fs::remove_file("./temp_copy_exact_nv12.bin").unwrap();
+    }
+
+    #[test]
+    fn test_frame_to_format_software() {
+        // 4x2 YUYV: white, black, two red pixels, then a green row
+        let pid = std::process::id();
+        let source = Frame::new(4, 2, 0, "YUYV").unwrap();
+        source
+            .alloc(Some(Path::new(&format!("/vsl_to_format_{}", pid))))
+            .unwrap();
+        source.mmap_mut().unwrap().copy_from_slice(&[
+            235, 128, 16, 128, 81, 90, 81, 240, //
+            145, 54, 145, 34, 145, 54, 145, 34,
+        ]);
+
+        let rgb = source.to_format(FourCC(*b"RGB3")).unwrap();
+        assert_eq!((rgb.width().unwrap(), rgb.height().unwrap()), (4, 2));
+        assert_eq!(FourCC::from(rgb.fourcc().unwrap()), FourCC(*b"RGB3"));
+        let pixels = rgb.to_vec_packed().unwrap();
+        let expected: [[u8; 3]; 8] = [
+            [255, 255, 255],
+            [0, 0, 0],
+            [255, 0, 0],
+            [255, 0, 0],
+            [0, 255, 0],
+            [0, 255, 0],
+            [0, 255, 0],
+            [0, 255, 0],
+        ];
+        for (px, want) in pixels.chunks_exact(3).zip(expected) {
+            for (got, want) in px.iter().zip(want) {
+                assert!(got.abs_diff(want) <= 2, "{:?} != {:?}", px, want);
+            }
+        }
+
+        // YUYV to NV12 goes through `convert`, averaging the chroma of the
+        // two rows of each 2x2 block
+        let nv12 = source.to_format(FourCC(*b"NV12")).unwrap();
+        assert_eq!(
+            &nv12.map().unwrap()[..12],
+            &[235, 16, 81, 81, 145, 145, 145, 145, 91, 81, 72, 137]
+        );
+
+        // Back to YUYV through RGB stays within rounding of the source
+        let yuyv = rgb.to_format(FourCC(*b"YUYV")).unwrap();
+        for (got, want) in yuyv.map().unwrap().iter().zip(source.map().unwrap().iter()) {
+            assert!(got.abs_diff(*want) <= 3, "{} != {}", got, want);
+        }
+
+        let err = source.to_format(FourCC(*b"MJPG")).unwrap_err();
+        assert!(matches!(err, Error::Io(_) | Error::UnsupportedFormat(_)));
+    }
+
+    #[test]
+    fn test_frame_to_format_grey() {
+        let source = Frame::new(4, 2, 0, "GREY").unwrap();
+        source.alloc(None).unwrap();
+        source
+            .mmap_mut()
+            .unwrap()
+            .copy_from_slice(&[0, 64, 128, 255, 10, 20, 30, 40]);
+
+        let rgb = source.to_format(FourCC(*b"RGB3")).unwrap();
+        let pixels = rgb.to_vec_packed().unwrap();
+        for (px, y) in pixels.chunks_exact(3).zip(source.map().unwrap().iter()) {
+            assert_eq!(px, [*y; 3]);
+        }
+
+        // Gray RGB converts back exactly, and colors through their luma
+        let grey = rgb.to_format(FourCC(*b"GREY")).unwrap();
+        assert_eq!(grey.to_vec_packed().unwrap(), source.to_vec().unwrap());
+        let y16 = rgb.to_format(FourCC(*b"Y16 ")).unwrap();
+        assert_eq!(&y16.map().unwrap()[..4], &[0, 0, 64, 64]);
+
+        let red = Frame::new(4, 2, 0, "RGB3").unwrap();
+        red.alloc(None).unwrap();
+        for px in red.mmap_mut().unwrap().chunks_exact_mut(3) {
+            px.copy_from_slice(&[255, 0, 0]);
+        }
+        let grey = red.to_format(FourCC(*b"GREY")).unwrap();
+        assert!(grey.map().unwrap().iter().all(|&y| y == 76));
+    }
+
+    #[test]
+    fn test_frame_copy_to_software() {
+        let pid = std::process::id();
+        let source = Frame::new(4, 2, 0, "NV12").unwrap();
+        source
+            .alloc(Some(Path::new(&format!("/vsl_copy_yuv_{}", pid))))
+            .unwrap();
+        source
+            .mmap_mut()
+            .unwrap()
+            .copy_from_slice(&[10, 20, 30, 40, 50, 60, 70, 80, 100, 200, 110, 210]);
+
+        let yuyv = Frame::new(4, 2, 0, "YUYV").unwrap();
+        yuyv.alloc(None).unwrap();
+        assert_eq!(source.copy_to(&yuyv, None).unwrap(), 16);
+        assert_eq!(
+            &yuyv.map().unwrap()[..16],
+            &[10, 100, 20, 200, 30, 110, 40, 210, 50, 100, 60, 200, 70, 110, 80, 210]
+        );
+
+        let nv12 = Frame::new(4, 2, 0, "NV12").unwrap();
+        nv12.alloc(None).unwrap();
+        assert_eq!(yuyv.copy_to(&nv12, None).unwrap(), 12);
+        assert_eq!(&nv12.map().unwrap()[..12], &source.map().unwrap()[..12]);
+
+        // Other pairs the software conversions handle fall back as well
+        let rgb = Frame::new(4, 2, 0, "RGB3").unwrap();
+        rgb.alloc(None).unwrap();
+        assert_eq!(source.copy_to(&rgb, None).unwrap(), 24);
+        assert_eq!(
+            &rgb.map().unwrap()[..3],
+            &convert::yuv_to_rgb([10, 100, 200])
+        );
+
+        let grey = Frame::new(4, 2, 0, "GREY").unwrap();
+        grey.alloc(None).unwrap();
+        grey.mmap_mut().unwrap().fill(200);
+        assert_eq!(grey.copy_to(&rgb, None).unwrap(), 24);
+        assert!(rgb.map().unwrap().iter().all(|&b| b == 200));
+        assert_eq!(rgb.copy_to(&grey, None).unwrap(), 8);
+        assert!(grey.map().unwrap().iter().all(|&y| y == 200));
+
+        // Formats outside the software conversions still need the hardware
+        let i420 = Frame::new(4, 2, 0, "I420").unwrap();
+        i420.alloc(None).unwrap();
+        assert!(source.copy_to(&i420, None).is_err());
+    }
+
+    #[test]
+    fn test_frame_copy_to_yuv_software_padded() {
+        // 4x2 NV12 with 8-byte rows and the chroma plane 8 bytes past the
+        // end of the luma plane
+        let (width, height, stride) = (4usize, 2usize, 8usize);
+        let backing = Frame::new(64, 1, 0, "GREY").unwrap();
+        backing.alloc(None).unwrap();
+        backing.mmap_mut().unwrap().fill(0xEE);
+        let fd = borrow_handle(&backing);
+        let planes = [
+            PlaneInput {
+                fd,
+                offset: 0,
+                stride,
+                size: stride * height,
+            },
+            PlaneInput {
+                fd,
+                offset: 24,
+                stride,
+                size: stride,
+            },
+        ];
+        let mut nv12 = Frame::from_planes(&planes, width as u32, height as u32, "NV12").unwrap();
+        let rows: [&[[u8; 4]]; 2] = [
+            &[[10, 20, 30, 40], [50, 60, 70, 80]],
+            &[[100, 200, 110, 210]],
+        ];
+        for (index, rows) in rows.iter().enumerate() {
+            let mut plane = nv12.plane_mut(index).unwrap();
+            for (row, values) in plane.rows_mut().zip(rows.iter()) {
+                row[..width].copy_from_slice(values);
+            }
+        }
+
+        let yuyv = Frame::new(4, 2, 0, "YUYV").unwrap();
+        yuyv.alloc(None).unwrap();
+        assert_eq!(nv12.copy_to(&yuyv, None).unwrap(), 16);
+        let expected = [
+            10, 100, 20, 200, 30, 110, 40, 210, 50, 100, 60, 200, 70, 110, 80, 210,
+        ];
+        assert_eq!(&yuyv.map().unwrap()[..16], &expected);
+
+        // Back into the padded layout, leaving the padding untouched
+        backing.mmap_mut().unwrap().fill(0xEE);
+        assert_eq!(yuyv.copy_to(&nv12, None).unwrap(), 12);
+        for (index, rows) in rows.iter().enumerate() {
+            let plane = nv12.map_plane(index).unwrap();
+            for (line, values) in plane.chunks(stride).zip(rows.iter()) {
+                assert_eq!(&line[..width], values);
+                assert!(line[width..].iter().all(|&b| b == 0xEE));
+            }
+        }
+        assert!(backing.map().unwrap()[16..24].iter().all(|&b| b == 0xEE));
+
+        // Without plane descriptors the luma stride comes from the frame
+        // stride, with the chroma plane right after the luma rows
+        backing.mmap_mut().unwrap().fill(0xEE);
+        let plain = Frame::new(4, 2, (stride * 3 / 2) as u32, "NV12").unwrap();
+        plain.attach(backing.handle().unwrap(), 24, 0).unwrap();
+        assert_eq!(yuyv.copy_to(&plain, None).unwrap(), 12);
+        let data = backing.map().unwrap();
+        assert_eq!(&data[..width], &rows[0][0]);
+        assert_eq!(&data[stride..stride + width], &rows[0][1]);
+        assert_eq!(&data[2 * stride..2 * stride + width], &rows[1][0]);
+    }
+
+    #[test]
+    fn test_frame_copy_to_conversion_unsupported() {
+        let source = Frame::new(4, 2, 0, "NV12").unwrap();
+        source.alloc(None).unwrap();
+        // The library cannot size a made-up format, so borrow a buffer
+        let backing = Frame::new(4, 2, 0, "RGB3").unwrap();
+        backing.alloc(None).unwrap();
+        let target = Frame::new(4, 2, 8, "ZZZZ").unwrap();
+        target.attach(backing.handle().unwrap(), 16, 0).unwrap();
+
+        let err = source.copy_to(&target, None).unwrap_err();
+        match err {
+            Error::ConversionUnsupported { from, to } => {
+                assert_eq!(from, FourCC(*b"NV12"));
+                assert_eq!(to, FourCC(*b"ZZZZ"));
+            }
+            e => panic!("expected ConversionUnsupported, got {:?}", e),
+        }
+        assert_eq!(
+            format!("{}", err),
+            "Conversion from NV12 to ZZZZ not supported"
+        );
+
+        // A convertible pair that fails for another reason, here scaling
+        // without the hardware, is an I/O error rather than unsupported
+        let scaled = Frame::new(8, 4, 0, "RGB3").unwrap();
+        scaled.alloc(None).unwrap();
+        let err = source.copy_to(&scaled, None).unwrap_err();
+        assert!(matches!(err, Error::Io(_)), "got {:?}", err);
+    }
+
+    #[test]
+    #[ignore = "test requires G2D hardware"]
+    fn test_frame_to_format_g2d() {
+        let source = Frame::new(1920, 1080, 0, "YUYV").unwrap();
+        source.alloc(None).unwrap();
+        source.mmap_mut().unwrap().fill(128);
+
+        let target = Frame::new(1920, 1080, 0, "NV12").unwrap();
+        target.alloc(None).unwrap();
+        source
+            .copy_to(&target, None)
+            .expect("G2D copy should be available");
+
+        let nv12 = source.to_format(FourCC(*b"NV12")).unwrap();
+        assert_eq!(FourCC::from(nv12.fourcc().unwrap()), FourCC(*b"NV12"));
+        assert_eq!(
+            (nv12.width().unwrap(), nv12.height().unwrap()),
+            (1920, 1080)
+        );
+        assert!(nv12.map().unwrap()[..1920 * 1080 * 3 / 2]
+            .iter()
+            .all(|&b| b.abs_diff(128) <= 2));
+    }
+
+    #[test]
+    fn test_letterbox_to() {
+        // 32x18 (16:9) source of a single color into a 16x16 target
+        let pid = std::process::id();
+        let source = Frame::new(32, 18, 0, "RGB3").unwrap();
+        source
+            .alloc(Some(Path::new(&format!("/vsl_letterbox_source_{}", pid))))
+            .unwrap();
+        {
+            let data = source.mmap_mut().unwrap();
+            for px in data.chunks_exact_mut(3) {
+                px.copy_from_slice(&[10, 20, 30]);
+            }
+        }
+
+        let target = Frame::new(16, 16, 0, "RGB3").unwrap();
+        target
+            .alloc(Some(Path::new(&format!("/vsl_letterbox_target_{}", pid))))
+            .unwrap();
+        let info = source.letterbox_to(&target, [114, 114, 114]).unwrap();
+
+        // Scaled to 16x9 and centered vertically with 3 rows above
+        assert_eq!(info.scale, 0.5);
+        assert_eq!((info.offset_x, info.offset_y), (0, 3));
+        assert_eq!((info.width, info.height), (16, 9));
+        assert_eq!(info.to_source(8.0, 3.0), (16.0, 0.0));
+        assert_eq!(info.to_source(16.0, 12.0), (32.0, 18.0));
+
+        let pixels = target.to_vec_packed().unwrap();
+        for (y, row) in pixels.chunks_exact(16 * 3).enumerate() {
+            let expected = if (3..12).contains(&y) {
+                [10, 20, 30]
+            } else {
+                [114, 114, 114]
+            };
+            for px in row.chunks_exact(3) {
+                assert_eq!(px, expected, "row {}", y);
+            }
+        }
+
+        let rgba = Frame::new(16, 16, 0, "RGBA").unwrap();
+        assert!(source.letterbox_to(&rgba, [0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_is_keyframe() {
+        // Encoded frames hold the bitstream, here attached from a file
+        let write = |name: &str, nals: &[u8]| {
+            let path = format!("./temp_keyframe_{}.bin", name);
+            let mut file = File::options()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&path)
+                .unwrap();
+            file.write_all(nals).unwrap();
+            let frame = Frame::new(64, 1, 64, "H264").unwrap();
+            frame.attach(file.as_raw_fd(), nals.len(), 0).unwrap();
+            fs::remove_file(&path).unwrap();
+            frame
+        };
+
+        // SPS, PPS and an IDR slice
+        let idr = write(
+            "idr",
+            &[
+                0, 0, 0, 1, 0x67, 0x42, 0, 0, 0, 1, 0x68, 0xce, 0, 0, 0, 1, 0x65, 0x88,
+            ],
+        );
+        assert!(idr.is_keyframe(FourCC(*b"H264")).unwrap());
+
+        // A P slice
+        let p_slice = write("p", &[0, 0, 0, 1, 0x41, 0x9a, 0x24]);
+        assert!(!p_slice.is_keyframe(FourCC(*b"H264")).unwrap());
+
+        assert!(idr.is_keyframe(FourCC(*b"NV12")).is_err());
+    }
+
+    /// Collects the `duration_ns` recorded on spans with a given name.
+    #[cfg(feature = "tracing")]
+    struct SpanDurations {
+        name: &'static str,
+        ids: std::sync::Mutex<Vec<u64>>,
+        next_id: std::sync::atomic::AtomicU64,
+        durations: std::sync::Arc<std::sync::Mutex<Vec<u64>>>,
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for SpanDurations {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let id = self
+                .next_id
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+                + 1;
+            if span.metadata().name() == self.name {
+                self.ids.lock().unwrap().push(id);
+            }
+            tracing::span::Id::from_u64(id)
+        }
+
+        fn record(&self, span: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+            struct Visitor<'a>(&'a std::sync::Mutex<Vec<u64>>);
+            impl tracing::field::Visit for Visitor<'_> {
+                fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+                    if field.name() == "duration_ns" {
+                        self.0.lock().unwrap().push(value);
+                    }
+                }
+                fn record_debug(&mut self, _: &tracing::field::Field, _: &dyn std::fmt::Debug) {}
+            }
+
+            if self.ids.lock().unwrap().contains(&span.into_u64()) {
+                values.record(&mut Visitor(&self.durations));
+            }
+        }
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+        fn event(&self, _: &tracing::Event<'_>) {}
+        fn enter(&self, _: &tracing::span::Id) {}
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn test_copy_to_tracing_span() {
+        let source = Frame::new(64, 48, 0, "RGB3").unwrap();
+        source.alloc(None).unwrap();
+        let target = Frame::new(64, 48, 0, "RGB3").unwrap();
+        target.alloc(None).unwrap();
+
+        let durations = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let subscriber = SpanDurations {
+            name: "copy_to",
+            ids: std::sync::Mutex::new(Vec::new()),
+            next_id: std::sync::atomic::AtomicU64::new(0),
+            durations: durations.clone(),
+        };
+        // The copy fails without a G2D or PXP backend, the span is emitted
+        // either way.
+        tracing::subscriber::with_default(subscriber, || {
+            let _ = source.copy_to(&target, None);
+        });
+
+        let durations = durations.lock().unwrap();
+        assert_eq!(durations.len(), 1, "expected one copy_to span");
+        assert!(durations[0] > 0);
+    }
 }
@@ -0,0 +1,292 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! H.264 sequence parameter set parsing.
+//!
+//! [`SpsInfo::parse`] reads the fields of an SPS (ITU-T H.264 7.3.2.1.1)
+//! needed to size buffers before the first frame is decoded: profile, level,
+//! chroma format, and the coded and cropped picture size. Fields after the
+//! cropping window, such as the VUI, are not read.
+
+use crate::{
+    nal::{unescape, BitReader},
+    Error,
+};
+use std::io;
+
+/// H.264 sequence parameter set NAL unit type.
+const NAL_SPS: u8 = 7;
+
+/// Chroma sampling of an H.264 stream (`chroma_format_idc`, Table 6-1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChromaFormat {
+    /// Luma only (`chroma_format_idc` 0)
+    Monochrome,
+    /// 4:2:0 (`chroma_format_idc` 1), the default when the SPS omits it
+    Yuv420,
+    /// 4:2:2 (`chroma_format_idc` 2)
+    Yuv422,
+    /// 4:4:4 (`chroma_format_idc` 3)
+    Yuv444,
+}
+
+/// Stream parameters read from an H.264 SPS by [`SpsInfo::parse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpsInfo {
+    /// `profile_idc`, e.g. 66 for Baseline, 77 for Main, 100 for High
+    pub profile_idc: u8,
+    /// `level_idc`, ten times the level number (40 for level 4.0)
+    pub level_idc: u8,
+    /// Chroma sampling of the stream
+    pub chroma_format: ChromaFormat,
+    /// Display width in pixels, after the cropping window is applied
+    pub width: u32,
+    /// Display height in pixels, after the cropping window is applied
+    pub height: u32,
+    /// Coded width in pixels, a whole number of macroblocks
+    pub coded_width: u32,
+    /// Coded height in pixels, a whole number of macroblocks
+    pub coded_height: u32,
+    /// Pixels cropped from the left edge of the coded picture
+    pub crop_left: u32,
+    /// Pixels cropped from the top edge of the coded picture
+    pub crop_top: u32,
+}
+
+impl SpsInfo {
+    /// Parses an SPS NAL unit, including its one-byte header but without a
+    /// start code, as stored in an `avcC` box or returned by
+    /// [`parse_annex_b`](crate::nal::parse_annex_b).
+    ///
+    /// Emulation prevention bytes are removed before parsing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::ErrorKind::InvalidData`] error if the NAL unit is not
+    /// an SPS or is truncated or malformed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use videostream::h264::{ChromaFormat, SpsInfo};
+    ///
+    /// // SPS of a 1920x1080 stream from openh264
+    /// let sps = [
+    ///     0x67, 0x42, 0xc0, 0x28, 0x8c, 0x68, 0x07, 0x80, 0x22, 0x7e, 0x58, 0x07,
+    ///     0x84, 0x42, 0x35,
+    /// ];
+    /// let info = SpsInfo::parse(&sps)?;
+    /// assert_eq!((info.width, info.height), (1920, 1080));
+    /// assert_eq!((info.coded_width, info.coded_height), (1920, 1088));
+    /// assert_eq!((info.profile_idc, info.level_idc), (66, 40));
+    /// assert_eq!(info.chroma_format, ChromaFormat::Yuv420);
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn parse(sps: &[u8]) -> Result<SpsInfo, Error> {
+        if sps.first().map(|header| header & 0x1f) != Some(NAL_SPS) {
+            return Err(invalid("not an H.264 SPS NAL unit"));
+        }
+        let rbsp = unescape(sps);
+        Self::from_rbsp(&mut BitReader::new(&rbsp)).ok_or_else(|| invalid("malformed H.264 SPS"))
+    }
+
+    /// Parses an unescaped SPS up to `frame_cropping`, starting at the NAL
+    /// header.
+    fn from_rbsp(bits: &mut BitReader) -> Option<SpsInfo> {
+        bits.skip(8)?; // NAL header
+        let profile_idc = bits.bits(8)? as u8;
+        bits.skip(8)?; // constraint flags
+        let level_idc = bits.bits(8)? as u8;
+        bits.ue()?; // seq_parameter_set_id
+
+        let mut chroma_format_idc = 1;
+        let mut separate_colour_plane = false;
+        if matches!(
+            profile_idc,
+            100 | 110 | 122 | 244 | 44 | 83 | 86 | 118 | 128 | 138 | 139 | 134 | 135
+        ) {
+            chroma_format_idc = bits.ue()?;
+            if chroma_format_idc == 3 {
+                separate_colour_plane = bits.bit()? == 1;
+            }
+            bits.ue()?; // bit_depth_luma_minus8
+            bits.ue()?; // bit_depth_chroma_minus8
+            bits.skip(1)?; // qpprime_y_zero_transform_bypass_flag
+            if bits.bit()? == 1 {
+                let lists = if chroma_format_idc == 3 { 12 } else { 8 };
+                for i in 0..lists {
+                    if bits.bit()? == 1 {
+                        skip_scaling_list(bits, if i < 6 { 16 } else { 64 })?;
+                    }
+                }
+            }
+        }
+        let chroma_format = match chroma_format_idc {
+            0 => ChromaFormat::Monochrome,
+            1 => ChromaFormat::Yuv420,
+            2 => ChromaFormat::Yuv422,
+            3 => ChromaFormat::Yuv444,
+            _ => return None,
+        };
+
+        bits.ue()?; // log2_max_frame_num_minus4
+        match bits.ue()? {
+            0 => {
+                bits.ue()?; // log2_max_pic_order_cnt_lsb_minus4
+            }
+            1 => {
+                bits.skip(1)?; // delta_pic_order_always_zero_flag
+                bits.se()?; // offset_for_non_ref_pic
+                bits.se()?; // offset_for_top_to_bottom_field
+                for _ in 0..bits.ue()? {
+                    bits.se()?;
+                }
+            }
+            _ => {}
+        }
+        bits.ue()?; // max_num_ref_frames
+        bits.skip(1)?; // gaps_in_frame_num_value_allowed_flag
+
+        let width_mbs = bits.ue()?.checked_add(1)?;
+        let height_map_units = bits.ue()?.checked_add(1)?;
+        let frame_mbs_only = bits.bit()?;
+        if frame_mbs_only == 0 {
+            bits.skip(1)?; // mb_adaptive_frame_field_flag
+        }
+        bits.skip(1)?; // direct_8x8_inference_flag
+
+        let coded_width = width_mbs.checked_mul(16)?;
+        let coded_height = height_map_units.checked_mul(16 * (2 - frame_mbs_only))?;
+        let (mut left, mut right, mut top, mut bottom) = (0, 0, 0, 0);
+        if bits.bit()? == 1 {
+            // Crop units from Table 6-1, where ChromaArrayType is 0 for
+            // monochrome or separately coded colour planes
+            let (unit_x, unit_y) = match (chroma_format, separate_colour_plane) {
+                (ChromaFormat::Monochrome, _) | (ChromaFormat::Yuv444, true) => {
+                    (1, 2 - frame_mbs_only)
+                }
+                (ChromaFormat::Yuv420, _) => (2, 2 * (2 - frame_mbs_only)),
+                (ChromaFormat::Yuv422, _) => (2, 2 - frame_mbs_only),
+                (ChromaFormat::Yuv444, false) => (1, 2 - frame_mbs_only),
+            };
+            left = bits.ue()?.checked_mul(unit_x)?;
+            right = bits.ue()?.checked_mul(unit_x)?;
+            top = bits.ue()?.checked_mul(unit_y)?;
+            bottom = bits.ue()?.checked_mul(unit_y)?;
+        }
+
+        let width = coded_width.checked_sub(left.checked_add(right)?)?;
+        let height = coded_height.checked_sub(top.checked_add(bottom)?)?;
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Some(SpsInfo {
+            profile_idc,
+            level_idc,
+            chroma_format,
+            width,
+            height,
+            coded_width,
+            coded_height,
+            crop_left: left,
+            crop_top: top,
+        })
+    }
+}
+
+/// Skips an H.264 `scaling_list()` of `size` coefficients.
+fn skip_scaling_list(bits: &mut BitReader, size: usize) -> Option<()> {
+    let mut last = 8;
+    let mut next = 8;
+    for _ in 0..size {
+        if next != 0 {
+            next = (last + bits.se()? + 256) % 256;
+        }
+        if next != 0 {
+            last = next;
+        }
+    }
+    Some(())
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SPS of a 1920x1080 Baseline stream from openh264.
+    const SPS_1080P_BASELINE: [u8; 15] = [
+        0x67, 0x42, 0xc0, 0x28, 0x8c, 0x68, 0x07, 0x80, 0x22, 0x7e, 0x58, 0x07, 0x84, 0x42, 0x35,
+    ];
+
+    #[test]
+    fn test_parse_baseline() {
+        let info = SpsInfo::parse(&SPS_1080P_BASELINE).unwrap();
+        assert_eq!(
+            info,
+            SpsInfo {
+                profile_idc: 66,
+                level_idc: 40,
+                chroma_format: ChromaFormat::Yuv420,
+                width: 1920,
+                height: 1080,
+                coded_width: 1920,
+                coded_height: 1088,
+                crop_left: 0,
+                crop_top: 0,
+            }
+        );
+
+        // 642x362 pads to 656x368, cropped on the right and bottom
+        let sps = [
+            0x67, 0x42, 0xc0, 0x16, 0x8c, 0x68, 0x0a, 0x42, 0xf6, 0x22, 0x48, 0x07, 0x84, 0x42,
+            0x35,
+        ];
+        let info = SpsInfo::parse(&sps).unwrap();
+        assert_eq!((info.width, info.height), (642, 362));
+        assert_eq!((info.coded_width, info.coded_height), (656, 368));
+        assert_eq!(info.level_idc, 22);
+    }
+
+    #[test]
+    fn test_parse_high() {
+        // x264 High profile 1280x720, with emulation prevention bytes in
+        // the VUI
+        let sps = [
+            0x67, 0x64, 0x00, 0x1f, 0xac, 0xd9, 0x40, 0x50, 0x05, 0xbb, 0x01, 0x10, 0x00, 0x00,
+            0x03, 0x00, 0x10, 0x00, 0x00, 0x03, 0x03, 0xc0, 0xf1, 0x83, 0x19, 0x60,
+        ];
+        let info = SpsInfo::parse(&sps).unwrap();
+        assert_eq!((info.profile_idc, info.level_idc), (100, 31));
+        assert_eq!(info.chroma_format, ChromaFormat::Yuv420);
+        assert_eq!((info.width, info.height), (1280, 720));
+        assert_eq!((info.coded_width, info.coded_height), (1280, 720));
+
+        // x264 High profile 1920x1080
+        let sps = [
+            0x67, 0x64, 0x00, 0x28, 0xac, 0xd9, 0x40, 0x78, 0x02, 0x27, 0xe5, 0xc0, 0x44, 0x00,
+            0x00, 0x03, 0x00, 0x04, 0x00, 0x00, 0x03, 0x00, 0xf0, 0x3c, 0x60, 0xc6, 0x58,
+        ];
+        let info = SpsInfo::parse(&sps).unwrap();
+        assert_eq!((info.profile_idc, info.level_idc), (100, 40));
+        assert_eq!((info.width, info.height), (1920, 1080));
+        assert_eq!(info.coded_height, 1088);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let err = |data: &[u8]| match SpsInfo::parse(data) {
+            Err(Error::Io(err)) => err.kind(),
+            other => panic!("expected InvalidData, got {:?}", other),
+        };
+        assert_eq!(err(&[]), io::ErrorKind::InvalidData);
+        // PPS
+        assert_eq!(err(&[0x68, 0xce, 0x3c, 0x80]), io::ErrorKind::InvalidData);
+        // Start code not stripped
+        assert_eq!(err(&[0, 0, 0, 1, 0x67, 0x42]), io::ErrorKind::InvalidData);
+        assert_eq!(err(&SPS_1080P_BASELINE[..8]), io::ErrorKind::InvalidData);
+    }
+}
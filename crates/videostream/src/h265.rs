@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! H.265 / HEVC sequence parameter set parsing.
+//!
+//! [`SpsInfo::parse`] reads an SPS (ITU-T H.265 7.3.2.2.1) through
+//! `profile_tier_level()` and the conformance window, which is enough to
+//! size buffers or write container headers before the first frame is
+//! decoded. Fields after the conformance window are not read.
+
+use crate::{
+    h264::ChromaFormat,
+    nal::{unescape, BitReader},
+    Error,
+};
+use std::io;
+
+/// H.265 sequence parameter set NAL unit type.
+const NAL_SPS: u8 = 33;
+
+/// Stream parameters read from an H.265 SPS by [`SpsInfo::parse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpsInfo {
+    /// `general_profile_idc`, e.g. 1 for Main, 2 for Main 10
+    pub profile_idc: u8,
+    /// `general_tier_flag`, `true` for the High tier
+    pub high_tier: bool,
+    /// `general_level_idc`, thirty times the level number (120 for level 4)
+    pub level_idc: u8,
+    /// Chroma sampling of the stream
+    pub chroma_format: ChromaFormat,
+    /// Display width in pixels, after the conformance window is applied
+    pub width: u32,
+    /// Display height in pixels, after the conformance window is applied
+    pub height: u32,
+    /// Coded width in pixels (`pic_width_in_luma_samples`)
+    pub coded_width: u32,
+    /// Coded height in pixels (`pic_height_in_luma_samples`)
+    pub coded_height: u32,
+    /// Pixels cropped from the left edge of the coded picture
+    pub crop_left: u32,
+    /// Pixels cropped from the top edge of the coded picture
+    pub crop_top: u32,
+}
+
+impl SpsInfo {
+    /// Parses an SPS NAL unit, including its two-byte header but without a
+    /// start code, as stored in an `hvcC` box or returned by
+    /// [`parse_annex_b`](crate::nal::parse_annex_b).
+    ///
+    /// Emulation prevention bytes are removed before parsing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::ErrorKind::InvalidData`] error if the NAL unit is not
+    /// an SPS or is truncated or malformed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use videostream::h265::SpsInfo;
+    ///
+    /// // SPS of a 1920x1080 Main profile stream from x265
+    /// let sps = [
+    ///     0x42, 0x01, 0x01, 0x01, 0x60, 0x00, 0x00, 0x03, 0x00, 0x90, 0x00, 0x00,
+    ///     0x03, 0x00, 0x00, 0x03, 0x00, 0x78, 0xa0, 0x03, 0xc0, 0x80, 0x10, 0xe5,
+    ///     0x96, 0x56, 0x69, 0x24, 0xca, 0xe0, 0x10, 0x00, 0x00, 0x03, 0x00, 0x10,
+    ///     0x00, 0x00, 0x03, 0x01, 0xe0, 0x80,
+    /// ];
+    /// let info = SpsInfo::parse(&sps)?;
+    /// assert_eq!((info.width, info.height), (1920, 1080));
+    /// assert_eq!((info.profile_idc, info.level_idc), (1, 120));
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn parse(sps: &[u8]) -> Result<SpsInfo, Error> {
+        if sps.len() < 2 || (sps[0] >> 1) & 0x3f != NAL_SPS {
+            return Err(invalid("not an H.265 SPS NAL unit"));
+        }
+        let rbsp = unescape(sps);
+        Self::from_rbsp(&mut BitReader::new(&rbsp)).ok_or_else(|| invalid("malformed H.265 SPS"))
+    }
+
+    /// Parses an unescaped SPS up to `conformance_window`, starting at the
+    /// NAL header.
+    fn from_rbsp(bits: &mut BitReader) -> Option<SpsInfo> {
+        bits.skip(16)?; // NAL header
+        bits.skip(4)?; // sps_video_parameter_set_id
+        let max_sub_layers_minus1 = bits.bits(3)? as usize;
+        bits.skip(1)?; // sps_temporal_id_nesting_flag
+
+        // profile_tier_level(): general profile and level, then the optional
+        // profile and level of each sub-layer
+        bits.skip(2)?; // general_profile_space
+        let high_tier = bits.bit()? == 1;
+        let profile_idc = bits.bits(5)? as u8;
+        bits.skip(32 + 48)?; // compatibility and constraint flags
+        let level_idc = bits.bits(8)? as u8;
+        let mut sub_layers = Vec::with_capacity(max_sub_layers_minus1);
+        for _ in 0..max_sub_layers_minus1 {
+            sub_layers.push((bits.bit()?, bits.bit()?));
+        }
+        if max_sub_layers_minus1 > 0 {
+            bits.skip(2 * (8 - max_sub_layers_minus1))?;
+        }
+        for (profile_present, level_present) in sub_layers {
+            bits.skip(88 * profile_present as usize + 8 * level_present as usize)?;
+        }
+
+        bits.ue()?; // sps_seq_parameter_set_id
+        let chroma_format = match bits.ue()? {
+            0 => ChromaFormat::Monochrome,
+            1 => ChromaFormat::Yuv420,
+            2 => ChromaFormat::Yuv422,
+            3 => {
+                bits.skip(1)?; // separate_colour_plane_flag
+                ChromaFormat::Yuv444
+            }
+            _ => return None,
+        };
+        let coded_width = bits.ue()?;
+        let coded_height = bits.ue()?;
+
+        let (mut left, mut right, mut top, mut bottom) = (0, 0, 0, 0);
+        if bits.bit()? == 1 {
+            // SubWidthC and SubHeightC from Table 6-1; separately coded
+            // colour planes crop in luma samples like 4:4:4
+            let (unit_x, unit_y) = match chroma_format {
+                ChromaFormat::Yuv420 => (2, 2),
+                ChromaFormat::Yuv422 => (2, 1),
+                ChromaFormat::Monochrome | ChromaFormat::Yuv444 => (1, 1),
+            };
+            left = bits.ue()?.checked_mul(unit_x)?;
+            right = bits.ue()?.checked_mul(unit_x)?;
+            top = bits.ue()?.checked_mul(unit_y)?;
+            bottom = bits.ue()?.checked_mul(unit_y)?;
+        }
+
+        let width = coded_width.checked_sub(left.checked_add(right)?)?;
+        let height = coded_height.checked_sub(top.checked_add(bottom)?)?;
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Some(SpsInfo {
+            profile_idc,
+            high_tier,
+            level_idc,
+            chroma_format,
+            width,
+            height,
+            coded_width,
+            coded_height,
+            crop_left: left,
+            crop_top: top,
+        })
+    }
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_x265() {
+        // 1280x720 Main, level 3.1
+        let sps = [
+            0x42, 0x01, 0x01, 0x01, 0x60, 0x00, 0x00, 0x03, 0x00, 0xb0, 0x00, 0x00, 0x03, 0x00,
+            0x00, 0x03, 0x00, 0x5d, 0xa0, 0x02, 0x80, 0x80, 0x2d, 0x16, 0x59, 0x59, 0xa4, 0x93,
+            0x2b, 0x9a, 0x02, 0x00, 0x00, 0x03, 0x00, 0x02, 0x00, 0x00, 0x03, 0x00, 0x3c, 0x10,
+        ];
+        assert_eq!(
+            SpsInfo::parse(&sps).unwrap(),
+            SpsInfo {
+                profile_idc: 1,
+                high_tier: false,
+                level_idc: 93,
+                chroma_format: ChromaFormat::Yuv420,
+                width: 1280,
+                height: 720,
+                coded_width: 1280,
+                coded_height: 720,
+                crop_left: 0,
+                crop_top: 0,
+            }
+        );
+
+        // 3840x2160 Main 10, level 5.1
+        let sps = [
+            0x42, 0x01, 0x01, 0x02, 0x20, 0x00, 0x00, 0x03, 0x00, 0x90, 0x00, 0x00, 0x03, 0x00,
+            0x00, 0x03, 0x00, 0x99, 0xa0, 0x01, 0xe0, 0x20, 0x02, 0x1c, 0x59, 0x65, 0x66, 0x92,
+            0x4c, 0xaf, 0x01, 0x6a, 0x12, 0x20, 0x13, 0x6c, 0x08, 0x00, 0x00, 0x03, 0x00, 0x08,
+            0x00, 0x00, 0x03, 0x01, 0xe0, 0x40,
+        ];
+        let info = SpsInfo::parse(&sps).unwrap();
+        assert_eq!((info.profile_idc, info.level_idc), (2, 153));
+        assert_eq!((info.width, info.height), (3840, 2160));
+    }
+
+    #[test]
+    fn test_parse_conformance_window() {
+        // 642x362 coded as 648x368, cropped by 3 chroma samples on the right
+        // and bottom
+        let sps = [
+            0x42, 0x01, 0x01, 0x01, 0x60, 0x00, 0x00, 0x03, 0x00, 0x90, 0x00, 0x00, 0x03, 0x00,
+            0x00, 0x03, 0x00, 0x5d, 0xa0, 0x05, 0x12, 0x01, 0x71, 0xc9, 0x26, 0x58,
+        ];
+        let info = SpsInfo::parse(&sps).unwrap();
+        assert_eq!((info.coded_width, info.coded_height), (648, 368));
+        assert_eq!((info.width, info.height), (642, 362));
+        assert_eq!((info.crop_left, info.crop_top), (0, 0));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let err = |data: &[u8]| match SpsInfo::parse(data) {
+            Err(Error::Io(err)) => err.kind(),
+            other => panic!("expected InvalidData, got {:?}", other),
+        };
+        assert_eq!(err(&[0x42]), io::ErrorKind::InvalidData);
+        // VPS
+        assert_eq!(err(&[0x40, 0x01, 0x0c, 0x01]), io::ErrorKind::InvalidData);
+        // H.264 SPS
+        assert_eq!(err(&[0x67, 0x42, 0xc0, 0x28]), io::ErrorKind::InvalidData);
+        // Truncated inside profile_tier_level()
+        assert_eq!(
+            err(&[0x42, 0x01, 0x01, 0x01, 0x60, 0x00]),
+            io::ErrorKind::InvalidData
+        );
+    }
+}
@@ -3,10 +3,13 @@
 
 use crate::Error;
 use std::{
-    ffi::{CStr, CString},
+    cell::Cell,
+    ffi::{c_int, c_void, CStr, CString},
     io,
     os::unix::prelude::OsStrExt,
+    panic::{catch_unwind, AssertUnwindSafe},
     path::{Path, PathBuf},
+    time::Duration,
 };
 use videostream_sys as ffi;
 
@@ -30,6 +33,154 @@ use videostream_sys as ffi;
 /// ```
 pub struct Host {
     ptr: *mut ffi::VSLHost,
+    handler: Option<Box<CommandHandler>>,
+    encoder_drops: Cell<u64>,
+    skip_policy: Option<Box<dyn Fn() -> bool>>,
+    policy_skips: Cell<u64>,
+    /// Set when [`Host::skip_frame`] admitted the next frame to post
+    admitted: Cell<bool>,
+}
+
+/// Frames discarded by a [`Host`], by cause, returned by
+/// [`Host::drop_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DropStats {
+    /// Frames that expired before any client locked them
+    pub expired: u64,
+    /// Frame events not delivered because a client's socket buffer was
+    /// full, counted once per client
+    pub client_slow: u64,
+    /// Frames the pipeline skipped because the encoder was backlogged,
+    /// reported with [`Host::record_encoder_drop`]
+    pub encoder_backlog: u64,
+    /// Frames skipped by the policy set with [`Host::with_skip_policy`]
+    pub skip_policy: u64,
+}
+
+impl DropStats {
+    /// Returns the sum of all counters.
+    pub fn total(&self) -> u64 {
+        self.expired + self.client_slow + self.encoder_backlog + self.skip_policy
+    }
+}
+
+/// Work done by one [`Host::service_timeout`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ServiceResult {
+    /// Sockets with pending activity, new connections or client messages,
+    /// that were serviced
+    pub clients_serviced: usize,
+    /// Frames expired before any client locked them, as counted by
+    /// [`DropStats::expired`]
+    pub frames_expired: u64,
+}
+
+/// Frame post with optional metadata, created by [`Host::post_builder`].
+///
+/// Duration, presentation and decode timestamps default to unknown (-1) and
+/// the keyframe flag to unset. Nothing is posted until [`post`](Self::post).
+#[must_use = "the frame is only posted by calling post()"]
+pub struct PostBuilder<'a> {
+    host: &'a Host,
+    frame: crate::frame::Frame,
+    expires: i64,
+    duration: i64,
+    pts: i64,
+    dts: i64,
+    keyframe: bool,
+}
+
+impl PostBuilder<'_> {
+    /// Sets the frame duration in nanoseconds.
+    pub fn duration(mut self, duration: i64) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the presentation timestamp in nanoseconds.
+    pub fn pts(mut self, pts: i64) -> Self {
+        self.pts = pts;
+        self
+    }
+
+    /// Sets the decode timestamp in nanoseconds.
+    pub fn dts(mut self, dts: i64) -> Self {
+        self.dts = dts;
+        self
+    }
+
+    /// Flags the frame as a keyframe, read by clients through
+    /// [`Frame::is_keyframe_flag`](crate::frame::Frame::is_keyframe_flag).
+    ///
+    /// Use this when posting frames encoded outside this library, so clients
+    /// joining mid-stream can find a decode entry point.
+    pub fn keyframe(mut self, keyframe: bool) -> Self {
+        self.keyframe = keyframe;
+        self
+    }
+
+    /// Posts the frame as [`Host::post`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if posting fails, and
+    /// [`Error::SymbolNotFound`] if the keyframe flag is set and the library
+    /// predates 2.6.
+    pub fn post(self) -> Result<(), Error> {
+        if self.keyframe {
+            self.frame.set_keyframe_flag(true)?;
+        }
+        self.host
+            .post(self.frame, self.expires, self.duration, self.pts, self.dts)
+    }
+}
+
+type CommandHandler = Box<dyn FnMut(&[u8]) -> Vec<u8>>;
+
+/// Calls the boxed [`CommandHandler`] registered as `userptr`, copying its
+/// reply into the library's buffer.
+unsafe extern "C" fn command_trampoline(
+    _host: *mut ffi::VSLHost,
+    request: *const c_void,
+    request_size: usize,
+    reply: *mut c_void,
+    reply_capacity: usize,
+    userptr: *mut c_void,
+) -> c_int {
+    // SAFETY: userptr is the boxed handler owned by the Host, which outlives
+    // the library's use of it, and request holds request_size bytes.
+    let handler = unsafe { &mut *(userptr as *mut CommandHandler) };
+    let request = if request_size == 0 {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(request as *const u8, request_size) }
+    };
+
+    let Ok(response) = catch_unwind(AssertUnwindSafe(|| handler(request))) else {
+        log::error!("command handler panicked");
+        return -1;
+    };
+
+    let len = response.len().min(reply_capacity);
+    if len < response.len() {
+        log::warn!(
+            "command reply of {} bytes truncated to {}",
+            response.len(),
+            reply_capacity
+        );
+    }
+    // SAFETY: reply has room for reply_capacity bytes and len is bounded by it.
+    unsafe { std::ptr::copy_nonoverlapping(response.as_ptr(), reply as *mut u8, len) };
+    len as c_int
+}
+
+/// Converts a socket path for the library, which spells the NUL byte that
+/// starts an abstract socket address as `@`.
+pub(crate) fn socket_path_c(path: &[u8]) -> Result<CString, Error> {
+    match path.strip_prefix(b"\0") {
+        Some(name) => Ok(CString::new([b"@", name].concat())?),
+        None => Ok(CString::new(path)?),
+    }
 }
 
 impl std::fmt::Debug for Host {
@@ -42,17 +193,177 @@ impl std::fmt::Debug for Host {
 }
 
 impl Host {
+    /// Longest socket path accepted, in bytes: the size of
+    /// `sockaddr_un::sun_path` on Linux.
+    pub const MAX_PATH_LEN: usize = 108;
+
     /// Creates a new Host and creates a socket at the specified path on which
     /// it will listen for client connections.
+    ///
+    /// A stale socket left at `path` by a host that exited without cleaning
+    /// up is replaced.
+    ///
+    /// A `path` starting with `@` (or a NUL byte) names a socket in the Linux
+    /// abstract namespace instead of a file. Abstract sockets leave no stale
+    /// file and need no directory shared between containers, but are only
+    /// reachable within the same network namespace, and are Linux only.
+    /// [`path()`](Self::path) reports them with the `@` prefix.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SocketPathTooLong`] if `path` exceeds
+    /// [`Host::MAX_PATH_LEN`] bytes, [`Error::SocketDirectoryNotFound`] if
+    /// the directory of an absolute `path` does not exist,
+    /// [`Error::AddressInUse`] if another host is listening on `path`, and
+    /// [`Error::Io`] for other socket failures.
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
-        let path_str_c = CString::new(path.as_ref().as_os_str().as_bytes())?;
+        let path = path.as_ref();
+        if path.as_os_str().len() > Self::MAX_PATH_LEN {
+            return Err(Error::SocketPathTooLong(path.to_path_buf()));
+        }
+        let dir = path
+            .parent()
+            .filter(|dir| path.is_absolute() && !dir.as_os_str().is_empty());
+        if let Some(dir) = dir.filter(|dir| !dir.is_dir()) {
+            return Err(Error::SocketDirectoryNotFound(dir.to_path_buf()));
+        }
+
+        let path_str_c = socket_path_c(path.as_os_str().as_bytes())?;
         let ptr = vsl!(vsl_host_init(path_str_c.as_ptr()));
         if ptr.is_null() {
             let err = io::Error::last_os_error();
-            return Err(err.into());
+            return Err(match err.raw_os_error() {
+                Some(libc::EADDRINUSE) => Error::AddressInUse(path.to_path_buf()),
+                Some(libc::ENAMETOOLONG) => Error::SocketPathTooLong(path.to_path_buf()),
+                _ => crate::call_error("vsl_host_init", err).into(),
+            });
         }
 
-        Ok(Host { ptr })
+        Ok(Host {
+            ptr,
+            handler: None,
+            encoder_drops: Cell::new(0),
+            skip_policy: None,
+            policy_skips: Cell::new(0),
+            admitted: Cell::new(false),
+        })
+    }
+
+    /// Sets a policy consulted before each frame is posted, skipping the
+    /// frame when it returns `true`.
+    ///
+    /// Lets a pipeline shed load, for example while the CPU is throttled,
+    /// without tearing down the host. A skipped frame is released instead of
+    /// posted, [`post`](Self::post) still returns `Ok(())`, and the skip is
+    /// counted in [`DropStats::skip_policy`]. Pipelines that encode before
+    /// posting call [`skip_frame`](Self::skip_frame) first so skipped frames
+    /// are not encoded either.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::host::Host;
+    ///
+    /// let host = Host::new("/tmp/video.sock")?.with_skip_policy(|| {
+    ///     std::fs::read_to_string("/sys/class/thermal/thermal_zone0/temp")
+    ///         .ok()
+    ///         .and_then(|temp| temp.trim().parse::<u32>().ok())
+    ///         .is_some_and(|millidegrees| millidegrees > 85_000)
+    /// });
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn with_skip_policy<F>(mut self, policy: F) -> Self
+    where
+        F: Fn() -> bool + 'static,
+    {
+        self.skip_policy = Some(Box::new(policy));
+        self
+    }
+
+    /// Consults the skip policy for the next frame, returning `true` and
+    /// counting the skip if the frame should be dropped.
+    ///
+    /// Call once per frame before encoding it. When the frame is admitted,
+    /// the next [`post`](Self::post) does not consult the policy again.
+    /// Always returns `false` without a policy.
+    pub fn skip_frame(&self) -> bool {
+        let Some(policy) = &self.skip_policy else {
+            return false;
+        };
+        if policy() {
+            self.policy_skips.set(self.policy_skips.get() + 1);
+            self.admitted.set(false);
+            return true;
+        }
+        self.admitted.set(true);
+        false
+    }
+
+    /// Sets the permission bits of the socket file, such as `0o660`.
+    ///
+    /// Clients need write permission on the socket to connect, so a host
+    /// shared with clients running as another user, typically in another
+    /// container, must grant it to their group or to everyone. Anyone able
+    /// to connect receives every frame and can send commands to the
+    /// [`set_command_handler`](Self::set_command_handler) handler; prefer a
+    /// group shared with the clients through [`with_group`](Self::with_group)
+    /// and `0o660` over a world-writable `0o666`. The mode is applied after
+    /// the socket is bound, so it holds the permissions of the process umask
+    /// until this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] for
+    /// abstract sockets, which have no file, or if changing the mode fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::host::Host;
+    ///
+    /// let host = Host::new("/run/videostream/camera.sock")?
+    ///     .with_group(44)?
+    ///     .with_permissions(0o660)?;
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn with_permissions(self, mode: u32) -> Result<Self, Error> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = self.socket_file()?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+        Ok(self)
+    }
+
+    /// Sets the group owning the socket file.
+    ///
+    /// Pair with [`with_permissions`](Self::with_permissions) to share the
+    /// socket with clients of that group only. The process must be a member
+    /// of `gid` or have `CAP_CHOWN`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] for
+    /// abstract sockets, which have no file, or if changing the group fails.
+    pub fn with_group(self, gid: u32) -> Result<Self, Error> {
+        let path = self.socket_file()?;
+        let path_c = CString::new(path.as_os_str().as_bytes())?;
+        // An owner of -1 leaves the owner unchanged
+        if unsafe { libc::chown(path_c.as_ptr(), libc::uid_t::MAX, gid) } != 0 {
+            return Err(crate::last_os_error("chown").into());
+        }
+        Ok(self)
+    }
+
+    /// Returns the path of the socket file, rejecting abstract sockets.
+    fn socket_file(&self) -> Result<PathBuf, Error> {
+        let path = self.path()?;
+        if path.as_os_str().as_bytes().starts_with(b"@") {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("abstract socket {} has no file", path.display()),
+            )));
+        }
+        Ok(path)
     }
 
     pub fn path(&self) -> Result<PathBuf, Error> {
@@ -68,7 +379,9 @@ impl Host {
     /// Polls the host's socket connections for activity.
     ///
     /// Waits for socket activity (new connections or client messages) using poll().
-    /// Should be called in a loop before [`Host::process`]. The `wait` parameter
+    /// Must be followed by [`Host::process`] whether or not there was activity,
+    /// otherwise posted frames never expire; [`Host::service_timeout`] does both.
+    /// The `wait` parameter
     /// controls timeout behavior:
     /// - `> 0`: Poll waits up to this duration in milliseconds
     /// - `= 0`: Returns immediately
@@ -94,19 +407,20 @@ impl Host {
     /// let host = Host::new("/tmp/video.sock")?;
     /// loop {
     ///     match host.poll(1000) {
-    ///         Ok(n) if n > 0 => {
-    ///             host.process()?;
-    ///         }
-    ///         Ok(_) => {} // timeout
+    ///         Ok(_) => host.process()?,
     ///         Err(e) => eprintln!("Poll error: {}", e),
     ///     }
     /// }
     /// # Ok::<(), videostream::Error>(())
     /// ```
+    #[deprecated(
+        since = "2.6.0",
+        note = "use `Host::service_timeout()`, which always expires frames after polling"
+    )]
     pub fn poll(&self, wait: i64) -> Result<i32, Error> {
         let ret = vsl!(vsl_host_poll(self.ptr, wait));
         if ret < 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_host_poll");
             return Err(err.into());
         }
         Ok(ret)
@@ -115,8 +429,9 @@ impl Host {
     /// Processes host tasks: expires old frames and services one client connection.
     ///
     /// First expires frames past their lifetime, then services the first available
-    /// connection (accepting new clients or processing client messages). Should be
-    /// called in a loop, typically after [`Host::poll`] indicates activity.
+    /// connection (accepting new clients or processing client messages). Must be
+    /// called after every [`Host::poll`], not only when it reports activity,
+    /// or expired frames hold their DMA buffers; [`Host::service_timeout`] does both.
     ///
     /// # Returns
     ///
@@ -134,21 +449,85 @@ impl Host {
     ///
     /// let host = Host::new("/tmp/video.sock")?;
     /// loop {
-    ///     if host.poll(1000)? > 0 {
-    ///         host.process()?;
-    ///     }
+    ///     host.poll(1000)?;
+    ///     host.process()?;
     /// }
     /// # Ok::<(), videostream::Error>(())
     /// ```
+    #[deprecated(
+        since = "2.6.0",
+        note = "use `Host::service_timeout()`, which always expires frames after polling"
+    )]
     pub fn process(&self) -> Result<(), Error> {
         let ret = vsl!(vsl_host_process(self.ptr));
         if ret < 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_host_process");
             return Err(err.into());
         }
         Ok(())
     }
 
+    /// Waits up to `timeout` for client activity, then services the host.
+    ///
+    /// Services every pending connection and client message and always
+    /// expires frames past their lifetime, even when no client is active.
+    /// Call it in the publishing loop at least as often as frames expire so
+    /// their DMA buffers are released; skipping expiry while no client is
+    /// connected eventually exhausts DMA memory.
+    ///
+    /// An interrupted wait is treated as no activity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if polling the sockets fails or the host lock
+    /// cannot be taken.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use videostream::host::Host;
+    ///
+    /// let host = Host::new("/tmp/video.sock")?;
+    /// loop {
+    ///     let result = host.service_timeout(Duration::from_millis(100))?;
+    ///     if result.frames_expired > 0 {
+    ///         println!("{} frames expired", result.frames_expired);
+    ///     }
+    /// }
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn service_timeout(&self, timeout: Duration) -> Result<ServiceResult, Error> {
+        // Expiry is only counted when the library reports drop statistics
+        let expired = || self.drop_stats().map(|stats| stats.expired).ok();
+        let before = expired();
+
+        let wait = i64::try_from(timeout.as_millis()).unwrap_or(i64::MAX);
+        let ready = vsl!(vsl_host_poll(self.ptr, wait));
+        let clients_serviced = if ready < 0 {
+            let err = crate::last_os_error("vsl_host_poll");
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err.into());
+            }
+            0
+        } else {
+            ready as usize
+        };
+
+        if vsl!(vsl_host_process(self.ptr)) < 0 {
+            return Err(crate::last_os_error("vsl_host_process").into());
+        }
+
+        let frames_expired = match (before, expired()) {
+            (Some(before), Some(after)) => after.saturating_sub(before),
+            _ => 0,
+        };
+        Ok(ServiceResult {
+            clients_serviced,
+            frames_expired,
+        })
+    }
+
     /// Services a single client socket.
     ///
     /// Processes messages from a specific client socket. Does not accept new
@@ -178,21 +557,29 @@ impl Host {
     ///
     /// // Service each client socket individually
     /// for sock in &sockets[1..] { // Skip listening socket
-    ///     if let Err(e) = host.service(*sock) {
+    ///     if let Err(e) = host.service_socket(*sock) {
     ///         eprintln!("Error servicing socket {}: {}", sock, e);
     ///     }
     /// }
     /// # Ok::<(), videostream::Error>(())
     /// ```
-    pub fn service(&self, sock: i32) -> Result<(), Error> {
+    pub fn service_socket(&self, sock: i32) -> Result<(), Error> {
         let ret = vsl!(vsl_host_service(self.ptr, sock));
         if ret < 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_host_service");
             return Err(err.into());
         }
         Ok(())
     }
 
+    /// Services a single client socket.
+    ///
+    /// Renamed [`Host::service_socket`]; kept so existing callers build.
+    #[deprecated(since = "2.6.0", note = "renamed to `Host::service_socket()`")]
+    pub fn service(&self, sock: i32) -> Result<(), Error> {
+        self.service_socket(sock)
+    }
+
     /// Requests a copy of the sockets managed by the host.
     ///
     /// Returns socket file descriptors for the host's listening socket and all
@@ -246,7 +633,7 @@ impl Host {
         ));
 
         if ret < 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_host_sockets");
             return Err(err.into());
         }
 
@@ -298,21 +685,155 @@ impl Host {
         pts: i64,
         dts: i64,
     ) -> Result<(), Error> {
+        // A frame admitted by skip_frame() is not checked twice
+        let admitted = self.admitted.replace(false);
+        if !admitted && self.skip_frame() {
+            return Ok(());
+        }
+        self.admitted.set(false);
+
+        #[cfg(feature = "tracing")]
+        let span = crate::trace::timed_span!("post");
+
         let frame_ptr = frame.as_ptr();
 
         let ret = vsl!(vsl_host_post(
             self.ptr, frame_ptr, expires, duration, pts, dts
         ));
         if ret < 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_host_post");
             return Err(err.into());
         }
 
+        // The host assigns the serial while posting
+        #[cfg(feature = "tracing")]
+        span.record_frame(&frame);
+
         // Only transfer ownership after successful posting
-        std::mem::forget(frame);
+        frame.into_raw();
         Ok(())
     }
 
+    /// Starts posting `frame`, expiring at `expires`, with optional metadata
+    /// set through the returned [`PostBuilder`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{fs::File, os::fd::AsRawFd};
+    /// use videostream::{host::Host, frame::Frame, timestamp};
+    ///
+    /// let host = Host::new("/tmp/encoded.sock")?;
+    /// // An access unit produced by an external encoder
+    /// let file = File::open("idr.h264")?;
+    /// let frame = Frame::new(1920, 1080, 1920, "H264")?;
+    /// frame.attach(file.as_raw_fd(), file.metadata()?.len() as usize, 0)?;
+    ///
+    /// host.post_builder(frame, timestamp()? + 1_000_000_000)
+    ///     .pts(0)
+    ///     .keyframe(true)
+    ///     .post()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn post_builder(&self, frame: crate::frame::Frame, expires: i64) -> PostBuilder<'_> {
+        PostBuilder {
+            host: self,
+            frame,
+            expires,
+            duration: -1,
+            pts: -1,
+            dts: -1,
+            keyframe: false,
+        }
+    }
+
+    /// Posts a frame to all connected clients without taking ownership.
+    ///
+    /// The host posts a new frame that shares `frame`'s buffer through a
+    /// duplicated file descriptor, so the buffer stays alive until both the
+    /// caller has dropped `frame` and the posted frame has expired and been
+    /// released by every client. `frame` keeps its own metadata: the serial
+    /// and timestamps assigned by the host belong to the posted frame and are
+    /// not visible through `frame`. Its capture time, keyframe flag, source
+    /// id and plane layout are carried over.
+    ///
+    /// Clients see the same memory as `frame`, with no copy and no locking
+    /// between the caller and the clients. Writing to `frame` before the post
+    /// expires may cause visual tearing on clients; reading it is safe. The
+    /// frame must be backed by a buffer (allocated or attached).
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - Frame to post (borrowed, remains usable afterwards)
+    /// * `expires` - Expiration time in nanoseconds (absolute, from [`crate::timestamp`])
+    /// * `duration` - Frame duration in nanoseconds (-1 if unknown)
+    /// * `pts` - Presentation timestamp in nanoseconds (-1 if unknown)
+    /// * `dts` - Decode timestamp in nanoseconds (-1 if unknown)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if `frame` has no buffer or posting fails, and
+    /// [`Error::SymbolNotFound`] if the loaded `libvideostream.so` predates
+    /// 2.6 and does not export `vsl_frame_offset`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::{host::Host, frame::Frame, timestamp};
+    ///
+    /// let host = Host::new("/tmp/video.sock")?;
+    /// let frame = Frame::new(1920, 1080, 0, "RGB3")?;
+    /// frame.alloc(None)?;
+    ///
+    /// host.post_ref(&frame, timestamp()? + 1_000_000_000, -1, -1, -1)?;
+    /// // The frame is still ours, e.g. to save it locally
+    /// let pixels = frame.to_vec()?;
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn post_ref(
+        &self,
+        frame: &crate::frame::Frame,
+        expires: i64,
+        duration: i64,
+        pts: i64,
+        dts: i64,
+    ) -> Result<(), Error> {
+        let fourcc = crate::fourcc::FourCC::from(frame.fourcc()?);
+        let shared = crate::frame::Frame::new(
+            frame.width()?.try_into()?,
+            frame.height()?.try_into()?,
+            frame.stride()?.try_into()?,
+            &fourcc.to_string(),
+        )?;
+        shared.attach(frame.handle()?, frame.size()?.try_into()?, frame.offset()?)?;
+        match frame.capture_time() {
+            Ok(capture_time) => shared.set_capture_time(capture_time)?,
+            Err(Error::SymbolNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+        match frame.is_keyframe_flag() {
+            Ok(keyframe) => shared.set_keyframe_flag(keyframe)?,
+            Err(Error::SymbolNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+        match frame.source_id() {
+            Ok(Some(id)) => shared.set_source_id(id)?,
+            Ok(None) | Err(Error::SymbolNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+        match shared.set_used_size(frame.used_size()?) {
+            Ok(()) | Err(Error::SymbolNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+        match frame.plane_descriptors() {
+            Ok(planes) => shared.set_plane_descriptors(&planes)?,
+            Err(Error::SymbolNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        self.post(shared, expires, duration, pts, dts)
+    }
+
     /// Drops a frame from the host.
     ///
     /// Removes the host association of the frame and returns ownership to the
@@ -348,11 +869,111 @@ impl Host {
     pub fn drop_frame(&self, frame: &crate::frame::Frame) -> Result<(), Error> {
         let ret = vsl!(vsl_host_drop(self.ptr, frame.as_ptr()));
         if ret < 0 {
-            let err = io::Error::last_os_error();
+            let err = crate::last_os_error("vsl_host_drop");
             return Err(err.into());
         }
         Ok(())
     }
+
+    /// Returns the frames discarded since the host was created, by cause.
+    ///
+    /// Expired and slow client drops are counted by the host as it posts
+    /// and processes frames. The host cannot see the encoder, so pipelines
+    /// that skip frames while the encoder catches up report them with
+    /// [`record_encoder_drop`](Self::record_encoder_drop).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6, or
+    /// [`Error::Io`] if the host lock cannot be taken.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::host::Host;
+    ///
+    /// let host = Host::new("/tmp/video.sock")?;
+    /// let drops = host.drop_stats()?;
+    /// if drops.client_slow > drops.expired {
+    ///     println!("clients cannot keep up: {:?}", drops);
+    /// }
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn drop_stats(&self) -> Result<DropStats, Error> {
+        let lib = ffi::init()?;
+        let drop_stats = lib
+            .vsl_host_drop_stats
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_host_drop_stats"))?;
+
+        let mut stats = ffi::VSLHostDropStats {
+            expired: 0,
+            client_slow: 0,
+        };
+        if unsafe { drop_stats(self.ptr, &mut stats) } < 0 {
+            return Err(crate::last_os_error("vsl_host_drop_stats").into());
+        }
+        Ok(DropStats {
+            expired: stats.expired,
+            client_slow: stats.client_slow,
+            encoder_backlog: self.encoder_drops.get(),
+            skip_policy: self.policy_skips.get(),
+        })
+    }
+
+    /// Counts a frame skipped because the encoder was backlogged, reported
+    /// in [`DropStats::encoder_backlog`].
+    pub fn record_encoder_drop(&self) {
+        self.encoder_drops.set(self.encoder_drops.get() + 1);
+    }
+
+    /// Registers the handler for commands sent with
+    /// [`Client::send_command`](crate::client::Client::send_command).
+    ///
+    /// The handler is called from [`Host::process`] and [`Host::service_timeout`]
+    /// with the request bytes and returns the reply, which is truncated to
+    /// 4096 bytes. A panicking handler is reported to the client as a failed
+    /// command. Replaces any previously registered handler.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SymbolNotFound`] if the library predates 2.6, or
+    /// [`Error::Io`] if the host lock cannot be taken.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::host::Host;
+    ///
+    /// let mut host = Host::new("/tmp/video.sock")?;
+    /// host.set_command_handler(|req| match req {
+    ///     b"status" => b"streaming".to_vec(),
+    ///     _ => b"unknown".to_vec(),
+    /// })?;
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn set_command_handler<F>(&mut self, handler: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8]) -> Vec<u8> + 'static,
+    {
+        let lib = ffi::init()?;
+        let set_handler = lib
+            .vsl_host_set_command_handler
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_host_set_command_handler"))?;
+
+        let mut handler: Box<CommandHandler> = Box::new(Box::new(handler));
+        let userptr = &mut *handler as *mut CommandHandler as *mut c_void;
+        let ret = unsafe { set_handler(self.ptr, Some(command_trampoline), userptr) };
+        if ret < 0 {
+            let err = crate::last_os_error("vsl_host_set_command_handler");
+            return Err(err.into());
+        }
+
+        // The previous handler is no longer reachable from the library.
+        self.handler = Some(handler);
+        Ok(())
+    }
 }
 
 impl Drop for Host {
@@ -393,13 +1014,148 @@ mod tests {
         assert!(!path.is_dir());
         assert!(!path.is_symlink());
 
-        // FIXME: currently the library will unlink old sockets, this should be
-        // corrected along with adding proper cleanup and error handling when a
-        // socket is already present.
-        //
-        // Creating a second host at the same path should raise an error.
-        // let host2 = Host::new(&path);
-        // assert!(host2.is_err());
+        // A second host cannot take over the path of a live one
+        match Host::new(&path) {
+            Err(Error::AddressInUse(in_use)) => assert_eq!(in_use, path),
+            other => panic!("expected AddressInUse, got {:?}", other),
+        }
+        assert_eq!(path, host.path().unwrap());
+    }
+
+    #[test]
+    fn test_host_abstract_socket() {
+        use crate::client::{Client, Reconnect};
+
+        let name = format!("vsl_abstract_{}", std::process::id());
+        let host = Host::new(format!("@{}", name)).unwrap();
+        assert_eq!(host.path().unwrap(), PathBuf::from(format!("@{}", name)));
+        assert!(!Path::new(&format!("@{}", name)).exists());
+        assert!(!Path::new(&name).exists());
+
+        // A leading NUL byte is the kernel's spelling of the same address
+        let client = Client::new(&format!("\0{}", name), Reconnect::No).unwrap();
+        host.service_timeout(Duration::from_millis(100)).unwrap();
+        assert_eq!(host.sockets().unwrap().len(), 2);
+
+        drop(client);
+        drop(host);
+        // Once released the name is free again
+        Host::new(format!("@{}", name)).unwrap();
+    }
+
+    #[test]
+    fn test_host_invalid_path() {
+        let long = PathBuf::from(format!("/tmp/{}.sock", "x".repeat(Host::MAX_PATH_LEN)));
+        match Host::new(&long) {
+            Err(err @ Error::SocketPathTooLong(_)) => {
+                assert!(err.to_string().contains("108 byte limit"), "{}", err)
+            }
+            other => panic!("expected SocketPathTooLong, got {:?}", other),
+        }
+
+        let missing = PathBuf::from(format!("/tmp/vsl_missing_{}", std::process::id()));
+        match Host::new(missing.join("host.sock")) {
+            Err(Error::SocketDirectoryNotFound(dir)) => assert_eq!(dir, missing),
+            other => panic!("expected SocketDirectoryNotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_host_permissions() {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let path = test_socket_path("permissions");
+        let gid = unsafe { libc::getegid() };
+        let host = Host::new(&path)
+            .unwrap()
+            .with_group(gid)
+            .unwrap()
+            .with_permissions(0o660)
+            .unwrap();
+        let meta = std::fs::metadata(&path).unwrap();
+        assert_eq!(meta.permissions().mode() & 0o777, 0o660);
+        assert_eq!(meta.gid(), gid);
+        drop(host);
+
+        // Abstract sockets have no file to change
+        let name = format!("@vsl_host_abstract_{}", std::process::id());
+        let err = Host::new(&name)
+            .unwrap()
+            .with_permissions(0o660)
+            .unwrap_err();
+        assert!(matches!(err, Error::Io(e) if e.kind() == io::ErrorKind::InvalidInput));
+    }
+
+    #[test]
+    fn test_host_drop_stats() {
+        use crate::frame::Frame;
+        use std::thread;
+
+        let path = test_socket_path("drop_stats");
+        let host = Host::new(&path).unwrap();
+        assert_eq!(host.drop_stats().unwrap(), DropStats::default());
+
+        // Nothing reads the frame before it expires
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame.alloc(None).unwrap();
+        let now = crate::timestamp().unwrap();
+        host.post(frame, now + 1_000_000, -1, -1, -1).unwrap();
+        thread::sleep(Duration::from_millis(5));
+        host.service_timeout(Duration::ZERO).unwrap();
+
+        host.record_encoder_drop();
+        let drops = host.drop_stats().unwrap();
+        assert_eq!(
+            drops,
+            DropStats {
+                expired: 1,
+                client_slow: 0,
+                encoder_backlog: 1,
+                skip_policy: 0,
+            }
+        );
+        assert_eq!(drops.total(), 2);
+    }
+
+    #[test]
+    fn test_host_skip_policy() {
+        use crate::frame::Frame;
+        use std::thread;
+
+        let path = test_socket_path("skip_policy");
+        let calls = std::rc::Rc::new(Cell::new(0u32));
+        let host = Host::new(&path).unwrap().with_skip_policy({
+            let calls = calls.clone();
+            move || {
+                calls.set(calls.get() + 1);
+                calls.get() % 2 == 0
+            }
+        });
+
+        let now = crate::timestamp().unwrap();
+        for _ in 0..10 {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            host.post(frame, now + 1_000_000, -1, -1, -1).unwrap();
+        }
+        assert_eq!(calls.get(), 10);
+
+        // Only the posted frames reach the host and expire there
+        thread::sleep(Duration::from_millis(5));
+        let result = host.service_timeout(Duration::ZERO).unwrap();
+        assert_eq!(result.frames_expired, 5);
+        let drops = host.drop_stats().unwrap();
+        assert_eq!(drops.skip_policy, 5);
+        assert_eq!(drops.total(), 10);
+
+        // A frame admitted before encoding is not checked again when posted
+        assert!(!host.skip_frame());
+        let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+        frame.alloc(None).unwrap();
+        host.post(frame, now + 1_000_000, -1, -1, -1).unwrap();
+        assert_eq!(calls.get(), 11);
+        assert!(host.skip_frame());
+        assert_eq!(host.drop_stats().unwrap().skip_policy, 6);
     }
 
     #[test]
@@ -419,6 +1175,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_host_poll_timeout() {
         let path = test_socket_path("poll");
         let host = Host::new(&path).unwrap();
@@ -429,6 +1186,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_host_process() {
         let path = test_socket_path("process");
         let host = Host::new(&path).unwrap();
@@ -440,6 +1198,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_host_service_expires_frames() {
+        use crate::frame::Frame;
+        use std::thread;
+
+        let path = test_socket_path("service");
+        let host = Host::new(&path).unwrap();
+
+        // No client ever connects, so only service_timeout() releases the frames
+        let mut expired = 0;
+        for _ in 0..3 {
+            let frame = Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            let now = crate::timestamp().unwrap();
+            host.post(frame, now + 1_000_000, -1, -1, -1).unwrap();
+            thread::sleep(Duration::from_millis(5));
+
+            let result = host.service_timeout(Duration::from_millis(1)).unwrap();
+            assert_eq!(result.clients_serviced, 0);
+            expired += result.frames_expired;
+        }
+        assert_eq!(expired, 3);
+        assert_eq!(host.drop_stats().unwrap().expired, 3);
+
+        // Nothing left to expire
+        let result = host.service_timeout(Duration::ZERO).unwrap();
+        assert_eq!(result, ServiceResult::default());
+    }
+
+    #[test]
+    fn test_host_post_ref() {
+        let path = test_socket_path("post_ref");
+        let host = Host::new(&path).unwrap();
+
+        let frame = crate::frame::Frame::new(4, 2, 0, "RGB3").unwrap();
+        frame
+            .alloc(Some(Path::new(&format!(
+                "/vsl_host_post_ref_{}",
+                std::process::id()
+            ))))
+            .unwrap();
+        let pattern: Vec<u8> = (0..24).collect();
+        frame.mmap_mut().unwrap()[..24].copy_from_slice(&pattern);
+
+        let expires = crate::timestamp().unwrap() + 1_000_000_000;
+        host.post_ref(&frame, expires, -1, -1, -1).unwrap();
+        host.post_ref(&frame, expires, -1, -1, -1).unwrap();
+
+        // The frame is still owned and readable after posting
+        assert_eq!(frame.to_vec_packed().unwrap(), pattern);
+
+        // and outlives the host releasing the posted copies
+        drop(host);
+        assert_eq!(frame.to_vec_packed().unwrap(), pattern);
+
+        // Frames without a buffer cannot be shared
+        let host = Host::new(&path).unwrap();
+        let empty = crate::frame::Frame::new(4, 2, 0, "RGB3").unwrap();
+        assert!(host.post_ref(&empty, expires, -1, -1, -1).is_err());
+    }
+
     #[test]
     fn test_host_drop_frame() {
         let path = test_socket_path("drop_frame");
@@ -111,7 +111,9 @@
 //! - **Hardware acceleration**: G2D format conversion, VPU encoding/decoding
 //! - **Multi-subscriber**: One host can serve many clients simultaneously
 //! - **V4L2 camera**: Native Linux camera capture with DmaBuf export
-//! - **Cross-process**: UNIX sockets enable containerized applications
+//! - **Cross-process**: UNIX sockets enable containerized applications; see
+//!   [`Host::with_permissions`](host::Host::with_permissions) for clients
+//!   running as another user
 //!
 //! # Platform Support
 //!
@@ -141,6 +143,7 @@ use std::{
     ffi::{CStr, NulError},
     fmt, io,
     num::TryFromIntError,
+    path::{Path, PathBuf},
     str,
 };
 use videostream_sys as ffi;
@@ -172,13 +175,46 @@ pub enum Error {
 
     /// Hardware not available (e.g., VPU hardware not present on the system)
     HardwareNotAvailable(&'static str),
+
+    /// Malformed FOURCC string: not exactly four printable ASCII characters
+    InvalidFourCC(String),
+
+    /// Well-formed FOURCC the library does not support for the operation
+    UnsupportedFormat(fourcc::FourCC),
+
+    /// No hardware encoder or decoder supports the codec
+    UnsupportedCodec(codec::Codec),
+
+    /// Neither hardware nor software can convert between the two formats
+    ConversionUnsupported {
+        /// Format of the source frame
+        from: fourcc::FourCC,
+        /// Format of the target frame
+        to: fourcc::FourCC,
+    },
+
+    /// Socket path longer than a UNIX socket address can hold, see
+    /// [`Host::MAX_PATH_LEN`](host::Host::MAX_PATH_LEN)
+    SocketPathTooLong(PathBuf),
+
+    /// Directory that should contain the socket does not exist
+    SocketDirectoryNotFound(PathBuf),
+
+    /// Another host is already listening on the socket path
+    AddressInUse(PathBuf),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::LibraryNotLoaded(err) => {
-                write!(f, "VideoStream library could not be loaded: {}", err)
+                write!(f, "VideoStream library could not be loaded: {}", err)?;
+                // libloading keeps the dlerror() text, including the
+                // attempted paths, in the source
+                match std::error::Error::source(err) {
+                    Some(source) => write!(f, ": {}", source),
+                    None => Ok(()),
+                }
             }
             Error::Io(err) => write!(f, "I/O error: {}", err),
             Error::Utf8(err) => write!(f, "UTF-8 conversion error: {}", err),
@@ -195,6 +231,35 @@ impl fmt::Display for Error {
             Error::HardwareNotAvailable(hw) => {
                 write!(f, "Hardware '{}' not available on this system", hw)
             }
+            Error::InvalidFourCC(fourcc) => {
+                write!(
+                    f,
+                    "Invalid FOURCC {:?}: expected four printable ASCII characters",
+                    fourcc
+                )
+            }
+            Error::UnsupportedFormat(fourcc) => write!(f, "Unsupported format {}", fourcc),
+            Error::UnsupportedCodec(codec) => {
+                write!(f, "Codec {} not supported by hardware", codec)
+            }
+            Error::ConversionUnsupported { from, to } => {
+                write!(f, "Conversion from {} to {} not supported", from, to)
+            }
+            Error::SocketPathTooLong(path) => write!(
+                f,
+                "Socket path {} is {} bytes, longer than the {} byte limit",
+                path.display(),
+                path.as_os_str().len(),
+                host::Host::MAX_PATH_LEN
+            ),
+            Error::SocketDirectoryNotFound(dir) => {
+                write!(f, "Socket directory {} does not exist", dir.display())
+            }
+            Error::AddressInUse(path) => write!(
+                f,
+                "Socket path {} is already in use by another host",
+                path.display()
+            ),
         }
     }
 }
@@ -210,6 +275,13 @@ impl error::Error for Error {
             Error::NullPointer => None,
             Error::SymbolNotFound(_) => None,
             Error::HardwareNotAvailable(_) => None,
+            Error::InvalidFourCC(_) => None,
+            Error::UnsupportedFormat(_) => None,
+            Error::UnsupportedCodec(_) => None,
+            Error::ConversionUnsupported { .. } => None,
+            Error::SocketPathTooLong(_) => None,
+            Error::SocketDirectoryNotFound(_) => None,
+            Error::AddressInUse(_) => None,
         }
     }
 }
@@ -244,6 +316,101 @@ impl From<TryFromIntError> for Error {
     }
 }
 
+impl Error {
+    /// Returns the OS error code behind an [`Error::Io`], if any.
+    ///
+    /// Unlike [`io::Error::raw_os_error`] on the inner error, this also sees
+    /// through errors that name the failing library function, as returned
+    /// for failed `vsl_*` calls.
+    pub fn raw_os_error(&self) -> Option<i32> {
+        match self {
+            Error::Io(err) => err.raw_os_error().or_else(|| {
+                err.get_ref()?
+                    .downcast_ref::<CallError>()?
+                    .source
+                    .raw_os_error()
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A failed library call: the function name and the errno it left.
+#[derive(Debug)]
+struct CallError {
+    symbol: &'static str,
+    source: io::Error,
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} failed: ", self.symbol)?;
+        match self.source.raw_os_error().and_then(errno_name) {
+            Some(name) => write!(f, "{}: {}", name, self.source),
+            None => write!(f, "{}", self.source),
+        }
+    }
+}
+
+impl error::Error for CallError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Wraps `err` from library function `symbol` so its message names the
+/// function, e.g. "vsl_frame_alloc failed: ENOMEM: ...", keeping its kind.
+///
+/// The failure is logged at debug level, so `RUST_LOG=videostream=debug`
+/// traces every failed call, including those the caller recovers from.
+pub(crate) fn call_error(symbol: &'static str, err: io::Error) -> io::Error {
+    log::debug!("{} failed: {}", symbol, err);
+    io::Error::new(
+        err.kind(),
+        CallError {
+            symbol,
+            source: err,
+        },
+    )
+}
+
+/// Reads errno after library function `symbol` failed, see [`call_error`].
+pub(crate) fn last_os_error(symbol: &'static str) -> io::Error {
+    call_error(symbol, io::Error::last_os_error())
+}
+
+/// Returns the symbolic name of common errno values.
+fn errno_name(errno: i32) -> Option<&'static str> {
+    Some(match errno {
+        libc::EPERM => "EPERM",
+        libc::ENOENT => "ENOENT",
+        libc::EINTR => "EINTR",
+        libc::EIO => "EIO",
+        libc::ENXIO => "ENXIO",
+        libc::EBADF => "EBADF",
+        libc::EAGAIN => "EAGAIN",
+        libc::ENOMEM => "ENOMEM",
+        libc::EACCES => "EACCES",
+        libc::EFAULT => "EFAULT",
+        libc::EBUSY => "EBUSY",
+        libc::EEXIST => "EEXIST",
+        libc::ENODEV => "ENODEV",
+        libc::EINVAL => "EINVAL",
+        libc::EMFILE => "EMFILE",
+        libc::ENOSPC => "ENOSPC",
+        libc::EPIPE => "EPIPE",
+        libc::ERANGE => "ERANGE",
+        libc::ENAMETOOLONG => "ENAMETOOLONG",
+        libc::ENOSYS => "ENOSYS",
+        libc::ENOTSUP => "ENOTSUP",
+        libc::EADDRINUSE => "EADDRINUSE",
+        libc::ENOTCONN => "ENOTCONN",
+        libc::ECONNREFUSED => "ECONNREFUSED",
+        libc::ETIMEDOUT => "ETIMEDOUT",
+        _ => return None,
+    })
+}
+
 /// Helper macro for calling C library functions safely.
 ///
 /// This macro handles library initialization and wraps unsafe FFI calls.
@@ -308,6 +475,33 @@ pub mod camera;
 /// four-character-code pixel formats (e.g., "YUYV", "NV12").
 pub mod fourcc;
 
+/// Pure-Rust pixel format conversion.
+///
+/// Provides stride-aware [`nv12_to_yuyv`](convert::nv12_to_yuyv) and
+/// [`yuyv_to_nv12`](convert::yuyv_to_nv12), the software fallback of
+/// [`Frame::copy_to`](frame::Frame::copy_to).
+pub mod convert;
+
+/// Annex-B NAL unit parsing for H.264/H.265 bitstreams.
+///
+/// Provides [`parse_annex_b`](nal::parse_annex_b) and IDR detection used by
+/// [`Frame::is_keyframe`](frame::Frame::is_keyframe).
+pub mod nal;
+
+/// H.264 sequence parameter set parsing.
+///
+/// Provides [`SpsInfo::parse`](h264::SpsInfo::parse), which reads the
+/// profile, level, chroma format, and picture size of a stream before its
+/// first frame is decoded.
+pub mod h264;
+
+/// H.265 / HEVC sequence parameter set parsing.
+///
+/// Provides [`SpsInfo::parse`](h265::SpsInfo::parse), which reads the
+/// profile, tier, level, and conformance-window cropped picture size of an
+/// HEVC stream.
+pub mod h265;
+
 /// Colorimetry enums for camera capture formats.
 ///
 /// Provides [`ColorSpace`](colorimetry::ColorSpace),
@@ -324,6 +518,22 @@ pub mod colorimetry;
 /// cameras, encoders, and decoders without hardcoded device paths.
 pub mod v4l2;
 
+/// Hardware codec capability probing.
+///
+/// Provides [`capabilities`](codec::capabilities), which reports per codec
+/// (H.264, HEVC, MJPEG, VP8, VP9) whether a V4L2 encoder and decoder are
+/// present.
+pub mod codec;
+
+/// Frame rate measurement.
+///
+/// Provides [`RateMeter`](rate::RateMeter), which reports the average and
+/// sliding-window frame rates of a stream as shown by the CLI.
+pub mod rate;
+
+#[cfg(feature = "tracing")]
+mod trace;
+
 /// Returns the VideoStream Library version string.
 ///
 /// The version follows semantic versioning (MAJOR.MINOR.PATCH).
@@ -346,6 +556,286 @@ pub fn version() -> Result<String, Error> {
     Ok(cstr.to_str()?.to_string())
 }
 
+/// Selects the `libvideostream.so` to load, overriding `VIDEOSTREAM_LIBRARY`.
+///
+/// Must be called before any other VideoStream function, since the library
+/// is loaded on first use.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if the library
+/// is already loaded or a path was already set.
+///
+/// # Example
+///
+/// ```no_run
+/// use videostream::{set_library_path, version};
+///
+/// set_library_path("/opt/videostream/lib/libvideostream.so")?;
+/// println!("VideoStream version: {}", version()?);
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub fn set_library_path<P: AsRef<Path>>(path: P) -> Result<(), Error> {
+    if ffi::set_library_path(path.as_ref().to_string_lossy()) {
+        Ok(())
+    } else {
+        Err(Error::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "library path must be set before the VideoStream library is loaded",
+        )))
+    }
+}
+
+/// Uses the in-memory mock instead of loading `libvideostream.so`.
+///
+/// The mock is only used when selected here; without this call a missing
+/// library is reported as [`Error::LibraryNotLoaded`]. Frames live in shared
+/// memory, [`camera`] generates a moving test pattern, [`encoder::Encoder`]
+/// wraps raw frames in fake H.264 or HEVC NAL units that
+/// [`decoder::Decoder`] unwraps again, and [`host::Host`] only reaches
+/// [`client::Client`]s in the same process. Functions without a mock
+/// implementation return [`Error::SymbolNotFound`].
+///
+/// Must be called before any other VideoStream function, since the library
+/// is loaded on first use.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] with [`io::ErrorKind::InvalidInput`] if the library
+/// is already loaded.
+///
+/// # Example
+///
+/// ```no_run
+/// use videostream::{use_mock, version};
+///
+/// use_mock()?;
+/// assert!(version()?.ends_with("-mock"));
+/// # Ok::<(), videostream::Error>(())
+/// ```
+#[cfg(feature = "mock")]
+pub fn use_mock() -> Result<(), Error> {
+    if ffi::use_mock() {
+        Ok(())
+    } else {
+        Err(Error::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the mock must be selected before the VideoStream library is loaded",
+        )))
+    }
+}
+
+/// Returns the path of the loaded `libvideostream.so`.
+///
+/// Loads the library if needed and resolves the file the dynamic linker
+/// actually mapped, which helps diagnose systems with several copies
+/// installed. Falls back to the requested path or name when the mapping
+/// cannot be found in `/proc/self/maps`.
+///
+/// # Errors
+///
+/// Returns [`Error::LibraryNotLoaded`] if `libvideostream.so` cannot be loaded.
+///
+/// # Example
+///
+/// ```no_run
+/// use videostream::library_path;
+///
+/// println!("Loaded {}", library_path()?.display());
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub fn library_path() -> Result<PathBuf, Error> {
+    let lib = ffi::init()?;
+    let requested = PathBuf::from(ffi::library_path());
+
+    let Ok(symbol) = lib.vsl_version.as_ref() else {
+        return Ok(requested);
+    };
+    let address = *symbol as usize;
+
+    // Each line: "start-end perms offset dev inode pathname"
+    let maps = std::fs::read_to_string("/proc/self/maps")?;
+    let resolved = maps.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let (start, end) = fields.next()?.split_once('-')?;
+        let start = usize::from_str_radix(start, 16).ok()?;
+        let end = usize::from_str_radix(end, 16).ok()?;
+        if !(start..end).contains(&address) {
+            return None;
+        }
+        fields.nth(4).map(PathBuf::from)
+    });
+
+    Ok(resolved.unwrap_or(requested))
+}
+
+/// Directory where the kernel exposes DMA-BUF heaps.
+const DMA_HEAP_DIR: &str = "/dev/dma_heap";
+
+/// A DMA-BUF heap that frames can be allocated from.
+///
+/// Pass [`path`](Self::path) to [`Frame::alloc`](frame::Frame::alloc) to
+/// allocate from this heap.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DmaHeapInfo {
+    /// Heap name, such as `linux,cma` or `system`
+    pub name: String,
+    /// Device node of the heap, such as `/dev/dma_heap/linux,cma`
+    pub path: PathBuf,
+    /// Whether the heap allocates physically contiguous (CMA) memory, as
+    /// required by G2D and the VPU, rather than scattered system pages
+    pub contiguous: bool,
+}
+
+/// Lists the DMA-BUF heaps available on this system.
+///
+/// Heaps are read from `/dev/dma_heap` and sorted with contiguous (CMA)
+/// heaps first, then by name. A heap is considered contiguous when its name
+/// contains `cma` or it is the `reserved` heap; all others, including
+/// `system`, are treated as scattered memory. Returns an empty list on
+/// kernels without DMA-BUF heap support.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] if `/dev/dma_heap` exists but cannot be read.
+///
+/// # Example
+///
+/// ```no_run
+/// use videostream::{dma_heaps, frame::Frame};
+///
+/// let frame = Frame::new(1920, 1080, 0, "NV12")?;
+/// match dma_heaps()?.into_iter().find(|heap| heap.contiguous) {
+///     Some(heap) => frame.alloc(Some(&heap.path))?,
+///     None => frame.alloc(None)?,
+/// }
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub fn dma_heaps() -> Result<Vec<DmaHeapInfo>, Error> {
+    let entries = match std::fs::read_dir(DMA_HEAP_DIR) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut heaps = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let contiguous = name.contains("cma") || name == "reserved";
+        heaps.push(DmaHeapInfo {
+            name,
+            path: entry.path(),
+            contiguous,
+        });
+    }
+    heaps.sort_by(|a, b| b.contiguous.cmp(&a.contiguous).then(a.name.cmp(&b.name)));
+
+    Ok(heaps)
+}
+
+/// A VideoStream host socket found by [`discover_streams`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StreamEndpoint {
+    /// Socket path of the host
+    pub path: PathBuf,
+    /// Format of the frame received while probing, or `None` if the host
+    /// accepted the connection but posted no frame in time
+    pub info: Option<StreamInfo>,
+}
+
+/// Frame format published by a host, as seen by [`discover_streams`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StreamInfo {
+    /// Frame width in pixels
+    pub width: i32,
+    /// Frame height in pixels
+    pub height: i32,
+    /// Frame pixel format
+    pub fourcc: fourcc::FourCC,
+}
+
+/// How long [`discover_streams`] waits for each host to post a frame.
+const DISCOVER_TIMEOUT_SECS: f32 = 0.5;
+
+/// Lists the VideoStream hosts publishing in `dir`.
+///
+/// Every socket in `dir` is probed by connecting as a
+/// [`Client`](client::Client) and waiting up to half a second for a frame,
+/// whose geometry and format are reported in [`StreamEndpoint::info`].
+/// Sockets that refuse the connection, such as those left behind by a host
+/// that exited, are skipped. Subdirectories are not searched. Results are
+/// sorted by path.
+///
+/// Probing consumes one frame from each host and idle hosts take the full
+/// timeout, so scanning a directory of many streams takes a while.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] if `dir` cannot be read, or
+/// [`Error::LibraryNotLoaded`] if `libvideostream.so` cannot be loaded.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use videostream::discover_streams;
+///
+/// for stream in discover_streams(Path::new("/tmp"))? {
+///     match stream.info {
+///         Some(info) => println!(
+///             "{}: {}x{} {}",
+///             stream.path.display(),
+///             info.width,
+///             info.height,
+///             info.fourcc
+///         ),
+///         None => println!("{}: idle", stream.path.display()),
+///     }
+/// }
+/// # Ok::<(), videostream::Error>(())
+/// ```
+pub fn discover_streams(dir: &Path) -> Result<Vec<StreamEndpoint>, Error> {
+    use std::os::unix::fs::FileTypeExt;
+
+    ffi::init()?;
+    let mut streams = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        // Entries removed during the scan are not live streams
+        if !entry.file_type().is_ok_and(|ty| ty.is_socket()) {
+            continue;
+        }
+        let path = entry.path();
+        let Some(path_str) = path.to_str() else {
+            continue;
+        };
+        let client = match client::Client::new(path_str, client::Reconnect::No) {
+            Ok(client) => client,
+            Err(err) => {
+                log::debug!("skipping {}: {}", path.display(), err);
+                continue;
+            }
+        };
+        client.set_timeout(DISCOVER_TIMEOUT_SECS)?;
+        let info = match client.get_frame(0) {
+            Ok(frame) => Some(StreamInfo {
+                width: frame.width()?,
+                height: frame.height()?,
+                fourcc: fourcc::FourCC::from(frame.fourcc()?),
+            }),
+            Err(err) => {
+                log::debug!("no frame from {}: {}", path.display(), err);
+                None
+            }
+        };
+        streams.push(StreamEndpoint { path, info });
+    }
+    streams.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(streams)
+}
+
 /// Returns the current monotonic timestamp in nanoseconds.
 ///
 /// Uses `CLOCK_MONOTONIC` for consistent timing across the system.
@@ -380,6 +870,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_library_path() {
+        let path = library_path().unwrap();
+        assert!(path.to_string_lossy().contains("libvideostream"));
+        // The library is already loaded, so the path can no longer change.
+        assert!(set_library_path("/nonexistent/libvideostream.so").is_err());
+    }
+
+    #[test]
+    fn test_discover_streams() {
+        use std::{os::unix::net::UnixListener, thread, time::Duration};
+
+        let dir = std::env::temp_dir().join(format!("vsl_discover_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("notes.txt"), b"not a socket").unwrap();
+        // A socket file whose listener is gone refuses connections
+        drop(UnixListener::bind(dir.join("stale.sock")).unwrap());
+
+        let live = host::Host::new(dir.join("live.sock")).unwrap();
+        let idle = host::Host::new(dir.join("idle.sock")).unwrap();
+
+        let scan = {
+            let dir = dir.clone();
+            thread::spawn(move || discover_streams(&dir))
+        };
+        // The live host accepts the probe and keeps posting frames
+        while !scan.is_finished() {
+            live.service_timeout(Duration::from_millis(10)).unwrap();
+            let frame = frame::Frame::new(64, 48, 0, "RGB3").unwrap();
+            frame.alloc(None).unwrap();
+            let now = timestamp().unwrap();
+            live.post(frame, now + 100_000_000, -1, -1, -1).unwrap();
+            thread::sleep(Duration::from_millis(10));
+        }
+        let streams = scan.join().unwrap().unwrap();
+
+        let paths: Vec<PathBuf> = streams.iter().map(|s| s.path.clone()).collect();
+        assert_eq!(paths, vec![dir.join("idle.sock"), dir.join("live.sock")]);
+        assert_eq!(streams[0].info, None);
+        assert_eq!(
+            streams[1].info,
+            Some(StreamInfo {
+                width: 64,
+                height: 48,
+                fourcc: fourcc::FourCC(*b"RGB3"),
+            })
+        );
+
+        drop(live);
+        drop(idle);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_dma_heaps() {
+        let heaps = dma_heaps().unwrap();
+        let present = std::fs::read_dir(DMA_HEAP_DIR)
+            .map(|mut entries| entries.next().is_some())
+            .unwrap_or(false);
+        if !present {
+            println!("no DMA heaps in {}, skipping", DMA_HEAP_DIR);
+            assert!(heaps.is_empty());
+            return;
+        }
+
+        assert!(!heaps.is_empty());
+        for heap in &heaps {
+            println!("{} contiguous={}", heap.path.display(), heap.contiguous);
+            assert_eq!(heap.path, Path::new(DMA_HEAP_DIR).join(&heap.name));
+        }
+        // Contiguous heaps are listed first
+        assert!(heaps.windows(2).all(|w| w[0].contiguous >= w[1].contiguous));
+    }
+
     #[test]
     fn test_timestamp() {
         let result = timestamp();
@@ -519,4 +1083,33 @@ mod tests {
         assert!(display.contains("VPU encoder"));
         assert!(display.contains("Hardware"));
     }
+
+    #[test]
+    fn test_error_display_fourcc() {
+        let display = format!("{}", Error::InvalidFourCC("RGB".to_string()));
+        assert!(display.contains("\"RGB\""));
+
+        let err = Error::UnsupportedFormat(fourcc::FourCC(*b"ZZZZ"));
+        assert!(format!("{}", err).contains("ZZZZ"));
+        assert!(error::Error::source(&err).is_none());
+    }
+
+    #[test]
+    fn test_error_display_unsupported_codec() {
+        let err = Error::UnsupportedCodec(codec::Codec::Vp9);
+        assert_eq!(format!("{}", err), "Codec VP9 not supported by hardware");
+        assert!(error::Error::source(&err).is_none());
+    }
+    #[test]
+    fn test_error_display_failed_call() {
+        let frame = frame::Frame::new(64, 48, 0, "RGB3").unwrap();
+        let err = frame
+            .alloc(Some(Path::new("/dev/dma_heap/vsl_missing")))
+            .unwrap_err();
+        let display = format!("{}", err);
+        assert!(display.contains("vsl_frame_alloc failed"), "{}", display);
+        assert!(display.contains("ENOENT"), "{}", display);
+        assert!(matches!(&err, Error::Io(e) if e.kind() == io::ErrorKind::NotFound));
+        assert_eq!(err.raw_os_error(), Some(libc::ENOENT));
+    }
 }
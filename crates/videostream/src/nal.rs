@@ -0,0 +1,691 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Annex-B NAL unit parsing for H.264 and H.265 bitstreams.
+//!
+//! Encoded frames carry one or more NAL units, each preceded by a
+//! `00 00 01` or `00 00 00 01` start code (ITU-T H.264 Annex B.1). The
+//! helpers here split a buffer into NAL units and inspect their headers
+//! without decoding the payload, and convert to and from AVCC, the
+//! length-prefixed form stored in MP4 samples (ISO/IEC 14496-15).
+
+use crate::{frame::Rect, h264, h265};
+
+/// H.264 coded slice of an IDR picture.
+const H264_NAL_IDR: u8 = 5;
+/// H.264 sequence parameter set.
+const H264_NAL_SPS: u8 = 7;
+/// H.264 picture parameter set.
+const H264_NAL_PPS: u8 = 8;
+/// H.265 IDR picture with leading pictures (`IDR_W_RADL`).
+const H265_NAL_IDR_W_RADL: u8 = 19;
+/// H.265 IDR picture without leading pictures (`IDR_N_LP`).
+const H265_NAL_IDR_N_LP: u8 = 20;
+/// H.265 video parameter set.
+const H265_NAL_VPS: u8 = 32;
+/// H.265 sequence parameter set.
+const H265_NAL_SPS: u8 = 33;
+/// H.265 picture parameter set.
+const H265_NAL_PPS: u8 = 34;
+
+pub use crate::codec::Codec;
+
+impl Codec {
+    /// Returns `true` if the codec's bitstream is a sequence of Annex-B NAL
+    /// units, which holds for H.264 and HEVC.
+    pub fn is_annex_b(self) -> bool {
+        matches!(self, Codec::H264 | Codec::Hevc)
+    }
+
+    /// Returns the NAL unit type from the first header byte of `nal`, or
+    /// `None` if `nal` is empty or the codec does not use NAL units.
+    pub fn nal_type(self, nal: &[u8]) -> Option<u8> {
+        let header = *nal.first()?;
+        match self {
+            Codec::H264 => Some(header & 0x1f),
+            Codec::Hevc => Some((header >> 1) & 0x3f),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `nal` is a slice of an IDR picture.
+    pub fn is_idr(self, nal: &[u8]) -> bool {
+        matches!(
+            (self, self.nal_type(nal)),
+            (Codec::H264, Some(H264_NAL_IDR))
+                | (Codec::Hevc, Some(H265_NAL_IDR_W_RADL | H265_NAL_IDR_N_LP))
+        )
+    }
+
+    /// Returns `true` if `nal` is a sequence parameter set.
+    pub fn is_sps(self, nal: &[u8]) -> bool {
+        matches!(
+            (self, self.nal_type(nal)),
+            (Codec::H264, Some(H264_NAL_SPS)) | (Codec::Hevc, Some(H265_NAL_SPS))
+        )
+    }
+
+    /// Returns `true` if `nal` is a parameter set: an SPS or PPS, or for
+    /// HEVC also a VPS.
+    pub fn is_parameter_set(self, nal: &[u8]) -> bool {
+        matches!(
+            (self, self.nal_type(nal)),
+            (Codec::H264, Some(H264_NAL_SPS | H264_NAL_PPS))
+                | (Codec::Hevc, Some(H265_NAL_VPS..=H265_NAL_PPS))
+        )
+    }
+}
+
+/// Length of the start code at `pos`, if any.
+fn start_code(data: &[u8], pos: usize) -> Option<usize> {
+    match data.get(pos..pos + 4) {
+        Some([0, 0, 0, 1]) => Some(4),
+        _ => match data.get(pos..pos + 3) {
+            Some([0, 0, 1]) => Some(3),
+            _ => None,
+        },
+    }
+}
+
+/// Splits an Annex-B bitstream into NAL units, without their start codes.
+///
+/// Bytes before the first start code and empty NAL units are skipped.
+/// Emulation prevention bytes are left in place.
+///
+/// # Example
+///
+/// ```
+/// use videostream::nal::parse_annex_b;
+///
+/// let data = [0, 0, 0, 1, 0x67, 0x42, 0, 0, 1, 0x65, 0x88];
+/// assert_eq!(parse_annex_b(&data), vec![&[0x67, 0x42][..], &[0x65, 0x88][..]]);
+/// ```
+pub fn parse_annex_b(data: &[u8]) -> Vec<&[u8]> {
+    let mut units = Vec::new();
+    let mut start = None;
+    let mut i = 0;
+
+    while i < data.len() {
+        match start_code(data, i) {
+            Some(len) => {
+                if let Some(begin) = start.filter(|&begin| begin < i) {
+                    units.push(&data[begin..i]);
+                }
+                i += len;
+                start = Some(i);
+            }
+            None => i += 1,
+        }
+    }
+
+    if let Some(begin) = start.filter(|&begin| begin < data.len()) {
+        units.push(&data[begin..]);
+    }
+
+    units
+}
+
+/// Four-byte start code written before each NAL unit by
+/// [`avcc_to_annexb()`].
+pub const START_CODE: [u8; 4] = [0, 0, 0, 1];
+
+/// Converts an Annex-B bitstream to AVCC with 4-byte length prefixes.
+///
+/// Each NAL unit found by [`parse_annex_b()`] is written after its length as
+/// a big-endian `u32`, matching `lengthSizeMinusOne = 3` in the `avcC` or
+/// `hvcC` record. Both 3 and 4-byte start codes are accepted. Zero bytes
+/// trailing a NAL unit belong to the next start code, since a NAL unit
+/// always ends in its RBSP stop bit, and are dropped.
+///
+/// # Example
+///
+/// ```
+/// use videostream::nal::annexb_to_avcc;
+///
+/// let data = [0, 0, 0, 1, 0x67, 0x42, 0, 0, 1, 0x65, 0x88];
+/// assert_eq!(
+///     annexb_to_avcc(&data),
+///     [0, 0, 0, 2, 0x67, 0x42, 0, 0, 0, 2, 0x65, 0x88]
+/// );
+/// ```
+pub fn annexb_to_avcc(data: &[u8]) -> Vec<u8> {
+    let mut avcc = Vec::with_capacity(data.len() + 16);
+    for nal in parse_annex_b(data) {
+        let len = nal.iter().rposition(|&byte| byte != 0).map_or(0, |i| i + 1);
+        if len > 0 {
+            avcc.extend_from_slice(&(len as u32).to_be_bytes());
+            avcc.extend_from_slice(&nal[..len]);
+        }
+    }
+    avcc
+}
+
+/// Splits an AVCC buffer into its NAL units, stopping at the first length
+/// that runs past the end of `data`.
+///
+/// Returns the NAL units and whether the whole buffer was consumed, or
+/// `None` if `nal_length_size` is not 1, 2 or 4.
+fn split_avcc(data: &[u8], nal_length_size: u8) -> Option<(Vec<&[u8]>, bool)> {
+    let size = usize::from(nal_length_size);
+    if !matches!(size, 1 | 2 | 4) {
+        return None;
+    }
+
+    let mut units = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        let Some(prefix) = rest.get(..size) else {
+            return Some((units, false));
+        };
+        let len = prefix
+            .iter()
+            .fold(0usize, |len, &byte| (len << 8) | usize::from(byte));
+        let Some(nal) = rest.get(size..size + len) else {
+            return Some((units, false));
+        };
+        if !nal.is_empty() {
+            units.push(nal);
+        }
+        rest = &rest[size + len..];
+    }
+    Some((units, true))
+}
+
+/// Splits an AVCC buffer into NAL units, validating the length prefixes.
+///
+/// `nal_length_size` is the prefix size in bytes, `lengthSizeMinusOne + 1`
+/// from the `avcC` or `hvcC` record. Empty NAL units are skipped. Returns
+/// `None` if `nal_length_size` is not 1, 2 or 4 or if the buffer ends inside
+/// a length prefix or NAL unit.
+///
+/// # Example
+///
+/// ```
+/// use videostream::nal::parse_avcc;
+///
+/// let data = [0, 2, 0x67, 0x42, 0, 2, 0x65, 0x88];
+/// assert_eq!(
+///     parse_avcc(&data, 2),
+///     Some(vec![&[0x67, 0x42][..], &[0x65, 0x88][..]])
+/// );
+/// assert_eq!(parse_avcc(&data[..7], 2), None);
+/// ```
+pub fn parse_avcc(data: &[u8], nal_length_size: u8) -> Option<Vec<&[u8]>> {
+    match split_avcc(data, nal_length_size)? {
+        (units, true) => Some(units),
+        (_, false) => None,
+    }
+}
+
+/// Converts an AVCC buffer to an Annex-B bitstream.
+///
+/// Each NAL unit is written after a 4-byte [`START_CODE`], whatever the
+/// length prefix size. Conversion stops at a truncated NAL unit, keeping the
+/// complete ones before it, and an invalid `nal_length_size` yields an empty
+/// bitstream; use [`parse_avcc()`] to reject such buffers instead.
+///
+/// # Example
+///
+/// ```
+/// use videostream::nal::avcc_to_annexb;
+///
+/// let data = [0, 2, 0x67, 0x42, 0, 2, 0x65, 0x88];
+/// assert_eq!(
+///     avcc_to_annexb(&data, 2),
+///     [0, 0, 0, 1, 0x67, 0x42, 0, 0, 0, 1, 0x65, 0x88]
+/// );
+/// ```
+pub fn avcc_to_annexb(data: &[u8], nal_length_size: u8) -> Vec<u8> {
+    let units = split_avcc(data, nal_length_size).map_or_else(Vec::new, |(units, _)| units);
+    let mut annexb = Vec::with_capacity(data.len() + 16);
+    for nal in units {
+        annexb.extend_from_slice(&START_CODE);
+        annexb.extend_from_slice(nal);
+    }
+    annexb
+}
+
+/// Returns `true` if the bitstream contains an IDR NAL unit.
+pub fn contains_idr(codec: Codec, data: &[u8]) -> bool {
+    parse_annex_b(data).iter().any(|nal| codec.is_idr(nal))
+}
+
+/// Returns the display region from the cropping window of the first SPS in
+/// the bitstream.
+///
+/// The coded size is a whole number of macroblocks (H.264) or coding blocks
+/// (H.265), so 1080p is coded as 1920x1088 and the SPS crops the padding.
+/// Returns `None` if the bitstream has no SPS or it cannot be parsed. An SPS
+/// without a cropping window yields the full coded size.
+///
+/// # Example
+///
+/// ```
+/// use videostream::nal::{display_rect, Codec};
+///
+/// // SPS of a 1920x1080 stream from openh264
+/// let sps = [
+///     0, 0, 0, 1, 0x67, 0x42, 0xc0, 0x28, 0x8c, 0x68, 0x07, 0x80, 0x22, 0x7e,
+///     0x58, 0x07, 0x84, 0x42, 0x35,
+/// ];
+/// let rect = display_rect(Codec::H264, &sps).unwrap();
+/// assert_eq!((rect.width, rect.height), (1920, 1080));
+/// ```
+pub fn display_rect(codec: Codec, data: &[u8]) -> Option<Rect> {
+    let sps = parse_annex_b(data)
+        .into_iter()
+        .find(|nal| codec.is_sps(nal))?;
+    let (left, top, width, height) = match codec {
+        Codec::H264 => {
+            let sps = h264::SpsInfo::parse(sps).ok()?;
+            (sps.crop_left, sps.crop_top, sps.width, sps.height)
+        }
+        Codec::Hevc => {
+            let sps = h265::SpsInfo::parse(sps).ok()?;
+            (sps.crop_left, sps.crop_top, sps.width, sps.height)
+        }
+        _ => return None,
+    };
+    Some(Rect::new(
+        left.try_into().ok()?,
+        top.try_into().ok()?,
+        width.try_into().ok()?,
+        height.try_into().ok()?,
+    ))
+}
+
+/// Returns `true` if the first H.264 PPS in the bitstream selects CABAC
+/// entropy coding, `false` for CAVLC.
+///
+/// Reads `entropy_coding_mode_flag` (ITU-T H.264 7.3.2.2). Returns `None` if
+/// the bitstream has no PPS or it cannot be parsed.
+///
+/// # Example
+///
+/// ```
+/// use videostream::nal::h264_cabac;
+///
+/// // PPS of a Baseline profile stream from openh264
+/// let pps = [0, 0, 0, 1, 0x68, 0xce, 0x3c, 0x80];
+/// assert_eq!(h264_cabac(&pps), Some(false));
+/// ```
+pub fn h264_cabac(data: &[u8]) -> Option<bool> {
+    let pps = parse_annex_b(data)
+        .into_iter()
+        .find(|nal| Codec::H264.nal_type(nal) == Some(H264_NAL_PPS))?;
+    let rbsp = unescape(pps);
+    let mut bits = BitReader::new(rbsp.get(1..)?);
+    // pic_parameter_set_id, seq_parameter_set_id
+    bits.ue()?;
+    bits.ue()?;
+    Some(bits.bit()? == 1)
+}
+
+/// Removes emulation prevention bytes (`00 00 03`) from a NAL unit.
+pub(crate) fn unescape(nal: &[u8]) -> Vec<u8> {
+    let mut rbsp = Vec::with_capacity(nal.len());
+    let mut zeros = 0;
+    for &byte in nal {
+        if zeros >= 2 && byte == 3 {
+            zeros = 0;
+            continue;
+        }
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        rbsp.push(byte);
+    }
+    rbsp
+}
+
+/// Reads an RBSP most significant bit first, including the Exp-Golomb codes
+/// used by parameter sets (ITU-T H.264 9.1).
+pub(crate) struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        BitReader { data, pos: 0 }
+    }
+
+    pub(crate) fn bit(&mut self) -> Option<u32> {
+        let byte = *self.data.get(self.pos / 8)?;
+        let bit = (byte >> (7 - self.pos % 8)) & 1;
+        self.pos += 1;
+        Some(u32::from(bit))
+    }
+
+    pub(crate) fn bits(&mut self, count: u32) -> Option<u32> {
+        (0..count).try_fold(0, |value, _| Some(value << 1 | self.bit()?))
+    }
+
+    pub(crate) fn skip(&mut self, count: usize) -> Option<()> {
+        self.pos += count;
+        (self.pos <= self.data.len() * 8).then_some(())
+    }
+
+    pub(crate) fn ue(&mut self) -> Option<u32> {
+        let mut zeros = 0;
+        while self.bit()? == 0 {
+            zeros += 1;
+            if zeros > 31 {
+                return None;
+            }
+        }
+        Some(((1u64 << zeros) - 1 + u64::from(self.bits(zeros)?)) as u32)
+    }
+
+    pub(crate) fn se(&mut self) -> Option<i32> {
+        let code = self.ue()?;
+        let magnitude = ((u64::from(code) + 1) / 2) as i32;
+        Some(if code % 2 == 1 { magnitude } else { -magnitude })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_annex_b() {
+        let data = [
+            0xff, // garbage before the first start code
+            0, 0, 0, 1, 0x67, 0x42, // SPS
+            0, 0, 1, 0x68, 0xce, // PPS
+            0, 0, 0, 1, // empty NAL unit
+            0, 0, 1, 0x65, 0x88, 0x00, 0x00, 0x03, 0x01, // IDR slice
+        ];
+        assert_eq!(
+            parse_annex_b(&data),
+            vec![
+                &[0x67, 0x42][..],
+                &[0x68, 0xce][..],
+                &[0x65, 0x88, 0x00, 0x00, 0x03, 0x01][..],
+            ]
+        );
+        assert!(parse_annex_b(&[0x65, 0x88]).is_empty());
+        assert!(parse_annex_b(&[0, 0, 1]).is_empty());
+    }
+
+    #[test]
+    fn test_avcc_round_trip() {
+        let annexb = [
+            0, 0, 0, 1, 0x67, 0x42, 0xc0, 0x28, // SPS
+            0, 0, 1, 0x68, 0xce, 0x3c, 0x80, 0, 0, // PPS with trailing zeros
+            0, 0, 0, 1, 0x65, 0x88, 0x00, 0x00, 0x03, 0x01, // IDR
+            0, 0, 1, 0x41, 0x9a, // P slice
+        ];
+        let avcc = annexb_to_avcc(&annexb);
+        assert_eq!(
+            avcc,
+            [
+                0, 0, 0, 4, 0x67, 0x42, 0xc0, 0x28, //
+                0, 0, 0, 4, 0x68, 0xce, 0x3c, 0x80, //
+                0, 0, 0, 6, 0x65, 0x88, 0x00, 0x00, 0x03, 0x01, //
+                0, 0, 0, 2, 0x41, 0x9a,
+            ]
+        );
+
+        let units = parse_annex_b(&annexb);
+        assert_eq!(units[1].len(), 6);
+        let expected: Vec<&[u8]> = vec![units[0], &units[1][..4], units[2], units[3]];
+        assert_eq!(parse_avcc(&avcc, 4).unwrap(), expected);
+
+        // Back to Annex-B with 4-byte start codes only, then AVCC again
+        let restored = avcc_to_annexb(&avcc, 4);
+        assert_eq!(parse_annex_b(&restored), expected);
+        assert_eq!(restored.len(), 4 * 4 + 4 + 4 + 6 + 2);
+        assert_eq!(annexb_to_avcc(&restored), avcc);
+    }
+
+    #[test]
+    fn test_avcc_length_sizes() {
+        let units: [&[u8]; 3] = [&[0x67, 0x42], &[0x68, 0xce], &[0x65; 200]];
+        let annexb: Vec<u8> = units
+            .iter()
+            .flat_map(|nal| START_CODE.iter().chain(nal.iter()).copied())
+            .collect();
+
+        for size in [1u8, 2, 4] {
+            let mut avcc = Vec::new();
+            for nal in units {
+                let len = (nal.len() as u32).to_be_bytes();
+                avcc.extend_from_slice(&len[4 - usize::from(size)..]);
+                avcc.extend_from_slice(nal);
+            }
+            assert_eq!(parse_avcc(&avcc, size).unwrap(), units);
+            assert_eq!(avcc_to_annexb(&avcc, size), annexb);
+        }
+
+        assert_eq!(parse_avcc(&[0, 0, 2, 0x67, 0x42], 3), None);
+        assert!(avcc_to_annexb(&[0, 0, 2, 0x67, 0x42], 3).is_empty());
+        assert_eq!(parse_avcc(&[], 4), Some(vec![]));
+    }
+
+    #[test]
+    fn test_avcc_truncated() {
+        let avcc = [0, 2, 0x67, 0x42, 0, 0, 0, 3, 0x65, 0x88];
+        assert_eq!(parse_avcc(&avcc, 2), None);
+        // The empty unit is skipped and the truncated one dropped
+        assert_eq!(avcc_to_annexb(&avcc, 2), [0, 0, 0, 1, 0x67, 0x42]);
+        assert_eq!(parse_avcc(&avcc[..1], 2), None);
+    }
+
+    #[test]
+    fn test_nal_type() {
+        assert_eq!(Codec::H264.nal_type(&[0x65]), Some(5));
+        assert_eq!(Codec::H264.nal_type(&[0x41]), Some(1));
+        assert_eq!(Codec::Hevc.nal_type(&[0x26, 0x01]), Some(19));
+        assert_eq!(Codec::Hevc.nal_type(&[]), None);
+    }
+
+    #[test]
+    fn test_parameter_sets() {
+        assert!(Codec::H264.is_sps(&[0x67, 0x42]));
+        assert!(!Codec::H264.is_sps(&[0x68, 0xce]));
+        assert!(Codec::H264.is_parameter_set(&[0x68, 0xce]));
+        assert!(!Codec::H264.is_parameter_set(&[0x65, 0x88]));
+
+        // VPS, SPS and PPS versus a prefix SEI
+        assert!(Codec::Hevc.is_sps(&[0x42, 0x01]));
+        assert!(!Codec::Hevc.is_sps(&[0x40, 0x01]));
+        for header in [0x40, 0x42, 0x44] {
+            assert!(Codec::Hevc.is_parameter_set(&[header, 0x01]));
+        }
+        assert!(!Codec::Hevc.is_parameter_set(&[0x4e, 0x01]));
+        assert!(!Codec::Mjpg.is_parameter_set(&[0x67]));
+    }
+
+    #[test]
+    fn test_contains_idr() {
+        // H.264: SPS, PPS, IDR slice versus a lone P slice
+        let idr = [
+            0, 0, 0, 1, 0x67, 0x42, 0, 0, 0, 1, 0x68, 0xce, 0, 0, 1, 0x65, 0x88,
+        ];
+        let p_slice = [0, 0, 0, 1, 0x41, 0x9a];
+        assert!(contains_idr(Codec::H264, &idr));
+        assert!(!contains_idr(Codec::H264, &p_slice));
+
+        // H.265: IDR_N_LP and IDR_W_RADL versus TRAIL_R
+        assert!(contains_idr(Codec::Hevc, &[0, 0, 1, 0x28, 0x01]));
+        assert!(contains_idr(Codec::Hevc, &[0, 0, 1, 0x26, 0x01]));
+        assert!(!contains_idr(Codec::Hevc, &[0, 0, 1, 0x02, 0x01]));
+
+        // Codecs without NAL units never contain an IDR
+        assert!(!contains_idr(Codec::Mjpg, &[0, 0, 1, 0x65, 0x88]));
+        assert!(Codec::Hevc.is_annex_b());
+        assert!(!Codec::Vp9.is_annex_b());
+    }
+
+    /// SPS of a 1920x1080 stream from openh264: 120x68 macroblocks cropped
+    /// by 8 rows.
+    const H264_SPS_1080P: [u8; 19] = [
+        0, 0, 0, 1, 0x67, 0x42, 0xc0, 0x28, 0x8c, 0x68, 0x07, 0x80, 0x22, 0x7e, 0x58, 0x07, 0x84,
+        0x42, 0x35,
+    ];
+
+    /// Writes Exp-Golomb coded test parameter sets.
+    #[derive(Default)]
+    struct BitWriter {
+        bits: Vec<bool>,
+    }
+
+    impl BitWriter {
+        fn bits(&mut self, value: u32, count: u32) {
+            self.bits
+                .extend((0..count).rev().map(|i| (value >> i) & 1 == 1));
+        }
+
+        fn ue(&mut self, value: u32) {
+            let code = value + 1;
+            let len = 32 - code.leading_zeros();
+            self.bits(0, len - 1);
+            self.bits(code, len);
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            // rbsp_trailing_bits
+            self.bits.push(true);
+            while self.bits.len() % 8 != 0 {
+                self.bits.push(false);
+            }
+            self.bits
+                .chunks(8)
+                .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | u8::from(bit)))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_display_rect_h264() {
+        let rect = display_rect(Codec::H264, &H264_SPS_1080P).unwrap();
+        assert_eq!(rect, Rect::new(0, 0, 1920, 1080));
+
+        // 642x362 pads to 656x368, cropped on the right and bottom
+        let sps = [
+            0, 0, 0, 1, 0x67, 0x42, 0xc0, 0x16, 0x8c, 0x68, 0x0a, 0x42, 0xf6, 0x22, 0x48, 0x07,
+            0x84, 0x42, 0x35,
+        ];
+        assert_eq!(
+            display_rect(Codec::H264, &sps),
+            Some(Rect::new(0, 0, 642, 362))
+        );
+
+        // The SPS is found after other NAL units, but not in a lone slice
+        let mut stream = vec![0, 0, 1, 0x09, 0xf0];
+        stream.extend_from_slice(&H264_SPS_1080P);
+        assert_eq!(display_rect(Codec::H264, &stream), Some(rect));
+        assert_eq!(display_rect(Codec::H264, &[0, 0, 0, 1, 0x41, 0x9a]), None);
+        assert_eq!(display_rect(Codec::H264, &H264_SPS_1080P[..10]), None);
+    }
+
+    #[test]
+    fn test_h264_cabac() {
+        assert_eq!(
+            h264_cabac(&[0, 0, 0, 1, 0x68, 0xce, 0x3c, 0x80]),
+            Some(false)
+        );
+        // pps_id 1, sps_id 0, CABAC
+        let mut stream = H264_SPS_1080P.to_vec();
+        stream.extend_from_slice(&[0, 0, 1, 0x68, 0x5c, 0x3c, 0xb0]);
+        assert_eq!(h264_cabac(&stream), Some(true));
+        assert_eq!(h264_cabac(&H264_SPS_1080P), None);
+        assert_eq!(h264_cabac(&[0, 0, 1, 0x68]), None);
+    }
+
+    #[test]
+    fn test_display_rect_h264_high_profile() {
+        // High profile 4:2:0 with a top and left crop
+        let mut sps = BitWriter::default();
+        sps.bits(0x67, 8);
+        sps.bits(100, 8); // profile_idc
+        sps.bits(0, 8); // constraint flags
+        sps.bits(40, 8); // level_idc
+        sps.ue(0); // seq_parameter_set_id
+        sps.ue(1); // chroma_format_idc
+        sps.ue(0); // bit_depth_luma_minus8
+        sps.ue(0); // bit_depth_chroma_minus8
+        sps.bits(0, 1); // qpprime_y_zero_transform_bypass_flag
+        sps.bits(0, 1); // seq_scaling_matrix_present_flag
+        sps.ue(0); // log2_max_frame_num_minus4
+        sps.ue(0); // pic_order_cnt_type
+        sps.ue(2); // log2_max_pic_order_cnt_lsb_minus4
+        sps.ue(1); // max_num_ref_frames
+        sps.bits(0, 1); // gaps_in_frame_num_value_allowed_flag
+        sps.ue(79); // pic_width_in_mbs_minus1
+        sps.ue(44); // pic_height_in_map_units_minus1
+        sps.bits(1, 1); // frame_mbs_only_flag
+        sps.bits(1, 1); // direct_8x8_inference_flag
+        sps.bits(1, 1); // frame_cropping_flag
+        sps.ue(2); // left
+        sps.ue(0); // right
+        sps.ue(4); // top
+        sps.ue(0); // bottom
+        let mut data = vec![0, 0, 0, 1];
+        data.extend(sps.finish());
+        assert_eq!(
+            display_rect(Codec::H264, &data),
+            Some(Rect::new(4, 8, 1276, 712))
+        );
+    }
+
+    #[test]
+    fn test_display_rect_h265() {
+        let mut sps = BitWriter::default();
+        sps.bits(0x4201, 16); // NAL header
+        sps.bits(0, 4); // sps_video_parameter_set_id
+        sps.bits(1, 3); // sps_max_sub_layers_minus1
+        sps.bits(1, 1); // sps_temporal_id_nesting_flag
+        sps.bits(0x0160_0000, 32); // general profile: Main
+        sps.bits(0, 32);
+        sps.bits(0, 24);
+        sps.bits(120, 8); // general_level_idc
+        sps.bits(0b01, 2); // sub-layer level present only
+        sps.bits(0, 14); // reserved_zero_2bits for the unused sub-layers
+        sps.bits(90, 8); // sub_layer_level_idc
+        sps.ue(0); // sps_seq_parameter_set_id
+        sps.ue(1); // chroma_format_idc
+        sps.ue(1920); // pic_width_in_luma_samples
+        sps.ue(1088); // pic_height_in_luma_samples
+        sps.bits(1, 1); // conformance_window_flag
+        sps.ue(0);
+        sps.ue(0);
+        sps.ue(0);
+        sps.ue(4);
+        sps.ue(0); // bit_depth_luma_minus8
+        let mut data = vec![0, 0, 1];
+        data.extend(sps.finish());
+        assert_eq!(
+            display_rect(Codec::Hevc, &data),
+            Some(Rect::new(0, 0, 1920, 1080))
+        );
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(
+            unescape(&[0x67, 0, 0, 3, 1, 0, 0, 3, 0, 3]),
+            vec![0x67, 0, 0, 1, 0, 0, 0, 3]
+        );
+    }
+
+    #[test]
+    fn test_bit_reader() {
+        // ue 0, ue 1, se -1, ue 3, se 2, a flag, then u(3) of 0b101
+        let data = [0b1010_0110, 0b0100_0010, 0b0110_1000];
+        let mut bits = BitReader::new(&data);
+        assert_eq!(bits.ue(), Some(0));
+        assert_eq!(bits.ue(), Some(1));
+        assert_eq!(bits.se(), Some(-1));
+        assert_eq!(bits.ue(), Some(3));
+        assert_eq!(bits.se(), Some(2));
+        assert_eq!(bits.bit(), Some(1));
+        assert_eq!(bits.bits(3), Some(0b101));
+        assert_eq!(bits.skip(3), Some(()));
+        assert_eq!(bits.bit(), None);
+        assert_eq!(bits.skip(1), None);
+
+        // More than 31 leading zeros is not a valid Exp-Golomb code
+        assert_eq!(BitReader::new(&[0, 0, 0, 0, 0xff]).ue(), None);
+    }
+}
@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Frame rate measurement, used by the CLI's `stream` and `receive` commands
+//! and the pipeline integration test so both report frame rates alike.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Sliding window used for the instantaneous frame rate.
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(1);
+
+/// Records frame events and reports average and sliding-window frame rates.
+///
+/// The average rate counts every frame since the meter was created or reset.
+/// The current rate and its percentiles only consider the frames within
+/// `window` of the latest one, so they follow rate changes and stalls after
+/// the window fills.
+#[derive(Debug, Clone)]
+pub struct RateMeter {
+    window: Duration,
+    start: Instant,
+    count: u64,
+    /// Frame times within the window of the latest frame, oldest first
+    events: VecDeque<Instant>,
+}
+
+impl RateMeter {
+    /// Creates a meter with the given sliding window, starting now.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            start: Instant::now(),
+            count: 0,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Records a frame arriving now.
+    pub fn record(&mut self) {
+        self.record_at(Instant::now());
+    }
+
+    /// Records a frame arriving at `at`. Times must not go backwards.
+    pub fn record_at(&mut self, at: Instant) {
+        self.count += 1;
+        self.events.push_back(at);
+        while let Some(&oldest) = self.events.front() {
+            if at.duration_since(oldest) < self.window {
+                break;
+            }
+            self.events.pop_front();
+        }
+    }
+
+    /// Clears all frames and restarts the average from now.
+    pub fn reset(&mut self) {
+        self.reset_at(Instant::now());
+    }
+
+    /// Clears all frames and restarts the average from `at`.
+    pub fn reset_at(&mut self, at: Instant) {
+        self.start = at;
+        self.count = 0;
+        self.events.clear();
+    }
+
+    /// Frames recorded since the meter was created or reset.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Average frame rate from the start of the meter until now.
+    pub fn average_fps(&self) -> f64 {
+        self.average_fps_at(Instant::now())
+    }
+
+    /// Average frame rate from the start of the meter until `now`.
+    pub fn average_fps_at(&self, now: Instant) -> f64 {
+        let secs = now.saturating_duration_since(self.start).as_secs_f64();
+        if secs > 0.0 {
+            self.count as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Frame rate over the sliding window, or 0 until two frames are recorded.
+    pub fn current_fps(&self) -> f64 {
+        match (self.events.front(), self.events.back()) {
+            (Some(&first), Some(&last)) if last > first => {
+                (self.events.len() - 1) as f64 / (last - first).as_secs_f64()
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Percentile `p` (0-100) of the per-frame rates within the sliding
+    /// window, each the inverse of the gap to the previous frame. Low
+    /// percentiles reflect the slowest frames.
+    pub fn percentile_fps(&self, p: f64) -> f64 {
+        let mut rates: Vec<f64> = self
+            .events
+            .iter()
+            .zip(self.events.iter().skip(1))
+            .map(|(&prev, &next)| (next - prev).as_secs_f64())
+            .filter(|&secs| secs > 0.0)
+            .map(|secs| 1.0 / secs)
+            .collect();
+        if rates.is_empty() {
+            return 0.0;
+        }
+        rates.sort_by(f64::total_cmp);
+        let idx = ((p.clamp(0.0, 100.0) / 100.0) * (rates.len() - 1) as f64).round() as usize;
+        rates[idx]
+    }
+}
+
+impl Default for RateMeter {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME_30FPS: Duration = Duration::from_nanos(1_000_000_000 / 30);
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "expected {:.3} fps, got {:.3}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_window_fills_at_30fps() {
+        let start = Instant::now();
+        let mut meter = RateMeter::new(Duration::from_secs(1));
+        meter.reset_at(start);
+
+        for i in 1..=90 {
+            meter.record_at(start + FRAME_30FPS * i);
+        }
+
+        let now = start + FRAME_30FPS * 90;
+        assert_eq!(meter.count(), 90);
+        assert_close(meter.current_fps(), 30.0);
+        assert_close(meter.average_fps_at(now), 30.0);
+        assert_close(meter.percentile_fps(50.0), 30.0);
+    }
+
+    #[test]
+    fn test_window_follows_rate_change() {
+        let start = Instant::now();
+        let mut meter = RateMeter::new(Duration::from_secs(1));
+        meter.reset_at(start);
+
+        // Two seconds at 30 fps, then two seconds at 10 fps
+        let mut at = start;
+        for _ in 0..60 {
+            at += FRAME_30FPS;
+            meter.record_at(at);
+        }
+        for _ in 0..20 {
+            at += Duration::from_millis(100);
+            meter.record_at(at);
+        }
+
+        // Only the 10 fps frames remain in the window
+        assert_close(meter.current_fps(), 10.0);
+        assert_close(meter.average_fps_at(at), 20.0);
+    }
+
+    #[test]
+    fn test_percentile_reports_slow_frames() {
+        let start = Instant::now();
+        let mut meter = RateMeter::new(Duration::from_secs(10));
+        meter.reset_at(start);
+
+        // 20 frames 50 ms apart with one 200 ms stall
+        let mut at = start;
+        for i in 0..20 {
+            at += if i == 10 {
+                Duration::from_millis(200)
+            } else {
+                Duration::from_millis(50)
+            };
+            meter.record_at(at);
+        }
+
+        assert_close(meter.percentile_fps(0.0), 5.0);
+        assert_close(meter.percentile_fps(50.0), 20.0);
+        assert_close(meter.percentile_fps(100.0), 20.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let start = Instant::now();
+        let mut meter = RateMeter::default();
+        meter.reset_at(start);
+        for i in 1..=10 {
+            meter.record_at(start + FRAME_30FPS * i);
+        }
+
+        let later = start + Duration::from_secs(5);
+        meter.reset_at(later);
+        assert_eq!(meter.count(), 0);
+        assert_eq!(meter.current_fps(), 0.0);
+        assert_eq!(meter.percentile_fps(50.0), 0.0);
+        assert_eq!(meter.average_fps_at(later), 0.0);
+
+        meter.record_at(later + Duration::from_millis(100));
+        assert_close(meter.average_fps_at(later + Duration::from_secs(1)), 1.0);
+    }
+}
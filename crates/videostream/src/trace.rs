@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Timed `tracing` spans around the hot FFI calls.
+//!
+//! Only compiled with the `tracing` feature. Each span carries `serial`,
+//! `size` and `duration_ns` fields; the first two are recorded by the caller
+//! once known and the duration when the span guard is dropped, so subscribers
+//! see it before the span closes.
+
+use crate::frame::Frame;
+use std::time::Instant;
+use tracing::span::EnteredSpan;
+
+/// Opens a [`Timed`] debug span with empty `serial`, `size` and
+/// `duration_ns` fields.
+macro_rules! timed_span {
+    ($name:literal) => {
+        $crate::trace::Timed::new(tracing::debug_span!(
+            $name,
+            serial = tracing::field::Empty,
+            size = tracing::field::Empty,
+            duration_ns = tracing::field::Empty,
+        ))
+    };
+}
+pub(crate) use timed_span;
+
+/// Entered span that records its elapsed time when dropped.
+pub(crate) struct Timed {
+    span: EnteredSpan,
+    start: Instant,
+}
+
+impl Timed {
+    pub(crate) fn new(span: tracing::Span) -> Self {
+        Timed {
+            span: span.entered(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Records the serial and payload size of `frame`, skipping fields the
+    /// library cannot report.
+    pub(crate) fn record_frame(&self, frame: &Frame) {
+        if let Ok(serial) = frame.serial() {
+            self.record_serial(serial);
+        }
+        if let Ok(size) = frame.used_size() {
+            self.record_size(size);
+        }
+    }
+
+    pub(crate) fn record_serial(&self, serial: i64) {
+        self.span.record("serial", serial);
+    }
+
+    pub(crate) fn record_size(&self, size: usize) {
+        self.span.record("size", size as u64);
+    }
+}
+
+impl Drop for Timed {
+    fn drop(&mut self) {
+        let elapsed = u64::try_from(self.start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.span.record("duration_ns", elapsed);
+    }
+}
@@ -474,6 +474,23 @@ impl Device {
         })
     }
 
+    /// Create a bare device descriptor for unit tests.
+    #[cfg(test)]
+    pub(crate) fn stub(path: &str, device_type: DeviceType) -> Self {
+        Device {
+            path: PathBuf::from(path),
+            driver: String::new(),
+            card: String::new(),
+            bus_info: String::new(),
+            device_type,
+            multiplanar: false,
+            capture_memory: Vec::new(),
+            output_memory: Vec::new(),
+            capture_formats: Vec::new(),
+            output_formats: Vec::new(),
+        }
+    }
+
     /// Device path (e.g., "/dev/video0")
     ///
     /// Returns the filesystem path to the device node.
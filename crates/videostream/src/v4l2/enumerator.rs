@@ -12,7 +12,8 @@ use crate::fourcc::FourCC;
 use crate::Error;
 use videostream_sys as ffi;
 
-use super::device::{Device, DeviceType};
+use super::device::{Device, DeviceType, Resolution};
+use super::media::MediaDevice;
 
 /// V4L2 Device Enumerator
 ///
@@ -237,6 +238,53 @@ impl DeviceEnumerator {
         result
     }
 
+    /// Enumerate V4L2 devices matching any of several types in one pass.
+    ///
+    /// Unlike calling [`enumerate_type()`](Self::enumerate_type) once per
+    /// type, this scans `/dev/video*` a single time and filters the result,
+    /// preserving the enumeration order. An empty `device_types` slice
+    /// returns an empty vector without scanning.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_types` - Types of devices to include
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VideoStream library cannot be loaded or the
+    /// enumeration function is not available.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::v4l2::{DeviceEnumerator, DeviceType};
+    ///
+    /// // Get encoders and decoders with a single scan
+    /// let codecs =
+    ///     DeviceEnumerator::enumerate_types(&[DeviceType::Encoder, DeviceType::Decoder])?;
+    /// for dev in codecs {
+    ///     println!("{:?}: {} at {}", dev.device_type(), dev.card(), dev.path_str());
+    /// }
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn enumerate_types(device_types: &[DeviceType]) -> Result<Vec<Device>, Error> {
+        Self::filter_types(device_types, Self::enumerate)
+    }
+
+    /// Run `scan` once and keep only devices of the requested types.
+    fn filter_types(
+        device_types: &[DeviceType],
+        scan: impl FnOnce() -> Result<Vec<Device>, Error>,
+    ) -> Result<Vec<Device>, Error> {
+        if device_types.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut devices = scan()?;
+        devices.retain(|device| device_types.contains(&device.device_type()));
+        Ok(devices)
+    }
+
     /// Find an encoder device that supports a specific output codec.
     ///
     /// Searches for a hardware encoder that can produce the specified compressed
@@ -458,6 +506,175 @@ impl DeviceEnumerator {
         }
     }
 
+    /// Find every camera that supports a format at or above a resolution.
+    ///
+    /// Unlike [`find_camera_with_resolution()`](Self::find_camera_with_resolution),
+    /// which returns the first match only, this lists all candidates so an
+    /// application can choose among them. Each camera is paired with the
+    /// smallest frame size it reports via `VIDIOC_ENUM_FRAMESIZES` that is at
+    /// least `min_res` in both dimensions, and the list is sorted by that
+    /// resolution's area so the closest match comes first.
+    ///
+    /// Stepwise and continuous cameras report only their minimum and maximum
+    /// frame size, so their best match is the maximum. Cameras that report no
+    /// frame sizes for `format` are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - Four-character code for the pixel format
+    /// * `min_res` - Minimum resolution (0 for any width or height)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VideoStream library cannot be loaded or the
+    /// enumeration functions are not available.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::v4l2::{DeviceEnumerator, Resolution};
+    ///
+    /// let cameras = DeviceEnumerator::find_cameras(b"NV12", Resolution::new(1920, 1080))?;
+    /// for (camera, res) in &cameras {
+    ///     println!("{} ({}) at {}", camera.path_str(), camera.card(), res);
+    /// }
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn find_cameras(
+        format: &[u8; 4],
+        min_res: Resolution,
+    ) -> Result<Vec<(Device, Resolution)>, Error> {
+        let lib = ffi::init()?;
+
+        let enumerate_fn = lib
+            .vsl_v4l2_enumerate_type
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_v4l2_enumerate_type"))?;
+        let resolutions_fn = lib
+            .vsl_v4l2_enum_resolutions
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_v4l2_enum_resolutions"))?;
+
+        let list_ptr = unsafe { enumerate_fn(DeviceType::Camera as u32) };
+        if list_ptr.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let fourcc = FourCC(*format);
+        let list = unsafe { &*list_ptr };
+        let mut cameras = Vec::new();
+
+        if !list.devices.is_null() && list.count > 0 {
+            let device_slice = unsafe { std::slice::from_raw_parts(list.devices, list.count) };
+            for ffi_dev in device_slice {
+                let Ok(device) = Device::from_ffi(ffi_dev) else {
+                    continue;
+                };
+                if !device.capture_formats().iter().any(|f| f.fourcc == fourcc) {
+                    continue;
+                }
+
+                let mut count = 0;
+                let res_ptr = unsafe { resolutions_fn(ffi_dev, fourcc.as_u32(), &mut count) };
+                if res_ptr.is_null() {
+                    continue;
+                }
+                let resolutions: Vec<Resolution> =
+                    unsafe { std::slice::from_raw_parts(res_ptr, count) }
+                        .iter()
+                        .map(|res| Resolution::new(res.width, res.height))
+                        .collect();
+                unsafe { libc::free(res_ptr.cast()) };
+
+                if let Some(best) = Self::best_resolution(&resolutions, min_res) {
+                    cameras.push((device, best));
+                }
+            }
+        }
+
+        // Free the list
+        if let Ok(free_fn) = lib.vsl_v4l2_device_list_free.as_ref() {
+            unsafe { free_fn(list_ptr) };
+        }
+
+        cameras.sort_by_key(|(_, res)| Self::area(*res));
+        Ok(cameras)
+    }
+
+    /// Smallest resolution in `resolutions` covering `min_res`.
+    fn best_resolution(resolutions: &[Resolution], min_res: Resolution) -> Option<Resolution> {
+        resolutions
+            .iter()
+            .filter(|res| res.width >= min_res.width && res.height >= min_res.height)
+            .min_by_key(|res| Self::area(**res))
+            .copied()
+    }
+
+    fn area(res: Resolution) -> u64 {
+        u64::from(res.width) * u64::from(res.height)
+    }
+
+    /// Enumerate media controller devices and their topology.
+    ///
+    /// Reads each `/dev/media*` graph using `MEDIA_IOC_G_TOPOLOGY`. Grouping
+    /// by [`Device::bus_info`] cannot tell which sensor, CSI receiver and ISI
+    /// channel feed a capture node; the media graph can, and each entity
+    /// exposed as a device node is associated with its `/dev` path so the
+    /// results can be joined with [`enumerate()`](Self::enumerate).
+    ///
+    /// # Returns
+    ///
+    /// A vector of [`MediaDevice`] graphs, sorted by path. The vector is
+    /// empty on systems without media controller devices.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the VideoStream library cannot be loaded or does
+    /// not export `vsl_media_enumerate`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use videostream::v4l2::DeviceEnumerator;
+    ///
+    /// for media in DeviceEnumerator::media_topology()? {
+    ///     println!("{}", media);
+    ///     if let Some(node) = media.entity_by_devnode("/dev/video0") {
+    ///         for entity in media.pipeline(node) {
+    ///             println!("  {}", entity);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), videostream::Error>(())
+    /// ```
+    pub fn media_topology() -> Result<Vec<MediaDevice>, Error> {
+        let lib = ffi::init()?;
+
+        let enumerate_fn = lib
+            .vsl_media_enumerate
+            .as_ref()
+            .map_err(|_| Error::SymbolNotFound("vsl_media_enumerate"))?;
+
+        let list_ptr = unsafe { enumerate_fn() };
+        if list_ptr.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let list = unsafe { &*list_ptr };
+        let mut devices = Vec::with_capacity(list.count);
+        if !list.devices.is_null() && list.count > 0 {
+            let device_slice = unsafe { std::slice::from_raw_parts(list.devices, list.count) };
+            devices.extend(device_slice.iter().map(MediaDevice::from_ffi));
+        }
+
+        // Free the list
+        if let Ok(free_fn) = lib.vsl_media_device_list_free.as_ref() {
+            unsafe { free_fn(list_ptr) };
+        }
+
+        Ok(devices)
+    }
+
     /// Parse a VSLDeviceList into a Vec<Device>
     fn parse_device_list(list_ptr: *mut ffi::VSLDeviceList) -> Result<Vec<Device>, Error> {
         let list = unsafe { &*list_ptr };
@@ -479,6 +696,7 @@ impl DeviceEnumerator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::Cell;
 
     #[test]
     fn test_device_type_name() {
@@ -492,4 +710,73 @@ mod tests {
         assert_eq!(format!("{}", DeviceType::Camera), "Camera");
         assert_eq!(format!("{}", DeviceType::Encoder), "Encoder");
     }
+
+    #[test]
+    fn test_enumerate_types_single_scan() {
+        let scans = Cell::new(0);
+        let scan = || {
+            scans.set(scans.get() + 1);
+            Ok(vec![
+                Device::stub("/dev/video0", DeviceType::Camera),
+                Device::stub("/dev/video1", DeviceType::Encoder),
+                Device::stub("/dev/video2", DeviceType::Decoder),
+                Device::stub("/dev/video3", DeviceType::Isp),
+                Device::stub("/dev/video4", DeviceType::Encoder),
+            ])
+        };
+
+        let devices =
+            DeviceEnumerator::filter_types(&[DeviceType::Encoder, DeviceType::Decoder], scan)
+                .unwrap();
+
+        assert_eq!(scans.get(), 1);
+        let paths: Vec<&str> = devices.iter().map(|d| d.path_str()).collect();
+        assert_eq!(paths, ["/dev/video1", "/dev/video2", "/dev/video4"]);
+    }
+
+    #[test]
+    fn test_enumerate_types_empty_skips_scan() {
+        let devices = DeviceEnumerator::filter_types(&[], || {
+            panic!("scan should not run for an empty type list")
+        })
+        .unwrap();
+        assert!(devices.is_empty());
+    }
+
+    #[test]
+    fn test_best_resolution() {
+        let resolutions = [
+            Resolution::new(640, 480),
+            Resolution::new(3840, 2160),
+            Resolution::new(1920, 1080),
+            Resolution::new(1280, 720),
+            Resolution::new(2560, 1080),
+        ];
+
+        let best = |w, h| DeviceEnumerator::best_resolution(&resolutions, Resolution::new(w, h));
+        assert_eq!(best(1920, 1080), Some(Resolution::new(1920, 1080)));
+        assert_eq!(best(1900, 1000), Some(Resolution::new(1920, 1080)));
+        assert_eq!(best(2000, 1000), Some(Resolution::new(2560, 1080)));
+        assert_eq!(best(2000, 1200), Some(Resolution::new(3840, 2160)));
+        assert_eq!(best(0, 0), Some(Resolution::new(640, 480)));
+        assert_eq!(best(4096, 2160), None);
+        assert_eq!(
+            DeviceEnumerator::best_resolution(&[], Resolution::new(0, 0)),
+            None
+        );
+    }
+
+    #[ignore = "test requires camera hardware (run with --include-ignored to enable)"]
+    #[test]
+    fn test_find_cameras_nv12_1080p() -> Result<(), Error> {
+        let min_res = Resolution::new(1920, 1080);
+        let cameras = DeviceEnumerator::find_cameras(b"NV12", min_res)?;
+        for (camera, res) in &cameras {
+            println!("{} ({}) at {}", camera.path_str(), camera.card(), res);
+            assert!(camera.is_camera());
+            assert!(res.width >= min_res.width && res.height >= min_res.height);
+        }
+        assert!(!cameras.is_empty(), "no NV12 camera supports 1080p");
+        Ok(())
+    }
 }
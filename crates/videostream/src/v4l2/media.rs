@@ -0,0 +1,385 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+
+//! Media Controller Topology
+//!
+//! This module provides the [`MediaDevice`] type describing a `/dev/media*`
+//! graph, as returned by
+//! [`DeviceEnumerator::media_topology`](super::DeviceEnumerator::media_topology).
+
+use std::{
+    ffi::CStr,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use videostream_sys as ffi;
+
+/// Link flag: the link is active and carries data
+const MEDIA_LNK_FL_ENABLED: u32 = 1 << 0;
+/// Link flag: the link cannot be disabled
+const MEDIA_LNK_FL_IMMUTABLE: u32 = 1 << 1;
+
+/// Entity in a media controller graph
+///
+/// An entity is a block in the capture pipeline such as a sensor, CSI
+/// receiver, ISI channel or video capture node. Entities exposed to
+/// userspace carry the path of their device node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaEntity {
+    /// Entity ID, unique within the media device
+    pub id: u32,
+    /// Entity name (e.g., "mxc_isi.0")
+    pub name: String,
+    /// Entity function (`MEDIA_ENT_F_*`)
+    pub function: u32,
+    /// Entity flags (`MEDIA_ENT_FL_*`)
+    pub flags: u32,
+    /// Device node (e.g., "/dev/video0" or "/dev/v4l-subdev2"), if any
+    pub devnode: Option<PathBuf>,
+}
+
+impl MediaEntity {
+    /// Create from FFI VSLMediaEntity
+    pub(crate) fn from_ffi(ffi_ent: &ffi::VSLMediaEntity) -> Self {
+        let name = unsafe {
+            CStr::from_ptr(ffi_ent.name.as_ptr())
+                .to_string_lossy()
+                .into_owned()
+        };
+        let devnode = unsafe {
+            CStr::from_ptr(ffi_ent.devnode.as_ptr())
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        MediaEntity {
+            id: ffi_ent.id,
+            name,
+            function: ffi_ent.function,
+            flags: ffi_ent.flags,
+            devnode: (!devnode.is_empty()).then(|| PathBuf::from(devnode)),
+        }
+    }
+
+    /// Check if this entity is a `/dev/video*` node
+    pub fn is_video_node(&self) -> bool {
+        self.devnode
+            .as_deref()
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("video"))
+    }
+}
+
+impl fmt::Display for MediaEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.id)?;
+        if let Some(devnode) = &self.devnode {
+            write!(f, " -> {}", devnode.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Data link between two entity pads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MediaLink {
+    /// Source entity ID
+    pub source_entity: u32,
+    /// Source pad index
+    pub source_pad: u32,
+    /// Sink entity ID
+    pub sink_entity: u32,
+    /// Sink pad index
+    pub sink_pad: u32,
+    /// Link flags (`MEDIA_LNK_FL_*`)
+    pub flags: u32,
+}
+
+impl MediaLink {
+    /// Create from FFI VSLMediaLink
+    pub(crate) fn from_ffi(ffi_link: &ffi::VSLMediaLink) -> Self {
+        MediaLink {
+            source_entity: ffi_link.source_entity,
+            source_pad: ffi_link.source_pad,
+            sink_entity: ffi_link.sink_entity,
+            sink_pad: ffi_link.sink_pad,
+            flags: ffi_link.flags,
+        }
+    }
+
+    /// Check if the link is active
+    pub fn is_enabled(&self) -> bool {
+        self.flags & MEDIA_LNK_FL_ENABLED != 0
+    }
+
+    /// Check if the link is fixed by the hardware
+    pub fn is_immutable(&self) -> bool {
+        self.flags & MEDIA_LNK_FL_IMMUTABLE != 0
+    }
+}
+
+/// Media controller device and its topology
+///
+/// Describes the entity graph behind a `/dev/media*` node. On i.MX the
+/// sensor, CSI and ISI subdevices feeding a `/dev/video*` node only appear
+/// here, so this is the place to discover which sensor a capture node is
+/// actually connected to.
+///
+/// # Example
+///
+/// ```no_run
+/// use videostream::v4l2::DeviceEnumerator;
+///
+/// for media in DeviceEnumerator::media_topology()? {
+///     println!("{}: {}", media.path_str(), media.model());
+///     for node in media.video_nodes() {
+///         let chain: Vec<_> = media.pipeline(node).iter().map(|e| e.name.as_str()).collect();
+///         println!("  {}: {}", node, chain.join(" -> "));
+///     }
+/// }
+/// # Ok::<(), videostream::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct MediaDevice {
+    /// Device path (e.g., "/dev/media0")
+    path: PathBuf,
+    /// Driver name
+    driver: String,
+    /// Device model
+    model: String,
+    /// Bus information
+    bus_info: String,
+    /// Entities in the graph
+    entities: Vec<MediaEntity>,
+    /// Data links between entity pads
+    links: Vec<MediaLink>,
+}
+
+impl MediaDevice {
+    /// Create from FFI VSLMediaDevice
+    pub(crate) fn from_ffi(ffi_dev: &ffi::VSLMediaDevice) -> Self {
+        let path = unsafe {
+            CStr::from_ptr(ffi_dev.path.as_ptr())
+                .to_string_lossy()
+                .into_owned()
+        };
+        let driver = unsafe {
+            CStr::from_ptr(ffi_dev.driver.as_ptr())
+                .to_string_lossy()
+                .into_owned()
+        };
+        let model = unsafe {
+            CStr::from_ptr(ffi_dev.model.as_ptr())
+                .to_string_lossy()
+                .into_owned()
+        };
+        let bus_info = unsafe {
+            CStr::from_ptr(ffi_dev.bus_info.as_ptr())
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        let mut entities = Vec::new();
+        if !ffi_dev.entities.is_null() && ffi_dev.num_entities > 0 {
+            let slice =
+                unsafe { std::slice::from_raw_parts(ffi_dev.entities, ffi_dev.num_entities) };
+            entities.extend(slice.iter().map(MediaEntity::from_ffi));
+        }
+
+        let mut links = Vec::new();
+        if !ffi_dev.links.is_null() && ffi_dev.num_links > 0 {
+            let slice = unsafe { std::slice::from_raw_parts(ffi_dev.links, ffi_dev.num_links) };
+            links.extend(slice.iter().map(MediaLink::from_ffi));
+        }
+
+        MediaDevice {
+            path: PathBuf::from(path),
+            driver,
+            model,
+            bus_info,
+            entities,
+            links,
+        }
+    }
+
+    /// Device path (e.g., "/dev/media0")
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Device path as string
+    ///
+    /// Returns empty string if the path is not valid UTF-8.
+    pub fn path_str(&self) -> &str {
+        self.path.to_str().unwrap_or("")
+    }
+
+    /// Driver name (e.g., "mxc-isi")
+    pub fn driver(&self) -> &str {
+        &self.driver
+    }
+
+    /// Device model (e.g., "FSL Capture Media Device")
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Bus information (e.g., "platform:4ad50000.isi")
+    pub fn bus_info(&self) -> &str {
+        &self.bus_info
+    }
+
+    /// All entities in the graph
+    pub fn entities(&self) -> &[MediaEntity] {
+        &self.entities
+    }
+
+    /// All data links in the graph
+    pub fn links(&self) -> &[MediaLink] {
+        &self.links
+    }
+
+    /// Look up an entity by ID
+    pub fn entity(&self, id: u32) -> Option<&MediaEntity> {
+        self.entities.iter().find(|e| e.id == id)
+    }
+
+    /// Look up the entity exposed through a device node
+    ///
+    /// Use this to locate a [`Device`](super::Device) from
+    /// [`DeviceEnumerator::enumerate`](super::DeviceEnumerator::enumerate)
+    /// in the graph.
+    pub fn entity_by_devnode(&self, devnode: impl AsRef<Path>) -> Option<&MediaEntity> {
+        let devnode = devnode.as_ref();
+        self.entities
+            .iter()
+            .find(|e| e.devnode.as_deref() == Some(devnode))
+    }
+
+    /// Entities exposed as `/dev/video*` nodes
+    pub fn video_nodes(&self) -> impl Iterator<Item = &MediaEntity> {
+        self.entities.iter().filter(|e| e.is_video_node())
+    }
+
+    /// Links whose sink is the given entity
+    pub fn links_to(&self, id: u32) -> impl Iterator<Item = &MediaLink> {
+        self.links.iter().filter(move |l| l.sink_entity == id)
+    }
+
+    /// Links whose source is the given entity
+    pub fn links_from(&self, id: u32) -> impl Iterator<Item = &MediaLink> {
+        self.links.iter().filter(move |l| l.source_entity == id)
+    }
+
+    /// Active chain of entities feeding the given entity
+    ///
+    /// Follows enabled links upstream from `entity` and returns the chain in
+    /// source-to-sink order, e.g. sensor, CSI, ISI, capture node. The chain
+    /// always ends with `entity` itself.
+    pub fn pipeline<'a>(&'a self, entity: &'a MediaEntity) -> Vec<&'a MediaEntity> {
+        let mut chain = vec![entity];
+        let mut current = entity.id;
+
+        while let Some(source) = self
+            .links_to(current)
+            .find(|l| l.is_enabled())
+            .and_then(|l| self.entity(l.source_entity))
+        {
+            if chain.iter().any(|e| e.id == source.id) {
+                break;
+            }
+            chain.push(source);
+            current = source.id;
+        }
+
+        chain.reverse();
+        chain
+    }
+}
+
+impl fmt::Display for MediaDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} [{}] ({} entities, {} links)",
+            self.path.display(),
+            self.model,
+            self.driver,
+            self.entities.len(),
+            self.links.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(id: u32, name: &str, devnode: Option<&str>) -> MediaEntity {
+        MediaEntity {
+            id,
+            name: name.to_string(),
+            function: 0,
+            flags: 0,
+            devnode: devnode.map(PathBuf::from),
+        }
+    }
+
+    fn link(source: u32, sink: u32, flags: u32) -> MediaLink {
+        MediaLink {
+            source_entity: source,
+            source_pad: 0,
+            sink_entity: sink,
+            sink_pad: 0,
+            flags,
+        }
+    }
+
+    fn isi_graph() -> MediaDevice {
+        MediaDevice {
+            path: PathBuf::from("/dev/media0"),
+            driver: "mxc-isi".to_string(),
+            model: "FSL Capture Media Device".to_string(),
+            bus_info: "platform:4ad50000.isi".to_string(),
+            entities: vec![
+                entity(1, "os08a20 2-0036", Some("/dev/v4l-subdev0")),
+                entity(2, "csidev-4ad30000.csi", Some("/dev/v4l-subdev1")),
+                entity(3, "crossbar", None),
+                entity(4, "mxc_isi.0", None),
+                entity(5, "mxc_isi.0.capture", Some("/dev/video0")),
+                entity(6, "mxc_isi.1.capture", Some("/dev/video1")),
+            ],
+            links: vec![
+                link(1, 2, MEDIA_LNK_FL_ENABLED | MEDIA_LNK_FL_IMMUTABLE),
+                link(2, 3, MEDIA_LNK_FL_ENABLED),
+                link(3, 4, MEDIA_LNK_FL_ENABLED),
+                link(4, 5, MEDIA_LNK_FL_ENABLED | MEDIA_LNK_FL_IMMUTABLE),
+                link(3, 6, 0),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_media_pipeline() {
+        let media = isi_graph();
+        let node = media.entity_by_devnode("/dev/video0").unwrap();
+        let chain: Vec<_> = media.pipeline(node).iter().map(|e| e.id).collect();
+        assert_eq!(chain, vec![1, 2, 3, 4, 5]);
+
+        // Disabled links are not followed
+        let node = media.entity_by_devnode("/dev/video1").unwrap();
+        assert_eq!(media.pipeline(node).len(), 1);
+    }
+
+    #[test]
+    fn test_media_video_nodes() {
+        let media = isi_graph();
+        let nodes: Vec<_> = media.video_nodes().map(|e| e.id).collect();
+        assert_eq!(nodes, vec![5, 6]);
+        assert!(media.entity_by_devnode("/dev/video9").is_none());
+        assert_eq!(media.links_from(3).count(), 2);
+        assert!(media.links_to(2).next().unwrap().is_immutable());
+    }
+}
@@ -17,6 +17,8 @@
 //! - **Resolution Discovery**: Query supported resolutions via `VIDIOC_ENUM_FRAMESIZES`
 //! - **Memory Detection**: Detect MMAP, USERPTR, and DMABUF support
 //! - **Auto-Detection**: Find devices by codec or format (e.g., "find H.264 encoder")
+//! - **Media Topology**: Read `/dev/media*` graphs to see which sensor and
+//!   subdevices feed each `/dev/video*` node
 //!
 //! # Quick Start
 //!
@@ -107,9 +109,12 @@
 //! - [`DeviceEnumerator`] - Main entry point for device discovery
 //! - [`Device`] - Device descriptor with capabilities and formats
 //! - [`Format`] - Pixel format with resolutions
+//! - [`MediaDevice`] - Media controller graph of a camera pipeline
 
 mod device;
 mod enumerator;
+mod media;
 
 pub use device::{Device, DeviceType, Format, MemoryCapabilities, MemoryType, Resolution};
 pub use enumerator::DeviceEnumerator;
+pub use media::{MediaDevice, MediaEntity, MediaLink};
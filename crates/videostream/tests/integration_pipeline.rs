@@ -33,7 +33,7 @@ use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 use videostream::{
-    camera, client, decoder, encoder, fourcc::FourCC, frame::Frame, host::Host, timestamp,
+    camera, client, decoder, encoder, fourcc::FourCC, frame::Frame, host::Host, rate, timestamp,
 };
 
 /// Test configuration for pipeline tests
@@ -89,25 +89,23 @@ struct PipelineMetrics {
     frames_received: usize,
     /// Total frames decoded
     frames_decoded: usize,
-    /// Number of keyframes detected
+    /// Number of keyframes flagged by the encoder
     keyframes: usize,
+    /// Number of received frames containing an IDR NAL unit
+    keyframes_received: usize,
+    /// Whether the first received frame contains an IDR NAL unit
+    first_frame_keyframe: bool,
     /// Total bytes transferred
     bytes_transferred: u64,
     /// Test duration in milliseconds
     duration_ms: u64,
     /// Number of dropped frames
     dropped_frames: usize,
+    /// Average rate at which the client received frames
+    fps: f64,
 }
 
 impl PipelineMetrics {
-    /// Calculate effective frame rate
-    fn fps(&self) -> f64 {
-        if self.duration_ms == 0 {
-            return 0.0;
-        }
-        (self.frames_received as f64 * 1000.0) / self.duration_ms as f64
-    }
-
     /// Calculate throughput in Mbps
     fn throughput_mbps(&self) -> f64 {
         if self.duration_ms == 0 {
@@ -165,7 +163,7 @@ fn test_camera_encode_h264_pipeline() {
     );
 
     // Performance requirements
-    let actual_fps = metrics.fps();
+    let actual_fps = metrics.fps;
     assert!(
         actual_fps >= 25.0,
         "Frame rate too low: {:.1} fps (expected ≥25 fps)",
@@ -185,6 +183,12 @@ fn test_camera_encode_h264_pipeline() {
         "No keyframes detected in H.264 stream"
     );
 
+    // The client connects before encoding starts, so it sees the first IDR
+    assert!(
+        metrics.first_frame_keyframe,
+        "First received H.264 frame is not an IDR"
+    );
+
     log::info!("✓ H.264 pipeline test passed: {:?}", metrics);
 }
 
@@ -214,13 +218,18 @@ fn test_camera_encode_h265_pipeline() {
         metrics.frames_received
     );
 
-    let actual_fps = metrics.fps();
+    let actual_fps = metrics.fps;
     assert!(
         actual_fps >= 25.0,
         "Frame rate too low: {:.1} fps (expected ≥25 fps)",
         actual_fps
     );
 
+    assert!(
+        metrics.first_frame_keyframe,
+        "First received HEVC frame is not an IDR"
+    );
+
     log::info!("✓ H.265/HEVC pipeline test passed: {:?}", metrics);
 }
 
@@ -354,6 +363,11 @@ fn run_encode_pipeline_test(
     let socket_path = config.socket_path.clone();
     let frame_count = config.frame_count;
     let codec = config.codec;
+    let codec_fourcc = match codec {
+        Some("hevc") => Some(FourCC(*b"HEVC")),
+        Some(_) => Some(FourCC(*b"H264")),
+        None => None,
+    };
     let fps = config.fps;
     let decode_output = config.decode_output;
     let warmup_frames = config.warmup_frames;
@@ -390,8 +404,11 @@ fn run_encode_pipeline_test(
         let mut received = 0;
         let mut bytes = 0u64;
         let mut keyframes = 0;
+        let mut first_keyframe = false;
         let mut decoded = 0;
 
+        let mut rate = rate::RateMeter::default();
+
         let mut last_frame_time = Instant::now();
         while received < frame_count && !shutdown_clone.load(Ordering::Relaxed) {
             let before_get_frame = Instant::now();
@@ -400,17 +417,25 @@ fn run_encode_pipeline_test(
                 Ok(frame) => {
                     let get_frame_duration = before_get_frame.elapsed();
                     received += 1;
+                    // Measure from the first frame, not from connecting
+                    if received == 1 {
+                        rate.reset();
+                    }
+                    rate.record();
 
                     // Check frame properties
                     let before_metadata = Instant::now();
-                    let size = frame.size().unwrap_or(0);
+                    let size = frame.used_size().unwrap_or(0);
                     let _metadata_duration = before_metadata.elapsed();
                     bytes += size as u64;
 
-                    // Check if it's a keyframe (size > average indicates keyframe for encoded frames)
-                    if received > 0 {
-                        let avg_frame_size = bytes / received as u64;
-                        if size as u64 > (avg_frame_size * 3 / 2) {
+                    // Check the NAL headers for an IDR picture
+                    if let Some(fourcc) = codec_fourcc {
+                        let keyframe = frame.is_keyframe(fourcc).unwrap_or(false);
+                        if received == 1 {
+                            first_keyframe = keyframe;
+                        }
+                        if keyframe {
                             keyframes += 1;
                         }
                     }
@@ -422,9 +447,9 @@ fn run_encode_pipeline_test(
                         let before_decode = Instant::now();
 
                         // Get frame data for decoding using mmap
-                        if let Ok(frame_data) = frame.mmap() {
+                        if let Ok(frame_data) = frame.map() {
                             if !frame_data.is_empty() {
-                                match dec.decode_frame(frame_data) {
+                                match dec.decode_frame(&frame_data[..size.min(frame_data.len())]) {
                                     Ok((ret_code, bytes_consumed, decoded_frame)) => {
                                         decode_duration = before_decode.elapsed();
 
@@ -504,7 +529,14 @@ fn run_encode_pipeline_test(
             }
         }
 
-        (received, bytes, keyframes, decoded)
+        (
+            received,
+            bytes,
+            keyframes,
+            first_keyframe,
+            decoded,
+            rate.average_fps(),
+        )
     });
 
     // Give client thread time to connect
@@ -556,12 +588,10 @@ fn run_encode_pipeline_test(
         let expires = now + 5_000_000_000; // 5 second expiration
         host.post(output_frame, expires, -1, -1, -1)?;
 
-        // Poll for client activity (100ms timeout)
-        host.poll(100)?;
-        // CRITICAL: Always call process() to expire old frames, not just when there's client activity.
-        // vsl_host_process() does two things: service clients AND expire frames.
-        // If we only call it when poll() > 0, frames never expire and DMA memory exhausts.
-        host.process()?;
+        // Service clients (100ms timeout) and expire old frames, which
+        // service_timeout() does even without client activity so DMA memory is
+        // released
+        host.service_timeout(Duration::from_millis(100))?;
 
         if (i + 1) % 30 == 0 {
             log::debug!("Captured and published {} frames", i + 1);
@@ -573,15 +603,14 @@ fn run_encode_pipeline_test(
     // Continue processing host events (expire frames, service clients)
     // until client thread finishes receiving all frames
     let mut wait_iterations = 0;
-    let (received, bytes, keyframes, decoded) = loop {
+    let (received, bytes, keyframes, first_keyframe, decoded, fps) = loop {
         // Check if client thread has finished
         if client_handle.is_finished() {
             break client_handle.join().unwrap();
         }
 
         // Continue servicing the host to expire frames and handle client
-        host.poll(10)?;
-        host.process()?;
+        host.service_timeout(Duration::from_millis(10))?;
         wait_iterations += 1;
         if wait_iterations > 500 {
             // Signal shutdown to client thread on timeout
@@ -595,15 +624,17 @@ fn run_encode_pipeline_test(
                 thread::sleep(Duration::from_millis(10));
             }
             // Get actual client results, not zeros
-            break client_handle.join().unwrap_or((0, 0, 0, 0));
+            break client_handle.join().unwrap_or((0, 0, 0, false, 0, 0.0));
         }
         thread::sleep(Duration::from_millis(10));
     };
 
     metrics.frames_received = received;
     metrics.bytes_transferred = bytes;
-    metrics.keyframes += keyframes;
+    metrics.keyframes_received = keyframes;
+    metrics.first_frame_keyframe = first_keyframe;
     metrics.frames_decoded = decoded;
+    metrics.fps = fps;
     metrics.dropped_frames = config.frame_count.saturating_sub(received);
 
     Ok(metrics)
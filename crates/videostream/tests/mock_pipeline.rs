@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright 2025 Au-Zone Technologies
+//
+// Runs capture → encode → host → client → decode on the in-memory mock, so
+// the whole pipeline is exercised without libvideostream.so or hardware.
+//
+//   cargo test -p videostream --features mock --test mock_pipeline
+
+#![cfg(feature = "mock")]
+
+use std::{sync::Arc, thread, time::Duration};
+use videostream::{
+    camera, client, decoder, encoder, fourcc::FourCC, frame::Frame, host::Host, timestamp,
+};
+
+/// Installs the mock, which the first test to run in this binary loads.
+fn select_mock() -> Result<(), videostream::Error> {
+    // Fails once another test has installed it
+    let _ = videostream::use_mock();
+    assert!(videostream::version()?.ends_with("-mock"));
+    Ok(())
+}
+
+/// Encodes `frames` 64x48 mock camera frames to H.264 with a keyframe
+/// every `gop` frames, returning the bitstream and each frame's luma.
+fn record_mock_stream(
+    frames: usize,
+    gop: u32,
+) -> Result<(Vec<u8>, Vec<Vec<u8>>), videostream::Error> {
+    let (width, height) = (64, 48);
+    let cam = camera::create_camera()
+        .with_device("/dev/video0")
+        .with_resolution(width, height)
+        .with_format(FourCC(*b"YUYV"))
+        .open()?;
+    cam.start()?;
+    let enc = encoder::Encoder::create(
+        encoder::VSLEncoderProfileEnum::Kbps5000 as u32,
+        u32::from(FourCC(*b"H264")),
+        30,
+    )?;
+    enc.set_gop_size(gop)?;
+
+    let mut stream = Vec::new();
+    let mut lumas = Vec::new();
+    for _ in 0..frames {
+        let buffer = cam.read()?;
+        let input: Frame = (&buffer).try_into()?;
+        lumas.push(input.map()?.iter().step_by(2).copied().collect());
+        let output = enc.new_output_frame(width, height, -1, -1, -1)?;
+        let crop = encoder::VSLRect::new(0, 0, width, height);
+        let size = unsafe { enc.frame(&input, &output, &crop, std::ptr::null_mut())? };
+        stream.extend_from_slice(&output.map()?[..size as usize]);
+    }
+    cam.stop()?;
+    Ok((stream, lumas))
+}
+
+#[test]
+fn test_mock_pipeline() -> Result<(), Box<dyn std::error::Error>> {
+    select_mock()?;
+
+    let (width, height) = (320, 240);
+    let cam = camera::create_camera()
+        .with_device("/dev/video0")
+        .with_resolution(width, height)
+        .with_format(FourCC(*b"YUYV"))
+        .open()?;
+    cam.start()?;
+
+    let enc = encoder::Encoder::create(
+        encoder::VSLEncoderProfileEnum::Kbps5000 as u32,
+        u32::from(FourCC(*b"H264")),
+        30,
+    )?;
+    let path = format!("/tmp/vsl_mock_pipeline_{}.sock", std::process::id());
+    let host = Host::new(&path)?;
+    let client = client::Client::new(&path, client::Reconnect::No)?;
+    let dec = decoder::Decoder::create(decoder::DecoderCodec::H264, 30)?;
+
+    for i in 0..10 {
+        let buffer = cam.read()?;
+        assert_eq!(buffer.sequence()?, i);
+        let input: Frame = (&buffer).try_into()?;
+        let captured: Vec<u8> = input.map()?.iter().step_by(2).copied().collect();
+
+        let output = enc.new_output_frame(width, height, -1, i as i64, i as i64)?;
+        let crop = encoder::VSLRect::new(0, 0, width, height);
+        let mut keyframe = 0;
+        let size = unsafe { enc.frame(&input, &output, &crop, &mut keyframe)? };
+        assert!(size > 0);
+        assert_eq!(keyframe != 0, i % 30 == 0);
+        assert_eq!(output.is_keyframe(FourCC(*b"H264"))?, keyframe != 0);
+
+        host.post(output, timestamp()? + 1_000_000_000, -1, i as i64, i as i64)?;
+        host.service_timeout(Duration::ZERO)?;
+
+        let encoded = client.get_frame(0)?;
+        assert_eq!(encoded.serial()?, i as i64 + 1);
+        assert_eq!(encoded.pts()?, i as i64);
+        encoded.trylock()?;
+        let data = encoded.map()?.to_vec();
+        encoded.unlock()?;
+        assert_eq!(data.len(), size as usize);
+
+        let (_, consumed, decoded) = dec.decode_frame(&data)?;
+        assert_eq!(consumed, data.len());
+        let decoded = decoded.ok_or("no frame decoded")?;
+        assert_eq!((decoded.width()?, decoded.height()?), (width, height));
+        assert_eq!(decoded.fourcc()?, u32::from(FourCC(*b"NV12")));
+
+        // The codec is lossless, so the luma survives the round trip
+        let luma = decoded.map()?[..captured.len()].to_vec();
+        assert_eq!(luma, captured, "luma of frame {}", i);
+    }
+
+    assert_eq!(host.drop_stats()?.expired, 0);
+    cam.stop()?;
+    Ok(())
+}
+
+#[test]
+fn test_shared_encoder_threads() -> Result<(), Box<dyn std::error::Error>> {
+    select_mock()?;
+
+    let enc = encoder::Encoder::create(
+        encoder::VSLEncoderProfileEnum::Kbps5000 as u32,
+        u32::from(FourCC(*b"H264")),
+        30,
+    )?;
+    let shared = Arc::new(encoder::SharedEncoder::new(enc));
+
+    // Each thread encodes its own resolution
+    let workers: Vec<_> = [(64, 48), (32, 24)]
+        .into_iter()
+        .map(|(width, height)| {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || -> Result<Vec<(i32, bool)>, videostream::Error> {
+                let source = Frame::new(width as u32, height as u32, 0, "YUYV")?;
+                source.alloc(None)?;
+                let crop = encoder::VSLRect::new(0, 0, width, height);
+
+                let mut encoded = Vec::new();
+                for _ in 0..10 {
+                    let output = shared.lock().new_output_frame(width, height, -1, -1, -1)?;
+                    encoded.push(shared.encode(&source, &output, &crop)?);
+                }
+                Ok(encoded)
+            })
+        })
+        .collect();
+    let encoded: Vec<_> = workers
+        .into_iter()
+        .map(|worker| worker.join().unwrap())
+        .collect::<Result<_, _>>()?;
+
+    // The mock carries the raw picture, so each thread's frames stay the
+    // size of its own resolution
+    for frames in &encoded {
+        assert_eq!(frames.len(), 10);
+        assert!(frames.iter().all(|&(size, _)| size == frames[0].0));
+    }
+    assert!(encoded[0][0].0 > encoded[1][0].0);
+
+    // The encoder counts one GOP across both threads' 20 frames
+    let keyframes = encoded.iter().flatten().filter(|(_, key)| *key).count();
+    assert_eq!(keyframes, 1);
+
+    let enc = Arc::try_unwrap(shared).ok().unwrap().into_inner();
+    assert_eq!(enc.gop_size()?, 30);
+    Ok(())
+}
+
+#[test]
+fn test_file_decoder_seek() -> Result<(), Box<dyn std::error::Error>> {
+    select_mock()?;
+
+    // Record 35 frames with keyframes every 10
+    let (stream, lumas) = record_mock_stream(35, 10)?;
+
+    let config = decoder::DecoderConfig::new(decoder::DecoderCodec::H264, 30);
+    let mut file = decoder::FileDecoder::from_bytes(stream, config)?;
+    assert_eq!(file.frame_count(), 35);
+    assert_eq!(file.keyframes(), [0, 10, 20, 30]);
+
+    let luma = |frame: &Frame| -> Result<Vec<u8>, videostream::Error> {
+        Ok(frame.map()?[..lumas[0].len()].to_vec())
+    };
+    // Each seek lands on its frame, decoding from the keyframe before it or
+    // continuing forwards; (target, frames decoded by the seek)
+    for (target, decoded) in [(25, 6), (27, 2), (12, 3), (13, 1), (31, 2), (9, 10)] {
+        let before = file.frames_decoded();
+        let frame = file.seek_frame(target)?;
+        assert_eq!(luma(&frame)?, lumas[target], "frame {}", target);
+        assert_eq!(file.frames_decoded() - before, decoded, "frame {}", target);
+    }
+
+    // Frame 34 is shown from 34/30 s until the end
+    let frame = file.seek(34 * 1_000_000_000 / 30 + 1)?;
+    assert_eq!(luma(&frame)?, lumas[34]);
+    assert!(file.seek_frame(35).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_decode_all() -> Result<(), Box<dyn std::error::Error>> {
+    select_mock()?;
+
+    // Five access units, including the SPS and PPS, in one buffer
+    let (stream, lumas) = record_mock_stream(5, 30)?;
+
+    // One call only takes the first NAL unit
+    let dec = decoder::Decoder::create(decoder::DecoderCodec::H264, 30)?;
+    let (_, consumed, _) = dec.decode_frame(&stream)?;
+    assert!(consumed < stream.len());
+
+    let dec = decoder::Decoder::create(decoder::DecoderCodec::H264, 30)?;
+    let frames = dec.decode_all(&stream)?;
+    assert_eq!(frames.len(), lumas.len());
+    for (i, (frame, luma)) in frames.iter().zip(&lumas).enumerate() {
+        assert_eq!(&frame.map()?[..luma.len()], luma.as_slice(), "frame {}", i);
+    }
+    assert!(dec.decode_all(&[])?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_camera_group_source_id() -> Result<(), Box<dyn std::error::Error>> {
+    select_mock()?;
+
+    let camera = |device: &str| {
+        camera::create_camera()
+            .with_device(device)
+            .with_resolution(64, 48)
+            .with_format(FourCC(*b"YUYV"))
+    };
+    let group = camera::CameraGroup::open([camera("/dev/video0"), camera("/dev/video1")])?;
+    group.start()?;
+
+    let path = format!("/tmp/vsl_mock_source_id_{}.sock", std::process::id());
+    let host = Host::new(&path)?;
+    let client = client::Client::new(&path, client::Reconnect::No)?;
+
+    // Both cameras publish on one stream, tagged with their group index
+    for _ in 0..3 {
+        for (index, buffer) in group.read_synced()?.iter().enumerate() {
+            assert_eq!(buffer.source_id(), Some(index as u32));
+            let frame: Frame = buffer.try_into()?;
+            host.post(frame, timestamp()? + 1_000_000_000, -1, -1, -1)?;
+            host.service_timeout(Duration::ZERO)?;
+            assert_eq!(client.get_frame(0)?.source_id()?, Some(index as u32));
+        }
+    }
+
+    // A camera read on its own has no source
+    group.stop()?;
+    let single = camera("/dev/video2").open()?;
+    single.start()?;
+    assert_eq!(single.read()?.source_id(), None);
+    single.stop()?;
+    Ok(())
+}
+
+#[test]
+fn test_mock_client_commands() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::fd::AsRawFd;
+
+    select_mock()?;
+
+    let path = format!("/tmp/vsl_mock_commands_{}.sock", std::process::id());
+    let mut host = Host::new(&path)?;
+    host.set_command_handler(|req| [b"echo:", req].concat())?;
+    assert!(host.sockets()?.is_empty());
+    let client = client::Client::new(&path, client::Reconnect::No)?;
+
+    // The client descriptor becomes readable once a frame is queued
+    let readable = |client: &client::Client| {
+        let mut pfd = libc::pollfd {
+            fd: client.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        unsafe { libc::poll(&mut pfd, 1, 0) == 1 }
+    };
+    assert!(client.as_raw_fd() >= 0);
+    assert!(!readable(&client));
+    let frame = Frame::new(64, 48, 0, "RGB3")?;
+    frame.alloc(None)?;
+    host.post(frame, timestamp()? + 1_000_000_000, -1, -1, -1)?;
+    assert!(readable(&client));
+    drop(client.get_frame(0)?);
+    assert!(!readable(&client));
+
+    // Commands are answered by the host's process()
+    let sender = thread::spawn(move || (client.send_command(b"ping"), client));
+    while !sender.is_finished() {
+        host.service_timeout(Duration::from_millis(10))?;
+    }
+    let (reply, client) = sender.join().unwrap();
+    assert_eq!(reply?, b"echo:ping");
+    assert_eq!(client.stats().reconnects, 0);
+
+    // A client set reports the host going away
+    let set: client::ClientSet = std::iter::once(client).collect();
+    drop(host);
+    let results = set.poll(Duration::from_millis(200))?;
+    assert_eq!(results.len(), 1);
+    let err = results[0].1.as_ref().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOTCONN));
+    Ok(())
+}
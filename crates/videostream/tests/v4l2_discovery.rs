@@ -748,3 +748,42 @@ fn test_device_display_trait() {
         );
     }
 }
+
+#[test]
+#[ignore = "requires media controller devices (run with --ignored on i.MX hardware)"]
+fn test_media_topology() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let media = DeviceEnumerator::media_topology().expect("media_topology should succeed");
+    assert!(
+        !media.is_empty(),
+        "expected at least one /dev/media* device"
+    );
+
+    for dev in &media {
+        println!("{}", dev);
+        for entity in dev.entities() {
+            println!("  {}", entity);
+        }
+        for link in dev.links() {
+            println!(
+                "  {}:{} -> {}:{} enabled={}",
+                link.source_entity,
+                link.source_pad,
+                link.sink_entity,
+                link.sink_pad,
+                link.is_enabled()
+            );
+        }
+        for node in dev.video_nodes() {
+            let chain: Vec<_> = dev.pipeline(node).iter().map(|e| e.name.as_str()).collect();
+            println!("  pipeline {}: {}", node, chain.join(" -> "));
+        }
+
+        // Every link must reference entities in the same graph
+        for link in dev.links() {
+            assert!(dev.entity(link.source_entity).is_some());
+            assert!(dev.entity(link.sink_entity).is_some());
+        }
+    }
+}
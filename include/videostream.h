@@ -163,6 +163,7 @@
 #define VSL_VERSION_2_2 VSL_VERSION_ENCODE(2, 2, 0)
 #define VSL_VERSION_2_4 VSL_VERSION_ENCODE(2, 4, 0)
 #define VSL_VERSION_2_5 VSL_VERSION_ENCODE(2, 5, 0)
+#define VSL_VERSION_2_6 VSL_VERSION_ENCODE(2, 6, 0)
 
 #ifndef VSL_TARGET_VERSION
 #define VSL_TARGET_VERSION VSL_VERSION_2_2
@@ -278,6 +279,17 @@
     VSL_DEPRECATED_FOR(2.5, replacement)
 #endif
 
+#if VSL_TARGET_VERSION < VSL_VERSION_ENCODE(2, 6, 0)
+#define VSL_AVAILABLE_SINCE_2_6 VSL_UNAVAILABLE(2.6)
+#define VSL_DEPRECATED_SINCE_2_6
+#define VSL_DEPRECATED_SINCE_2_6_FOR(replacement)
+#else
+#define VSL_AVAILABLE_SINCE_2_6
+#define VSL_DEPRECATED_SINCE_2_6 VSL_DEPRECATED(2.6)
+#define VSL_DEPRECATED_SINCE_2_6_FOR(replacement) \
+    VSL_DEPRECATED_FOR(2.6, replacement)
+#endif
+
 #define VSL_FOURCC(a, b, c, d)                                         \
     ((uint32_t) (a) | ((uint32_t) (b) << 8) | ((uint32_t) (c) << 16) | \
      ((uint32_t) (d) << 24))
@@ -344,6 +356,51 @@ typedef struct vsl_rect {
     int height;
 } VSLRect;
 
+/**
+ * Metadata of a pending frame returned by vsl_client_peek().
+ *
+ * @since 2.6
+ */
+typedef struct vsl_frame_meta {
+    /**
+     * Frame serial number assigned by the host.
+     */
+    int64_t serial;
+    /**
+     * Host timestamp in nanoseconds when the frame was posted.
+     */
+    int64_t timestamp;
+    /**
+     * Presentation timestamp in nanoseconds, or -1 if unknown.
+     */
+    int64_t pts;
+    /**
+     * Size of the frame buffer in bytes.
+     */
+    size_t size;
+    /**
+     * Frame pixel format as a FourCC code.
+     */
+    uint32_t fourcc;
+} VSLFrameMeta;
+
+/**
+ * Frames discarded by a host, by cause, returned by vsl_host_drop_stats().
+ *
+ * @since 2.6
+ */
+typedef struct vsl_host_drop_stats {
+    /**
+     * Frames that expired before any client locked them.
+     */
+    uint64_t expired;
+    /**
+     * Frame events not delivered because a client's socket buffer was full,
+     * counted once per client.
+     */
+    uint64_t client_slow;
+} VSLHostDropStats;
+
 /**
  * Encoder profile defining target bitrate for video encoding.
  *
@@ -419,6 +476,30 @@ typedef enum {
      * is confirmed.
      */
     VSL_DEC_HEVC,
+
+    /**
+     * Motion JPEG, a sequence of independently coded JPEG images.
+     *
+     * Only decoded by V4L2 devices that report the MJPG format.
+     * @since 2.6
+     */
+    VSL_DEC_MJPG,
+
+    /**
+     * VP8 codec (RFC 6386).
+     *
+     * Only decoded by V4L2 devices that report the VP80 format.
+     * @since 2.6
+     */
+    VSL_DEC_VP8,
+
+    /**
+     * VP9 codec.
+     *
+     * Only decoded by V4L2 devices that report the VP90 format.
+     * @since 2.6
+     */
+    VSL_DEC_VP9,
 } VSLDecoderCodec;
 
 /**
@@ -477,6 +558,34 @@ typedef enum {
  */
 typedef void (*vsl_frame_cleanup)(VSLFrame* frame);
 
+/**
+ * Maximum size in bytes of a command request or reply exchanged through
+ * vsl_client_send_command().
+ *
+ * @since 2.6
+ */
+#define VSL_COMMAND_MAX 4096
+
+/**
+ * Function pointer definition for servicing client commands, registered with
+ * @ref vsl_host_set_command_handler.
+ *
+ * @param host The host instance
+ * @param request Request bytes sent by the client
+ * @param request_size Size of the request in bytes
+ * @param reply Buffer receiving the reply
+ * @param reply_capacity Capacity of the reply buffer (VSL_COMMAND_MAX)
+ * @param userptr Pointer given to vsl_host_set_command_handler()
+ * @return Size of the reply in bytes, or -1 to report failure to the client
+ * @since 2.6
+ */
+typedef int (*vsl_host_command_handler)(VSLHost*    host,
+                                        const void* request,
+                                        size_t      request_size,
+                                        void*       reply,
+                                        size_t      reply_capacity,
+                                        void*       userptr);
+
 /**
  * Returns the VideoStream Library version string.
  *
@@ -509,8 +618,14 @@ vsl_timestamp();
  * connections from clients. Frames posted to the host are broadcast to all
  * connected clients.
  *
- * @param path UNIX socket path (filesystem or abstract). If path starts with
- *             '/' it creates a filesystem socket, otherwise an abstract socket.
+ * A path starting with '@' names a socket in the Linux abstract namespace:
+ * the '@' stands for the leading NUL byte of the address, and the socket has
+ * no filesystem entry, so it leaves no stale file and needs no shared
+ * directory, but is only reachable from the same network namespace. Other
+ * paths create a socket file, relative to the working directory unless they
+ * start with '/'.
+ *
+ * @param path UNIX socket path, or '@' followed by an abstract socket name
  * @return Pointer to VSLHost object on success, NULL on failure (sets errno)
  * @since 1.0
  * @memberof VSLHost
@@ -676,13 +791,54 @@ VSL_API
 int
 vsl_host_drop(VSLHost* host, VSLFrame* frame);
 
+/**
+ * Reads the host's dropped frame counters.
+ *
+ * Counters accumulate from host creation. Frames expired by
+ * vsl_host_post() and vsl_host_process() are counted as expired only if no
+ * client locked them; frames removed with vsl_host_drop() are not counted.
+ *
+ * @param host The host instance
+ * @param stats Output for the counters
+ * @return 0 on success, -1 on error (sets errno)
+ * @since 2.6
+ * @memberof VSLHost
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_host_drop_stats(VSLHost* host, VSLHostDropStats* stats);
+
+/**
+ * Registers the handler for commands sent by clients.
+ *
+ * Commands arrive on the same socket as frame lock requests and are serviced
+ * by vsl_host_service() and vsl_host_process(), which call the handler with
+ * the host lock held, so the handler must not call other vsl_host functions.
+ * Without a handler clients receive ENOTSUP.
+ *
+ * @param host The host instance
+ * @param handler Command handler, or NULL to remove the current handler
+ * @param userptr Pointer passed to each handler call
+ * @return 0 on success, -1 on error (sets errno)
+ * @since 2.6
+ * @memberof VSLHost
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_host_set_command_handler(VSLHost*                 host,
+                             vsl_host_command_handler handler,
+                             void*                    userptr);
+
 /**
  * Creates a client and connects to the host at the provided path.
  *
  * Establishes a connection to a VSLHost via UNIX domain socket. The client
  * can receive frames broadcast by the host.
  *
- * @param path UNIX socket path matching the host's path
+ * @param path UNIX socket path matching the host's path, including the
+ *             leading '@' of an abstract socket (see vsl_host_init())
  * @param userptr Optional user data pointer (retrievable via
  * vsl_client_userptr)
  * @param reconnect If true, automatically reconnect if connection is lost
@@ -771,6 +927,65 @@ VSL_API
 void
 vsl_client_set_timeout(VSLClient* client, float timeout);
 
+/**
+ * Returns the socket connecting the client to the host.
+ *
+ * The socket becomes readable when the host sends a frame, so it can be
+ * polled alongside other descriptors before calling vsl_client_peek() or
+ * vsl_frame_wait(). The descriptor changes when the client reconnects and
+ * remains owned by the client.
+ *
+ * @param client The client instance
+ * @return Socket file descriptor, or -1 if disconnected (sets errno ENOTCONN)
+ * @since 2.6
+ * @memberof VSLClient
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_client_fd(VSLClient* client);
+
+/**
+ * Returns how many times the client has reconnected to the host.
+ *
+ * The count increases each time the client opens a new connection after
+ * the previous one was closed, whether by vsl_client_disconnect() or by the
+ * host going away. Each connection is a new session: a restarted host
+ * numbers its frames from 1 again, so callers tracking frame serials should
+ * reset their baseline when the count changes.
+ *
+ * @param client The client instance
+ * @return Number of reconnections, or -1 on error (sets errno: EINVAL for a
+ *         NULL client)
+ * @since 2.6
+ * @memberof VSLClient
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int64_t
+vsl_client_reconnects(VSLClient* client);
+
+/**
+ * Returns how many frame events the client discarded while waiting for a
+ * reply from the host.
+ *
+ * vsl_frame_trylock(), vsl_frame_unlock() and vsl_client_send_command() read
+ * their reply from the socket the frames arrive on and discard the frame
+ * events queued ahead of it. Those frames never reach vsl_frame_wait(), so
+ * callers counting dropped frames should include them. The count is not
+ * reset when the client reconnects.
+ *
+ * @param client The client instance
+ * @return Number of discarded frame events, or -1 on error (sets errno:
+ *         EINVAL for a NULL client)
+ * @since 2.6
+ * @memberof VSLClient
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int64_t
+vsl_client_discarded(VSLClient* client);
+
 /**
  * Creates and posts the video frame along with optional user pointer to any
  * arbitrary data.  Typically it would be used for holding a reference to
@@ -879,11 +1094,18 @@ vsl_frame_unalloc(VSLFrame* frame);
  * Associates an existing buffer (typically DmaBuf from camera or hardware
  * accelerator) with the frame. The frame does not take ownership of the FD.
  *
+ * A nonzero @p offset selects a frame inside a larger buffer.  It need not be
+ * page aligned; vsl_frame_mmap() maps the enclosing pages and returns a
+ * pointer to the frame data.  V4L2 codecs import the whole descriptor and
+ * ignore the offset, so frames fed to them should use offset 0.
+ *
  * @param frame Frame to attach buffer to
  * @param fd File descriptor (typically DmaBuf) to attach
- * @param size Buffer size in bytes (0 to use stride*height)
+ * @param size Frame size in bytes starting at @p offset (0 to use
+ *             stride*height)
  * @param offset Byte offset to frame data start (must provide size if offset>0)
- * @return 0 on success, -1 on failure (sets errno)
+ * @return 0 on success, -1 on failure (sets errno, EINVAL if the range runs
+ *         past the end of a file or DmaBuf)
  * @since 1.3
  * @memberof VSLFrame
  */
@@ -949,6 +1171,28 @@ VSL_API
 int
 vsl_frame_copy(VSLFrame* target, VSLFrame* source, const VSLRect* crop);
 
+/**
+ * Rotates the source frame clockwise into the target frame using G2D.
+ *
+ * Both frames must share the pixel format. The target must be the source
+ * size for 180 degrees and have width and height swapped for 90 and 270
+ * degrees. Only DmaBuf frames with physical addresses can be rotated; other
+ * frames, or systems without G2D, fail with ENOTSUP so callers can fall back
+ * to a software rotation.
+ *
+ * @param target Destination frame (receives rotated data)
+ * @param source Source frame to rotate
+ * @param degrees Clockwise rotation: 90, 180 or 270
+ * @return 0 on success, -1 on failure (sets errno: EINVAL for invalid
+ *         rotation or mismatched frames, ENOTSUP if G2D cannot rotate them)
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_frame_rotate(VSLFrame* target, VSLFrame* source, int degrees);
+
 /**
  * Returns the user pointer associated with this frame.
  *
@@ -997,6 +1241,57 @@ VSL_API
 VSLFrame*
 vsl_frame_wait(VSLClient* client, int64_t until);
 
+/**
+ * Reads the metadata of the next queued frame without dequeuing it.
+ *
+ * Does not block. Control messages and expired frames at the head of the
+ * queue are discarded, and a connection closed by the host is closed here
+ * too. A disconnected client created with reconnect enabled first makes one
+ * attempt to reconnect. The next call to vsl_frame_wait() with until=0
+ * returns the peeked frame. The frame is not locked, so the host may still
+ * expire it before it is received.
+ *
+ * @param client The client instance
+ * @param meta Output for the pending frame's metadata
+ * @return 1 if a frame is pending, 0 if none is queued or the client is
+ *         disconnected, -1 on error (sets errno)
+ * @since 2.6
+ * @memberof VSLClient
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_client_peek(VSLClient* client, VSLFrameMeta* meta);
+
+/**
+ * Sends a command to the host and waits for its reply.
+ *
+ * The request is serviced by the handler registered with
+ * vsl_host_set_command_handler(). Frames arriving while waiting for the reply
+ * are discarded, as with vsl_frame_trylock().
+ *
+ * @param client The client instance
+ * @param request Request bytes, at most VSL_COMMAND_MAX
+ * @param request_size Size of the request in bytes
+ * @param reply Buffer receiving the reply, truncated to reply_capacity
+ * @param reply_capacity Capacity of the reply buffer
+ * @return Size of the host's reply in bytes, or -1 on error (sets errno:
+ *         EMSGSIZE if the request is too large, ENOTSUP if the host has no
+ *         handler, EIO if the handler failed, ETIMEDOUT if the host did not
+ *         reply within the timeout set by vsl_client_set_timeout(), ENOTCONN
+ *         if disconnected)
+ * @since 2.6
+ * @memberof VSLClient
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_client_send_command(VSLClient*  client,
+                        const void* request,
+                        size_t      request_size,
+                        void*       reply,
+                        size_t      reply_capacity);
+
 /**
  * Releases the frame, performing required cleanup.
  *
@@ -1126,6 +1421,149 @@ VSL_API
 int64_t
 vsl_frame_dts(const VSLFrame* frame);
 
+/**
+ * Returns the capture timestamp for this frame in nanoseconds.
+ *
+ * The capture time is carried with the frame from host to client so
+ * consumers can measure end-to-end latency as
+ * vsl_frame_timestamp() - vsl_frame_capture_time(). It uses the same
+ * CLOCK_MONOTONIC time base as vsl_timestamp().
+ *
+ * @param frame The frame instance
+ * @return Capture timestamp in nanoseconds, or 0 if not set
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int64_t
+vsl_frame_capture_time(const VSLFrame* frame);
+
+/**
+ * Sets the capture timestamp for this frame in nanoseconds.
+ *
+ * Must be set before the frame is posted with vsl_host_post() for clients
+ * to receive it.
+ *
+ * @param frame The frame instance
+ * @param capture_time Capture timestamp in nanoseconds (CLOCK_MONOTONIC),
+ *                     or 0 to clear
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+void
+vsl_frame_set_capture_time(VSLFrame* frame, int64_t capture_time);
+
+/**
+ * Returns whether the frame is flagged as a keyframe.
+ *
+ * The flag is set by the producer with vsl_frame_set_keyframe() before the
+ * frame is posted and is carried from host to client, so clients joining a
+ * stream mid-way can wait for a decode entry point.
+ *
+ * @param frame The frame instance
+ * @return 1 if the frame is flagged as a keyframe, 0 otherwise
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_frame_keyframe(const VSLFrame* frame);
+
+/**
+ * Flags the frame as a keyframe, or clears the flag.
+ *
+ * Must be set before the frame is posted with vsl_host_post() for clients
+ * to receive it.
+ *
+ * @param frame The frame instance
+ * @param keyframe Non-zero to flag the frame as a keyframe, 0 to clear
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+void
+vsl_frame_set_keyframe(VSLFrame* frame, int keyframe);
+
+/**
+ * Returns the identifier of the source that produced the frame.
+ *
+ * Pipelines that publish frames from several cameras on one stream stamp
+ * each frame with its camera index using vsl_frame_set_source_id(). The
+ * identifier is carried from host to client and through the encoder, so
+ * receivers can demultiplex the combined stream.
+ *
+ * @param frame The frame instance
+ * @param id Output: the source identifier, or 0 if none is set
+ * @return 1 if a source identifier is set, 0 if not, -1 on error (sets
+ *         errno: EINVAL for a NULL frame or id)
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_frame_source_id(const VSLFrame* frame, uint32_t* id);
+
+/**
+ * Sets the identifier of the source that produced the frame, such as the
+ * index of its camera in a multi-camera rig.
+ *
+ * Must be set before the frame is posted with vsl_host_post() for clients
+ * to receive it.
+ *
+ * @param frame The frame instance
+ * @param id Source identifier
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+void
+vsl_frame_set_source_id(VSLFrame* frame, uint32_t id);
+
+/**
+ * Returns the region of the frame intended for display.
+ *
+ * Encoders pad frames to macroblock boundaries, so a decoded frame can be
+ * larger than the picture it carries. The display rectangle is set by the
+ * producer with vsl_frame_set_display_rect() and is carried from host to
+ * client so consumers know which sub-region to show.
+ *
+ * @param frame The frame instance
+ * @param rect Output: the display rectangle, or all zeros if none is set
+ * @return 1 if a display rectangle is set, 0 if not, -1 on error (sets
+ *         errno: EINVAL for a NULL frame or rect)
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_frame_display_rect(const VSLFrame* frame, VSLRect* rect);
+
+/**
+ * Sets the region of the frame intended for display, or clears it.
+ *
+ * Must be set before the frame is posted with vsl_host_post() for clients
+ * to receive it.
+ *
+ * @param frame The frame instance
+ * @param rect The display rectangle, or NULL to clear it
+ * @return 0 on success, -1 on error (sets errno: EINVAL for a NULL frame or
+ *         a rectangle outside the frame)
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_frame_set_display_rect(VSLFrame* frame, const VSLRect* rect);
+
 /**
  * Returns the expiration time for this frame in nanoseconds.
  *
@@ -1190,6 +1628,10 @@ vsl_frame_height(const VSLFrame* frame);
  * Stride is the number of bytes from the start of one row to the next.
  * May be larger than width*bytes_per_pixel due to alignment requirements.
  *
+ * For planar and semi-planar YUV formats such as NV12 the stride is the
+ * luma row stride plus half of it, width*3/2 for tightly packed planes.
+ * Use vsl_frame_planes() for the stride of each plane.
+ *
  * @param frame The frame instance
  * @return Row stride in bytes
  * @since 1.3
@@ -1216,6 +1658,163 @@ VSL_API
 int
 vsl_frame_size(const VSLFrame* frame);
 
+/**
+ * Returns the number of valid bytes in the video frame buffer.
+ *
+ * Encoders set this to the size of the encoded data and, as in earlier
+ * releases, also report it from vsl_frame_size(). Producers posting a
+ * partially filled buffer set it with vsl_frame_set_used_size() while
+ * vsl_frame_size() stays the buffer size. Otherwise it equals
+ * vsl_frame_size(). The value is carried from host to client with the frame.
+ *
+ * @param frame The frame instance
+ * @return Valid payload size in bytes
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+size_t
+vsl_frame_used_size(const VSLFrame* frame);
+
+/**
+ * Sets the number of valid bytes in the video frame buffer.
+ *
+ * Producers of variable-size payloads call this before posting a frame so
+ * clients read only the valid data. Pass 0 to mark the whole buffer valid.
+ *
+ * @param frame The frame instance
+ * @param used Valid payload size in bytes, at most vsl_frame_size()
+ * @return 0 on success, -1 with errno EINVAL if @p used exceeds the buffer
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_frame_set_used_size(VSLFrame* frame, size_t used);
+
+/**
+ * Maximum number of plane descriptors carried by a frame.
+ *
+ * @since 2.6
+ */
+#define VSL_FRAME_MAX_PLANES 3
+
+/**
+ * Layout of one plane of a planar or semi-planar frame, such as the luma
+ * and chroma planes of NV12.
+ *
+ * @since 2.6
+ */
+typedef struct vsl_frame_plane {
+    /**
+     * Byte offset of the plane within the frame's buffer, counted from the
+     * start of the buffer like vsl_frame_offset().
+     */
+    size_t offset;
+    /**
+     * Row stride of the plane in bytes.
+     */
+    int stride;
+    /**
+     * Size of the plane in bytes.
+     */
+    size_t size;
+} VSLFramePlane;
+
+/**
+ * Describes the planes of the frame buffer.
+ *
+ * The descriptors are carried from host to client with the frame, so a
+ * client can locate each plane even when the producer aligned its planes
+ * differently from the defaults derived from the stride. Every plane must
+ * lie within the frame buffer, so set them after vsl_frame_alloc() or
+ * vsl_frame_attach(). Pass a @p count of 0 to clear them.
+ *
+ * @param frame The frame instance
+ * @param planes Array of @p count plane descriptors (may be NULL when
+ *        @p count is 0)
+ * @param count Number of planes, at most VSL_FRAME_MAX_PLANES
+ * @return 0 on success, -1 with errno EINVAL for a NULL frame, a count out
+ *         of range or a plane outside the frame buffer
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_frame_set_planes(VSLFrame* frame, const VSLFramePlane* planes, int count);
+
+/**
+ * Returns the plane descriptors of the frame.
+ *
+ * Copies up to @p max descriptors set with vsl_frame_set_planes(), on the
+ * host or before the frame was posted, into @p planes.
+ *
+ * @param frame The frame instance
+ * @param planes Array receiving up to @p max descriptors (may be NULL when
+ *        @p max is 0)
+ * @param max Capacity of @p planes
+ * @return Number of planes described, which may exceed @p max, 0 if the
+ *         frame has no descriptors, or -1 with errno EINVAL for a NULL frame
+ *         or a negative @p max
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_frame_planes(const VSLFrame* frame, VSLFramePlane* planes, int max);
+
+/**
+ * Returns the byte offset of the frame data within its buffer.
+ *
+ * Non-zero only for frames attached with an offset through
+ * vsl_frame_attach().
+ *
+ * @param frame The frame instance
+ * @return Offset in bytes, or 0 if @p frame is NULL
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+size_t
+vsl_frame_offset(const VSLFrame* frame);
+
+/**
+ * Memory backing a frame, as reported by vsl_frame_memory().
+ *
+ * @since 2.6
+ */
+typedef enum {
+    /** No buffer has been allocated or attached. */
+    VSL_FRAME_MEMORY_NONE = 0,
+    /** DmaBuf allocated from a DMA heap; CPU access requires vsl_frame_sync().
+     */
+    VSL_FRAME_MEMORY_DMABUF = 1,
+    /** POSIX shared memory; coherent, vsl_frame_sync() is a no-op. */
+    VSL_FRAME_MEMORY_SHM = 2,
+    /** Descriptor attached through vsl_frame_attach() or received from a
+       host; vsl_frame_sync() is a no-op. */
+    VSL_FRAME_MEMORY_EXTERNAL = 3,
+} VSLFrameMemory;
+
+/**
+ * Returns the kind of memory backing the frame.
+ *
+ * @param frame The frame instance
+ * @return The frame memory type, VSL_FRAME_MEMORY_NONE if @p frame is NULL
+ *         or has no buffer
+ * @since 2.6
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+VSLFrameMemory
+vsl_frame_memory(const VSLFrame* frame);
+
 /**
  * Returns the file descriptor for this frame.
  *
@@ -1269,141 +1868,464 @@ void*
 vsl_frame_mmap(VSLFrame* frame, size_t* size);
 
 /**
- * Unmaps the frame from the process memory space.
+ * Unmaps the frame from the process memory space.
+ *
+ * Releases the memory mapping created by vsl_frame_mmap(). Should be called
+ * when done accessing frame data.
+ *
+ * @param frame The frame instance to unmap
+ * @since 1.0
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_1_0
+VSL_API
+void
+vsl_frame_munmap(VSLFrame* frame);
+
+/**
+ * Cache synchronization session control for DMA-backed buffers.
+ *
+ * Controls CPU cache coherency for DMA buffers. Automatically called by
+ * mmap/munmap, but can be used manually for in-place frame updates.
+ *
+ * Call with enable=1 before accessing, enable=0 after modifying.
+ * Mode: DMA_BUF_SYNC_READ (CPU reads), DMA_BUF_SYNC_WRITE (CPU writes),
+ *       or DMA_BUF_SYNC_RW (both).
+ *
+ * @param frame The frame object to synchronize
+ * @param enable 1 to start sync session, 0 to end it
+ * @param mode Sync mode: DMA_BUF_SYNC_READ, DMA_BUF_SYNC_WRITE, or
+ * DMA_BUF_SYNC_RW
+ * @return 0 on success, -1 on failure (sets errno)
+ * @since 1.3
+ * @memberof VSLFrame
+ */
+VSL_AVAILABLE_SINCE_1_3
+VSL_API
+int
+vsl_frame_sync(const VSLFrame* frame, int enable, int mode);
+
+/**
+ * Returns a fourcc integer code from the string.
+ *
+ * Converts a 4-character string to FOURCC code. Example: "NV12" ->
+ * VSL_FOURCC('N','V','1','2').
+ *
+ * @param fourcc String containing exactly 4 characters (e.g., "NV12", "YUY2")
+ * @return FOURCC code as uint32_t, or 0 if invalid/unsupported
+ * @since 1.3
+ */
+VSL_AVAILABLE_SINCE_1_3
+VSL_API
+uint32_t
+vsl_fourcc_from_string(const char* fourcc);
+
+/**
+ * @brief Creates VSLEncoder instance
+ *
+ * @param profile VSLEncoderProfile determining encode quality
+ * @param outputFourcc fourcc code defining the codec
+ * @param fps output stream fps
+ * @return VSLEncoder* new encoder instance
+ *
+ * Every encoder instance must be released using vsl_encoder_release
+ *
+ * For Hantro VC8000e encoder initialization is performed when vsl_encode_frame
+ * is called for a first time
+ */
+VSL_AVAILABLE_SINCE_1_3
+VSL_API
+VSLEncoder*
+vsl_encoder_create(VSLEncoderProfile profile, uint32_t outputFourcc, int fps);
+
+/**
+ * @brief Creates VSLEncoder instance with explicit backend selection
+ *
+ * Extended version of vsl_encoder_create() that allows selecting a specific
+ * codec backend. Use this when you need to force V4L2 or Hantro backend.
+ *
+ * @param profile VSLEncoderProfile determining encode quality
+ * @param outputFourcc fourcc code defining the codec (H264 or HEVC)
+ * @param fps output stream fps
+ * @param backend Which backend to use (VSL_CODEC_BACKEND_AUTO, _V4L2, _HANTRO)
+ * @return VSLEncoder* new encoder instance, or NULL if backend unavailable
+ *
+ * @since 2.0
+ */
+VSL_AVAILABLE_SINCE_2_0
+VSL_API
+VSLEncoder*
+vsl_encoder_create_ex(VSLEncoderProfile profile,
+                      uint32_t          outputFourcc,
+                      int               fps,
+                      VSLCodecBackend   backend);
+
+/**
+ * @brief Creates VSLEncoder instance on a specific V4L2 encoder device
+ *
+ * Like vsl_encoder_create() with the V4L2 backend, but opens @p device
+ * instead of auto-detecting the encoder. Systems with several VPU instances
+ * can use this to pin each stream to one of them. The VSL_V4L2_ENCODER_DEV
+ * environment override is not consulted.
+ *
+ * @param device V4L2 encoder device path (e.g. "/dev/video1")
+ * @param profile VSLEncoderProfile determining encode quality
+ * @param outputFourcc fourcc code defining the codec (H264 or HEVC)
+ * @param fps output stream fps
+ * @return VSLEncoder* new encoder instance, or NULL with errno set to
+ *         EINVAL for a NULL @p device or unsupported codec, ENOTSUP if the
+ *         library was built without the V4L2 backend, or the error from
+ *         opening the device
+ *
+ * @since 2.6
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+VSLEncoder*
+vsl_encoder_create_device(const char*       device,
+                          VSLEncoderProfile profile,
+                          uint32_t          outputFourcc,
+                          int               fps);
+
+/**
+ * @brief Destroys VSLEncoder instance
+ *
+ * Frees all resources associated with the encoder, including hardware
+ * resources. Do not use the encoder after calling this function.
+ *
+ * @param encoder VSLEncoder instance to destroy
+ * @since 1.3
+ */
+VSL_AVAILABLE_SINCE_1_3
+VSL_API
+void
+vsl_encoder_release(VSLEncoder* encoder);
+
+/**
+ * @brief Encode frame
+ *
+ * Encodes the source frame into the destination frame using hardware
+ * acceleration (Hantro VPU on i.MX 8M Plus, Wave6 VPU on i.MX 95).
+ * First call initializes the encoder with
+ * the given parameters. Subsequent calls must use identical source/destination
+ * dimensions, formats, and crop region.
+ *
+ * @param encoder VSLEncoder instance
+ * @param source Source frame (raw video data)
+ * @param destination Pre-allocated destination frame (receives encoded data)
+ * @param cropRegion Optional crop region in source coordinates (NULL for no
+ * crop)
+ * @param keyframe Optional output: set to 1 if encoded frame is IDR/keyframe,
+ *                 0 otherwise. Pass NULL to ignore.
+ * @retval 0 on success
+ * @retval -1 on failure (check errno for details)
+ * @since 1.3
+ */
+
+VSL_AVAILABLE_SINCE_1_3
+VSL_API
+int
+vsl_encode_frame(VSLEncoder*    encoder,
+                 VSLFrame*      source,
+                 VSLFrame*      destination,
+                 const VSLRect* cropRegion,
+                 int*           keyframe);
+
+/**
+ * Maximum number of regions accepted by vsl_encoder_set_roi().
+ *
+ * @since 2.6
+ */
+#define VSL_ENCODER_MAX_ROI 8
+
+/**
+ * Region of interest with its own encoding quality.
+ *
+ * @since 2.6
+ */
+typedef struct vsl_encoder_roi {
+    /**
+     * Region in source frame coordinates.
+     */
+    VSLRect rect;
+    /**
+     * QP offset applied to macroblocks in the region, from -51 to 51.
+     * Negative values raise quality, positive values lower it.
+     */
+    int qp_delta;
+} VSLEncoderROI;
+
+/**
+ * @brief Sets encoder regions of interest
+ *
+ * Encodes the given regions with their QP offset relative to the rest of the
+ * frame, so a region can be kept sharp (negative delta) while the bitrate is
+ * spent elsewhere, or degraded (positive delta). Regions replace any
+ * previously set and apply to subsequent frames; pass a count of 0 to clear
+ * them. The encoder may align regions to its macroblock grid.
+ *
+ * ROI encoding is available on the V4L2 backend when the driver exposes the
+ * i.MX VPU ROI control (vsi_v4l2 on i.MX 8M Plus). Other backends and drivers
+ * fail with ENOTSUP.
+ *
+ * @param encoder VSLEncoder instance
+ * @param regions Array of @p count regions (may be NULL when @p count is 0)
+ * @param count Number of regions, at most VSL_ENCODER_MAX_ROI
+ * @retval 0 on success
+ * @retval -1 on failure (sets errno: EINVAL for an invalid region or count,
+ *         ENOTSUP if the backend does not support ROI encoding)
+ * @since 2.6
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_encoder_set_roi(VSLEncoder*          encoder,
+                    const VSLEncoderROI* regions,
+                    int                  count);
+
+/**
+ * @brief Changes the encoder target bitrate
+ *
+ * Overrides the bitrate selected by the encoder profile. Once the encoder is
+ * streaming the new target applies to subsequent frames without a keyframe
+ * or stream restart; before the first frame it replaces the profile bitrate.
+ * The encoder's rate control converges on the new target over a few frames.
+ *
+ * Runtime bitrate changes are available on the V4L2 backend. The Hantro
+ * backend fails with ENOTSUP.
+ *
+ * @param encoder VSLEncoder instance
+ * @param kbps Target bitrate in kilobits per second, from 1 to 2147483
+ * @retval 0 on success
+ * @retval -1 on failure (sets errno: EINVAL for a NULL encoder or an out of
+ *         range bitrate, ENOTSUP if the backend cannot change its bitrate)
+ * @since 2.6
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_encoder_set_bitrate(VSLEncoder* encoder, uint32_t kbps);
+
+/**
+ * Encoder rate control mode, selected with vsl_encoder_set_rate_control().
+ *
+ * @since 2.6
+ */
+typedef enum {
+    /**
+     * Constant bitrate: the encoder holds the target bitrate, varying
+     * quality with scene complexity.
+     */
+    VSL_ENCODER_RC_CBR = 0,
+
+    /**
+     * Variable bitrate: the encoder averages the target bitrate, spending
+     * more bits on complex scenes.
+     */
+    VSL_ENCODER_RC_VBR = 1,
+
+    /**
+     * Constant QP: frame level rate control is disabled and every frame is
+     * quantized with a fixed QP, ignoring the target bitrate.
+     */
+    VSL_ENCODER_RC_CONSTANT_QP = 2,
+} VSLEncoderRateControl;
+
+/**
+ * @brief Selects the encoder rate control mode
  *
- * Releases the memory mapping created by vsl_frame_mmap(). Should be called
- * when done accessing frame data.
+ * Replaces the driver's default rate control, which targets the profile
+ * bitrate, with @p mode. VSL_ENCODER_RC_CBR and VSL_ENCODER_RC_VBR target the
+ * bitrate set by the profile or vsl_encoder_set_bitrate().
+ * VSL_ENCODER_RC_CONSTANT_QP encodes every frame at @p qp instead, trading a
+ * predictable bitrate for a predictable quality; low QP values approach
+ * visually lossless output for analytics and archival pipelines. The H.264
+ * and HEVC QP range is 0 (finest) to 51 (coarsest); @p qp is ignored by the
+ * bitrate modes.
  *
- * @param frame The frame instance to unmap
- * @since 1.0
- * @memberof VSLFrame
+ * Call before the first frame: most drivers reject rate control changes
+ * while streaming with EBUSY. Rate control modes are available on the V4L2
+ * backend, constant QP only when the driver implements
+ * V4L2_CID_MPEG_VIDEO_FRAME_RC_ENABLE. The Hantro backend fails with
+ * ENOTSUP.
+ *
+ * @param encoder VSLEncoder instance
+ * @param mode Rate control mode
+ * @param qp Quantization parameter for VSL_ENCODER_RC_CONSTANT_QP, from 0
+ *        to 51
+ * @retval 0 on success
+ * @retval -1 on failure (sets errno: EINVAL for a NULL encoder, an unknown
+ *         mode or an out of range QP, ENOTSUP if the backend or driver lacks
+ *         the mode, or the driver's error from VIDIOC_S_CTRL)
+ * @since 2.6
  */
-VSL_AVAILABLE_SINCE_1_0
+VSL_AVAILABLE_SINCE_2_6
 VSL_API
-void
-vsl_frame_munmap(VSLFrame* frame);
+int
+vsl_encoder_set_rate_control(VSLEncoder*           encoder,
+                             VSLEncoderRateControl mode,
+                             int                   qp);
 
 /**
- * Cache synchronization session control for DMA-backed buffers.
+ * H.264 entropy coding mode, selected with vsl_encoder_set_entropy_mode().
  *
- * Controls CPU cache coherency for DMA buffers. Automatically called by
- * mmap/munmap, but can be used manually for in-place frame updates.
+ * @since 2.6
+ */
+typedef enum {
+    /**
+     * Context-adaptive variable-length coding, the only mode of the
+     * Baseline profile. Cheaper to decode and supported by every decoder.
+     */
+    VSL_ENCODER_ENTROPY_CAVLC = 0,
+
+    /**
+     * Context-adaptive binary arithmetic coding, available from the Main
+     * profile. Typically 10-15% smaller at the same quality, at a higher
+     * decoding cost.
+     */
+    VSL_ENCODER_ENTROPY_CABAC = 1,
+} VSLEncoderEntropyMode;
+
+/**
+ * @brief Selects the H.264 entropy coding mode
  *
- * Call with enable=1 before accessing, enable=0 after modifying.
- * Mode: DMA_BUF_SYNC_READ (CPU reads), DMA_BUF_SYNC_WRITE (CPU writes),
- *       or DMA_BUF_SYNC_RW (both).
+ * Replaces the driver's default entropy coding, which is CABAC for the High
+ * profile streams the V4L2 backend produces, with @p mode. Choose CAVLC for
+ * decoders limited to the Baseline profile or to save decoding power.
  *
- * @param frame The frame object to synchronize
- * @param enable 1 to start sync session, 0 to end it
- * @param mode Sync mode: DMA_BUF_SYNC_READ, DMA_BUF_SYNC_WRITE, or
- * DMA_BUF_SYNC_RW
- * @return 0 on success, -1 on failure (sets errno)
- * @since 1.3
- * @memberof VSLFrame
+ * Call before the first frame: most drivers reject the change while
+ * streaming with EBUSY. Available on the V4L2 backend for H.264 when the
+ * driver implements V4L2_CID_MPEG_VIDEO_H264_ENTROPY_MODE. HEVC always
+ * codes with CABAC, so only VSL_ENCODER_ENTROPY_CABAC is accepted for it.
+ * The Hantro backend fails with ENOTSUP.
+ *
+ * @param encoder VSLEncoder instance
+ * @param mode Entropy coding mode
+ * @retval 0 on success
+ * @retval -1 on failure (sets errno: EINVAL for a NULL encoder, an unknown
+ *         mode or CAVLC with HEVC, ENOTSUP if the backend or driver lacks
+ *         the control, or the driver's error from VIDIOC_S_CTRL)
+ * @since 2.6
  */
-VSL_AVAILABLE_SINCE_1_3
+VSL_AVAILABLE_SINCE_2_6
 VSL_API
 int
-vsl_frame_sync(const VSLFrame* frame, int enable, int mode);
+vsl_encoder_set_entropy_mode(VSLEncoder* encoder, VSLEncoderEntropyMode mode);
 
 /**
- * Returns a fourcc integer code from the string.
+ * @brief Marks the next encoded frame as the long-term reference
  *
- * Converts a 4-character string to FOURCC code. Example: "NV12" ->
- * VSL_FOURCC('N','V','1','2').
+ * The next frame passed to vsl_encode_frame() is stored as the encoder's
+ * long-term reference (LTR), replacing any previous one. Unlike short-term
+ * references it is kept across the rest of the GOP, so after packet loss a
+ * frame encoded with vsl_encoder_use_ltr() lets the decoder recover without
+ * a full IDR, provided the receiver got the LTR frame itself.
  *
- * @param fourcc String containing exactly 4 characters (e.g., "NV12", "YUY2")
- * @return FOURCC code as uint32_t, or 0 if invalid/unsupported
- * @since 1.3
+ * LTR control is available on the V4L2 backend when the driver implements
+ * the V4L2_CID_MPEG_VIDEO_FRAME_LTR_INDEX and
+ * V4L2_CID_MPEG_VIDEO_USE_LTR_FRAMES controls; the encoder then reserves one
+ * LTR slot when it starts. Other backends and drivers fail with ENOTSUP.
+ *
+ * @param encoder VSLEncoder instance
+ * @retval 0 on success
+ * @retval -1 on failure (sets errno: EINVAL for a NULL encoder, ENOTSUP if
+ *         the backend or driver has no LTR control)
+ * @since 2.6
  */
-VSL_AVAILABLE_SINCE_1_3
+VSL_AVAILABLE_SINCE_2_6
 VSL_API
-uint32_t
-vsl_fourcc_from_string(const char* fourcc);
+int
+vsl_encoder_mark_ltr(VSLEncoder* encoder);
 
 /**
- * @brief Creates VSLEncoder instance
+ * @brief Encodes the next frame against the long-term reference
  *
- * @param profile VSLEncoderProfile determining encode quality
- * @param outputFourcc fourcc code defining the codec
- * @param fps output stream fps
- * @return VSLEncoder* new encoder instance
+ * The next frame passed to vsl_encode_frame() is predicted only from the
+ * frame marked with vsl_encoder_mark_ltr(), discarding the short-term
+ * references a receiver may have lost. Call it when a client reports loss
+ * after the LTR frame was delivered.
  *
- * Every encoder instance must be released using vsl_encoder_release
+ * Support is the same as for vsl_encoder_mark_ltr().
  *
- * For Hantro VC8000e encoder initialization is performed when vsl_encode_frame
- * is called for a first time
+ * @param encoder VSLEncoder instance
+ * @retval 0 on success
+ * @retval -1 on failure (sets errno: EINVAL for a NULL encoder or when no
+ *         frame has been marked, ENOTSUP if the backend or driver has no LTR
+ *         control)
+ * @since 2.6
  */
-VSL_AVAILABLE_SINCE_1_3
+VSL_AVAILABLE_SINCE_2_6
 VSL_API
-VSLEncoder*
-vsl_encoder_create(VSLEncoderProfile profile, uint32_t outputFourcc, int fps);
+int
+vsl_encoder_use_ltr(VSLEncoder* encoder);
 
 /**
- * @brief Creates VSLEncoder instance with explicit backend selection
+ * @brief Sets the keyframe interval (GOP size) in frames
  *
- * Extended version of vsl_encoder_create() that allows selecting a specific
- * codec backend. Use this when you need to force V4L2 or Hantro backend.
+ * Every @p frames-th encoded frame is an IDR. Once the encoder is streaming
+ * the new interval applies without a stream restart and does not force a
+ * keyframe itself: the next IDR comes when the new interval has elapsed
+ * since the last one, as counted by the driver. Before the first frame it
+ * replaces the default interval of one second of frames.
  *
- * @param profile VSLEncoderProfile determining encode quality
- * @param outputFourcc fourcc code defining the codec (H264 or HEVC)
- * @param fps output stream fps
- * @param backend Which backend to use (VSL_CODEC_BACKEND_AUTO, _V4L2, _HANTRO)
- * @return VSLEncoder* new encoder instance, or NULL if backend unavailable
+ * Runtime changes are available on the V4L2 backend when the driver accepts
+ * V4L2_CID_MPEG_VIDEO_GOP_SIZE while streaming. The Hantro backend only
+ * accepts a new interval before the first frame and fails with ENOTSUP
+ * afterwards.
  *
- * @since 2.0
+ * @param encoder VSLEncoder instance
+ * @param frames Frames from one IDR to the next, from 1 to INT32_MAX
+ * @retval 0 on success
+ * @retval -1 on failure (sets errno: EINVAL for a NULL encoder or an out of
+ *         range interval, ENOTSUP if the backend cannot change its interval,
+ *         or the driver's error from VIDIOC_S_CTRL)
+ * @since 2.6
  */
-VSL_AVAILABLE_SINCE_2_0
+VSL_AVAILABLE_SINCE_2_6
 VSL_API
-VSLEncoder*
-vsl_encoder_create_ex(VSLEncoderProfile profile,
-                      uint32_t          outputFourcc,
-                      int               fps,
-                      VSLCodecBackend   backend);
+int
+vsl_encoder_set_gop_size(VSLEncoder* encoder, uint32_t frames);
 
 /**
- * @brief Destroys VSLEncoder instance
+ * @brief Returns the keyframe interval (GOP size) in frames
  *
- * Frees all resources associated with the encoder, including hardware
- * resources. Do not use the encoder after calling this function.
+ * Reports the interval set with vsl_encoder_set_gop_size(), or the default
+ * of one second of frames at the encoder's frame rate.
  *
- * @param encoder VSLEncoder instance to destroy
- * @since 1.3
+ * @param encoder VSLEncoder instance
+ * @return Frames from one IDR to the next, or -1 on error (sets errno:
+ *         EINVAL for a NULL encoder)
+ * @since 2.6
  */
-VSL_AVAILABLE_SINCE_1_3
+VSL_AVAILABLE_SINCE_2_6
 VSL_API
-void
-vsl_encoder_release(VSLEncoder* encoder);
+int
+vsl_encoder_gop_size(const VSLEncoder* encoder);
 
 /**
- * @brief Encode frame
+ * @brief Drains a frame still held by the encoder at end of stream
  *
- * Encodes the source frame into the destination frame using hardware
- * acceleration (Hantro VPU on i.MX 8M Plus, Wave6 VPU on i.MX 95).
- * First call initializes the encoder with
- * the given parameters. Subsequent calls must use identical source/destination
- * dimensions, formats, and crop region.
+ * A V4L2 encoder may return from vsl_encode_frame() before the frame is
+ * encoded, leaving it to come out of a later call. When the stream ends,
+ * call vsl_encoder_flush() until it returns 0 to collect those frames so the
+ * output ends on a complete frame. Each call writes at most one encoded
+ * frame to @p destination, as vsl_encode_frame() does.
+ *
+ * The first call stops the encoder; vsl_encode_frame() fails with EPIPE
+ * afterwards. Backends that encode synchronously return 0 straight away.
  *
  * @param encoder VSLEncoder instance
- * @param source Source frame (raw video data)
- * @param destination Pre-allocated destination frame (receives encoded data)
- * @param cropRegion Optional crop region in source coordinates (NULL for no
- * crop)
- * @param keyframe Optional output: set to 1 if encoded frame is IDR/keyframe,
- *                 0 otherwise. Pass NULL to ignore.
- * @retval 0 on success
- * @retval -1 on failure (check errno for details)
- * @since 1.3
+ * @param destination Frame from vsl_encoder_new_output_frame() receiving the
+ *                    encoded data
+ * @param keyframe Output: 1 if the drained frame is a keyframe (may be NULL)
+ * @return Encoded size in bytes, 0 once the encoder is drained, or -1 on
+ *         error (sets errno: EINVAL for a NULL encoder or destination)
+ * @since 2.6
  */
-
-VSL_AVAILABLE_SINCE_1_3
+VSL_AVAILABLE_SINCE_2_6
 VSL_API
 int
-vsl_encode_frame(VSLEncoder*    encoder,
-                 VSLFrame*      source,
-                 VSLFrame*      destination,
-                 const VSLRect* cropRegion,
-                 int*           keyframe);
+vsl_encoder_flush(VSLEncoder* encoder, VSLFrame* destination, int* keyframe);
 
 /**
  * @brief Creates a new output frame for encoder
@@ -1524,6 +2446,141 @@ VSL_API
 int
 vsl_camera_mirror_v(const vsl_camera* ctx, bool mirror);
 
+/**
+ * Sets the sensor crop rectangle (region of interest) for capture.
+ *
+ * Uses VIDIOC_S_SELECTION with the V4L2_SEL_TGT_CROP target. The rectangle
+ * is validated against the V4L2_SEL_TGT_CROP_BOUNDS reported by the driver,
+ * which may further adjust it to hardware constraints; on success @p rect
+ * holds the rectangle actually applied. Depending on the driver the capture
+ * format may change to the cropped size or the crop may be scaled back to
+ * the configured format; the stride of the camera buffers is refreshed and
+ * vsl_camera_get_format() reports the resulting size.
+ *
+ * @param ctx Camera context
+ * @param rect Crop rectangle in sensor coordinates, updated with the
+ *             rectangle applied by the driver
+ * @return 0 on success, -1 on failure (sets errno: EINVAL if the rectangle
+ *         is empty or outside the crop bounds, ENOTTY if the driver does not
+ *         support cropping)
+ * @since 2.6
+ * @memberof VSLCamera
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_camera_set_selection(const vsl_camera* ctx, VSLRect* rect);
+
+/**
+ * Returns the current sensor crop rectangle.
+ *
+ * Uses VIDIOC_G_SELECTION with the V4L2_SEL_TGT_CROP target.
+ *
+ * @param ctx Camera context
+ * @param rect Receives the crop rectangle in sensor coordinates
+ * @return 0 on success, -1 on failure (sets errno: ENOTTY if the driver does
+ *         not support cropping)
+ * @since 2.6
+ * @memberof VSLCamera
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_camera_get_selection(const vsl_camera* ctx, VSLRect* rect);
+
+/**
+ * Returns the current capture format.
+ *
+ * Uses VIDIOC_G_FMT. The format is negotiated by vsl_camera_init_device() but
+ * may change afterwards, for example when vsl_camera_set_selection() crops
+ * on a driver which delivers the crop at its own size.
+ *
+ * @param ctx Camera context
+ * @param width Receives the frame width in pixels, may be NULL
+ * @param height Receives the frame height in pixels, may be NULL
+ * @param fourcc Receives the pixel format, may be NULL
+ * @return 0 on success, -1 on failure (sets errno)
+ * @since 2.6
+ * @memberof VSLCamera
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_camera_get_format(const vsl_camera* ctx,
+                      int*              width,
+                      int*              height,
+                      u_int32_t*        fourcc);
+
+/**
+ * Requests the field order used when the capture format is negotiated.
+ *
+ * Sets the `field` member passed to VIDIOC_S_FMT by the next
+ * vsl_camera_init_device() call, so must be called before it. The value is
+ * a raw V4L2 `V4L2_FIELD_*` constant from `<linux/videodev2.h>`, such as
+ * `V4L2_FIELD_NONE` for progressive frames or `V4L2_FIELD_INTERLACED_TB`
+ * for interleaved fields with the top field first. The default
+ * `V4L2_FIELD_ANY` (0) lets the driver choose. Drivers may substitute the
+ * field order they support; use vsl_camera_field() to read the result.
+ *
+ * @param ctx Camera context from vsl_camera_open_device()
+ * @param field Requested `V4L2_FIELD_*` value
+ * @return 0 on success, -1 on failure (sets errno: EINVAL if @p ctx is NULL
+ *         or @p field is not a V4L2 field order)
+ * @since 2.6
+ * @memberof VSLCamera
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_camera_set_field(vsl_camera* ctx, uint32_t field);
+
+/**
+ * Requests how camera buffers are shared with other devices.
+ *
+ * Must be called before vsl_camera_init_device(). Capture buffers are always
+ * allocated by the driver and memory mapped; @p memory selects what else is
+ * provided:
+ *
+ * - `VSL_V4L2_MEM_DMABUF` exports every buffer as a DMABUF for zero-copy
+ *   hand-off to an encoder or GPU, and vsl_camera_init_device() fails if the
+ *   driver cannot export one.
+ * - `VSL_V4L2_MEM_MMAP` skips the export for CPU-only access, so
+ *   vsl_camera_buffer_dma_fd() returns -1.
+ *
+ * Without this call buffers are exported when the driver allows it. The
+ * request is only recorded here; vsl_camera_init_device() fails with ENOTSUP
+ * if the driver cannot provide it.
+ *
+ * @param ctx Camera context from vsl_camera_open_device()
+ * @param memory A single `VSLMemoryType` value
+ * @return 0 on success, -1 on failure (sets errno: EINVAL if @p ctx is NULL
+ *         or @p memory is not a single memory type, ENOTSUP for
+ *         `VSL_V4L2_MEM_USERPTR`)
+ * @since 2.6
+ * @memberof VSLCamera
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_camera_set_memory(vsl_camera* ctx, uint32_t memory);
+
+/**
+ * Returns the field order negotiated for the camera.
+ *
+ * Captured from VIDIOC_S_FMT during vsl_camera_init_device() and constant
+ * until vsl_camera_uninit_device().
+ *
+ * @param ctx Camera context
+ * @return `V4L2_FIELD_*` enum value, or 0 (`V4L2_FIELD_ANY`) if `ctx` is
+ *         NULL or the device has not been initialized.
+ * @since 2.6
+ * @memberof VSLCamera
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+uint32_t
+vsl_camera_field(const vsl_camera* ctx);
+
 /**
  * Starts the camera stream.
  *
@@ -1939,9 +2996,11 @@ vsl_camera_enum_mplane_fmts(const vsl_camera* ctx, uint32_t* codes, int size);
  * backend (V4L2 preferred, Hantro fallback). The decoder is initialized on the
  * first call to vsl_decode_frame().
  *
- * @param codec Codec type: VSL_DEC_H264 or VSL_DEC_HEVC
+ * @param codec Codec type: VSL_DEC_H264, VSL_DEC_HEVC, VSL_DEC_MJPG,
+ *              VSL_DEC_VP8 or VSL_DEC_VP9
  * @param fps Expected frame rate (used for buffer management)
- * @return Pointer to VSLDecoder instance, or NULL on failure
+ * @return Pointer to VSLDecoder instance, or NULL on failure. errno is set
+ *         to ENOTSUP when no hardware decoder supports the codec.
  * @since 1.4
  */
 VSL_AVAILABLE_SINCE_1_4
@@ -1955,11 +3014,14 @@ vsl_decoder_create(VSLDecoderCodec codec, int fps);
  * Creates a hardware video decoder with explicit backend selection. Use this
  * when you need to force a specific backend instead of auto-detection.
  *
- * @param codec Codec fourcc: VSL_FOURCC('H','2','6','4') or
- *              VSL_FOURCC('H','E','V','C')
+ * @param codec Codec fourcc: VSL_FOURCC('H','2','6','4'),
+ *              VSL_FOURCC('H','E','V','C') or, with the V4L2 backend,
+ *              VSL_FOURCC('M','J','P','G'), VSL_FOURCC('V','P','8','0') or
+ *              VSL_FOURCC('V','P','9','0')
  * @param fps Expected frame rate (used for buffer management)
  * @param backend Backend to use (AUTO, HANTRO, or V4L2)
- * @return Pointer to VSLDecoder instance, or NULL on failure
+ * @return Pointer to VSLDecoder instance, or NULL on failure. errno is set
+ *         to ENOTSUP when the backend has no decoder for the codec.
  * @since 2.0
  */
 VSL_AVAILABLE_SINCE_2_0
@@ -2044,6 +3106,83 @@ VSL_API
 VSLRect
 vsl_decoder_crop(const VSLDecoder* decoder);
 
+/**
+ * @brief Requests the pixel format of decoded frames
+ *
+ * Asks the decoder to write decoded frames in @p fourcc instead of NV12, such
+ * as VSL_FOURCC('R','G','B','3') for display or inference consumers, saving a
+ * separate conversion pass. The format is negotiated when the stream headers
+ * are parsed by the first vsl_decode_frame() calls. If the hardware cannot
+ * produce it the decoder prints a warning and keeps NV12, so callers should
+ * check vsl_frame_fourcc() of the decoded frames.
+ *
+ * Output format selection is available on the V4L2 backend. The Hantro
+ * backend fails with ENOTSUP.
+ *
+ * @param decoder VSLDecoder instance
+ * @param fourcc Requested pixel format of decoded frames
+ * @retval 0 on success
+ * @retval -1 on failure (sets errno: EINVAL for a NULL decoder or zero
+ *         fourcc, EBUSY once the decoder is initialized, ENOTSUP if the
+ *         backend cannot select its output format)
+ * @since 2.6
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_decoder_set_output_fourcc(VSLDecoder* decoder, uint32_t fourcc);
+
+/**
+ * @brief Sets the number of decoded frame buffers
+ *
+ * Sizes the pool of buffers the decoder writes decoded frames into. A
+ * smaller pool saves memory, a larger one lets callers hold on to more
+ * decoded frames before the decoder stalls. The driver may raise the count
+ * to the minimum the stream needs when the headers are parsed by the first
+ * vsl_decode_frame() calls.
+ *
+ * Buffer count selection is available on the V4L2 backend. The Hantro
+ * backend fails with ENOTSUP.
+ *
+ * @param decoder VSLDecoder instance
+ * @param count Number of buffers from 1 to 8, or 0 for the default of 8
+ * @retval 0 on success
+ * @retval -1 on failure (sets errno: EINVAL for a NULL decoder or an out of
+ *         range count, EBUSY once the decoder is initialized, ENOTSUP if the
+ *         backend cannot size its pool)
+ * @since 2.6
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_decoder_set_output_buffers(VSLDecoder* decoder, int count);
+
+/**
+ * @brief Enables low-latency decoding
+ *
+ * Returns each frame as soon as it is decoded instead of holding frames back
+ * to reorder B-frames into presentation order. This removes the reorder
+ * delay for streams without B-frames, such as those from the VideoStream
+ * encoder; streams with B-frames are then returned in decode order. Must be
+ * called before the first vsl_decode_frame().
+ *
+ * Low-latency decoding is available on the V4L2 backend when the driver
+ * implements V4L2_CID_MPEG_VIDEO_DEC_DISPLAY_DELAY_ENABLE. Other backends and
+ * drivers fail with ENOTSUP.
+ *
+ * @param decoder VSLDecoder instance
+ * @param enable Non-zero to enable, 0 to restore the default
+ * @retval 0 on success
+ * @retval -1 on failure (sets errno: EINVAL for a NULL decoder, EBUSY once
+ *         decoding has started, ENOTSUP if the backend or driver cannot
+ *         change its display delay)
+ * @since 2.6
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+int
+vsl_decoder_set_low_latency(VSLDecoder* decoder, int enable);
+
 /**
  * @brief Destroys VSLDecoder instance
  *
@@ -2711,6 +3850,149 @@ VSL_API
 char*
 vsl_v4l2_fourcc_to_string(uint32_t fourcc, char buf[5]);
 
+/* ============================================================================
+ * Media Controller Topology
+ * ============================================================================
+ */
+
+/**
+ * @struct VSLMediaEntity
+ * @brief Entity in a media controller graph
+ *
+ * An entity is a hardware or software block in the pipeline such as a
+ * sensor, CSI receiver, ISI channel or video capture node. Entities that
+ * are exposed to userspace through a `/dev/video*` or `/dev/v4l-subdev*`
+ * node carry the resolved node path in @ref devnode.
+ *
+ * @since 2.6
+ */
+typedef struct {
+    /** Entity ID, unique within the media device */
+    uint32_t id;
+    /** Entity name (e.g., "mxc_isi.0", "csidev-4ad30000.csi") */
+    char name[64];
+    /** Entity function (`MEDIA_ENT_F_*`) */
+    uint32_t function;
+    /** Entity flags (`MEDIA_ENT_FL_*`) */
+    uint32_t flags;
+    /** Device node path (e.g., "/dev/video0"), empty if the entity has none */
+    char devnode[64];
+} VSLMediaEntity;
+
+/**
+ * @struct VSLMediaLink
+ * @brief Data link between two entity pads
+ *
+ * @since 2.6
+ */
+typedef struct {
+    /** Source entity ID */
+    uint32_t source_entity;
+    /** Source pad index on the source entity */
+    uint32_t source_pad;
+    /** Sink entity ID */
+    uint32_t sink_entity;
+    /** Sink pad index on the sink entity */
+    uint32_t sink_pad;
+    /** Link flags (`MEDIA_LNK_FL_ENABLED`, `MEDIA_LNK_FL_IMMUTABLE`) */
+    uint32_t flags;
+} VSLMediaLink;
+
+/**
+ * @struct VSLMediaDevice
+ * @brief Media controller device and its topology
+ *
+ * @note This structure is allocated by vsl_media_enumerate() and must be
+ *       freed with vsl_media_device_list_free().
+ *
+ * @since 2.6
+ */
+typedef struct {
+    /** Device path (e.g., "/dev/media0") */
+    char path[64];
+    /** Driver name (e.g., "mxc-isi") */
+    char driver[16];
+    /** Device model (e.g., "FSL Capture Media Device") */
+    char model[32];
+    /** Bus info (e.g., "platform:4ad50000.isi") */
+    char bus_info[32];
+    /** Array of entities in the graph */
+    VSLMediaEntity* entities;
+    /** Number of valid entries in entities */
+    size_t num_entities;
+    /** Array of data links between entity pads */
+    VSLMediaLink* links;
+    /** Number of valid entries in links */
+    size_t num_links;
+} VSLMediaDevice;
+
+/**
+ * @struct VSLMediaDeviceList
+ * @brief List of discovered media controller devices
+ *
+ * Returned by vsl_media_enumerate(). Must be freed with
+ * vsl_media_device_list_free().
+ *
+ * @since 2.6
+ */
+typedef struct {
+    /** Array of media device descriptors */
+    VSLMediaDevice* devices;
+    /** Number of devices in the array */
+    size_t count;
+} VSLMediaDeviceList;
+
+/**
+ * @brief Enumerates media controller devices and their topology
+ *
+ * Scans `/dev/media*` and reads each device's graph using
+ * `MEDIA_IOC_DEVICE_INFO` and `MEDIA_IOC_G_TOPOLOGY`. Entities exposed
+ * through a device node are associated with their `/dev` path, so the
+ * `/dev/video*` nodes returned by vsl_v4l2_enumerate() can be located in
+ * the camera pipeline.
+ *
+ * @return Media device list on success (caller must free with
+ * vsl_media_device_list_free()), or NULL on error
+ * @retval NULL Failed to enumerate (check errno)
+ * @retval errno=ENOMEM Out of memory
+ * @retval errno=ENOTSUP Media controller API not available
+ *
+ * @note Devices that are busy or inaccessible are silently skipped.
+ * @note The returned list may be empty if no media devices are found.
+ *
+ * @par Example
+ * @code{.c}
+ * VSLMediaDeviceList* list = vsl_media_enumerate();
+ * for (size_t i = 0; list && i < list->count; i++) {
+ *     VSLMediaDevice* media = &list->devices[i];
+ *     for (size_t j = 0; j < media->num_entities; j++) {
+ *         printf("%s: %s %s\n", media->path, media->entities[j].name,
+ *                media->entities[j].devnode);
+ *     }
+ * }
+ * vsl_media_device_list_free(list);
+ * @endcode
+ *
+ * @see vsl_media_device_list_free
+ * @since 2.6
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+VSLMediaDeviceList*
+vsl_media_enumerate(void);
+
+/**
+ * @brief Frees a media device list returned by vsl_media_enumerate()
+ *
+ * @param[in] list Media device list to free (NULL is safe and does nothing)
+ *
+ * @since 2.6
+ */
+VSL_AVAILABLE_SINCE_2_6
+VSL_API
+void
+vsl_media_device_list_free(VSLMediaDeviceList* list);
+
 /** @} */ /* end of v4l2_device group */
 
 #ifdef __cplusplus
@@ -55,6 +55,8 @@ struct vsl_client {
     float              sock_timeout_secs;
     bool               reconnect;
     bool               is_reconnecting;
+    int64_t            reconnects;
+    int64_t            discarded;
 };
 
 static float  DEFAULT_SOCK_TO_SECS = 1.0F;
@@ -97,7 +99,10 @@ try_reconnect(VSLClient* client, uint8_t* wait_stage)
 
     // Try to get a new socket
     int sock = get_socket(client->sock_addr, client->sock_addrlen);
-    if (sock >= 0) { client->sock = sock; }
+    if (sock >= 0) {
+        client->sock = sock;
+        client->reconnects++;
+    }
 
     return true;
 }
@@ -186,7 +191,10 @@ handle_connection_closed(VSLClient* client, uint8_t* wait_stage)
     usleep(wait_stages_ms[*wait_stage] * 1000);
 
     int sock = get_socket(client->sock_addr, client->sock_addrlen);
-    if (sock >= 0) { client->sock = sock; }
+    if (sock >= 0) {
+        client->sock = sock;
+        client->reconnects++;
+    }
 
     return true;
 }
@@ -210,6 +218,10 @@ vsl_frame_strerror(enum vsl_frame_error err)
         return "invalid control";
     case VSL_FRAME_TOO_MANY_FRAMES_LOCKED:
         return "too many frames locked";
+    case VSL_FRAME_ERROR_NO_COMMAND_HANDLER:
+        return "no command handler";
+    case VSL_FRAME_ERROR_COMMAND_FAILED:
+        return "command failed";
     }
 
     return "unknown error";
@@ -227,6 +239,10 @@ vsl_frame_errno(enum vsl_frame_error err)
         return EBADMSG;
     case VSL_FRAME_TOO_MANY_FRAMES_LOCKED:
         return ENOLCK;
+    case VSL_FRAME_ERROR_NO_COMMAND_HANDLER:
+        return ENOTSUP;
+    case VSL_FRAME_ERROR_COMMAND_FAILED:
+        return EIO;
     }
 
     return EINVAL;
@@ -396,6 +412,8 @@ vsl_client_init(const char* path, void* userptr, bool reconnect)
     client->sock_addrlen      = addrlen;
     client->sock_timeout_secs = DEFAULT_SOCK_TO_SECS;
     client->is_reconnecting   = false;
+    client->reconnects        = 0;
+    client->discarded         = 0;
 
     create_timer(client);
 
@@ -445,6 +463,68 @@ vsl_client_path(const VSLClient* client)
     return NULL;
 }
 
+VSL_API
+int
+vsl_client_fd(VSLClient* client)
+{
+    if (!client) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    int err = pthread_mutex_lock(&client->lock);
+    if (err) {
+        errno = err;
+        return -1;
+    }
+
+    int sock = client->sock;
+    pthread_mutex_unlock(&client->lock);
+
+    if (sock < 0) { errno = ENOTCONN; }
+    return sock;
+}
+
+VSL_API
+int64_t
+vsl_client_reconnects(VSLClient* client)
+{
+    if (!client) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    int err = pthread_mutex_lock(&client->lock);
+    if (err) {
+        errno = err;
+        return -1;
+    }
+
+    int64_t reconnects = client->reconnects;
+    pthread_mutex_unlock(&client->lock);
+    return reconnects;
+}
+
+VSL_API
+int64_t
+vsl_client_discarded(VSLClient* client)
+{
+    if (!client) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    int err = pthread_mutex_lock(&client->lock);
+    if (err) {
+        errno = err;
+        return -1;
+    }
+
+    int64_t discarded = client->discarded;
+    pthread_mutex_unlock(&client->lock);
+    return discarded;
+}
+
 VSL_API
 void
 vsl_client_set_timeout(VSLClient* client, float timeout)
@@ -459,6 +539,203 @@ vsl_client_set_timeout(VSLClient* client, float timeout)
     create_timer(client);
 }
 
+VSL_API
+int
+vsl_client_peek(VSLClient* client, VSLFrameMeta* meta)
+{
+    struct vsl_frame_event event;
+    struct vsl_aux         aux;
+    struct msghdr          msg;
+    struct iovec           iov;
+    ssize_t                ret = 0;
+
+    if (!client || !meta) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    int err = pthread_mutex_lock(&client->lock);
+    if (err) {
+        errno = err;
+        return -1;
+    }
+
+    // One connection attempt, so callers polling vsl_client_fd() recover
+    // from a host restart without blocking in vsl_frame_wait().
+    if (client->sock == SOCKET_ERROR && client->reconnect) {
+        int sock = get_socket(client->sock_addr, client->sock_addrlen);
+        if (sock >= 0) {
+            client->sock = sock;
+            client->reconnects++;
+        }
+    }
+
+    while (client->sock >= 0) {
+        memset(&msg, 0, sizeof(msg));
+        memset(&iov, 0, sizeof(iov));
+        memset(&aux, 0, sizeof(aux));
+        memset(&event, 0, sizeof(event));
+        aux.handle         = -1;
+        msg.msg_iov        = &iov;
+        msg.msg_iovlen     = 1;
+        msg.msg_control    = &aux;
+        msg.msg_controllen = sizeof(aux);
+        iov.iov_base       = &event;
+        iov.iov_len        = sizeof(event);
+
+        ret = recvmsg(client->sock, &msg, MSG_PEEK | MSG_DONTWAIT);
+        if (ret == 0) {
+            // The host hung up; the next call may reconnect.
+            close_client_socket(client);
+            break;
+        }
+        if (ret < 0) { break; }
+
+        // Peeking still installs any passed descriptor, which is not needed.
+        close_aux_handle_if_valid(aux.handle);
+
+        if (!event.error && event.info.serial &&
+            !(event.info.expires && event.info.expires < vsl_timestamp())) {
+            meta->serial    = event.info.serial;
+            meta->timestamp = event.info.timestamp;
+            meta->pts       = event.info.pts;
+            meta->size      = event.info.size;
+            meta->fourcc    = event.info.fourcc;
+            pthread_mutex_unlock(&client->lock);
+            return 1;
+        }
+
+        // Discard the control message or expired frame at the head.
+        aux.handle         = -1;
+        msg.msg_controllen = sizeof(aux);
+        if (recvmsg(client->sock, &msg, MSG_DONTWAIT) > 0) {
+            close_aux_handle_if_valid(aux.handle);
+        }
+    }
+
+    if (ret == -1 && errno != EAGAIN && errno != EWOULDBLOCK) {
+        pthread_mutex_unlock(&client->lock);
+        return -1;
+    }
+
+    pthread_mutex_unlock(&client->lock);
+    return 0;
+}
+
+VSL_API
+int
+vsl_client_send_command(VSLClient*  client,
+                        const void* request,
+                        size_t      request_size,
+                        void*       reply,
+                        size_t      reply_capacity)
+{
+    struct vsl_frame_command command;
+    struct vsl_frame_reply   response;
+    ssize_t                  ret;
+
+    if (!client || (!request && request_size) || (!reply && reply_capacity)) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    if (request_size > VSL_COMMAND_MAX) {
+        errno = EMSGSIZE;
+        return -1;
+    }
+
+    int err = pthread_mutex_lock(&client->lock);
+    if (err) {
+        errno = err;
+        return -1;
+    }
+
+    if (client->sock < 0) {
+        pthread_mutex_unlock(&client->lock);
+        errno = ENOTCONN;
+        return -1;
+    }
+
+    memset(&command.control, 0, sizeof(command.control));
+    command.control.message = VSL_FRAME_COMMAND;
+    if (request_size) { memcpy(command.payload, request, request_size); }
+
+    ret = send(client->sock,
+               &command,
+               sizeof(command.control) + request_size,
+               0);
+    if (ret <= 0) {
+        fprintf(stderr,
+                "%s failed to send: %s\n",
+                __FUNCTION__,
+                strerror(errno));
+        close_client_socket(client);
+        pthread_mutex_unlock(&client->lock);
+        if (ret == 0) { errno = ENOTCONN; }
+        return -1;
+    }
+
+    // Frame events queued ahead of the reply are discarded, as for unlock;
+    // recv() without a control buffer lets the kernel close passed handles.
+    do {
+        if (client->sock < 0) {
+            pthread_mutex_unlock(&client->lock);
+            errno = ENOTCONN;
+            return -1;
+        }
+
+        struct pollfd pfd;
+        pfd.fd       = client->sock;
+        pfd.events   = POLLIN;
+        pfd.revents  = 0;
+        int poll_ret = poll(&pfd, 1, client->sock_timeout_secs * 1000);
+        if (poll_ret == -1) {
+            close_client_socket(client);
+            pthread_mutex_unlock(&client->lock);
+            return -1;
+        } else if (poll_ret == 0) {
+            fprintf(stderr,
+                    "%s timeout waiting for command reply\n",
+                    __FUNCTION__);
+            close_client_socket(client);
+            pthread_mutex_unlock(&client->lock);
+            errno = ETIMEDOUT;
+            return -1;
+        }
+
+        memset(&response.event, 0, sizeof(response.event));
+        ret = recv(client->sock, &response, sizeof(response), MSG_DONTWAIT);
+        if (ret == -1 && (errno == EAGAIN || errno == EWOULDBLOCK)) {
+            continue;
+        } else if (ret <= 0) {
+            close_client_socket(client);
+            pthread_mutex_unlock(&client->lock);
+            if (ret == 0) { errno = ENOTCONN; }
+            return -1;
+        }
+        if (ret >= (ssize_t) sizeof(response.event) &&
+            response.event.info.serial) {
+            client->discarded++;
+        }
+    } while (ret < (ssize_t) sizeof(response.event) ||
+             response.event.info.serial != 0);
+
+    pthread_mutex_unlock(&client->lock);
+
+    if (response.event.error) {
+        errno = vsl_frame_errno(response.event.error);
+        return -1;
+    }
+
+    size_t size = MIN(response.event.info.size,
+                      (size_t) ret - sizeof(response.event));
+    if (reply_capacity) {
+        memcpy(reply, response.payload, MIN(size, reply_capacity));
+    }
+
+    return (int) size;
+}
+
 VSL_API
 VSLFrame*
 vsl_frame_wait(VSLClient* client, int64_t until)
@@ -503,7 +780,10 @@ vsl_frame_wait(VSLClient* client, int64_t until)
 
             if (sock == -1 && !client->reconnect) { return NULL; }
 
-            if (sock >= 0) { client->sock = sock; }
+            if (sock >= 0) {
+                client->sock = sock;
+                client->reconnects++;
+            }
         }
 
         uint8_t current_wait_stage = 0;
@@ -798,6 +1078,8 @@ vsl_frame_trylock(VSLFrame* frame)
                 pthread_mutex_unlock(&client->lock);
                 return -1;
             }
+        } else if (event.info.serial) {
+            client->discarded++;
         }
     } while (event.info.serial); // non-zero serial indicates a frame event.
 
@@ -831,6 +1113,12 @@ vsl_frame_trylock(VSLFrame* frame)
 #endif
         errno = EMFILE;
         return -1;
+    case VSL_FRAME_ERROR_NO_COMMAND_HANDLER:
+    case VSL_FRAME_ERROR_COMMAND_FAILED:
+        // Command replies are never sent in response to a lock request.
+        pthread_mutex_unlock(&client->lock);
+        errno = EBADMSG;
+        return -1;
     case VSL_FRAME_SUCCESS:
         break;
     }
@@ -964,6 +1252,8 @@ vsl_frame_unlock(VSLFrame* frame)
             close_client_socket(client);
             pthread_mutex_unlock(&client->lock);
             return -1;
+        } else if (event.info.serial) {
+            client->discarded++;
         }
     } while (event.info.serial); // non-zero serial indicates frame event.
 
@@ -208,6 +208,9 @@ sockaddr_from_path(const char*         path,
     }
 
     memcpy(addr->sun_path, path, path_len);
+    // A leading '@' names a Linux abstract socket, whose address starts with
+    // a NUL byte and is not NUL terminated.
+    if (path[0] == '@') { addr->sun_path[0] = '\0'; }
 
     if (addrlen) {
         *addrlen = (socklen_t) (path_len + sizeof(addr->sun_family));
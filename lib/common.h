@@ -130,6 +130,8 @@ get_numerator_framerate(char* framerate);
 extern int
 get_denominator_framerate(char* framerate);
 
+/* Fills a UNIX socket address from path, mapping a leading '@' to the
+ * abstract namespace. */
 extern int
 sockaddr_from_path(const char*         path,
                    struct sockaddr_un* addr,
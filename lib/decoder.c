@@ -48,6 +48,15 @@ vsl_decoder_create(VSLDecoderCodec codec, int fps)
     case VSL_DEC_HEVC:
         fourcc = VSL_FOURCC('H', 'E', 'V', 'C');
         break;
+    case VSL_DEC_MJPG:
+        fourcc = VSL_FOURCC('M', 'J', 'P', 'G');
+        break;
+    case VSL_DEC_VP8:
+        fourcc = VSL_FOURCC('V', 'P', '8', '0');
+        break;
+    case VSL_DEC_VP9:
+        fourcc = VSL_FOURCC('V', 'P', '9', '0');
+        break;
     default:
         fprintf(stderr, "vsl_decoder_create: unsupported codec: %d\n", codec);
         errno = EINVAL;
@@ -162,6 +171,87 @@ vsl_decoder_release(VSLDecoder* decoder)
     }
 }
 
+VSL_API
+int
+vsl_decoder_set_output_fourcc(VSLDecoder* decoder, uint32_t fourcc)
+{
+    if (!decoder || fourcc == 0) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_decoder_backend(decoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_decoder_set_output_fourcc_v4l2(decoder, fourcc);
+#endif
+
+    default:
+        // The Hantro VPU wrapper always produces NV12.
+        fprintf(stderr,
+                "vsl_decoder_set_output_fourcc: not supported by %s backend\n",
+                vsl_codec_backend_name(backend));
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
+VSL_API
+int
+vsl_decoder_set_output_buffers(VSLDecoder* decoder, int count)
+{
+    if (!decoder || count < 0) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_decoder_backend(decoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_decoder_set_output_buffers_v4l2(decoder, count);
+#endif
+
+    default:
+        // The Hantro VPU wrapper sizes its pool from the stream headers.
+        fprintf(stderr,
+                "vsl_decoder_set_output_buffers: not supported by %s backend\n",
+                vsl_codec_backend_name(backend));
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
+VSL_API
+int
+vsl_decoder_set_low_latency(VSLDecoder* decoder, int enable)
+{
+    if (!decoder) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_decoder_backend(decoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_decoder_set_low_latency_v4l2(decoder, enable);
+#endif
+
+    default:
+        // The Hantro VPU wrapper fixes frame reordering when it is opened.
+        fprintf(stderr,
+                "vsl_decoder_set_low_latency: not supported by %s backend\n",
+                vsl_codec_backend_name(backend));
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
 VSL_API
 int
 vsl_decoder_width(const VSLDecoder* decoder)
@@ -145,8 +145,12 @@ vsl_decoder_create_hantro(uint32_t codec, int fps)
     } else if (codec == VSL_FOURCC('H', 'E', 'V', 'C')) {
         inputCodec = VSL_DEC_HEVC;
     } else {
-        fprintf(stderr, "%s: unsupported codec: 0x%08x\n", __FUNCTION__, codec);
-        errno = EINVAL;
+        // The Hantro wrapper is only set up for H.264 and HEVC
+        fprintf(stderr,
+                "%s: codec 0x%08x not supported by hardware\n",
+                __FUNCTION__,
+                codec);
+        errno = ENOTSUP;
         return NULL;
     }
 
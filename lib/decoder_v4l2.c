@@ -10,6 +10,7 @@
 #include <errno.h>
 #include <fcntl.h>
 #include <poll.h>
+#include <pthread.h>
 #include <stdio.h>
 #include <stdlib.h>
 #include <string.h>
@@ -27,11 +28,43 @@ vsl_codec_to_v4l2_fmt(uint32_t codec)
         return V4L2_PIX_FMT_H264;
     case VSL_FOURCC('H', 'E', 'V', 'C'):
         return V4L2_PIX_FMT_HEVC;
+    case VSL_FOURCC('M', 'J', 'P', 'G'):
+        return V4L2_PIX_FMT_MJPEG;
+    case VSL_FOURCC('V', 'P', '8', '0'):
+        return V4L2_PIX_FMT_VP8;
+    case VSL_FOURCC('V', 'P', '9', '0'):
+        return V4L2_PIX_FMT_VP9;
     default:
         return 0;
     }
 }
 
+// Convert codec fourcc to the VSLDecoderCodec reported by the decoder
+static VSLDecoderCodec
+vsl_codec_to_dec_codec(uint32_t codec)
+{
+    switch (codec) {
+    case VSL_FOURCC('H', 'E', 'V', 'C'):
+        return VSL_DEC_HEVC;
+    case VSL_FOURCC('M', 'J', 'P', 'G'):
+        return VSL_DEC_MJPG;
+    case VSL_FOURCC('V', 'P', '8', '0'):
+        return VSL_DEC_VP8;
+    case VSL_FOURCC('V', 'P', '9', '0'):
+        return VSL_DEC_VP9;
+    default:
+        return VSL_DEC_H264;
+    }
+}
+
+// Display delay controls (Linux 5.15, not in older kernel headers)
+#ifndef V4L2_CID_MPEG_VIDEO_DEC_DISPLAY_DELAY
+#define V4L2_CID_MPEG_VIDEO_DEC_DISPLAY_DELAY (V4L2_CID_CODEC_BASE + 653)
+#endif
+#ifndef V4L2_CID_MPEG_VIDEO_DEC_DISPLAY_DELAY_ENABLE
+#define V4L2_CID_MPEG_VIDEO_DEC_DISPLAY_DELAY_ENABLE (V4L2_CID_CODEC_BASE + 654)
+#endif
+
 // Wrapper for V4L2 ioctl with retry on EINTR
 static int
 xioctl(int fd, unsigned long request, void* arg)
@@ -178,8 +211,11 @@ alloc_capture_frame(struct vsl_decoder_v4l2* dec, int index)
     // This ensures the DMABUF is large enough for the driver
     size_t alloc_size = dec->capture.plane_sizes[0];
     if (alloc_size == 0) {
-        // Fallback to calculated NV12 size if driver didn't report
-        alloc_size = (size_t) dec->capture.stride * dec->height * 3 / 2;
+        // Fallback to calculated size if driver didn't report
+        alloc_size = (size_t) dec->capture.stride * dec->height;
+        if (dec->out_fourcc == VSL_FOURCC('N', 'V', '1', '2')) {
+            alloc_size = alloc_size * 3 / 2;
+        }
     }
     cap->frame->info.size = alloc_size;
 
@@ -208,6 +244,54 @@ alloc_capture_frame(struct vsl_decoder_v4l2* dec, int index)
     return 0;
 }
 
+// Try to switch the CAPTURE queue to the requested packed format. Returns 0
+// with the format recorded in dec state, or -1 with the queue restored to its
+// previous format.
+static int
+try_capture_format(struct vsl_decoder_v4l2* dec, uint32_t fourcc)
+{
+    struct v4l2_format orig;
+    memset(&orig, 0, sizeof(orig));
+    orig.type = dec->capture_type;
+    if (xioctl(dec->fd, VIDIOC_G_FMT, &orig) < 0) { return -1; }
+
+    struct v4l2_format fmt = orig;
+    if (dec->multiplanar) {
+        fmt.fmt.pix_mp.pixelformat = fourcc;
+        fmt.fmt.pix_mp.num_planes  = 1;
+    } else {
+        fmt.fmt.pix.pixelformat = fourcc;
+    }
+
+    // Drivers substitute formats they cannot produce instead of failing
+    bool accepted = xioctl(dec->fd, VIDIOC_S_FMT, &fmt) == 0;
+    if (dec->multiplanar) {
+        accepted = accepted && fmt.fmt.pix_mp.pixelformat == fourcc &&
+                   fmt.fmt.pix_mp.num_planes == 1;
+    } else {
+        accepted = accepted && fmt.fmt.pix.pixelformat == fourcc;
+    }
+    if (!accepted) {
+        xioctl(dec->fd, VIDIOC_S_FMT, &orig);
+        return -1;
+    }
+
+    if (dec->multiplanar) {
+        dec->width                  = fmt.fmt.pix_mp.width;
+        dec->height                 = fmt.fmt.pix_mp.height;
+        dec->capture.stride         = fmt.fmt.pix_mp.plane_fmt[0].bytesperline;
+        dec->capture.plane_sizes[0] = fmt.fmt.pix_mp.plane_fmt[0].sizeimage;
+    } else {
+        dec->width                  = fmt.fmt.pix.width;
+        dec->height                 = fmt.fmt.pix.height;
+        dec->capture.stride         = fmt.fmt.pix.bytesperline;
+        dec->capture.plane_sizes[0] = fmt.fmt.pix.sizeimage;
+    }
+    dec->capture.plane_sizes[1] = 0;
+    dec->out_fourcc             = fourcc;
+    return 0;
+}
+
 // Read negotiated CAPTURE format dimensions into dec state.
 // On MPLANE drivers (e.g. Wave6), we force V4L2_PIX_FMT_NV12 via S_FMT first
 // to lock num_planes=1 (contiguous NV12). The driver also supports NV12M
@@ -216,6 +300,17 @@ alloc_capture_frame(struct vsl_decoder_v4l2* dec, int index)
 static int
 read_capture_format(struct vsl_decoder_v4l2* dec)
 {
+    if (dec->req_fourcc && dec->req_fourcc != VSL_FOURCC('N', 'V', '1', '2')) {
+        if (try_capture_format(dec, dec->req_fourcc) == 0) { return 0; }
+        fprintf(stderr,
+                "[decoder_v4l2] CAPTURE format %c%c%c%c not supported, "
+                "decoding to NV12\n",
+                dec->req_fourcc & 0xff,
+                (dec->req_fourcc >> 8) & 0xff,
+                (dec->req_fourcc >> 16) & 0xff,
+                (dec->req_fourcc >> 24) & 0xff);
+    }
+
     struct v4l2_format fmt;
     memset(&fmt, 0, sizeof(fmt));
     fmt.type = dec->capture_type;
@@ -303,7 +398,8 @@ setup_capture_queue(struct vsl_decoder_v4l2* dec)
     // Request CAPTURE buffers (DMABUF import mode)
     struct v4l2_requestbuffers req;
     memset(&req, 0, sizeof(req));
-    req.count  = VSL_V4L2_DEC_CAPTURE_BUFFERS;
+    req.count  = dec->req_buffers ? dec->req_buffers
+                                  : VSL_V4L2_DEC_CAPTURE_BUFFERS;
     req.type   = dec->capture_type;
     req.memory = V4L2_MEMORY_DMABUF;
 
@@ -449,6 +545,93 @@ stop_streaming(struct vsl_decoder_v4l2* dec)
     return 0;
 }
 
+// Cleanup callback for decoded frames
+struct v4l2_frame_cleanup_data {
+    struct vsl_decoder_v4l2* decoder;
+    int                      buffer_index;
+    // CAPTURE frame handed over by release_capture_buffer() when the decoder
+    // dropped the buffer while this frame still held it
+    VSLFrame* capture_frame;
+};
+
+// Guards the hand-off between decoded frames and their CAPTURE buffers: the
+// lent, returned and decoder links. Frames may be released on any thread,
+// such as a host serving clients, and may outlive their decoder, so the lock
+// is not part of the decoder. The CAPTURE queue itself is only touched by
+// the decoder thread.
+static pthread_mutex_t lent_lock = PTHREAD_MUTEX_INITIALIZER;
+
+static void
+v4l2_frame_cleanup(VSLFrame* frame)
+{
+    if (!frame || !frame->userptr) { return; }
+
+    struct v4l2_frame_cleanup_data* data          = frame->userptr;
+    VSLFrame*                       capture_frame = NULL;
+
+    pthread_mutex_lock(&lent_lock);
+    if (data->decoder && data->buffer_index >= 0) {
+        // Hand the buffer back, the decoder re-queues it before its next
+        // dequeue
+        struct vsl_v4l2_capture_buffer* cap =
+            &data->decoder->capture.buffers[data->buffer_index];
+        cap->lent     = NULL;
+        cap->returned = true;
+    } else {
+        // The decoder is gone or reallocated its buffers, this frame held
+        // the last reference to the DMA buffer
+        capture_frame = data->capture_frame;
+    }
+    pthread_mutex_unlock(&lent_lock);
+
+    if (capture_frame) { vsl_frame_release(capture_frame); }
+    free(data);
+}
+
+// Re-queue CAPTURE buffers handed back by released frames
+static void
+requeue_returned_buffers(struct vsl_decoder_v4l2* dec)
+{
+    bool returned[VSL_V4L2_DEC_CAPTURE_BUFFERS] = {false};
+
+    pthread_mutex_lock(&lent_lock);
+    for (int i = 0; i < dec->capture.count; i++) {
+        returned[i]                      = dec->capture.buffers[i].returned;
+        dec->capture.buffers[i].returned = false;
+    }
+    pthread_mutex_unlock(&lent_lock);
+
+    for (int i = 0; i < dec->capture.count; i++) {
+        if (returned[i]) { queue_capture_buffer(dec, i); }
+    }
+}
+
+// Release a CAPTURE buffer. A buffer still held by a decoded frame, such as
+// one posted to a host, is handed over to that frame instead so its DMA
+// buffer stays valid until the frame is released.
+static void
+release_capture_buffer(struct vsl_decoder_v4l2* dec, int index)
+{
+    struct vsl_v4l2_capture_buffer* cap = &dec->capture.buffers[index];
+    VSLFrame*                       capture_frame = cap->frame;
+
+    pthread_mutex_lock(&lent_lock);
+    if (cap->lent) {
+        cap->lent->decoder       = NULL;
+        cap->lent->capture_frame = cap->frame;
+        cap->lent                = NULL;
+        capture_frame            = NULL;
+    }
+    cap->returned = false;
+    pthread_mutex_unlock(&lent_lock);
+
+    if (capture_frame) { vsl_frame_release(capture_frame); }
+
+    cap->frame     = NULL;
+    cap->dmabuf_fd = -1;
+    cap->queued    = false;
+}
+
 // Handle resolution change event
 static int
 handle_resolution_change(struct vsl_decoder_v4l2* dec)
@@ -463,12 +646,7 @@ handle_resolution_change(struct vsl_decoder_v4l2* dec)
 
     // Free old CAPTURE buffers
     for (int i = 0; i < dec->capture.count; i++) {
-        if (dec->capture.buffers[i].frame) {
-            vsl_frame_release(dec->capture.buffers[i].frame);
-            dec->capture.buffers[i].frame     = NULL;
-            dec->capture.buffers[i].dmabuf_fd = -1;
-            dec->capture.buffers[i].queued    = false;
-        }
+        release_capture_buffer(dec, i);
     }
 
     // Release old buffers
@@ -509,27 +687,6 @@ handle_resolution_change(struct vsl_decoder_v4l2* dec)
     return 0;
 }
 
-// Cleanup callback for decoded frames
-struct v4l2_frame_cleanup_data {
-    struct vsl_decoder_v4l2* decoder;
-    int                      buffer_index;
-};
-
-static void
-v4l2_frame_cleanup(VSLFrame* frame)
-{
-    if (!frame || !frame->userptr) { return; }
-
-    struct v4l2_frame_cleanup_data* data = frame->userptr;
-
-    // Re-queue the buffer for reuse
-    if (data->decoder && data->buffer_index >= 0) {
-        queue_capture_buffer(data->decoder, data->buffer_index);
-    }
-
-    free(data);
-}
-
 // Helper: Create output frame from dequeued capture buffer
 // Returns frame on success, NULL on failure (buffer is re-queued on failure)
 static VSLFrame*
@@ -547,9 +704,16 @@ create_output_frame(struct vsl_decoder_v4l2* dec, int cap_idx)
     cleanup_data->decoder      = dec;
     cleanup_data->buffer_index = cap_idx;
 
+    // NV12 frames report the luma stride plus half of it, like frames the
+    // library allocates itself
+    int stride = dec->capture.stride;
+    if (dec->out_fourcc == VSL_FOURCC('N', 'V', '1', '2')) {
+        stride += stride >> 1;
+    }
+
     VSLFrame* out = vsl_frame_init(dec->width,
                                    dec->height,
-                                   dec->capture.stride,
+                                   stride,
                                    dec->out_fourcc,
                                    cleanup_data,
                                    v4l2_frame_cleanup);
@@ -562,10 +726,13 @@ create_output_frame(struct vsl_decoder_v4l2* dec, int cap_idx)
     out->handle      = dec->capture.buffers[cap_idx].dmabuf_fd;
     out->info.width  = dec->width;
     out->info.height = dec->height;
-    out->info.stride = dec->capture.stride;
+    out->info.stride = stride;
     out->info.size   = dec->capture.plane_sizes[0];
     out->info.paddr  = vsl_frame_paddr(existing);
 
+    pthread_mutex_lock(&lent_lock);
+    dec->capture.buffers[cap_idx].lent = cleanup_data;
+    pthread_mutex_unlock(&lent_lock);
     return out;
 }
 
@@ -597,6 +764,8 @@ try_dequeue_capture_frame(struct vsl_decoder_v4l2* dec)
 {
     if (!dec->streaming) { return NULL; }
 
+    requeue_returned_buffers(dec);
+
     struct v4l2_buffer cap_buf;
     struct v4l2_plane  planes[1];
     memset(&cap_buf, 0, sizeof(cap_buf));
@@ -846,14 +1015,7 @@ verify_m2m_capability(struct vsl_decoder_v4l2* dec, const char* dev_path)
 static void
 cleanup_capture_buffers(struct vsl_decoder_v4l2* dec, int count)
 {
-    for (int i = 0; i < count; i++) {
-        if (dec->capture.buffers[i].frame) {
-            vsl_frame_release(dec->capture.buffers[i].frame);
-            dec->capture.buffers[i].frame     = NULL;
-            dec->capture.buffers[i].dmabuf_fd = -1;
-            dec->capture.buffers[i].queued    = false;
-        }
-    }
+    for (int i = 0; i < count; i++) { release_capture_buffer(dec, i); }
 }
 
 // Helper: Allocate and queue all capture buffers
@@ -926,7 +1088,8 @@ request_capture_buffers(struct vsl_decoder_v4l2* dec)
 {
     struct v4l2_requestbuffers req;
     memset(&req, 0, sizeof(req));
-    req.count  = VSL_V4L2_DEC_CAPTURE_BUFFERS;
+    req.count  = dec->req_buffers ? dec->req_buffers
+                                  : VSL_V4L2_DEC_CAPTURE_BUFFERS;
     req.type   = dec->capture_type;
     req.memory = V4L2_MEMORY_DMABUF;
 
@@ -973,6 +1136,18 @@ vsl_decoder_create_v4l2(uint32_t codec, int fps)
                     (char) ((codec >> 24) & 0xFF));
         }
     }
+    if (!dev_path && codec != VSL_FOURCC('H', '2', '6', '4') &&
+        codec != VSL_FOURCC('H', 'E', 'V', 'C')) {
+        // The legacy default device only decodes H.264 and HEVC
+        fprintf(stderr,
+                "[decoder_v4l2] codec '%c%c%c%c' not supported by hardware\n",
+                (char) (codec & 0xFF),
+                (char) ((codec >> 8) & 0xFF),
+                (char) ((codec >> 16) & 0xFF),
+                (char) ((codec >> 24) & 0xFF));
+        errno = ENOTSUP;
+        return NULL;
+    }
     if (!dev_path) {
         dev_path = VSL_V4L2_DECODER_DEV_DEFAULT;
         fprintf(stderr,
@@ -993,8 +1168,7 @@ vsl_decoder_create_v4l2(uint32_t codec, int fps)
     dec->fd         = -1;
     dec->fps        = fps;
     dec->out_fourcc = VSL_FOURCC('N', 'V', '1', '2');
-    dec->codec =
-        (codec == VSL_FOURCC('H', '2', '6', '4')) ? VSL_DEC_H264 : VSL_DEC_HEVC;
+    dec->codec      = vsl_codec_to_dec_codec(codec);
 
     // Initialize capture buffer fds
     for (int i = 0; i < VSL_V4L2_DEC_CAPTURE_BUFFERS; i++) {
@@ -1066,11 +1240,10 @@ vsl_decoder_release_v4l2(VSLDecoder* decoder)
         }
     }
 
-    // Release CAPTURE frame buffers
+    // Release CAPTURE frame buffers, frames still held by the caller keep
+    // their buffer until they are released
     for (int i = 0; i < dec->capture.count; i++) {
-        if (dec->capture.buffers[i].frame) {
-            vsl_frame_release(dec->capture.buffers[i].frame);
-        }
+        release_capture_buffer(dec, i);
     }
 
     // Close device
@@ -1195,6 +1368,78 @@ vsl_decoder_height_v4l2(const VSLDecoder* decoder_)
     return dec->height;
 }
 
+int
+vsl_decoder_set_output_fourcc_v4l2(VSLDecoder* decoder, uint32_t fourcc)
+{
+    struct vsl_decoder_v4l2* dec = (struct vsl_decoder_v4l2*) decoder;
+
+    // The CAPTURE format is fixed once its buffers are allocated.
+    if (dec->initialized) {
+        errno = EBUSY;
+        return -1;
+    }
+
+    dec->req_fourcc = fourcc;
+    return 0;
+}
+
+int
+vsl_decoder_set_output_buffers_v4l2(VSLDecoder* decoder, int count)
+{
+    struct vsl_decoder_v4l2* dec = (struct vsl_decoder_v4l2*) decoder;
+
+    if (count < 0 || count > VSL_V4L2_DEC_CAPTURE_BUFFERS) {
+        errno = EINVAL;
+        return -1;
+    }
+    // The CAPTURE buffers are allocated once the headers are parsed.
+    if (dec->initialized) {
+        errno = EBUSY;
+        return -1;
+    }
+
+    dec->req_buffers = count;
+    return 0;
+}
+
+int
+vsl_decoder_set_low_latency_v4l2(VSLDecoder* decoder, int enable)
+{
+    struct vsl_decoder_v4l2* dec = (struct vsl_decoder_v4l2*) decoder;
+
+    // Display delay is latched when the OUTPUT queue starts streaming.
+    if (dec->output_streaming) {
+        errno = EBUSY;
+        return -1;
+    }
+
+    struct v4l2_query_ext_ctrl query;
+    memset(&query, 0, sizeof(query));
+    query.id = V4L2_CID_MPEG_VIDEO_DEC_DISPLAY_DELAY_ENABLE;
+    if (xioctl(dec->fd, VIDIOC_QUERY_EXT_CTRL, &query) < 0) {
+        fprintf(stderr,
+                "[decoder_v4l2] driver has no display delay control: %s\n",
+                strerror(errno));
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    struct v4l2_control ctrl = {
+        .id    = V4L2_CID_MPEG_VIDEO_DEC_DISPLAY_DELAY_ENABLE,
+        .value = enable ? 1 : 0,
+    };
+    if (xioctl(dec->fd, VIDIOC_S_CTRL, &ctrl) < 0) { return -1; }
+
+    // A delay of zero frames returns each frame as soon as it is decoded.
+    // Drivers that only have the enable control already behave that way.
+    ctrl.id    = V4L2_CID_MPEG_VIDEO_DEC_DISPLAY_DELAY;
+    ctrl.value = 0;
+    if (enable) { xioctl(dec->fd, VIDIOC_S_CTRL, &ctrl); }
+
+    dec->low_latency = enable != 0;
+    return 0;
+}
+
 VSLRect
 vsl_decoder_crop_v4l2(const VSLDecoder* decoder_)
 {
@@ -46,10 +46,17 @@ struct vsl_v4l2_output_buffer {
 /**
  * V4L2 CAPTURE queue buffer info (decoded frames).
  */
+struct v4l2_frame_cleanup_data;
+
 struct vsl_v4l2_capture_buffer {
     int       dmabuf_fd; // DMA-BUF file descriptor
     VSLFrame* frame;     // Associated VSLFrame
     bool      queued;    // true if buffer is queued to driver
+    // Decoded frame holding the buffer, NULL while the decoder owns it
+    struct v4l2_frame_cleanup_data* lent;
+    // Released by its decoded frame and waiting to be re-queued by the
+    // decoder thread
+    bool returned;
 };
 
 /**
@@ -76,6 +83,9 @@ struct vsl_decoder_v4l2 {
     VSLDecoderCodec codec;      // H.264 or HEVC
     int             fps;        // Frame rate hint
     uint32_t        out_fourcc; // Output pixel format (NV12)
+    uint32_t        req_fourcc; // Requested output format, 0 for NV12
+    int  req_buffers; // Requested CAPTURE buffer count, 0 for default
+    bool low_latency; // Display frames in decode order
 
     // Decoded frame dimensions (set after INIT_OK)
     int     width;
@@ -144,6 +154,43 @@ vsl_decode_frame_v4l2(VSLDecoder*  decoder,
                       size_t*      bytes_used,
                       VSLFrame**   output_frame);
 
+/**
+ * Request the CAPTURE pixel format, negotiated when the stream headers are
+ * parsed.
+ *
+ * @param decoder Decoder instance
+ * @param fourcc Requested pixel format (non-zero)
+ * @return 0 on success, -1 with errno EBUSY once initialized
+ */
+int
+vsl_decoder_set_output_fourcc_v4l2(VSLDecoder* decoder, uint32_t fourcc);
+
+/**
+ * Set the number of CAPTURE buffers requested when the stream headers are
+ * parsed. The driver may raise it to the minimum the stream needs.
+ *
+ * @param decoder Decoder instance
+ * @param count Buffer count from 1 to VSL_V4L2_DEC_CAPTURE_BUFFERS, 0 for the
+ *        default
+ * @return 0 on success, -1 with errno EINVAL for an out of range count or
+ *         EBUSY once initialized
+ */
+int
+vsl_decoder_set_output_buffers_v4l2(VSLDecoder* decoder, int count);
+
+/**
+ * Enable or disable low-latency decoding through the display delay
+ * controls, so frames are returned in decode order without waiting to
+ * reorder them.
+ *
+ * @param decoder Decoder instance
+ * @param enable Non-zero to enable
+ * @return 0 on success, -1 with errno EBUSY once streaming or ENOTSUP if
+ *         the driver lacks the controls
+ */
+int
+vsl_decoder_set_low_latency_v4l2(VSLDecoder* decoder, int enable);
+
 /**
  * Get decoded frame width.
  */
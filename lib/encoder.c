@@ -9,6 +9,7 @@
  */
 
 #include "codec_backend.h"
+#include "frame.h"
 #include "videostream.h"
 
 #ifdef ENABLE_V4L2_CODEC
@@ -86,6 +87,29 @@ vsl_encoder_create_ex(VSLEncoderProfile profile,
     }
 }
 
+VSL_API
+VSLEncoder*
+vsl_encoder_create_device(const char*       device,
+                          VSLEncoderProfile profile,
+                          uint32_t          outputFourcc,
+                          int               fps)
+{
+    if (!device) {
+        errno = EINVAL;
+        return NULL;
+    }
+
+#ifdef ENABLE_V4L2_CODEC
+    return vsl_encoder_create_v4l2_device(device, profile, outputFourcc, fps);
+#else
+    fprintf(stderr,
+            "vsl_encoder_create_device: V4L2 backend not available "
+            "(compiled out)\n");
+    errno = ENOTSUP;
+    return NULL;
+#endif
+}
+
 VSL_API
 int
 vsl_encode_frame(VSLEncoder*    encoder,
@@ -101,6 +125,14 @@ vsl_encode_frame(VSLEncoder*    encoder,
 
     VSLCodecBackend backend = get_encoder_backend(encoder);
 
+    // Carry the capture time through encode for end-to-end latency tracking,
+    // and the source so combined streams can be demultiplexed.
+    if (source && destination) {
+        destination->info.capture_time  = source->info.capture_time;
+        destination->info.has_source_id = source->info.has_source_id;
+        destination->info.source_id     = source->info.source_id;
+    }
+
     switch (backend) {
 #ifdef ENABLE_V4L2_CODEC
     case VSL_CODEC_BACKEND_V4L2:
@@ -127,6 +159,271 @@ vsl_encode_frame(VSLEncoder*    encoder,
     }
 }
 
+VSL_API
+int
+vsl_encoder_set_roi(VSLEncoder*          encoder,
+                    const VSLEncoderROI* regions,
+                    int                  count)
+{
+    if (!encoder || count < 0 || count > VSL_ENCODER_MAX_ROI ||
+        (count > 0 && !regions)) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    for (int i = 0; i < count; i++) {
+        const VSLEncoderROI* roi = &regions[i];
+        if (roi->rect.x < 0 || roi->rect.y < 0 || roi->rect.width <= 0 ||
+            roi->rect.height <= 0 || roi->qp_delta < -51 ||
+            roi->qp_delta > 51) {
+            errno = EINVAL;
+            return -1;
+        }
+    }
+
+    VSLCodecBackend backend = get_encoder_backend(encoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_encoder_set_roi_v4l2(encoder, regions, count);
+#endif
+
+    default:
+        // The Hantro VPU wrapper has no ROI controls.
+        fprintf(stderr,
+                "vsl_encoder_set_roi: not supported by %s backend\n",
+                vsl_codec_backend_name(backend));
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
+VSL_API
+int
+vsl_encoder_set_bitrate(VSLEncoder* encoder, uint32_t kbps)
+{
+    // V4L2 controls carry the bitrate in bps as a signed 32-bit value.
+    if (!encoder || kbps == 0 || kbps > INT32_MAX / 1000) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_encoder_backend(encoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_encoder_set_bitrate_v4l2(encoder, kbps);
+#endif
+
+    default:
+        // The Hantro VPU wrapper fixes the rate control at open.
+        fprintf(stderr,
+                "vsl_encoder_set_bitrate: not supported by %s backend\n",
+                vsl_codec_backend_name(backend));
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
+VSL_API
+int
+vsl_encoder_set_rate_control(VSLEncoder*           encoder,
+                             VSLEncoderRateControl mode,
+                             int                   qp)
+{
+    if (!encoder || mode > VSL_ENCODER_RC_CONSTANT_QP) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    if (mode == VSL_ENCODER_RC_CONSTANT_QP && (qp < 0 || qp > 51)) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_encoder_backend(encoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_encoder_set_rate_control_v4l2(encoder, mode, qp);
+#endif
+
+    default:
+        // The Hantro VPU wrapper fixes the rate control at open.
+        fprintf(stderr,
+                "vsl_encoder_set_rate_control: not supported by %s backend\n",
+                vsl_codec_backend_name(backend));
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
+VSL_API
+int
+vsl_encoder_set_entropy_mode(VSLEncoder* encoder, VSLEncoderEntropyMode mode)
+{
+    if (!encoder || mode > VSL_ENCODER_ENTROPY_CABAC) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_encoder_backend(encoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_encoder_set_entropy_mode_v4l2(encoder, mode);
+#endif
+
+    default:
+        fprintf(stderr,
+                "vsl_encoder_set_entropy_mode: not supported by %s backend\n",
+                vsl_codec_backend_name(backend));
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
+VSL_API
+int
+vsl_encoder_mark_ltr(VSLEncoder* encoder)
+{
+    if (!encoder) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_encoder_backend(encoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_encoder_mark_ltr_v4l2(encoder);
+#endif
+
+    default:
+        // The Hantro VPU wrapper has no reference picture controls.
+        fprintf(stderr,
+                "vsl_encoder_mark_ltr: not supported by %s backend\n",
+                vsl_codec_backend_name(backend));
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
+VSL_API
+int
+vsl_encoder_use_ltr(VSLEncoder* encoder)
+{
+    if (!encoder) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_encoder_backend(encoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_encoder_use_ltr_v4l2(encoder);
+#endif
+
+    default:
+        fprintf(stderr,
+                "vsl_encoder_use_ltr: not supported by %s backend\n",
+                vsl_codec_backend_name(backend));
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
+VSL_API
+int
+vsl_encoder_set_gop_size(VSLEncoder* encoder, uint32_t frames)
+{
+    // V4L2 controls carry the GOP size as a signed 32-bit value.
+    if (!encoder || frames == 0 || frames > INT32_MAX) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_encoder_backend(encoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_encoder_set_gop_size_v4l2(encoder, (int) frames);
+#endif
+
+#ifdef ENABLE_HANTRO_CODEC
+    case VSL_CODEC_BACKEND_HANTRO:
+        return vsl_encoder_set_gop_size_hantro(encoder, (int) frames);
+#endif
+
+    default:
+        fprintf(stderr,
+                "vsl_encoder_set_gop_size: not supported by %s backend\n",
+                vsl_codec_backend_name(backend));
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
+VSL_API
+int
+vsl_encoder_gop_size(const VSLEncoder* encoder)
+{
+    if (!encoder) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_encoder_backend(encoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return ((const struct vsl_encoder_v4l2*) encoder)->gop_size;
+#endif
+
+#ifdef ENABLE_HANTRO_CODEC
+    case VSL_CODEC_BACKEND_HANTRO:
+        return ((const struct vsl_encoder_hantro*) encoder)->gop_size;
+#endif
+
+    default:
+        errno = ENOTSUP;
+        return -1;
+    }
+}
+
+VSL_API
+int
+vsl_encoder_flush(VSLEncoder* encoder, VSLFrame* destination, int* keyframe)
+{
+    if (!encoder || !destination) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLCodecBackend backend = get_encoder_backend(encoder);
+
+    switch (backend) {
+#ifdef ENABLE_V4L2_CODEC
+    case VSL_CODEC_BACKEND_V4L2:
+        return vsl_encoder_flush_v4l2(encoder, destination, keyframe);
+#endif
+
+    default:
+        // The Hantro VPU wrapper returns each frame from vsl_encode_frame()
+        // and holds nothing back.
+        return 0;
+    }
+}
+
 VSL_API
 void
 vsl_encoder_release(VSLEncoder* encoder)
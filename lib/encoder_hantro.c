@@ -70,6 +70,7 @@ vsl_encoder_create_hantro(VSLEncoderProfile profile,
 
     encoder->backend       = VSL_CODEC_BACKEND_HANTRO;
     encoder->fps           = fps;
+    encoder->gop_size      = fps;
     encoder->output_fourcc = outputFourcc;
     encoder->profile       = profile;
 
@@ -209,7 +210,7 @@ vsl_encoder_init_hantro(struct vsl_encoder_hantro* encoder,
 
     sEncOpenParamSimp.nFrameRate = encoder->fps;
 
-    sEncOpenParamSimp.nGOPSize = encoder->fps;
+    sEncOpenParamSimp.nGOPSize = encoder->gop_size;
     sEncOpenParamSimp.nBitRate = 0;
     sEncOpenParamSimp.nIntraQP = 0;
 
@@ -292,6 +293,21 @@ vsl_encoder_release_hantro(VSLEncoder* encoder)
     free(enc);
 }
 
+int
+vsl_encoder_set_gop_size_hantro(VSLEncoder* encoder, int frames)
+{
+    struct vsl_encoder_hantro* enc = (struct vsl_encoder_hantro*) encoder;
+
+    // The simple open parameters are fixed once the VPU is open.
+    if (enc->handle) {
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    enc->gop_size = frames;
+    return 0;
+}
+
 VSL_API
 void
 vsl_encoder_frame_cleanup(VSLFrame* frame)
@@ -386,8 +402,8 @@ vsl_encode_frame_hantro(VSLEncoder*    encoder,
     sEncEncParam.nFrameRate  = enc->fps;
     sEncEncParam.nQuantParam = 35;
     sEncEncParam.nInPhyInput = source->info.paddr + source->info.offset;
-    sEncEncParam.nInVirtInput =
-        (unsigned long) source->map + source->info.offset;
+    // The mapping already starts at the frame offset, see vsl_frame_mmap().
+    sEncEncParam.nInVirtInput = (unsigned long) source->map;
     sEncEncParam.nInInputSize = (int) source->info.size;
 
 #ifndef NDEBUG
@@ -417,6 +433,7 @@ vsl_encode_frame_hantro(VSLEncoder*    encoder,
     if ((sEncEncParam.eOutRetCode & VPU_ENC_OUTPUT_DIS) ||
         (sEncEncParam.eOutRetCode & VPU_ENC_OUTPUT_SEQHEADER)) {
         destination->info.size = sEncEncParam.nOutOutputSize;
+        destination->info.used = sEncEncParam.nOutOutputSize;
         return (int) sEncEncParam.nOutOutputSize;
     }
 
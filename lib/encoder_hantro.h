@@ -20,6 +20,7 @@ struct vsl_encoder_hantro {
     int               out_width;
     int               out_height;
     int               fps;
+    int               gop_size;
     uint32_t          input_fourcc;
     uint32_t          output_fourcc;
     VpuEncHandle      handle;
@@ -88,4 +89,14 @@ vsl_encoder_new_output_frame_hantro(const VSLEncoder* encoder,
                                     int64_t           pts,
                                     int64_t           dts);
 
+/**
+ * Set the keyframe interval used when the VPU is opened on the first frame.
+ *
+ * @param encoder Encoder instance
+ * @param frames Frames between IDRs (positive)
+ * @return 0 on success, -1 with errno ENOTSUP once the VPU is open
+ */
+int
+vsl_encoder_set_gop_size_hantro(VSLEncoder* encoder, int frames);
+
 #endif // HANTRO_ENCODER_H
\ No newline at end of file
@@ -26,6 +26,11 @@
 #define V4L2_PIX_FMT_HEVC v4l2_fourcc('H', 'E', 'V', 'C')
 #endif
 
+// Marks the final CAPTURE buffer of a drain (Linux 4.5)
+#ifndef V4L2_BUF_FLAG_LAST
+#define V4L2_BUF_FLAG_LAST 0x00100000
+#endif
+
 // Helper for ioctl with retry on EINTR
 static int
 xioctl(int fd, unsigned long request, void* arg)
@@ -46,6 +51,12 @@ vsl_to_v4l2_codec(uint32_t fourcc)
         return V4L2_PIX_FMT_H264;
     case VSL_FOURCC('H', 'E', 'V', 'C'):
         return V4L2_PIX_FMT_HEVC;
+    case VSL_FOURCC('M', 'J', 'P', 'G'):
+        return V4L2_PIX_FMT_MJPEG;
+    case VSL_FOURCC('V', 'P', '8', '0'):
+        return V4L2_PIX_FMT_VP8;
+    case VSL_FOURCC('V', 'P', '9', '0'):
+        return V4L2_PIX_FMT_VP9;
     default:
         return 0;
     }
@@ -57,6 +68,40 @@ vsl_to_v4l2_codec(uint32_t fourcc)
 #define VSI_V4L2_PIX_FMT_AB24 v4l2_fourcc('A', 'B', '2', '4') // RGBA 8-8-8-8
 #define VSI_V4L2_PIX_FMT_AR24 v4l2_fourcc('A', 'R', '2', '4') // BGRA 8-8-8-8
 
+// ROI control of the vsi_v4l2enc driver (NXP linux/imx_vpu.h, non-standard)
+#ifndef V4L2_CID_USER_IMX_BASE
+#define V4L2_CID_USER_IMX_BASE (V4L2_CID_USER_BASE + 0x1090)
+#endif
+#ifndef V4L2_CID_ROI
+#define V4L2_CID_ROI (V4L2_CID_USER_IMX_BASE + 3)
+#endif
+#define VSI_V4L2_MAX_ROI_REGIONS 8
+
+struct vsi_v4l2_enc_roi_param {
+    struct v4l2_rect rect;
+    uint32_t         enable;
+    int32_t          qp_delta;
+    uint32_t         reserved[2];
+};
+
+struct vsi_v4l2_enc_roi_params {
+    uint32_t                      num_roi_regions;
+    struct vsi_v4l2_enc_roi_param roi_params[VSI_V4L2_MAX_ROI_REGIONS];
+    uint32_t                      config_store;
+    uint32_t                      reserved[2];
+};
+
+// Long-term reference controls (Linux 5.13, not in older kernel headers)
+#ifndef V4L2_CID_MPEG_VIDEO_LTR_COUNT
+#define V4L2_CID_MPEG_VIDEO_LTR_COUNT (V4L2_CID_CODEC_BASE + 232)
+#endif
+#ifndef V4L2_CID_MPEG_VIDEO_FRAME_LTR_INDEX
+#define V4L2_CID_MPEG_VIDEO_FRAME_LTR_INDEX (V4L2_CID_CODEC_BASE + 233)
+#endif
+#ifndef V4L2_CID_MPEG_VIDEO_USE_LTR_FRAMES
+#define V4L2_CID_MPEG_VIDEO_USE_LTR_FRAMES (V4L2_CID_CODEC_BASE + 234)
+#endif
+
 // Convert VSL fourcc to V4L2 input format and number of planes
 // Uses vsi_v4l2enc driver-specific fourcc codes
 static uint32_t
@@ -130,6 +175,55 @@ set_ctrl(int fd, uint32_t id, int32_t value)
     return 0;
 }
 
+// Returns true if the driver implements control `id`
+static bool
+has_ctrl(int fd, uint32_t id)
+{
+    struct v4l2_query_ext_ctrl query;
+    memset(&query, 0, sizeof(query));
+    query.id = id;
+    return xioctl(fd, VIDIOC_QUERY_EXT_CTRL, &query) == 0 &&
+           !(query.flags & V4L2_CTRL_FLAG_DISABLED);
+}
+
+// Returns true if the driver can mark and reference a long-term frame
+static bool
+has_ltr_ctrls(int fd)
+{
+    return has_ctrl(fd, V4L2_CID_MPEG_VIDEO_FRAME_LTR_INDEX) &&
+           has_ctrl(fd, V4L2_CID_MPEG_VIDEO_USE_LTR_FRAMES);
+}
+
+// Applies the rate control mode and, for constant QP, the frame QP
+static int
+apply_rate_control(struct vsl_encoder_v4l2* enc)
+{
+    if (enc->rc_mode == VSL_ENCODER_RC_CONSTANT_QP) {
+        uint32_t i_qp = V4L2_CID_MPEG_VIDEO_H264_I_FRAME_QP;
+        uint32_t p_qp = V4L2_CID_MPEG_VIDEO_H264_P_FRAME_QP;
+        if (enc->output_fourcc == VSL_FOURCC('H', 'E', 'V', 'C')) {
+            i_qp = V4L2_CID_MPEG_VIDEO_HEVC_I_FRAME_QP;
+            p_qp = V4L2_CID_MPEG_VIDEO_HEVC_P_FRAME_QP;
+        }
+
+        if (set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_FRAME_RC_ENABLE, 0) < 0 ||
+            set_ctrl(enc->fd, i_qp, enc->qp) < 0 ||
+            set_ctrl(enc->fd, p_qp, enc->qp) < 0) {
+            return -1;
+        }
+        return 0;
+    }
+
+    int32_t mode = enc->rc_mode == VSL_ENCODER_RC_CBR
+                       ? V4L2_MPEG_VIDEO_BITRATE_MODE_CBR
+                       : V4L2_MPEG_VIDEO_BITRATE_MODE_VBR;
+    if (has_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_FRAME_RC_ENABLE) &&
+        set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_FRAME_RC_ENABLE, 1) < 0) {
+        return -1;
+    }
+    return set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_BITRATE_MODE, mode);
+}
+
 // Helper: Find free OUTPUT buffer, returns index or -1 if none available
 static int
 find_free_enc_output_buffer(struct vsl_encoder_v4l2* enc)
@@ -516,7 +610,7 @@ stop_streaming(struct vsl_encoder_v4l2* enc)
 static int
 configure_encoder(struct vsl_encoder_v4l2* enc)
 {
-    uint32_t bitrate = profile_to_bitrate(enc->profile);
+    uint32_t bitrate = enc->bitrate;
 
     // Set bitrate
     if (set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_BITRATE, bitrate) < 0) {
@@ -525,11 +619,18 @@ configure_encoder(struct vsl_encoder_v4l2* enc)
     }
 
     // Set GOP size (keyframe interval)
-    int gop_size = enc->fps; // One keyframe per second
+    int gop_size = enc->gop_size;
     if (set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_GOP_SIZE, gop_size) < 0) {
         fprintf(stderr, "V4L2 encoder: failed to set GOP size %d\n", gop_size);
     }
 
+    // Leave the driver's default rate control unless one was selected
+    if (enc->rc_mode >= 0 && apply_rate_control(enc) < 0) {
+        fprintf(stderr,
+                "V4L2 encoder: failed to set rate control mode %d\n",
+                enc->rc_mode);
+    }
+
     // Set codec-specific parameters
     if (enc->output_fourcc == VSL_FOURCC('H', '2', '6', '4')) {
         // H.264 profile: High
@@ -541,6 +642,15 @@ configure_encoder(struct vsl_encoder_v4l2* enc)
         set_ctrl(enc->fd,
                  V4L2_CID_MPEG_VIDEO_H264_LEVEL,
                  V4L2_MPEG_VIDEO_H264_LEVEL_4_0);
+
+        // Entropy coding: CABAC or CAVLC, both allowed by High profile
+        if (enc->entropy_mode >= 0) {
+            set_ctrl(enc->fd,
+                     V4L2_CID_MPEG_VIDEO_H264_ENTROPY_MODE,
+                     enc->entropy_mode == VSL_ENCODER_ENTROPY_CABAC
+                         ? V4L2_MPEG_VIDEO_H264_ENTROPY_MODE_CABAC
+                         : V4L2_MPEG_VIDEO_H264_ENTROPY_MODE_CAVLC);
+        }
     } else if (enc->output_fourcc == VSL_FOURCC('H', 'E', 'V', 'C')) {
         // HEVC profile: Main
         set_ctrl(enc->fd,
@@ -553,6 +663,12 @@ configure_encoder(struct vsl_encoder_v4l2* enc)
                  V4L2_MPEG_VIDEO_HEVC_LEVEL_4);
     }
 
+    // Reserve a long-term reference slot for vsl_encoder_mark_ltr()
+    if (has_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_LTR_COUNT) &&
+        has_ltr_ctrls(enc->fd)) {
+        set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_LTR_COUNT, 1);
+    }
+
     fprintf(stderr,
             "V4L2 encoder: configured bitrate=%u bps, GOP=%d\n",
             bitrate,
@@ -561,19 +677,31 @@ configure_encoder(struct vsl_encoder_v4l2* enc)
     return 0;
 }
 
-VSLEncoder*
-vsl_encoder_create_v4l2(VSLEncoderProfile profile,
-                        uint32_t          output_fourcc,
-                        int               fps)
+static bool
+validate_codec(uint32_t output_fourcc)
 {
-    // Validate codec
-    uint32_t v4l2_codec = vsl_to_v4l2_codec(output_fourcc);
-    if (v4l2_codec == 0) {
+    if (vsl_to_v4l2_codec(output_fourcc) == 0) {
         fprintf(stderr,
                 "V4L2 encoder: unsupported codec fourcc 0x%08x\n",
                 output_fourcc);
-        return NULL;
+        errno = EINVAL;
+        return false;
     }
+    return true;
+}
+
+static VSLEncoder*
+open_encoder(const char*       dev_path,
+             VSLEncoderProfile profile,
+             uint32_t          output_fourcc,
+             int               fps);
+
+VSLEncoder*
+vsl_encoder_create_v4l2(VSLEncoderProfile profile,
+                        uint32_t          output_fourcc,
+                        int               fps)
+{
+    if (!validate_codec(output_fourcc)) { return NULL; }
 
     // Determine device path:
     // 1. Check environment variable override
@@ -592,6 +720,18 @@ vsl_encoder_create_v4l2(VSLEncoderProfile profile,
                     (char) ((output_fourcc >> 24) & 0xFF));
         }
     }
+    if (!dev_path && output_fourcc != VSL_FOURCC('H', '2', '6', '4') &&
+        output_fourcc != VSL_FOURCC('H', 'E', 'V', 'C')) {
+        // The legacy default device only encodes H.264 and HEVC
+        fprintf(stderr,
+                "V4L2 encoder: codec '%c%c%c%c' not supported by hardware\n",
+                (char) (output_fourcc & 0xFF),
+                (char) ((output_fourcc >> 8) & 0xFF),
+                (char) ((output_fourcc >> 16) & 0xFF),
+                (char) ((output_fourcc >> 24) & 0xFF));
+        errno = ENOTSUP;
+        return NULL;
+    }
     if (!dev_path) {
         dev_path = VSL_V4L2_ENCODER_DEV_DEFAULT;
         fprintf(stderr,
@@ -599,6 +739,25 @@ vsl_encoder_create_v4l2(VSLEncoderProfile profile,
                 dev_path);
     }
 
+    return open_encoder(dev_path, profile, output_fourcc, fps);
+}
+
+VSLEncoder*
+vsl_encoder_create_v4l2_device(const char*       dev_path,
+                               VSLEncoderProfile profile,
+                               uint32_t          output_fourcc,
+                               int               fps)
+{
+    if (!validate_codec(output_fourcc)) { return NULL; }
+    return open_encoder(dev_path, profile, output_fourcc, fps);
+}
+
+static VSLEncoder*
+open_encoder(const char*       dev_path,
+             VSLEncoderProfile profile,
+             uint32_t          output_fourcc,
+             int               fps)
+{
     // Open V4L2 device
     int fd = open(dev_path, O_RDWR | O_NONBLOCK);
     if (fd < 0) {
@@ -662,12 +821,231 @@ vsl_encoder_create_v4l2(VSLEncoderProfile profile,
     enc->output_type   = output_type;
     enc->capture_type  = capture_type;
     enc->profile       = profile;
+    enc->bitrate       = profile_to_bitrate(profile);
     enc->output_fourcc = output_fourcc;
     enc->fps           = fps;
+    enc->gop_size      = fps; // One keyframe per second
+    enc->rc_mode       = -1; // Driver default rate control
+    enc->entropy_mode  = -1; // Driver default entropy coding
 
     return (VSLEncoder*) enc;
 }
 
+int
+vsl_encoder_set_roi_v4l2(VSLEncoder*          encoder,
+                         const VSLEncoderROI* regions,
+                         int                  count)
+{
+    struct vsl_encoder_v4l2* enc = (struct vsl_encoder_v4l2*) encoder;
+
+    if (count > VSI_V4L2_MAX_ROI_REGIONS) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    struct v4l2_query_ext_ctrl query;
+    memset(&query, 0, sizeof(query));
+    query.id = V4L2_CID_ROI;
+    if (xioctl(enc->fd, VIDIOC_QUERY_EXT_CTRL, &query) < 0) {
+        fprintf(stderr,
+                "V4L2 encoder: driver has no ROI control: %s\n",
+                strerror(errno));
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    struct vsi_v4l2_enc_roi_params params;
+    memset(&params, 0, sizeof(params));
+    params.num_roi_regions = count;
+    for (int i = 0; i < count; i++) {
+        params.roi_params[i].rect.left   = regions[i].rect.x;
+        params.roi_params[i].rect.top    = regions[i].rect.y;
+        params.roi_params[i].rect.width  = regions[i].rect.width;
+        params.roi_params[i].rect.height = regions[i].rect.height;
+        params.roi_params[i].enable      = 1;
+        params.roi_params[i].qp_delta    = regions[i].qp_delta;
+    }
+
+    struct v4l2_ext_control ctrl;
+    memset(&ctrl, 0, sizeof(ctrl));
+    ctrl.id   = V4L2_CID_ROI;
+    ctrl.size = sizeof(params);
+    ctrl.ptr  = &params;
+
+    struct v4l2_ext_controls ctrls;
+    memset(&ctrls, 0, sizeof(ctrls));
+    ctrls.which    = V4L2_CTRL_WHICH_CUR_VAL;
+    ctrls.count    = 1;
+    ctrls.controls = &ctrl;
+
+    if (xioctl(enc->fd, VIDIOC_S_EXT_CTRLS, &ctrls) < 0) {
+        fprintf(stderr,
+                "V4L2 encoder: setting %d ROI regions failed: %s\n",
+                count,
+                strerror(errno));
+        return -1;
+    }
+
+    return 0;
+}
+
+int
+vsl_encoder_set_bitrate_v4l2(VSLEncoder* encoder, uint32_t kbps)
+{
+    struct vsl_encoder_v4l2* enc     = (struct vsl_encoder_v4l2*) encoder;
+    uint32_t                 bitrate = kbps * 1000;
+
+    // Controls are applied by configure_encoder() on the first frame.
+    if (enc->initialized &&
+        set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_BITRATE, bitrate) < 0) {
+        return -1;
+    }
+
+    enc->bitrate = bitrate;
+    return 0;
+}
+
+int
+vsl_encoder_set_gop_size_v4l2(VSLEncoder* encoder, int frames)
+{
+    struct vsl_encoder_v4l2* enc = (struct vsl_encoder_v4l2*) encoder;
+
+    // Controls are applied by configure_encoder() on the first frame.
+    if (enc->initialized &&
+        set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_GOP_SIZE, frames) < 0) {
+        return -1;
+    }
+
+    enc->gop_size = frames;
+    return 0;
+}
+
+int
+vsl_encoder_set_rate_control_v4l2(VSLEncoder*           encoder,
+                                  VSLEncoderRateControl mode,
+                                  int                   qp)
+{
+    struct vsl_encoder_v4l2* enc = (struct vsl_encoder_v4l2*) encoder;
+
+    uint32_t required = mode == VSL_ENCODER_RC_CONSTANT_QP
+                            ? V4L2_CID_MPEG_VIDEO_FRAME_RC_ENABLE
+                            : V4L2_CID_MPEG_VIDEO_BITRATE_MODE;
+    if (!has_ctrl(enc->fd, required)) {
+        fprintf(stderr,
+                "V4L2 encoder: driver has no control for rate control mode "
+                "%d\n",
+                mode);
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    int prev_mode = enc->rc_mode;
+    int prev_qp   = enc->qp;
+    enc->rc_mode  = mode;
+    enc->qp       = qp;
+
+    // Controls are applied by configure_encoder() on the first frame.
+    if (enc->initialized && apply_rate_control(enc) < 0) {
+        int err      = errno;
+        enc->rc_mode = prev_mode;
+        enc->qp      = prev_qp;
+        errno        = err;
+        return -1;
+    }
+
+    return 0;
+}
+
+int
+vsl_encoder_set_entropy_mode_v4l2(VSLEncoder*           encoder,
+                                  VSLEncoderEntropyMode mode)
+{
+    struct vsl_encoder_v4l2* enc = (struct vsl_encoder_v4l2*) encoder;
+
+    // HEVC has no entropy mode choice: it always codes with CABAC.
+    if (enc->output_fourcc != VSL_FOURCC('H', '2', '6', '4')) {
+        if (mode == VSL_ENCODER_ENTROPY_CABAC) {
+            return 0;
+        }
+        fprintf(stderr, "V4L2 encoder: CAVLC is only available for H.264\n");
+        errno = EINVAL;
+        return -1;
+    }
+
+    if (!has_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_H264_ENTROPY_MODE)) {
+        fprintf(stderr, "V4L2 encoder: driver has no entropy mode control\n");
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    int32_t value = mode == VSL_ENCODER_ENTROPY_CABAC
+                        ? V4L2_MPEG_VIDEO_H264_ENTROPY_MODE_CABAC
+                        : V4L2_MPEG_VIDEO_H264_ENTROPY_MODE_CAVLC;
+
+    // Controls are applied by configure_encoder() on the first frame.
+    if (enc->initialized &&
+        set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_H264_ENTROPY_MODE, value) < 0) {
+        return -1;
+    }
+
+    enc->entropy_mode = mode;
+    return 0;
+}
+
+int
+vsl_encoder_mark_ltr_v4l2(VSLEncoder* encoder)
+{
+    struct vsl_encoder_v4l2* enc = (struct vsl_encoder_v4l2*) encoder;
+
+    if (!has_ltr_ctrls(enc->fd)) {
+        fprintf(stderr, "V4L2 encoder: driver has no LTR controls\n");
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    enc->ltr_mark_pending = true;
+    return 0;
+}
+
+int
+vsl_encoder_use_ltr_v4l2(VSLEncoder* encoder)
+{
+    struct vsl_encoder_v4l2* enc = (struct vsl_encoder_v4l2*) encoder;
+
+    if (!has_ltr_ctrls(enc->fd)) {
+        fprintf(stderr, "V4L2 encoder: driver has no LTR controls\n");
+        errno = ENOTSUP;
+        return -1;
+    }
+    if (!enc->ltr_valid && !enc->ltr_mark_pending) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    enc->ltr_use_pending = true;
+    return 0;
+}
+
+// Apply pending LTR requests to the frame about to be queued
+static int
+apply_ltr(struct vsl_encoder_v4l2* enc)
+{
+    if (enc->ltr_use_pending && enc->ltr_valid) {
+        if (set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_USE_LTR_FRAMES, 1) < 0) {
+            return -1;
+        }
+        enc->ltr_use_pending = false;
+    }
+    if (enc->ltr_mark_pending) {
+        if (set_ctrl(enc->fd, V4L2_CID_MPEG_VIDEO_FRAME_LTR_INDEX, 0) < 0) {
+            return -1;
+        }
+        enc->ltr_mark_pending = false;
+        enc->ltr_valid        = true;
+    }
+    return 0;
+}
+
 void
 vsl_encoder_release_v4l2(VSLEncoder* encoder)
 {
@@ -697,6 +1075,59 @@ vsl_encoder_release_v4l2(VSLEncoder* encoder)
     free(enc);
 }
 
+// Copy a dequeued CAPTURE buffer into destination and re-queue it
+static int
+copy_capture_buffer(struct vsl_encoder_v4l2* enc,
+                    struct v4l2_buffer*      cap_buf,
+                    VSLFrame*                destination,
+                    int*                     keyframe)
+{
+    int cap_idx      = cap_buf->index;
+    int encoded_size = enc->multiplanar ? (int) cap_buf->m.planes[0].bytesused
+                                        : (int) cap_buf->bytesused;
+
+    enc->capture.buffers[cap_idx].queued = false;
+
+    // Copy encoded data to destination frame
+    void* dst_ptr = vsl_frame_mmap(destination, NULL);
+    if (!dst_ptr) {
+        fprintf(stderr, "V4L2 encoder: failed to mmap destination frame\n");
+        // Re-queue capture buffer
+        if (xioctl(enc->fd, VIDIOC_QBUF, cap_buf) == 0) {
+            enc->capture.buffers[cap_idx].queued = true;
+        }
+        return -1;
+    }
+
+    memcpy(dst_ptr, enc->capture.buffers[cap_idx].mmap_ptr, encoded_size);
+
+    // Update destination frame info
+    destination->info.width  = enc->width;
+    destination->info.height = enc->height;
+    destination->info.stride = enc->stride;
+    destination->info.fourcc = enc->output_fourcc;
+    destination->info.size   = encoded_size;
+    destination->info.used   = encoded_size;
+
+    // Check if keyframe
+    if (keyframe) {
+        *keyframe = (cap_buf->flags & V4L2_BUF_FLAG_KEYFRAME) ? 1 : 0;
+    }
+
+    // Re-queue capture buffer, unless it ends a drain
+    if (cap_buf->flags & V4L2_BUF_FLAG_LAST) { return encoded_size; }
+    if (xioctl(enc->fd, VIDIOC_QBUF, cap_buf) < 0) {
+        fprintf(stderr,
+                "V4L2 encoder: failed to re-queue CAPTURE[%d]: %s\n",
+                cap_idx,
+                strerror(errno));
+    } else {
+        enc->capture.buffers[cap_idx].queued = true;
+    }
+
+    return encoded_size;
+}
+
 int
 vsl_encode_frame_v4l2(VSLEncoder*    encoder,
                       VSLFrame*      source,
@@ -709,6 +1140,12 @@ vsl_encode_frame_v4l2(VSLEncoder*    encoder,
     struct vsl_encoder_v4l2* enc        = (struct vsl_encoder_v4l2*) encoder;
     uint64_t                 start_time = vsl_timestamp_us();
 
+    if (enc->draining) {
+        fprintf(stderr, "V4L2 encoder: cannot encode after flush\n");
+        errno = EPIPE;
+        return -1;
+    }
+
     // Initialize encoder on first frame (need dimensions and format from
     // source)
     if (!enc->initialized) {
@@ -752,6 +1189,8 @@ vsl_encode_frame_v4l2(VSLEncoder*    encoder,
         return -1;
     }
 
+    if (apply_ltr(enc) < 0) { return -1; }
+
     // Queue source frame to OUTPUT queue
     struct v4l2_buffer buf;
     struct v4l2_plane  planes[VSL_V4L2_ENC_MAX_PLANES];
@@ -822,46 +1261,9 @@ vsl_encode_frame_v4l2(VSLEncoder*    encoder,
         return -1;
     }
 
-    int cap_idx      = cap_buf.index;
-    int encoded_size = enc->multiplanar ? (int) cap_planes[0].bytesused
-                                        : (int) cap_buf.bytesused;
-
-    enc->capture.buffers[cap_idx].queued = false;
-
-    // Copy encoded data to destination frame
-    void* dst_ptr = vsl_frame_mmap(destination, NULL);
-    if (!dst_ptr) {
-        fprintf(stderr, "V4L2 encoder: failed to mmap destination frame\n");
-        // Re-queue capture buffer
-        if (xioctl(enc->fd, VIDIOC_QBUF, &cap_buf) == 0) {
-            enc->capture.buffers[cap_idx].queued = true;
-        }
-        return -1;
-    }
-
-    memcpy(dst_ptr, enc->capture.buffers[cap_idx].mmap_ptr, encoded_size);
-
-    // Update destination frame info
-    destination->info.width  = enc->width;
-    destination->info.height = enc->height;
-    destination->info.stride = enc->stride;
-    destination->info.fourcc = enc->output_fourcc;
-    destination->info.size   = encoded_size;
-
-    // Check if keyframe
-    if (keyframe) {
-        *keyframe = (cap_buf.flags & V4L2_BUF_FLAG_KEYFRAME) ? 1 : 0;
-    }
-
-    // Re-queue capture buffer
-    if (xioctl(enc->fd, VIDIOC_QBUF, &cap_buf) < 0) {
-        fprintf(stderr,
-                "V4L2 encoder: failed to re-queue CAPTURE[%d]: %s\n",
-                cap_idx,
-                strerror(errno));
-    } else {
-        enc->capture.buffers[cap_idx].queued = true;
-    }
+    int encoded_size =
+        copy_capture_buffer(enc, &cap_buf, destination, keyframe);
+    if (encoded_size < 0) { return -1; }
 
     // Also try to dequeue used OUTPUT buffer
     memset(&buf, 0, sizeof(buf));
@@ -884,6 +1286,84 @@ vsl_encode_frame_v4l2(VSLEncoder*    encoder,
     return encoded_size;
 }
 
+int
+vsl_encoder_flush_v4l2(VSLEncoder* encoder,
+                       VSLFrame*   destination,
+                       int*        keyframe)
+{
+    if (!encoder || !destination) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    struct vsl_encoder_v4l2* enc = (struct vsl_encoder_v4l2*) encoder;
+    if (!enc->streaming || enc->drained) { return 0; }
+
+    if (!enc->draining) {
+        struct v4l2_encoder_cmd cmd;
+        memset(&cmd, 0, sizeof(cmd));
+        cmd.cmd        = V4L2_ENC_CMD_STOP;
+        enc->drain_cmd = xioctl(enc->fd, VIDIOC_ENCODER_CMD, &cmd) == 0;
+        if (!enc->drain_cmd) {
+            // Without the drain sequence only frames already encoded are
+            // collected, until none arrive within the poll timeout
+            fprintf(stderr,
+                    "V4L2 encoder: VIDIOC_ENCODER_CMD stop failed: %s\n",
+                    strerror(errno));
+        }
+        enc->draining = true;
+    }
+
+    struct pollfd pfd = {
+        .fd     = enc->fd,
+        .events = POLLIN,
+    };
+    int timeout = VSL_V4L2_ENC_POLL_TIMEOUT_MS * (enc->drain_cmd ? 10 : 1);
+    int ret     = poll(&pfd, 1, timeout);
+    if (ret < 0) {
+        fprintf(stderr, "V4L2 encoder: poll failed: %s\n", strerror(errno));
+        return -1;
+    }
+    if (ret == 0 || (pfd.revents & POLLERR)) {
+        if (ret == 0 && enc->drain_cmd) {
+            fprintf(stderr, "V4L2 encoder: drain timed out\n");
+        }
+        enc->drained = true;
+        return 0;
+    }
+
+    struct v4l2_buffer cap_buf;
+    struct v4l2_plane  cap_planes[1];
+
+    memset(&cap_buf, 0, sizeof(cap_buf));
+    memset(cap_planes, 0, sizeof(cap_planes));
+    cap_buf.type   = enc->capture_type;
+    cap_buf.memory = V4L2_MEMORY_MMAP;
+    if (enc->multiplanar) {
+        cap_buf.length   = 1;
+        cap_buf.m.planes = cap_planes;
+    }
+
+    if (xioctl(enc->fd, VIDIOC_DQBUF, &cap_buf) < 0) {
+        // EPIPE: the last buffer was already dequeued
+        if (errno == EPIPE || errno == EAGAIN) {
+            enc->drained = true;
+            return 0;
+        }
+        fprintf(stderr,
+                "V4L2 encoder: VIDIOC_DQBUF CAPTURE failed: %s\n",
+                strerror(errno));
+        return -1;
+    }
+
+    if (cap_buf.flags & V4L2_BUF_FLAG_LAST) { enc->drained = true; }
+
+    int encoded_size =
+        copy_capture_buffer(enc, &cap_buf, destination, keyframe);
+    if (encoded_size > 0) { enc->frames_encoded++; }
+    return encoded_size;
+}
+
 VSLFrame*
 vsl_encoder_new_output_frame_v4l2(const VSLEncoder* encoder,
                                   int               width,
@@ -79,8 +79,13 @@ struct vsl_encoder_v4l2 {
 
     // Encoder configuration
     VSLEncoderProfile profile;       // Bitrate profile
+    uint32_t          bitrate;       // Target bitrate in bps
     uint32_t          output_fourcc; // Output codec (H264/HEVC)
     int               fps;           // Frame rate
+    int               gop_size;      // Frames between IDRs
+    int               rc_mode;       // VSLEncoderRateControl, -1 for default
+    int               qp;            // Frame QP for VSL_ENCODER_RC_CONSTANT_QP
+    int               entropy_mode;  // VSLEncoderEntropyMode, -1 for default
 
     // Input frame dimensions
     int      width;
@@ -109,6 +114,16 @@ struct vsl_encoder_v4l2 {
     bool initialized; // Encoder initialized with first frame
     bool streaming;   // Both queues streaming
 
+    // Long-term reference requests, applied to the next queued frame
+    bool ltr_mark_pending;
+    bool ltr_use_pending;
+    bool ltr_valid; // A frame has been marked as the LTR
+
+    // End-of-stream drain started by vsl_encoder_flush()
+    bool draining;
+    bool drain_cmd; // Driver accepted V4L2_ENC_CMD_STOP
+    bool drained;   // No more encoded frames will be produced
+
     // Statistics
     uint64_t frames_encoded;
     uint64_t total_encode_time_us;
@@ -127,6 +142,21 @@ vsl_encoder_create_v4l2(VSLEncoderProfile profile,
                         uint32_t          output_fourcc,
                         int               fps);
 
+/**
+ * Create a V4L2-based encoder instance on a specific device node.
+ *
+ * @param dev_path V4L2 encoder device path (e.g. "/dev/video1")
+ * @param profile Bitrate profile for encoding quality
+ * @param output_fourcc Codec type (H264 or HEVC fourcc)
+ * @param fps Frame rate for encoding
+ * @return Encoder instance or NULL on failure
+ */
+VSLEncoder*
+vsl_encoder_create_v4l2_device(const char*       dev_path,
+                               VSLEncoderProfile profile,
+                               uint32_t          output_fourcc,
+                               int               fps);
+
 /**
  * Release V4L2 encoder and all associated resources.
  *
@@ -152,6 +182,104 @@ vsl_encode_frame_v4l2(VSLEncoder*    encoder,
                       const VSLRect* crop_region,
                       int*           keyframe);
 
+/**
+ * Set encoder regions of interest through the i.MX VPU ROI control.
+ *
+ * @param encoder Encoder instance
+ * @param regions Validated regions (may be NULL when count is 0)
+ * @param count Number of regions, at most VSL_ENCODER_MAX_ROI
+ * @return 0 on success, -1 on error (ENOTSUP if the driver lacks the control)
+ */
+int
+vsl_encoder_set_roi_v4l2(VSLEncoder*          encoder,
+                         const VSLEncoderROI* regions,
+                         int                  count);
+
+/**
+ * Set the target bitrate, applied immediately once streaming and otherwise
+ * when the encoder is configured on the first frame.
+ *
+ * @param encoder Encoder instance
+ * @param kbps Target bitrate in kilobits per second (non-zero)
+ * @return 0 on success, -1 on error (errno from VIDIOC_S_CTRL)
+ */
+int
+vsl_encoder_set_bitrate_v4l2(VSLEncoder* encoder, uint32_t kbps);
+
+/**
+ * Set the keyframe interval, applied immediately once streaming and
+ * otherwise when the encoder is configured on the first frame.
+ *
+ * @param encoder Encoder instance
+ * @param frames Frames between IDRs (positive)
+ * @return 0 on success, -1 on error (errno from VIDIOC_S_CTRL)
+ */
+int
+vsl_encoder_set_gop_size_v4l2(VSLEncoder* encoder, int frames);
+
+/**
+ * Set the rate control mode, applied immediately once streaming and
+ * otherwise when the encoder is configured on the first frame.
+ *
+ * @param encoder Encoder instance
+ * @param mode Validated rate control mode
+ * @param qp Frame QP (0-51) for VSL_ENCODER_RC_CONSTANT_QP
+ * @return 0 on success, -1 on error (ENOTSUP if the driver lacks the
+ *         control, otherwise errno from VIDIOC_S_CTRL)
+ */
+int
+vsl_encoder_set_rate_control_v4l2(VSLEncoder*           encoder,
+                                  VSLEncoderRateControl mode,
+                                  int                   qp);
+
+/**
+ * Set the H.264 entropy coding mode, applied immediately once streaming
+ * and otherwise when the encoder is configured on the first frame.
+ *
+ * @param encoder Encoder instance
+ * @param mode Validated entropy coding mode
+ * @return 0 on success, -1 on error (EINVAL for CAVLC with HEVC, ENOTSUP if
+ *         the driver lacks the control, otherwise errno from VIDIOC_S_CTRL)
+ */
+int
+vsl_encoder_set_entropy_mode_v4l2(VSLEncoder*           encoder,
+                                  VSLEncoderEntropyMode mode);
+
+/**
+ * Mark the next queued frame as the long-term reference.
+ *
+ * @param encoder Encoder instance
+ * @return 0 on success, -1 on error (ENOTSUP if the driver lacks the control)
+ */
+int
+vsl_encoder_mark_ltr_v4l2(VSLEncoder* encoder);
+
+/**
+ * Predict the next queued frame from the long-term reference only.
+ *
+ * @param encoder Encoder instance
+ * @return 0 on success, -1 on error (EINVAL if no frame was marked, ENOTSUP
+ *         if the driver lacks the control)
+ */
+int
+vsl_encoder_use_ltr_v4l2(VSLEncoder* encoder);
+
+/**
+ * Drain one encoded frame still held by the encoder at end of stream.
+ *
+ * The first call asks the driver to stop (V4L2_ENC_CMD_STOP) so every queued
+ * frame is encoded; later calls dequeue them one at a time.
+ *
+ * @param encoder Encoder instance
+ * @param destination Output frame receiving the encoded data
+ * @param keyframe Output: 1 if the drained frame is a keyframe (may be NULL)
+ * @return Encoded size in bytes, 0 once drained, -1 on error
+ */
+int
+vsl_encoder_flush_v4l2(VSLEncoder* encoder,
+                       VSLFrame*   destination,
+                       int*        keyframe);
+
 /**
  * Create an output frame suitable for V4L2 encoder.
  *
@@ -3,6 +3,7 @@
 
 #include <errno.h>
 #include <fcntl.h>
+#include <stdatomic.h>
 #include <stdio.h>
 #include <string.h>
 #include <sys/ioctl.h>
@@ -16,6 +17,7 @@
 #include "dma-buf.h"
 #include "dma-heap.h"
 #include "frame.h"
+#include "g2d.h"
 #include "libg2d.h"
 #include "videostream.h"
 
@@ -61,6 +63,10 @@ frame_stride(uint32_t fourcc, int width)
         return width + (width >> 1);
     case make_fourcc('N', 'V', '6', '1'):
         return width + (width >> 1);
+    case make_fourcc('G', 'R', 'E', 'Y'):
+        return width;
+    case make_fourcc('Y', '1', '6', ' '):
+        return width * 2;
     default:
         return 0;
     }
@@ -114,6 +120,93 @@ vsl_frame_dts(const VSLFrame* frame)
     return frame->info.dts;
 }
 
+VSL_API
+int64_t
+vsl_frame_capture_time(const VSLFrame* frame)
+{
+    if (!frame) { return 0; }
+    return frame->info.capture_time;
+}
+
+VSL_API
+void
+vsl_frame_set_capture_time(VSLFrame* frame, int64_t capture_time)
+{
+    if (!frame) { return; }
+    frame->info.capture_time = capture_time;
+}
+
+VSL_API
+int
+vsl_frame_keyframe(const VSLFrame* frame)
+{
+    if (!frame) { return 0; }
+    return frame->info.keyframe;
+}
+
+VSL_API
+void
+vsl_frame_set_keyframe(VSLFrame* frame, int keyframe)
+{
+    if (!frame) { return; }
+    frame->info.keyframe = keyframe != 0;
+}
+
+VSL_API
+int
+vsl_frame_source_id(const VSLFrame* frame, uint32_t* id)
+{
+    if (!frame || !id) {
+        errno = EINVAL;
+        return -1;
+    }
+    *id = frame->info.has_source_id ? frame->info.source_id : 0;
+    return frame->info.has_source_id != 0;
+}
+
+VSL_API
+void
+vsl_frame_set_source_id(VSLFrame* frame, uint32_t id)
+{
+    if (!frame) { return; }
+    frame->info.source_id     = id;
+    frame->info.has_source_id = 1;
+}
+
+VSL_API
+int
+vsl_frame_display_rect(const VSLFrame* frame, VSLRect* rect)
+{
+    if (!frame || !rect) {
+        errno = EINVAL;
+        return -1;
+    }
+    *rect = frame->info.display_rect;
+    return rect->width > 0 && rect->height > 0;
+}
+
+VSL_API
+int
+vsl_frame_set_display_rect(VSLFrame* frame, const VSLRect* rect)
+{
+    if (!frame) {
+        errno = EINVAL;
+        return -1;
+    }
+    if (!rect) {
+        memset(&frame->info.display_rect, 0, sizeof(VSLRect));
+        return 0;
+    }
+    if (rect->x < 0 || rect->y < 0 || rect->width <= 0 || rect->height <= 0 ||
+        rect->x + rect->width > frame->info.width ||
+        rect->y + rect->height > frame->info.height) {
+        errno = EINVAL;
+        return -1;
+    }
+    frame->info.display_rect = *rect;
+    return 0;
+}
+
 VSL_API
 int64_t
 vsl_frame_expires(const VSLFrame* frame)
@@ -162,6 +255,92 @@ vsl_frame_size(const VSLFrame* frame)
     return frame->info.size;
 }
 
+VSL_API
+size_t
+vsl_frame_used_size(const VSLFrame* frame)
+{
+    if (!frame) { return 0; }
+    return frame->info.used ? frame->info.used : frame->info.size;
+}
+
+VSL_API
+int
+vsl_frame_set_used_size(VSLFrame* frame, size_t used)
+{
+    if (!frame || used > frame->info.size) {
+        errno = EINVAL;
+        return -1;
+    }
+    frame->info.used = used;
+    return 0;
+}
+
+VSL_API
+int
+vsl_frame_set_planes(VSLFrame* frame, const VSLFramePlane* planes, int count)
+{
+    if (!frame || count < 0 || count > VSL_FRAME_MAX_PLANES ||
+        (count && !planes)) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    size_t start = (size_t) frame->info.offset;
+    size_t end   = start + frame->info.size;
+    for (int i = 0; i < count; i++) {
+        if (planes[i].offset < start || planes[i].size > end ||
+            planes[i].offset > end - planes[i].size || planes[i].stride < 0) {
+            errno = EINVAL;
+            return -1;
+        }
+    }
+
+    memset(frame->info.planes, 0, sizeof(frame->info.planes));
+    if (count) { memcpy(frame->info.planes, planes, count * sizeof(*planes)); }
+    frame->info.plane_count = count;
+    return 0;
+}
+
+VSL_API
+int
+vsl_frame_planes(const VSLFrame* frame, VSLFramePlane* planes, int max)
+{
+    if (!frame || max < 0 || (max && !planes)) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    int count = frame->info.plane_count;
+    for (int i = 0; i < count && i < max; i++) {
+        planes[i] = frame->info.planes[i];
+    }
+    return count;
+}
+
+VSL_API
+size_t
+vsl_frame_offset(const VSLFrame* frame)
+{
+    if (!frame) { return 0; }
+    return frame->info.offset;
+}
+
+VSL_API
+VSLFrameMemory
+vsl_frame_memory(const VSLFrame* frame)
+{
+    if (!frame || frame->handle == -1) { return VSL_FRAME_MEMORY_NONE; }
+
+    switch (frame->allocator) {
+    case VSL_FRAME_ALLOCATOR_DMAHEAP:
+        return VSL_FRAME_MEMORY_DMABUF;
+    case VSL_FRAME_ALLOCATOR_SHM:
+        return VSL_FRAME_MEMORY_SHM;
+    default:
+        return VSL_FRAME_MEMORY_EXTERNAL;
+    }
+}
+
 VSL_API
 intptr_t
 vsl_frame_paddr(VSLFrame* frame)
@@ -193,6 +372,17 @@ vsl_frame_paddr(VSLFrame* frame)
     return frame->info.paddr;
 }
 
+/**
+ * Distance from the page boundary below the frame offset to the frame data.
+ */
+static size_t
+frame_map_delta(const VSLFrame* frame)
+{
+    long page = sysconf(_SC_PAGESIZE);
+    if (page <= 0) { return 0; }
+    return frame->info.offset % (size_t) page;
+}
+
 VSL_API
 void*
 vsl_frame_mmap(VSLFrame* frame, size_t* size)
@@ -213,12 +403,15 @@ vsl_frame_mmap(VSLFrame* frame, size_t* size)
            frame->info.offset);
 #endif
 
-    void* map = mmap(NULL,
-                     frame->info.size,
+    // mmap() requires a page aligned offset, so map from the page holding
+    // the frame start and hand out a pointer to the frame data itself.
+    size_t delta = frame_map_delta(frame);
+    void*  map   = mmap(NULL,
+                     frame->info.size + delta,
                      PROT_READ | PROT_WRITE,
                      MAP_SHARED,
                      frame->handle,
-                     frame->info.offset);
+                     frame->info.offset - delta);
     if (map == MAP_FAILED) {
         fprintf(stderr,
                 "%s: mmap failed: %s (frame=%p, fd=%d, size=%zu, offset=%zd, "
@@ -233,13 +426,11 @@ vsl_frame_mmap(VSLFrame* frame, size_t* size)
         return NULL;
     }
 
-    frame->map = map;
-    // FIXME: should we calculate the frame size to only map it?  Need to
-    // confirm how this works with the offset!
+    frame->map     = (uint8_t*) map + delta;
     frame->mapsize = frame->info.size;
     if (size) { *size = frame->mapsize; }
 
-    return map;
+    return frame->map;
 }
 
 VSL_API
@@ -247,7 +438,8 @@ void
 vsl_frame_munmap(VSLFrame* frame)
 {
     if (frame && frame->map) {
-        munmap(frame->map, frame->mapsize);
+        size_t delta = frame_map_delta(frame);
+        munmap((uint8_t*) frame->map - delta, frame->mapsize + delta);
         frame->map     = NULL;
         frame->mapsize = 0;
         vsl_frame_sync(frame, 0, O_RDWR);
@@ -570,12 +762,15 @@ vsl_frame_alloc(VSLFrame* frame, const char* path)
         } else if (access("/dev/dma_heap/system", R_OK | W_OK) == 0) {
             frame->path = strdup("/dev/dma_heap/system");
         } else {
+            // Frames allocated by one thread must not share an object.
+            static atomic_uint shm_serial;
             frame->path = calloc(1, 128);
             snprintf(frame->path,
                      128,
-                     "/VSL_%ld_%ld",
+                     "/VSL_%ld_%ld_%u",
                      (long) getpid(),
-                     (long) syscall(SYS_gettid));
+                     (long) syscall(SYS_gettid),
+                     atomic_fetch_add(&shm_serial, 1));
 
             return frame_alloc_shm(frame);
         }
@@ -632,6 +827,7 @@ vsl_frame_unalloc(VSLFrame* frame)
     }
 
     frame->info.size   = 0;
+    frame->info.used   = 0;
     frame->info.offset = 0;
 }
 
@@ -688,9 +884,27 @@ vsl_frame_attach(VSLFrame* frame, int fd, size_t size, size_t offset)
         }
     }
 
+    // Reject a range that runs past the end of the buffer.  Regular files,
+    // memfds and DmaBufs report their size through fstat(); other
+    // descriptors (e.g. devices) report zero and are not checked.
+    struct stat st;
+    if (fstat(fd, &st) == 0 && st.st_size > 0 &&
+        (offset > (size_t) st.st_size ||
+         size > (size_t) st.st_size - offset)) {
+        fprintf(stderr,
+                "%s: range %zu+%zu exceeds buffer size %lld\n",
+                __FUNCTION__,
+                offset,
+                size,
+                (long long) st.st_size);
+        errno = EINVAL;
+        return -1;
+    }
+
     frame->handle      = dup(fd);
     frame->info.offset = offset;
     frame->info.size   = size;
+    frame->info.used   = 0;
 
     frame->allocator = VSL_FRAME_ALLOCATOR_EXTERNAL;
 
@@ -724,3 +938,49 @@ vsl_frame_copy(VSLFrame* target, VSLFrame* source, const VSLRect* crop)
     errno = ENOTSUP;
     return -1;
 }
+
+VSL_API
+int
+vsl_frame_rotate(VSLFrame* target, VSLFrame* source, int degrees)
+{
+    if (!target || !source ||
+        (degrees != 90 && degrees != 180 && degrees != 270)) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    const struct vsl_frame_info* src = &source->info;
+    const struct vsl_frame_info* dst = &target->info;
+    int                          rotated = degrees != 180;
+
+    if (src->fourcc != dst->fourcc ||
+        dst->width != (rotated ? src->height : src->width) ||
+        dst->height != (rotated ? src->width : src->height)) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    // Only DmaBuf frames have the physical addresses G2D requires.
+    if (source->allocator == VSL_FRAME_ALLOCATOR_SHM ||
+        target->allocator == VSL_FRAME_ALLOCATOR_SHM || source->handle < 0 ||
+        target->handle < 0) {
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    intptr_t src_paddr = vsl_frame_paddr(source);
+    intptr_t dst_paddr = vsl_frame_paddr(target);
+    if (src_paddr == -1 || dst_paddr == -1) {
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    return g2d_rotate(src_paddr + src->offset,
+                      src->stride,
+                      dst_paddr + dst->offset,
+                      dst->stride,
+                      src->fourcc,
+                      src->width,
+                      src->height,
+                      degrees);
+}
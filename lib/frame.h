@@ -4,6 +4,7 @@
 #ifndef VSL_FRAME_H
 #define VSL_FRAME_H
 
+#include <stdbool.h>
 #include <stdint.h>
 #include <stdlib.h>
 #include <sys/mman.h>
@@ -20,11 +21,14 @@ enum vsl_frame_error {
     VSL_FRAME_ERROR_EXPIRED,
     VSL_FRAME_ERROR_INVALID_CONTROL,
     VSL_FRAME_TOO_MANY_FRAMES_LOCKED,
+    VSL_FRAME_ERROR_NO_COMMAND_HANDLER,
+    VSL_FRAME_ERROR_COMMAND_FAILED,
 };
 
 enum vsl_frame_message {
     VSL_FRAME_TRYLOCK,
     VSL_FRAME_UNLOCK,
+    VSL_FRAME_COMMAND,
 };
 
 enum vsl_frame_allocator {
@@ -33,21 +37,31 @@ enum vsl_frame_allocator {
     VSL_FRAME_ALLOCATOR_SHM,
 };
 
+/* Sent over the UNIX socket in host byte order: host and client always share
+ * a machine, and nothing here is stored in the shared frame buffer. */
 struct vsl_frame_info {
-    int64_t  serial;
-    int64_t  timestamp;
-    int64_t  duration;
-    int64_t  pts;
-    int64_t  dts;
-    int64_t  expires;
-    int      locked;
-    uint32_t fourcc;
-    int      width;
-    int      height;
-    intptr_t paddr;
-    size_t   size;
-    off_t    offset;
-    int      stride;
+    int64_t       serial;
+    int64_t       timestamp;
+    int64_t       duration;
+    int64_t       pts;
+    int64_t       dts;
+    int64_t       expires;
+    int           locked;
+    uint32_t      fourcc;
+    int           width;
+    int           height;
+    intptr_t      paddr;
+    size_t        size;
+    off_t         offset;
+    int           stride;
+    int64_t       capture_time;
+    int           keyframe;
+    VSLRect       display_rect;
+    size_t        used;        // Encoded payload bytes, 0 for the whole buffer
+    int           plane_count; // Planes described in planes, 0 when none
+    VSLFramePlane planes[VSL_FRAME_MAX_PLANES];
+    int           has_source_id; // Non-zero once source_id is set
+    uint32_t      source_id;
 };
 
 struct vsl_frame {
@@ -63,6 +77,7 @@ struct vsl_frame {
     VSLClient*               client;
     enum vsl_frame_allocator allocator;
     char*                    path;
+    bool                     was_locked; // A client locked it while posted
 };
 
 struct vsl_frame_control {
@@ -75,6 +90,19 @@ struct vsl_frame_event {
     struct vsl_frame_info info;
 };
 
+/* Command request: the control header followed by up to VSL_COMMAND_MAX bytes
+ * in the same packet. */
+struct vsl_frame_command {
+    struct vsl_frame_control control;
+    uint8_t                  payload[VSL_COMMAND_MAX];
+};
+
+/* Command reply: a frame event with serial 0 followed by the reply bytes. */
+struct vsl_frame_reply {
+    struct vsl_frame_event event;
+    uint8_t                payload[VSL_COMMAND_MAX];
+};
+
 struct vsl_aux {
     struct cmsghdr hdr;
     int            handle;
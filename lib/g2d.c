@@ -1,8 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright Ⓒ 2025 Au-Zone Technologies. All Rights Reserved.
 
+#include <errno.h>
 #include <stdlib.h>
 
+#include "g2d.h"
+#include "videostream.h"
+
 #define LIBG2D_IMPLEMENTATION
 #include "libg2d.h"
 
@@ -38,3 +42,97 @@ g2d_release()
         g2d = NULL;
     }
 }
+
+static int
+g2d_format_from_fourcc(uint32_t fourcc, enum g2d_format* format, int* bpp)
+{
+    switch (fourcc) {
+    case VSL_FOURCC('R', 'G', 'B', 'P'):
+        *format = G2D_RGB565;
+        *bpp    = 2;
+        return 0;
+    case VSL_FOURCC('R', 'G', 'B', '3'):
+        *format = G2D_RGB888;
+        *bpp    = 3;
+        return 0;
+    case VSL_FOURCC('B', 'G', 'R', '3'):
+        *format = G2D_BGR888;
+        *bpp    = 3;
+        return 0;
+    case VSL_FOURCC('R', 'G', 'B', 'A'):
+        *format = G2D_RGBA8888;
+        *bpp    = 4;
+        return 0;
+    case VSL_FOURCC('B', 'G', 'R', 'A'):
+        *format = G2D_BGRA8888;
+        *bpp    = 4;
+        return 0;
+    case VSL_FOURCC('Y', 'U', 'Y', 'V'):
+        *format = G2D_YUYV;
+        *bpp    = 2;
+        return 0;
+    default:
+        return -1;
+    }
+}
+
+int
+g2d_rotate(intptr_t src_paddr,
+           int      src_stride,
+           intptr_t dst_paddr,
+           int      dst_stride,
+           uint32_t fourcc,
+           int      width,
+           int      height,
+           int      degrees)
+{
+    enum g2d_format format;
+    int             bpp;
+    void*           handle = NULL;
+
+    if (!g2d || g2d_format_from_fourcc(fourcc, &format, &bpp)) {
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    struct g2d_surface src = {0};
+    struct g2d_surface dst = {0};
+
+    src.format    = format;
+    src.planes[0] = (int) src_paddr;
+    src.right     = width;
+    src.bottom    = height;
+    src.stride    = src_stride / bpp;
+    src.width     = width;
+    src.height    = height;
+    src.rot       = G2D_ROTATION_0;
+
+    // G2D describes the destination in its own (rotated) orientation.
+    int rotated = degrees == 90 || degrees == 270;
+    dst.format    = format;
+    dst.planes[0] = (int) dst_paddr;
+    dst.width     = rotated ? height : width;
+    dst.height    = rotated ? width : height;
+    dst.right     = dst.width;
+    dst.bottom    = dst.height;
+    dst.stride    = dst_stride / bpp;
+    dst.rot       = degrees == 90    ? G2D_ROTATION_90
+                    : degrees == 180 ? G2D_ROTATION_180
+                                     : G2D_ROTATION_270;
+
+    if (g2d->open(&handle)) {
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    int err = g2d->blit(handle, &src, &dst);
+    if (!err) { err = g2d->finish(handle); }
+    g2d->close(handle);
+
+    if (err) {
+        errno = EIO;
+        return -1;
+    }
+
+    return 0;
+}
@@ -4,10 +4,24 @@
 #ifndef G2D_H
 #define G2D_H
 
+#include <stdint.h>
+
 extern void
 g2d_init();
 
 extern void
 g2d_release();
 
+/* Rotates width x height pixels between physical buffers, strides in bytes.
+ * Returns -1 with errno ENOTSUP when G2D or the format is unavailable. */
+extern int
+g2d_rotate(intptr_t src_paddr,
+           int      src_stride,
+           intptr_t dst_paddr,
+           int      dst_stride,
+           uint32_t fourcc,
+           int      width,
+           int      height,
+           int      degrees);
+
 #endif /* G2D_H */
\ No newline at end of file
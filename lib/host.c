@@ -48,6 +48,9 @@ struct vsl_host {
     VSLFrame**                frames;
     int64_t                   serial;
     pthread_mutex_t           lock;
+    vsl_host_command_handler  command_handler;
+    void*                     command_userptr;
+    VSLHostDropStats          drops;
 };
 
 static inline void
@@ -146,6 +149,7 @@ expire_frames(VSLHost* host)
             VSLFrame* old = host->frames[i];
             if (old->info.locked) { continue; }
             if (old->info.expires && old->info.expires < now) {
+                if (!old->was_locked) { host->drops.expired++; }
                 vsl_frame_release(old);
             }
         }
@@ -247,15 +251,19 @@ vsl_host_init(const char* path)
     if (err && errno == EADDRINUSE) {
         if (-1 == connect(sock, (struct sockaddr*) &addr, addrlen) &&
             errno == ECONNREFUSED) {
-            unlink(path);
+            if (path[0] != '@') { unlink(path); }
             err = bind(sock, (struct sockaddr*) &addr, addrlen);
+        } else {
+            // Another host is listening, report the bind failure rather
+            // than the outcome of the probe.
+            errno = EADDRINUSE;
         }
     }
     if (err) {
         fprintf(stderr,
                 "%s failed to bind unix socket on %s %s: %s\n",
                 __FUNCTION__,
-                (path[0] == '/' ? "path" : "abstract address"),
+                (path[0] == '@' ? "abstract address" : "path"),
                 path,
                 strerror(errno));
         close(sock);
@@ -328,7 +336,7 @@ vsl_host_release(VSLHost* host)
     if (host->sockets) { free(host->sockets); }
     if (host->frames) { free(host->frames); }
     if (host->path) {
-        unlink(host->path);
+        if (host->path[0] != '@') { unlink(host->path); }
         free(host->path);
     }
 
@@ -382,6 +390,7 @@ vsl_host_post(VSLHost*  host,
         return -1;
     }
 
+    frame->was_locked     = false;
     frame->info.serial    = ++host->serial;
     frame->info.timestamp = vsl_timestamp();
     frame->info.expires   = expires;
@@ -447,6 +456,7 @@ vsl_host_post(VSLHost*  host,
         if (errno == EAGAIN || errno == EWOULDBLOCK) {
             // Socket buffer full - client is busy, drop frame for this
             // client but don't disconnect. This is normal backpressure.
+            host->drops.client_slow++;
 #ifndef NDEBUG
             fprintf(stderr,
                     "[HOST] sendmsg to socket %d: buffer full, dropping frame "
@@ -515,6 +525,65 @@ vsl_host_drop(VSLHost* host, VSLFrame* frame)
     return -1;
 }
 
+VSL_API
+int
+vsl_host_drop_stats(VSLHost* host, VSLHostDropStats* stats)
+{
+    if (!host || !stats) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    struct timespec locktimeout;
+    clock_gettime(CLOCK_REALTIME, &locktimeout);
+    timespec_add_nsec(&locktimeout, LOCK_TIMEOUT);
+    int err = pthread_mutex_timedlock(&host->lock, &locktimeout);
+    if (err) {
+        fprintf(stderr,
+                "%s pthread_mutex_lock failed: %s\n",
+                __FUNCTION__,
+                strerror(err));
+        errno = err;
+        return -1;
+    }
+
+    *stats = host->drops;
+
+    pthread_mutex_unlock(&host->lock);
+    return 0;
+}
+
+VSL_API
+int
+vsl_host_set_command_handler(VSLHost*                 host,
+                             vsl_host_command_handler handler,
+                             void*                    userptr)
+{
+    if (!host) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    struct timespec locktimeout;
+    clock_gettime(CLOCK_REALTIME, &locktimeout);
+    timespec_add_nsec(&locktimeout, LOCK_TIMEOUT);
+    int err = pthread_mutex_timedlock(&host->lock, &locktimeout);
+    if (err) {
+        fprintf(stderr,
+                "%s pthread_mutex_lock failed: %s\n",
+                __FUNCTION__,
+                strerror(err));
+        errno = err;
+        return -1;
+    }
+
+    host->command_handler = handler;
+    host->command_userptr = userptr;
+
+    pthread_mutex_unlock(&host->lock);
+    return 0;
+}
+
 VSL_API
 VSLFrame*
 vsl_frame_register(VSLHost*          host,
@@ -609,10 +678,14 @@ vsl_host_poll(VSLHost* host, int64_t wait)
 }
 
 static int
-recv_client_control(int sock, struct vsl_frame_control* control)
+recv_client_control(int                       sock,
+                    struct vsl_frame_command* command,
+                    size_t*                   payload_size)
 {
+    struct vsl_frame_control* control = &command->control;
+
     errno       = 0;
-    ssize_t len = recv(sock, control, sizeof(*control), 0);
+    ssize_t len = recv(sock, command, sizeof(*command), 0);
 
 #ifndef NDEBUG
     printf("%s %d read bytes: %zd error: %s\n",
@@ -644,7 +717,9 @@ recv_client_control(int sock, struct vsl_frame_control* control)
     } else if (len == 0) {
         errno = ECONNRESET;
         return -1;
-    } else if (len != sizeof(*control)) {
+    } else if (len < (ssize_t) sizeof(*control) ||
+               (control->message != VSL_FRAME_COMMAND &&
+                len != sizeof(*control))) {
         fprintf(stderr,
                 "%s %d partial read %zd of %zu\n",
                 __FUNCTION__,
@@ -655,9 +730,35 @@ recv_client_control(int sock, struct vsl_frame_control* control)
         return -1;
     }
 
+    *payload_size = len - sizeof(*control);
     return 0;
 }
 
+static size_t
+service_client_command(VSLHost*                        host,
+                       const struct vsl_frame_command* command,
+                       size_t                          request_size,
+                       struct vsl_frame_reply*         reply)
+{
+    if (!host->command_handler) {
+        reply->event.error = VSL_FRAME_ERROR_NO_COMMAND_HANDLER;
+        return 0;
+    }
+
+    int ret = host->command_handler(host,
+                                    command->payload,
+                                    request_size,
+                                    reply->payload,
+                                    sizeof(reply->payload),
+                                    host->command_userptr);
+    if (ret < 0) {
+        reply->event.error = VSL_FRAME_ERROR_COMMAND_FAILED;
+        return 0;
+    }
+
+    return MIN((size_t) ret, sizeof(reply->payload));
+}
+
 static void
 service_client_trylock(VSLHost*                        host,
                        int                             sock,
@@ -675,6 +776,7 @@ service_client_trylock(VSLHost*                        host,
                 event->error = VSL_FRAME_TOO_MANY_FRAMES_LOCKED;
             } else if (host->frames[i]->info.locked >= 0) {
                 host->frames[i]->info.locked++;
+                host->frames[i]->was_locked = true;
                 event->info.locked = 1;
             }
 #ifndef NDEBUG
@@ -751,35 +853,46 @@ service_client_unlock(VSLHost*                        host,
 static int
 service_client(VSLHost* host, int sock)
 {
-    struct vsl_frame_control control;
-    struct vsl_frame_event   event;
+    struct vsl_frame_command command;
+    struct vsl_frame_reply   reply;
+    size_t                   request_size = 0;
+    size_t                   reply_size   = 0;
+
+    if (recv_client_control(sock, &command, &request_size)) { return -1; }
 
-    if (recv_client_control(sock, &control)) { return -1; }
+    struct vsl_frame_control* control = &command.control;
+    struct vsl_frame_event*   event   = &reply.event;
 
-    memset(&event, 0, sizeof(event));
+    memset(event, 0, sizeof(*event));
 
 #ifndef NDEBUG
     printf("%s %d: %s %ld\n",
            __FUNCTION__,
            sock,
-           control.message == VSL_FRAME_TRYLOCK  ? "lock"
-           : control.message == VSL_FRAME_UNLOCK ? "unlock"
-                                                 : "invalid",
-           control.serial);
+           control->message == VSL_FRAME_TRYLOCK   ? "lock"
+           : control->message == VSL_FRAME_UNLOCK  ? "unlock"
+           : control->message == VSL_FRAME_COMMAND ? "command"
+                                                   : "invalid",
+           control->serial);
 #endif
 
-    switch (control.message) {
+    switch (control->message) {
     case VSL_FRAME_TRYLOCK:
-        service_client_trylock(host, sock, &control, &event);
+        service_client_trylock(host, sock, control, event);
         break;
     case VSL_FRAME_UNLOCK:
-        service_client_unlock(host, sock, &control, &event);
+        service_client_unlock(host, sock, control, event);
+        break;
+    case VSL_FRAME_COMMAND:
+        reply_size =
+            service_client_command(host, &command, request_size, &reply);
+        event->info.size = reply_size;
         break;
     default:
-        event.error = VSL_FRAME_ERROR_INVALID_CONTROL;
+        event->error = VSL_FRAME_ERROR_INVALID_CONTROL;
     }
 
-    ssize_t ret = send(sock, &event, sizeof(event), 0);
+    ssize_t ret = send(sock, &reply, sizeof(*event) + reply_size, 0);
     if (ret == -1) {
         if (errno == EAGAIN || errno == EWOULDBLOCK) {
             // Socket buffer full - response couldn't be sent but we already
@@ -232,10 +232,11 @@ vsl_camera_get_data(vsl_camera* ctx)
 
     if (0 == r) {
         fprintf(stderr, "Camera timeout");
+        errno = ETIMEDOUT;
         return NULL;
     }
+    /* EAGAIN from read_frame if no buffer was ready after all. */
     return read_frame(ctx);
-    /* EAGAIN - continue select loop. */
 }
 
 VSL_API
@@ -417,11 +418,17 @@ init_dma_(vsl_camera* ctx, int* buf_count)
             perror("mmap failed");
             return -1;
         }
+        if (ctx->request_memory == VSL_V4L2_MEM_MMAP) { continue; }
         int dmafd = 0;
         if (buffer_export(ctx->fd, V4L2_BUF_TYPE_VIDEO_CAPTURE, i, &dmafd) ==
             0) {
             ctx->buffers[i].dmafd     = dmafd;
             ctx->buffers[i].phys_addr = get_paddr(dmafd);
+        } else if (ctx->request_memory == VSL_V4L2_MEM_DMABUF) {
+            if (!errno || errno == EINVAL || errno == ENOTTY) {
+                errno = ENOTSUP;
+            }
+            return -1;
         }
     }
     return 0;
@@ -495,6 +502,7 @@ init_dma_mplane(vsl_camera* ctx, int* buf_count)
             perror("mmap failed");
             return -1;
         }
+        if (ctx->request_memory == VSL_V4L2_MEM_MMAP) { continue; }
         int dmafd = 0;
         if (buffer_export_mp(ctx->fd,
                              V4L2_BUF_TYPE_VIDEO_CAPTURE_MPLANE,
@@ -503,6 +511,11 @@ init_dma_mplane(vsl_camera* ctx, int* buf_count)
                              1) == 0) {
             ctx->buffers[i].dmafd     = dmafd;
             ctx->buffers[i].phys_addr = get_paddr(dmafd);
+        } else if (ctx->request_memory == VSL_V4L2_MEM_DMABUF) {
+            if (!errno || errno == EINVAL || errno == ENOTTY) {
+                errno = ENOTSUP;
+            }
+            return -1;
         }
     }
     return 0;
@@ -685,7 +698,7 @@ vsl_camera_init_device(vsl_camera* ctx,
         if (*width > 0) fmt.fmt.pix.width = *width;
         if (*height > 0) fmt.fmt.pix.height = *height;
         if (*fourcc != 0) fmt.fmt.pix.pixelformat = *fourcc;
-        fmt.fmt.pix.field = V4L2_FIELD_ANY;
+        fmt.fmt.pix.field = ctx->request_field;
 
         // the driver will set these values
         fmt.fmt.pix.sizeimage    = 0;
@@ -695,7 +708,7 @@ vsl_camera_init_device(vsl_camera* ctx,
         if (*width > 0) fmt.fmt.pix_mp.width = *width;
         if (*height > 0) fmt.fmt.pix_mp.height = *height;
         if (*fourcc != 0) fmt.fmt.pix_mp.pixelformat = *fourcc;
-        fmt.fmt.pix_mp.field      = V4L2_FIELD_ANY;
+        fmt.fmt.pix_mp.field      = ctx->request_field;
         fmt.fmt.pix_mp.num_planes = 1;
 
         // the driver will set these values
@@ -743,11 +756,13 @@ vsl_camera_init_device(vsl_camera* ctx,
                                    : fmt.fmt.pix_mp.plane_fmt[0].bytesperline;
 
     if (ctx->not_plane) {
+        ctx->field          = fmt.fmt.pix.field;
         ctx->color_space    = fmt.fmt.pix.colorspace;
         ctx->color_transfer = fmt.fmt.pix.xfer_func;
         ctx->color_encoding = fmt.fmt.pix.ycbcr_enc;
         ctx->color_range    = fmt.fmt.pix.quantization;
     } else {
+        ctx->field          = fmt.fmt.pix_mp.field;
         ctx->color_space    = fmt.fmt.pix_mp.colorspace;
         ctx->color_transfer = fmt.fmt.pix_mp.xfer_func;
         ctx->color_encoding = fmt.fmt.pix_mp.ycbcr_enc;
@@ -856,6 +871,198 @@ vsl_camera_mirror_v(const vsl_camera* ctx, bool mirror)
     return 0;
 }
 
+static int
+camera_selection(const vsl_camera* ctx, u_int32_t target, VSLRect* rect)
+{
+    struct v4l2_selection sel;
+    CLEAR(sel);
+    // The selection API takes the single-planar type for both capture APIs.
+    sel.type   = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+    sel.target = target;
+    if (-1 == xioctl(ctx->fd, VIDIOC_G_SELECTION, &sel)) { return -1; }
+
+    rect->x      = sel.r.left;
+    rect->y      = sel.r.top;
+    rect->width  = sel.r.width;
+    rect->height = sel.r.height;
+    return 0;
+}
+
+// Reads the current capture format with VIDIOC_G_FMT; any output may be NULL.
+static int
+camera_format(const vsl_camera* ctx,
+              int*              width,
+              int*              height,
+              u_int32_t*        fourcc,
+              u_int32_t*        bytes_per_line)
+{
+    struct v4l2_format fmt;
+    CLEAR(fmt);
+    fmt.type = ctx->not_plane ? V4L2_BUF_TYPE_VIDEO_CAPTURE
+                              : V4L2_BUF_TYPE_VIDEO_CAPTURE_MPLANE;
+    if (-1 == xioctl(ctx->fd, VIDIOC_G_FMT, &fmt)) { return -1; }
+
+    if (ctx->not_plane) {
+        if (width) { *width = fmt.fmt.pix.width; }
+        if (height) { *height = fmt.fmt.pix.height; }
+        if (fourcc) { *fourcc = fmt.fmt.pix.pixelformat; }
+        if (bytes_per_line) { *bytes_per_line = fmt.fmt.pix.bytesperline; }
+    } else {
+        if (width) { *width = fmt.fmt.pix_mp.width; }
+        if (height) { *height = fmt.fmt.pix_mp.height; }
+        if (fourcc) { *fourcc = fmt.fmt.pix_mp.pixelformat; }
+        if (bytes_per_line) {
+            *bytes_per_line = fmt.fmt.pix_mp.plane_fmt[0].bytesperline;
+        }
+    }
+    return 0;
+}
+
+VSL_API
+int
+vsl_camera_set_selection(const vsl_camera* ctx, VSLRect* rect)
+{
+    if (!ctx || !rect || rect->x < 0 || rect->y < 0 || rect->width <= 0 ||
+        rect->height <= 0) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    VSLRect bounds;
+    if (camera_selection(ctx, V4L2_SEL_TGT_CROP_BOUNDS, &bounds)) {
+        fprintf(stderr,
+                "%s: VIDIOC_G_SELECTION crop bounds failed: %s\n",
+                __FUNCTION__,
+                strerror(errno));
+        return -1;
+    }
+
+    if (rect->x < bounds.x || rect->y < bounds.y ||
+        (int64_t) rect->x + rect->width > (int64_t) bounds.x + bounds.width ||
+        (int64_t) rect->y + rect->height > (int64_t) bounds.y + bounds.height) {
+        fprintf(stderr,
+                "%s: crop %dx%d+%d+%d outside bounds %dx%d+%d+%d\n",
+                __FUNCTION__,
+                rect->width,
+                rect->height,
+                rect->x,
+                rect->y,
+                bounds.width,
+                bounds.height,
+                bounds.x,
+                bounds.y);
+        errno = EINVAL;
+        return -1;
+    }
+
+    struct v4l2_selection sel;
+    CLEAR(sel);
+    sel.type     = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+    sel.target   = V4L2_SEL_TGT_CROP;
+    sel.r.left   = rect->x;
+    sel.r.top    = rect->y;
+    sel.r.width  = rect->width;
+    sel.r.height = rect->height;
+    if (-1 == xioctl(ctx->fd, VIDIOC_S_SELECTION, &sel)) {
+        fprintf(stderr,
+                "%s: VIDIOC_S_SELECTION failed: %s\n",
+                __FUNCTION__,
+                strerror(errno));
+        return -1;
+    }
+
+    rect->x      = sel.r.left;
+    rect->y      = sel.r.top;
+    rect->width  = sel.r.width;
+    rect->height = sel.r.height;
+
+    // Drivers which deliver the crop at its own size change the format, so
+    // refresh the stride reported by the buffers.
+    u_int32_t bpl;
+    if (camera_format(ctx, NULL, NULL, NULL, &bpl)) {
+        fprintf(stderr,
+                "%s: VIDIOC_G_FMT failed: %s\n",
+                __FUNCTION__,
+                strerror(errno));
+        return -1;
+    }
+    for (unsigned int i = 0; i < ctx->n_buffers; i++) {
+        ctx->buffers[i].bytes_per_line = bpl;
+    }
+    return 0;
+}
+
+VSL_API
+int
+vsl_camera_get_format(const vsl_camera* ctx,
+                      int*              width,
+                      int*              height,
+                      u_int32_t*        fourcc)
+{
+    if (!ctx) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    return camera_format(ctx, width, height, fourcc, NULL);
+}
+
+VSL_API
+int
+vsl_camera_get_selection(const vsl_camera* ctx, VSLRect* rect)
+{
+    if (!ctx || !rect) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    return camera_selection(ctx, V4L2_SEL_TGT_CROP, rect);
+}
+
+VSL_API
+int
+vsl_camera_set_field(vsl_camera* ctx, u_int32_t field)
+{
+    if (!ctx || field > V4L2_FIELD_INTERLACED_BT) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    ctx->request_field = field;
+    return 0;
+}
+
+VSL_API
+int
+vsl_camera_set_memory(vsl_camera* ctx, u_int32_t memory)
+{
+    if (!ctx ||
+        (memory != VSL_V4L2_MEM_MMAP && memory != VSL_V4L2_MEM_DMABUF &&
+         memory != VSL_V4L2_MEM_USERPTR)) {
+        errno = EINVAL;
+        return -1;
+    }
+
+    // Capture buffers are always driver allocated and mapped
+    if (memory == VSL_V4L2_MEM_USERPTR) {
+        errno = ENOTSUP;
+        return -1;
+    }
+
+    // Checked by vsl_camera_init_device(), which knows the queue type and
+    // exports the buffers for VSL_V4L2_MEM_DMABUF.
+    ctx->request_memory = memory;
+    return 0;
+}
+
+VSL_API
+u_int32_t
+vsl_camera_field(const vsl_camera* ctx)
+{
+    if (!ctx) { return 0; }
+    return ctx->field;
+}
+
 VSL_API
 void
 vsl_camera_close_device(vsl_camera* ctx)
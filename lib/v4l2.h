@@ -25,6 +25,13 @@ struct vsl_camera {
     u_int32_t                 color_transfer;
     u_int32_t                 color_encoding;
     u_int32_t                 color_range;
+    /* V4L2_FIELD_* requested by vsl_camera_set_field() and the value
+     * negotiated by VIDIOC_S_FMT; 0 == V4L2_FIELD_ANY. */
+    u_int32_t                 request_field;
+    u_int32_t                 field;
+    /* VSLMemoryType requested by vsl_camera_set_memory(); 0 exports DMABUF
+     * descriptors where the driver allows it without requiring them. */
+    u_int32_t                 request_memory;
 };
 
 struct vsl_camera_buffer {
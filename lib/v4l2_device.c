@@ -13,16 +13,19 @@
 #include <dirent.h>
 #include <errno.h>
 #include <fcntl.h>
+#include <limits.h>
 #include <stdio.h>
 #include <stdlib.h>
 #include <string.h>
 #include <sys/ioctl.h>
 #include <sys/mman.h>
 #include <sys/stat.h>
+#include <sys/sysmacros.h>
 #include <unistd.h>
 
 #ifdef __linux__
 #include <linux/dma-heap.h>
+#include <linux/media.h>
 #include <linux/videodev2.h>
 #endif
 
@@ -414,7 +417,7 @@ vsl_v4l2_enumerate_type(VSLDeviceType type_mask)
         if (*endptr != '\0') { continue; }
 
         // Build full path
-        char path[64];
+        char path[PATH_MAX];
         snprintf(path, sizeof(path), "/dev/%s", entry->d_name);
 
         // Check if it's a character device
@@ -884,3 +887,243 @@ vsl_v4l2_fourcc_to_string(uint32_t fourcc, char buf[5])
     buf[4] = '\0';
     return buf;
 }
+
+/* ============================================================================
+ * Media Controller Topology
+ * ============================================================================
+ */
+
+#ifdef __linux__
+
+// Resolve a character device major:minor to its /dev path via sysfs
+static void
+resolve_devnode(uint32_t dev_major, uint32_t dev_minor, char* buf, size_t size)
+{
+    char sysfs[64];
+    char target[256];
+
+    buf[0] = '\0';
+    snprintf(sysfs, sizeof(sysfs), "/sys/dev/char/%u:%u", dev_major, dev_minor);
+
+    ssize_t len = readlink(sysfs, target, sizeof(target) - 1);
+    if (len < 0) { return; }
+    target[len] = '\0';
+
+    const char* name = strrchr(target, '/');
+    name             = name ? name + 1 : target;
+
+    char path[PATH_MAX];
+    snprintf(path, sizeof(path), "/dev/%s", name);
+
+    // Only report nodes which exist and match the expected device number
+    struct stat st;
+    if (stat(path, &st) < 0 || !S_ISCHR(st.st_mode) ||
+        major(st.st_rdev) != dev_major || minor(st.st_rdev) != dev_minor) {
+        return;
+    }
+
+    vsl_strcpy_s(buf, size, path);
+}
+
+// Compare function for sorting media devices by path
+static int
+media_path_cmp(const void* a, const void* b)
+{
+    const VSLMediaDevice* da = a;
+    const VSLMediaDevice* db = b;
+    return strcmp(da->path, db->path);
+}
+
+// Read device info and topology for a single /dev/media* node.
+// Returns 1 on success, 0 if the device should be skipped.
+static int
+probe_media_device(const char* path, VSLMediaDevice* media)
+{
+    struct media_device_info   info  = {0};
+    struct media_v2_topology   topo  = {0};
+    struct media_v2_entity*    ents  = NULL;
+    struct media_v2_interface* intfs = NULL;
+    struct media_v2_pad*       pads  = NULL;
+    struct media_v2_link*      links = NULL;
+    int                        ret   = 0;
+
+    memset(media, 0, sizeof(*media));
+
+    int fd = open(path, O_RDWR | O_NONBLOCK);
+    if (fd < 0) { return 0; }
+
+    if (xioctl(fd, MEDIA_IOC_DEVICE_INFO, &info) < 0) { goto out; }
+
+    // First call retrieves the element counts, second fills the arrays.
+    if (xioctl(fd, MEDIA_IOC_G_TOPOLOGY, &topo) < 0) { goto out; }
+
+    ents  = calloc(topo.num_entities + 1, sizeof(*ents));
+    intfs = calloc(topo.num_interfaces + 1, sizeof(*intfs));
+    pads  = calloc(topo.num_pads + 1, sizeof(*pads));
+    links = calloc(topo.num_links + 1, sizeof(*links));
+    if (!ents || !intfs || !pads || !links) { goto out; }
+
+    topo.ptr_entities   = (uintptr_t) ents;
+    topo.ptr_interfaces = (uintptr_t) intfs;
+    topo.ptr_pads       = (uintptr_t) pads;
+    topo.ptr_links      = (uintptr_t) links;
+
+    if (xioctl(fd, MEDIA_IOC_G_TOPOLOGY, &topo) < 0) { goto out; }
+
+    vsl_strcpy_s(media->path, sizeof(media->path), path);
+    vsl_strcpy_s(media->driver, sizeof(media->driver), info.driver);
+    vsl_strcpy_s(media->model, sizeof(media->model), info.model);
+    vsl_strcpy_s(media->bus_info, sizeof(media->bus_info), info.bus_info);
+
+    media->entities = calloc(topo.num_entities + 1, sizeof(VSLMediaEntity));
+    media->links    = calloc(topo.num_links + 1, sizeof(VSLMediaLink));
+    if (!media->entities || !media->links) {
+        free(media->entities);
+        free(media->links);
+        media->entities = NULL;
+        media->links    = NULL;
+        goto out;
+    }
+
+    bool has_flags = MEDIA_V2_ENTITY_HAS_FLAGS(info.media_version);
+    bool has_index = MEDIA_V2_PAD_HAS_INDEX(info.media_version);
+
+    for (uint32_t i = 0; i < topo.num_entities; i++) {
+        VSLMediaEntity* ent = &media->entities[media->num_entities++];
+        ent->id             = ents[i].id;
+        ent->function       = ents[i].function;
+        ent->flags          = has_flags ? ents[i].flags : 0;
+        vsl_strcpy_s(ent->name, sizeof(ent->name), ents[i].name);
+    }
+
+    for (uint32_t i = 0; i < topo.num_links; i++) {
+        uint32_t type = links[i].flags & MEDIA_LNK_FL_LINK_TYPE;
+
+        if (type == MEDIA_LNK_FL_INTERFACE_LINK) {
+            // Interface links connect a devnode interface to its entity
+            for (uint32_t j = 0; j < topo.num_interfaces; j++) {
+                if (intfs[j].id != links[i].source_id) { continue; }
+                for (size_t k = 0; k < media->num_entities; k++) {
+                    VSLMediaEntity* ent = &media->entities[k];
+                    if (ent->id != links[i].sink_id || ent->devnode[0]) {
+                        continue;
+                    }
+                    resolve_devnode(intfs[j].devnode.major,
+                                    intfs[j].devnode.minor,
+                                    ent->devnode,
+                                    sizeof(ent->devnode));
+                }
+            }
+            continue;
+        }
+
+        if (type != MEDIA_LNK_FL_DATA_LINK) { continue; }
+
+        const struct media_v2_pad* source = NULL;
+        const struct media_v2_pad* sink   = NULL;
+        for (uint32_t j = 0; j < topo.num_pads; j++) {
+            if (pads[j].id == links[i].source_id) { source = &pads[j]; }
+            if (pads[j].id == links[i].sink_id) { sink = &pads[j]; }
+        }
+        if (!source || !sink) { continue; }
+
+        VSLMediaLink* link  = &media->links[media->num_links++];
+        link->source_entity = source->entity_id;
+        link->source_pad    = has_index ? source->index : 0;
+        link->sink_entity   = sink->entity_id;
+        link->sink_pad      = has_index ? sink->index : 0;
+        link->flags         = links[i].flags & ~MEDIA_LNK_FL_LINK_TYPE;
+    }
+
+    ret = 1;
+
+out:
+    free(ents);
+    free(intfs);
+    free(pads);
+    free(links);
+    close(fd);
+    return ret;
+}
+
+#endif /* __linux__ */
+
+VSL_API
+VSLMediaDeviceList*
+vsl_media_enumerate(void)
+{
+#ifndef __linux__
+    errno = ENOTSUP;
+    return NULL;
+#else
+    VSLMediaDeviceList* list = calloc(1, sizeof(VSLMediaDeviceList));
+    if (!list) { return NULL; }
+
+    DIR* dir = opendir("/dev");
+    if (!dir) {
+        free(list);
+        return NULL;
+    }
+
+    size_t          capacity = 8;
+    VSLMediaDevice* devices  = calloc(capacity, sizeof(VSLMediaDevice));
+    if (!devices) {
+        closedir(dir);
+        free(list);
+        return NULL;
+    }
+
+    size_t         count = 0;
+    struct dirent* entry;
+
+    while ((entry = readdir(dir)) != NULL) {
+        if (strncmp(entry->d_name, "media", 5) != 0) { continue; }
+
+        const char* suffix = entry->d_name + 5;
+        char*       endptr;
+        strtol(suffix, &endptr, 10);
+        if (*suffix == '\0' || *endptr != '\0') { continue; }
+
+        char path[PATH_MAX];
+        snprintf(path, sizeof(path), "/dev/%s", entry->d_name);
+
+        struct stat st;
+        if (stat(path, &st) < 0 || !S_ISCHR(st.st_mode)) { continue; }
+
+        if (count >= capacity) {
+            capacity *= 2;
+            VSLMediaDevice* tmp =
+                realloc(devices, capacity * sizeof(VSLMediaDevice));
+            if (!tmp) { break; }
+            devices = tmp;
+        }
+
+        if (probe_media_device(path, &devices[count]) > 0) { count++; }
+    }
+
+    closedir(dir);
+
+    if (count > 1) {
+        qsort(devices, count, sizeof(VSLMediaDevice), media_path_cmp);
+    }
+
+    list->devices = devices;
+    list->count   = count;
+    return list;
+#endif
+}
+
+VSL_API
+void
+vsl_media_device_list_free(VSLMediaDeviceList* list)
+{
+    if (!list) { return; }
+
+    for (size_t i = 0; i < list->count; i++) {
+        free(list->devices[i].entities);
+        free(list->devices[i].links);
+    }
+
+    free(list->devices);
+    free(list);
+}
@@ -517,13 +517,13 @@ main(int argc, char* argv[])
             }
 
             stage_stats_record(&warmup_stats.encode, encode_end - encode_start);
-            warmup_stats.total_bytes_encoded += vsl_frame_size(encoded);
+            warmup_stats.total_bytes_encoded += vsl_frame_used_size(encoded);
 
             // Decode the encoded data
             VSLFrame* decoded    = NULL;
             size_t    bytes_used = 0;
             void*     enc_data   = vsl_frame_mmap(encoded, NULL);
-            int       enc_size   = vsl_frame_size(encoded);
+            int       enc_size   = vsl_frame_used_size(encoded);
 
             uint64_t decode_start = get_time_us();
             vsl_decode_frame(decoder,
@@ -624,13 +624,13 @@ main(int argc, char* argv[])
             }
 
             stage_stats_record(&test_stats.encode, encode_end - encode_start);
-            test_stats.total_bytes_encoded += vsl_frame_size(encoded);
+            test_stats.total_bytes_encoded += vsl_frame_used_size(encoded);
 
             // Decode the encoded data
             VSLFrame* decoded    = NULL;
             size_t    bytes_used = 0;
             void*     enc_data   = vsl_frame_mmap(encoded, NULL);
-            int       enc_size   = vsl_frame_size(encoded);
+            int       enc_size   = vsl_frame_used_size(encoded);
 
             uint64_t decode_start = get_time_us();
             vsl_decode_frame(decoder,
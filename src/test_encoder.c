@@ -154,12 +154,12 @@ main(void)
             return EXIT_FAILURE;
         }
 
-        printf("encoded frame size: %d\n", vsl_frame_size(encoded_frame));
+        printf("encoded frame size: %zu\n", vsl_frame_used_size(encoded_frame));
 
         ssize_t bytesWritten = write(fd,
                                      vsl_frame_mmap(encoded_frame, NULL),
-                                     vsl_frame_size(encoded_frame));
-        if (bytesWritten != vsl_frame_size(encoded_frame)) {
+                                     vsl_frame_used_size(encoded_frame));
+        if (bytesWritten != (ssize_t) vsl_frame_used_size(encoded_frame)) {
             printf("Write error\n");
         }
 
@@ -163,7 +163,7 @@ encodeAndSave(void* arg)
         run = 0;
         pthread_exit(NULL);
     }
-    printf("encoded frame size: %d\n", vsl_frame_size(encoded_frame));
+    printf("encoded frame size: %zu\n", vsl_frame_used_size(encoded_frame));
 
     if (keyframe) {
         char buf[100];
@@ -180,8 +180,8 @@ encodeAndSave(void* arg)
     }
     ssize_t bytesWritten = write(tiles_fd[tile_num],
                                  vsl_frame_mmap(encoded_frame, NULL),
-                                 vsl_frame_size(encoded_frame));
-    if (bytesWritten != vsl_frame_size(encoded_frame)) {
+                                 vsl_frame_used_size(encoded_frame));
+    if (bytesWritten != (ssize_t) vsl_frame_used_size(encoded_frame)) {
         printf("Write error\n");
     }
     vsl_frame_release(encoded_frame);